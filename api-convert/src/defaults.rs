@@ -0,0 +1,16 @@
+use realearn_api::persistence::Interval;
+
+pub const UNIT_INTERVAL: Interval<f64> = Interval(0.0, 1.0);
+pub const GLUE_STEP_SIZE_INTERVAL: Interval<f64> = Interval(0.01, 0.01);
+// Should be the same as GLUE_STEP_SIZE_INTERVAL because the native data structure only saves one.
+pub const GLUE_STEP_FACTOR_INTERVAL: Interval<i32> = Interval(1, 1);
+pub const GLUE_SOURCE_INTERVAL: Interval<f64> = UNIT_INTERVAL;
+pub const GLUE_TARGET_INTERVAL: Interval<f64> = UNIT_INTERVAL;
+pub const GLUE_JUMP_INTERVAL: Interval<f64> = UNIT_INTERVAL;
+pub const GLUE_REVERSE: bool = false;
+pub const GLUE_WRAP: bool = false;
+pub const GLUE_ROUND_TARGET_VALUE: bool = false;
+pub const FIRE_MODE_PRESS_DURATION_INTERVAL: Interval<u32> = Interval(0, 0);
+pub const FIRE_MODE_TIMEOUT: u32 = 0;
+pub const FIRE_MODE_RATE: u32 = 0;
+pub const FIRE_MODE_SINGLE_PRESS_MAX_DURATION: u32 = 0;
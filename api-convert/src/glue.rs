@@ -0,0 +1,193 @@
+use crate::{defaults, ConversionResult};
+use helgoboss_learn::{DiscreteIncrement, SoftSymmetricUnitValue, UnitValue};
+use realearn_api::persistence::*;
+use std::convert::TryInto;
+use std::time::Duration;
+
+pub fn convert_absolute_mode(m: Option<AbsoluteMode>) -> helgoboss_learn::AbsoluteMode {
+    use helgoboss_learn::AbsoluteMode as T;
+    use AbsoluteMode::*;
+    match m.unwrap_or_default() {
+        Normal => T::Normal,
+        IncrementalButton => T::IncrementalButton,
+        ToggleButton => T::ToggleButton,
+        MakeRelative => T::MakeRelative,
+        PerformanceControl => T::PerformanceControl,
+    }
+}
+
+pub fn convert_fire_mode(m: &FireMode) -> helgoboss_learn::FireMode {
+    use helgoboss_learn::FireMode as T;
+    use FireMode::*;
+    match m {
+        Normal(_) => T::Normal,
+        AfterTimeout(_) => T::AfterTimeout,
+        AfterTimeoutKeepFiring(_) => T::AfterTimeoutKeepFiring,
+        OnSinglePress(_) => T::OnSinglePress,
+        OnDoublePress(_) => T::OnDoublePress,
+    }
+}
+
+/// Returns the interval of press durations which is relevant for the given fire mode.
+pub fn convert_press_duration_interval(
+    m: &FireMode,
+) -> ConversionResult<helgoboss_learn::Interval<Duration>> {
+    use FireMode::*;
+    let (min, max) = match m {
+        Normal(m) => {
+            let i = m
+                .press_duration_interval
+                .unwrap_or(defaults::FIRE_MODE_PRESS_DURATION_INTERVAL);
+            (i.0, i.1)
+        }
+        OnSinglePress(m) => {
+            let max = m
+                .max_duration
+                .unwrap_or(defaults::FIRE_MODE_SINGLE_PRESS_MAX_DURATION);
+            (0, max)
+        }
+        AfterTimeout(m) => {
+            let min = m.timeout.unwrap_or(defaults::FIRE_MODE_TIMEOUT);
+            (min, min)
+        }
+        AfterTimeoutKeepFiring(m) => {
+            let min = m.timeout.unwrap_or(defaults::FIRE_MODE_TIMEOUT);
+            (min, min)
+        }
+        OnDoublePress(_) => (0, 0),
+    };
+    let result = helgoboss_learn::Interval::try_new(
+        Duration::from_millis(min as u64),
+        Duration::from_millis(max as u64),
+    )?;
+    Ok(result)
+}
+
+/// Returns the (non-synced) turbo rate, which is zero if the given fire mode doesn't keep firing.
+pub fn convert_turbo_rate(m: &FireMode) -> Duration {
+    let millis = match m {
+        FireMode::AfterTimeoutKeepFiring(m) => m.rate.unwrap_or(defaults::FIRE_MODE_RATE),
+        _ => 0,
+    };
+    Duration::from_millis(millis as u64)
+}
+
+pub fn convert_takeover_mode(m: Option<TakeoverMode>) -> helgoboss_learn::TakeoverMode {
+    use helgoboss_learn::TakeoverMode as T;
+    use TakeoverMode::*;
+    match m.unwrap_or_default() {
+        Off => T::Off,
+        PickUpTolerant => T::PickupTolerant,
+        PickUp => T::Pickup,
+        LongTimeNoSee => T::LongTimeNoSee,
+        Parallel => T::Parallel,
+        CatchUp => T::CatchUp,
+    }
+}
+
+pub fn convert_button_usage(f: Option<ButtonFilter>) -> helgoboss_learn::ButtonUsage {
+    use helgoboss_learn::ButtonUsage as T;
+    if let Some(f) = f {
+        use ButtonFilter::*;
+        match f {
+            PressOnly => T::PressOnly,
+            ReleaseOnly => T::ReleaseOnly,
+        }
+    } else {
+        T::Both
+    }
+}
+
+pub fn convert_encoder_usage(f: Option<EncoderFilter>) -> helgoboss_learn::EncoderUsage {
+    use helgoboss_learn::EncoderUsage as T;
+    if let Some(f) = f {
+        use EncoderFilter::*;
+        match f {
+            IncrementOnly => T::IncrementOnly,
+            DecrementOnly => T::DecrementOnly,
+        }
+    } else {
+        T::Both
+    }
+}
+
+pub fn convert_out_of_range_behavior(
+    b: Option<OutOfRangeBehavior>,
+) -> helgoboss_learn::OutOfRangeBehavior {
+    use helgoboss_learn::OutOfRangeBehavior as T;
+    use OutOfRangeBehavior::*;
+    match b.unwrap_or_default() {
+        MinOrMax => T::MinOrMax,
+        Min => T::Min,
+        Ignore => T::Ignore,
+    }
+}
+
+pub fn convert_group_interaction(i: Option<Interaction>) -> helgoboss_learn::GroupInteraction {
+    use helgoboss_learn::GroupInteraction as T;
+    if let Some(i) = i {
+        use Interaction::*;
+        match i {
+            SameControl => T::SameControl,
+            SameTargetValue => T::SameTargetValue,
+            InverseControl => T::InverseControl,
+            InverseTargetValue => T::InverseTargetValue,
+            InverseTargetValueOnOnly => T::InverseTargetValueOnOnly,
+        }
+    } else {
+        T::None
+    }
+}
+
+pub fn convert_step_factor_interval(
+    i: Interval<i32>,
+) -> ConversionResult<helgoboss_learn::Interval<DiscreteIncrement>> {
+    let result = helgoboss_learn::Interval::try_new(
+        i.0.try_into().unwrap_or(DiscreteIncrement::POSITIVE_MIN),
+        i.1.try_into().unwrap_or(DiscreteIncrement::POSITIVE_MIN),
+    )?;
+    Ok(result)
+}
+
+pub fn convert_step_size_interval(
+    i: Interval<f64>,
+) -> ConversionResult<helgoboss_learn::Interval<SoftSymmetricUnitValue>> {
+    let uv_interval = convert_unit_value_interval(i)?;
+    let result = helgoboss_learn::Interval::try_new(
+        uv_interval.min_val().to_symmetric(),
+        uv_interval.max_val().to_symmetric(),
+    )?;
+    Ok(result)
+}
+
+pub fn convert_unit_value_interval(
+    interval: Interval<f64>,
+) -> ConversionResult<helgoboss_learn::Interval<UnitValue>> {
+    let result =
+        helgoboss_learn::Interval::try_new(interval.0.try_into()?, interval.1.try_into()?)?;
+    Ok(result)
+}
+
+pub fn convert_virtual_color(color: VirtualColor) -> helgoboss_learn::VirtualColor {
+    use helgoboss_learn::VirtualColor as T;
+    use VirtualColor::*;
+    match color {
+        Rgb(c) => T::Rgb(convert_rgb_color(c)),
+        Prop(c) => T::Prop { prop: c.prop },
+    }
+}
+
+fn convert_rgb_color(color: RgbColor) -> helgoboss_learn::RgbColor {
+    helgoboss_learn::RgbColor::new(color.0, color.1, color.2)
+}
+
+pub fn convert_feedback_value_table(t: FeedbackValueTable) -> helgoboss_learn::FeedbackValueTable {
+    match t {
+        FeedbackValueTable::FromTextToDiscrete(v) => {
+            helgoboss_learn::FeedbackValueTable::FromTextToDiscrete(v.value)
+        }
+        FeedbackValueTable::FromTextToContinuous(v) => {
+            helgoboss_learn::FeedbackValueTable::FromTextToContinuous(v.value)
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Conversions from ReaLearn's API data structures (see [`realearn_api::persistence`]) into the
+//! corresponding structures of the mapping engine which don't depend on a ReaLearn instance.
+//!
+//! Shared by ReaLearn itself and by the embedding library, so that both interpret a source or
+//! glue description in exactly the same way.
+pub mod defaults;
+
+mod glue;
+pub use glue::*;
+
+mod source;
+pub use source::*;
+
+pub type ConversionResult<T> = Result<T, Box<dyn std::error::Error>>;
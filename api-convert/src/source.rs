@@ -0,0 +1,35 @@
+use crate::ConversionResult;
+use helgoboss_midi::{Channel, U7};
+use realearn_api::persistence::SourceCharacter;
+use std::convert::TryInto;
+
+pub fn convert_channel(ch: Option<u8>) -> ConversionResult<Option<Channel>> {
+    if let Some(ch) = ch {
+        let ch: Channel = ch.try_into()?;
+        Ok(Some(ch))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn convert_u7(n: Option<u8>) -> ConversionResult<Option<U7>> {
+    if let Some(n) = n {
+        let n: U7 = n.try_into()?;
+        Ok(Some(n))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn convert_character(s: Option<SourceCharacter>) -> helgoboss_learn::SourceCharacter {
+    use helgoboss_learn::SourceCharacter as T;
+    use SourceCharacter::*;
+    match s.unwrap_or_default() {
+        Range => T::RangeElement,
+        Button => T::MomentaryButton,
+        Relative1 => T::Encoder1,
+        Relative2 => T::Encoder2,
+        Relative3 => T::Encoder3,
+        StatefulButton => T::ToggleButton,
+    }
+}
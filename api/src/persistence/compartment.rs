@@ -1,7 +1,7 @@
 use crate::persistence::*;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Default, Serialize, Deserialize, JsonSchema)]
 pub struct Compartment {
@@ -21,4 +21,15 @@ pub struct Compartment {
     pub custom_data: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    /// At the moment, init messages are only used in the controller compartment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_init: Option<LifecycleHook>,
+    /// At the moment, shutdown messages are only used in the controller compartment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_shutdown: Option<LifecycleHook>,
+    /// Controller-specific MIDI/OSC message templates, keyed by template name.
+    ///
+    /// At the moment, message templates are only used in the controller compartment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_templates: Option<BTreeMap<String, String>>,
 }
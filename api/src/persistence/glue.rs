@@ -35,6 +35,8 @@ pub struct Glue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub control_transformation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub control_curve: Option<ControlCurve>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub step_size_interval: Option<Interval<f64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub step_factor_interval: Option<Interval<i32>>,
@@ -45,6 +47,8 @@ pub struct Glue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relative_mode: Option<RelativeMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub acceleration_curve: Option<AccelerationCurve>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub interaction: Option<Interaction>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fire_mode: Option<FireMode>,
@@ -97,6 +101,60 @@ impl Default for RelativeMode {
     }
 }
 
+/// Curve which is applied to encoder increments when converting them to absolute values
+/// ("Make absolute").
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind")]
+pub enum AccelerationCurve {
+    /// Treats each increment as one single step, no matter how fast the encoder is turned.
+    Off,
+    /// Uses the increment as is, so turning twice as fast results in twice as many steps.
+    Linear,
+    /// Raises the increment to a power, so turning fast results in disproportionately many steps.
+    Exponential(ExponentialAccelerationCurve),
+}
+
+impl Default for AccelerationCurve {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExponentialAccelerationCurve {
+    /// The higher, the stronger the acceleration. `0.0` corresponds to linear acceleration.
+    pub sensitivity: f64,
+}
+
+/// Curve which shapes the value on its way from source interval to target interval.
+///
+/// A simpler alternative to writing a control transformation. Ignored if a control transformation
+/// is set.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind")]
+pub enum ControlCurve {
+    /// Leaves the value as is.
+    Linear,
+    /// Rises steeply at first and then flattens out. Good for frequencies.
+    Logarithmic(ShapedControlCurve),
+    /// Rises slowly at first and then steeply. Good for volumes and fine control at the low end.
+    Exponential(ShapedControlCurve),
+    /// Rises slowly at both ends and steeply in the middle.
+    SCurve(ShapedControlCurve),
+}
+
+impl Default for ControlCurve {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ShapedControlCurve {
+    /// How strongly the curve deviates from a straight line, from `0.0` (linear) to `1.0`.
+    pub amount: f64,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind")]
 pub enum FireMode {
@@ -130,6 +188,16 @@ pub struct AfterTimeoutKeepFiringFireMode {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u32>,
     pub rate: Option<u32>,
+    /// If set, the rate follows the project tempo and `rate` is ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_rate: Option<NoteLength>,
+}
+
+/// Musical note length as fraction of a whole note, e.g. 1/8 or 1/12 (eighth triplet).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoteLength {
+    pub numerator: u32,
+    pub denominator: u32,
 }
 
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
@@ -243,5 +311,5 @@ pub struct TextFeedback {
     pub text_expression: Option<String>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Interval<T>(pub T, pub T);
@@ -20,4 +20,10 @@ pub struct Group {
     pub feedback_enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub activation_condition: Option<ActivationCondition>,
+    /// Feedback color used for all mappings of this group which don't define a color
+    /// themselves.
+    ///
+    /// Useful for controllers with RGB pads in order to give each group its own color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_color: Option<VirtualColor>,
 }
@@ -1,4 +1,7 @@
 use super::*;
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +21,9 @@ pub struct Mapping {
     pub group: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub visible_in_projection: Option<bool>,
+    /// Sends feedback only while this mapping is shown in the projection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_only_if_visible_in_projection: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -38,6 +44,15 @@ pub struct Mapping {
     pub target: Option<Target>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success_audio_feedback: Option<SuccessAudioFeedback>,
+    /// Makes this mapping part of a crossfade within its group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crossfade_role: Option<CrossfadeRole>,
+    /// Post-processes the feedback, e.g. in order to let a button LED blink.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_effect: Option<FeedbackEffect>,
+    /// Inverts numeric feedback, e.g. in order to switch a button LED off while the target is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invert_feedback: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unprocessed: Option<serde_json::Map<String, serde_json::Value>>,
 }
@@ -46,6 +61,10 @@ pub struct Mapping {
 pub struct LifecycleHook {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub send_midi_feedback: Option<Vec<SendMidiFeedbackAction>>,
+    /// REAPER actions to be invoked, identified by their command ID (built-in actions) or command
+    /// name (e.g. `_SWS_SAVESEL`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoke_reaper_actions: Option<Vec<String>>,
 }
 
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -67,6 +86,57 @@ pub enum SuccessAudioFeedback {
     Simple,
 }
 
+/// Role of a mapping within a crossfade group.
+///
+/// The crossfader mapping distributes its target value to all side-A and side-B mappings of the
+/// same group: The more it moves towards the maximum, the more side A fades out and side B fades in.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    JsonSchema,
+)]
+#[repr(usize)]
+pub enum CrossfadeRole {
+    /// Crossfader which sums up the side values to a constant amplitude.
+    #[display(fmt = "Crossfader (linear)")]
+    LinearCrossfader,
+    /// Crossfader which sums up the side values to a constant power.
+    #[display(fmt = "Crossfader (equal power)")]
+    EqualPowerCrossfader,
+    #[display(fmt = "Side A")]
+    SideA,
+    #[display(fmt = "Side B")]
+    SideB,
+}
+
+/// Feedback post-processing, mainly useful for buttons with LEDs.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind")]
+pub enum FeedbackEffect {
+    /// Lets the LED blink as long as the target value is within the given interval.
+    Blink(BlinkFeedbackEffect),
+    /// Lets the LED light up fully for a moment whenever the target value changes.
+    Flash,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BlinkFeedbackEffect {
+    /// Target value interval in which the LED should blink.
+    ///
+    /// If not provided, the LED blinks whenever the target value is greater than zero.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_interval: Option<Interval<f64>>,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind")]
 pub enum ActivationCondition {
@@ -75,6 +145,12 @@ pub enum ActivationCondition {
     Eel(EelActivationCondition),
     Expression(ExpressionActivationCondition),
     TargetValue(TargetValueActivationCondition),
+    /// Active while the project is playing (including recording).
+    Playing,
+    /// Active while the project is recording.
+    Recording,
+    /// Active while the project is stopped or paused.
+    Stopped,
 }
 
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
@@ -129,6 +205,7 @@ pub enum VirtualControlElementId {
 pub enum VirtualControlElementCharacter {
     Multi,
     Button,
+    Display,
 }
 
 impl Default for VirtualControlElementCharacter {
@@ -83,6 +83,7 @@ mod tests {
                     controller_number: Some(64),
                     character: Some(SourceCharacter::Button),
                     fourteen_bit: Some(false),
+                    debounce: None,
                 },
             )),
             glue: Some(Glue {
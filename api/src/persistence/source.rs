@@ -1,3 +1,4 @@
+pub use audio::*;
 pub use keyboard::*;
 pub use midi::*;
 pub use osc::*;
@@ -19,6 +20,9 @@ pub enum Source {
     Timer(TimerSource),
     RealearnParameter(RealearnParameterSource),
     Speech(SpeechSource),
+    OneShotTimer(OneShotTimerSource),
+    Lfo(LfoSource),
+    Random(RandomSource),
     // MIDI
     MidiNoteVelocity(MidiNoteVelocitySource),
     MidiNoteKeyNumber(MidiNoteKeyNumberSource),
@@ -31,6 +35,7 @@ pub enum Source {
     MidiParameterNumberValue(MidiParameterNumberValueSource),
     MidiClockTempo(MidiClockTempoSource),
     MidiClockTransport(MidiClockTransportSource),
+    MidiMachineControl(MidiMachineControlSource),
     MidiRaw(MidiRawSource),
     MidiScript(MidiScriptSource),
     MackieLcd(MackieLcdSource),
@@ -39,6 +44,8 @@ pub enum Source {
     SlKeyboardDisplay(SlKeyboardDisplaySource),
     SiniConE24Display(SiniConE24DisplaySource),
     LaunchpadProScrollingTextDisplay(LaunchpadProScrollingTextDisplaySource),
+    // Audio
+    AudioInput(AudioInputSource),
     // OSC
     Osc(OscSource),
     // Keyboard
@@ -67,8 +74,49 @@ impl Default for FeedbackBehavior {
     }
 }
 
+/// Filters out value flutter (e.g. alternating adjacent values sent by cheap controllers) before
+/// the source value reaches the glue section.
+///
+/// Only affects absolute values. The minimum and maximum value are always let through.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SourceDebounce {
+    /// Ignores values which differ less than this from the last accepted value (0.0 to 1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_change: Option<f64>,
+    /// Ignores values which reverse the direction of movement within this many milliseconds after
+    /// the last accepted value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_time: Option<u64>,
+}
+
+/// Makes a spring-loaded pitch wheel usable as bipolar control (e.g. for pan or jog).
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PitchBendBehavior {
+    /// Treats values this close to the center as exact center (0.0 to 1.0, relative to the
+    /// distance between center and end).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center_dead_zone: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<PitchBendMode>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum PitchBendMode {
+    /// Emits the wheel position, with the center mapped to exactly 50%.
+    Absolute,
+    /// Emits increments/decrements when moving the wheel away from the center and ignores the
+    /// spring returning it to the center.
+    RelativeFromCenter,
+}
+
+impl Default for PitchBendMode {
+    fn default() -> Self {
+        Self::Absolute
+    }
+}
+
 mod midi {
-    use crate::persistence::FeedbackBehavior;
+    use crate::persistence::{FeedbackBehavior, PitchBendBehavior, SourceDebounce};
     use derive_more::Display;
     use enum_iterator::IntoEnumIterator;
     use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -83,6 +131,8 @@ mod midi {
         pub channel: Option<u8>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub key_number: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub velocity_feedback: Option<VelocityFeedback>,
     }
 
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -93,7 +143,7 @@ mod midi {
         pub channel: Option<u8>,
     }
 
-    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiPolyphonicKeyPressureAmountSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_behavior: Option<FeedbackBehavior>,
@@ -101,9 +151,41 @@ mod midi {
         pub channel: Option<u8>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub key_number: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub velocity_feedback: Option<VelocityFeedback>,
+        /// If `true`, feedback is sent as note-on message (on the same note) instead of
+        /// polyphonic key pressure message.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub feedback_as_note_on: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
     }
 
-    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    /// Determines the velocity of note feedback.
+    ///
+    /// Many pad controllers choose the pad color or its intensity depending on the velocity.
+    #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+    #[serde(tag = "kind")]
+    pub enum VelocityFeedback {
+        /// Scales the feedback value to the given velocity range.
+        Range(VelocityFeedbackRange),
+        /// Picks one of the given velocities, depending on the feedback value.
+        Palette(VelocityFeedbackPalette),
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+    pub struct VelocityFeedbackRange {
+        pub min: u8,
+        pub max: u8,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+    pub struct VelocityFeedbackPalette {
+        /// Velocities from lowest to highest feedback value. Must not be empty.
+        pub velocities: Vec<u8>,
+    }
+
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiControlChangeValueSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_behavior: Option<FeedbackBehavior>,
@@ -115,6 +197,8 @@ mod midi {
         pub character: Option<SourceCharacter>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub fourteen_bit: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
     }
 
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -135,23 +219,29 @@ mod midi {
         pub program_number: Option<u8>,
     }
 
-    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiChannelPressureAmountSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_behavior: Option<FeedbackBehavior>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub channel: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
     }
 
-    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiPitchBendChangeValueSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_behavior: Option<FeedbackBehavior>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub channel: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub behavior: Option<PitchBendBehavior>,
     }
 
-    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiParameterNumberValueSource {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_behavior: Option<FeedbackBehavior>,
@@ -165,6 +255,8 @@ mod midi {
         pub registered: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub character: Option<SourceCharacter>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
     }
 
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -179,6 +271,15 @@ mod midi {
         pub message: Option<MidiClockTransportMessage>,
     }
 
+    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    pub struct MidiMachineControlSource {
+        /// Device ID of the sending device. `127` (the default) is the "all-call" ID.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub device_id: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub command: Option<MidiMachineControlCommand>,
+    }
+
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MidiRawSource {
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -260,6 +361,52 @@ mod midi {
         }
     }
 
+    /// MIDI Machine Control (MMC) command.
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Serialize,
+        Deserialize,
+        IntoEnumIterator,
+        TryFromPrimitive,
+        IntoPrimitive,
+        Display,
+        JsonSchema,
+    )]
+    #[repr(usize)]
+    pub enum MidiMachineControlCommand {
+        #[display(fmt = "Stop")]
+        Stop,
+        #[display(fmt = "Play")]
+        Play,
+        #[display(fmt = "Deferred play")]
+        DeferredPlay,
+        #[display(fmt = "Fast forward")]
+        FastForward,
+        #[display(fmt = "Rewind")]
+        Rewind,
+        #[display(fmt = "Record strobe (punch in)")]
+        RecordStrobe,
+        #[display(fmt = "Record exit (punch out)")]
+        RecordExit,
+        #[display(fmt = "Record pause")]
+        RecordPause,
+        #[display(fmt = "Pause")]
+        Pause,
+        /// Fires no matter which position the message carries.
+        #[display(fmt = "Locate")]
+        Locate,
+    }
+
+    impl Default for MidiMachineControlCommand {
+        fn default() -> Self {
+            MidiMachineControlCommand::Play
+        }
+    }
+
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct MackieLcdSource {
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -324,7 +471,7 @@ mod midi {
 }
 
 mod osc {
-    use crate::persistence::{FeedbackBehavior, OscArgument};
+    use crate::persistence::{FeedbackBehavior, OscArgument, SourceDebounce};
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
 
@@ -340,11 +487,16 @@ mod osc {
         pub relative: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub feedback_arguments: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub debounce: Option<SourceDebounce>,
     }
 }
 
 mod reaper {
     use super::*;
+    use derive_more::Display;
+    use enum_iterator::IntoEnumIterator;
+    use num_enum::{IntoPrimitive, TryFromPrimitive};
 
     #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
     pub struct RealearnInstanceStartSource;
@@ -361,6 +513,145 @@ mod reaper {
     pub struct TimerSource {
         pub duration: u64,
     }
+
+    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    pub struct OneShotTimerSource {
+        /// Milliseconds after mapping activation until the timer fires.
+        pub duration: u64,
+    }
+
+    #[derive(Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+    pub struct LfoSource {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub shape: Option<LfoShape>,
+        /// Length of one cycle in beats (quarter notes), following the project tempo.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub period: Option<f64>,
+    }
+
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Serialize,
+        Deserialize,
+        IntoEnumIterator,
+        TryFromPrimitive,
+        IntoPrimitive,
+        Display,
+        JsonSchema,
+    )]
+    #[repr(usize)]
+    pub enum LfoShape {
+        #[display(fmt = "Sine")]
+        Sine,
+        #[display(fmt = "Triangle")]
+        Triangle,
+        #[display(fmt = "Saw up")]
+        SawUp,
+        #[display(fmt = "Saw down")]
+        SawDown,
+        #[display(fmt = "Square")]
+        Square,
+    }
+
+    impl Default for LfoShape {
+        fn default() -> Self {
+            Self::Sine
+        }
+    }
+
+    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    pub struct RandomSource {
+        /// Milliseconds between two random values.
+        pub interval: u64,
+    }
+}
+
+mod audio {
+    use derive_more::Display;
+    use enum_iterator::IntoEnumIterator;
+    use num_enum::{IntoPrimitive, TryFromPrimitive};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+    pub struct AudioInputSource {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub input: Option<AudioInputKind>,
+        /// Zero-based audio channel index.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub channel: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub measurement: Option<AudioLevelMeasurement>,
+        /// Time in milliseconds needed to follow a rising level.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub attack: Option<u64>,
+        /// Time in milliseconds needed to follow a falling level.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub release: Option<u64>,
+    }
+
+    /// Where the audio of an audio input source comes from.
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Serialize,
+        Deserialize,
+        IntoEnumIterator,
+        TryFromPrimitive,
+        IntoPrimitive,
+        Display,
+        JsonSchema,
+    )]
+    #[repr(usize)]
+    pub enum AudioInputKind {
+        /// Audio input of the ReaLearn instance.
+        #[display(fmt = "FX input")]
+        FxInput,
+        /// Input of the audio device, independent of any track routing.
+        #[display(fmt = "Hardware input")]
+        HardwareInput,
+    }
+
+    impl Default for AudioInputKind {
+        fn default() -> Self {
+            Self::FxInput
+        }
+    }
+
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Serialize,
+        Deserialize,
+        IntoEnumIterator,
+        TryFromPrimitive,
+        IntoPrimitive,
+        Display,
+        JsonSchema,
+    )]
+    #[repr(usize)]
+    pub enum AudioLevelMeasurement {
+        #[display(fmt = "Peak")]
+        Peak,
+        #[display(fmt = "RMS")]
+        Rms,
+    }
+
+    impl Default for AudioLevelMeasurement {
+        fn default() -> Self {
+            Self::Peak
+        }
+    }
 }
 
 mod keyboard {
@@ -15,14 +15,23 @@ pub enum Target {
     LastTouched(LastTouchedTarget),
     AutomationModeOverride(AutomationModeOverrideTarget),
     ReaperAction(ReaperActionTarget),
+    ToolbarToggle(ToolbarToggleTarget),
     TransportAction(TransportActionTarget),
     AnyOn(AnyOnTarget),
     #[serde(alias = "CycleThroughTracks")]
     BrowseTracks(BrowseTracksTarget),
+    NavigateTracks(NavigateTracksTarget),
+    BrowseProjectTabs(BrowseProjectTabsTarget),
+    NavigateProjectTabs(NavigateProjectTabsTarget),
+    RenderProject(RenderProjectTarget),
+    ToggleRenderQueue(ToggleRenderQueueTarget),
+    MidiEditorAction(MidiEditorActionTarget),
     Seek(SeekTarget),
     PlayRate(PlayRateTarget),
     Tempo(TempoTarget),
     GoToBookmark(GoToBookmarkTarget),
+    LoopRegion(LoopRegionTarget),
+    ItemProperty(ItemPropertyTarget),
     TrackArmState(TrackArmStateTarget),
     TrackParentSendState(TrackParentSendStateTarget),
     AllTrackFxOnOffState(AllTrackFxOnOffStateTarget),
@@ -36,6 +45,7 @@ pub enum Target {
     TrackPan(TrackPanTarget),
     TrackWidth(TrackWidthTarget),
     TrackVolume(TrackVolumeTarget),
+    TrackVolumeTrim(TrackVolumeTrimTarget),
     #[serde(rename = "Track")]
     TrackTool(TrackToolTarget),
     TrackVisibility(TrackVisibilityTarget),
@@ -45,6 +55,8 @@ pub enum Target {
     FxOnOffState(FxOnOffStateTarget),
     FxOnlineOfflineState(FxOnlineOfflineStateTarget),
     LoadFxSnapshot(LoadFxSnapshotTarget),
+    SaveFxParameterSnapshot(SaveFxParameterSnapshotTarget),
+    RecallFxParameterSnapshot(RecallFxParameterSnapshotTarget),
     #[serde(alias = "CycleThroughFxPresets")]
     BrowseFxPresets(BrowseFxPresetsTarget),
     #[serde(rename = "Fx")]
@@ -74,6 +86,10 @@ pub enum Target {
     #[serde(alias = "LoadMappingSnapshots")]
     LoadMappingSnapshot(LoadMappingSnapshotTarget),
     TakeMappingSnapshot(TakeMappingSnapshotTarget),
+    WriteEnvelopePoints(WriteEnvelopePointsTarget),
+    GlobalParameterValue(GlobalParameterValueTarget),
+    FeedbackBrightness(FeedbackBrightnessTarget),
+    ResyncFeedback(ResyncFeedbackTarget),
     #[serde(alias = "CycleThroughGroupMappings")]
     BrowseGroupMappings(BrowseGroupMappingsTarget),
     BrowsePotFilterItems(BrowsePotFilterItemsTarget),
@@ -142,6 +158,15 @@ pub struct ReaperActionTarget {
     pub track: Option<TrackDescriptor>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ToolbarToggleTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// The action whose toggle state should be controlled and reflected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<ReaperCommand>,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TransportActionTarget {
     #[serde(flatten)]
@@ -168,6 +193,91 @@ pub struct BrowseTracksTarget {
     pub mode: Option<BrowseTracksMode>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NavigateTracksTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scroll_arrange_view: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scroll_mixer: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<BrowseTracksMode>,
+    /// Number of tracks to move per increment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_size: Option<u32>,
+    /// Whether to continue at the other end when reaching the first or last track.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_around: Option<bool>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BrowseProjectTabsTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NavigateProjectTabsTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// Whether to continue at the other end when reaching the first or last project tab.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_around: Option<bool>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RenderProjectTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ToggleRenderQueueTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MidiEditorActionTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<MidiEditorAction>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    derive_more::Display,
+    enum_iterator::IntoEnumIterator,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum MidiEditorAction {
+    #[display(fmt = "Zoom in horizontally")]
+    ZoomIn,
+    #[display(fmt = "Zoom out horizontally")]
+    ZoomOut,
+    #[display(fmt = "Grid size")]
+    GridSize,
+    #[display(fmt = "Step input")]
+    StepInput,
+}
+
+impl Default for MidiEditorAction {
+    fn default() -> Self {
+        Self::ZoomIn
+    }
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SeekTarget {
     #[serde(flatten)]
@@ -196,6 +306,15 @@ pub struct PlayRateTarget {
     pub commons: TargetCommons,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ItemPropertyTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// The property of the first selected item (or its active take) to be controlled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<ItemPropertyKind>,
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TempoTarget {
     #[serde(flatten)]
@@ -215,6 +334,16 @@ pub struct GoToBookmarkTarget {
     pub seek_behavior: Option<SeekBehavior>,
 }
 
+#[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LoopRegionTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    pub region: BookmarkRef,
+    /// Whether to start playback at the beginning of the region if the project is not playing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_playback: Option<bool>,
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TrackArmStateTarget {
     #[serde(flatten)]
@@ -374,6 +503,14 @@ pub struct TrackVolumeTarget {
     pub use_selection_ganging: Option<bool>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TrackVolumeTrimTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct TrackToolTarget {
     #[serde(flatten)]
@@ -566,6 +703,8 @@ pub struct TrackSoloStateTarget {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub behavior: Option<SoloBehavior>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub solo_defeat: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_track_grouping: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub use_selection_ganging: Option<bool>,
@@ -606,6 +745,34 @@ pub struct LoadFxSnapshotTarget {
     pub snapshot: Option<FxSnapshot>,
 }
 
+/// Saves the current values of all parameters of an FX as snapshot of this ReaLearn instance.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SaveFxParameterSnapshotTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fx: Option<FxDescriptor>,
+    /// Number of the snapshot (starting with 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<u32>,
+}
+
+/// Applies the parameter values of a previously saved FX parameter snapshot to an FX.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RecallFxParameterSnapshotTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fx: Option<FxDescriptor>,
+    /// Number of the snapshot (starting with 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<u32>,
+    /// Time in milliseconds within which the parameters should glide from their current values
+    /// to the snapshot values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glide_time: Option<u64>,
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 pub struct BrowseFxPresetsTarget {
     #[serde(flatten)]
@@ -666,7 +833,7 @@ pub struct FxVisibilityTarget {
     pub display_kind: Option<FxDisplayKind>,
 }
 
-#[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[derive(PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FxParameterValueTarget {
     #[serde(flatten)]
     pub commons: TargetCommons,
@@ -675,6 +842,22 @@ pub struct FxParameterValueTarget {
     pub poll_for_feedback: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retrigger: Option<bool>,
+    /// Applies the value directly in the audio thread if the FX is on the same track as ReaLearn.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_latency: Option<bool>,
+    /// Overrides the parameter thinning of the instance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinning: Option<ParameterThinning>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ParameterThinning {
+    /// Minimum time in milliseconds between two parameter changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_interval: Option<u32>,
+    /// Minimum difference between two parameter changes (normalized, 0.0 to 1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_delta: Option<f64>,
 }
 
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -901,6 +1084,36 @@ pub struct TakeMappingSnapshotTarget {
     pub snapshot: BackwardCompatibleMappingSnapshotDescForTake,
 }
 
+/// Writes the current target values of the other mappings in the same group as envelope points.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WriteEnvelopePointsTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
+/// Sets the value of a global parameter, which is shared among all ReaLearn instances.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GlobalParameterValueTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// Name which identifies the global parameter.
+    pub name: String,
+}
+
+/// Scales all numeric feedback values sent by this ReaLearn instance.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FeedbackBrightnessTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
+/// Sends the feedback of all mappings of this ReaLearn instance again.
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ResyncFeedbackTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum BackwardCompatibleMappingSnapshotDescForTake {
@@ -1046,6 +1259,12 @@ pub struct VirtualTarget {
     pub id: VirtualControlElementId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub character: Option<VirtualControlElementCharacter>,
+    /// Number of lines of the display (only relevant for display elements).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_line_count: Option<u32>,
+    /// Number of characters per display line (only relevant for display elements).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_line_length: Option<u32>,
 }
 
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -1095,6 +1314,38 @@ impl Default for MonitoringMode {
     }
 }
 
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    derive_more::Display,
+    enum_iterator::IntoEnumIterator,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum ItemPropertyKind {
+    #[display(fmt = "Item volume")]
+    Volume,
+    #[display(fmt = "Item mute")]
+    Mute,
+    #[display(fmt = "Take pitch")]
+    TakePitch,
+    #[display(fmt = "Take playrate")]
+    TakePlayRate,
+}
+
+impl Default for ItemPropertyKind {
+    fn default() -> Self {
+        Self::Volume
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum TransportAction {
     PlayStop,
@@ -1270,6 +1521,18 @@ pub enum ClipMatrixAction {
     SetRecordDurationToFourBars,
     #[display(fmt = "Set record duration to 8 bars")]
     SetRecordDurationToEightBars,
+    #[display(fmt = "Click on/off state")]
+    ClickOnOffState,
+    #[display(fmt = "Click volume")]
+    ClickVolume,
+    #[display(fmt = "Shift clip view left")]
+    ShiftViewLeft,
+    #[display(fmt = "Shift clip view right")]
+    ShiftViewRight,
+    #[display(fmt = "Shift clip view up")]
+    ShiftViewUp,
+    #[display(fmt = "Shift clip view down")]
+    ShiftViewDown,
 }
 
 impl Default for ClipMatrixAction {
@@ -1498,8 +1761,16 @@ pub enum BookmarkDescriptor {
 #[derive(Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum BookmarkRef {
-    ById { id: u32 },
-    ByIndex { index: u32 },
+    ById {
+        id: u32,
+    },
+    ByIndex {
+        index: u32,
+    },
+    /// Takes the first bookmark whose name matches. Supports wildcards.
+    ByName {
+        name: String,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -1741,6 +2012,7 @@ impl Default for ClipRowDescriptor {
 pub enum MidiDestination {
     FxOutput,
     FeedbackOutput,
+    Device { id: u8 },
 }
 
 impl Default for MidiDestination {
@@ -452,6 +452,8 @@ fn virtual_target(id: String, character: VirtualControlElementCharacter) -> Opti
     let t = VirtualTarget {
         id: VirtualControlElementId::Named(id),
         character: Some(character),
+        display_line_count: None,
+        display_line_length: None,
     };
     Some(Target::Virtual(t))
 }
@@ -548,6 +550,7 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                         feedback_behavior: None,
                         channel: Some(channel.get()),
                         key_number: Some(key_number.get()),
+                        velocity_feedback: None,
                     }),
                     reverse_if_button_like: velocity == U7::MIN,
                 }
@@ -566,6 +569,7 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                         feedback_behavior: None,
                         channel: Some(channel.get()),
                         key_number: Some(key_number.get()),
+                        velocity_feedback: None,
                     }),
                     reverse_if_button_like: true,
                 }
@@ -585,6 +589,9 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                             feedback_behavior: None,
                             channel: Some(channel.get()),
                             key_number: Some(key_number.get()),
+                            velocity_feedback: None,
+                            feedback_as_note_on: None,
+                            debounce: None,
                         },
                     ),
                     reverse_if_button_like: pressure_amount == U7::MIN,
@@ -606,6 +613,7 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                         controller_number: Some(controller_number.get()),
                         character: Some(input.character),
                         fourteen_bit: Some(input.fourteen_bit),
+                        debounce: None,
                     }),
                     reverse_if_button_like: control_value == U7::MIN,
                 }
@@ -638,6 +646,7 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                     source: Source::MidiChannelPressureAmount(MidiChannelPressureAmountSource {
                         feedback_behavior: None,
                         channel: Some(channel.get()),
+                        debounce: None,
                     }),
                     reverse_if_button_like: pressure_amount == U7::MIN,
                 }
@@ -654,6 +663,8 @@ fn convert_max_short_msg_to_source(input: MsgConvInput) -> CsiResult<MsgConvOutp
                     source: Source::MidiPitchBendChangeValue(MidiPitchBendChangeValueSource {
                         feedback_behavior: None,
                         channel: Some(channel.get()),
+                        debounce: None,
+                        behavior: None,
                     }),
                     reverse_if_button_like: pitch_bend_value == U14::MIN,
                 }
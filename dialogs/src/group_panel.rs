@@ -18,10 +18,17 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             WS_CAPTION,
             WS_SYSMENU,
         ]),
-        controls: vec![ok_button(
-            ids.named_id("ID_GROUP_PANEL_OK"),
-            context.rect(197, 53, 50, 14),
-        )],
+        controls: vec![
+            ok_button(
+                ids.named_id("ID_GROUP_PANEL_OK"),
+                context.rect(197, 53, 50, 14),
+            ),
+            pushbutton(
+                "Feedback color...",
+                ids.named_id("ID_GROUP_PANEL_FEEDBACK_COLOR_BUTTON"),
+                context.rect(7, 53, 120, 14),
+            ),
+        ],
         ..context.default_dialog()
     }
 }
@@ -98,6 +98,11 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("ID_MAIN_COMPARTMENT_RADIO_BUTTON"),
             context.rect(289, 48, 145, 8),
         ) + WS_TABSTOP,
+        pushbutton(
+            "Overload!",
+            ids.named_id("ID_AUDIO_OVERLOAD_BUTTON"),
+            context.rect(436, 45, 30, 14),
+        ) + NOT_WS_TABSTOP,
     ];
     let lower_part_controls = [
         // Preset
@@ -13,21 +13,20 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
         ) + CBS_DROPDOWNLIST
             + CBS_HASSTRINGS
             + WS_TABSTOP,
-        context.checkbox(
-            "Show in projection",
-            ids.named_id("ID_MAPPING_SHOW_IN_PROJECTION_CHECK_BOX"),
-            rect(180, 53, 74, 8),
+        dropdown(
+            ids.named_id("ID_MAPPING_PROJECTION_COMBO_BOX"),
+            context.rect(174, 51, 100, 15),
         ) + WS_GROUP
             + WS_TABSTOP,
         pushbutton(
             "Advanced settings",
             ids.named_id("ID_MAPPING_ADVANCED_BUTTON"),
-            context.rect(259, 50, 87, 14),
+            context.rect(279, 50, 78, 14),
         ) + NOT_WS_TABSTOP,
         pushbutton(
             "Find in mapping list",
             ids.named_id("ID_MAPPING_FIND_IN_LIST_BUTTON"),
-            context.rect(352, 50, 87, 14),
+            context.rect(362, 50, 77, 14),
         ) + NOT_WS_TABSTOP,
     ];
     let source_controls = [
@@ -135,6 +134,11 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("ID_SOURCE_SCRIPT_DETAIL_BUTTON"),
             context.rect(155, 213, 13, 14),
         ),
+        pushbutton(
+            "Debounce...",
+            ids.named_id("ID_SOURCE_DEBOUNCE_BUTTON"),
+            context.rect(107, 191, 61, 11),
+        ),
     ];
     let target_controls = [
         groupbox("Target", ids.id(), context.rect(177, 67, 265, 165)),
@@ -151,8 +155,13 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
         ltext(
             "Hint",
             ids.named_id("ID_TARGET_HINT"),
-            context.rect(283, 80, 155, 9),
+            context.rect(283, 80, 85, 9),
         ) + WS_TABSTOP,
+        dropdown(
+            ids.named_id("ID_TARGET_HISTORY_COMBO_BOX"),
+            context.rect(372, 77, 66, 15),
+        ) + WS_VSCROLL
+            + WS_TABSTOP,
         ltext("Type", ids.id(), context.rect(181, 98, 35, 9)) + NOT_WS_GROUP,
         dropdown(
             ids.named_id("ID_TARGET_CATEGORY_COMBO_BOX"),
@@ -491,6 +500,15 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("ID_SETTINGS_MODE_COMBO_BOX"),
             context.rect(50, 355, 168, 15),
         ) + WS_TABSTOP,
+        ltext(
+            "Button behavior",
+            ids.named_id("ID_MODE_BUTTON_BEHAVIOR_LABEL"),
+            context.rect(15, 376, 70, 9),
+        ) + NOT_WS_GROUP,
+        dropdown(
+            ids.named_id("ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX"),
+            context.rect(92, 374, 125, 15),
+        ) + WS_TABSTOP,
         ltext(
             "Takeover",
             ids.named_id("ID_MODE_TAKEOVER_LABEL"),
@@ -519,6 +537,10 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("ID_MODE_EEL_CONTROL_TRANSFORMATION_DETAIL_BUTTON"),
             context.rect(201, 435, 13, 14),
         ),
+        dropdown(
+            ids.named_id("ID_MODE_CONTROL_CURVE_COMBO_BOX"),
+            context.rect(112, 420, 102, 15),
+        ) + WS_TABSTOP,
         groupbox(
             "For encoders and incremental buttons (control only)",
             ids.named_id("ID_MODE_RELATIVE_GROUP_BOX"),
@@ -574,7 +596,7 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             + NOT_WS_GROUP,
         dropdown(
             ids.named_id("ID_MODE_RELATIVE_FILTER_COMBO_BOX"),
-            context.rect(231, 388, 104, 15),
+            context.rect(231, 388, 64, 15),
         ) + WS_TABSTOP,
         context.checkbox(
             "Wrap",
@@ -586,6 +608,10 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("ID_SETTINGS_MAKE_ABSOLUTE_CHECK_BOX"),
             rect(375, 391, 60, 8),
         ) + WS_TABSTOP,
+        dropdown(
+            ids.named_id("ID_MODE_ACCELERATION_CURVE_COMBO_BOX"),
+            context.rect(298, 388, 42, 15),
+        ) + WS_TABSTOP,
         groupbox(
             "For buttons (control only)",
             ids.named_id("ID_MODE_BUTTON_GROUP_BOX"),
@@ -691,6 +717,26 @@ pub fn create(context: ScopedContext, ids: &mut IdGenerator) -> Dialog {
             ids.named_id("IDC_MAPPING_ENABLED_CHECK_BOX"),
             rect(405, 516, 39, 10),
         ) + WS_TABSTOP,
+        pushbutton(
+            "?",
+            ids.named_id("ID_MAPPING_SECTION_HELP_BUTTON"),
+            context.rect(430, 0, 10, 9),
+        ) + NOT_WS_TABSTOP,
+        pushbutton(
+            "?",
+            ids.named_id("ID_SOURCE_SECTION_HELP_BUTTON"),
+            context.rect(160, 66, 10, 9),
+        ) + NOT_WS_TABSTOP,
+        pushbutton(
+            "?",
+            ids.named_id("ID_TARGET_SECTION_HELP_BUTTON"),
+            context.rect(430, 66, 10, 9),
+        ) + NOT_WS_TABSTOP,
+        pushbutton(
+            "?",
+            ids.named_id("ID_GLUE_SECTION_HELP_BUTTON"),
+            context.rect(430, 231, 10, 9),
+        ) + NOT_WS_TABSTOP,
     ];
     Dialog {
         id: ids.named_id("ID_MAPPING_PANEL"),
@@ -0,0 +1,199 @@
+use crate::{convert_glue, convert_source, EmbeddedTimestamp, NoTransformation};
+use helgoboss_learn::{
+    AbsoluteValue, AbstractTimestamp, ControlEvent, ControlValue, FeedbackValue, MidiSource,
+    MidiSourceValue, Mode, ModeControlOptions, ModeControlResult, ModeFeedbackOptions,
+    NumericFeedbackValue, SourceContext, Target,
+};
+use helgoboss_midi::RawShortMessage;
+use realearn_api::persistence::{Glue, Source};
+use std::borrow::Cow;
+use std::error::Error;
+
+/// A single mapping consisting of a MIDI source and a glue section, processing values for a
+/// target provided by the embedding extension.
+///
+/// Processing happens synchronously, so the methods should be called in the thread in which the
+/// target can be controlled (usually the main thread).
+#[derive(Debug)]
+pub struct EmbeddedMapping {
+    source: MidiSource,
+    mode: Mode<NoTransformation, EmbeddedTimestamp>,
+}
+
+impl EmbeddedMapping {
+    /// Creates a mapping from the given source and glue descriptions.
+    ///
+    /// Returns an error if the descriptions are invalid or use features which are not supported
+    /// when embedding.
+    pub fn new(source: Source, glue: Glue) -> Result<Self, Box<dyn Error>> {
+        let mapping = Self {
+            source: convert_source(source)?,
+            mode: Mode::new(convert_glue(glue)?),
+        };
+        Ok(mapping)
+    }
+
+    /// Processes the given incoming MIDI message.
+    ///
+    /// Returns `None` if the message doesn't match the source or if the glue section decided to
+    /// do nothing. Otherwise returns what should happen with the target. The target is only
+    /// queried, never changed, so hitting it is up to the caller.
+    pub fn control<'a>(
+        &mut self,
+        msg: &MidiSourceValue<RawShortMessage>,
+        target: &impl Target<'a, Context = ()>,
+    ) -> Option<ModeControlResult<ControlValue>> {
+        let control_value = self.source.control(msg)?;
+        let evt = ControlEvent::new(control_value, EmbeddedTimestamp::now());
+        self.mode.control_with_options(
+            evt,
+            target,
+            (),
+            ModeControlOptions {
+                enforce_rotate: self.mode.settings().rotate,
+            },
+            None,
+        )
+    }
+
+    /// Should be called regularly (e.g. in the main loop) if the glue section uses features which
+    /// fire without incoming messages, such as "Fire after timeout" or turbo.
+    pub fn poll<'a>(
+        &mut self,
+        target: &impl Target<'a, Context = ()>,
+    ) -> Option<ModeControlResult<ControlValue>> {
+        self.mode.poll(target, (), EmbeddedTimestamp::now())
+    }
+
+    /// Returns the MIDI message which should be sent to the controller in order to reflect the
+    /// given target value.
+    pub fn feedback(
+        &self,
+        target_value: AbsoluteValue,
+    ) -> Option<MidiSourceValue<'static, RawShortMessage>> {
+        let feedback_value =
+            FeedbackValue::Numeric(NumericFeedbackValue::new(Default::default(), target_value));
+        let options = ModeFeedbackOptions {
+            source_is_virtual: false,
+            max_discrete_source_value: self.source.max_discrete_value(),
+        };
+        let mode_value = self.mode.feedback_with_options_detail(
+            Cow::Owned(feedback_value),
+            options,
+            Default::default(),
+        )?;
+        let source_value = self
+            .source
+            .feedback_flexible(mode_value.into_owned(), &SourceContext)?;
+        Some(source_value.final_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::{ControlType, UnitValue};
+    use helgoboss_midi::{Channel, ControllerNumber, ShortMessageFactory, U7};
+    use realearn_api::persistence::{Interval, MidiControlChangeValueSource, MidiRawSource};
+
+    #[test]
+    fn control_applies_glue() {
+        // Given
+        let mut mapping = EmbeddedMapping::new(cc_source(7), half_target_interval()).unwrap();
+        let target = TestTarget(UnitValue::MIN);
+        // When
+        let result = mapping.control(&MidiSourceValue::Plain(cc(7, 127)), &target);
+        // Then
+        let Some(ModeControlResult::HitTarget {
+            value: ControlValue::AbsoluteContinuous(v),
+        }) = result
+        else {
+            panic!("target should be hit with an absolute value but was {result:?}");
+        };
+        assert_eq!(v, UnitValue::new(0.5));
+    }
+
+    #[test]
+    fn control_ignores_other_messages() {
+        // Given
+        let mut mapping = EmbeddedMapping::new(cc_source(7), Glue::default()).unwrap();
+        let target = TestTarget(UnitValue::MIN);
+        // When
+        let result = mapping.control(&MidiSourceValue::Plain(cc(8, 127)), &target);
+        // Then
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn feedback_applies_glue() {
+        // Given
+        let mapping = EmbeddedMapping::new(cc_source(7), half_target_interval()).unwrap();
+        // When
+        let result = mapping.feedback(AbsoluteValue::Continuous(UnitValue::new(0.5)));
+        // Then
+        let Some(MidiSourceValue::Plain(msg)) = result else {
+            panic!("feedback should be a plain MIDI message but was {result:?}");
+        };
+        assert_eq!(msg, cc(7, 127));
+    }
+
+    #[test]
+    fn reject_unsupported_sources() {
+        // Given
+        let source = Source::MidiRaw(MidiRawSource::default());
+        // When
+        let result = EmbeddedMapping::new(source, Glue::default());
+        // Then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_transformations() {
+        // Given
+        let glue = Glue {
+            control_transformation: Some("y = x".to_string()),
+            ..Default::default()
+        };
+        // When
+        let result = EmbeddedMapping::new(cc_source(7), glue);
+        // Then
+        assert!(result.is_err());
+    }
+
+    struct TestTarget(UnitValue);
+
+    impl<'a> Target<'a> for TestTarget {
+        type Context = ();
+
+        fn current_value(&self, _: ()) -> Option<AbsoluteValue> {
+            Some(AbsoluteValue::Continuous(self.0))
+        }
+
+        fn control_type(&self, _: ()) -> ControlType {
+            ControlType::AbsoluteContinuous
+        }
+    }
+
+    fn cc_source(controller_number: u8) -> Source {
+        Source::MidiControlChangeValue(MidiControlChangeValueSource {
+            channel: Some(0),
+            controller_number: Some(controller_number),
+            ..Default::default()
+        })
+    }
+
+    fn half_target_interval() -> Glue {
+        Glue {
+            target_interval: Some(Interval(0.0, 0.5)),
+            ..Default::default()
+        }
+    }
+
+    fn cc(controller_number: u8, value: u8) -> RawShortMessage {
+        RawShortMessage::control_change(
+            Channel::new(0),
+            ControllerNumber::new(controller_number),
+            U7::new(value),
+        )
+    }
+}
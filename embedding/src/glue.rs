@@ -0,0 +1,95 @@
+use crate::{ConversionResult, NoTransformation};
+use helgoboss_learn::{ModeSettings, ValueSequence};
+use realearn_api::persistence::*;
+use realearn_api_convert::*;
+
+/// Converts the given glue description into mode settings.
+///
+/// Everything which needs REAPER or the EEL scripting engine (transformations, control curves,
+/// tempo-synced turbo) or which only makes sense in the context of a ReaLearn instance (group
+/// interaction, encoder acceleration) results in an error.
+pub fn convert_glue(g: Glue) -> ConversionResult<ModeSettings<NoTransformation>> {
+    if g.control_transformation
+        .map_or(false, |t| !t.trim().is_empty())
+    {
+        return Err("control transformations are not supported when embedding".into());
+    }
+    if g.control_curve.map_or(false, |c| c != ControlCurve::Linear) {
+        return Err("control curves are not supported when embedding".into());
+    }
+    if g.acceleration_curve
+        .map_or(false, |c| c != AccelerationCurve::Linear)
+    {
+        return Err("acceleration curves are not supported when embedding".into());
+    }
+    if g.interaction.is_some() {
+        return Err("group interaction is not supported when embedding".into());
+    }
+    let fire_mode = g.fire_mode.unwrap_or_default();
+    if let FireMode::AfterTimeoutKeepFiring(m) = &fire_mode {
+        if m.synced_rate.is_some() {
+            return Err("tempo-synced turbo is not supported when embedding".into());
+        }
+    }
+    let (feedback_type, feedback_commons, textual_feedback_expression) = {
+        use helgoboss_learn::FeedbackType as T;
+        match g.feedback.unwrap_or_default() {
+            Feedback::Numeric(fb) => {
+                if fb.transformation.map_or(false, |t| !t.trim().is_empty()) {
+                    return Err("feedback transformations are not supported when embedding".into());
+                }
+                (T::Numerical, fb.commons, String::new())
+            }
+            Feedback::Text(fb) => (
+                T::Textual,
+                fb.commons,
+                fb.text_expression.unwrap_or_default(),
+            ),
+        }
+    };
+    let settings = ModeSettings {
+        absolute_mode: convert_absolute_mode(g.absolute_mode),
+        source_value_interval: convert_unit_value_interval(
+            g.source_interval.unwrap_or(defaults::GLUE_SOURCE_INTERVAL),
+        )?,
+        target_value_interval: convert_unit_value_interval(
+            g.target_interval.unwrap_or(defaults::GLUE_TARGET_INTERVAL),
+        )?,
+        jump_interval: convert_unit_value_interval(
+            g.jump_interval.unwrap_or(defaults::GLUE_JUMP_INTERVAL),
+        )?,
+        step_size_interval: convert_step_size_interval(
+            g.step_size_interval
+                .unwrap_or(defaults::GLUE_STEP_SIZE_INTERVAL),
+        )?,
+        step_factor_interval: convert_step_factor_interval(
+            g.step_factor_interval
+                .unwrap_or(defaults::GLUE_STEP_FACTOR_INTERVAL),
+        )?,
+        fire_mode: convert_fire_mode(&fire_mode),
+        press_duration_interval: convert_press_duration_interval(&fire_mode)?,
+        turbo_rate: convert_turbo_rate(&fire_mode),
+        takeover_mode: convert_takeover_mode(g.takeover_mode),
+        encoder_usage: convert_encoder_usage(g.encoder_filter),
+        button_usage: convert_button_usage(g.button_filter),
+        reverse: g.reverse.unwrap_or(defaults::GLUE_REVERSE),
+        rotate: g.wrap.unwrap_or(defaults::GLUE_WRAP),
+        round_target_value: g
+            .round_target_value
+            .unwrap_or(defaults::GLUE_ROUND_TARGET_VALUE),
+        out_of_range_behavior: convert_out_of_range_behavior(g.out_of_range_behavior),
+        make_absolute: g.relative_mode.unwrap_or_default() == RelativeMode::MakeAbsolute,
+        target_value_sequence: if let Some(s) = g.target_value_sequence {
+            s.parse::<ValueSequence>()?
+        } else {
+            Default::default()
+        },
+        feedback_type,
+        textual_feedback_expression,
+        feedback_color: feedback_commons.color.map(convert_virtual_color),
+        feedback_background_color: feedback_commons.background_color.map(convert_virtual_color),
+        feedback_value_table: g.feedback_value_table.map(convert_feedback_value_table),
+        ..Default::default()
+    };
+    Ok(settings)
+}
@@ -0,0 +1,39 @@
+//! Library for embedding ReaLearn's mapping engine into other REAPER extensions.
+//!
+//! An [`EmbeddedMapping`] is created from a source and a glue section, described in exactly the
+//! same way as in ReaLearn's Lua presets (see [`realearn_api::persistence`]). The target is
+//! provided by the embedding extension itself by implementing [`Target`].
+//!
+//! ```ignore
+//! let mapping = EmbeddedMapping::new(source, glue)?;
+//! // For each incoming MIDI message
+//! if let Some(ModeControlResult::HitTarget { value }) = mapping.control(&msg, &my_target) {
+//!     my_target.hit(value);
+//! }
+//! // Whenever the target value changes
+//! if let Some(msg) = mapping.feedback(my_target.current_value(())?) {
+//!     send_to_controller(msg);
+//! }
+//! ```
+mod embedded_mapping;
+pub use embedded_mapping::*;
+
+mod glue;
+pub use glue::*;
+
+mod source;
+pub use source::*;
+
+mod timestamp;
+pub use timestamp::*;
+
+mod transformation;
+pub use transformation::*;
+
+pub use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, MidiSourceValue, ModeControlResult, Target, UnitValue,
+};
+pub use helgoboss_midi::RawShortMessage;
+pub use realearn_api::persistence::{Glue, Source};
+
+use realearn_api_convert::ConversionResult;
@@ -0,0 +1,61 @@
+use crate::ConversionResult;
+use helgoboss_learn::MidiSource;
+use helgoboss_midi::U7;
+use realearn_api::persistence::Source;
+use realearn_api_convert::{convert_channel, convert_character, convert_u7};
+
+/// Converts the given source description into a MIDI source.
+///
+/// Only MIDI sources which can be processed without any REAPER-specific context are supported.
+/// Source settings which ReaLearn applies outside of the source itself (e.g. debouncing or
+/// velocity feedback) are ignored.
+pub fn convert_source(s: Source) -> ConversionResult<MidiSource> {
+    use Source::*;
+    let source = match s {
+        MidiNoteVelocity(s) => MidiSource::NoteVelocity {
+            channel: convert_channel(s.channel)?,
+            key_number: convert_u7(s.key_number)?.map(|n| n.into()),
+        },
+        MidiNoteKeyNumber(s) => MidiSource::NoteKeyNumber {
+            channel: convert_channel(s.channel)?,
+        },
+        MidiPolyphonicKeyPressureAmount(s) => MidiSource::PolyphonicKeyPressureAmount {
+            channel: convert_channel(s.channel)?,
+            key_number: convert_u7(s.key_number)?.map(|n| n.into()),
+        },
+        MidiControlChangeValue(s) => {
+            let channel = convert_channel(s.channel)?;
+            let controller_number = convert_u7(s.controller_number)?;
+            let custom_character = convert_character(s.character);
+            if s.fourteen_bit == Some(true) {
+                MidiSource::ControlChange14BitValue {
+                    channel,
+                    // We accept even non-MSB numbers and convert them into them (same as ReaLearn).
+                    msb_controller_number: controller_number.map(|n| U7::new(n.get() % 32).into()),
+                    custom_character,
+                }
+            } else {
+                MidiSource::ControlChangeValue {
+                    channel,
+                    controller_number: controller_number.map(|n| n.into()),
+                    custom_character,
+                }
+            }
+        }
+        MidiProgramChangeNumber(s) => MidiSource::ProgramChangeNumber {
+            channel: convert_channel(s.channel)?,
+        },
+        MidiSpecificProgramChange(s) => MidiSource::SpecificProgramChange {
+            channel: convert_channel(s.channel)?,
+            program_number: convert_u7(s.program_number)?,
+        },
+        MidiChannelPressureAmount(s) => MidiSource::ChannelPressureAmount {
+            channel: convert_channel(s.channel)?,
+        },
+        MidiPitchBendChangeValue(s) => MidiSource::PitchBendChangeValue {
+            channel: convert_channel(s.channel)?,
+        },
+        _ => return Err("only short MIDI message sources are supported when embedding".into()),
+    };
+    Ok(source)
+}
@@ -0,0 +1,28 @@
+use helgoboss_learn::AbstractTimestamp;
+use std::fmt::{Display, Formatter};
+use std::ops::Sub;
+use std::time::{Duration, Instant};
+
+/// Timestamp of a control event processed by an embedded mapping.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EmbeddedTimestamp(Instant);
+
+impl AbstractTimestamp for EmbeddedTimestamp {
+    fn now() -> Self {
+        Self(Instant::now())
+    }
+}
+
+impl Sub for EmbeddedTimestamp {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
+impl Display for EmbeddedTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
@@ -0,0 +1,25 @@
+use helgoboss_learn::{Transformation, TransformationInput, TransformationOutput};
+
+/// Transformation type of embedded mappings.
+///
+/// Transformations need the EEL scripting engine, which is part of ReaLearn itself. That's why
+/// embedded mappings never have one.
+#[derive(Clone, Debug)]
+pub enum NoTransformation {}
+
+impl Transformation for NoTransformation {
+    type AdditionalInput = ();
+
+    fn transform(
+        &self,
+        _input: TransformationInput<f64>,
+        _output_value: f64,
+        _additional_input: (),
+    ) -> Result<TransformationOutput<f64>, &'static str> {
+        match *self {}
+    }
+
+    fn wants_to_be_polled(&self) -> bool {
+        match *self {}
+    }
+}
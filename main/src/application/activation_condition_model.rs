@@ -4,6 +4,7 @@ use crate::application::{
 };
 use crate::domain::{
     ActivationCondition, EelCondition, ExpressionCondition, ExpressionEvaluator, MappingId,
+    TransportCondition,
 };
 
 #[allow(clippy::enum_variant_names)]
@@ -32,7 +33,7 @@ impl GetProcessingRelevance for ActivationConditionProp {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct ActivationConditionModel {
     activation_type: ActivationType,
     modifier_condition_1: ModifierConditionModel,
@@ -138,6 +139,9 @@ impl ActivationConditionModel {
                 },
                 Err(_) => ActivationCondition::Always,
             },
+            WhilePlaying => ActivationCondition::Transport(TransportCondition::Playing),
+            WhileRecording => ActivationCondition::Transport(TransportCondition::Recording),
+            WhileStopped => ActivationCondition::Transport(TransportCondition::Stopped),
         }
     }
 
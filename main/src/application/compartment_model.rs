@@ -1,7 +1,11 @@
 use crate::application::{
-    Affected, GroupModel, GroupProp, MappingCommand, MappingModel, MappingProp,
+    Affected, GroupModel, GroupProp, LifecycleModel, MappingCommand, MappingModel, MappingProp,
+    MessageTemplatesModel,
 };
-use crate::domain::{CompartmentParamIndex, GroupId, MappingId, ParamSetting};
+use crate::domain::{
+    CompartmentParamIndex, ControllerLifecycleMidiData, GroupId, MappingId, ParamSetting,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -13,6 +17,31 @@ pub struct CompartmentModel {
     /// At the moment, custom data is only used in the controller compartment.
     pub custom_data: HashMap<String, serde_json::Value>,
     pub notes: String,
+    /// At the moment, lifecycle messages are only used in the controller compartment.
+    pub lifecycle: CompartmentLifecycleModel,
+    /// At the moment, message templates are only used in the controller compartment.
+    pub message_templates: MessageTemplatesModel,
+}
+
+/// Messages which are sent independently of any mapping, e.g. for putting the controller into a
+/// particular mode.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompartmentLifecycleModel {
+    pub on_init: LifecycleModel,
+    pub on_shutdown: LifecycleModel,
+}
+
+impl CompartmentLifecycleModel {
+    pub fn create_controller_lifecycle_midi_data(
+        &self,
+    ) -> Result<ControllerLifecycleMidiData, &'static str> {
+        let data = ControllerLifecycleMidiData {
+            init_midi_messages: self.on_init.create_lifecycle_midi_messages()?,
+            shutdown_midi_messages: self.on_shutdown.create_lifecycle_midi_messages()?,
+        };
+        Ok(data)
+    }
 }
 
 pub enum CompartmentCommand {
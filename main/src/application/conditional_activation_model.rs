@@ -37,6 +37,15 @@ pub enum ActivationType {
     #[serde(rename = "target-value")]
     #[display(fmt = "When target value met")]
     TargetValue,
+    #[serde(rename = "playing")]
+    #[display(fmt = "While playing")]
+    WhilePlaying,
+    #[serde(rename = "recording")]
+    #[display(fmt = "While recording")]
+    WhileRecording,
+    #[serde(rename = "stopped")]
+    #[display(fmt = "While stopped")]
+    WhileStopped,
 }
 
 impl Default for ActivationType {
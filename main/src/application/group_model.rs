@@ -4,6 +4,7 @@ use crate::application::{
 };
 use crate::domain::{Compartment, GroupId, GroupKey, Tag};
 use core::fmt;
+use helgoboss_learn::VirtualColor;
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
@@ -12,6 +13,7 @@ pub enum GroupCommand {
     SetTags(Vec<Tag>),
     SetControlIsEnabled(bool),
     SetFeedbackIsEnabled(bool),
+    SetFeedbackColor(Option<VirtualColor>),
     ChangeActivationCondition(ActivationConditionCommand),
 }
 
@@ -20,6 +22,7 @@ pub enum GroupProp {
     Tags,
     ControlIsEnabled,
     FeedbackIsEnabled,
+    FeedbackColor,
     InActivationCondition(Affected<ActivationConditionProp>),
 }
 
@@ -27,7 +30,7 @@ impl GetProcessingRelevance for GroupProp {
     fn processing_relevance(&self) -> Option<ProcessingRelevance> {
         use GroupProp as P;
         match self {
-            P::Tags | P::ControlIsEnabled | P::FeedbackIsEnabled => {
+            P::Tags | P::ControlIsEnabled | P::FeedbackIsEnabled | P::FeedbackColor => {
                 Some(ProcessingRelevance::ProcessingRelevant)
             }
             P::InActivationCondition(p) => p.processing_relevance(),
@@ -46,6 +49,7 @@ pub struct GroupModel {
     tags: Vec<Tag>,
     control_is_enabled: bool,
     feedback_is_enabled: bool,
+    feedback_color: Option<VirtualColor>,
     pub activation_condition_model: ActivationConditionModel,
 }
 
@@ -74,6 +78,10 @@ impl<'a> Change<'a> for GroupModel {
                 self.feedback_is_enabled = v;
                 One(P::FeedbackIsEnabled)
             }
+            C::SetFeedbackColor(v) => {
+                self.feedback_color = v;
+                One(P::FeedbackColor)
+            }
             C::ChangeActivationCondition(cmd) => {
                 return self
                     .activation_condition_model
@@ -110,6 +118,11 @@ impl GroupModel {
         self.feedback_is_enabled
     }
 
+    /// Feedback color of all mappings in this group which don't have a color on their own.
+    pub fn feedback_color(&self) -> Option<&VirtualColor> {
+        self.feedback_color.as_ref()
+    }
+
     pub fn activation_condition_model(&self) -> &ActivationConditionModel {
         &self.activation_condition_model
     }
@@ -153,6 +166,7 @@ impl GroupModel {
             tags: Default::default(),
             control_is_enabled: true,
             feedback_is_enabled: true,
+            feedback_color: None,
             activation_condition_model: ActivationConditionModel::default(),
         }
     }
@@ -190,6 +204,7 @@ impl GroupModel {
                 .activation_condition_model
                 .create_activation_condition(),
             tags: self.tags.clone(),
+            feedback_color: self.feedback_color.clone(),
         }
     }
 }
@@ -1,33 +1,64 @@
-use crate::domain::{LifecycleMidiData, LifecycleMidiMessage, MappingExtension};
+use crate::domain::{
+    ControlQuantization, ControlScheduling, LifecycleActionData, LifecycleMidiData,
+    LifecycleMidiMessage, MappingExtension, StepSequence,
+};
 
 use crate::application::parse_hex_string;
+use crate::base::default_util::is_default;
 use crate::domain::ui_util::DisplayRawMidi;
-use helgoboss_learn::RawMidiEvent;
+use helgoboss_learn::{RawMidiEvent, UnitValue};
+use reaper_high::{Action, Reaper};
 use serde::{Deserialize, Serialize};
 use serde_with::SerializeDisplay;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct MappingExtensionModel {
     pub on_activate: LifecycleModel,
     pub on_deactivate: LifecycleModel,
+    #[serde(skip_serializing_if = "is_default")]
+    pub on_control: ControlSchedulingModel,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct LifecycleModel {
     pub send_midi_feedback: Vec<LifecycleMidiMessageModel>,
+    /// Command IDs or command names of REAPER actions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub invoke_reaper_actions: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ControlSchedulingModel {
+    /// Delay in milliseconds.
+    pub delay: u64,
+    /// Either "grid" (project grid) or a fraction of a whole note such as "1/16".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantize: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_sequence: Option<StepSequenceModel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepSequenceModel {
+    /// Target values between 0.0 and 1.0.
+    pub values: Vec<f64>,
+    /// Time between two steps in milliseconds.
+    pub interval: u64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LifecycleMidiMessageModel {
     Raw(RawMidiMessage),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RawMidiMessage {
     HexString(RawHexStringMidiMessage),
@@ -44,7 +75,7 @@ impl RawMidiMessage {
     }
 }
 
-#[derive(Clone, Debug, SerializeDisplay, Deserialize)]
+#[derive(Clone, Debug, PartialEq, SerializeDisplay, Deserialize)]
 #[serde(try_from = "String")]
 pub struct RawHexStringMidiMessage(pub Vec<u8>);
 
@@ -54,7 +85,7 @@ impl Display for RawHexStringMidiMessage {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RawByteArrayMidiMessage(pub Vec<u8>);
 
 impl TryFrom<String> for RawHexStringMidiMessage {
@@ -79,20 +110,101 @@ impl LifecycleMidiMessageModel {
     }
 }
 
+impl LifecycleModel {
+    pub fn create_lifecycle_midi_messages(
+        &self,
+    ) -> Result<Vec<LifecycleMidiMessage>, &'static str> {
+        self.send_midi_feedback
+            .iter()
+            .map(|m| m.create_lifecycle_midi_message())
+            .collect()
+    }
+
+    pub fn create_lifecycle_actions(&self) -> Result<Vec<Action>, &'static str> {
+        self.invoke_reaper_actions
+            .iter()
+            .map(|command| {
+                let reaper = Reaper::get();
+                match command.parse::<u32>() {
+                    // Built-in action
+                    Ok(command_id_int) => {
+                        let command_id = command_id_int
+                            .try_into()
+                            .map_err(|_| "invalid command ID of lifecycle action")?;
+                        Ok(reaper.main_section().action_by_command_id(command_id))
+                    }
+                    // ReaScript or custom action
+                    Err(_) => Ok(reaper.action_by_command_name(command.as_str())),
+                }
+            })
+            .collect()
+    }
+}
+
 impl MappingExtensionModel {
     pub fn create_mapping_extension(&self) -> Result<MappingExtension, &'static str> {
-        fn convert_messages(
-            model: &[LifecycleMidiMessageModel],
-        ) -> Result<Vec<LifecycleMidiMessage>, &'static str> {
-            model
-                .iter()
-                .map(|m| m.create_lifecycle_midi_message())
-                .collect()
-        }
-        let ext = MappingExtension::new(LifecycleMidiData {
-            activation_midi_messages: convert_messages(&self.on_activate.send_midi_feedback)?,
-            deactivation_midi_messages: convert_messages(&self.on_deactivate.send_midi_feedback)?,
-        });
+        let ext = MappingExtension::new(
+            LifecycleMidiData {
+                activation_midi_messages: self.on_activate.create_lifecycle_midi_messages()?,
+                deactivation_midi_messages: self.on_deactivate.create_lifecycle_midi_messages()?,
+            },
+            LifecycleActionData {
+                activation_actions: self.on_activate.create_lifecycle_actions()?,
+                deactivation_actions: self.on_deactivate.create_lifecycle_actions()?,
+            },
+            self.on_control.create_control_scheduling()?,
+        );
         Ok(ext)
     }
 }
+
+impl ControlSchedulingModel {
+    pub fn create_control_scheduling(&self) -> Result<ControlScheduling, &'static str> {
+        let step_sequence = match &self.step_sequence {
+            None => None,
+            Some(s) => {
+                if s.values.is_empty() {
+                    return Err("step sequence needs at least one value");
+                }
+                if s.interval == 0 {
+                    return Err("step sequence interval must be greater than zero");
+                }
+                let values: Result<Vec<_>, _> =
+                    s.values.iter().map(|v| UnitValue::try_new(*v)).collect();
+                Some(StepSequence {
+                    values: values
+                        .map_err(|_| "step sequence values must be between 0.0 and 1.0")?,
+                    interval: Duration::from_millis(s.interval),
+                })
+            }
+        };
+        let quantization = match &self.quantize {
+            None => None,
+            Some(q) => Some(parse_control_quantization(q)?),
+        };
+        let scheduling = ControlScheduling {
+            delay: Duration::from_millis(self.delay),
+            quantization,
+            step_sequence,
+        };
+        Ok(scheduling)
+    }
+}
+
+fn parse_control_quantization(text: &str) -> Result<ControlQuantization, &'static str> {
+    const ERROR: &str = "quantize must be \"grid\" or a fraction such as \"1/16\"";
+    let text = text.trim();
+    if text == "grid" {
+        return Ok(ControlQuantization::ProjectGrid);
+    }
+    let (numerator, denominator) = text.split_once('/').ok_or(ERROR)?;
+    let numerator: u32 = numerator.trim().parse().map_err(|_| ERROR)?;
+    let denominator: u32 = denominator.trim().parse().map_err(|_| ERROR)?;
+    if numerator == 0 || denominator == 0 {
+        return Err(ERROR);
+    }
+    let quantization = ControlQuantization::Fixed {
+        grid_size: numerator as f64 / denominator as f64,
+    };
+    Ok(quantization)
+}
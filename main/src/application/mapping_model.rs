@@ -1,23 +1,27 @@
 use crate::application::{
-    merge_affected, ActivationConditionCommand, ActivationConditionModel, ActivationConditionProp,
-    Affected, Change, ChangeResult, GetProcessingRelevance, MappingExtensionModel, ModeCommand,
-    ModeModel, ModeProp, ProcessingRelevance, SourceCommand, SourceModel, SourceProp,
-    TargetCategory, TargetCommand, TargetModel, TargetModelFormatVeryShort, TargetModelWithContext,
-    TargetProp,
+    find_duplicate_source, merge_affected, validate_categories, ActivationConditionCommand,
+    ActivationConditionModel, ActivationConditionProp, Affected, Change, ChangeResult,
+    GetProcessingRelevance, MappingExtensionModel, MappingIssue, MappingIssueCode,
+    MappingValidationContext, MessageTemplatesModel, ModeCommand, ModeModel, ModeProp,
+    ProcessingRelevance, SourceCommand, SourceModel, SourceProp, TargetCategory, TargetCommand,
+    TargetModel, TargetModelFormatVeryShort, TargetModelWithContext, TargetProp,
 };
 use crate::domain::{
     ActivationCondition, Compartment, CompoundMappingSource, CompoundMappingTarget,
-    EelTransformation, ExtendedProcessorContext, ExtendedSourceCharacter, FeedbackSendBehavior,
-    GroupId, MainMapping, MappingId, MappingKey, Mode, PersistentMappingProcessingState,
-    ProcessorMappingOptions, QualifiedMappingId, RealearnTarget, ReaperTarget, Script, Tag,
-    TargetCharacter, UnresolvedCompoundMappingTarget, VirtualFx, VirtualTrack,
+    ExtendedProcessorContext, ExtendedSourceCharacter, FeedbackSendBehavior, GroupId, MainMapping,
+    MappingId, MappingKey, Mode, PersistentMappingProcessingState, ProcessorMappingOptions,
+    QualifiedMappingId, RealearnTarget, ReaperTarget, Script, Tag, TargetCharacter,
+    UnresolvedCompoundMappingTarget, VirtualFx, VirtualTrack,
 };
 use helgoboss_learn::{
     AbsoluteMode, ControlType, DetailedSourceCharacter, DiscreteIncrement, Interval,
-    ModeApplicabilityCheckInput, ModeParameter, SourceCharacter, Target, UnitValue,
+    ModeApplicabilityCheckInput, ModeParameter, SourceCharacter, Target, UnitValue, VirtualColor,
 };
 
-use realearn_api::persistence::TrackScope;
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use realearn_api::persistence::{CrossfadeRole, FeedbackEffect, TrackScope};
 use std::cell::RefCell;
 use std::error::Error;
 use std::rc::Rc;
@@ -31,7 +35,11 @@ pub enum MappingCommand {
     SetFeedbackIsEnabled(bool),
     SetFeedbackSendBehavior(FeedbackSendBehavior),
     SetVisibleInProjection(bool),
+    SetFeedbackOnlyIfVisibleInProjection(bool),
     SetBeepOnSuccess(bool),
+    SetCrossfadeRole(Option<CrossfadeRole>),
+    SetFeedbackEffect(Option<FeedbackEffect>),
+    SetInvertFeedback(bool),
     ChangeActivationCondition(ActivationConditionCommand),
     ChangeSource(SourceCommand),
     ChangeMode(ModeCommand),
@@ -48,7 +56,11 @@ pub enum MappingProp {
     FeedbackIsEnabled,
     FeedbackSendBehavior,
     VisibleInProjection,
+    FeedbackOnlyIfVisibleInProjection,
     BeepOnSuccess,
+    CrossfadeRole,
+    FeedbackEffect,
+    InvertFeedback,
     AdvancedSettings,
     InActivationCondition(Affected<ActivationConditionProp>),
     InSource(Affected<SourceProp>),
@@ -66,8 +78,12 @@ impl GetProcessingRelevance for MappingProp {
             | P::FeedbackIsEnabled
             | P::FeedbackSendBehavior
             | P::VisibleInProjection
+            | P::FeedbackOnlyIfVisibleInProjection
             | P::AdvancedSettings
-            | P::BeepOnSuccess => Some(ProcessingRelevance::ProcessingRelevant),
+            | P::BeepOnSuccess
+            | P::CrossfadeRole
+            | P::FeedbackEffect
+            | P::InvertFeedback => Some(ProcessingRelevance::ProcessingRelevant),
             P::InActivationCondition(p) => p.processing_relevance(),
             P::InMode(p) => p.processing_relevance(),
             P::InSource(p) => p.processing_relevance(),
@@ -96,7 +112,11 @@ pub struct MappingModel {
     feedback_send_behavior: FeedbackSendBehavior,
     pub activation_condition_model: ActivationConditionModel,
     visible_in_projection: bool,
+    feedback_only_if_visible_in_projection: bool,
     beep_on_success: bool,
+    crossfade_role: Option<CrossfadeRole>,
+    feedback_effect: Option<FeedbackEffect>,
+    invert_feedback: bool,
     pub source_model: SourceModel,
     pub mode_model: ModeModel,
     pub target_model: TargetModel,
@@ -166,10 +186,26 @@ impl<'a> Change<'a> for MappingModel {
                 self.visible_in_projection = v;
                 One(P::VisibleInProjection)
             }
+            C::SetFeedbackOnlyIfVisibleInProjection(v) => {
+                self.feedback_only_if_visible_in_projection = v;
+                One(P::FeedbackOnlyIfVisibleInProjection)
+            }
             C::SetBeepOnSuccess(v) => {
                 self.beep_on_success = v;
                 One(P::BeepOnSuccess)
             }
+            C::SetCrossfadeRole(v) => {
+                self.crossfade_role = v;
+                One(P::CrossfadeRole)
+            }
+            C::SetFeedbackEffect(v) => {
+                self.feedback_effect = v;
+                One(P::FeedbackEffect)
+            }
+            C::SetInvertFeedback(v) => {
+                self.invert_feedback = v;
+                One(P::InvertFeedback)
+            }
             C::ChangeActivationCondition(cmd) => {
                 return self
                     .activation_condition_model
@@ -219,7 +255,11 @@ impl MappingModel {
             feedback_send_behavior: Default::default(),
             activation_condition_model: Default::default(),
             visible_in_projection: true,
+            feedback_only_if_visible_in_projection: false,
             beep_on_success: false,
+            crossfade_role: None,
+            feedback_effect: None,
+            invert_feedback: false,
             source_model: SourceModel::new(),
             mode_model: Default::default(),
             target_model: TargetModel::default_for_compartment(compartment),
@@ -260,10 +300,36 @@ impl MappingModel {
         self.visible_in_projection
     }
 
+    pub fn feedback_only_if_visible_in_projection(&self) -> bool {
+        self.feedback_only_if_visible_in_projection
+    }
+
+    pub fn projection_visibility(&self) -> ProjectionVisibility {
+        if !self.visible_in_projection {
+            ProjectionVisibility::Hidden
+        } else if self.feedback_only_if_visible_in_projection {
+            ProjectionVisibility::ShownWithFeedbackOnlyIfShown
+        } else {
+            ProjectionVisibility::Shown
+        }
+    }
+
     pub fn beep_on_success(&self) -> bool {
         self.beep_on_success
     }
 
+    pub fn crossfade_role(&self) -> Option<CrossfadeRole> {
+        self.crossfade_role
+    }
+
+    pub fn feedback_effect(&self) -> Option<FeedbackEffect> {
+        self.feedback_effect
+    }
+
+    pub fn invert_feedback(&self) -> bool {
+        self.invert_feedback
+    }
+
     pub fn activation_condition_model(&self) -> &ActivationConditionModel {
         &self.activation_condition_model
     }
@@ -375,12 +441,18 @@ impl MappingModel {
 
     fn update_extension_model_from_advanced_settings(&mut self) -> Result<(), String> {
         // Immediately update extension model
-        let extension_model = if let Some(yaml_mapping) = self.advanced_settings() {
-            serde_yaml::from_value(serde_yaml::Value::Mapping(yaml_mapping.clone()))
-                .map_err(|e| e.to_string())?
-        } else {
-            Default::default()
-        };
+        let extension_model: MappingExtensionModel =
+            if let Some(yaml_mapping) = self.advanced_settings() {
+                serde_yaml::from_value(serde_yaml::Value::Mapping(yaml_mapping.clone()))
+                    .map_err(|e| e.to_string())?
+            } else {
+                Default::default()
+            };
+        // Reject invalid scheduling right away instead of silently ignoring it later
+        extension_model
+            .on_control
+            .create_control_scheduling()
+            .map_err(|e| e.to_string())?;
         self.extension_model = extension_model;
         Ok(())
     }
@@ -397,6 +469,37 @@ impl MappingModel {
         self.compartment
     }
 
+    /// Checks this mapping for problems.
+    ///
+    /// Which checks are done depends on the given context. Used for showing warnings in the UI
+    /// and for reporting problems via the HTTP API.
+    pub fn validate(&self, context: MappingValidationContext) -> Vec<MappingIssue> {
+        let mut issues = vec![];
+        validate_categories(
+            self.compartment,
+            self.source_model.category(),
+            self.target_model.category(),
+            &mut issues,
+        );
+        if let Some(group_ids) = context.group_ids {
+            if !self.group_id.is_default() && !group_ids.contains(&self.group_id) {
+                issues.push(MappingIssue::error(
+                    MappingIssueCode::GroupNotFound,
+                    "Refers to a group which doesn't exist",
+                ));
+            }
+        }
+        if let Some(compartment_mappings) = context.compartment_mappings {
+            issues.extend(find_duplicate_source(self, compartment_mappings));
+        }
+        if let Some(instance_state) = context.instance_state {
+            if let Some(o) = instance_state.missing_target_object(self.qualified_id()) {
+                issues.push(MappingIssue::missing_target_object(o));
+            }
+        }
+        issues
+    }
+
     pub fn with_context<'a>(
         &'a self,
         context: ExtendedProcessorContext<'a>,
@@ -480,26 +583,8 @@ impl MappingModel {
     }
 
     pub fn base_mode_applicability_check_input(&self) -> ModeApplicabilityCheckInput {
-        ModeApplicabilityCheckInput {
-            target_is_virtual: self.target_model.is_virtual(),
-            // TODO-high-discrete Enable (also taking source into consideration!)
-            target_supports_discrete_values: false,
-            control_transformation_uses_time: {
-                let transformation = EelTransformation::compile_for_control(
-                    self.mode_model.eel_control_transformation(),
-                );
-                transformation.map(|t| t.uses_time()).unwrap_or(false)
-            },
-            is_feedback: false,
-            make_absolute: self.mode_model.make_absolute(),
-            use_textual_feedback: self.mode_model.feedback_type().is_textual(),
-            // Any is okay, will be overwritten.
-            source_character: DetailedSourceCharacter::RangeControl,
-            absolute_mode: self.mode_model.absolute_mode(),
-            // Any is okay, will be overwritten.
-            mode_parameter: ModeParameter::TargetMinMax,
-            target_value_sequence_is_set: !self.mode_model.target_value_sequence().is_empty(),
-        }
+        self.mode_model
+            .base_applicability_check_input(self.target_model.is_virtual())
     }
 
     pub fn control_is_enabled_and_supported(&self) -> bool {
@@ -529,15 +614,32 @@ impl MappingModel {
         )
     }
 
-    fn create_source(&self) -> CompoundMappingSource {
-        self.source_model.create_source()
+    /// Returns those glue parameters which have no effect with the current source and target.
+    ///
+    /// Their controls are hidden in the mapping panel, which is why it's worth telling the user
+    /// about them.
+    pub fn irrelevant_mode_parameters(&self) -> Vec<ModeParameter> {
+        let possible_source_characters = self.source_model.possible_detailed_characters();
+        let base_input = self.base_mode_applicability_check_input();
+        EXPLAINED_MODE_PARAMETERS
+            .into_iter()
+            .filter(|p| {
+                !self.mode_parameter_is_relevant(*p, base_input, &possible_source_characters)
+            })
+            .collect()
     }
 
-    fn create_mode(&self) -> Mode {
+    fn create_source(&self, message_templates: &MessageTemplatesModel) -> CompoundMappingSource {
+        self.source_model
+            .create_source_with_templates(message_templates)
+    }
+
+    fn create_mode(&self, group_feedback_color: Option<&VirtualColor>) -> Mode {
         let possible_source_characters = self.source_model.possible_detailed_characters();
         self.mode_model.create_mode(
             self.base_mode_applicability_check_input(),
             &possible_source_characters,
+            group_feedback_color,
         )
     }
 
@@ -553,10 +655,15 @@ impl MappingModel {
 
     /// Creates an intermediate mapping for splintering into very dedicated mapping types that are
     /// then going to be distributed to real-time and main processor.
-    pub fn create_main_mapping(&self, group_data: GroupData) -> MainMapping {
+    pub fn create_main_mapping(
+        &self,
+        group_data: GroupData,
+        message_templates: &MessageTemplatesModel,
+    ) -> MainMapping {
         let id = self.id;
-        let source = self.create_source();
-        let mode = self.create_mode();
+        let source = self.create_source(message_templates);
+        let mode = self.create_mode(group_data.feedback_color.as_ref());
+        let tempo_synced_turbo = self.mode_model.create_tempo_synced_turbo(&mode);
         let unresolved_target = self.create_target();
         let activation_condition = self
             .activation_condition_model
@@ -569,6 +676,11 @@ impl MappingModel {
             feedback_is_enabled: group_data.feedback_is_enabled && self.feedback_is_enabled(),
             feedback_send_behavior: self.feedback_send_behavior(),
             beep_on_success: self.beep_on_success,
+            crossfade_role: self.crossfade_role,
+            feedback_effect: self.feedback_effect,
+            invert_feedback: self.invert_feedback,
+            visible_in_projection: self.visible_in_projection,
+            feedback_only_if_visible_in_projection: self.feedback_only_if_visible_in_projection,
         };
         let mut merged_tags = group_data.tags;
         merged_tags.extend_from_slice(&self.tags);
@@ -580,8 +692,13 @@ impl MappingModel {
             self.name.clone(),
             merged_tags,
             source,
+            self.source_model.create_note_feedback_options(),
+            self.source_model.create_debounce_settings(),
+            self.source_model.create_pitch_bend_settings(),
             mode,
             self.mode_model.group_interaction(),
+            self.mode_model.acceleration_curve(),
+            tempo_synced_turbo,
             unresolved_target,
             group_data.activation_condition,
             activation_condition,
@@ -593,11 +710,54 @@ impl MappingModel {
     }
 }
 
+/// Glue parameters which have a dedicated control in the mapping panel.
+const EXPLAINED_MODE_PARAMETERS: [ModeParameter; 13] = [
+    ModeParameter::SourceMinMax,
+    ModeParameter::Reverse,
+    ModeParameter::TargetMinMax,
+    ModeParameter::TargetValueSequence,
+    ModeParameter::OutOfRangeBehavior,
+    ModeParameter::TakeoverMode,
+    ModeParameter::ControlTransformation,
+    ModeParameter::StepSizeMin,
+    ModeParameter::StepFactorMin,
+    ModeParameter::RelativeFilter,
+    ModeParameter::Rotate,
+    ModeParameter::MakeAbsolute,
+    ModeParameter::FireMode,
+];
+
+/// Combines the projection-related mapping settings for choosing them in one place.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Debug, IntoEnumIterator, TryFromPrimitive, IntoPrimitive, Display,
+)]
+#[repr(usize)]
+pub enum ProjectionVisibility {
+    #[display(fmt = "Hide in projection")]
+    Hidden,
+    #[display(fmt = "Show in projection")]
+    Shown,
+    /// Feedback is only sent while the mapping is shown in the projection.
+    #[display(fmt = "Show + feedback only if shown")]
+    ShownWithFeedbackOnlyIfShown,
+}
+
+impl ProjectionVisibility {
+    pub fn is_visible(self) -> bool {
+        self != Self::Hidden
+    }
+
+    pub fn restricts_feedback(self) -> bool {
+        self == Self::ShownWithFeedbackOnlyIfShown
+    }
+}
+
 pub struct GroupData {
     pub control_is_enabled: bool,
     pub feedback_is_enabled: bool,
     pub activation_condition: ActivationCondition,
     pub tags: Vec<Tag>,
+    pub feedback_color: Option<VirtualColor>,
 }
 
 impl Default for GroupData {
@@ -607,6 +767,7 @@ impl Default for GroupData {
             feedback_is_enabled: true,
             activation_condition: ActivationCondition::Always,
             tags: vec![],
+            feedback_color: None,
         }
     }
 }
@@ -717,7 +878,7 @@ impl<'a> MappingModelWithContext<'a> {
     /// If this returns `true`, the Speed sliders will be shown, allowing relative
     /// increments/decrements to be throttled or multiplied.
     pub fn uses_step_factors(&self) -> bool {
-        let mode = self.mapping.create_mode();
+        let mode = self.mapping.create_mode(None);
         if mode.settings().make_absolute {
             // If we convert increments to absolute values, we want step sizes of course.
             return false;
@@ -0,0 +1,205 @@
+use crate::application::{MappingModel, SharedMapping, SourceCategory, TargetCategory};
+use crate::domain::{
+    Compartment, CompoundMappingSource, GroupId, InstanceState, MissingTargetObject,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// A problem with a mapping, as detected by
+/// [`MappingModel::validate`](crate::application::MappingModel::validate).
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingIssue {
+    pub severity: IssueSeverity,
+    pub code: MappingIssueCode,
+    pub message: String,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueSeverity {
+    /// The mapping (or document) can't be loaded or parts of it won't work.
+    Error,
+    /// The mapping works but is probably not what the author intended.
+    Warning,
+}
+
+/// Machine-readable kind of a mapping issue.
+///
+/// IMPORTANT: Don't rename the variants! They are part of the HTTP API.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MappingIssueCode {
+    SourceCategoryNotAllowed,
+    TargetCategoryNotAllowed,
+    GroupNotFound,
+    DuplicateSource,
+    TargetTrackMissing,
+    TargetFxMissing,
+}
+
+impl MappingIssueCode {
+    /// Short label suitable for being shown next to the mapping.
+    pub fn label(self) -> &'static str {
+        use MappingIssueCode::*;
+        match self {
+            SourceCategoryNotAllowed => "source not allowed",
+            TargetCategoryNotAllowed => "target not allowed",
+            GroupNotFound => "group missing",
+            DuplicateSource => "duplicate source",
+            TargetTrackMissing => "track missing",
+            TargetFxMissing => "FX missing",
+        }
+    }
+
+    pub fn concerns_source(self) -> bool {
+        use MappingIssueCode::*;
+        matches!(self, SourceCategoryNotAllowed | DuplicateSource)
+    }
+
+    pub fn concerns_target(self) -> bool {
+        use MappingIssueCode::*;
+        matches!(
+            self,
+            TargetCategoryNotAllowed | TargetTrackMissing | TargetFxMissing
+        )
+    }
+}
+
+/// Determines which checks are done when validating a mapping.
+#[derive(Copy, Clone, Default)]
+pub struct MappingValidationContext<'a> {
+    /// If set, it's checked whether the processing layer found the target's track or FX missing.
+    pub instance_state: Option<&'a InstanceState>,
+    /// If set, it's checked whether the mapping's group exists.
+    pub group_ids: Option<&'a HashSet<GroupId>>,
+    /// All mappings of the compartment, in list order. If set, it's checked whether a preceding
+    /// mapping has the same source.
+    pub compartment_mappings: Option<&'a [SharedMapping]>,
+}
+
+impl MappingIssue {
+    pub fn error(code: MappingIssueCode, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(code: MappingIssueCode, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn missing_target_object(missing_object: MissingTargetObject) -> Self {
+        let code = match missing_object {
+            MissingTargetObject::Track => MappingIssueCode::TargetTrackMissing,
+            MissingTargetObject::Fx => MappingIssueCode::TargetFxMissing,
+        };
+        Self::warning(
+            code,
+            format!("Target {} doesn't exist anymore", missing_object),
+        )
+    }
+}
+
+/// Returns an issue if a mapping which precedes the given one in the list has the same source,
+/// group and activation condition, which means that both react to the same events.
+pub(crate) fn find_duplicate_source(
+    mapping: &MappingModel,
+    compartment_mappings: &[SharedMapping],
+) -> Option<MappingIssue> {
+    let source = mapping.source_model.create_source();
+    if source == CompoundMappingSource::Never {
+        return None;
+    }
+    let duplicate = compartment_mappings
+        .iter()
+        .map_while(|m| {
+            // The mapping itself might be borrowed mutably. Then we can't see it anyway.
+            let m = m.try_borrow().ok()?;
+            if m.id() == mapping.id() {
+                return None;
+            }
+            let is_duplicate = m.group_id() == mapping.group_id()
+                && m.activation_condition_model == mapping.activation_condition_model
+                && m.source_model.create_source() == source;
+            Some(is_duplicate.then(|| describe_mapping(&m)))
+        })
+        .flatten()
+        .next()?;
+    let issue = MappingIssue::warning(
+        MappingIssueCode::DuplicateSource,
+        format!(
+            "Has the same source and activation condition as {}",
+            duplicate
+        ),
+    );
+    Some(issue)
+}
+
+fn describe_mapping(mapping: &MappingModel) -> String {
+    if mapping.name().is_empty() {
+        format!("\"{}\"", mapping.key())
+    } else {
+        format!("\"{}\"", mapping.name())
+    }
+}
+
+/// Checks whether source and target category are supported in the given compartment.
+pub(crate) fn validate_categories(
+    compartment: Compartment,
+    source_category: SourceCategory,
+    target_category: TargetCategory,
+    issues: &mut Vec<MappingIssue>,
+) {
+    if !source_category.is_allowed_in(compartment) {
+        issues.push(MappingIssue::error(
+            MappingIssueCode::SourceCategoryNotAllowed,
+            format!(
+                "Source category \"{}\" is not supported in the {} compartment",
+                source_category, compartment
+            ),
+        ));
+    }
+    if !target_category.is_allowed_in(compartment) {
+        issues.push(MappingIssue::error(
+            MappingIssueCode::TargetCategoryNotAllowed,
+            format!(
+                "Target category \"{}\" is not supported in the {} compartment",
+                target_category, compartment
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_categories() {
+        // Given
+        let mut issues = vec![];
+        // When
+        validate_categories(
+            Compartment::Main,
+            SourceCategory::Virtual,
+            TargetCategory::Virtual,
+            &mut issues,
+        );
+        validate_categories(
+            Compartment::Controller,
+            SourceCategory::Midi,
+            TargetCategory::Virtual,
+            &mut issues,
+        );
+        // Then
+        let codes: Vec<_> = issues.iter().map(|i| i.code).collect();
+        assert_eq!(codes, vec![MappingIssueCode::TargetCategoryNotAllowed]);
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Raw MIDI pattern which is inserted for the `{val}` placeholder (7-bit value).
+const RAW_MIDI_VALUE_PATTERN: &str = "[0gfe dcba]";
+
+/// Controller-specific MIDI/OSC message templates, keyed by template name.
+///
+/// Instead of spelling out a raw MIDI pattern or OSC address pattern, a source can refer to a
+/// template and pass arguments to it, e.g. `@led_ring ch=3`. Given the template
+/// `B0 3{ch} {val}`, this results in the raw MIDI pattern `B0 33 [0gfe dcba]`. This makes it
+/// possible to support exotic controllers just by adjusting the controller preset.
+///
+/// At the moment, message templates are only used in the controller compartment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MessageTemplatesModel {
+    templates: BTreeMap<String, String>,
+}
+
+impl MessageTemplatesModel {
+    pub fn new(templates: BTreeMap<String, String>) -> Self {
+        Self { templates }
+    }
+
+    pub fn into_templates(self) -> BTreeMap<String, String> {
+        self.templates
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Checks that all template names and templates are well-formed.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        for (name, template) in &self.templates {
+            if !is_valid_identifier(name) {
+                return Err("template names may only contain letters, digits and underscores");
+            }
+            parse_template(template)?;
+        }
+        Ok(())
+    }
+
+    /// Resolves the given raw MIDI pattern if it refers to a template.
+    ///
+    /// The placeholder `{val}` stands for the 7-bit feedback/control value.
+    pub fn expand_raw_midi_pattern<'a>(&self, text: &'a str) -> Result<Cow<'a, str>, &'static str> {
+        self.expand(text, Some(RAW_MIDI_VALUE_PATTERN))
+    }
+
+    /// Resolves the given OSC address pattern if it refers to a template.
+    pub fn expand_osc_address_pattern<'a>(
+        &self,
+        text: &'a str,
+    ) -> Result<Cow<'a, str>, &'static str> {
+        self.expand(text, None)
+    }
+
+    fn expand<'a>(
+        &self,
+        text: &'a str,
+        value_replacement: Option<&str>,
+    ) -> Result<Cow<'a, str>, &'static str> {
+        let reference = match parse_reference(text) {
+            None => return Ok(Cow::Borrowed(text)),
+            Some(r) => r?,
+        };
+        let template = self
+            .templates
+            .get(reference.name)
+            .ok_or("message template doesn't exist")?;
+        let mut used_arg_names = HashSet::new();
+        let mut result = String::with_capacity(template.len());
+        for segment in parse_template(template)? {
+            match segment {
+                TemplateSegment::Literal(s) => result.push_str(s),
+                TemplateSegment::Placeholder("val") => {
+                    let replacement = value_replacement
+                        .ok_or("{val} is not supported in this kind of template")?;
+                    result.push_str(replacement);
+                }
+                TemplateSegment::Placeholder(name) => {
+                    let arg = reference
+                        .args
+                        .get(name)
+                        .ok_or("message template argument missing")?;
+                    result.push_str(arg);
+                    used_arg_names.insert(name);
+                }
+            }
+        }
+        if reference.args.keys().any(|k| !used_arg_names.contains(k)) {
+            return Err("unknown message template argument");
+        }
+        Ok(Cow::Owned(result))
+    }
+}
+
+/// Reference to a message template, e.g. `@led_ring ch=3`.
+#[derive(Eq, PartialEq, Debug)]
+struct TemplateReference<'a> {
+    name: &'a str,
+    args: HashMap<&'a str, &'a str>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum TemplateSegment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Returns `None` if the given text doesn't refer to a template.
+fn parse_reference(text: &str) -> Option<Result<TemplateReference, &'static str>> {
+    let text = text.trim().strip_prefix('@')?;
+    Some(parse_reference_without_prefix(text))
+}
+
+fn parse_reference_without_prefix(text: &str) -> Result<TemplateReference, &'static str> {
+    let mut tokens = text.split_whitespace();
+    let name = tokens.next().ok_or("message template name missing")?;
+    let args = tokens
+        .map(|token| {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or("message template arguments must have the form name=value")?;
+            if !is_valid_identifier(key) {
+                return Err("invalid message template argument name");
+            }
+            Ok((key, value))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(TemplateReference { name, args })
+}
+
+fn parse_template(template: &str) -> Result<Vec<TemplateSegment>, &'static str> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while !rest.is_empty() {
+        match rest.find(|c: char| c == '{' || c == '}') {
+            None => {
+                segments.push(TemplateSegment::Literal(rest));
+                break;
+            }
+            Some(i) => {
+                if rest[i..].starts_with('}') {
+                    return Err("unexpected } in message template");
+                }
+                if i > 0 {
+                    segments.push(TemplateSegment::Literal(&rest[..i]));
+                }
+                let after_open = &rest[i + 1..];
+                let close = after_open
+                    .find('}')
+                    .ok_or("unclosed placeholder in message template")?;
+                let name = &after_open[..close];
+                if !is_valid_identifier(name) {
+                    return Err("invalid placeholder name in message template");
+                }
+                segments.push(TemplateSegment::Placeholder(name));
+                rest = &after_open[close + 1..];
+            }
+        }
+    }
+    Ok(segments)
+}
+
+fn is_valid_identifier(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templates(entries: &[(&str, &str)]) -> MessageTemplatesModel {
+        MessageTemplatesModel {
+            templates: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn expand_raw_midi_pattern() {
+        // Given
+        let templates = templates(&[("led_ring", "B0 3{ch} {val}")]);
+        // When
+        let plain = templates.expand_raw_midi_pattern("B0 07 [0gfe dcba]");
+        let expanded = templates.expand_raw_midi_pattern("@led_ring ch=3");
+        let missing_arg = templates.expand_raw_midi_pattern("@led_ring");
+        let unknown_arg = templates.expand_raw_midi_pattern("@led_ring ch=3 foo=1");
+        // Then
+        assert_eq!(plain.unwrap(), "B0 07 [0gfe dcba]");
+        assert_eq!(expanded.unwrap(), "B0 33 [0gfe dcba]");
+        assert!(missing_arg.is_err());
+        assert!(unknown_arg.is_err());
+    }
+
+    #[test]
+    fn expand_osc_address_pattern() {
+        // Given
+        let templates = templates(&[("volume", "/track/{ch}/volume"), ("bad", "/x/{val}")]);
+        // When
+        let expanded = templates.expand_osc_address_pattern("@volume ch=5");
+        let with_value = templates.expand_osc_address_pattern("@bad");
+        // Then
+        assert_eq!(expanded.unwrap(), "/track/5/volume");
+        assert!(with_value.is_err());
+    }
+
+    #[test]
+    fn validate() {
+        // Given
+        let valid = templates(&[("a", "F0 {x} {x} {val} F7")]);
+        let unclosed = templates(&[("a", "B0 {ch")]);
+        let stray_brace = templates(&[("a", "B0 ch} 7F")]);
+        let bad_name = templates(&[("a b", "B0 00 7F")]);
+        // When
+        // Then
+        assert!(valid.validate().is_ok());
+        assert!(unclosed.validate().is_err());
+        assert!(stray_brace.validate().is_err());
+        assert!(bad_name.validate().is_err());
+    }
+}
@@ -10,9 +10,18 @@ pub use mode_model::*;
 mod target_model;
 pub use target_model::*;
 
+mod target_remapping;
+pub use target_remapping::*;
+
+mod message_template;
+pub use message_template::*;
+
 mod mapping_model;
 pub use mapping_model::*;
 
+mod mapping_validation;
+pub use mapping_validation::*;
+
 mod group_model;
 pub use group_model::*;
 
@@ -45,3 +54,6 @@ pub use compartment_model::*;
 
 mod props;
 pub use props::*;
+
+mod window_layout;
+pub use window_layout::*;
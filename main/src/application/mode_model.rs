@@ -1,4 +1,7 @@
-use crate::domain::{EelTransformation, Mode};
+use crate::domain::{
+    build_control_curve_eel, build_feedback_curve_eel, ControlEventTimestamp, EelTransformation,
+    Mode, TempoSyncedTurbo,
+};
 
 use helgoboss_learn::{
     check_mode_applicability, create_unit_value_interval, full_discrete_interval,
@@ -9,7 +12,11 @@ use helgoboss_learn::{
 };
 
 use crate::application::{Affected, Change, GetProcessingRelevance, ProcessingRelevance};
-use realearn_api::persistence::FeedbackValueTable;
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use realearn_api::persistence::{AccelerationCurve, ControlCurve, FeedbackValueTable, NoteLength};
+use realearn_api_convert::convert_feedback_value_table;
 use std::time::Duration;
 
 pub enum ModeCommand {
@@ -25,6 +32,7 @@ pub enum ModeCommand {
     SetMinPressDuration(Duration),
     SetMaxPressDuration(Duration),
     SetTurboRate(Duration),
+    SetSyncedTurboRate(Option<NoteLength>),
     SetLegacyJumpInterval(Option<Interval<UnitValue>>),
     SetOutOfRangeBehavior(OutOfRangeBehavior),
     SetFireMode(FireMode),
@@ -42,6 +50,8 @@ pub enum ModeCommand {
     SetMaxStepFactor(DiscreteIncrement),
     SetRotate(bool),
     SetMakeAbsolute(bool),
+    SetAccelerationCurve(AccelerationCurve),
+    SetControlCurve(ControlCurve),
     SetGroupInteraction(GroupInteraction),
     SetTargetValueSequence(ValueSequence),
     SetFeedbackType(FeedbackType),
@@ -49,6 +59,8 @@ pub enum ModeCommand {
     SetFeedbackColor(Option<VirtualColor>),
     SetFeedbackBackgroundColor(Option<VirtualColor>),
     SetFeedbackValueTable(Option<FeedbackValueTable>),
+    /// Adjusts all the settings which are necessary to achieve the given button behavior.
+    SetButtonBehavior(ButtonBehavior),
     /// This doesn't reset the mode type, just all the values.
     ResetWithinType,
 }
@@ -61,6 +73,7 @@ pub enum ModeProp {
     Reverse,
     PressDurationInterval,
     TurboRate,
+    SyncedTurboRate,
     LegacyJumpInterval,
     OutOfRangeBehavior,
     FireMode,
@@ -74,6 +87,8 @@ pub enum ModeProp {
     StepFactorInterval,
     Rotate,
     MakeAbsolute,
+    AccelerationCurve,
+    ControlCurve,
     GroupInteraction,
     TargetValueSequence,
     FeedbackType,
@@ -99,6 +114,8 @@ pub struct ModeModel {
     reverse: bool,
     press_duration_interval: Interval<Duration>,
     turbo_rate: Duration,
+    /// If set, the turbo rate follows the project tempo and `turbo_rate` is ignored.
+    synced_turbo_rate: Option<NoteLength>,
     /// Since 2.14.0-pre.10, this should be `None` for all new mappings.
     ///
     /// In this case, a dynamic jump interval will be used.
@@ -131,6 +148,10 @@ pub struct ModeModel {
     step_factor_interval: Interval<DiscreteIncrement>,
     rotate: bool,
     make_absolute: bool,
+    /// Only relevant if `make_absolute` is enabled.
+    acceleration_curve: AccelerationCurve,
+    /// Only used if the EEL control transformation is empty.
+    control_curve: ControlCurve,
     group_interaction: GroupInteraction,
     target_value_sequence: ValueSequence,
     feedback_type: FeedbackType,
@@ -152,6 +173,7 @@ impl Default for ModeModel {
                 Duration::from_millis(0),
             ),
             turbo_rate: Duration::from_millis(0),
+            synced_turbo_rate: None,
             legacy_jump_interval: None,
             out_of_range_behavior: Default::default(),
             fire_mode: Default::default(),
@@ -165,6 +187,8 @@ impl Default for ModeModel {
             step_factor_interval: Self::default_step_factor_interval(),
             rotate: false,
             make_absolute: false,
+            acceleration_curve: Default::default(),
+            control_curve: Default::default(),
             group_interaction: Default::default(),
             target_value_sequence: Default::default(),
             feedback_type: Default::default(),
@@ -239,6 +263,10 @@ impl<'a> Change<'a> for ModeModel {
                 self.turbo_rate = v;
                 One(P::TurboRate)
             }
+            C::SetSyncedTurboRate(v) => {
+                self.synced_turbo_rate = v;
+                One(P::SyncedTurboRate)
+            }
             C::SetLegacyJumpInterval(v) => {
                 self.legacy_jump_interval = v;
                 One(P::LegacyJumpInterval)
@@ -307,6 +335,14 @@ impl<'a> Change<'a> for ModeModel {
                 self.make_absolute = v;
                 One(P::MakeAbsolute)
             }
+            C::SetAccelerationCurve(v) => {
+                self.acceleration_curve = v;
+                One(P::AccelerationCurve)
+            }
+            C::SetControlCurve(v) => {
+                self.control_curve = v;
+                One(P::ControlCurve)
+            }
             C::SetGroupInteraction(v) => {
                 self.group_interaction = v;
                 One(P::GroupInteraction)
@@ -335,6 +371,29 @@ impl<'a> Change<'a> for ModeModel {
                 self.feedback_value_table = v;
                 One(P::FeedbackValueTable)
             }
+            C::SetButtonBehavior(v) => {
+                let defaults = Self::default();
+                let (absolute_mode, button_usage, reverse) = match v {
+                    ButtonBehavior::Momentary => (AbsoluteMode::Normal, ButtonUsage::Both, false),
+                    ButtonBehavior::Toggle => {
+                        (AbsoluteMode::ToggleButton, ButtonUsage::Both, false)
+                    }
+                    ButtonBehavior::LatchOn => {
+                        (AbsoluteMode::Normal, ButtonUsage::PressOnly, false)
+                    }
+                    ButtonBehavior::LatchOff => {
+                        (AbsoluteMode::Normal, ButtonUsage::PressOnly, true)
+                    }
+                };
+                self.absolute_mode = absolute_mode;
+                self.button_usage = button_usage;
+                self.reverse = reverse;
+                self.fire_mode = defaults.fire_mode;
+                self.press_duration_interval = defaults.press_duration_interval;
+                self.turbo_rate = defaults.turbo_rate;
+                self.synced_turbo_rate = defaults.synced_turbo_rate;
+                Multiple
+            }
             C::ResetWithinType => {
                 *self = Default::default();
                 Multiple
@@ -387,6 +446,10 @@ impl ModeModel {
         self.turbo_rate
     }
 
+    pub fn synced_turbo_rate(&self) -> Option<NoteLength> {
+        self.synced_turbo_rate
+    }
+
     pub fn legacy_jump_interval(&self) -> Option<Interval<UnitValue>> {
         self.legacy_jump_interval
     }
@@ -411,6 +474,29 @@ impl ModeModel {
         self.button_usage
     }
 
+    /// Returns the button behavior which corresponds to the current settings or `None` if the
+    /// settings are more specific than that.
+    pub fn button_behavior(&self) -> Option<ButtonBehavior> {
+        let defaults = Self::default();
+        if self.fire_mode != defaults.fire_mode
+            || self.press_duration_interval != defaults.press_duration_interval
+            || self.turbo_rate != defaults.turbo_rate
+            || self.synced_turbo_rate != defaults.synced_turbo_rate
+        {
+            return None;
+        }
+        use AbsoluteMode::*;
+        use ButtonUsage::*;
+        let behavior = match (self.absolute_mode, self.button_usage, self.reverse) {
+            (Normal, Both, false) => ButtonBehavior::Momentary,
+            (ToggleButton, Both, false) => ButtonBehavior::Toggle,
+            (Normal, PressOnly, false) => ButtonBehavior::LatchOn,
+            (Normal, PressOnly, true) => ButtonBehavior::LatchOff,
+            _ => return None,
+        };
+        Some(behavior)
+    }
+
     pub fn encoder_usage(&self) -> EncoderUsage {
         self.encoder_usage
     }
@@ -439,6 +525,14 @@ impl ModeModel {
         self.make_absolute
     }
 
+    pub fn acceleration_curve(&self) -> AccelerationCurve {
+        self.acceleration_curve
+    }
+
+    pub fn control_curve(&self) -> ControlCurve {
+        self.control_curve
+    }
+
     pub fn group_interaction(&self) -> GroupInteraction {
         self.group_interaction
     }
@@ -486,12 +580,98 @@ impl ModeModel {
         })
     }
 
-    /// Creates a mode reflecting this model's current values
+    /// Returns the fire mode which is actually used by the mode.
+    ///
+    /// With a tempo-synced turbo rate, the mode only takes care of firing after the timeout. The
+    /// repetitions are done by the mapping (see [`Self::create_tempo_synced_turbo`]).
+    fn effective_fire_mode(&self) -> FireMode {
+        match (self.fire_mode, self.synced_turbo_rate) {
+            (FireMode::AfterTimeoutKeepFiring, Some(_)) => FireMode::AfterTimeout,
+            (fire_mode, _) => fire_mode,
+        }
+    }
+
+    /// Creates the tempo-synced turbo for the given mode (created by [`Self::create_mode`]).
+    ///
+    /// Returns `None` if the turbo rate is not synced or the fire mode is not relevant.
+    pub fn create_tempo_synced_turbo(
+        &self,
+        mode: &Mode,
+    ) -> Option<TempoSyncedTurbo<ControlEventTimestamp>> {
+        let note_length = self.synced_turbo_rate?;
+        if self.fire_mode != FireMode::AfterTimeoutKeepFiring
+            || mode.settings().fire_mode != FireMode::AfterTimeout
+        {
+            return None;
+        }
+        Some(TempoSyncedTurbo::new(
+            self.press_duration_interval.min_val(),
+            note_length,
+        ))
+    }
+
+    /// Returns the EEL control transformation which is actually used.
+    ///
+    /// A hand-written transformation takes precedence over the control curve.
+    fn effective_eel_control_transformation(&self) -> Option<String> {
+        if self.eel_control_transformation.trim().is_empty() {
+            build_control_curve_eel(self.control_curve)
+        } else {
+            Some(self.eel_control_transformation.clone())
+        }
+    }
+
+    /// Returns the EEL feedback transformation which is actually used.
+    ///
+    /// If there's no hand-written feedback transformation, the control curve is inverted (but only
+    /// if the control curve is effective at all).
+    fn effective_eel_feedback_transformation(&self) -> Option<String> {
+        if !self.eel_feedback_transformation.trim().is_empty() {
+            Some(self.eel_feedback_transformation.clone())
+        } else if self.eel_control_transformation.trim().is_empty() {
+            build_feedback_curve_eel(self.control_curve)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the input for checking which mode parameters are relevant, without source
+    /// character and mode parameter (these are set by the consumer).
+    pub fn base_applicability_check_input(
+        &self,
+        target_is_virtual: bool,
+    ) -> ModeApplicabilityCheckInput {
+        ModeApplicabilityCheckInput {
+            target_is_virtual,
+            // TODO-high-discrete Enable (also taking source into consideration!)
+            target_supports_discrete_values: false,
+            control_transformation_uses_time: {
+                let transformation =
+                    EelTransformation::compile_for_control(&self.eel_control_transformation);
+                transformation.map(|t| t.uses_time()).unwrap_or(false)
+            },
+            is_feedback: false,
+            make_absolute: self.make_absolute,
+            use_textual_feedback: self.feedback_type.is_textual(),
+            // Any is okay, will be overwritten.
+            source_character: DetailedSourceCharacter::RangeControl,
+            absolute_mode: self.absolute_mode,
+            // Any is okay, will be overwritten.
+            mode_parameter: ModeParameter::TargetMinMax,
+            target_value_sequence_is_set: !self.target_value_sequence.is_empty(),
+        }
+    }
+
+    /// Creates a mode reflecting this model's current values.
+    ///
+    /// The fallback feedback color is used if this mode doesn't define a feedback color itself
+    /// (e.g. the color of the group).
     #[allow(clippy::if_same_then_else)]
     pub fn create_mode(
         &self,
         base_input: ModeApplicabilityCheckInput,
         possible_source_characters: &[DetailedSourceCharacter],
+        fallback_feedback_color: Option<&VirtualColor>,
     ) -> Mode {
         let is_relevant = |mode_parameter: ModeParameter| {
             // We take both control and feedback into account to not accidentally get slightly
@@ -566,7 +746,7 @@ impl ModeModel {
                 full_discrete_interval()
             },
             fire_mode: if is_relevant(ModeParameter::FireMode) {
-                self.fire_mode
+                self.effective_fire_mode()
             } else {
                 FireMode::default()
             },
@@ -608,23 +788,21 @@ impl ModeModel {
                 OutOfRangeBehavior::default()
             },
             control_transformation: if is_relevant(ModeParameter::ControlTransformation) {
-                EelTransformation::compile_for_control(&self.eel_control_transformation).ok()
+                self.effective_eel_control_transformation()
+                    .and_then(|code| EelTransformation::compile_for_control(&code).ok())
             } else {
                 None
             },
             feedback_transformation: if is_relevant(ModeParameter::FeedbackTransformation) {
-                EelTransformation::compile_for_feedback(&self.eel_feedback_transformation).ok()
+                self.effective_eel_feedback_transformation()
+                    .and_then(|code| EelTransformation::compile_for_feedback(&code).ok())
             } else {
                 None
             },
-            feedback_value_table: self.feedback_value_table.as_ref().map(|t| match t {
-                FeedbackValueTable::FromTextToDiscrete(v) => {
-                    helgoboss_learn::FeedbackValueTable::FromTextToDiscrete(v.value.clone())
-                }
-                FeedbackValueTable::FromTextToContinuous(v) => {
-                    helgoboss_learn::FeedbackValueTable::FromTextToContinuous(v.value.clone())
-                }
-            }),
+            feedback_value_table: self
+                .feedback_value_table
+                .clone()
+                .map(convert_feedback_value_table),
             make_absolute: if is_relevant(ModeParameter::MakeAbsolute) {
                 self.make_absolute
             } else {
@@ -643,7 +821,11 @@ impl ModeModel {
             } else {
                 String::new()
             },
-            feedback_color: self.feedback_color.clone(),
+            feedback_color: self
+                .feedback_color
+                .as_ref()
+                .or(fallback_feedback_color)
+                .cloned(),
             feedback_background_color: self.feedback_background_color.clone(),
         })
     }
@@ -652,3 +834,44 @@ impl ModeModel {
 fn default_jump_interval() -> Interval<UnitValue> {
     create_unit_value_interval(0.0, 0.03)
 }
+
+/// Simple way of choosing how a button controls the target, without having to combine absolute
+/// mode, fire mode, button filter and reverse manually.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Debug, IntoEnumIterator, TryFromPrimitive, IntoPrimitive, Display,
+)]
+#[repr(usize)]
+pub enum ButtonBehavior {
+    /// Target is on while the button is pressed and off when it's released.
+    #[display(fmt = "Momentary")]
+    Momentary,
+    /// Each press switches the target on or off.
+    #[display(fmt = "Toggle")]
+    Toggle,
+    /// Each press switches the target on, releasing does nothing.
+    #[display(fmt = "Latch on only")]
+    LatchOn,
+    /// Each press switches the target off, releasing does nothing.
+    #[display(fmt = "Latch off only")]
+    LatchOff,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_behavior_round_trip() {
+        for behavior in ButtonBehavior::into_enum_iter() {
+            // Given
+            let mut model = ModeModel::default();
+            model.change(ModeCommand::SetAbsoluteMode(
+                AbsoluteMode::IncrementalButton,
+            ));
+            // When
+            model.change(ModeCommand::SetButtonBehavior(behavior));
+            // Then
+            assert_eq!(model.button_behavior(), Some(behavior));
+        }
+    }
+}
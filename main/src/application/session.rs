@@ -1,9 +1,11 @@
 use crate::application::{
     share_group, share_mapping, Affected, Change, ChangeResult, CompartmentCommand,
-    CompartmentModel, CompartmentProp, ControllerPreset, FxId, FxPresetLinkConfig, GroupCommand,
-    GroupModel, MainPreset, MainPresetAutoLoadMode, MappingCommand, MappingModel, MappingProp,
-    Preset, PresetLinkManager, PresetManager, ProcessingRelevance, SharedGroup, SharedMapping,
-    SourceModel, TargetCategory, TargetModel, TargetProp, VirtualControlElementType,
+    CompartmentLifecycleModel, CompartmentModel, CompartmentProp, ControllerPreset, FxId,
+    FxPresetLinkConfig, GroupCommand, GroupModel, MainPreset, MainPresetAutoLoadMode,
+    MappingCommand, MappingModel, MappingProp, MessageTemplatesModel, Preset, PresetLinkManager,
+    PresetManager, PresetSubstitutions, ProcessingRelevance, SharedGroup, SharedMapping,
+    SourceCategory, SourceCommand, SourceModel, TargetCategory, TargetCommand, TargetModel,
+    TargetProp, VirtualControlElementType, WindowBounds, WindowLayout,
 };
 use crate::base::{
     prop, when, AsyncNotifier, Global, NamedChannelSender, Prop, SenderToNormalThread,
@@ -11,37 +13,45 @@ use crate::base::{
 };
 use crate::domain::{
     convert_plugin_param_index_range_to_iter, BackboneState, BasicSettings, Compartment,
-    CompartmentParamIndex, CompartmentParams, CompoundMappingSource, ControlContext, ControlInput,
-    DomainEvent, DomainEventHandler, ExtendedProcessorContext, FeedbackAudioHookTask,
-    FeedbackOutput, FeedbackRealTimeTask, FinalSourceFeedbackValue, GroupId, GroupKey,
-    IncomingCompoundSourceValue, InputDescriptor, InstanceContainer, InstanceId, InstanceState,
-    MainMapping, MappingId, MappingKey, MappingMatchedEvent, MessageCaptureEvent, MidiControlInput,
-    NormalMainTask, NormalRealTimeTask, OscFeedbackTask, ParamSetting, PluginParams,
-    ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId, RealearnClipMatrix,
-    RealearnTarget, ReaperTarget, SharedInstanceState, StayActiveWhenProjectInBackground, Tag,
-    TargetControlEvent, TargetValueChangedEvent, VirtualControlElementId, VirtualFx, VirtualSource,
-    VirtualSourceValue,
+    CompartmentParamIndex, CompartmentParams, CompoundMappingSource, ControlContext,
+    ControlElementTouchedEvent, ControlInput, DiagnosticEvent, DomainEvent, DomainEventHandler,
+    ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackOutput, FeedbackRealTimeTask,
+    FinalSourceFeedbackValue, FxParameterTarget, GroupId, GroupKey, IncomingCompoundSourceValue,
+    InputDescriptor, InstanceContainer, InstanceId, InstanceState, MainMapping, MappingId,
+    MappingKey, MappingMatchedEvent, MessageCaptureEvent, MidiControlInput, MidiPassThroughFilter,
+    NormalMainTask, NormalRealTimeTask, Notification, NotificationContext, OscFeedbackTask,
+    ParamSetting, PluginParams, ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId,
+    RealearnClipMatrix, RealearnTarget, ReaperTarget, SharedInstanceState,
+    StayActiveWhenProjectInBackground, Tag, TargetControlEvent, TargetValueChangedEvent,
+    VirtualControlElementId, VirtualControlElementSharing, VirtualFx, VirtualSource,
+    VirtualSourceValue, XyPadRole,
 };
 use derivative::Derivative;
+use derive_more::Display;
 use enum_map::EnumMap;
 
-use reaper_high::{ChangeEvent, Reaper};
+use reaper_high::{ChangeEvent, Fx, Reaper};
 use rx_util::Notifier;
 use rxrust::prelude::*;
-use slog::{debug, trace};
-use std::cell::{Ref, RefCell};
+use slog::{debug, trace, warn};
+use std::cell::{Cell, Ref, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::ops::Range;
 
 use crate::domain;
 use core::iter;
-use helgoboss_learn::{ControlResult, ControlValue, SourceContext, UnitValue};
+use helgoboss_learn::{ControlResult, ControlValue, OscTypeTag, SourceContext, UnitValue};
 use itertools::Itertools;
 use playtime_clip_engine::base::ClipMatrixEvent;
 use realearn_api::persistence::{FxDescriptor, TrackDescriptor};
 use reaper_medium::RecordingInput;
 use std::error::Error;
 use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+/// How long to wait after the last FX has been added before looking for moved FX.
+const FX_REFERENCE_FIX_DELAY: Duration = Duration::from_millis(500);
 
 pub trait SessionUi {
     fn show_mapping(&self, compartment: Compartment, mapping_id: MappingId);
@@ -51,6 +61,8 @@ pub trait SessionUi {
     fn celebrate_success(&self);
     fn conditions_changed(&self);
     fn send_projection_feedback(&self, session: &Session, value: ProjectionFeedbackValue);
+    fn send_control_element_touched(&self, session: &Session, event: ControlElementTouchedEvent);
+    fn send_diagnostic_event(&self, session: &Session, event: DiagnosticEvent);
     fn clip_matrix_changed(
         &self,
         session: &Session,
@@ -72,6 +84,29 @@ pub trait SessionUi {
         affected: Affected<SessionProp>,
         initiator: Option<u32>,
     );
+    fn session_event_occurred(&self, session: &Session, event: SessionEvent);
+}
+
+/// Noteworthy things happening in a session which might be interesting for external systems.
+#[derive(Clone, Debug)]
+pub enum SessionEvent {
+    PresetLoaded {
+        compartment: Compartment,
+        /// `None` if the <None> preset has been loaded.
+        preset_id: Option<String>,
+    },
+    MappingLearned {
+        mapping_id: QualifiedMappingId,
+        part: LearnedMappingPart,
+    },
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
+pub enum LearnedMappingPart {
+    #[display(fmt = "source")]
+    Source,
+    #[display(fmt = "target")]
+    Target,
 }
 
 pub trait ParamContainer {
@@ -92,6 +127,7 @@ pub struct Session {
     logger: slog::Logger,
     pub let_matched_events_through: Prop<bool>,
     pub let_unmatched_events_through: Prop<bool>,
+    pub midi_pass_through_filter: Prop<MidiPassThroughFilter>,
     pub stay_active_when_project_in_background: Prop<StayActiveWhenProjectInBackground>,
     pub auto_correct_settings: Prop<bool>,
     pub real_input_logging_enabled: Prop<bool>,
@@ -99,12 +135,16 @@ pub struct Session {
     pub virtual_input_logging_enabled: Prop<bool>,
     pub virtual_output_logging_enabled: Prop<bool>,
     pub target_control_logging_enabled: Prop<bool>,
+    /// Shows the feedback which would be sent if a feedback output was selected.
+    pub simulate_feedback: Prop<bool>,
     pub send_feedback_only_if_armed: Prop<bool>,
     pub reset_feedback_when_releasing_source: Prop<bool>,
+    pub send_midi_clock: Prop<bool>,
     pub control_input: Prop<ControlInput>,
     pub feedback_output: Prop<Option<FeedbackOutput>>,
     pub main_preset_auto_load_mode: Prop<MainPresetAutoLoadMode>,
     pub lives_on_upper_floor: Prop<bool>,
+    pub virtual_control_element_sharing: Prop<VirtualControlElementSharing>,
     pub tags: Prop<Vec<Tag>>,
     pub compartment_is_dirty: EnumMap<Compartment, Prop<bool>>,
     // Is set when in the state of learning multiple mappings ("batch learn")
@@ -119,6 +159,10 @@ pub struct Session {
     /// At the moment, custom data is only used in the controller compartment.
     custom_compartment_data: EnumMap<Compartment, HashMap<String, serde_json::Value>>,
     compartment_notes: EnumMap<Compartment, String>,
+    /// At the moment, lifecycle messages are only used in the controller compartment.
+    compartment_lifecycle: EnumMap<Compartment, CompartmentLifecycleModel>,
+    /// At the moment, message templates are only used in the controller compartment.
+    compartment_message_templates: EnumMap<Compartment, MessageTemplatesModel>,
     default_main_group: SharedGroup,
     default_controller_group: SharedGroup,
     groups: EnumMap<Compartment, Vec<SharedGroup>>,
@@ -153,6 +197,19 @@ pub struct Session {
     instance_track_descriptor: TrackDescriptor,
     instance_fx_descriptor: FxDescriptor,
     memorized_main_compartment: Option<CompartmentModel>,
+    /// Interior mutability because window bounds are memorized while closing windows, which
+    /// can happen while the session is borrowed.
+    window_layout: RefCell<WindowLayout>,
+    /// Number of bulk edits which are currently in progress (see [`Session::begin_bulk_edit`]).
+    bulk_edit_depth: Cell<u32>,
+    /// Compartments whose mappings need to be synced to the processors when the bulk edit ends.
+    compartments_with_pending_mapping_sync: EnumMap<Compartment, Cell<bool>>,
+    /// When to look for moved FX next because FX have been added (see
+    /// [`Session::fix_fx_references_of_mapping_targets`]).
+    fx_reference_fix_due: Option<Instant>,
+    /// Substitutes for tracks and FX which main presets refer to but which don't exist in this
+    /// project (see [`Session::add_preset_substitutions`]).
+    preset_substitutions: PresetSubstitutions,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -200,17 +257,23 @@ impl LearnManyState {
 
 pub mod session_defaults {
     use crate::application::MainPresetAutoLoadMode;
-    use crate::domain::StayActiveWhenProjectInBackground;
+    use crate::domain::{
+        MidiPassThroughFilter, StayActiveWhenProjectInBackground, VirtualControlElementSharing,
+    };
     use realearn_api::persistence::FxDescriptor;
 
     pub const LET_MATCHED_EVENTS_THROUGH: bool = false;
     pub const LET_UNMATCHED_EVENTS_THROUGH: bool = true;
+    pub const MIDI_PASS_THROUGH_FILTER: MidiPassThroughFilter = MidiPassThroughFilter::PASS_ALL;
     pub const STAY_ACTIVE_WHEN_PROJECT_IN_BACKGROUND: StayActiveWhenProjectInBackground =
         StayActiveWhenProjectInBackground::OnlyIfBackgroundProjectIsRunning;
     pub const AUTO_CORRECT_SETTINGS: bool = true;
     pub const LIVES_ON_UPPER_FLOOR: bool = false;
+    pub const VIRTUAL_CONTROL_ELEMENT_SHARING: VirtualControlElementSharing =
+        VirtualControlElementSharing::Off;
     pub const SEND_FEEDBACK_ONLY_IF_ARMED: bool = true;
     pub const RESET_FEEDBACK_WHEN_RELEASING_SOURCE: bool = true;
+    pub const SEND_MIDI_CLOCK: bool = false;
     pub const MAIN_PRESET_AUTO_LOAD_MODE: MainPresetAutoLoadMode = MainPresetAutoLoadMode::Off;
     /// This is mainly for backward-compatibility with "Auto-load: Depending on focused FX"
     /// but also is a quite common use case, so why not.
@@ -246,6 +309,7 @@ impl Session {
             logger: parent_logger.clone(),
             let_matched_events_through: prop(session_defaults::LET_MATCHED_EVENTS_THROUGH),
             let_unmatched_events_through: prop(session_defaults::LET_UNMATCHED_EVENTS_THROUGH),
+            midi_pass_through_filter: prop(session_defaults::MIDI_PASS_THROUGH_FILTER),
             stay_active_when_project_in_background: prop(
                 session_defaults::STAY_ACTIVE_WHEN_PROJECT_IN_BACKGROUND,
             ),
@@ -255,14 +319,19 @@ impl Session {
             virtual_input_logging_enabled: prop(false),
             virtual_output_logging_enabled: prop(false),
             target_control_logging_enabled: prop(false),
+            simulate_feedback: prop(false),
             send_feedback_only_if_armed: prop(session_defaults::SEND_FEEDBACK_ONLY_IF_ARMED),
             reset_feedback_when_releasing_source: prop(
                 session_defaults::RESET_FEEDBACK_WHEN_RELEASING_SOURCE,
             ),
+            send_midi_clock: prop(session_defaults::SEND_MIDI_CLOCK),
             control_input: prop(Default::default()),
             feedback_output: prop(None),
             main_preset_auto_load_mode: prop(session_defaults::MAIN_PRESET_AUTO_LOAD_MODE),
             lives_on_upper_floor: prop(false),
+            virtual_control_element_sharing: prop(
+                session_defaults::VIRTUAL_CONTROL_ELEMENT_SHARING,
+            ),
             tags: Default::default(),
             compartment_is_dirty: Default::default(),
             learn_many_state: prop(None),
@@ -274,6 +343,8 @@ impl Session {
             mappings: Default::default(),
             custom_compartment_data: Default::default(),
             compartment_notes: Default::default(),
+            compartment_lifecycle: Default::default(),
+            compartment_message_templates: Default::default(),
             default_main_group: Rc::new(RefCell::new(GroupModel::default_for_compartment(
                 Compartment::Main,
             ))),
@@ -307,6 +378,11 @@ impl Session {
             instance_track_descriptor: Default::default(),
             instance_fx_descriptor: session_defaults::INSTANCE_FX_DESCRIPTOR,
             memorized_main_compartment: None,
+            window_layout: Default::default(),
+            bulk_edit_depth: Cell::new(0),
+            compartments_with_pending_mapping_sync: Default::default(),
+            fx_reference_fix_due: None,
+            preset_substitutions: Default::default(),
         };
         session
     }
@@ -437,16 +513,27 @@ impl Session {
     }
 
     fn full_sync(&mut self) {
+        // Sync controller lifecycle messages before the feedback device, so the init messages
+        // are sent as soon as feedback gets enabled.
+        self.sync_controller_lifecycle();
         // It's important to sync feedback device first, otherwise the initial feedback messages
         // won't arrive!
         self.sync_settings();
         self.sync_upper_floor_membership();
         // Now sync mappings - which includes initial feedback.
         for compartment in Compartment::enum_iter() {
-            self.sync_all_mappings_full(compartment);
+            self.sync_all_mappings_full_unless_bulk_edit(compartment);
         }
     }
 
+    /// Makes the main processor hold back the initial feedback burst (and target resolution which
+    /// is not needed for control) after the next full mapping sync and catch up after the given
+    /// delay instead.
+    pub fn defer_initial_feedback(&self, delay: Duration) {
+        self.normal_main_task_sender
+            .send_complaining(NormalMainTask::DeferInitialFeedback { delay });
+    }
+
     /// Makes all autostart mappings hit the target.
     pub fn notify_realearn_instance_started(&self) {
         self.normal_main_task_sender
@@ -461,6 +548,24 @@ impl Session {
             .send_complaining(NormalMainTask::HitTarget { id, value });
     }
 
+    /// Returns a function which sends the given value to the source of the given mapping,
+    /// bypassing mode and target.
+    ///
+    /// The function can be moved to and called from other threads (e.g. UI threads).
+    pub fn test_feedback_sender(&self) -> impl Fn(QualifiedMappingId, UnitValue) + Send + 'static {
+        let sender = self.normal_main_task_sender.clone();
+        move |id, value| sender.send_complaining(NormalMainTask::SendTestFeedback { id, value })
+    }
+
+    /// Returns a function which lets main mappings process the given virtual control value as if
+    /// it was emitted by a controller mapping.
+    ///
+    /// The function can be moved to and called from other threads (e.g. UI threads).
+    pub fn virtual_control_sender(&self) -> impl Fn(VirtualSourceValue) + Send + 'static {
+        let sender = self.normal_main_task_sender.clone();
+        move |value| sender.send_complaining(NormalMainTask::ControlVirtually { value })
+    }
+
     /// Connects the dots.
     // TODO-low Too large. Split this into several methods.
     pub fn activate(&mut self, weak_session: WeakSession) {
@@ -472,7 +577,7 @@ impl Session {
             .with(weak_session.clone())
             .do_async(|shared_session, compartment| {
                 let mut session = shared_session.borrow_mut();
-                session.sync_all_mappings_full(compartment);
+                session.sync_all_mappings_full_unless_bulk_edit(compartment);
                 session.mark_compartment_dirty(compartment);
             });
         // Whenever anything in a mapping list changes and other things which affect all
@@ -481,7 +586,9 @@ impl Session {
         when(self.mapping_list_changed())
             .with(weak_session.clone())
             .do_async(move |session, (compartment, _)| {
-                session.borrow_mut().sync_all_mappings_full(compartment);
+                session
+                    .borrow()
+                    .sync_all_mappings_full_unless_bulk_edit(compartment);
             });
         // Marking project as dirty if certain things are changed. Should only contain events that
         // are triggered by the user.
@@ -501,6 +608,19 @@ impl Session {
             .do_sync(move |s, _| {
                 s.borrow().sync_upper_floor_membership();
             });
+        // Don't keep showing simulated feedback when feedback is not simulated anymore.
+        when(
+            self.simulate_feedback
+                .changed()
+                .merge(self.feedback_output.changed()),
+        )
+        .with(weak_session.clone())
+        .do_async(move |s, _| {
+            s.borrow()
+                .instance_state
+                .borrow_mut()
+                .set_simulated_feedback(None);
+        });
         // Keep syncing some general settings to real-time processor.
         when(self.settings_changed())
             .with(weak_session.clone())
@@ -515,11 +635,42 @@ impl Session {
                 // We have this explicit stop criteria because we listen to global REAPER events.
                 .take_until(self.party_is_over()),
         )
-        .with(weak_session)
+        .with(weak_session.clone())
         .do_async(|s, _| {
             s.borrow_mut()
                 .invalidate_fx_indexes_of_mapping_targets(Rc::downgrade(&s));
         });
+        // When FX is moved to another track, make mappings which refer to that FX by ID follow it.
+        // FX are often added in bursts (e.g. when loading a project), so we wait until it settles.
+        when(
+            Global::control_surface_rx()
+                .fx_added()
+                .take_until(self.party_is_over()),
+        )
+        .with(weak_session.clone())
+        .do_async(|s, _| {
+            s.borrow_mut().fx_reference_fix_due = Some(Instant::now() + FX_REFERENCE_FIX_DELAY);
+        });
+        when(
+            Global::control_surface_rx()
+                .main_thread_idle()
+                .take_until(self.party_is_over()),
+        )
+        .with(weak_session)
+        .do_sync(|s, _| {
+            let due = s.borrow().fx_reference_fix_due;
+            if !matches!(due, Some(due) if Instant::now() >= due) {
+                return;
+            }
+            let mut session = s.borrow_mut();
+            session.fx_reference_fix_due = None;
+            let ids: Vec<_> = session
+                .all_mappings()
+                .map(|m| m.borrow().qualified_id())
+                .collect();
+            // Mappings whose FX doesn't exist anymore are not an error in this case
+            let _ = session.fix_fx_references_of_mapping_targets(ids, Rc::downgrade(&s));
+        });
     }
 
     pub fn activate_main_preset_auto_load_mode(&mut self, mode: MainPresetAutoLoadMode) {
@@ -560,6 +711,7 @@ impl Session {
             .all_mappings()
             .map(|m| m.borrow().qualified_id())
             .collect();
+        self.begin_bulk_edit();
         for id in ids {
             self.change_mapping_by_id_with_closure(id, None, weak_session.clone(), |ctx| {
                 let affected = ctx
@@ -571,6 +723,36 @@ impl Session {
             })
             .expect("error when invalidating FX indexes");
         }
+        self.end_bulk_edit(weak_session);
+    }
+
+    /// Makes the targets of the given mappings follow FX which have been moved to another track or
+    /// position (see [`TargetModel::fix_fx_reference`]).
+    ///
+    /// Returns the mappings whose FX couldn't be found, together with the reason.
+    pub fn fix_fx_references_of_mapping_targets(
+        &mut self,
+        ids: Vec<QualifiedMappingId>,
+        weak_session: WeakSession,
+    ) -> Vec<(QualifiedMappingId, String)> {
+        self.begin_bulk_edit();
+        let errors = ids
+            .into_iter()
+            .filter_map(|id| {
+                self.change_mapping_by_id_with_closure(id, None, weak_session.clone(), |ctx| {
+                    let affected = ctx
+                        .mapping
+                        .target_model
+                        .fix_fx_reference(ctx.mapping.compartment(), ctx.extended_context)?
+                        .map(|affected| Affected::One(MappingProp::InTarget(affected)));
+                    Ok(affected)
+                })
+                .err()
+                .map(|e| (id, e))
+            })
+            .collect();
+        self.end_bulk_edit(weak_session);
+        errors
     }
 
     /// Settings are all the things displayed in the ReaLearn header panel.
@@ -578,18 +760,22 @@ impl Session {
         self.let_matched_events_through
             .changed()
             .merge(self.let_unmatched_events_through.changed())
+            .merge(self.midi_pass_through_filter.changed())
             .merge(self.stay_active_when_project_in_background.changed())
             .merge(self.control_input.changed())
             .merge(self.feedback_output.changed())
             .merge(self.auto_correct_settings.changed())
             .merge(self.send_feedback_only_if_armed.changed())
             .merge(self.reset_feedback_when_releasing_source.changed())
+            .merge(self.send_midi_clock.changed())
+            .merge(self.virtual_control_element_sharing.changed())
             .merge(self.main_preset_auto_load_mode.changed())
             .merge(self.real_input_logging_enabled.changed())
             .merge(self.real_output_logging_enabled.changed())
             .merge(self.virtual_input_logging_enabled.changed())
             .merge(self.virtual_output_logging_enabled.changed())
             .merge(self.target_control_logging_enabled.changed())
+            .merge(self.simulate_feedback.changed())
     }
 
     pub fn captured_incoming_message(&mut self, event: MessageCaptureEvent) {
@@ -731,6 +917,12 @@ impl Session {
                         compartment,
                     )
                 });
+                drop(mapping);
+                let event = SessionEvent::MappingLearned {
+                    mapping_id: qualified_id,
+                    part: LearnedMappingPart::Target,
+                };
+                self.ui.session_event_occurred(self, event);
             }
         }
     }
@@ -777,7 +969,7 @@ impl Session {
         self.add_group(compartment, group)
     }
 
-    fn add_group(&mut self, compartment: Compartment, group: GroupModel) -> GroupId {
+    pub fn add_group(&mut self, compartment: Compartment, group: GroupModel) -> GroupId {
         let id = group.id();
         let shared_group = Rc::new(RefCell::new(group));
         self.groups[compartment].push(shared_group);
@@ -1144,14 +1336,20 @@ impl Session {
                             // Sync all mappings to processor if necessary (change of a single
                             // group can affect many mappings)
                             if affected.processing_relevance().is_some() {
-                                session.sync_all_mappings_full(*compartment);
+                                session.sync_all_mappings_full_unless_bulk_edit(*compartment);
                             }
                             // Mark dirty
                             session.mark_compartment_dirty(*compartment);
                         }
                         One(InCompartment(compartment, One(InMapping(mapping_id, affected)))) => {
                             // Sync mapping to processors if necessary.
-                            if let Some(relevance) = affected.processing_relevance() {
+                            if session.bulk_edit_is_in_progress() {
+                                // Will be synced as part of a full sync when the bulk edit ends.
+                                if affected.processing_relevance().is_some() {
+                                    session.compartments_with_pending_mapping_sync[*compartment]
+                                        .set(true);
+                                }
+                            } else if let Some(relevance) = affected.processing_relevance() {
                                 if let Some((_, mapping)) =
                                     session.find_mapping_and_index_by_id(*compartment, *mapping_id)
                                 {
@@ -1312,6 +1510,121 @@ impl Session {
         self.notify_mapping_list_changed(compartment, None);
     }
 
+    /// Adds one main mapping for each FX parameter in the given range.
+    ///
+    /// The mappings are named after the parameters and get virtual sources with sequentially
+    /// assigned control elements, starting at the given index.
+    pub fn add_mappings_for_fx_parameters(
+        &mut self,
+        group_id: GroupId,
+        fx: &Fx,
+        param_indexes: Range<u32>,
+        control_element_type: VirtualControlElementType,
+        first_control_element_index: u32,
+    ) -> Result<(), &'static str> {
+        check_fx_parameter_range(fx, &param_indexes)?;
+        let compartment = Compartment::Main;
+        let context = self.extended_context();
+        let mappings: Vec<_> = param_indexes
+            .zip(first_control_element_index..)
+            .map(|(param_index, control_element_index)| {
+                let mut mapping = MappingModel::new(
+                    compartment,
+                    group_id,
+                    MappingKey::random(),
+                    MappingId::random(),
+                );
+                let param = fx.parameter_by_index(param_index);
+                let _ = mapping.change(MappingCommand::SetName(param.name().into_string()));
+                let source_commands = [
+                    SourceCommand::SetCategory(SourceCategory::Virtual),
+                    SourceCommand::SetControlElementType(control_element_type),
+                    SourceCommand::SetControlElementId(VirtualControlElementId::Indexed(
+                        control_element_index,
+                    )),
+                ];
+                for cmd in source_commands {
+                    let _ = mapping.source_model.change(cmd);
+                }
+                let target = ReaperTarget::FxParameter(FxParameterTarget {
+                    is_real_time_ready: false,
+                    param,
+                    poll_for_feedback: true,
+                    retrigger: false,
+                    low_latency: false,
+                    thinning: None,
+                });
+                let _ = mapping
+                    .target_model
+                    .apply_from_target(&target, context, compartment);
+                mapping
+            })
+            .collect();
+        let index = self.mappings[compartment].len();
+        self.insert_mappings_at(compartment, index, mappings.into_iter());
+        Ok(())
+    }
+
+    /// Adds controller mappings which expose an XY pad of an OSC app as virtual control elements
+    /// (see [`XyPadRole`]).
+    ///
+    /// Follows the convention of common OSC apps (e.g. TouchOSC): The pad sends X and Y as first
+    /// and second argument of the given address and the touch state to the same address with
+    /// suffix "/z".
+    pub fn add_osc_xy_pad_mappings(
+        &mut self,
+        group_id: GroupId,
+        pad_name: &str,
+        osc_address: &str,
+    ) -> Result<(), &'static str> {
+        let osc_address = osc_address.trim().trim_end_matches('/');
+        if !osc_address.starts_with('/') {
+            return Err("OSC address must start with a slash");
+        }
+        let compartment = Compartment::Controller;
+        let mappings: Result<Vec<_>, &'static str> = XyPadRole::ALL
+            .into_iter()
+            .map(|role| {
+                let control_element = role.control_element(pad_name)?;
+                let mut mapping = MappingModel::new(
+                    compartment,
+                    group_id,
+                    MappingKey::random(),
+                    MappingId::random(),
+                );
+                let _ = mapping.change(MappingCommand::SetName(control_element.id().to_string()));
+                let (address, arg_index) = match role {
+                    XyPadRole::X => (osc_address.to_string(), 0),
+                    XyPadRole::Y => (osc_address.to_string(), 1),
+                    XyPadRole::Touch => (format!("{}/z", osc_address), 0),
+                };
+                let source_commands = [
+                    SourceCommand::SetCategory(SourceCategory::Osc),
+                    SourceCommand::SetOscAddressPattern(address),
+                    SourceCommand::SetOscArgIndex(Some(arg_index)),
+                    SourceCommand::SetOscArgTypeTag(OscTypeTag::Float),
+                ];
+                for cmd in source_commands {
+                    let _ = mapping.source_model.change(cmd);
+                }
+                let target_commands = [
+                    TargetCommand::SetCategory(TargetCategory::Virtual),
+                    TargetCommand::SetControlElementType(
+                        VirtualControlElementType::from_control_element(control_element),
+                    ),
+                    TargetCommand::SetControlElementId(control_element.id()),
+                ];
+                for cmd in target_commands {
+                    let _ = mapping.target_model.change(cmd);
+                }
+                Ok(mapping)
+            })
+            .collect();
+        let index = self.mappings[compartment].len();
+        self.insert_mappings_at(compartment, index, mappings?.into_iter());
+        Ok(())
+    }
+
     fn mapping_key_set(&self, compartment: Compartment) -> HashSet<MappingKey> {
         self.mappings[compartment]
             .iter()
@@ -1517,6 +1830,11 @@ impl Session {
         self.mappings[compartment].iter()
     }
 
+    /// Returns the mappings of the given compartment in list order.
+    pub fn mapping_slice(&self, compartment: Compartment) -> &[SharedMapping] {
+        &self.mappings[compartment]
+    }
+
     pub fn default_group(&self, compartment: Compartment) -> &SharedGroup {
         match compartment {
             Compartment::Controller => &self.default_controller_group,
@@ -1620,12 +1938,19 @@ impl Session {
                 if let Some(source) = session.create_compound_source(event) {
                     // The learn process should stop when removing a mapping but just in case,
                     // let's react gracefully if the mapping doesn't exist anymore (do nothing).
-                    let _ = session.change_mapping_by_id_with_closure(
+                    let result = session.change_mapping_by_id_with_closure(
                         qualified_id,
                         None,
                         Rc::downgrade(&shared_session),
                         |ctx| Ok(ctx.mapping.source_model.apply_from_source(&source)),
                     );
+                    if result.is_ok() {
+                        let event = SessionEvent::MappingLearned {
+                            mapping_id: qualified_id,
+                            part: LearnedMappingPart::Source,
+                        };
+                        session.ui.session_event_occurred(&session, event);
+                    }
                 }
             }
         });
@@ -1821,6 +2146,29 @@ impl Session {
         self.instance_preset_link_config = config;
     }
 
+    pub fn window_layout(&self) -> WindowLayout {
+        self.window_layout.borrow().clone()
+    }
+
+    pub fn set_window_layout(&mut self, layout: WindowLayout) {
+        self.window_layout.replace(layout);
+    }
+
+    pub fn window_bounds(&self, window_name: &str) -> Option<WindowBounds> {
+        self.window_layout.borrow().window_bounds(window_name)
+    }
+
+    /// Marks the session as dirty if the bounds changed, so they are saved with the project.
+    pub fn memorize_window_bounds(&self, window_name: &str, bounds: WindowBounds) {
+        let changed = self
+            .window_layout
+            .borrow_mut()
+            .memorize_window_bounds(window_name, bounds);
+        if changed {
+            self.mark_dirty();
+        }
+    }
+
     pub fn set_active_controller_id_without_notification(
         &mut self,
         active_controller_id: Option<String>,
@@ -1875,6 +2223,56 @@ impl Session {
         &self.compartment_notes[compartment]
     }
 
+    pub fn compartment_lifecycle(&self, compartment: Compartment) -> &CompartmentLifecycleModel {
+        &self.compartment_lifecycle[compartment]
+    }
+
+    pub fn set_compartment_lifecycle_without_notification(
+        &mut self,
+        compartment: Compartment,
+        lifecycle: CompartmentLifecycleModel,
+    ) {
+        self.compartment_lifecycle[compartment] = lifecycle;
+    }
+
+    pub fn set_compartment_lifecycle(
+        &mut self,
+        compartment: Compartment,
+        lifecycle: CompartmentLifecycleModel,
+    ) {
+        self.compartment_lifecycle[compartment] = lifecycle;
+        if compartment == Compartment::Controller {
+            self.sync_controller_lifecycle();
+        }
+        self.mark_compartment_dirty(compartment);
+    }
+
+    pub fn compartment_message_templates(
+        &self,
+        compartment: Compartment,
+    ) -> &MessageTemplatesModel {
+        &self.compartment_message_templates[compartment]
+    }
+
+    pub fn set_compartment_message_templates_without_notification(
+        &mut self,
+        compartment: Compartment,
+        templates: MessageTemplatesModel,
+    ) {
+        self.compartment_message_templates[compartment] = templates;
+    }
+
+    /// Sources can refer to message templates, so all mappings of the compartment are synced.
+    pub fn set_compartment_message_templates(
+        &mut self,
+        compartment: Compartment,
+        templates: MessageTemplatesModel,
+    ) {
+        self.compartment_message_templates[compartment] = templates;
+        self.sync_all_mappings_full_unless_bulk_edit(compartment);
+        self.mark_compartment_dirty(compartment);
+    }
+
     pub fn active_main_preset(&self) -> Option<MainPreset> {
         let id = self.active_preset_id(Compartment::Main)?;
         self.main_preset_manager.find_by_id(id)
@@ -1905,6 +2303,7 @@ impl Session {
         self.active_controller_preset_id = id;
         self.replace_compartment(compartment, model);
         self.compartment_is_dirty[compartment].set(false);
+        self.notify_preset_loaded(compartment);
     }
 
     pub fn memorized_main_compartment(&self) -> Option<&CompartmentModel> {
@@ -1931,6 +2330,17 @@ impl Session {
         self.active_main_preset_id = id;
         self.replace_compartment(compartment, model);
         self.compartment_is_dirty[compartment].set(false);
+        self.notify_preset_loaded(compartment);
+    }
+
+    /// Remembers substitutes for tracks and FX which main presets refer to but which don't exist
+    /// in this project.
+    ///
+    /// They are applied whenever the main compartment is replaced, no matter if by loading a
+    /// preset (manually, via auto-load or via API) or by importing. The presets themselves stay
+    /// untouched.
+    pub fn add_preset_substitutions(&mut self, substitutions: PresetSubstitutions) {
+        self.preset_substitutions.merge(substitutions);
     }
 
     fn activate_main_preset_for_auto_load(&mut self, id: Option<String>) {
@@ -1949,6 +2359,15 @@ impl Session {
         self.active_main_preset_id = id;
         self.replace_compartment(compartment, model);
         self.compartment_is_dirty[compartment].set(false);
+        self.notify_preset_loaded(compartment);
+    }
+
+    fn notify_preset_loaded(&self, compartment: Compartment) {
+        let event = SessionEvent::PresetLoaded {
+            compartment,
+            preset_id: self.active_preset_id(compartment).map(|id| id.to_string()),
+        };
+        self.ui.session_event_occurred(self, event);
     }
 
     pub fn extract_compartment_model(&self, compartment: Compartment) -> CompartmentModel {
@@ -1968,6 +2387,8 @@ impl Session {
                 .collect(),
             custom_data: self.custom_compartment_data[compartment].clone(),
             notes: self.compartment_notes[compartment].clone(),
+            lifecycle: self.compartment_lifecycle[compartment].clone(),
+            message_templates: self.compartment_message_templates[compartment].clone(),
         }
     }
 
@@ -1983,8 +2404,14 @@ impl Session {
 
     /// Precondition: The given compartment model should be valid (e.g. no duplicate IDs)!
     fn replace_compartment(&mut self, compartment: Compartment, model: Option<CompartmentModel>) {
+        // Nothing in here defers its reaction, so we can end the bulk edit right away.
+        self.begin_bulk_edit();
         self.stop_mapping_actions();
-        if let Some(model) = model {
+        if let Some(mut model) = model {
+            if compartment == Compartment::Main {
+                let project = self.processor_context.project_or_current_project();
+                self.preset_substitutions.apply(&mut model, project);
+            }
             let default_group = match compartment {
                 Compartment::Main => &mut self.default_main_group,
                 Compartment::Controller => &mut self.default_controller_group,
@@ -1999,11 +2426,14 @@ impl Session {
                 .update_compartment_params(compartment, compartment_params.clone());
             self.custom_compartment_data[compartment] = model.custom_data;
             self.compartment_notes[compartment] = model.notes;
+            self.compartment_lifecycle[compartment] = model.lifecycle;
+            self.compartment_message_templates[compartment] = model.message_templates;
         } else {
             self.clear_compartment_data(compartment);
         }
         self.reset_parameters(compartment);
         self.notify_everything_has_changed();
+        self.end_bulk_edit_now();
     }
 
     fn reset_parameters(&self, compartment: Compartment) {
@@ -2027,6 +2457,8 @@ impl Session {
             .update_compartment_params(compartment, Default::default());
         self.custom_compartment_data[compartment] = Default::default();
         self.compartment_notes[compartment] = Default::default();
+        self.compartment_lifecycle[compartment] = Default::default();
+        self.compartment_message_templates[compartment] = Default::default();
     }
 
     pub fn update_certain_param_settings(
@@ -2289,13 +2721,17 @@ impl Session {
             virtual_input_logging_enabled: self.virtual_input_logging_enabled.get(),
             virtual_output_logging_enabled: self.virtual_output_logging_enabled.get(),
             target_control_logging_enabled: self.target_control_logging_enabled.get(),
+            simulate_feedback: self.simulate_feedback.get(),
             send_feedback_only_if_armed: self.send_feedback_only_if_armed.get(),
             reset_feedback_when_releasing_source: self.reset_feedback_when_releasing_source.get(),
+            send_midi_clock: self.send_midi_clock.get(),
             let_matched_events_through: self.let_matched_events_through.get(),
             let_unmatched_events_through: self.let_unmatched_events_through.get(),
+            midi_pass_through_filter: self.midi_pass_through_filter.get(),
             stay_active_when_project_in_background: self
                 .stay_active_when_project_in_background
                 .get(),
+            virtual_control_element_sharing: self.virtual_control_element_sharing.get(),
         };
         self.normal_main_task_sender
             .send_complaining(NormalMainTask::UpdateSettings(settings));
@@ -2303,6 +2739,22 @@ impl Session {
             .send_complaining(NormalRealTimeTask::UpdateSettings(settings));
     }
 
+    fn sync_controller_lifecycle(&self) {
+        let data = match self.compartment_lifecycle[Compartment::Controller]
+            .create_controller_lifecycle_midi_data()
+        {
+            Ok(d) => d,
+            Err(e) => {
+                self.notify_user(Notification::instance_warning(format!(
+                    "Controller init/shutdown messages are invalid and won't be sent: {e}"
+                )));
+                Default::default()
+            }
+        };
+        self.normal_main_task_sender
+            .send_complaining(NormalMainTask::UpdateControllerLifecycleMidi(data));
+    }
+
     fn sync_persistent_mapping_processing_state(&self, mapping: &MappingModel) {
         self.normal_main_task_sender.send_complaining(
             NormalMainTask::UpdatePersistentMappingProcessingState {
@@ -2317,7 +2769,10 @@ impl Session {
             .find_group_of_mapping(m)
             .map(|g| g.borrow().create_data())
             .unwrap_or_default();
-        let main_mapping = m.create_main_mapping(group_data);
+        let main_mapping = m.create_main_mapping(
+            group_data,
+            &self.compartment_message_templates[m.compartment()],
+        );
         self.normal_main_task_sender
             .send_complaining(NormalMainTask::UpdateSingleMapping(Box::new(main_mapping)));
     }
@@ -2335,6 +2790,60 @@ impl Session {
         }
     }
 
+    /// Starts a bulk edit.
+    ///
+    /// Until the matching [`Session::end_bulk_edit`], changes to mappings and groups are not
+    /// synced to the processors one by one. Instead, they are coalesced into one full mapping sync
+    /// per affected compartment. Bulk edits can be nested.
+    pub fn begin_bulk_edit(&self) {
+        self.bulk_edit_depth.set(self.bulk_edit_depth.get() + 1);
+    }
+
+    /// Ends a bulk edit started with [`Session::begin_bulk_edit`].
+    ///
+    /// Most reactions to changes are deferred to the next main loop cycle (see `handle_affected`),
+    /// so we end the bulk edit deferred as well. Otherwise those reactions would sync each change
+    /// separately again.
+    pub fn end_bulk_edit(&self, weak_session: WeakSession) {
+        Global::task_support()
+            .do_later_in_main_thread_from_main_thread_asap(move || {
+                if let Some(session) = weak_session.upgrade() {
+                    session.borrow().end_bulk_edit_now();
+                }
+            })
+            .unwrap();
+    }
+
+    fn end_bulk_edit_now(&self) {
+        let depth = self.bulk_edit_depth.get();
+        if depth == 0 {
+            return;
+        }
+        self.bulk_edit_depth.set(depth - 1);
+        if depth > 1 {
+            return;
+        }
+        for compartment in Compartment::enum_iter() {
+            if self.compartments_with_pending_mapping_sync[compartment].replace(false) {
+                self.sync_all_mappings_full(compartment);
+            }
+        }
+    }
+
+    pub fn bulk_edit_is_in_progress(&self) -> bool {
+        self.bulk_edit_depth.get() > 0
+    }
+
+    /// Does a full mapping sync or - if a bulk edit is in progress - schedules it for the end of
+    /// the bulk edit.
+    fn sync_all_mappings_full_unless_bulk_edit(&self, compartment: Compartment) {
+        if self.bulk_edit_is_in_progress() {
+            self.compartments_with_pending_mapping_sync[compartment].set(true);
+        } else {
+            self.sync_all_mappings_full(compartment);
+        }
+    }
+
     /// Does a full mapping sync.
     fn sync_all_mappings_full(&self, compartment: Compartment) {
         let main_mappings = self.create_main_mappings(compartment);
@@ -2347,6 +2856,7 @@ impl Session {
 
     /// Creates mappings from mapping models so they can be distributed to different processors.
     fn create_main_mappings(&self, compartment: Compartment) -> Vec<MainMapping> {
+        let message_templates = &self.compartment_message_templates[compartment];
         let group_map: HashMap<GroupId, Ref<GroupModel>> = self
             .groups_including_default_group(compartment)
             .map(|group| {
@@ -2367,7 +2877,7 @@ impl Session {
                     .get(&mapping.group_id())
                     .map(|g| g.create_data())
                     .unwrap_or_default();
-                mapping.create_main_mapping(group_data)
+                mapping.create_main_mapping(group_data, message_templates)
             })
             .collect()
     }
@@ -2397,6 +2907,36 @@ impl Session {
         &self.logger
     }
 
+    /// Returns a one-line description of the given notification, suitable for the user.
+    pub fn describe_notification(&self, notification: &Notification) -> String {
+        let context_label = match notification.context {
+            NotificationContext::Instance => "instance".to_string(),
+            NotificationContext::Mapping(id) => {
+                match self.find_mapping_and_index_by_qualified_id(id) {
+                    None => "deleted mapping".to_string(),
+                    Some((_, m)) => format!("mapping \"{}\"", m.borrow().effective_name()),
+                }
+            }
+        };
+        format!(
+            "{} in {}: {}",
+            notification.severity, context_label, notification.msg
+        )
+    }
+
+    /// Logs the given notification, adds it to the notification list and informs the UI.
+    pub fn notify_user(&self, notification: Notification) {
+        warn!(self.logger, "{}", self.describe_notification(&notification));
+        self.instance_state
+            .borrow_mut()
+            .add_notification(notification);
+        let instance_state = self.instance_state.borrow();
+        if let Some(entry) = instance_state.notifications().latest() {
+            self.ui
+                .send_diagnostic_event(self, DiagnosticEvent::Notified(entry));
+        }
+    }
+
     /// Does a full resync and notifies the UI async.
     ///
     /// Explicitly doesn't mark the project as dirty - because this is also used when loading data
@@ -2438,6 +2978,13 @@ impl DomainEventHandler for WeakSession {
                     .borrow_mut()
                     .set_on_mappings(on_mappings);
             }
+            UpdatedMissingTargetObjects(missing_target_objects) => {
+                session
+                    .borrow()
+                    .instance_state
+                    .borrow_mut()
+                    .set_missing_target_objects(missing_target_objects);
+            }
             GlobalControlAndFeedbackStateChanged(state) => {
                 session
                     .borrow()
@@ -2445,6 +2992,37 @@ impl DomainEventHandler for WeakSession {
                     .borrow_mut()
                     .set_global_control_and_feedback_state(state);
             }
+            RealTimeOverrunsDetected(overruns) => {
+                let session = session.borrow();
+                session
+                    .instance_state
+                    .borrow_mut()
+                    .add_real_time_overruns(overruns);
+                session.ui.send_diagnostic_event(
+                    &session,
+                    DiagnosticEvent::RealTimeOverrunsDetected(overruns),
+                );
+            }
+            MidiInputDropsDetected(drops) => {
+                session
+                    .borrow()
+                    .instance_state
+                    .borrow_mut()
+                    .add_midi_input_drops(drops);
+            }
+            Notified(notification) => {
+                session.borrow().notify_user(notification);
+            }
+            FeedbackSimulated(msg) => {
+                let session = session.borrow();
+                session
+                    .ui
+                    .send_diagnostic_event(&session, DiagnosticEvent::FeedbackSimulated(&msg));
+                session
+                    .instance_state
+                    .borrow_mut()
+                    .set_simulated_feedback(Some(msg));
+            }
             UpdatedSingleMappingOnState(event) => {
                 session
                     .borrow()
@@ -2479,6 +3057,10 @@ impl DomainEventHandler for WeakSession {
                 let s = session.try_borrow()?;
                 s.ui.send_projection_feedback(&s, value);
             }
+            ControlElementTouched(event) => {
+                let s = session.try_borrow()?;
+                s.ui.send_control_element_touched(&s, event);
+            }
             ClipMatrixChanged {
                 matrix,
                 events,
@@ -2617,6 +3199,18 @@ fn mapping_has_project_references(mapping: &SharedMapping) -> bool {
     }
 }
 
+/// Checks if mappings can be added for the given parameters of the given FX (see
+/// [`Session::add_mappings_for_fx_parameters`]).
+pub fn check_fx_parameter_range(fx: &Fx, param_indexes: &Range<u32>) -> Result<(), &'static str> {
+    if param_indexes.is_empty() {
+        return Err("no parameters given");
+    }
+    if param_indexes.end > fx.parameter_count() {
+        return Err("FX doesn't have that many parameters");
+    }
+    Ok(())
+}
+
 pub fn reaper_supports_global_midi_filter() -> bool {
     let v = Reaper::get().version().to_string();
     let v_without_arch = v.split('/').next().unwrap();
@@ -1,11 +1,16 @@
 use crate::application::{
-    Affected, Change, GetProcessingRelevance, MappingProp, ProcessingRelevance,
+    Affected, Change, GetProcessingRelevance, MappingProp, MessageTemplatesModel,
+    ProcessingRelevance,
 };
 use crate::domain::{
-    BackboneState, Compartment, CompartmentParamIndex, CompoundMappingSource, EelMidiSourceScript,
-    ExtendedSourceCharacter, FlexibleMidiSourceScript, KeySource, Keystroke, LuaMidiSourceScript,
-    MidiSource, RealearnParameterSource, ReaperSource, SpeechSource, TimerSource,
-    VirtualControlElement, VirtualControlElementId, VirtualSource, VirtualTarget,
+    parse_midi_machine_control_pattern, AudioSource, BackboneState, Compartment,
+    CompartmentParamIndex, CompoundMappingSource, EelMidiSourceScript, ExtendedSourceCharacter,
+    FlexibleMidiSourceScript, KeySource, Keystroke, LfoSource, LuaMidiSourceScript,
+    MidiMachineControlSource, MidiSource, NoteFeedbackOptions, OneShotTimerSource,
+    PitchBendSettings, RandomSource, RealearnParameterSource, ReaperSource, SourceDebounceSettings,
+    SpeechSource, TimerSource, VirtualControlElement, VirtualControlElementId, VirtualSource,
+    VirtualTarget, DEFAULT_AUDIO_ATTACK, DEFAULT_AUDIO_RELEASE, DEFAULT_LFO_PERIOD,
+    MMC_ALL_CALL_DEVICE_ID,
 };
 use derive_more::Display;
 use enum_iterator::IntoEnumIterator;
@@ -17,7 +22,10 @@ use helgoboss_learn::{
 };
 use helgoboss_midi::{Channel, U14, U7};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use realearn_api::persistence::MidiScriptKind;
+use realearn_api::persistence::{
+    AudioInputKind, AudioLevelMeasurement, LfoShape, MidiMachineControlCommand, MidiScriptKind,
+    PitchBendBehavior, SourceDebounce, VelocityFeedback,
+};
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
 use std::borrow::Cow;
@@ -35,6 +43,7 @@ pub enum SourceCommand {
     SetParameterNumberMessageNumber(Option<U14>),
     SetCustomCharacter(SourceCharacter),
     SetMidiClockTransportMessage(MidiClockTransportMessage),
+    SetMidiMachineControlCommand(MidiMachineControlCommand),
     SetIsRegistered(Option<bool>),
     SetIs14Bit(Option<bool>),
     SetRawMidiPattern(String),
@@ -43,6 +52,15 @@ pub enum SourceCommand {
     SetDisplayType(DisplayType),
     SetDisplayId(Option<u8>),
     SetLine(Option<u8>),
+    SetVelocityFeedback(Option<VelocityFeedback>),
+    SetFeedbackAsNoteOn(bool),
+    SetDebounce(Option<SourceDebounce>),
+    SetPitchBendBehavior(Option<PitchBendBehavior>),
+    SetAudioInputKind(AudioInputKind),
+    SetAudioChannel(u32),
+    SetAudioLevelMeasurement(AudioLevelMeasurement),
+    SetAudioAttackMillis(u64),
+    SetAudioReleaseMillis(u64),
     SetOscAddressPattern(String),
     SetOscArgIndex(Option<u32>),
     SetOscArgTypeTag(OscTypeTag),
@@ -51,6 +69,8 @@ pub enum SourceCommand {
     SetOscFeedbackArgs(Vec<String>),
     SetReaperSourceType(ReaperSourceType),
     SetTimerMillis(u64),
+    SetLfoShape(LfoShape),
+    SetLfoPeriod(f64),
     SetParameterIndex(CompartmentParamIndex),
     SetKeystroke(Option<Keystroke>),
     SetControlElementType(VirtualControlElementType),
@@ -66,6 +86,7 @@ pub enum SourceProp {
     ParameterNumberMessageNumber,
     CustomCharacter,
     MidiClockTransportMessage,
+    MidiMachineControlCommand,
     IsRegistered,
     Is14Bit,
     RawMidiPattern,
@@ -74,6 +95,15 @@ pub enum SourceProp {
     DisplayType,
     DisplayId,
     Line,
+    VelocityFeedback,
+    FeedbackAsNoteOn,
+    Debounce,
+    PitchBendBehavior,
+    AudioInputKind,
+    AudioChannel,
+    AudioLevelMeasurement,
+    AudioAttackMillis,
+    AudioReleaseMillis,
     OscAddressPattern,
     OscArgIndex,
     OscArgTypeTag,
@@ -84,6 +114,8 @@ pub enum SourceProp {
     ControlElementType,
     ControlElementId,
     TimerMillis,
+    LfoShape,
+    LfoPeriod,
     ParameterIndex,
     Keystroke,
 }
@@ -132,6 +164,10 @@ impl<'a> Change<'a> for SourceModel {
                 self.midi_clock_transport_message = v;
                 One(P::MidiClockTransportMessage)
             }
+            C::SetMidiMachineControlCommand(v) => {
+                self.midi_machine_control_command = v;
+                One(P::MidiMachineControlCommand)
+            }
             C::SetIsRegistered(v) => {
                 self.is_registered = v;
                 One(P::IsRegistered)
@@ -164,6 +200,42 @@ impl<'a> Change<'a> for SourceModel {
                 self.line = v;
                 One(P::Line)
             }
+            C::SetVelocityFeedback(v) => {
+                self.velocity_feedback = v;
+                One(P::VelocityFeedback)
+            }
+            C::SetFeedbackAsNoteOn(v) => {
+                self.feedback_as_note_on = v;
+                One(P::FeedbackAsNoteOn)
+            }
+            C::SetDebounce(v) => {
+                self.debounce = v;
+                One(P::Debounce)
+            }
+            C::SetPitchBendBehavior(v) => {
+                self.pitch_bend_behavior = v;
+                One(P::PitchBendBehavior)
+            }
+            C::SetAudioInputKind(v) => {
+                self.audio_input_kind = v;
+                One(P::AudioInputKind)
+            }
+            C::SetAudioChannel(v) => {
+                self.audio_channel = v;
+                One(P::AudioChannel)
+            }
+            C::SetAudioLevelMeasurement(v) => {
+                self.audio_level_measurement = v;
+                One(P::AudioLevelMeasurement)
+            }
+            C::SetAudioAttackMillis(v) => {
+                self.audio_attack_millis = v;
+                One(P::AudioAttackMillis)
+            }
+            C::SetAudioReleaseMillis(v) => {
+                self.audio_release_millis = v;
+                One(P::AudioReleaseMillis)
+            }
             C::SetOscAddressPattern(v) => {
                 self.osc_address_pattern = v;
                 One(P::OscAddressPattern)
@@ -204,6 +276,14 @@ impl<'a> Change<'a> for SourceModel {
                 self.timer_millis = v;
                 One(P::TimerMillis)
             }
+            C::SetLfoShape(v) => {
+                self.lfo_shape = v;
+                One(P::LfoShape)
+            }
+            C::SetLfoPeriod(v) => {
+                self.lfo_period = v;
+                One(P::LfoPeriod)
+            }
             C::SetParameterIndex(v) => {
                 self.parameter_index = v;
                 One(P::ParameterIndex)
@@ -228,6 +308,7 @@ pub struct SourceModel {
     midi_message_number: Option<U7>,
     parameter_number_message_number: Option<U14>,
     midi_clock_transport_message: MidiClockTransportMessage,
+    midi_machine_control_command: MidiMachineControlCommand,
     is_registered: Option<bool>,
     is_14_bit: Option<bool>,
     raw_midi_pattern: String,
@@ -236,6 +317,16 @@ pub struct SourceModel {
     display_type: DisplayType,
     display_id: Option<u8>,
     line: Option<u8>,
+    velocity_feedback: Option<VelocityFeedback>,
+    feedback_as_note_on: bool,
+    debounce: Option<SourceDebounce>,
+    pitch_bend_behavior: Option<PitchBendBehavior>,
+    // Audio
+    audio_input_kind: AudioInputKind,
+    audio_channel: u32,
+    audio_level_measurement: AudioLevelMeasurement,
+    audio_attack_millis: u64,
+    audio_release_millis: u64,
     // OSC
     osc_address_pattern: String,
     osc_arg_index: Option<u32>,
@@ -246,6 +337,9 @@ pub struct SourceModel {
     // REAPER
     reaper_source_type: ReaperSourceType,
     timer_millis: u64,
+    lfo_shape: LfoShape,
+    /// Length of one LFO cycle in beats.
+    lfo_period: f64,
     parameter_index: CompartmentParamIndex,
     // Key
     keystroke: Option<Keystroke>,
@@ -266,6 +360,7 @@ impl SourceModel {
             parameter_number_message_number: None,
             custom_character: Default::default(),
             midi_clock_transport_message: Default::default(),
+            midi_machine_control_command: Default::default(),
             is_registered: Some(false),
             is_14_bit: Some(false),
             raw_midi_pattern: "".to_owned(),
@@ -274,6 +369,15 @@ impl SourceModel {
             display_type: Default::default(),
             display_id: Default::default(),
             line: None,
+            velocity_feedback: None,
+            feedback_as_note_on: false,
+            debounce: None,
+            pitch_bend_behavior: None,
+            audio_input_kind: Default::default(),
+            audio_channel: 0,
+            audio_level_measurement: Default::default(),
+            audio_attack_millis: DEFAULT_AUDIO_ATTACK.as_millis() as _,
+            audio_release_millis: DEFAULT_AUDIO_RELEASE.as_millis() as _,
             osc_address_pattern: "".to_owned(),
             osc_arg_index: Some(0),
             osc_arg_type_tag: Default::default(),
@@ -282,6 +386,8 @@ impl SourceModel {
             osc_feedback_args: vec![],
             reaper_source_type: Default::default(),
             timer_millis: Default::default(),
+            lfo_shape: Default::default(),
+            lfo_period: DEFAULT_LFO_PERIOD,
             parameter_index: Default::default(),
             keystroke: None,
         }
@@ -315,6 +421,10 @@ impl SourceModel {
         self.midi_clock_transport_message
     }
 
+    pub fn midi_machine_control_command(&self) -> MidiMachineControlCommand {
+        self.midi_machine_control_command
+    }
+
     pub fn is_registered(&self) -> Option<bool> {
         self.is_registered
     }
@@ -347,6 +457,42 @@ impl SourceModel {
         self.line
     }
 
+    pub fn velocity_feedback(&self) -> Option<&VelocityFeedback> {
+        self.velocity_feedback.as_ref()
+    }
+
+    pub fn feedback_as_note_on(&self) -> bool {
+        self.feedback_as_note_on
+    }
+
+    pub fn debounce(&self) -> Option<&SourceDebounce> {
+        self.debounce.as_ref()
+    }
+
+    pub fn pitch_bend_behavior(&self) -> Option<&PitchBendBehavior> {
+        self.pitch_bend_behavior.as_ref()
+    }
+
+    pub fn audio_input_kind(&self) -> AudioInputKind {
+        self.audio_input_kind
+    }
+
+    pub fn audio_channel(&self) -> u32 {
+        self.audio_channel
+    }
+
+    pub fn audio_level_measurement(&self) -> AudioLevelMeasurement {
+        self.audio_level_measurement
+    }
+
+    pub fn audio_attack_millis(&self) -> u64 {
+        self.audio_attack_millis
+    }
+
+    pub fn audio_release_millis(&self) -> u64 {
+        self.audio_release_millis
+    }
+
     pub fn osc_address_pattern(&self) -> &str {
         &self.osc_address_pattern
     }
@@ -387,6 +533,14 @@ impl SourceModel {
         self.timer_millis
     }
 
+    pub fn lfo_shape(&self) -> LfoShape {
+        self.lfo_shape
+    }
+
+    pub fn lfo_period(&self) -> f64 {
+        self.lfo_period
+    }
+
     pub fn control_element_type(&self) -> VirtualControlElementType {
         self.control_element_type
     }
@@ -401,7 +555,7 @@ impl SourceModel {
             Midi => self.midi_source_type.supports_control(),
             Osc => self.osc_arg_type_tag.supports_control(),
             Reaper => self.reaper_source_type.supports_control(),
-            Virtual | Keyboard => true,
+            Virtual | Keyboard | Audio => true,
             // Main use case: Group interaction (follow-only).
             Never => true,
         }
@@ -414,7 +568,7 @@ impl SourceModel {
             Osc => self.osc_arg_type_tag.supports_feedback(),
             Reaper => self.reaper_source_type.supports_feedback(),
             Virtual => true,
-            Keyboard | Never => false,
+            Keyboard | Audio | Never => false,
         }
     }
 
@@ -473,12 +627,25 @@ impl SourceModel {
                         pattern,
                         custom_character,
                     } => {
+                        let pattern = pattern.to_string();
+                        if let Some((device_id, command)) =
+                            parse_midi_machine_control_pattern(&pattern)
+                        {
+                            self.midi_message_number = Some(U7::new(device_id));
+                            self.midi_machine_control_command = command;
+                        }
                         self.custom_character = *custom_character;
-                        self.raw_midi_pattern = pattern.to_string();
+                        self.raw_midi_pattern = pattern;
                     }
                     _ => {}
                 }
             }
+            MidiMachineControl(s) => {
+                self.category = SourceCategory::Midi;
+                self.midi_source_type = MidiSourceType::MachineControl;
+                self.midi_message_number = Some(U7::new(s.device_id()));
+                self.midi_machine_control_command = s.command();
+            }
             Virtual(s) => {
                 self.category = SourceCategory::Virtual;
                 self.control_element_type = VirtualControlElementType::from_source(s);
@@ -503,7 +670,13 @@ impl SourceModel {
                     RealearnParameter(p) => {
                         self.parameter_index = p.parameter_index;
                     }
-                    MidiDeviceChanges | RealearnInstanceStart | Timer(_) | Speech(_) => {}
+                    MidiDeviceChanges
+                    | RealearnInstanceStart
+                    | Timer(_)
+                    | Speech(_)
+                    | OneShotTimer(_)
+                    | Lfo(_)
+                    | Random(_) => {}
                 }
             }
             Never => {
@@ -513,6 +686,14 @@ impl SourceModel {
                 self.category = SourceCategory::Keyboard;
                 self.keystroke = Some(s.stroke());
             }
+            Audio(s) => {
+                self.category = SourceCategory::Audio;
+                self.audio_input_kind = s.input();
+                self.audio_channel = s.channel();
+                self.audio_level_measurement = s.measurement();
+                self.audio_attack_millis = s.attack().as_millis() as _;
+                self.audio_release_millis = s.release().as_millis() as _;
+            }
         };
         Some(Affected::Multiple)
     }
@@ -532,6 +713,7 @@ impl SourceModel {
     pub fn possible_detailed_characters(&self) -> Vec<DetailedSourceCharacter> {
         match self.create_source() {
             CompoundMappingSource::Midi(s) => s.possible_detailed_characters(),
+            CompoundMappingSource::MidiMachineControl(_) => vec![DetailedSourceCharacter::Trigger],
             CompoundMappingSource::Osc(s) => s.possible_detailed_characters(),
             CompoundMappingSource::Virtual(s) => match s.control_element() {
                 VirtualControlElement::Multi(_) => vec![
@@ -545,6 +727,7 @@ impl SourceModel {
                     DetailedSourceCharacter::MomentaryOnOffButton,
                     DetailedSourceCharacter::Trigger,
                 ],
+                VirtualControlElement::Display(_) => vec![DetailedSourceCharacter::RangeControl],
             },
             CompoundMappingSource::Reaper(s) => s.possible_detailed_characters(),
             // Can be anything, depending on the mapping that uses the group interaction.
@@ -556,16 +739,71 @@ impl SourceModel {
                 DetailedSourceCharacter::Relative,
             ],
             CompoundMappingSource::Key(_) => vec![DetailedSourceCharacter::MomentaryOnOffButton],
+            CompoundMappingSource::Audio(_) => vec![DetailedSourceCharacter::RangeControl],
         }
     }
 
     /// Creates a source reflecting this model's current values
     pub fn create_source(&self) -> CompoundMappingSource {
-        self.create_source_internal()
+        self.create_source_with_templates(&Default::default())
+    }
+
+    /// Like [`Self::create_source`] but resolves references to the given message templates.
+    pub fn create_source_with_templates(
+        &self,
+        message_templates: &MessageTemplatesModel,
+    ) -> CompoundMappingSource {
+        self.create_source_internal(message_templates)
             .unwrap_or(CompoundMappingSource::Never)
     }
 
-    fn create_source_internal(&self) -> Option<CompoundMappingSource> {
+    /// Creates the options which adjust the feedback of note sources.
+    pub fn create_note_feedback_options(&self) -> NoteFeedbackOptions {
+        if self.category != SourceCategory::Midi
+            || !self.midi_source_type.supports_velocity_feedback()
+        {
+            return Default::default();
+        }
+        NoteFeedbackOptions {
+            velocity_feedback: self.velocity_feedback.clone(),
+            send_as_note_on: self.midi_source_type == MidiSourceType::PolyphonicKeyPressureAmount
+                && self.feedback_as_note_on,
+        }
+    }
+
+    /// Creates the settings of the filter which removes value flutter before mode processing.
+    pub fn create_debounce_settings(&self) -> SourceDebounceSettings {
+        if !self.supports_debounce() {
+            return Default::default();
+        }
+        match self.debounce {
+            None => Default::default(),
+            Some(d) => SourceDebounceSettings {
+                min_change: d.min_change.unwrap_or_default(),
+                hold_time: Duration::from_millis(d.hold_time.unwrap_or_default()),
+            },
+        }
+    }
+
+    /// Creates the settings for making pitch wheels usable as bipolar control.
+    ///
+    /// Returns `None` if the pitch bend values should be processed as is.
+    pub fn create_pitch_bend_settings(&self) -> Option<PitchBendSettings> {
+        if !self.supports_pitch_bend_behavior() {
+            return None;
+        }
+        let behavior = self.pitch_bend_behavior?;
+        let settings = PitchBendSettings {
+            center_dead_zone: behavior.center_dead_zone.unwrap_or_default(),
+            mode: behavior.mode.unwrap_or_default(),
+        };
+        Some(settings)
+    }
+
+    fn create_source_internal(
+        &self,
+        message_templates: &MessageTemplatesModel,
+    ) -> Option<CompoundMappingSource> {
         use SourceCategory::*;
         let source = match self.category {
             Midi => {
@@ -620,8 +858,21 @@ impl SourceModel {
                     ClockTransport => MidiSource::ClockTransport {
                         message: self.midi_clock_transport_message,
                     },
+                    MachineControl => {
+                        let source = MidiMachineControlSource::new(
+                            self.midi_message_number
+                                .map(|n| n.get())
+                                .unwrap_or(MMC_ALL_CALL_DEVICE_ID),
+                            self.midi_machine_control_command,
+                        );
+                        return Some(CompoundMappingSource::MidiMachineControl(source));
+                    }
                     Raw => MidiSource::Raw {
-                        pattern: self.raw_midi_pattern.parse().unwrap_or_default(),
+                        pattern: message_templates
+                            .expand_raw_midi_pattern(&self.raw_midi_pattern)
+                            .ok()
+                            .and_then(|p| p.parse().ok())
+                            .unwrap_or_default(),
                         custom_character: self.custom_character,
                     },
                     Script => MidiSource::Script {
@@ -653,7 +904,10 @@ impl SourceModel {
             }
             Osc => {
                 let osc_source = OscSource::new(
-                    self.osc_address_pattern.clone(),
+                    message_templates
+                        .expand_osc_address_pattern(&self.osc_address_pattern)
+                        .map(|p| p.into_owned())
+                        .unwrap_or_default(),
                     self.osc_arg_descriptor(),
                     self.osc_feedback_args
                         .iter()
@@ -672,15 +926,33 @@ impl SourceModel {
                         ReaperSource::RealearnParameter(self.create_realearn_parameter_source())
                     }
                     Speech => ReaperSource::Speech(SpeechSource::new()),
+                    OneShotTimer => ReaperSource::OneShotTimer(OneShotTimerSource::new(
+                        Duration::from_millis(self.timer_millis),
+                    )),
+                    Lfo => ReaperSource::Lfo(LfoSource::new(self.lfo_shape, self.lfo_period)),
+                    Random => ReaperSource::Random(RandomSource::new(Duration::from_millis(
+                        self.timer_millis,
+                    ))),
                 };
                 CompoundMappingSource::Reaper(reaper_source)
             }
             Never => CompoundMappingSource::Never,
             Keyboard => CompoundMappingSource::Key(self.create_key_source()?),
+            Audio => CompoundMappingSource::Audio(self.create_audio_source()),
         };
         Some(source)
     }
 
+    fn create_audio_source(&self) -> AudioSource {
+        AudioSource::new(
+            self.audio_input_kind,
+            self.audio_channel,
+            self.audio_level_measurement,
+            Duration::from_millis(self.audio_attack_millis),
+            Duration::from_millis(self.audio_release_millis),
+        )
+    }
+
     pub fn create_key_source(&self) -> Option<KeySource> {
         Some(KeySource::new(self.keystroke?))
     }
@@ -760,7 +1032,7 @@ impl SourceModel {
 
     pub fn supports_type(&self) -> bool {
         use SourceCategory::*;
-        matches!(self.category, Midi | Virtual | Reaper)
+        matches!(self.category, Midi | Virtual | Reaper | Audio)
     }
 
     pub fn supports_channel(&self) -> bool {
@@ -770,6 +1042,19 @@ impl SourceModel {
         self.midi_source_type.supports_channel()
     }
 
+    pub fn supports_debounce(&self) -> bool {
+        match self.category {
+            SourceCategory::Midi => self.midi_source_type.supports_debounce(),
+            SourceCategory::Osc => true,
+            _ => false,
+        }
+    }
+
+    pub fn supports_pitch_bend_behavior(&self) -> bool {
+        self.category == SourceCategory::Midi
+            && self.midi_source_type == MidiSourceType::PitchBendChangeValue
+    }
+
     pub fn supports_osc_arg_value_range(&self) -> bool {
         self.category == SourceCategory::Osc
             && self.osc_arg_index.is_some()
@@ -859,6 +1144,17 @@ impl Display for SourceModel {
                     ]
                 }
                 MidiSourceType::ClockTempo => vec!["MIDI clock".into(), "Tempo".into()],
+                MidiSourceType::MachineControl => {
+                    let device_label = match self.midi_message_number {
+                        None => "All devices".into(),
+                        Some(n) => format!("Device {}", n.get()).into(),
+                    };
+                    vec![
+                        "MMC".into(),
+                        self.midi_machine_control_command.to_string().into(),
+                        device_label,
+                    ]
+                }
                 MidiSourceType::ClockTransport => {
                     vec![
                         "MIDI clock".into(),
@@ -899,9 +1195,17 @@ impl Display for SourceModel {
             Reaper => {
                 let type_label = self.reaper_source_type.to_string().into();
                 match self.reaper_source_type {
-                    ReaperSourceType::Timer => {
+                    ReaperSourceType::Timer
+                    | ReaperSourceType::OneShotTimer
+                    | ReaperSourceType::Random => {
                         vec![type_label, format!("{} ms", self.timer_millis).into()]
                     }
+                    ReaperSourceType::Lfo => {
+                        vec![
+                            type_label,
+                            format!("{} ({} beats)", self.lfo_shape, self.lfo_period).into(),
+                        ]
+                    }
                     ReaperSourceType::RealearnParameter => {
                         vec![
                             type_label,
@@ -921,6 +1225,15 @@ impl Display for SourceModel {
                     .unwrap_or_else(|| Cow::Borrowed(KEY_UNDEFINED_LABEL));
                 vec![text]
             }
+            Audio => vec![
+                self.audio_input_kind.to_string().into(),
+                format!(
+                    "Channel {} ({})",
+                    self.audio_channel + 1,
+                    self.audio_level_measurement
+                )
+                .into(),
+            ],
         };
         let non_empty_lines: Vec<_> = lines.into_iter().filter(|l| !l.is_empty()).collect();
         write!(f, "{}", non_empty_lines.join("\n"))
@@ -959,6 +1272,9 @@ pub enum SourceCategory {
     #[serde(rename = "reaper")]
     #[display(fmt = "REAPER")]
     Reaper,
+    #[serde(rename = "audio")]
+    #[display(fmt = "Audio input")]
+    Audio,
     #[serde(rename = "virtual")]
     #[display(fmt = "Virtual")]
     Virtual,
@@ -982,6 +1298,7 @@ impl SourceCategory {
                 Osc => true,
                 Reaper => true,
                 Keyboard => true,
+                Audio => false,
                 Virtual => false,
             },
             Compartment::Main => true,
@@ -1039,6 +1356,8 @@ pub enum MidiSourceType {
     Display = 12,
     #[display(fmt = "Specific program change")]
     SpecificProgramChange = 13,
+    #[display(fmt = "MIDI Machine Control (MMC)")]
+    MachineControl = 14,
 }
 
 impl Default for MidiSourceType {
@@ -1063,7 +1382,13 @@ impl MidiSourceType {
             ParameterNumberValue { .. } => MidiSourceType::ParameterNumberValue,
             ClockTempo => MidiSourceType::ClockTempo,
             ClockTransport { .. } => MidiSourceType::ClockTransport,
-            Raw { .. } => MidiSourceType::Raw,
+            Raw { pattern, .. } => {
+                if parse_midi_machine_control_pattern(&pattern.to_string()).is_some() {
+                    MidiSourceType::MachineControl
+                } else {
+                    MidiSourceType::Raw
+                }
+            }
             Script { .. } => MidiSourceType::Script,
             Display { .. } => MidiSourceType::Display,
         }
@@ -1076,6 +1401,7 @@ impl MidiSourceType {
             NoteVelocity | PolyphonicKeyPressureAmount => "Note number",
             ParameterNumberValue => "Number",
             SpecificProgramChange => "Program",
+            MachineControl => "Device ID",
             _ => "",
         }
     }
@@ -1100,7 +1426,11 @@ impl MidiSourceType {
         use MidiSourceType::*;
         matches!(
             self,
-            ControlChangeValue | NoteVelocity | PolyphonicKeyPressureAmount | SpecificProgramChange
+            ControlChangeValue
+                | NoteVelocity
+                | PolyphonicKeyPressureAmount
+                | SpecificProgramChange
+                | MachineControl
         )
     }
 
@@ -1117,6 +1447,24 @@ impl MidiSourceType {
         self.supports_parameter_number_message_props()
     }
 
+    /// Returns whether this source type can emit continuous absolute values which might flutter.
+    pub fn supports_debounce(self) -> bool {
+        use MidiSourceType::*;
+        matches!(
+            self,
+            ControlChangeValue
+                | PitchBendChangeValue
+                | ChannelPressureAmount
+                | PolyphonicKeyPressureAmount
+                | ParameterNumberValue
+        )
+    }
+
+    pub fn supports_velocity_feedback(self) -> bool {
+        use MidiSourceType::*;
+        matches!(self, NoteVelocity | PolyphonicKeyPressureAmount)
+    }
+
     pub fn supports_custom_character(self) -> bool {
         use MidiSourceType::*;
         matches!(self, ControlChangeValue | ParameterNumberValue | Raw)
@@ -1133,7 +1481,7 @@ impl MidiSourceType {
 
     pub fn supports_feedback(self) -> bool {
         use MidiSourceType::*;
-        !matches!(self, ClockTempo | ClockTransport)
+        !matches!(self, ClockTempo | ClockTransport | MachineControl)
     }
 }
 
@@ -1159,6 +1507,9 @@ pub enum VirtualControlElementType {
     #[serde(rename = "button")]
     #[display(fmt = "Button")]
     Button,
+    #[serde(rename = "display")]
+    #[display(fmt = "Display")]
+    Display,
 }
 
 impl Default for VirtualControlElementType {
@@ -1169,18 +1520,19 @@ impl Default for VirtualControlElementType {
 
 impl VirtualControlElementType {
     pub fn from_source(source: &VirtualSource) -> VirtualControlElementType {
-        use VirtualControlElement::*;
-        match source.control_element() {
-            Multi(_) => VirtualControlElementType::Multi,
-            Button(_) => VirtualControlElementType::Button,
-        }
+        Self::from_control_element(source.control_element())
     }
 
     pub fn from_target(target: &VirtualTarget) -> VirtualControlElementType {
+        Self::from_control_element(target.control_element())
+    }
+
+    pub fn from_control_element(element: VirtualControlElement) -> VirtualControlElementType {
         use VirtualControlElement::*;
-        match target.control_element() {
+        match element {
             Multi(_) => VirtualControlElementType::Multi,
             Button(_) => VirtualControlElementType::Button,
+            Display(_) => VirtualControlElementType::Display,
         }
     }
 
@@ -1189,6 +1541,7 @@ impl VirtualControlElementType {
         match self {
             Multi => VirtualControlElement::Multi(id),
             Button => VirtualControlElement::Button(id),
+            Display => VirtualControlElement::Display(id),
         }
     }
 }
@@ -1224,6 +1577,15 @@ pub enum ReaperSourceType {
     #[serde(rename = "speech")]
     #[display(fmt = "Speech (feedback only, no Linux)")]
     Speech,
+    #[serde(rename = "one-shot-timer")]
+    #[display(fmt = "One-shot timer")]
+    OneShotTimer,
+    #[serde(rename = "lfo")]
+    #[display(fmt = "LFO")]
+    Lfo,
+    #[serde(rename = "random")]
+    #[display(fmt = "Random")]
+    Random,
 }
 
 impl Default for ReaperSourceType {
@@ -1241,13 +1603,22 @@ impl ReaperSourceType {
             Timer(_) => Self::Timer,
             RealearnParameter(_) => Self::RealearnParameter,
             Speech(_) => Self::Speech,
+            OneShotTimer(_) => Self::OneShotTimer,
+            Lfo(_) => Self::Lfo,
+            Random(_) => Self::Random,
         }
     }
 
     pub fn supports_control(self) -> bool {
         use ReaperSourceType::*;
         match self {
-            MidiDeviceChanges | RealearnInstanceStart | Timer | RealearnParameter => true,
+            MidiDeviceChanges
+            | RealearnInstanceStart
+            | Timer
+            | RealearnParameter
+            | OneShotTimer
+            | Lfo
+            | Random => true,
             Speech => false,
         }
     }
@@ -1255,7 +1626,13 @@ impl ReaperSourceType {
     pub fn supports_feedback(self) -> bool {
         use ReaperSourceType::*;
         match self {
-            MidiDeviceChanges | RealearnInstanceStart | Timer | RealearnParameter => false,
+            MidiDeviceChanges
+            | RealearnInstanceStart
+            | Timer
+            | RealearnParameter
+            | OneShotTimer
+            | Lfo
+            | Random => false,
             Speech => true,
         }
     }
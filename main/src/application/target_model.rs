@@ -7,51 +7,59 @@ use helgoboss_learn::{
 };
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
-    Action, BookmarkType, Fx, FxChain, FxParameter, Guid, Project, Track, TrackRoute,
-    TrackRoutePartner,
+    Action, BookmarkType, Fx, FxChain, FxChainContext, FxParameter, Guid, Project, Track,
+    TrackRoute, TrackRoutePartner,
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::application::{
-    Affected, Change, GetProcessingRelevance, ProcessingRelevance, VirtualControlElementType,
+    rank_by_name_similarity, Affected, Change, GetProcessingRelevance, ProcessingRelevance,
+    RemapCandidate, VirtualControlElementType,
 };
 use crate::domain::{
     find_bookmark, get_fx_name, get_fx_params, get_non_present_virtual_route_label,
     get_non_present_virtual_track_label, get_track_routes, ActionInvocationType, AnyOnParameter,
     Compartment, CompoundMappingTarget, Exclusivity, ExpressionEvaluator, ExtendedProcessorContext,
-    FeedbackResolution, FxDescriptor, FxDisplayType, FxParameterDescriptor, GroupId,
-    MappingSnapshotId, MouseActionType, OscDeviceId, PotFilterItemsTargetSettings,
-    ProcessorContext, RealearnTarget, ReaperTarget, ReaperTargetType, SeekOptions,
-    SendMidiDestination, SoloBehavior, Tag, TagScope, TouchedRouteParameterType,
-    TouchedTrackParameterType, TrackDescriptor, TrackExclusivity, TrackGangBehavior,
-    TrackRouteDescriptor, TrackRouteSelector, TrackRouteType, TransportAction,
-    UnresolvedActionTarget, UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
-    UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget,
-    UnresolvedBrowsePotFilterItemsTarget, UnresolvedBrowsePotPresetsTarget,
+    FeedbackResolution, FxDescriptor, FxDisplayType, FxParameterDescriptor, FxParameterSnapshotId,
+    GroupId, MappingSnapshotId, MissingTargetObject, MouseActionType, OscDeviceId,
+    ParameterThinning, PotFilterItemsTargetSettings, ProcessorContext, RealearnTarget,
+    ReaperTarget, ReaperTargetType, SeekOptions, SendMidiDestination, SoloBehavior, Tag, TagScope,
+    TargetCharacter, TouchedRouteParameterType, TouchedTrackParameterType, TrackDescriptor,
+    TrackExclusivity, TrackGangBehavior, TrackRouteDescriptor, TrackRouteSelector, TrackRouteType,
+    TransportAction, UnresolvedActionTarget, UnresolvedAllTrackFxEnableTarget,
+    UnresolvedAnyOnTarget, UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseFxsTarget,
+    UnresolvedBrowseGroupTarget, UnresolvedBrowsePotFilterItemsTarget,
+    UnresolvedBrowsePotPresetsTarget, UnresolvedBrowseProjectTabsTarget,
     UnresolvedBrowseTracksTarget, UnresolvedClipColumnTarget, UnresolvedClipManagementTarget,
     UnresolvedClipMatrixTarget, UnresolvedClipRowTarget, UnresolvedClipSeekTarget,
     UnresolvedClipTransportTarget, UnresolvedClipVolumeTarget, UnresolvedCompoundMappingTarget,
     UnresolvedDummyTarget, UnresolvedEnableInstancesTarget, UnresolvedEnableMappingsTarget,
-    UnresolvedFxEnableTarget, UnresolvedFxOnlineTarget, UnresolvedFxOpenTarget,
-    UnresolvedFxParameterTarget, UnresolvedFxParameterTouchStateTarget, UnresolvedFxPresetTarget,
-    UnresolvedFxToolTarget, UnresolvedGoToBookmarkTarget, UnresolvedLastTouchedTarget,
+    UnresolvedFeedbackBrightnessTarget, UnresolvedFxEnableTarget, UnresolvedFxOnlineTarget,
+    UnresolvedFxOpenTarget, UnresolvedFxParameterTarget, UnresolvedFxParameterTouchStateTarget,
+    UnresolvedFxPresetTarget, UnresolvedFxToolTarget, UnresolvedGlobalParameterValueTarget,
+    UnresolvedGoToBookmarkTarget, UnresolvedItemPropertyTarget, UnresolvedLastTouchedTarget,
     UnresolvedLoadFxSnapshotTarget, UnresolvedLoadMappingSnapshotTarget,
-    UnresolvedLoadPotPresetTarget, UnresolvedMidiSendTarget, UnresolvedMouseTarget,
-    UnresolvedOscSendTarget, UnresolvedPlayrateTarget, UnresolvedPreviewPotPresetTarget,
-    UnresolvedReaperTarget, UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget,
+    UnresolvedLoadPotPresetTarget, UnresolvedLoopRegionTarget, UnresolvedMidiEditorActionTarget,
+    UnresolvedMidiSendTarget, UnresolvedMouseTarget, UnresolvedNavigateProjectTabsTarget,
+    UnresolvedNavigateTracksTarget, UnresolvedOscSendTarget, UnresolvedPlayrateTarget,
+    UnresolvedPreviewPotPresetTarget, UnresolvedReaperTarget,
+    UnresolvedRecallFxParameterSnapshotTarget, UnresolvedRenderProjectTarget,
+    UnresolvedResyncFeedbackTarget, UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget,
     UnresolvedRouteMuteTarget, UnresolvedRoutePanTarget, UnresolvedRoutePhaseTarget,
-    UnresolvedRouteTouchStateTarget, UnresolvedRouteVolumeTarget, UnresolvedSeekTarget,
-    UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget, UnresolvedTrackArmTarget,
-    UnresolvedTrackAutomationModeTarget, UnresolvedTrackMonitoringModeTarget,
-    UnresolvedTrackMuteTarget, UnresolvedTrackPanTarget, UnresolvedTrackParentSendTarget,
-    UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget, UnresolvedTrackSelectionTarget,
-    UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget, UnresolvedTrackToolTarget,
-    UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget, UnresolvedTrackWidthTarget,
-    UnresolvedTransportTarget, VirtualChainFx, VirtualClipColumn, VirtualClipRow, VirtualClipSlot,
-    VirtualControlElement, VirtualControlElementId, VirtualFx, VirtualFxParameter,
-    VirtualMappingSnapshotIdForLoad, VirtualMappingSnapshotIdForTake, VirtualTarget, VirtualTrack,
-    VirtualTrackRoute,
+    UnresolvedRouteTouchStateTarget, UnresolvedRouteVolumeTarget,
+    UnresolvedSaveFxParameterSnapshotTarget, UnresolvedSeekTarget,
+    UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget, UnresolvedToggleRenderQueueTarget,
+    UnresolvedToolbarToggleTarget, UnresolvedTrackArmTarget, UnresolvedTrackAutomationModeTarget,
+    UnresolvedTrackMonitoringModeTarget, UnresolvedTrackMuteTarget, UnresolvedTrackPanTarget,
+    UnresolvedTrackParentSendTarget, UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget,
+    UnresolvedTrackSelectionTarget, UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget,
+    UnresolvedTrackToolTarget, UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget,
+    UnresolvedTrackVolumeTrimTarget, UnresolvedTrackWidthTarget, UnresolvedTransportTarget,
+    UnresolvedWriteEnvelopePointsTarget, VirtualChainFx, VirtualClipColumn, VirtualClipRow,
+    VirtualClipSlot, VirtualControlElement, VirtualControlElementId, VirtualDisplayLayout,
+    VirtualFx, VirtualFxParameter, VirtualMappingSnapshotIdForLoad,
+    VirtualMappingSnapshotIdForTake, VirtualTarget, VirtualTrack, VirtualTrackRoute,
 };
 use serde_repr::*;
 use std::borrow::Cow;
@@ -63,18 +71,19 @@ use playtime_clip_engine::base::ClipTransportOptions;
 use realearn_api::persistence::{
     Axis, BrowseTracksMode, ClipColumnAction, ClipColumnDescriptor, ClipColumnTrackContext,
     ClipManagementAction, ClipMatrixAction, ClipRowAction, ClipRowDescriptor, ClipSlotDescriptor,
-    ClipTransportAction, FxChainDescriptor, FxDescriptorCommons, FxToolAction,
-    MappingSnapshotDescForLoad, MappingSnapshotDescForTake, MonitoringMode, MouseAction,
-    MouseButton, PotFilterItemKind, SeekBehavior, TrackDescriptorCommons, TrackFxChain, TrackScope,
-    TrackToolAction,
+    ClipTransportAction, FxChainDescriptor, FxDescriptorCommons, FxToolAction, ItemPropertyKind,
+    MappingSnapshotDescForLoad, MappingSnapshotDescForTake, MidiEditorAction, MonitoringMode,
+    MouseAction, MouseButton, PotFilterItemKind, SeekBehavior, TrackDescriptorCommons,
+    TrackFxChain, TrackScope, TrackToolAction,
 };
 use reaper_medium::{
-    AutomationMode, BookmarkId, GlobalAutomationModeOverride, InputMonitoringMode, TrackArea,
-    TrackLocation, TrackSendDirection,
+    AutomationMode, BookmarkId, GlobalAutomationModeOverride, InputMonitoringMode,
+    MidiOutputDeviceId, TrackArea, TrackLocation, TrackSendDirection,
 };
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
+use std::time::Duration;
 use wildmatch::WildMatch;
 
 #[allow(clippy::enum_variant_names)]
@@ -83,6 +92,7 @@ pub enum TargetCommand {
     SetUnit(TargetUnit),
     SetControlElementType(VirtualControlElementType),
     SetControlElementId(VirtualControlElementId),
+    SetDisplayLayout(VirtualDisplayLayout),
     SetTargetType(ReaperTargetType),
     SetAction(Option<Action>),
     SetActionInvocationType(ActionInvocationType),
@@ -101,6 +111,8 @@ pub enum TargetCommand {
     SetParamName(String),
     SetParamExpression(String),
     SetRetrigger(bool),
+    SetLowLatency(bool),
+    SetParameterThinning(Option<ParameterThinning>),
     SetRouteSelectorType(TrackRouteSelectorType),
     SetRouteType(TrackRouteType),
     SetRouteId(Option<Guid>),
@@ -109,6 +121,7 @@ pub enum TargetCommand {
     SetRouteExpression(String),
     SetSeekBehavior(SeekBehavior),
     SetSoloBehavior(SoloBehavior),
+    SetSoloDefeat(bool),
     SetTrackExclusivity(TrackExclusivity),
     SetTrackToolAction(TrackToolAction),
     SetGangBehavior(TrackGangBehavior),
@@ -120,6 +133,7 @@ pub enum TargetCommand {
     SetTouchedTrackParameterType(TouchedTrackParameterType),
     SetTouchedRouteParameterType(TouchedRouteParameterType),
     SetBookmarkRef(u32),
+    SetBookmarkName(String),
     SetBookmarkType(BookmarkType),
     SetBookmarkAnchorType(BookmarkAnchorType),
     SetUseTimeSelection(bool),
@@ -132,12 +146,17 @@ pub enum TargetCommand {
     SetTrackArea(RealearnTrackArea),
     SetAutomationMode(RealearnAutomationMode),
     SetMonitoringMode(MonitoringMode),
+    SetItemProperty(ItemPropertyKind),
+    SetMidiEditorAction(MidiEditorAction),
     SetAutomationModeOverrideType(AutomationModeOverrideType),
     SetFxDisplayType(FxDisplayType),
     SetScrollArrangeView(bool),
     SetScrollMixer(bool),
+    SetTrackBankSize(u32),
+    SetWrapAround(bool),
     SetRawMidiPattern(String),
     SetSendMidiDestination(SendMidiDestination),
+    SetMidiOutputDeviceId(Option<MidiOutputDeviceId>),
     SetOscAddressPattern(String),
     SetOscArgIndex(Option<u32>),
     SetOscArgTypeTag(OscTypeTag),
@@ -167,6 +186,9 @@ pub enum TargetCommand {
     SetMappingSnapshotTypeForTake(MappingSnapshotTypeForTake),
     SetMappingSnapshotId(Option<MappingSnapshotId>),
     SetMappingSnapshotDefaultValue(Option<AbsoluteValue>),
+    SetGlobalParameterName(String),
+    SetFxParameterSnapshotId(FxParameterSnapshotId),
+    SetGlideTime(Duration),
     SetPotFilterItemKind(PotFilterItemKind),
 }
 
@@ -176,6 +198,7 @@ pub enum TargetProp {
     Unit,
     ControlElementType,
     ControlElementId,
+    DisplayLayout,
     TargetType,
     Action,
     ActionInvocationType,
@@ -198,6 +221,8 @@ pub enum TargetProp {
     ParamName,
     ParamExpression,
     Retrigger,
+    LowLatency,
+    ParameterThinning,
     RouteSelectorType,
     RouteType,
     RouteId,
@@ -205,6 +230,7 @@ pub enum TargetProp {
     RouteName,
     RouteExpression,
     SoloBehavior,
+    SoloDefeat,
     SeekBehavior,
     TrackExclusivity,
     TrackToolAction,
@@ -217,6 +243,7 @@ pub enum TargetProp {
     TouchedTrackParameterType,
     TouchedRouteParameterType,
     BookmarkRef,
+    BookmarkName,
     BookmarkType,
     BookmarkAnchorType,
     UseTimeSelection,
@@ -229,12 +256,17 @@ pub enum TargetProp {
     TrackArea,
     AutomationMode,
     MonitoringMode,
+    ItemProperty,
+    MidiEditorAction,
     AutomationModeOverrideType,
     FxDisplayType,
     ScrollArrangeView,
     ScrollMixer,
+    TrackBankSize,
+    WrapAround,
     RawMidiPattern,
     SendMidiDestination,
+    MidiOutputDeviceId,
     OscAddressPattern,
     OscArgIndex,
     OscArgTypeTag,
@@ -264,6 +296,9 @@ pub enum TargetProp {
     MappingSnapshotTypeForTake,
     MappingSnapshotId,
     MappingSnapshotDefaultValue,
+    GlobalParameterName,
+    FxParameterSnapshotId,
+    GlideTime,
     PotFilterItemKind,
 }
 
@@ -299,6 +334,10 @@ impl<'a> Change<'a> for TargetModel {
                 self.control_element_id = v;
                 One(P::ControlElementId)
             }
+            C::SetDisplayLayout(v) => {
+                self.display_layout = v;
+                One(P::DisplayLayout)
+            }
             C::SetTargetType(v) => {
                 self.r#type = v;
                 One(P::TargetType)
@@ -371,6 +410,14 @@ impl<'a> Change<'a> for TargetModel {
                 self.retrigger = v;
                 One(P::Retrigger)
             }
+            C::SetLowLatency(v) => {
+                self.low_latency = v;
+                One(P::LowLatency)
+            }
+            C::SetParameterThinning(v) => {
+                self.parameter_thinning = v;
+                One(P::ParameterThinning)
+            }
             C::SetRouteSelectorType(v) => {
                 self.route_selector_type = v;
                 One(P::RouteSelectorType)
@@ -399,6 +446,10 @@ impl<'a> Change<'a> for TargetModel {
                 self.solo_behavior = v;
                 One(P::SoloBehavior)
             }
+            C::SetSoloDefeat(v) => {
+                self.solo_defeat = v;
+                One(P::SoloDefeat)
+            }
             C::SetSeekBehavior(v) => {
                 self.seek_behavior = v;
                 One(P::SeekBehavior)
@@ -447,6 +498,10 @@ impl<'a> Change<'a> for TargetModel {
                 self.bookmark_ref = v;
                 One(P::BookmarkRef)
             }
+            C::SetBookmarkName(v) => {
+                self.bookmark_name = v;
+                One(P::BookmarkName)
+            }
             C::SetBookmarkType(v) => {
                 self.bookmark_type = v;
                 One(P::BookmarkType)
@@ -495,6 +550,14 @@ impl<'a> Change<'a> for TargetModel {
                 self.monitoring_mode = v;
                 One(P::MonitoringMode)
             }
+            C::SetItemProperty(v) => {
+                self.item_property = v;
+                One(P::ItemProperty)
+            }
+            C::SetMidiEditorAction(v) => {
+                self.midi_editor_action = v;
+                One(P::MidiEditorAction)
+            }
             C::SetAutomationModeOverrideType(v) => {
                 self.automation_mode_override_type = v;
                 One(P::AutomationModeOverrideType)
@@ -511,6 +574,14 @@ impl<'a> Change<'a> for TargetModel {
                 self.scroll_mixer = v;
                 One(P::ScrollMixer)
             }
+            C::SetTrackBankSize(v) => {
+                self.track_bank_size = v.max(1);
+                One(P::TrackBankSize)
+            }
+            C::SetWrapAround(v) => {
+                self.wrap_around = v;
+                One(P::WrapAround)
+            }
             C::SetRawMidiPattern(v) => {
                 self.raw_midi_pattern = v;
                 One(P::RawMidiPattern)
@@ -519,6 +590,10 @@ impl<'a> Change<'a> for TargetModel {
                 self.send_midi_destination = v;
                 One(P::SendMidiDestination)
             }
+            C::SetMidiOutputDeviceId(v) => {
+                self.midi_output_device_id = v;
+                One(P::MidiOutputDeviceId)
+            }
             C::SetOscAddressPattern(v) => {
                 self.osc_address_pattern = v;
                 One(P::OscAddressPattern)
@@ -587,6 +662,18 @@ impl<'a> Change<'a> for TargetModel {
                 self.mapping_snapshot_default_value = v;
                 One(P::MappingSnapshotDefaultValue)
             }
+            C::SetGlobalParameterName(v) => {
+                self.global_parameter_name = v;
+                One(P::GlobalParameterName)
+            }
+            C::SetFxParameterSnapshotId(v) => {
+                self.fx_parameter_snapshot_id = v.max(1);
+                One(P::FxParameterSnapshotId)
+            }
+            C::SetGlideTime(v) => {
+                self.glide_time = v;
+                One(P::GlideTime)
+            }
             C::SetClipSlot(s) => {
                 self.clip_slot = s;
                 One(P::ClipSlot)
@@ -653,6 +740,7 @@ pub struct TargetModel {
     // # For virtual targets
     control_element_type: VirtualControlElementType,
     control_element_id: VirtualControlElementId,
+    display_layout: VirtualDisplayLayout,
     // # For REAPER targets
     // TODO-low Rename this to reaper_target_type
     r#type: ReaperTargetType,
@@ -687,6 +775,9 @@ pub struct TargetModel {
     param_name: String,
     param_expression: String,
     retrigger: bool,
+    low_latency: bool,
+    /// `None` means that the instance default is used.
+    parameter_thinning: Option<ParameterThinning>,
     // # For track route targets
     route_selector_type: TrackRouteSelectorType,
     route_type: TrackRouteType,
@@ -697,6 +788,7 @@ pub struct TargetModel {
     touched_route_parameter_type: TouchedRouteParameterType,
     // # For track solo targets
     solo_behavior: SoloBehavior,
+    solo_defeat: bool,
     // # For seek and goto bookmark targets
     seek_behavior: SeekBehavior,
     // # For toggleable track targets
@@ -711,6 +803,7 @@ pub struct TargetModel {
     touched_track_parameter_type: TouchedTrackParameterType,
     // # For "Go to marker/region" target
     bookmark_ref: u32,
+    bookmark_name: String,
     bookmark_type: BookmarkType,
     bookmark_anchor_type: BookmarkAnchorType,
     // # For "Go to marker/region" target and "Seek" target
@@ -728,6 +821,10 @@ pub struct TargetModel {
     automation_mode: RealearnAutomationMode,
     // # For track monitoring mode target
     monitoring_mode: MonitoringMode,
+    // # For item property target
+    item_property: ItemPropertyKind,
+    // # For MIDI editor action target
+    midi_editor_action: MidiEditorAction,
     // # For automation mode override target
     automation_mode_override_type: AutomationModeOverrideType,
     // # For FX Open and Browse FXs target
@@ -735,9 +832,13 @@ pub struct TargetModel {
     // # For track selection related targets
     scroll_arrange_view: bool,
     scroll_mixer: bool,
+    // # For Navigate tracks target
+    track_bank_size: u32,
+    wrap_around: bool,
     // # For Send MIDI target
     raw_midi_pattern: String,
     send_midi_destination: SendMidiDestination,
+    midi_output_device_id: Option<MidiOutputDeviceId>,
     // # For Send OSC target
     osc_address_pattern: String,
     osc_arg_index: Option<u32>,
@@ -768,6 +869,11 @@ pub struct TargetModel {
     mapping_snapshot_type_for_take: MappingSnapshotTypeForTake,
     mapping_snapshot_id: Option<MappingSnapshotId>,
     mapping_snapshot_default_value: Option<AbsoluteValue>,
+    // # For global parameter targets
+    global_parameter_name: String,
+    // # For FX parameter snapshot targets
+    fx_parameter_snapshot_id: FxParameterSnapshotId,
+    glide_time: Duration,
     exclusivity: Exclusivity,
     group_id: GroupId,
     active_mappings_only: bool,
@@ -782,6 +888,7 @@ impl Default for TargetModel {
             unit: Default::default(),
             control_element_type: VirtualControlElementType::default(),
             control_element_id: Default::default(),
+            display_layout: Default::default(),
             r#type: ReaperTargetType::Dummy,
             action: None,
             action_invocation_type: ActionInvocationType::default(),
@@ -804,6 +911,8 @@ impl Default for TargetModel {
             param_name: "".to_owned(),
             param_expression: "".to_owned(),
             retrigger: false,
+            low_latency: false,
+            parameter_thinning: None,
             route_selector_type: Default::default(),
             route_type: Default::default(),
             route_id: None,
@@ -812,6 +921,7 @@ impl Default for TargetModel {
             route_expression: Default::default(),
             touched_route_parameter_type: Default::default(),
             solo_behavior: Default::default(),
+            solo_defeat: false,
             seek_behavior: Default::default(),
             track_exclusivity: Default::default(),
             transport_action: TransportAction::default(),
@@ -819,6 +929,7 @@ impl Default for TargetModel {
             fx_snapshot: None,
             touched_track_parameter_type: Default::default(),
             bookmark_ref: 0,
+            bookmark_name: String::new(),
             bookmark_type: BookmarkType::Marker,
             bookmark_anchor_type: Default::default(),
             use_time_selection: false,
@@ -831,12 +942,17 @@ impl Default for TargetModel {
             track_area: Default::default(),
             automation_mode: Default::default(),
             monitoring_mode: Default::default(),
+            item_property: Default::default(),
+            midi_editor_action: Default::default(),
             automation_mode_override_type: Default::default(),
             fx_display_type: Default::default(),
             scroll_arrange_view: false,
             scroll_mixer: false,
+            track_bank_size: 1,
+            wrap_around: false,
             raw_midi_pattern: Default::default(),
             send_midi_destination: Default::default(),
+            midi_output_device_id: None,
             osc_address_pattern: "".to_owned(),
             osc_arg_index: Some(0),
             osc_arg_type_tag: Default::default(),
@@ -851,6 +967,9 @@ impl Default for TargetModel {
             mapping_snapshot_type_for_take: MappingSnapshotTypeForTake::LastLoaded,
             mapping_snapshot_id: None,
             mapping_snapshot_default_value: None,
+            global_parameter_name: "".to_owned(),
+            fx_parameter_snapshot_id: 1,
+            glide_time: Duration::ZERO,
             exclusivity: Default::default(),
             group_id: Default::default(),
             active_mappings_only: false,
@@ -893,6 +1012,10 @@ impl TargetModel {
         self.control_element_id
     }
 
+    pub fn display_layout(&self) -> VirtualDisplayLayout {
+        self.display_layout
+    }
+
     pub fn target_type(&self) -> ReaperTargetType {
         self.r#type
     }
@@ -913,6 +1036,10 @@ impl TargetModel {
         self.track_type
     }
 
+    pub fn track_id(&self) -> Option<Guid> {
+        self.track_id
+    }
+
     pub fn track_name(&self) -> &str {
         &self.track_name
     }
@@ -1001,6 +1128,10 @@ impl TargetModel {
         self.solo_behavior
     }
 
+    pub fn solo_defeat(&self) -> bool {
+        self.solo_defeat
+    }
+
     pub fn seek_behavior(&self) -> SeekBehavior {
         self.seek_behavior
     }
@@ -1065,7 +1196,24 @@ impl TargetModel {
         self.bookmark_ref
     }
 
+    pub fn bookmark_name(&self) -> &str {
+        &self.bookmark_name
+    }
+
+    /// Returns how the bookmark is referenced, e.g. `#3` for the third bookmark.
+    fn bookmark_ref_label(&self) -> String {
+        match self.bookmark_anchor_type {
+            BookmarkAnchorType::Id => self.bookmark_ref.to_string(),
+            BookmarkAnchorType::Index => format!("#{}", self.bookmark_ref),
+            BookmarkAnchorType::Name => format!("\"{}\"", self.bookmark_name),
+        }
+    }
+
     pub fn bookmark_type(&self) -> BookmarkType {
+        if self.category == TargetCategory::Reaper && self.r#type == ReaperTargetType::LoopRegion {
+            // Only regions can be looped
+            return BookmarkType::Region;
+        }
         self.bookmark_type
     }
 
@@ -1113,6 +1261,14 @@ impl TargetModel {
         self.monitoring_mode
     }
 
+    pub fn item_property(&self) -> ItemPropertyKind {
+        self.item_property
+    }
+
+    pub fn midi_editor_action(&self) -> MidiEditorAction {
+        self.midi_editor_action
+    }
+
     pub fn automation_mode_override_type(&self) -> AutomationModeOverrideType {
         self.automation_mode_override_type
     }
@@ -1129,6 +1285,14 @@ impl TargetModel {
         self.scroll_mixer
     }
 
+    pub fn track_bank_size(&self) -> u32 {
+        self.track_bank_size
+    }
+
+    pub fn wrap_around(&self) -> bool {
+        self.wrap_around
+    }
+
     pub fn raw_midi_pattern(&self) -> &str {
         &self.raw_midi_pattern
     }
@@ -1137,6 +1301,10 @@ impl TargetModel {
         self.send_midi_destination
     }
 
+    pub fn midi_output_device_id(&self) -> Option<MidiOutputDeviceId> {
+        self.midi_output_device_id
+    }
+
     pub fn osc_address_pattern(&self) -> &str {
         &self.osc_address_pattern
     }
@@ -1145,6 +1313,18 @@ impl TargetModel {
         self.mapping_snapshot_default_value
     }
 
+    pub fn global_parameter_name(&self) -> &str {
+        &self.global_parameter_name
+    }
+
+    pub fn fx_parameter_snapshot_id(&self) -> FxParameterSnapshotId {
+        self.fx_parameter_snapshot_id
+    }
+
+    pub fn glide_time(&self) -> Duration {
+        self.glide_time
+    }
+
     pub fn osc_arg_index(&self) -> Option<u32> {
         self.osc_arg_index
     }
@@ -1173,6 +1353,14 @@ impl TargetModel {
         self.retrigger
     }
 
+    pub fn low_latency(&self) -> bool {
+        self.low_latency
+    }
+
+    pub fn parameter_thinning(&self) -> Option<ParameterThinning> {
+        self.parameter_thinning
+    }
+
     pub fn tags(&self) -> &[Tag] {
         &self.tags
     }
@@ -1248,6 +1436,49 @@ impl TargetModel {
         Ok(self.set_virtual_route(virtual_route))
     }
 
+    /// Makes the target point to the FX with the remembered ID again if that FX has been moved to
+    /// another track or another position, e.g. by dragging it from one track to another.
+    ///
+    /// Only affects targets which refer to the FX by ID and whose FX can't be resolved anymore.
+    /// If the FX is now on another track, the track selector is changed as well, but only if it
+    /// refers to the track by ID. Returns `Ok(None)` if there's nothing to fix.
+    pub fn fix_fx_reference(
+        &mut self,
+        compartment: Compartment,
+        context: ExtendedProcessorContext,
+    ) -> Result<Option<Affected<TargetProp>>, &'static str> {
+        if !self.supports_fx() || self.fx_type != VirtualFxType::ById {
+            return Ok(None);
+        }
+        let fx_id = match self.fx_id {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+        let target = self.with_context(context, compartment);
+        if let Ok(fx) = target.first_fx() {
+            if fx.get_or_query_guid().ok() == Some(fx_id) {
+                return Ok(None);
+            }
+        }
+        let current_track = target.first_effective_track().ok();
+        let project = context.context().project_or_current_project();
+        let fx = find_fx_in_project(project, &fx_id).ok_or("FX not found in project")?;
+        let track = fx.track().ok_or("FX is not on a track")?;
+        if current_track.as_ref() != Some(track) {
+            if self.track_type != VirtualTrackType::ById {
+                return Err("FX is on another track but track is not referred to by ID");
+            }
+            let _ =
+                self.set_virtual_track(VirtualTrack::ById(*track.guid()), Some(context.context()));
+        }
+        let virtual_fx = VirtualFx::ChainFx {
+            is_input_fx: fx.is_input_fx(),
+            chain_fx: VirtualChainFx::ById(fx_id, Some(fx.index())),
+        };
+        let _ = self.set_virtual_fx(virtual_fx, context, compartment);
+        Ok(Some(Affected::Multiple))
+    }
+
     pub fn take_fx_snapshot(
         &self,
         context: ExtendedProcessorContext,
@@ -1650,6 +1881,9 @@ impl TargetModel {
                 self.action = Some(t.action.clone());
                 self.action_invocation_type = t.invocation_type;
             }
+            ToolbarToggle(t) => {
+                self.action = Some(t.action.clone());
+            }
             FxParameter(t) => {
                 self.param_type = VirtualFxParameterType::ById;
                 self.param_index = t.param.index();
@@ -1659,17 +1893,29 @@ impl TargetModel {
             }
             TrackSolo(t) => {
                 self.solo_behavior = t.behavior;
+                self.solo_defeat = t.solo_defeat;
             }
             GoToBookmark(t) => {
                 self.bookmark_ref = t.index;
                 self.bookmark_type = t.bookmark_type;
             }
+            LoopRegion(t) => {
+                self.bookmark_anchor_type = BookmarkAnchorType::Index;
+                self.bookmark_ref = t.position.get() - 1;
+                self.seek_play = t.start_playback;
+            }
             TrackAutomationMode(t) => {
                 self.automation_mode = RealearnAutomationMode::from_reaper(t.mode);
             }
             TrackMonitoringMode(t) => {
                 self.monitoring_mode = convert_monitoring_mode_to_realearn(t.mode);
             }
+            ItemProperty(t) => {
+                self.item_property = t.property;
+            }
+            MidiEditorAction(t) => {
+                self.midi_editor_action = t.action;
+            }
             RouteAutomationMode(t) => {
                 self.automation_mode = RealearnAutomationMode::from_reaper(t.mode);
             }
@@ -2147,6 +2393,13 @@ impl TargetModel {
                         axis: self.axis,
                         button: self.mouse_button,
                     }),
+                    ToolbarToggle => {
+                        // The action doesn't need to be available yet. Extensions might register
+                        // their actions after ReaLearn has been loaded.
+                        UnresolvedReaperTarget::ToolbarToggle(UnresolvedToolbarToggleTarget {
+                            action: self.action.clone().ok_or("action not set")?,
+                        })
+                    }
                     Action => UnresolvedReaperTarget::Action(UnresolvedActionTarget {
                         action: self.resolved_action()?,
                         invocation_type: self.action_invocation_type,
@@ -2161,6 +2414,8 @@ impl TargetModel {
                             fx_parameter_descriptor: self.fx_parameter_descriptor()?,
                             poll_for_feedback: self.poll_for_feedback,
                             retrigger: self.retrigger,
+                            low_latency: self.low_latency,
+                            thinning: self.parameter_thinning,
                         })
                     }
                     FxParameterTouchState => UnresolvedReaperTarget::FxParameterTouchState(
@@ -2174,6 +2429,11 @@ impl TargetModel {
                             gang_behavior: self.fixed_gang_behavior(),
                         })
                     }
+                    TrackVolumeTrim => {
+                        UnresolvedReaperTarget::TrackVolumeTrim(UnresolvedTrackVolumeTrimTarget {
+                            track_descriptor: self.track_descriptor()?,
+                        })
+                    }
                     TrackTool => UnresolvedReaperTarget::TrackTool(UnresolvedTrackToolTarget {
                         track_descriptor: self.track_descriptor()?,
                         action: self.track_tool_action,
@@ -2251,6 +2511,7 @@ impl TargetModel {
                     TrackSolo => UnresolvedReaperTarget::TrackSolo(UnresolvedTrackSoloTarget {
                         track_descriptor: self.track_descriptor()?,
                         behavior: self.solo_behavior,
+                        solo_defeat: self.solo_defeat,
                         exclusivity: self.track_exclusivity,
                         gang_behavior: self.fixed_gang_behavior(),
                     }),
@@ -2324,6 +2585,29 @@ impl TargetModel {
                             mode: self.browse_tracks_mode,
                         })
                     }
+                    NavigateTracks => {
+                        UnresolvedReaperTarget::NavigateTracks(UnresolvedNavigateTracksTarget {
+                            scroll_arrange_view: self.scroll_arrange_view,
+                            scroll_mixer: self.scroll_mixer,
+                            mode: self.browse_tracks_mode,
+                            bank_size: self.track_bank_size,
+                            wrap_around: self.wrap_around,
+                        })
+                    }
+                    BrowseProjectTabs => {
+                        UnresolvedReaperTarget::BrowseProjectTabs(UnresolvedBrowseProjectTabsTarget)
+                    }
+                    NavigateProjectTabs => UnresolvedReaperTarget::NavigateProjectTabs(
+                        UnresolvedNavigateProjectTabsTarget {
+                            wrap_around: self.wrap_around,
+                        },
+                    ),
+                    RenderProject => {
+                        UnresolvedReaperTarget::RenderProject(UnresolvedRenderProjectTarget)
+                    }
+                    ToggleRenderQueue => {
+                        UnresolvedReaperTarget::ToggleRenderQueue(UnresolvedToggleRenderQueueTarget)
+                    }
                     BrowseFxs => UnresolvedReaperTarget::BrowseFxs(UnresolvedBrowseFxsTarget {
                         track_descriptor: self.track_descriptor()?,
                         is_input_fx: self.fx_is_input_fx,
@@ -2350,6 +2634,19 @@ impl TargetModel {
                                 .clone(),
                         })
                     }
+                    SaveFxParameterSnapshot => UnresolvedReaperTarget::SaveFxParameterSnapshot(
+                        UnresolvedSaveFxParameterSnapshotTarget {
+                            fx_descriptor: self.fx_descriptor()?,
+                            snapshot_id: self.fx_parameter_snapshot_id,
+                        },
+                    ),
+                    RecallFxParameterSnapshot => UnresolvedReaperTarget::RecallFxParameterSnapshot(
+                        UnresolvedRecallFxParameterSnapshotTarget {
+                            fx_descriptor: self.fx_descriptor()?,
+                            snapshot_id: self.fx_parameter_snapshot_id,
+                            glide_time: self.glide_time,
+                        },
+                    ),
                     LastTouched => UnresolvedReaperTarget::LastTouched(UnresolvedLastTouchedTarget),
                     TrackTouchState => {
                         UnresolvedReaperTarget::TrackTouchState(UnresolvedTrackTouchStateTarget {
@@ -2358,16 +2655,33 @@ impl TargetModel {
                             exclusivity: self.track_exclusivity,
                         })
                     }
+                    ItemProperty => {
+                        UnresolvedReaperTarget::ItemProperty(UnresolvedItemPropertyTarget {
+                            property: self.item_property,
+                        })
+                    }
+                    MidiEditorAction => {
+                        UnresolvedReaperTarget::MidiEditorAction(UnresolvedMidiEditorActionTarget {
+                            action: self.midi_editor_action,
+                        })
+                    }
                     GoToBookmark => {
                         UnresolvedReaperTarget::GoToBookmark(UnresolvedGoToBookmarkTarget {
                             bookmark_type: self.bookmark_type,
                             bookmark_anchor_type: self.bookmark_anchor_type,
                             bookmark_ref: self.bookmark_ref,
+                            bookmark_name: self.bookmark_name.clone(),
                             set_time_selection: self.use_time_selection,
                             set_loop_points: self.use_loop_points,
                             seek_behavior: self.seek_behavior,
                         })
                     }
+                    LoopRegion => UnresolvedReaperTarget::LoopRegion(UnresolvedLoopRegionTarget {
+                        bookmark_anchor_type: self.bookmark_anchor_type,
+                        bookmark_ref: self.bookmark_ref,
+                        bookmark_name: self.bookmark_name.clone(),
+                        start_playback: self.seek_play,
+                    }),
                     Seek => UnresolvedReaperTarget::Seek(UnresolvedSeekTarget {
                         options: self.seek_options(),
                         behavior: self.seek_behavior,
@@ -2375,6 +2689,7 @@ impl TargetModel {
                     SendMidi => UnresolvedReaperTarget::SendMidi(UnresolvedMidiSendTarget {
                         pattern: self.raw_midi_pattern.parse().unwrap_or_default(),
                         destination: self.send_midi_destination,
+                        output_device_id: self.midi_output_device_id,
                     }),
                     SendOsc => UnresolvedReaperTarget::SendOsc(UnresolvedOscSendTarget {
                         address_pattern: self.osc_address_pattern.clone(),
@@ -2457,6 +2772,20 @@ impl TargetModel {
                         parameter: self.any_on_parameter,
                     }),
                     Dummy => UnresolvedReaperTarget::Dummy(UnresolvedDummyTarget),
+                    WriteEnvelopePoints => UnresolvedReaperTarget::WriteEnvelopePoints(
+                        UnresolvedWriteEnvelopePointsTarget,
+                    ),
+                    GlobalParameterValue => UnresolvedReaperTarget::GlobalParameterValue(
+                        UnresolvedGlobalParameterValueTarget {
+                            name: self.global_parameter_name.clone(),
+                        },
+                    ),
+                    FeedbackBrightness => UnresolvedReaperTarget::FeedbackBrightness(
+                        UnresolvedFeedbackBrightnessTarget,
+                    ),
+                    ResyncFeedback => {
+                        UnresolvedReaperTarget::ResyncFeedback(UnresolvedResyncFeedbackTarget)
+                    }
                     BrowsePotFilterItems => UnresolvedReaperTarget::BrowsePotFilterItems(
                         UnresolvedBrowsePotFilterItemsTarget {
                             settings: PotFilterItemsTargetSettings {
@@ -2479,7 +2808,8 @@ impl TargetModel {
                 Ok(UnresolvedCompoundMappingTarget::Reaper(target))
             }
             Virtual => {
-                let virtual_target = VirtualTarget::new(self.create_control_element());
+                let virtual_target = VirtualTarget::new(self.create_control_element())
+                    .with_display_layout(self.display_layout);
                 Ok(UnresolvedCompoundMappingTarget::Virtual(virtual_target))
             }
         }
@@ -2796,6 +3126,12 @@ impl<'a> Display for TargetModelFormatVeryShort<'a> {
                         None => write!(f, "Action {}", self.0.command_id_label()),
                         Some(a) => f.write_str(a.name().expect("should be available").to_str()),
                     },
+                    ToolbarToggle => {
+                        write!(f, "{}: {}", tt.short_name(), self.0.action_name_label())
+                    }
+                    GlobalParameterValue => {
+                        write!(f, "{}: {}", tt.short_name(), self.0.global_parameter_name)
+                    }
                     AutomationModeOverride => {
                         write!(f, "{}: ", tt.short_name())?;
                         use AutomationModeOverrideType::*;
@@ -2811,20 +3147,21 @@ impl<'a> Display for TargetModelFormatVeryShort<'a> {
                     AnyOn => {
                         write!(f, "{}", self.0.any_on_parameter)
                     }
+                    ItemProperty => {
+                        write!(f, "{}", self.0.item_property)
+                    }
+                    MidiEditorAction => {
+                        write!(f, "{}", self.0.midi_editor_action)
+                    }
                     GoToBookmark => {
                         let type_label = match self.0.bookmark_type {
                             BookmarkType::Marker => "Marker",
                             BookmarkType::Region => "Region",
                         };
-                        let bm_prefix = match self.0.bookmark_anchor_type {
-                            BookmarkAnchorType::Id => "",
-                            BookmarkAnchorType::Index => "#",
-                        };
-                        write!(
-                            f,
-                            "Go to {} {}{}",
-                            type_label, bm_prefix, self.0.bookmark_ref
-                        )
+                        write!(f, "Go to {} {}", type_label, self.0.bookmark_ref_label())
+                    }
+                    LoopRegion => {
+                        write!(f, "Loop region {}", self.0.bookmark_ref_label())
                     }
                     TrackAutomationMode => {
                         write!(f, "{}: {}", tt.short_name(), self.0.automation_mode)
@@ -2956,21 +3293,23 @@ impl<'a> TargetModelFormatMultiLine<'a> {
     fn bookmark_label(&self) -> String {
         // TODO-medium We should do this similar to the other target objects and introduce a
         //  virtual struct.
-        let bookmark_type = self.target.bookmark_type;
+        let bookmark_type = self.target.bookmark_type();
         let anchor_type = self.target.bookmark_anchor_type;
         let bookmark_ref = self.target.bookmark_ref;
+        let bookmark_name = &self.target.bookmark_name;
         match anchor_type {
-            BookmarkAnchorType::Id => {
+            BookmarkAnchorType::Id | BookmarkAnchorType::Name => {
                 let res = find_bookmark(
                     self.context.context().project_or_current_project(),
                     bookmark_type,
                     anchor_type,
                     bookmark_ref,
+                    bookmark_name,
                 );
                 if let Ok(res) = res {
                     get_bookmark_label_by_id(bookmark_type, res.basic_info.id, &res.bookmark.name())
                 } else {
-                    get_non_present_bookmark_label(anchor_type, bookmark_ref)
+                    get_non_present_bookmark_label(anchor_type, bookmark_ref, bookmark_name)
                 }
             }
             BookmarkAnchorType::Index => {
@@ -3012,7 +3351,7 @@ impl<'a> Display for TargetModelFormatMultiLine<'a> {
                     ClipTransport | ClipSeek | ClipVolume => {
                         write!(f, "{}", tt)
                     }
-                    Action => write!(
+                    Action | ToolbarToggle => write!(
                         f,
                         "{}\n{}\n{}",
                         tt,
@@ -3027,9 +3366,9 @@ impl<'a> Display for TargetModelFormatMultiLine<'a> {
                         self.fx_label(),
                         self.fx_param_label()
                     ),
-                    TrackTool | TrackVolume | TrackPeak | TrackPan | TrackWidth | TrackArm
-                    | TrackSelection | TrackMute | TrackPhase | TrackSolo | TrackShow
-                    | BrowseFxs | AllTrackFxEnable | TrackParentSend => {
+                    TrackTool | TrackVolume | TrackVolumeTrim | TrackPeak | TrackPan
+                    | TrackWidth | TrackArm | TrackSelection | TrackMute | TrackPhase
+                    | TrackSolo | TrackShow | BrowseFxs | AllTrackFxEnable | TrackParentSend => {
                         write!(f, "{}\nTrack {}", tt, self.track_label())
                     }
                     TrackAutomationMode => {
@@ -3072,6 +3411,14 @@ impl<'a> Display for TargetModelFormatMultiLine<'a> {
                             .map(|s| s.to_string())
                             .unwrap_or_else(|| "-".to_owned())
                     ),
+                    SaveFxParameterSnapshot | RecallFxParameterSnapshot => write!(
+                        f,
+                        "{}\nTrack {}\nFX {}\nSnapshot {}",
+                        tt,
+                        self.track_label(),
+                        self.fx_label(),
+                        self.target.fx_parameter_snapshot_id
+                    ),
                     LoadMappingSnapshot => {
                         write!(
                             f,
@@ -3094,6 +3441,9 @@ impl<'a> Display for TargetModelFormatMultiLine<'a> {
                             format_tags_as_csv(self.target.tags())
                         )
                     }
+                    GlobalParameterValue => {
+                        write!(f, "{}\n{}", tt, self.target.global_parameter_name)
+                    }
                     TrackTouchState => write!(
                         f,
                         "{}\nTrack {}\n{}",
@@ -3101,9 +3451,15 @@ impl<'a> Display for TargetModelFormatMultiLine<'a> {
                         self.track_label(),
                         self.target.touched_track_parameter_type
                     ),
-                    GoToBookmark => {
+                    GoToBookmark | LoopRegion => {
                         write!(f, "{}\n{}", tt, self.bookmark_label())
                     }
+                    ItemProperty => {
+                        write!(f, "{}\n{}", tt, self.target.item_property)
+                    }
+                    MidiEditorAction => {
+                        write!(f, "{}\n{}", tt, self.target.midi_editor_action)
+                    }
                     Mouse => {
                         write!(f, "{}\n{}", tt, self.target.mouse_action_type)?;
                         if self.target.supports_axis() {
@@ -3205,6 +3561,12 @@ impl<'a> TargetModelWithContext<'a> {
             })
             .unwrap_or(false)
     }
+
+    pub fn is_known_to_be_continuous(&self) -> bool {
+        self.resolve_first()
+            .map(|t| t.character(self.context.control_context()) == TargetCharacter::Continuous)
+            .unwrap_or(false)
+    }
     // Returns an error if the FX doesn't exist.
     pub fn first_fx(&self) -> Result<Fx, &'static str> {
         first_effective_fx(
@@ -3235,6 +3597,31 @@ impl<'a> TargetModelWithContext<'a> {
             .ok_or("virtual track not complete")?;
         first_effective_track(&virtual_track, self.compartment, self.context)
     }
+
+    /// Returns existing tracks or FXs which could replace the given missing object, ranked by
+    /// the similarity of their name to the last known name of the missing object.
+    pub fn remap_candidates(&self, missing_object: MissingTargetObject) -> Vec<RemapCandidate> {
+        match missing_object {
+            MissingTargetObject::Track => {
+                let candidates = self.project().tracks().map(|t| {
+                    let name = t.name().map(|n| n.to_str().to_owned()).unwrap_or_default();
+                    (RemapCandidate::Track(t), name)
+                });
+                rank_by_name_similarity(&self.target.track_name, candidates)
+            }
+            MissingTargetObject::Fx => {
+                let chain = match self.first_fx_chain() {
+                    Ok(c) => c,
+                    Err(_) => return vec![],
+                };
+                let candidates = chain.fxs().map(|fx| {
+                    let name = get_fx_name(&fx);
+                    (RemapCandidate::Fx(fx), name)
+                });
+                rank_by_name_similarity(&self.target.fx_name, candidates)
+            }
+        }
+    }
 }
 
 pub fn first_effective_track(
@@ -3296,10 +3683,12 @@ impl Display for FormattableBookmarkType {
 pub fn get_non_present_bookmark_label(
     anchor_type: BookmarkAnchorType,
     bookmark_ref: u32,
+    bookmark_name: &str,
 ) -> String {
     match anchor_type {
         BookmarkAnchorType::Id => format!("<Not present> (ID {})", bookmark_ref),
         BookmarkAnchorType::Index => format!("{}. <Not present>", bookmark_ref),
+        BookmarkAnchorType::Name => format!("<Not present> ({})", bookmark_name),
     }
 }
 
@@ -3379,6 +3768,20 @@ fn virtualize_track(
     }
 }
 
+fn find_fx_in_project(project: Project, fx_id: &Guid) -> Option<Fx> {
+    project
+        .master_track()
+        .ok()
+        .into_iter()
+        .chain(project.tracks())
+        .find_map(|track| {
+            [track.normal_fx_chain(), track.input_fx_chain()]
+                .into_iter()
+                .map(|chain| chain.fx_by_guid(fx_id))
+                .find(|fx| fx.is_available())
+        })
+}
+
 fn virtualize_fx(
     fx: &Fx,
     context: &ProcessorContext,
@@ -3387,8 +3790,11 @@ fn virtualize_fx(
     if context.containing_fx() == fx {
         VirtualFx::This
     } else {
+        // Monitoring FX are addressed as input FX of the master track (that's REAPER's convention
+        // and ours).
+        let is_monitoring_fx = matches!(fx.chain().context(), FxChainContext::Monitoring);
         VirtualFx::ChainFx {
-            is_input_fx: fx.is_input_fx(),
+            is_input_fx: fx.is_input_fx() || is_monitoring_fx,
             chain_fx: if special_monitoring_fx_handling && context.is_on_monitoring_fx_chain() {
                 // Doesn't make sense to refer to FX via UUID if we are on monitoring FX chain.
                 VirtualChainFx::ByIndex(fx.index())
@@ -3553,6 +3959,8 @@ pub enum BookmarkAnchorType {
     Id,
     #[display(fmt = "At position")]
     Index,
+    #[display(fmt = "By name")]
+    Name,
 }
 
 impl Default for BookmarkAnchorType {
@@ -4304,7 +4712,7 @@ pub fn get_virtual_fx_label(
     }
 }
 
-fn get_track_label(track: &Track) -> String {
+pub fn get_track_label(track: &Track) -> String {
     match track.location() {
         TrackLocation::MasterTrack => "<Master track>".into(),
         TrackLocation::NormalTrack(i) => {
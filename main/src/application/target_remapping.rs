@@ -0,0 +1,292 @@
+use crate::application::{
+    get_fx_label, get_track_label, Change, CompartmentModel, ConcreteTrackInstruction,
+    TargetCategory, TargetCommand, TargetModel, VirtualFxType, VirtualTrackType,
+};
+use crate::domain::{get_fx_name, MissingTargetObject};
+use reaper_high::{Fx, Project, Track};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use wildmatch::WildMatch;
+
+/// Maximum number of candidates offered when remapping a missing track or FX.
+const MAX_REMAP_CANDIDATE_COUNT: usize = 10;
+
+/// Existing track or FX which could replace the missing object of a target.
+#[derive(Clone, Debug)]
+pub enum RemapCandidate {
+    Track(Track),
+    Fx(Fx),
+}
+
+impl RemapCandidate {
+    pub fn label(&self) -> String {
+        match self {
+            RemapCandidate::Track(t) => get_track_label(t),
+            RemapCandidate::Fx(fx) => get_fx_label(fx.index(), fx),
+        }
+    }
+}
+
+/// Track or FX which a preset refers to by name but which doesn't exist in the current project,
+/// e.g. because the preset has been built for another project.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct MissingPresetReference {
+    pub object: MissingTargetObject,
+    pub name: String,
+}
+
+/// Replacements for missing preset references, chosen by the user when loading a preset.
+#[derive(Clone, Debug, Default)]
+pub struct PresetSubstitutions {
+    tracks: HashMap<String, Track>,
+    fx_names: HashMap<String, String>,
+}
+
+impl PresetSubstitutions {
+    pub fn substitute_track(&mut self, name: String, track: Track) {
+        self.tracks.insert(name, track);
+    }
+
+    pub fn substitute_fx(&mut self, name: String, fx_name: String) {
+        self.fx_names.insert(name, fx_name);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty() && self.fx_names.is_empty()
+    }
+
+    /// Adds the given substitutions, replacing existing ones for the same names.
+    pub fn merge(&mut self, other: PresetSubstitutions) {
+        self.tracks.extend(other.tracks);
+        self.fx_names.extend(other.fx_names);
+    }
+
+    /// Replaces the track and FX references in the mappings of the given compartment which can't
+    /// be resolved in the given project.
+    ///
+    /// Targets which refer to a track by name keep doing so (with the name of the substitute).
+    /// Targets which refer to a track by ID are pointed to the substitute's ID.
+    pub fn apply(&self, model: &mut CompartmentModel, project: Project) {
+        if self.is_empty() {
+            return;
+        }
+        let project_names = ProjectNames::collect(project);
+        for mapping in &mut model.mappings {
+            self.apply_to_target(&mut mapping.target_model, &project_names);
+        }
+    }
+
+    fn apply_to_target(&self, target: &mut TargetModel, project_names: &ProjectNames) {
+        if project_names.track_is_missing(target) {
+            if let Some(track) = self.tracks.get(target.track_name()) {
+                use VirtualTrackType::*;
+                match target.track_type() {
+                    ByName | AllByName => {
+                        let name = track.name().map(|n| n.into_string()).unwrap_or_default();
+                        target.change(TargetCommand::SetTrackName(name));
+                    }
+                    ById | ByIdOrName => {
+                        target.set_concrete_track(
+                            ConcreteTrackInstruction::ByIdWithTrack(track.clone()),
+                            false,
+                            false,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if project_names.fx_is_missing(target) {
+            if let Some(fx_name) = self.fx_names.get(target.fx_name()) {
+                target.change(TargetCommand::SetFxName(fx_name.clone()));
+            }
+        }
+    }
+}
+
+/// Returns the tracks and FX which the given compartment refers to but which can't be found in
+/// the given project, sorted and without duplicates.
+///
+/// Only looks at named references (and at the names of tracks referenced by ID), because those are
+/// the ones which can be substituted in a meaningful way.
+pub fn find_missing_preset_references(
+    model: &CompartmentModel,
+    project: Project,
+) -> Vec<MissingPresetReference> {
+    let project_names = ProjectNames::collect(project);
+    let mut missing = BTreeSet::new();
+    for mapping in &model.mappings {
+        let target = &mapping.target_model;
+        if project_names.track_is_missing(target) {
+            missing.insert(MissingPresetReference {
+                object: MissingTargetObject::Track,
+                name: target.track_name().to_string(),
+            });
+        }
+        if project_names.fx_is_missing(target) {
+            missing.insert(MissingPresetReference {
+                object: MissingTargetObject::Fx,
+                name: target.fx_name().to_string(),
+            });
+        }
+    }
+    missing.into_iter().collect()
+}
+
+/// Names of the tracks and FX of a project, for checking whether the named references of targets
+/// can be resolved.
+struct ProjectNames {
+    project: Project,
+    track_names: Vec<String>,
+    fx_names: Vec<String>,
+}
+
+impl ProjectNames {
+    fn collect(project: Project) -> Self {
+        Self {
+            project,
+            track_names: project_track_names(project),
+            fx_names: project_fx_names(project),
+        }
+    }
+
+    fn track_is_missing(&self, target: &TargetModel) -> bool {
+        if target.category() != TargetCategory::Reaper
+            || !target.supports_track()
+            || target.track_name().is_empty()
+        {
+            return false;
+        }
+        use VirtualTrackType::*;
+        match target.track_type() {
+            ByName | AllByName => !name_exists(target.track_name(), &self.track_names),
+            ById | ByIdOrName => {
+                let exists_by_id = target
+                    .track_id()
+                    .and_then(|id| self.project.track_by_guid(&id).ok())
+                    .map(|t| t.is_available())
+                    .unwrap_or(false);
+                !exists_by_id && !self.track_names.iter().any(|n| n == target.track_name())
+            }
+            _ => false,
+        }
+    }
+
+    fn fx_is_missing(&self, target: &TargetModel) -> bool {
+        target.category() == TargetCategory::Reaper
+            && target.supports_fx()
+            && uses_fx_name(target)
+            && !target.fx_name().is_empty()
+            && !name_exists(target.fx_name(), &self.fx_names)
+    }
+}
+
+/// Returns the names of all FX in the given project, sorted by similarity to the given name.
+pub fn fx_substitute_candidates(project: Project, name: &str) -> Vec<String> {
+    let candidates = project_fx_names(project)
+        .into_iter()
+        .map(|n| (n.clone(), n));
+    rank_by_name_similarity(name, candidates)
+}
+
+/// Returns all tracks in the given project, sorted by similarity to the given name.
+pub fn track_substitute_candidates(project: Project, name: &str) -> Vec<Track> {
+    let candidates = project.tracks().map(|t| {
+        let track_name = t.name().map(|n| n.into_string()).unwrap_or_default();
+        (t, track_name)
+    });
+    rank_by_name_similarity(name, candidates)
+}
+
+fn uses_fx_name(target: &TargetModel) -> bool {
+    matches!(
+        target.fx_type(),
+        VirtualFxType::ByName | VirtualFxType::AllByName
+    )
+}
+
+fn name_exists(name: &str, existing_names: &[String]) -> bool {
+    let wild_match = WildMatch::new(name);
+    existing_names.iter().any(|n| wild_match.matches(n))
+}
+
+fn project_track_names(project: Project) -> Vec<String> {
+    project
+        .tracks()
+        .filter_map(|t| Some(t.name()?.into_string()))
+        .collect()
+}
+
+fn project_fx_names(project: Project) -> Vec<String> {
+    let names: BTreeSet<_> = project
+        .tracks()
+        .chain(project.master_track().ok())
+        .flat_map(|t| {
+            t.normal_fx_chain()
+                .fxs()
+                .chain(t.input_fx_chain().fxs())
+                .map(|fx| get_fx_name(&fx))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    names.into_iter().collect()
+}
+
+/// Sorts the given candidates by the similarity of their name to the given name, most similar
+/// first, and keeps only the best ones.
+pub fn rank_by_name_similarity<T>(
+    name: &str,
+    candidates: impl Iterator<Item = (T, String)>,
+) -> Vec<T> {
+    let mut rated: Vec<_> = candidates
+        .map(|(c, candidate_name)| (c, name_similarity(name, &candidate_name)))
+        .collect();
+    // Stable, so equally similar candidates keep their original order
+    rated.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    rated
+        .into_iter()
+        .take(MAX_REMAP_CANDIDATE_COUNT)
+        .map(|(c, _)| c)
+        .collect()
+}
+
+/// Returns a value between 0.0 (nothing in common) and 1.0 (equal, ignoring case).
+///
+/// Uses the Sørensen–Dice coefficient of the character bigrams.
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+    let total_count = a.len() + b.len();
+    if total_count == 0 {
+        return 0.0;
+    }
+    let mut common_count = 0;
+    for bigram in a {
+        if let Some(i) = b.iter().position(|b| *b == bigram) {
+            b.swap_remove(i);
+            common_count += 1;
+        }
+    }
+    (2 * common_count) as f64 / total_count as f64
+}
+
+fn bigrams(text: &str) -> Vec<(char, char)> {
+    let chars: Vec<_> = text.to_lowercase().chars().collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_candidates_by_name() {
+        // Given
+        let names = ["Drums", "Bass", "Bass DI 2", "Vox"];
+        let candidates = names.iter().map(|n| (*n, n.to_string()));
+        // When
+        let ranked = rank_by_name_similarity("bass di", candidates);
+        // Then
+        assert_eq!(ranked, vec!["Bass DI 2", "Bass", "Drums", "Vox"]);
+    }
+}
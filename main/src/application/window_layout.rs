@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Remembers where the user placed the top-level windows of a ReaLearn instance (e.g. mapping
+/// panels) and how large they were, keyed by window name.
+///
+/// The main panel is not part of this because it's embedded in REAPER's FX window, whose placement
+/// is managed by REAPER itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WindowLayout {
+    windows: BTreeMap<String, WindowBounds>,
+}
+
+/// Position and size of a window in screen coordinates (pixels).
+///
+/// The position is the top-left corner and can be negative on multi-monitor setups.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl WindowLayout {
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    pub fn window_bounds(&self, window_name: &str) -> Option<WindowBounds> {
+        self.windows.get(window_name).copied()
+    }
+
+    /// Returns whether the memorized bounds changed.
+    pub fn memorize_window_bounds(&mut self, window_name: &str, bounds: WindowBounds) -> bool {
+        let previous_bounds = self.windows.insert(window_name.to_string(), bounds);
+        previous_bounds != Some(bounds)
+    }
+}
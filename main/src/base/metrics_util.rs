@@ -25,20 +25,32 @@ pub fn init_metrics() {
         .unwrap();
 }
 
+pub fn metrics_enabled() -> bool {
+    *METRICS_ENABLED
+}
+
 pub fn measure_time<R>(id: &'static str, f: impl FnOnce() -> R) -> R {
     if !*METRICS_ENABLED {
         return f();
     }
     let start = Instant::now();
     let result = f();
-    let task = MetricsTask::Histogram {
-        id,
-        delta: start.elapsed(),
-    };
+    record_duration(id, vec![], start.elapsed());
+    result
+}
+
+/// Records an already measured duration, e.g. in order to attach labels.
+///
+/// Does nothing if metrics are disabled. Check [`metrics_enabled`] before building the labels if
+/// that's expensive.
+pub fn record_duration(id: &'static str, labels: Vec<(&'static str, String)>, delta: Duration) {
+    if !*METRICS_ENABLED {
+        return;
+    }
+    let task = MetricsTask::Histogram { id, labels, delta };
     if METRICS_CHANNEL.sender.try_send(task).is_err() {
         tracing::debug!("ReaLearn metrics channel is full");
     }
-    result
 }
 
 struct MetricsChannel {
@@ -54,14 +66,22 @@ impl Default for MetricsChannel {
 }
 
 enum MetricsTask {
-    Histogram { id: &'static str, delta: Duration },
+    Histogram {
+        id: &'static str,
+        labels: Vec<(&'static str, String)>,
+        delta: Duration,
+    },
 }
 
 fn keep_recording_metrics(receiver: Receiver<MetricsTask>) {
     while let Ok(task) = receiver.recv() {
         match task {
-            MetricsTask::Histogram { id, delta } => {
-                metrics::histogram!(id, delta);
+            MetricsTask::Histogram { id, labels, delta } => {
+                let labels: Vec<_> = labels
+                    .into_iter()
+                    .map(|(key, value)| metrics::Label::new(key, value))
+                    .collect();
+                metrics::histogram!(id, delta, labels);
             }
         }
     }
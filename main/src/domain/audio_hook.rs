@@ -1,7 +1,7 @@
 use crate::base::non_blocking_lock;
 use crate::domain::{
-    classify_midi_message, AudioBlockProps, ControlEvent, ControlEventTimestamp, Garbage,
-    GarbageBin, IncomingMidiMessage, InstanceId, MidiControlInput, MidiEvent,
+    classify_midi_message, AudioBlockProps, AudioChannels, ControlEvent, ControlEventTimestamp,
+    Garbage, GarbageBin, IncomingMidiMessage, InstanceId, MidiControlInput, MidiEvent,
     MidiMessageClassification, MidiScanResult, MidiScanner, RealTimeProcessor,
 };
 use assert_no_alloc::*;
@@ -159,7 +159,12 @@ impl RealearnAudioHook {
         }
     }
 
-    fn call_real_time_processors(&mut self, block_props: AudioBlockProps, might_be_rebirth: bool) {
+    fn call_real_time_processors(
+        &mut self,
+        block_props: AudioBlockProps,
+        might_be_rebirth: bool,
+        hardware_inputs: HardwareAudioInputs,
+    ) {
         match &mut self.state {
             AudioHookState::Normal => {
                 let timestamp = ControlEventTimestamp::now();
@@ -167,6 +172,7 @@ impl RealearnAudioHook {
                     block_props,
                     might_be_rebirth,
                     timestamp,
+                    hardware_inputs,
                 );
             }
             AudioHookState::LearningSource {
@@ -201,6 +207,7 @@ impl RealearnAudioHook {
         block_props: AudioBlockProps,
         might_be_rebirth: bool,
         timestamp: ControlEventTimestamp,
+        hardware_inputs: HardwareAudioInputs,
     ) {
         // 1a. Drive real-time processors and determine used MIDI devices "on the go".
         //
@@ -221,7 +228,12 @@ impl RealearnAudioHook {
             // stop doing so synchronously if the plug-in is
             // gone.
             let mut guard = p.lock_recover();
-            guard.run_from_audio_hook_all(block_props, might_be_rebirth, timestamp);
+            guard.run_from_audio_hook_all(
+                block_props,
+                might_be_rebirth,
+                timestamp,
+                &hardware_inputs,
+            );
             if guard.control_is_globally_enabled() {
                 if let MidiControlInput::Device(dev_id) = guard.midi_control_input() {
                     midi_dev_id_is_used[dev_id.get() as usize] = true;
@@ -241,7 +253,6 @@ impl RealearnAudioHook {
             let its_our_turn = (t.destination.is_midi_overdub && args.is_post)
                 || (!t.destination.is_midi_overdub && !args.is_post);
             if its_our_turn && !process_clip_record_task(args, t) {
-                tracing_debug!("Clearing clip record task from audio hook");
                 self.clip_record_task = None;
             }
         }
@@ -323,7 +334,6 @@ impl RealearnAudioHook {
                     }
                 }
                 StartClipRecording(task) => {
-                    tracing_debug!("Audio hook received clip record task");
                     self.clip_record_task = Some(task);
                 }
             }
@@ -365,7 +375,8 @@ impl OnAudioBuffer for RealearnAudioHook {
                     false
                 };
                 self.process_feedback_tasks();
-                self.call_real_time_processors(block_props, might_be_rebirth);
+                let hardware_inputs = HardwareAudioInputs::new(args.reg, block_props.block_length);
+                self.call_real_time_processors(block_props, might_be_rebirth, hardware_inputs);
             }
             self.process_clip_record_task(&args);
             // Process normal tasks after processing the clip record task so that clip recording
@@ -526,6 +537,38 @@ fn write_midi_to_clip_slot(
     });
 }
 
+/// Gives access to the audio of the hardware inputs in the current audio block.
+#[derive(Copy, Clone)]
+struct HardwareAudioInputs<'a> {
+    register: &'a AudioHookRegister,
+    block_length: usize,
+}
+
+impl<'a> HardwareAudioInputs<'a> {
+    pub fn new(register: &'a AudioHookRegister, block_length: usize) -> Self {
+        Self {
+            register,
+            block_length,
+        }
+    }
+}
+
+impl<'a> AudioChannels for HardwareAudioInputs<'a> {
+    fn channel(&self, index: u32) -> Option<&[f64]> {
+        let reg = unsafe { self.register.get().as_ref() };
+        if index as i32 >= reg.input_nch {
+            return None;
+        }
+        let get_buffer = reg.GetBuffer?;
+        let buf = unsafe { (get_buffer)(false, index as _) };
+        if buf.is_null() {
+            return None;
+        }
+        let samples = unsafe { std::slice::from_raw_parts(buf, self.block_length) };
+        Some(samples)
+    }
+}
+
 #[derive(Copy, Clone)]
 struct AudioHookWriteAudioRequest<'a> {
     channel_offset: usize,
@@ -0,0 +1,152 @@
+use crate::domain::Smoother;
+use helgoboss_learn::{ControlValue, UnitValue};
+use realearn_api::persistence::{AudioInputKind, AudioLevelMeasurement};
+use std::time::Duration;
+
+/// Levels at or below this are translated to control value 0%.
+const MIN_DB: f64 = -60.0;
+
+/// Level changes smaller than this don't result in a new control value. Prevents flooding the
+/// processors with control values which wouldn't make any difference anyway.
+const MIN_VALUE_CHANGE: f64 = 0.001;
+
+pub const DEFAULT_AUDIO_ATTACK: Duration = Duration::from_millis(10);
+pub const DEFAULT_AUDIO_RELEASE: Duration = Duration::from_millis(300);
+
+/// Gives access to the samples of the current audio block, channel by channel.
+pub trait AudioChannels {
+    /// Returns `None` if the channel doesn't exist.
+    fn channel(&self, index: u32) -> Option<&[f64]>;
+}
+
+/// Follows the level of an audio channel, like an envelope follower.
+///
+/// Processed in the real-time processor, once per audio block. The level is translated to an
+/// absolute control value by mapping -60 dB to 0% and 0 dB to 100%.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AudioSource {
+    input: AudioInputKind,
+    /// Zero-based.
+    channel: u32,
+    measurement: AudioLevelMeasurement,
+    attack: Duration,
+    release: Duration,
+    /// Smoothed level (linear amplitude).
+    envelope: Smoother,
+    last_value: Option<UnitValue>,
+}
+
+impl AudioSource {
+    pub fn new(
+        input: AudioInputKind,
+        channel: u32,
+        measurement: AudioLevelMeasurement,
+        attack: Duration,
+        release: Duration,
+    ) -> Self {
+        Self {
+            input,
+            channel,
+            measurement,
+            attack,
+            release,
+            envelope: Smoother::default(),
+            last_value: None,
+        }
+    }
+
+    pub fn input(&self) -> AudioInputKind {
+        self.input
+    }
+
+    pub fn channel(&self) -> u32 {
+        self.channel
+    }
+
+    pub fn measurement(&self) -> AudioLevelMeasurement {
+        self.measurement
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.attack
+    }
+
+    pub fn release(&self) -> Duration {
+        self.release
+    }
+
+    pub fn on_deactivate(&mut self) {
+        self.envelope.reset(0.0);
+        self.last_value = None;
+    }
+
+    /// Measures the level of the given audio block and returns a control value if the smoothed
+    /// level changed noticeably.
+    pub fn process_block(&mut self, samples: &[f64], sample_rate: f64) -> Option<ControlValue> {
+        if samples.is_empty() || sample_rate <= 0.0 {
+            return None;
+        }
+        let level = match self.measurement {
+            AudioLevelMeasurement::Peak => samples.iter().fold(0.0, |max, s| s.abs().max(max)),
+            AudioLevelMeasurement::Rms => {
+                let sum_of_squares: f64 = samples.iter().map(|s| s * s).sum();
+                (sum_of_squares / samples.len() as f64).sqrt()
+            }
+        };
+        let time_constant = if level > self.envelope.value() {
+            self.attack
+        } else {
+            self.release
+        };
+        let block_duration = Duration::from_secs_f64(samples.len() as f64 / sample_rate);
+        let envelope = self.envelope.process(level, time_constant, block_duration);
+        let value = amplitude_to_unit_value(envelope);
+        if let Some(last_value) = self.last_value {
+            if (value.get() - last_value.get()).abs() < MIN_VALUE_CHANGE {
+                return None;
+            }
+        }
+        self.last_value = Some(value);
+        Some(ControlValue::AbsoluteContinuous(value))
+    }
+}
+
+fn amplitude_to_unit_value(amplitude: f64) -> UnitValue {
+    if amplitude <= 0.0 {
+        return UnitValue::MIN;
+    }
+    let db = 20.0 * amplitude.log10();
+    UnitValue::new_clamped((db - MIN_DB) / -MIN_DB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_level() {
+        // Given
+        let mut source = AudioSource::new(
+            AudioInputKind::FxInput,
+            0,
+            AudioLevelMeasurement::Peak,
+            Duration::ZERO,
+            Duration::from_millis(100),
+        );
+        let full_scale_block = [1.0, -1.0, 0.5, 0.0];
+        let silent_block = [0.0; 4];
+        // When
+        let attack_value = source.process_block(&full_scale_block, 40.0);
+        let release_value = source.process_block(&silent_block, 40.0);
+        let same_value = source.process_block(&[0.0; 0], 40.0);
+        // Then
+        assert_eq!(
+            attack_value,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+        // Block of 100 ms with time constant of 100 ms => ~37% of the level remains (~ -8.7 dB)
+        let release_value = release_value.unwrap().to_unit_value().unwrap().get();
+        assert!((release_value - 0.855).abs() < 0.001);
+        assert_eq!(same_value, None);
+    }
+}
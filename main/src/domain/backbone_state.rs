@@ -1,25 +1,32 @@
 use crate::base::{SenderToNormalThread, SenderToRealTimeThread};
 use crate::domain::{
-    ClipMatrixRef, ControlInput, DeviceControlInput, DeviceFeedbackOutput, FeedbackOutput,
-    InstanceId, InstanceState, InstanceStateChanged, NormalAudioHookTask, NormalRealTimeTask,
-    QualifiedClipMatrixEvent, RealearnClipMatrix, RealearnSourceState, RealearnTargetState,
-    ReaperTarget, SafeLua, SharedInstanceState, WeakInstanceState,
+    ClipMatrixRef, ControlEvent, ControlInput, DeviceControlInput, DeviceFeedbackOutput,
+    FeedbackOutput, InstanceId, InstanceState, InstanceStateChanged, NormalAudioHookTask,
+    NormalRealTimeTask, PublishedVirtualControlEvent, QualifiedClipMatrixEvent, RealearnClipMatrix,
+    RealearnSourceState, RealearnTarget, RealearnTargetState, ReaperTarget, SafeLua,
+    SharedInstanceState, VirtualControlBus, VirtualSourceValue, WeakInstanceState,
 };
 use playtime_clip_engine::rt::WeakMatrix;
 use reaper_high::{Reaper, Track};
+use rxrust::prelude::*;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 use std::rc::Rc;
 
 make_available_globally_in_main_thread_on_demand!(BackboneState);
 
+/// How many recently touched targets are remembered.
+const TOUCHED_TARGET_HISTORY_CAPACITY: usize = 10;
+
 /// This is the domain-layer "backbone" which can hold state that's shared among all ReaLearn
 /// instances.
 pub struct BackboneState {
     source_state: RefCell<RealearnSourceState>,
     target_state: RefCell<RealearnTargetState>,
-    last_touched_target: RefCell<Option<ReaperTarget>>,
+    /// Recently touched targets, the most recent one first. Doesn't contain duplicates.
+    touched_target_history: RefCell<VecDeque<ReaperTarget>>,
+    touched_target_history_changed_subject: LocalSubject<'static, (), ()>,
     /// Value: Instance ID of the ReaLearn instance that owns the control input.
     control_input_usages: RefCell<HashMap<DeviceControlInput, HashSet<InstanceId>>>,
     /// Value: Instance ID of the ReaLearn instance that owns the feedback output.
@@ -29,6 +36,8 @@ pub struct BackboneState {
     /// borrow a clip matrix which is owned by instance A. This is great because it allows us to
     /// control the same clip matrix from different controllers.
     instance_states: RefCell<HashMap<InstanceId, WeakInstanceState>>,
+    /// Distributes virtual control events between ReaLearn instances.
+    virtual_control_bus: RefCell<VirtualControlBus>,
 }
 
 impl BackboneState {
@@ -36,11 +45,13 @@ impl BackboneState {
         Self {
             source_state: Default::default(),
             target_state: RefCell::new(target_context),
-            last_touched_target: Default::default(),
+            touched_target_history: Default::default(),
+            touched_target_history_changed_subject: Default::default(),
             control_input_usages: Default::default(),
             feedback_output_usages: Default::default(),
             upper_floor_instances: Default::default(),
             instance_states: Default::default(),
+            virtual_control_bus: Default::default(),
         }
     }
 
@@ -76,7 +87,24 @@ impl BackboneState {
     }
 
     pub fn last_touched_target(&self) -> Option<ReaperTarget> {
-        self.last_touched_target.borrow().clone()
+        self.touched_target_history.borrow().front().cloned()
+    }
+
+    /// Returns the recently touched targets, the most recent one first.
+    pub fn touched_target_history(&self) -> Vec<ReaperTarget> {
+        self.touched_target_history
+            .borrow()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Fires whenever the touched target history changes (not when the most recently touched
+    /// target is touched again).
+    pub fn touched_target_history_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.touched_target_history_changed_subject.clone()
     }
 
     pub fn lives_on_upper_floor(&self, instance_id: &InstanceId) -> bool {
@@ -91,6 +119,32 @@ impl BackboneState {
         self.upper_floor_instances.borrow_mut().remove(instance_id);
     }
 
+    pub fn subscribe_to_virtual_control_events(
+        &self,
+        instance_id: InstanceId,
+        sender: SenderToNormalThread<PublishedVirtualControlEvent>,
+    ) {
+        self.virtual_control_bus
+            .borrow_mut()
+            .subscribe(instance_id, sender);
+    }
+
+    pub fn unsubscribe_from_virtual_control_events(&self, instance_id: &InstanceId) {
+        self.virtual_control_bus
+            .borrow_mut()
+            .unsubscribe(instance_id);
+    }
+
+    /// Sends the given virtual control event to all instances which subscribed to virtual
+    /// control events.
+    pub fn publish_virtual_control_event(
+        &self,
+        publisher: InstanceId,
+        event: ControlEvent<VirtualSourceValue>,
+    ) {
+        self.virtual_control_bus.borrow().publish(publisher, event);
+    }
+
     pub fn create_instance(
         &self,
         id: InstanceId,
@@ -356,7 +410,30 @@ impl BackboneState {
     }
 
     pub(super) fn set_last_touched_target(&self, target: ReaperTarget) {
-        *self.last_touched_target.borrow_mut() = Some(target);
+        {
+            let mut history = self.touched_target_history.borrow_mut();
+            if history.front() == Some(&target) {
+                return;
+            }
+            history.retain(|t| t != &target && target_objects_exist(t));
+            history.push_front(target);
+            history.truncate(TOUCHED_TARGET_HISTORY_CAPACITY);
+        }
+        self.touched_target_history_changed_subject.clone().next(());
+    }
+
+    /// Removes targets from the touched target history whose project, track or FX doesn't exist
+    /// anymore.
+    pub(super) fn prune_touched_target_history(&self) {
+        {
+            let mut history = self.touched_target_history.borrow_mut();
+            let count_before = history.len();
+            history.retain(target_objects_exist);
+            if history.len() == count_before {
+                return;
+            }
+        }
+        self.touched_target_history_changed_subject.clone().next(());
     }
 
     fn interaction_is_allowed<D: Eq + Hash>(
@@ -413,6 +490,12 @@ fn update_io_usage<D: Eq + Hash + Copy>(
     device != previously_used_device
 }
 
+fn target_objects_exist(target: &ReaperTarget) -> bool {
+    target.project().map_or(true, |p| p.is_available())
+        && target.track().map_or(true, |t| t.is_available())
+        && target.fx().map_or(true, |fx| fx.is_available())
+}
+
 const NO_CLIP_MATRIX_SET: &str = "no clip matrix set for this instance";
 const REFERENCED_INSTANCE_NOT_AVAILABLE: &str = "other instance not available";
 const REFERENCED_CLIP_MATRIX_NOT_AVAILABLE: &str = "clip matrix of other instance not available";
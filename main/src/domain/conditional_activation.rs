@@ -1,11 +1,14 @@
 use crate::base::eel;
 use crate::domain::{
-    CompartmentParamIndex, CompartmentParams, EffectiveParamValue, ExpressionEvaluator, MappingId,
-    RawParamValue, COMPARTMENT_PARAMETER_COUNT, EXPRESSION_NONE_VALUE,
+    BackboneState, CompartmentParamIndex, CompartmentParams, EffectiveParamValue,
+    ExpressionEvaluator, MappingId, RawParamValue, COMPARTMENT_PARAMETER_COUNT,
+    EXPRESSION_NONE_VALUE,
 };
 use helgoboss_learn::AbsoluteValue;
+use reaper_high::{Project, Track};
 use std::collections::HashSet;
 use std::error::Error;
+use wildmatch::WildMatch;
 
 #[derive(Debug)]
 pub enum ActivationCondition {
@@ -22,6 +25,7 @@ pub enum ActivationCondition {
         lead_mapping: Option<MappingId>,
         condition: Box<ExpressionEvaluator>,
     },
+    Transport(TransportCondition),
 }
 
 impl ActivationCondition {
@@ -30,6 +34,16 @@ impl ActivationCondition {
         !matches!(self, ActivationCondition::Always)
     }
 
+    /// Returns if this activation condition can be affected by changes of REAPER's state (e.g.
+    /// track selection or play state).
+    pub fn can_be_affected_by_reaper_state(&self) -> bool {
+        match self {
+            ActivationCondition::Expression(condition) => condition.depends_on_reaper_state(),
+            ActivationCondition::Transport(_) => true,
+            _ => false,
+        }
+    }
+
     /// Returns the referenced lead mapping of this activation condition if it's a target-value
     /// based one.
     pub fn target_value_lead_mapping(&self) -> Option<MappingId> {
@@ -45,9 +59,12 @@ impl ActivationCondition {
     /// Returns if this activation condition is fulfilled in presence of the given set of
     /// parameters.
     ///
+    /// The given project is the one of the ReaLearn instance. It's used for conditions which depend
+    /// on REAPER's state.
+    ///
     /// Returns `None` if the condition doesn't depend on parameter values (in which case it must
     /// be evaluated in other ways).
-    pub fn is_fulfilled(&self, params: &CompartmentParams) -> Option<bool> {
+    pub fn is_fulfilled(&self, params: &CompartmentParams, project: Project) -> Option<bool> {
         use ActivationCondition::*;
         let res = match self {
             Always => true,
@@ -60,8 +77,9 @@ impl ActivationCondition {
                 condition.notify_params_changed(params);
                 condition.is_fulfilled()
             }
-            Expression(condition) => condition.is_fulfilled(params),
+            Expression(condition) => condition.is_fulfilled(params, project),
             TargetValue { .. } => return None,
+            Transport(condition) => condition.is_fulfilled(project),
         };
         Some(res)
     }
@@ -113,6 +131,7 @@ impl ActivationCondition {
         index: CompartmentParamIndex,
         // Previous value at changed index
         previous_value: RawParamValue,
+        project: Project,
     ) -> Option<bool> {
         use ActivationCondition::*;
         let is_fulfilled = match self {
@@ -146,16 +165,38 @@ impl ActivationCondition {
                 }
                 condition.is_fulfilled()
             }
-            Expression(condition) => condition.is_fulfilled(params),
+            Expression(condition) => condition.is_fulfilled(params, project),
             Always => return None,
-            // This conditional activation doesn't depend on parameter values, it's evaluated
+            // These conditional activations don't depend on parameter values, they are evaluated
             // in other ways.
-            TargetValue { .. } => return None,
+            TargetValue { .. } | Transport(_) => return None,
         };
         Some(is_fulfilled)
     }
 }
 
+/// Makes a mapping active only in a particular play state of the project, e.g. in order to
+/// prevent performance-only mappings from interfering while editing.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TransportCondition {
+    /// Also fulfilled while recording.
+    Playing,
+    Recording,
+    /// Also fulfilled while paused.
+    Stopped,
+}
+
+impl TransportCondition {
+    pub fn is_fulfilled(&self, project: Project) -> bool {
+        let play_state = project.play_state();
+        match self {
+            TransportCondition::Playing => play_state.is_playing,
+            TransportCondition::Recording => play_state.is_recording,
+            TransportCondition::Stopped => !play_state.is_playing,
+        }
+    }
+}
+
 fn modifier_conditions_are_fulfilled(
     conditions: &[ModifierCondition],
     params: &CompartmentParams,
@@ -216,20 +257,123 @@ impl ModifierCondition {
 #[derive(Debug)]
 pub struct ExpressionCondition {
     evaluator: ExpressionEvaluator,
+    /// REAPER state queried by the expression. The query at index `i` provides the value of the
+    /// generated variable `reaper_state_i`.
+    reaper_state_queries: Vec<ReaperStateQuery>,
 }
 
 impl ExpressionCondition {
     pub fn compile(expression: &str) -> Result<Self, Box<dyn Error>> {
+        let (expression, reaper_state_queries) = extract_reaper_state_queries(expression);
         let condition = Self {
-            evaluator: ExpressionEvaluator::compile(expression)?,
+            evaluator: ExpressionEvaluator::compile(&expression)?,
+            reaper_state_queries,
         };
         Ok(condition)
     }
 
-    pub fn is_fulfilled(&self, params: &CompartmentParams) -> bool {
-        let result = self.evaluator.evaluate_with_params(params);
+    pub fn depends_on_reaper_state(&self) -> bool {
+        !self.reaper_state_queries.is_empty()
+    }
+
+    /// Evaluates the expression. REAPER state is queried in the given project.
+    pub fn is_fulfilled(&self, params: &CompartmentParams, project: Project) -> bool {
+        let result = if self.reaper_state_queries.is_empty() {
+            self.evaluator.evaluate_with_params(params)
+        } else {
+            // REAPER state is only queried if the expression actually reaches the variable.
+            self.evaluator
+                .evaluate_with_params_and_vars(params, |name, _| {
+                    self.reaper_state_var(name, project)
+                })
+        };
         result.map(|v| v > 0.0).unwrap_or(false)
     }
+
+    fn reaper_state_var(&self, name: &str, project: Project) -> Option<f64> {
+        let index: usize = name.strip_prefix(REAPER_STATE_VAR_PREFIX)?.parse().ok()?;
+        let query = self.reaper_state_queries.get(index)?;
+        Some(query.value(project))
+    }
+}
+
+const REAPER_STATE_VAR_PREFIX: &str = "reaper_state_";
+
+#[derive(Debug)]
+enum ReaperStateQuery {
+    TrackSelected(WildMatch),
+    TrackMuted(WildMatch),
+    TrackSoloed(WildMatch),
+    TrackArmed(WildMatch),
+    Playing,
+    Recording,
+    /// Value of a global parameter (shared among all ReaLearn instances).
+    GlobalParameter(String),
+}
+
+impl ReaperStateQuery {
+    fn value(&self, project: Project) -> f64 {
+        use ReaperStateQuery::*;
+        let is_fulfilled = match self {
+            TrackSelected(name) => any_track_matches(project, name, |t| t.is_selected()),
+            TrackMuted(name) => any_track_matches(project, name, |t| t.is_muted()),
+            TrackSoloed(name) => any_track_matches(project, name, |t| t.is_solo()),
+            TrackArmed(name) => any_track_matches(project, name, |t| t.is_armed(false)),
+            Playing => project.is_playing(),
+            Recording => project.play_state().is_recording,
+            GlobalParameter(name) => {
+                return BackboneState::target_state()
+                    .borrow()
+                    .global_parameter_value(name)
+                    .get();
+            }
+        };
+        if is_fulfilled {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+fn any_track_matches(project: Project, name: &WildMatch, f: impl Fn(&Track) -> bool) -> bool {
+    project.tracks().any(|t| match t.name() {
+        None => false,
+        Some(n) => name.matches(n.to_str()) && f(&t),
+    })
+}
+
+/// fasteval doesn't support string arguments, so we replace each usage of a REAPER state function
+/// (e.g. `track_selected("Drums")` or `global_param("mode")`) or variable (e.g. `playing`) with a
+/// generated variable.
+fn extract_reaper_state_queries(expression: &str) -> (String, Vec<ReaperStateQuery>) {
+    let reaper_state_regex = regex!(
+        r#"\b(track_selected|track_muted|track_soloed|track_armed|global_param)\s*\(\s*"([^"]*)"\s*\)|\b(playing|recording)\b"#
+    );
+    let mut queries = vec![];
+    let expression = reaper_state_regex.replace_all(expression, |captures: &regex::Captures| {
+        let query = if let Some(var) = captures.get(3) {
+            if var.as_str() == "playing" {
+                ReaperStateQuery::Playing
+            } else {
+                ReaperStateQuery::Recording
+            }
+        } else if &captures[1] == "global_param" {
+            ReaperStateQuery::GlobalParameter(captures[2].to_string())
+        } else {
+            let name = WildMatch::new(&captures[2]);
+            match &captures[1] {
+                "track_selected" => ReaperStateQuery::TrackSelected(name),
+                "track_muted" => ReaperStateQuery::TrackMuted(name),
+                "track_soloed" => ReaperStateQuery::TrackSoloed(name),
+                _ => ReaperStateQuery::TrackArmed(name),
+            }
+        };
+        let var_name = format!("{}{}", REAPER_STATE_VAR_PREFIX, queries.len());
+        queries.push(query);
+        var_name
+    });
+    (expression.into_owned(), queries)
 }
 
 #[derive(Debug)]
@@ -308,6 +452,22 @@ impl EelCondition {
     }
 }
 
+/// Returns the compartment parameters which the given EEL script or expression refers to, either
+/// as variable (`p1`, one-based) or via the parameter array (`p[0]`, zero-based).
+///
+/// Array accesses with a computed index can't be detected.
+pub fn extract_referenced_param_indexes(script: &str) -> HashSet<CompartmentParamIndex> {
+    let array_regex = regex!(r#"\bp\s*\[\s*([0-9]+)\s*\]"#);
+    let array_indexes = array_regex
+        .captures_iter(script)
+        .flat_map(|m| m[1].parse::<u32>());
+    extract_used_param_indexes(script)
+        .into_iter()
+        .chain(array_indexes)
+        .filter_map(|i| CompartmentParamIndex::try_from(i).ok())
+        .collect()
+}
+
 fn extract_used_param_indexes(eel_script: &str) -> HashSet<u32> {
     let param_regex = regex!(r#"\bp([0-9]+)\b"#);
     param_regex
@@ -317,3 +477,55 @@ fn extract_used_param_indexes(eel_script: &str) -> HashSet<u32> {
         .map(|i: u32| i - 1)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_reaper_state() {
+        // Given
+        let expression = r#"p[0] > 0.5 && track_selected( "Drums*" ) && !playing"#;
+        // When
+        let (expression, queries) = extract_reaper_state_queries(expression);
+        // Then
+        assert_eq!(
+            expression,
+            "p[0] > 0.5 && reaper_state_0 && !reaper_state_1"
+        );
+        assert_eq!(queries.len(), 2);
+        match &queries[0] {
+            ReaperStateQuery::TrackSelected(name) => assert!(name.matches("Drums 1")),
+            _ => panic!("unexpected query"),
+        }
+        assert!(matches!(queries[1], ReaperStateQuery::Playing));
+    }
+
+    #[test]
+    fn extract_referenced_params() {
+        // Given
+        let script = "y = p3 > 0.5 && p[0] < 0.2 && p[ 12 ] == 0 && p1000 == 0";
+        // When
+        let indexes = extract_referenced_param_indexes(script);
+        // Then
+        let expected: HashSet<_> = [0, 2, 12]
+            .into_iter()
+            .map(|i| CompartmentParamIndex::try_from(i).unwrap())
+            .collect();
+        assert_eq!(indexes, expected);
+    }
+
+    #[test]
+    fn extract_global_param() {
+        // Given
+        let expression = r#"global_param("mode") > 0.5"#;
+        // When
+        let (expression, queries) = extract_reaper_state_queries(expression);
+        // Then
+        assert_eq!(expression, "reaper_state_0 > 0.5");
+        match &queries[0] {
+            ReaperStateQuery::GlobalParameter(name) => assert_eq!(name, "mode"),
+            _ => panic!("unexpected query"),
+        }
+    }
+}
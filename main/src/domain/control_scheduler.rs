@@ -0,0 +1,502 @@
+use crate::domain::{ControlOptions, QualifiedMappingId};
+use helgoboss_learn::{ControlValue, UnitValue, BASE_EPSILON};
+use reaper_high::{Project, Reaper};
+use std::collections::HashMap;
+use std::ptr::null_mut;
+use std::time::{Duration, Instant};
+
+/// Per-mapping settings which make the main processor hit the target later or repeatedly instead
+/// of immediately.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ControlScheduling {
+    /// Incoming control values are processed only after this delay.
+    pub delay: Duration,
+    /// If set, incoming control values are deferred until the project play position reaches the
+    /// next grid position (before applying the delay). Only while the project is playing.
+    pub quantization: Option<ControlQuantization>,
+    /// If set, pressing the button hits the target with the values of this sequence, one after
+    /// the other, until the button is released.
+    pub step_sequence: Option<StepSequence>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ControlQuantization {
+    /// Uses the current grid setting of the project.
+    ProjectGrid,
+    /// Uses a fixed grid.
+    Fixed {
+        /// Grid size as fraction of a whole note (e.g. 0.0625 for 1/16).
+        grid_size: f64,
+    },
+}
+
+impl ControlQuantization {
+    /// Returns the grid size as fraction of a whole note.
+    fn grid_size(&self, project: Project) -> f64 {
+        match self {
+            ControlQuantization::ProjectGrid => {
+                let mut division = 0.0;
+                unsafe {
+                    Reaper::get().medium_reaper().low().GetSetProjectGrid(
+                        project.raw().as_ptr(),
+                        false,
+                        &mut division,
+                        null_mut(),
+                        null_mut(),
+                    );
+                }
+                division
+            }
+            ControlQuantization::Fixed { grid_size } => *grid_size,
+        }
+    }
+
+    /// Returns the next grid position after the current play position.
+    ///
+    /// Returns `None` if the project is not playing, in which case there's no point in
+    /// quantizing.
+    fn next_grid_position(&self, project: Project) -> Option<GridPosition> {
+        let play_position = play_position_in_quarter_notes(project)?;
+        let grid_size = self.grid_size(project) * 4.0;
+        if grid_size <= 0.0 {
+            return None;
+        }
+        let grid_position = GridPosition {
+            position: next_grid_position(play_position, grid_size),
+            grid_size,
+        };
+        Some(grid_position)
+    }
+}
+
+/// Returns the (latency-compensated) play position of the given project in quarter notes.
+///
+/// Returns `None` if the project is not playing.
+pub fn play_position_in_quarter_notes(project: Project) -> Option<f64> {
+    if !project.is_playing() {
+        return None;
+    }
+    let pos = project.play_position_latency_compensated();
+    let qn = Reaper::get()
+        .medium_reaper()
+        .time_map_2_time_to_qn_abs(project.context(), pos);
+    Some(qn.get())
+}
+
+/// A position on the grid of the project timeline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GridPosition {
+    /// Position in quarter notes.
+    position: f64,
+    /// Grid size in quarter notes.
+    grid_size: f64,
+}
+
+impl GridPosition {
+    /// Returns whether the given play position (in quarter notes) has reached this grid position.
+    ///
+    /// Also returns `true` if the project is not playing anymore or jumped back, because then
+    /// the grid position won't be reached soon.
+    fn is_reached(&self, play_position: Option<f64>) -> bool {
+        match play_position {
+            None => true,
+            Some(p) => p >= self.position - BASE_EPSILON || p < self.position - self.grid_size,
+        }
+    }
+}
+
+/// Returns the next position on the grid, in the same unit as the given position.
+///
+/// If the position is already (almost) on the grid, it's returned as is.
+fn next_grid_position(pos: f64, grid_size: f64) -> f64 {
+    let pos_in_grid_units = pos / grid_size;
+    let previous_grid_index = pos_in_grid_units.floor();
+    if pos_in_grid_units - previous_grid_index < BASE_EPSILON {
+        return previous_grid_index * grid_size;
+    }
+    (previous_grid_index + 1.0) * grid_size
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepSequence {
+    /// Target values. Must not be empty.
+    pub values: Vec<UnitValue>,
+    /// Time between two steps. Must not be zero.
+    pub interval: Duration,
+}
+
+impl ControlScheduling {
+    pub fn is_active(&self) -> bool {
+        !self.delay.is_zero() || self.quantization.is_some() || self.step_sequence.is_some()
+    }
+
+    /// Returns from when on the delay is measured.
+    ///
+    /// That's the next grid position if quantization is enabled and the project is playing,
+    /// otherwise it's now.
+    pub fn start(&self, project: Project, now: Instant) -> ScheduleStart {
+        match self
+            .quantization
+            .and_then(|q| q.next_grid_position(project))
+        {
+            None => ScheduleStart::Time(now),
+            Some(p) => ScheduleStart::GridPosition(p),
+        }
+    }
+}
+
+/// From when on the delay of a scheduled control value is measured.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ScheduleStart {
+    Time(Instant),
+    /// As soon as the project play position reaches the given grid position.
+    GridPosition(GridPosition),
+}
+
+/// When something scheduled is due.
+#[derive(Copy, Clone, Debug)]
+enum Due {
+    At(Instant),
+    /// Waiting for the project play position to reach the given grid position. Due after the
+    /// given delay from then on.
+    AfterGridPosition {
+        grid_position: GridPosition,
+        delay: Duration,
+    },
+}
+
+impl Due {
+    fn new(start: ScheduleStart, delay: Duration) -> Self {
+        match start {
+            ScheduleStart::Time(t) => Self::At(t + delay),
+            ScheduleStart::GridPosition(grid_position) => Self::AfterGridPosition {
+                grid_position,
+                delay,
+            },
+        }
+    }
+
+    /// Returns the due time as soon as it's known.
+    fn resolve(&mut self, now: Instant, play_position: Option<f64>) -> Option<Instant> {
+        match *self {
+            Self::At(t) => Some(t),
+            Self::AfterGridPosition {
+                grid_position,
+                delay,
+            } => {
+                if !grid_position.is_reached(play_position) {
+                    return None;
+                }
+                let t = now + delay;
+                *self = Self::At(t);
+                Some(t)
+            }
+        }
+    }
+}
+
+/// Something the main processor should do now because it has been scheduled earlier.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ScheduledControl {
+    /// Process the given control value as if it was just received from the source.
+    Control {
+        mapping_id: QualifiedMappingId,
+        value: ControlValue,
+        options: ControlOptions,
+    },
+    /// Hit the target directly with the given value (without going through the glue section).
+    SequenceStep {
+        mapping_id: QualifiedMappingId,
+        value: UnitValue,
+    },
+}
+
+/// Queue of delayed control events and currently running step sequences.
+///
+/// Due times are derived from the previous due time, not from the time of polling. This prevents
+/// step sequences from drifting. Quantized events are due when the project play position reaches
+/// the grid position, so they follow tempo changes. The precision is limited by the frequency of
+/// the main loop.
+#[derive(Debug, Default)]
+pub struct ControlScheduler {
+    delayed_events: Vec<DelayedControlEvent>,
+    running_sequences: HashMap<QualifiedMappingId, RunningStepSequence>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct DelayedControlEvent {
+    due: Due,
+    mapping_id: QualifiedMappingId,
+    value: ControlValue,
+    options: ControlOptions,
+}
+
+#[derive(Debug)]
+struct RunningStepSequence {
+    sequence: StepSequence,
+    next_step_index: usize,
+    next_due: Due,
+}
+
+impl ControlScheduler {
+    /// Schedules the given incoming control value according to the given mapping settings.
+    ///
+    /// The delay is measured from the given start (see [`ControlScheduling::start`]).
+    pub fn schedule(
+        &mut self,
+        mapping_id: QualifiedMappingId,
+        scheduling: &ControlScheduling,
+        value: ControlValue,
+        options: ControlOptions,
+        start: ScheduleStart,
+    ) {
+        let due = Due::new(start, scheduling.delay);
+        if let Some(sequence) = &scheduling.step_sequence {
+            // Relative values can neither start nor stop a sequence.
+            if let Ok(v) = value.to_unit_value() {
+                if v.is_zero() {
+                    self.running_sequences.remove(&mapping_id);
+                } else {
+                    self.running_sequences.entry(mapping_id).or_insert_with(|| {
+                        RunningStepSequence {
+                            sequence: sequence.clone(),
+                            next_step_index: 0,
+                            next_due: due,
+                        }
+                    });
+                }
+            }
+        } else {
+            self.delayed_events.push(DelayedControlEvent {
+                due,
+                mapping_id,
+                value,
+                options,
+            });
+        }
+    }
+
+    /// Stops a possibly running step sequence of the given mapping.
+    pub fn stop_sequence(&mut self, mapping_id: &QualifiedMappingId) {
+        self.running_sequences.remove(mapping_id);
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.delayed_events.is_empty() && self.running_sequences.is_empty()
+    }
+
+    /// Returns everything that's due, in chronological order.
+    ///
+    /// The play position is the current play position of the project in quarter notes (`None` if
+    /// the project is not playing, see [`play_position_in_quarter_notes`]).
+    ///
+    /// Each step sequence yields at most one step per call. If the caller couldn't keep up,
+    /// missed steps are skipped instead of being fired in a burst.
+    pub fn poll(&mut self, now: Instant, play_position: Option<f64>) -> Vec<ScheduledControl> {
+        if self.is_idle() {
+            return vec![];
+        }
+        let mut due_items: Vec<(Instant, ScheduledControl)> = vec![];
+        self.delayed_events.retain_mut(|e| {
+            let Some(due) = e.due.resolve(now, play_position) else {
+                return true;
+            };
+            if due > now {
+                return true;
+            }
+            let item = ScheduledControl::Control {
+                mapping_id: e.mapping_id,
+                value: e.value,
+                options: e.options,
+            };
+            due_items.push((due, item));
+            false
+        });
+        for (mapping_id, s) in self.running_sequences.iter_mut() {
+            let Some(due) = s.next_due.resolve(now, play_position) else {
+                continue;
+            };
+            if due > now {
+                continue;
+            }
+            let values = &s.sequence.values;
+            let item = ScheduledControl::SequenceStep {
+                mapping_id: *mapping_id,
+                value: values[s.next_step_index % values.len()],
+            };
+            due_items.push((due, item));
+            s.next_step_index = (s.next_step_index + 1) % values.len();
+            let mut next_due = due + s.sequence.interval;
+            if next_due <= now {
+                next_due = now + s.sequence.interval;
+            }
+            s.next_due = Due::At(next_due);
+        }
+        due_items.sort_by_key(|(due, _)| *due);
+        due_items.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Compartment, MappingId};
+
+    fn sequence_scheduling() -> ControlScheduling {
+        ControlScheduling {
+            delay: Duration::ZERO,
+            quantization: None,
+            step_sequence: Some(StepSequence {
+                values: vec![UnitValue::MAX, UnitValue::MIN],
+                interval: Duration::from_millis(100),
+            }),
+        }
+    }
+
+    fn press(pressed: bool) -> ControlValue {
+        let v = if pressed {
+            UnitValue::MAX
+        } else {
+            UnitValue::MIN
+        };
+        ControlValue::AbsoluteContinuous(v)
+    }
+
+    #[test]
+    fn delay() {
+        // Given
+        let mut scheduler = ControlScheduler::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let scheduling = ControlScheduling {
+            delay: Duration::from_millis(500),
+            quantization: None,
+            step_sequence: None,
+        };
+        let start = Instant::now();
+        // When
+        scheduler.schedule(
+            id,
+            &scheduling,
+            press(true),
+            Default::default(),
+            ScheduleStart::Time(start),
+        );
+        // Then
+        assert!(scheduler.poll(start, None).is_empty());
+        assert_eq!(
+            scheduler.poll(start + Duration::from_millis(500), None),
+            vec![ScheduledControl::Control {
+                mapping_id: id,
+                value: press(true),
+                options: Default::default()
+            }]
+        );
+        assert!(scheduler.is_idle());
+    }
+
+    #[test]
+    fn step_sequence_runs_while_pressed() {
+        // Given
+        let mut scheduler = ControlScheduler::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let scheduling = sequence_scheduling();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let step = |value| {
+            vec![ScheduledControl::SequenceStep {
+                mapping_id: id,
+                value,
+            }]
+        };
+        // When
+        scheduler.schedule(
+            id,
+            &scheduling,
+            press(true),
+            Default::default(),
+            ScheduleStart::Time(start),
+        );
+        // Then
+        assert_eq!(scheduler.poll(at(0), None), step(UnitValue::MAX));
+        assert!(scheduler.poll(at(50), None).is_empty());
+        assert_eq!(scheduler.poll(at(110), None), step(UnitValue::MIN));
+        // Main loop stalled, missed steps are skipped
+        assert_eq!(scheduler.poll(at(450), None), step(UnitValue::MAX));
+        assert!(scheduler.poll(at(500), None).is_empty());
+        assert_eq!(scheduler.poll(at(550), None), step(UnitValue::MIN));
+        scheduler.schedule(
+            id,
+            &scheduling,
+            press(false),
+            Default::default(),
+            ScheduleStart::Time(at(560)),
+        );
+        assert!(scheduler.poll(at(1000), None).is_empty());
+        assert!(scheduler.is_idle());
+    }
+
+    #[test]
+    fn quantization_follows_play_position() {
+        // Given
+        let mut scheduler = ControlScheduler::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let scheduling = ControlScheduling {
+            delay: Duration::ZERO,
+            quantization: Some(ControlQuantization::Fixed { grid_size: 0.0625 }),
+            step_sequence: None,
+        };
+        let start = ScheduleStart::GridPosition(GridPosition {
+            position: 10.25,
+            grid_size: 0.25,
+        });
+        let now = Instant::now();
+        // When
+        scheduler.schedule(id, &scheduling, press(true), Default::default(), start);
+        scheduler.schedule(id, &scheduling, press(false), Default::default(), start);
+        // Then
+        assert!(scheduler.poll(now, Some(10.1)).is_empty());
+        assert!(scheduler
+            .poll(now + Duration::from_secs(1), Some(10.2))
+            .is_empty());
+        let control = |pressed| ScheduledControl::Control {
+            mapping_id: id,
+            value: press(pressed),
+            options: Default::default(),
+        };
+        assert_eq!(
+            scheduler.poll(now, Some(10.25)),
+            vec![control(true), control(false)]
+        );
+        assert!(scheduler.is_idle());
+    }
+
+    #[test]
+    fn quantization_gives_up_when_stopped_or_jumped_back() {
+        // Given
+        let grid_position = GridPosition {
+            position: 10.25,
+            grid_size: 0.25,
+        };
+        // When
+        let stopped = grid_position.is_reached(None);
+        let jumped_back = grid_position.is_reached(Some(2.0));
+        let waiting = grid_position.is_reached(Some(10.01));
+        // Then
+        assert!(stopped);
+        assert!(jumped_back);
+        assert!(!waiting);
+    }
+
+    #[test]
+    fn next_grid_position_on_16th_grid() {
+        // Given
+        let sixteenth_in_quarter_notes = 0.25;
+        // When
+        let next_positions: Vec<_> = [10.1, 10.25, 10.26]
+            .into_iter()
+            .map(|pos| next_grid_position(pos, sixteenth_in_quarter_notes))
+            .collect();
+        // Then
+        assert_eq!(next_positions, vec![10.25, 10.25, 10.5]);
+    }
+}
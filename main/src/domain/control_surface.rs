@@ -10,7 +10,7 @@ use crate::domain::{
     ReaperTarget, SharedMainProcessors, SharedRealTimeProcessor, TouchedTrackParameterType,
 };
 use crossbeam_channel::Receiver;
-use helgoboss_learn::{AbstractTimestamp, ModeGarbage, RawMidiEvents};
+use helgoboss_learn::{AbstractTimestamp, ModeGarbage, RawMidiEvents, UnitValue};
 use reaper_high::{
     ChangeDetectionMiddleware, ChangeEvent, ControlSurfaceEvent, ControlSurfaceMiddleware,
     FutureMiddleware, Fx, FxParameter, MainTaskMiddleware, Project, Reaper,
@@ -32,6 +32,7 @@ use slog::debug;
 use smallvec::SmallVec;
 use std::collections::HashMap;
 use std::mem;
+use std::time::Instant;
 
 type OscCaptureSender = async_channel::Sender<OscScanResult>;
 
@@ -126,6 +127,10 @@ pub enum AdditionalFeedbackEvent {
     ///
     /// REAPER itself doesn't fire any change event in this case.
     FocusSwitchedBetweenMainAndFx,
+    /// Sent whenever the value of a global parameter (shared among all instances) changes.
+    GlobalParameterChanged(GlobalParameterChangedEvent),
+    /// Sent whenever the trim amount of a "Track: Trim volume (VCA)" target changes.
+    VolumeTrimChanged(VolumeTrimChangedEvent),
 }
 
 #[derive(Debug)]
@@ -169,6 +174,19 @@ pub struct ActionInvokedEvent {
     pub command_id: CommandId,
 }
 
+#[derive(Debug)]
+pub struct GlobalParameterChangedEvent {
+    pub name: String,
+    pub new_value: UnitValue,
+}
+
+#[derive(Debug)]
+pub struct VolumeTrimChangedEvent {
+    pub key: String,
+    /// Trim in dB.
+    pub new_value: f64,
+}
+
 #[derive(Debug)]
 pub struct FxSnapshotLoadedEvent {
     pub fx: Fx,
@@ -257,6 +275,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         self.poll_clip_matrixes();
         self.process_incoming_clip_matrix_events();
         self.run_main_processors(timestamp);
+        self.write_thinned_fx_parameter_values();
         self.drop_garbage();
         self.process_deferred_control_surface_events();
         self.counter += 1;
@@ -337,6 +356,9 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
             .chain(monitoring_fx_events.into_iter())
         {
             self.rx_middleware.handle_change(e.clone());
+            if matches!(e, ChangeEvent::TrackRemoved(_) | ChangeEvent::FxRemoved(_)) {
+                BackboneState::get().prune_touched_target_history();
+            }
             if let Some(target) = ReaperTarget::touched_from_change_event(e) {
                 // TODO-medium Now we have the necessary framework (AdditionalFeedbackEvent)
                 //  to also support action, FX snapshot and ReaLearn monitoring FX parameter
@@ -392,6 +414,19 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         }
     }
 
+    /// Writes FX parameter values which have been held back by parameter thinning and are due now.
+    fn write_thinned_fx_parameter_values(&self) {
+        let due_values = BackboneState::target_state()
+            .borrow_mut()
+            .poll_thinned_fx_parameter_values(Instant::now());
+        for (fx, index, value) in due_values {
+            // The FX might be gone in the meantime. Not worth a notification.
+            let _ = fx
+                .parameter_by_index(index)
+                .set_reaper_normalized_value(ReaperNormalizedFxParamValue::new(value.get()));
+        }
+    }
+
     fn drop_garbage(&mut self) {
         for garbage in self.garbage_receiver.try_iter().take(GARBAGE_BULK_SIZE) {
             let _ = garbage;
@@ -0,0 +1,79 @@
+use helgoboss_learn::UnitValue;
+use realearn_api::persistence::CrossfadeRole;
+use std::f64::consts::FRAC_PI_2;
+
+/// Calculates the value which a crossfade side should take when the crossfader is at the given
+/// position.
+///
+/// Returns `None` if the given crossfader role is not a crossfader or the given side role is not a
+/// side.
+pub fn calculate_crossfade_value(
+    crossfader_role: CrossfadeRole,
+    side_role: CrossfadeRole,
+    crossfader_pos: UnitValue,
+) -> Option<UnitValue> {
+    use CrossfadeRole::*;
+    let x = match side_role {
+        SideA => 1.0 - crossfader_pos.get(),
+        SideB => crossfader_pos.get(),
+        LinearCrossfader | EqualPowerCrossfader => return None,
+    };
+    let value = match crossfader_role {
+        LinearCrossfader => x,
+        EqualPowerCrossfader => (x * FRAC_PI_2).sin(),
+        SideA | SideB => return None,
+    };
+    Some(UnitValue::new_clamped(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn equal_power() {
+        // Given
+        let center = UnitValue::new(0.5);
+        // When
+        let a = calculate_crossfade_value(
+            CrossfadeRole::EqualPowerCrossfader,
+            CrossfadeRole::SideA,
+            center,
+        )
+        .unwrap();
+        let b = calculate_crossfade_value(
+            CrossfadeRole::EqualPowerCrossfader,
+            CrossfadeRole::SideB,
+            center,
+        )
+        .unwrap();
+        // Then
+        assert_abs_diff_eq!(a.get(), b.get());
+        assert_abs_diff_eq!(a.get().powi(2) + b.get().powi(2), 1.0, epsilon = 0.000001);
+    }
+
+    #[test]
+    fn linear() {
+        // Given
+        let pos = UnitValue::new(0.25);
+        // When
+        let a =
+            calculate_crossfade_value(CrossfadeRole::LinearCrossfader, CrossfadeRole::SideA, pos)
+                .unwrap();
+        let b =
+            calculate_crossfade_value(CrossfadeRole::LinearCrossfader, CrossfadeRole::SideB, pos)
+                .unwrap();
+        // Then
+        assert_abs_diff_eq!(a.get(), 0.75);
+        assert_abs_diff_eq!(b.get(), 0.25);
+    }
+
+    #[test]
+    fn no_crossfader() {
+        assert_eq!(
+            calculate_crossfade_value(CrossfadeRole::SideA, CrossfadeRole::SideB, UnitValue::MAX),
+            None
+        );
+    }
+}
@@ -1,9 +1,27 @@
 use crate::base::eel;
+use derive_more::Display;
 use helgoboss_learn::{Transformation, TransformationInput, TransformationOutput, UnitValue};
 use std::os::raw::c_void;
 
 use reaper_medium::reaper_str;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Maximum time a single execution of a transformation script may take.
+///
+/// Transformations are executed in the main thread and sometimes even in the audio thread, so a
+/// script which runs much longer than that would make REAPER stutter.
+const MAX_EXECUTION_TIME: Duration = Duration::from_millis(5);
+
+/// Number of consecutive executions exceeding [`MAX_EXECUTION_TIME`] after which the script is
+/// disabled.
+///
+/// A single slow execution can also be caused by the system being busy in general.
+const MAX_CONSECUTIVE_OVERRUNS: u8 = 3;
+
+/// How long a script stays disabled before it's given another chance.
+const DISABLED_DURATION: Duration = Duration::from_secs(10);
 
 #[derive(Default)]
 pub struct AdditionalTransformationInput {
@@ -22,6 +40,72 @@ struct EelUnit {
     y: eel::Variable,
     y_last: eel::Variable,
     rel_time: Option<eel::Variable>,
+    /// Point in time at which the script was compiled. Reference for the atomic timestamps.
+    compiled_at: Instant,
+    /// Number of executions in a row which exceeded the time limit.
+    consecutive_overruns: AtomicU8,
+    /// Milliseconds since compilation until which the script is disabled (0 means enabled).
+    disabled_until: AtomicU64,
+    /// Bit set of issues which occurred but haven't been taken yet.
+    pending_issues: AtomicU8,
+    /// Bit set of issues which have ever occurred. Each issue is reported only once.
+    occurred_issues: AtomicU8,
+}
+
+/// A problem which occurred when executing a transformation script.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
+#[repr(u8)]
+pub enum EelTransformationIssue {
+    #[display(fmt = "EEL transformation produced an invalid number (NaN or infinity)")]
+    NonFiniteOutput = 1,
+    #[display(
+        fmt = "EEL transformation took too long repeatedly and has been disabled for a while"
+    )]
+    TimeLimitExceeded = 2,
+}
+
+impl EelTransformationIssue {
+    const ALL: [Self; 2] = [Self::NonFiniteOutput, Self::TimeLimitExceeded];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl EelUnit {
+    fn report_issue(&self, issue: EelTransformationIssue) {
+        let previously_occurred = self
+            .occurred_issues
+            .fetch_or(issue.bit(), Ordering::Relaxed);
+        if previously_occurred & issue.bit() == 0 {
+            self.pending_issues.fetch_or(issue.bit(), Ordering::Relaxed);
+        }
+    }
+
+    fn millis_since_compilation(&self) -> u64 {
+        self.compiled_at.elapsed().as_millis() as u64
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.millis_since_compilation() < self.disabled_until.load(Ordering::Relaxed)
+    }
+
+    /// Records whether an execution exceeded the time limit and disables the script if this
+    /// happened too often in a row.
+    fn record_execution_time(&self, elapsed: Duration) {
+        if elapsed <= MAX_EXECUTION_TIME {
+            self.consecutive_overruns.store(0, Ordering::Relaxed);
+            return;
+        }
+        let overruns = self.consecutive_overruns.fetch_add(1, Ordering::Relaxed) + 1;
+        if overruns < MAX_CONSECUTIVE_OVERRUNS {
+            return;
+        }
+        self.consecutive_overruns.store(0, Ordering::Relaxed);
+        let until = self.millis_since_compilation() + DISABLED_DURATION.as_millis() as u64;
+        self.disabled_until.store(until, Ordering::Relaxed);
+        self.report_issue(EelTransformationIssue::TimeLimitExceeded);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -90,6 +174,18 @@ impl EelTransformation {
         EelTransformation::compile(eel_script, OutputVariable::X)
     }
 
+    /// Returns the issues which occurred when executing the script for the first time since the
+    /// last call.
+    ///
+    /// Issues are recorded instead of reported immediately because the transformation might be
+    /// executed in the audio thread. Each kind of issue is returned only once per compiled script.
+    pub fn take_issues(&self) -> impl Iterator<Item = EelTransformationIssue> {
+        let bits = self.eel_unit.pending_issues.swap(0, Ordering::Relaxed);
+        EelTransformationIssue::ALL
+            .into_iter()
+            .filter(move |issue| bits & issue.bit() != 0)
+    }
+
     // Compiles the given script and creates an appropriate transformation.
     fn compile(eel_script: &str, result_var: OutputVariable) -> Result<EelTransformation, String> {
         if eel_script.trim().is_empty() {
@@ -117,6 +213,11 @@ impl EelTransformation {
             y,
             y_last,
             rel_time,
+            compiled_at: Instant::now(),
+            consecutive_overruns: AtomicU8::new(0),
+            disabled_until: AtomicU64::new(0),
+            pending_issues: AtomicU8::new(0),
+            occurred_issues: AtomicU8::new(0),
         };
         let transformation = EelTransformation {
             eel_unit: Arc::new(eel_unit),
@@ -140,9 +241,12 @@ impl Transformation for EelTransformation {
         output_value: f64,
         additional_input: AdditionalTransformationInput,
     ) -> Result<TransformationOutput<f64>, &'static str> {
-        let v = unsafe {
+        let eel_unit = &*self.eel_unit;
+        if eel_unit.is_disabled() {
+            return Err("EEL transformation disabled because it took too long");
+        }
+        let (v, elapsed) = unsafe {
             use OutputVariable::*;
-            let eel_unit = &*self.eel_unit;
             let (input_var, output_var) = match self.output_var {
                 X => (eel_unit.y, eel_unit.x),
                 Y => (eel_unit.x, eel_unit.y),
@@ -153,8 +257,16 @@ impl Transformation for EelTransformation {
             if let Some(rel_time_var) = eel_unit.rel_time {
                 rel_time_var.set(input.meta_data.rel_time.as_millis() as _);
             }
+            let start = Instant::now();
             eel_unit.program.execute();
-            output_var.get()
+            (output_var.get(), start.elapsed())
+        };
+        eel_unit.record_execution_time(elapsed);
+        let v = if v.is_finite() {
+            v
+        } else {
+            eel_unit.report_issue(EelTransformationIssue::NonFiniteOutput);
+            sanitize_non_finite_output(v)
         };
         let output = if v == STOP {
             TransformationOutput::Stop
@@ -173,6 +285,15 @@ impl Transformation for EelTransformation {
     }
 }
 
+/// Maps NaN to 0.0 and infinity to the nearest bound of the unit interval.
+fn sanitize_non_finite_output(v: f64) -> f64 {
+    if v == f64::INFINITY {
+        1.0
+    } else {
+        0.0
+    }
+}
+
 /// Exposed as variable `stop`.
 const STOP: f64 = f64::MAX;
 /// Exposed as variable `none`.
@@ -186,3 +307,18 @@ const NONE: f64 = f64::MIN;
 /// It's good that this is encapsulated in a function. Maybe we can improve the behavior in future
 /// by setting an extra output variable in the implementation of our `stop` function.
 const CONTROL_AND_STOP_MAGIC: f64 = 8965019.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_non_finite_outputs() {
+        // Given
+        let inputs = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        // When
+        let outputs = inputs.map(sanitize_non_finite_output);
+        // Then
+        assert_eq!(outputs, [0.0, 1.0, 0.0]);
+    }
+}
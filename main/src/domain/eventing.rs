@@ -1,14 +1,16 @@
 use crate::domain::{
     Compartment, CompoundMappingTarget, ControlLogContext, ControlLogEntry, MappingId,
-    MessageCaptureResult, PluginParamIndex, PluginParams, ProjectionFeedbackValue,
-    QualifiedMappingId, RawParamValue, RealearnClipMatrix,
+    MessageCaptureResult, MidiInputDrops, MissingTargetObject, Notification, PluginParamIndex,
+    PluginParams, ProjectionFeedbackValue, QualifiedMappingId, RawParamValue, RealTimeOverruns,
+    RealearnClipMatrix, VirtualControlElement,
 };
-use helgoboss_learn::AbsoluteValue;
+use helgoboss_learn::{AbsoluteValue, ControlValue};
 use playtime_clip_engine::base::ClipMatrixEvent;
 use reaper_high::ChangeEvent;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 /// An event which is sent to upper layers and processed there
 #[derive(Debug)]
@@ -16,6 +18,7 @@ pub enum DomainEvent<'a> {
     CapturedIncomingMessage(MessageCaptureEvent),
     GlobalControlAndFeedbackStateChanged(GlobalControlAndFeedbackState),
     UpdatedOnMappings(HashSet<QualifiedMappingId>),
+    UpdatedMissingTargetObjects(HashMap<QualifiedMappingId, MissingTargetObject>),
     UpdatedSingleMappingOnState(UpdatedSingleMappingOnStateEvent),
     UpdatedSingleParameterValue {
         index: PluginParamIndex,
@@ -24,6 +27,7 @@ pub enum DomainEvent<'a> {
     UpdatedAllParameters(PluginParams),
     TargetValueChanged(TargetValueChangedEvent<'a>),
     ProjectionFeedback(ProjectionFeedbackValue),
+    ControlElementTouched(ControlElementTouchedEvent),
     MappingMatched(MappingMatchedEvent),
     TargetControlled(TargetControlEvent),
     FullResyncRequested,
@@ -38,6 +42,11 @@ pub enum DomainEvent<'a> {
     ControlSurfaceChangeEventForClipEngine(&'a RealearnClipMatrix, &'a ChangeEvent),
     TimeForCelebratingSuccess,
     ConditionsChanged,
+    RealTimeOverrunsDetected(RealTimeOverruns),
+    MidiInputDropsDetected(MidiInputDrops),
+    /// Feedback which would have been sent if a feedback output was selected (formatted).
+    FeedbackSimulated(String),
+    Notified(Notification),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
@@ -81,6 +90,16 @@ impl MappingMatchedEvent {
     }
 }
 
+/// Emitted when a control element has been touched, that is, when a controller mapping with a
+/// virtual target has been controlled.
+#[derive(Clone, Debug)]
+pub struct ControlElementTouchedEvent {
+    /// Key of the controller mapping, used by the projection to identify the control element.
+    pub mapping_key: Rc<str>,
+    pub control_element: VirtualControlElement,
+    pub value: ControlValue,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct TargetControlEvent {
     pub id: QualifiedMappingId,
@@ -0,0 +1,193 @@
+use crate::domain::{Compartment, QualifiedMappingId};
+use helgoboss_learn::{AbsoluteValue, UnitValue};
+use realearn_api::persistence::{BlinkFeedbackEffect, FeedbackEffect};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const BLINK_INTERVAL: Duration = Duration::from_millis(300);
+const FLASH_DURATION: Duration = Duration::from_millis(150);
+
+/// What should be sent to the source of a mapping because of its feedback effect.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FeedbackEffectOutput {
+    /// Lights fully on.
+    On,
+    /// Lights off.
+    Off,
+    /// The normal feedback, reflecting the current target value.
+    Normal,
+}
+
+/// Keeps track of blinking and flashing mappings and decides when their feedback needs to change.
+#[derive(Debug, Default)]
+pub struct FeedbackEffectEngine {
+    states: HashMap<QualifiedMappingId, EffectState>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum EffectState {
+    Blinking { lit: bool, next_toggle: Instant },
+    Flashing { until: Instant },
+}
+
+impl FeedbackEffectEngine {
+    /// Should be called whenever the target value of a mapping with flash effect changes.
+    ///
+    /// Returns what should be sent instead of the normal feedback.
+    pub fn flash(&mut self, id: QualifiedMappingId, now: Instant) -> FeedbackEffectOutput {
+        self.states.insert(
+            id,
+            EffectState::Flashing {
+                until: now + FLASH_DURATION,
+            },
+        );
+        FeedbackEffectOutput::On
+    }
+
+    /// Should be called regularly for each mapping with a feedback effect.
+    ///
+    /// The target value is only needed for the blink effect. Returns `Some` if the feedback of
+    /// the mapping needs to change.
+    pub fn poll(
+        &mut self,
+        id: QualifiedMappingId,
+        effect: &FeedbackEffect,
+        target_value: Option<AbsoluteValue>,
+        now: Instant,
+    ) -> Option<FeedbackEffectOutput> {
+        match effect {
+            FeedbackEffect::Blink(e) => {
+                let should_blink = target_value
+                    .map(|v| blink_interval_contains(e, v.to_unit_value()))
+                    .unwrap_or(false);
+                if let Some(EffectState::Blinking { lit, next_toggle }) = self.states.get_mut(&id) {
+                    if !should_blink {
+                        let was_lit = *lit;
+                        self.states.remove(&id);
+                        return if was_lit {
+                            None
+                        } else {
+                            Some(FeedbackEffectOutput::Normal)
+                        };
+                    }
+                    if now < *next_toggle {
+                        return None;
+                    }
+                    *lit = !*lit;
+                    *next_toggle = now + BLINK_INTERVAL;
+                    let output = if *lit {
+                        FeedbackEffectOutput::Normal
+                    } else {
+                        FeedbackEffectOutput::Off
+                    };
+                    return Some(output);
+                }
+                if should_blink {
+                    // The normal feedback has been sent already, so we start in the lit phase.
+                    self.states.insert(
+                        id,
+                        EffectState::Blinking {
+                            lit: true,
+                            next_toggle: now + BLINK_INTERVAL,
+                        },
+                    );
+                }
+                None
+            }
+            FeedbackEffect::Flash => {
+                let Some(EffectState::Flashing { until }) = self.states.get(&id) else {
+                    return None;
+                };
+                if now < *until {
+                    return None;
+                }
+                self.states.remove(&id);
+                Some(FeedbackEffectOutput::Normal)
+            }
+        }
+    }
+
+    /// Forgets the effect states of all mappings in the given compartment, e.g. because its
+    /// mappings have been replaced.
+    pub fn clear_compartment(&mut self, compartment: Compartment) {
+        self.states.retain(|id, _| id.compartment != compartment);
+    }
+
+    /// Forgets the effect state of the given mapping, e.g. because its effect has been removed.
+    pub fn forget(&mut self, id: QualifiedMappingId) {
+        self.states.remove(&id);
+    }
+}
+
+fn blink_interval_contains(effect: &BlinkFeedbackEffect, value: UnitValue) -> bool {
+    match effect.target_interval {
+        None => !value.is_zero(),
+        Some(i) => value.get() >= i.0 && value.get() <= i.1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::MappingId;
+
+    #[test]
+    fn blink() {
+        // Given
+        let mut engine = FeedbackEffectEngine::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let effect = FeedbackEffect::Blink(Default::default());
+        let on = Some(AbsoluteValue::Continuous(UnitValue::MAX));
+        let off = Some(AbsoluteValue::Continuous(UnitValue::MIN));
+        let start = Instant::now();
+        // When
+        let starting = engine.poll(id, &effect, on, start);
+        let within_interval = engine.poll(id, &effect, on, start + Duration::from_millis(100));
+        let after_interval = engine.poll(id, &effect, on, start + BLINK_INTERVAL);
+        let after_target_off = engine.poll(id, &effect, off, start + BLINK_INTERVAL);
+        // Then
+        assert_eq!(starting, None);
+        assert_eq!(within_interval, None);
+        assert_eq!(after_interval, Some(FeedbackEffectOutput::Off));
+        assert_eq!(after_target_off, Some(FeedbackEffectOutput::Normal));
+    }
+
+    #[test]
+    fn flash() {
+        // Given
+        let mut engine = FeedbackEffectEngine::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let start = Instant::now();
+        // When
+        let flashing = engine.flash(id, start);
+        let during_flash = engine.poll(id, &FeedbackEffect::Flash, None, start);
+        let after_flash = engine.poll(id, &FeedbackEffect::Flash, None, start + FLASH_DURATION);
+        // Then
+        assert_eq!(flashing, FeedbackEffectOutput::On);
+        assert_eq!(during_flash, None);
+        assert_eq!(after_flash, Some(FeedbackEffectOutput::Normal));
+    }
+
+    #[test]
+    fn clear_only_given_compartment() {
+        // Given
+        let mut engine = FeedbackEffectEngine::default();
+        let main_id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let controller_id = QualifiedMappingId::new(Compartment::Controller, MappingId::random());
+        let start = Instant::now();
+        engine.flash(main_id, start);
+        engine.flash(controller_id, start);
+        // When
+        engine.clear_compartment(Compartment::Controller);
+        // Then
+        let later = start + FLASH_DURATION;
+        assert_eq!(
+            engine.poll(main_id, &FeedbackEffect::Flash, None, later),
+            Some(FeedbackEffectOutput::Normal)
+        );
+        assert_eq!(
+            engine.poll(controller_id, &FeedbackEffect::Flash, None, later),
+            None
+        );
+    }
+}
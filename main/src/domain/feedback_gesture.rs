@@ -0,0 +1,91 @@
+use crate::domain::{Compartment, QualifiedMappingId};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// If the target value of a mapping hasn't been changed from elsewhere for that long, the change
+/// gesture is considered finished.
+const GESTURE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Keeps track of mappings whose target value is currently being changed from elsewhere (e.g. by
+/// mouse or automation) while the mapping itself is not being controlled.
+///
+/// The feedback of those mappings is polled in each main loop cycle until the change gesture is
+/// finished. That way LED rings and motorized faders don't appear stale mid-gesture, even if
+/// REAPER doesn't notify us about each intermediate value.
+#[derive(Debug, Default)]
+pub struct FeedbackGestureTracker {
+    last_external_changes: HashMap<QualifiedMappingId, Instant>,
+}
+
+impl FeedbackGestureTracker {
+    /// Should be called whenever the target value of a continuous mapping changed without the
+    /// mapping having been controlled itself.
+    pub fn notify_external_change(&mut self, id: QualifiedMappingId, now: Instant) {
+        self.last_external_changes.insert(id, now);
+    }
+
+    /// Should be called in each main loop cycle.
+    ///
+    /// Returns the mappings with an ongoing gesture and the mappings whose gesture has just been
+    /// finished. Both should be polled for feedback one more time.
+    pub fn poll(&mut self, now: Instant) -> (Vec<QualifiedMappingId>, Vec<QualifiedMappingId>) {
+        if self.last_external_changes.is_empty() {
+            return (vec![], vec![]);
+        }
+        let mut finished = vec![];
+        self.last_external_changes.retain(|id, last_change| {
+            let is_ongoing = now.saturating_duration_since(*last_change) < GESTURE_TIMEOUT;
+            if !is_ongoing {
+                finished.push(*id);
+            }
+            is_ongoing
+        });
+        let ongoing = self.last_external_changes.keys().copied().collect();
+        (ongoing, finished)
+    }
+
+    /// Forgets the gestures of all mappings in the given compartment, e.g. because its mappings
+    /// have been replaced.
+    pub fn clear_compartment(&mut self, compartment: Compartment) {
+        self.last_external_changes
+            .retain(|id, _| id.compartment != compartment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::MappingId;
+
+    #[test]
+    fn gesture_times_out() {
+        // Given
+        let mut tracker = FeedbackGestureTracker::default();
+        let id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let start = Instant::now();
+        // When
+        tracker.notify_external_change(id, start);
+        let during_gesture = tracker.poll(start + Duration::from_millis(100));
+        let after_gesture = tracker.poll(start + GESTURE_TIMEOUT);
+        let later = tracker.poll(start + GESTURE_TIMEOUT * 2);
+        // Then
+        assert_eq!(during_gesture, (vec![id], vec![]));
+        assert_eq!(after_gesture, (vec![], vec![id]));
+        assert_eq!(later, (vec![], vec![]));
+    }
+
+    #[test]
+    fn clear_only_given_compartment() {
+        // Given
+        let mut tracker = FeedbackGestureTracker::default();
+        let main_id = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let controller_id = QualifiedMappingId::new(Compartment::Controller, MappingId::random());
+        let start = Instant::now();
+        tracker.notify_external_change(main_id, start);
+        tracker.notify_external_change(controller_id, start);
+        // When
+        tracker.clear_compartment(Compartment::Controller);
+        // Then
+        assert_eq!(tracker.poll(start), (vec![main_id], vec![]));
+    }
+}
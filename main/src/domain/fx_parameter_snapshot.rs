@@ -0,0 +1,206 @@
+use crate::domain::Smoother;
+use reaper_high::{Fx, Guid};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Number under which an FX parameter snapshot is saved (starting with 1).
+pub type FxParameterSnapshotId = u32;
+
+/// Number of smoothing time constants which fit into the glide time.
+///
+/// After 5 time constants, more than 99% of the distance is covered. The remainder is applied at
+/// the end of the glide.
+const GLIDE_TIME_CONSTANTS: u32 = 5;
+
+/// Normalized values of all parameters of an FX.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FxParameterSnapshot {
+    /// GUID of the FX from which the snapshot was taken. `None` if the FX doesn't have one.
+    pub fx_guid: Option<Guid>,
+    pub values: Vec<f64>,
+}
+
+impl FxParameterSnapshot {
+    pub fn new(fx_guid: Option<Guid>, values: Vec<f64>) -> Self {
+        Self { fx_guid, values }
+    }
+
+    pub fn capture(fx: &Fx) -> Self {
+        let values = (0..fx.parameter_count())
+            .map(|i| fx.parameter_by_index(i).reaper_normalized_value().get())
+            .collect();
+        Self {
+            fx_guid: fx.guid(),
+            values,
+        }
+    }
+
+    /// Returns whether this snapshot was taken from the given FX.
+    pub fn belongs_to(&self, fx: &Fx) -> bool {
+        match self.fx_guid {
+            None => true,
+            Some(guid) => fx.guid() == Some(guid),
+        }
+    }
+}
+
+/// Contains the FX parameter snapshots of one instance and keeps track of running glides.
+#[derive(Debug, Default)]
+pub struct FxParameterSnapshotContainer {
+    snapshots: BTreeMap<FxParameterSnapshotId, FxParameterSnapshot>,
+    glides: Vec<FxParameterGlide>,
+}
+
+#[derive(Debug)]
+struct FxParameterGlide {
+    fx: Fx,
+    parameters: Vec<GlidingFxParameter>,
+    start_time: Instant,
+    last_poll_time: Instant,
+    duration: Duration,
+}
+
+#[derive(Debug)]
+struct GlidingFxParameter {
+    index: usize,
+    smoother: Smoother,
+    end_value: f64,
+}
+
+impl FxParameterSnapshotContainer {
+    pub fn new(snapshots: BTreeMap<FxParameterSnapshotId, FxParameterSnapshot>) -> Self {
+        Self {
+            snapshots,
+            glides: vec![],
+        }
+    }
+
+    pub fn snapshots(&self) -> &BTreeMap<FxParameterSnapshotId, FxParameterSnapshot> {
+        &self.snapshots
+    }
+
+    pub fn save_snapshot(&mut self, id: FxParameterSnapshotId, fx: &Fx) {
+        self.snapshots.insert(id, FxParameterSnapshot::capture(fx));
+    }
+
+    /// Applies the parameter values of the given snapshot to the given FX.
+    ///
+    /// Fails if the snapshot was taken from another FX. If the glide time is not zero, the values
+    /// are not applied immediately but approached gradually with each call of
+    /// [`Self::poll_glides`]. A running glide of the same FX is replaced.
+    pub fn recall_snapshot(
+        &mut self,
+        id: FxParameterSnapshotId,
+        fx: &Fx,
+        glide_time: Duration,
+    ) -> Result<(), &'static str> {
+        let snapshot = self
+            .snapshots
+            .get(&id)
+            .ok_or("FX parameter snapshot doesn't exist")?;
+        if !snapshot.belongs_to(fx) {
+            return Err("FX parameter snapshot was taken from another FX");
+        }
+        self.glides.retain(|g| &g.fx != fx);
+        if glide_time.is_zero() {
+            apply_values(fx, snapshot.values.iter().copied().enumerate());
+            return Ok(());
+        }
+        let start_values = FxParameterSnapshot::capture(fx).values;
+        let parameters = changed_values(&start_values, &snapshot.values)
+            .map(|(index, start_value, end_value)| GlidingFxParameter {
+                index,
+                smoother: Smoother::new(start_value),
+                end_value,
+            })
+            .collect();
+        let now = Instant::now();
+        let glide = FxParameterGlide {
+            fx: fx.clone(),
+            parameters,
+            start_time: now,
+            last_poll_time: now,
+            duration: glide_time,
+        };
+        self.glides.push(glide);
+        Ok(())
+    }
+
+    /// Advances all running glides. Should be called regularly in the main thread.
+    pub fn poll_glides(&mut self, now: Instant) {
+        self.glides.retain_mut(|g| {
+            if !g.fx.is_available() {
+                return false;
+            }
+            let finished = g.poll(now);
+            apply_values(&g.fx, g.current_values());
+            !finished
+        });
+    }
+}
+
+impl FxParameterGlide {
+    /// Lets the parameter values approach their end values and returns whether the glide is
+    /// finished.
+    fn poll(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_poll_time);
+        self.last_poll_time = now;
+        let finished = now.saturating_duration_since(self.start_time) >= self.duration;
+        let time_constant = if finished {
+            Duration::ZERO
+        } else {
+            self.duration / GLIDE_TIME_CONSTANTS
+        };
+        for p in &mut self.parameters {
+            p.smoother.process(p.end_value, time_constant, elapsed);
+        }
+        finished
+    }
+
+    fn current_values(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.parameters
+            .iter()
+            .map(|p| (p.index, p.smoother.value()))
+    }
+}
+
+/// Returns the index, start and end value of each parameter whose start and end value differ.
+///
+/// If the number of values differs (e.g. because the FX has been updated to a version with more
+/// parameters), only the common parameters are considered.
+#[allow(clippy::float_cmp)]
+fn changed_values<'a>(
+    start_values: &'a [f64],
+    end_values: &'a [f64],
+) -> impl Iterator<Item = (usize, f64, f64)> + 'a {
+    start_values
+        .iter()
+        .zip(end_values.iter())
+        .enumerate()
+        .filter(|(_, (start, end))| start != end)
+        .map(|(i, (start, end))| (i, *start, *end))
+}
+
+fn apply_values(fx: &Fx, values: impl Iterator<Item = (usize, f64)>) {
+    for (i, v) in values {
+        let _ = fx
+            .parameter_by_index(i as u32)
+            .set_reaper_normalized_value(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consider_only_changed_and_common_values() {
+        // Given
+        let start_values = [0.0, 0.5, 1.0, 0.2];
+        let end_values = [1.0, 0.5, 0.0];
+        // When
+        let values: Vec<_> = changed_values(&start_values, &end_values).collect();
+        // Then
+        assert_eq!(values, vec![(0, 0.0, 1.0), (2, 1.0, 0.0)]);
+    }
+}
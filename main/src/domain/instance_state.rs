@@ -2,8 +2,10 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::rc::{Rc, Weak};
+use std::time::{Duration, SystemTime};
 
 use enum_map::EnumMap;
+use helgoboss_learn::{AbsoluteValue, UnitValue};
 use reaper_high::Track;
 use rxrust::prelude::*;
 
@@ -12,9 +14,11 @@ use crate::domain::pot::nks::FilterItemId;
 use crate::domain::pot::{PotUnit, PresetId, RuntimePotUnit};
 use crate::domain::{
     pot, BackboneState, Compartment, FxDescriptor, FxInputClipRecordTask,
-    GlobalControlAndFeedbackState, GroupId, HardwareInputClipRecordTask, InstanceId, MappingId,
-    MappingSnapshotContainer, NormalAudioHookTask, NormalRealTimeTask, QualifiedMappingId, Tag,
-    TagScope, TrackDescriptor, VirtualMappingSnapshotIdForLoad,
+    FxParameterSnapshotContainer, GlobalControlAndFeedbackState, GroupId,
+    HardwareInputClipRecordTask, InstanceId, MappingId, MappingSnapshotContainer, MidiInputDrops,
+    MissingTargetObject, NormalAudioHookTask, NormalRealTimeTask, Notification, NotificationLog,
+    ParameterThinning, QualifiedMappingId, RealTimeOverruns, Tag, TagScope, TrackDescriptor,
+    ValueDisplayPreferences, VirtualMappingSnapshotIdForLoad,
 };
 use playtime_clip_engine::base::{
     ApiClipWithColumn, ClipMatrixEvent, ClipMatrixHandler, ClipRecordInput, ClipRecordTask, Matrix,
@@ -27,6 +31,9 @@ pub type WeakInstanceState = Weak<RefCell<InstanceState>>;
 
 pub type RealearnClipMatrix = Matrix<RealearnClipMatrixHandler>;
 
+/// Sending all feedback more often than that would flood the controller.
+pub const MIN_FEEDBACK_RESYNC_INTERVAL: Duration = Duration::from_secs(1);
+
 /// State connected to the instance which also needs to be accessible from layers *above* the
 /// processing layer (otherwise it could reside in the main processor).
 ///
@@ -78,10 +85,46 @@ pub struct InstanceState {
     /// - Completely derived from mappings, so it's redundant state.
     /// - It's needed by both processing layer and layers above.
     on_mappings: Prop<HashSet<QualifiedMappingId>>,
+    /// The mappings whose target refers to a track or FX which doesn't exist (anymore).
+    ///
+    /// - Not persistent
+    /// - Completely derived from mappings, so it's redundant state.
+    /// - It's needed by layers above the processing layer.
+    missing_target_objects: Prop<HashMap<QualifiedMappingId, MissingTargetObject>>,
     /// Whether control/feedback are globally active.
     ///
     /// Not persistent.
     global_control_and_feedback_state: Prop<GlobalControlAndFeedbackState>,
+    /// Accumulated real-time processing overruns since the instance was loaded.
+    ///
+    /// Not persistent.
+    real_time_overruns: Prop<RealTimeOverruns>,
+    /// Accumulated MIDI input drops (due to flood protection) since the instance was loaded.
+    ///
+    /// Not persistent.
+    midi_input_drops: Prop<MidiInputDrops>,
+    /// The feedback which would have been sent most recently if a feedback output was selected.
+    ///
+    /// Only set if feedback simulation is enabled. Not persistent.
+    simulated_feedback: Prop<Option<String>>,
+    /// Recent problems which the user should know about.
+    ///
+    /// Not persistent.
+    notifications: Prop<NotificationLog>,
+    /// Usage statistics of mappings since the instance was loaded.
+    ///
+    /// - Not persistent
+    /// - Updated by the main processor.
+    mapping_statistics: HashMap<QualifiedMappingId, MappingStatistics>,
+    /// Time which the main processor spent controlling mappings, per compartment.
+    ///
+    /// - Not persistent
+    /// - Updated by the main processor.
+    compartment_processing_times: EnumMap<Compartment, ProcessingTime>,
+    /// Whether the processing time should also be attributed to individual mappings.
+    ///
+    /// Not persistent.
+    mapping_metering_enabled: bool,
     /// All mapping tags whose mappings have been switched on via tag.
     ///
     /// - Persistent
@@ -94,6 +137,27 @@ pub struct InstanceState {
     /// - Set by target "ReaLearn: Enable/disable instances".
     /// - Non-redundant state!
     active_instance_tags: HashSet<Tag>,
+    /// Factor by which all outgoing numeric feedback values of this instance are scaled.
+    ///
+    /// - Persistent
+    /// - Set by target "ReaLearn: Feedback brightness" and in the instance options.
+    feedback_brightness: UnitValue,
+    /// How target values are displayed and entered.
+    ///
+    /// - Persistent
+    /// - Set in the instance options.
+    value_display_preferences: ValueDisplayPreferences,
+    /// Default thinning for FX parameter targets (can be overridden per mapping).
+    ///
+    /// - Persistent
+    /// - Set in the instance options.
+    parameter_thinning: ParameterThinning,
+    /// If set, all feedback is sent again periodically in this interval (for controllers which
+    /// occasionally miss messages).
+    ///
+    /// - Persistent
+    /// - Set in the instance options.
+    feedback_resync_interval: Option<Duration>,
     /// For clip matrix copy and paste via controller.
     ///
     /// Not persistent
@@ -102,6 +166,13 @@ pub struct InstanceState {
     ///
     /// Not persistent
     copied_clips_in_row: Vec<ApiClipWithColumn>,
+    /// Scroll position of the clip matrix view, so that a small grid controller can address a
+    /// larger clip matrix.
+    ///
+    /// - Not persistent
+    /// - Set by target "Clip matrix" with the "Shift clip view ..." actions.
+    /// - Respected when resolving the slot, column and row coordinates of clip targets.
+    clip_view_offset: ClipViewOffset,
     /// Instance track.
     ///
     /// The instance track is persistent but it's persisted from the session, not from here.
@@ -122,6 +193,10 @@ pub struct InstanceState {
     ///
     /// Persistent.
     mapping_snapshot_container: EnumMap<Compartment, MappingSnapshotContainer>,
+    /// FX parameter snapshots.
+    ///
+    /// Persistent.
+    fx_parameter_snapshot_container: FxParameterSnapshotContainer,
     /// Saves the current state for Pot preset navigation.
     ///
     /// Persistent.
@@ -195,11 +270,74 @@ impl ClipMatrixHandler for RealearnClipMatrixHandler {
     }
 }
 
+/// Column and row of the clip matrix slot which is addressed by slot coordinate (0, 0).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct ClipViewOffset {
+    pub column: usize,
+    pub row: usize,
+}
+
+impl ClipViewOffset {
+    pub fn shifted(
+        self,
+        column_delta: i32,
+        row_delta: i32,
+        column_count: usize,
+        row_count: usize,
+    ) -> Self {
+        Self {
+            column: shift_coordinate(self.column, column_delta, column_count),
+            row: shift_coordinate(self.row, row_delta, row_count),
+        }
+    }
+}
+
+fn shift_coordinate(coordinate: usize, delta: i32, count: usize) -> usize {
+    let max = count.saturating_sub(1) as i64;
+    (coordinate as i64 + delta as i64).clamp(0, max) as usize
+}
+
 #[derive(Debug)]
 pub struct MappingInfo {
     pub name: String,
 }
 
+/// Helps finding dead or conflicting mappings in big setups.
+#[derive(Clone, Debug, Default)]
+pub struct MappingStatistics {
+    /// Number of incoming control events which matched the source of this mapping.
+    pub control_event_count: u64,
+    /// When the source of this mapping matched the last time.
+    pub last_matched: Option<SystemTime>,
+    /// Target value which was last sent as feedback.
+    pub last_feedback_value: Option<AbsoluteValue>,
+    /// Time spent controlling this mapping (only recorded if mapping metering is enabled).
+    pub processing_time: ProcessingTime,
+}
+
+/// Accumulated main processor time spent on processing something.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ProcessingTime {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl ProcessingTime {
+    pub fn record(&mut self, delta: Duration) {
+        self.count += 1;
+        self.total += delta;
+        self.max = self.max.max(delta);
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.count as u32
+    }
+}
+
 impl InstanceState {
     pub(super) fn new(
         instance_id: InstanceId,
@@ -222,14 +360,28 @@ impl InstanceState {
             active_mapping_by_group: Default::default(),
             mapping_infos: Default::default(),
             on_mappings: Default::default(),
+            missing_target_objects: Default::default(),
             global_control_and_feedback_state: Default::default(),
+            real_time_overruns: Default::default(),
+            midi_input_drops: Default::default(),
+            simulated_feedback: Default::default(),
+            notifications: Default::default(),
+            mapping_statistics: Default::default(),
+            compartment_processing_times: Default::default(),
+            mapping_metering_enabled: false,
             active_mapping_tags: Default::default(),
             active_instance_tags: Default::default(),
+            feedback_brightness: UnitValue::MAX,
+            value_display_preferences: Default::default(),
+            parameter_thinning: Default::default(),
+            feedback_resync_interval: None,
             copied_clips_in_slot: vec![],
             copied_clips_in_row: vec![],
+            clip_view_offset: Default::default(),
             instance_track_descriptor: Default::default(),
             instance_fx_descriptor: Default::default(),
             mapping_snapshot_container: Default::default(),
+            fx_parameter_snapshot_container: Default::default(),
             pot_unit: Default::default(),
         }
     }
@@ -311,6 +463,18 @@ impl InstanceState {
         &mut self.mapping_snapshot_container[compartment]
     }
 
+    pub fn set_fx_parameter_snapshot_container(&mut self, container: FxParameterSnapshotContainer) {
+        self.fx_parameter_snapshot_container = container;
+    }
+
+    pub fn fx_parameter_snapshot_container(&self) -> &FxParameterSnapshotContainer {
+        &self.fx_parameter_snapshot_container
+    }
+
+    pub fn fx_parameter_snapshot_container_mut(&mut self) -> &mut FxParameterSnapshotContainer {
+        &mut self.fx_parameter_snapshot_container
+    }
+
     /// Marks the given snapshot as the active one for all tags in the given scope and sends
     /// instance feedback.
     pub fn mark_snapshot_active(
@@ -375,6 +539,33 @@ impl InstanceState {
         &self.copied_clips_in_row
     }
 
+    pub fn clip_view_offset(&self) -> ClipViewOffset {
+        self.clip_view_offset
+    }
+
+    /// Shifts the clip view by the given number of columns and rows.
+    ///
+    /// The offset never gets negative and never goes beyond the last column or row of the given
+    /// matrix dimensions. Returns whether the offset has changed.
+    pub fn shift_clip_view(
+        &mut self,
+        column_delta: i32,
+        row_delta: i32,
+        column_count: usize,
+        row_count: usize,
+    ) -> bool {
+        let new_offset =
+            self.clip_view_offset
+                .shifted(column_delta, row_delta, column_count, row_count);
+        if new_offset == self.clip_view_offset {
+            return false;
+        }
+        self.clip_view_offset = new_offset;
+        self.instance_feedback_event_sender
+            .send_complaining(InstanceStateChanged::ClipViewOffset);
+        true
+    }
+
     pub fn owned_clip_matrix(&self) -> Option<&RealearnClipMatrix> {
         use ClipMatrixRef::*;
         match self.clip_matrix_ref.as_ref()? {
@@ -534,10 +725,59 @@ impl InstanceState {
             .send_complaining(InstanceStateChanged::ActiveInstanceTags);
     }
 
+    pub fn feedback_brightness(&self) -> UnitValue {
+        self.feedback_brightness
+    }
+
+    pub fn set_feedback_brightness_without_notification(&mut self, brightness: UnitValue) {
+        self.feedback_brightness = brightness;
+    }
+
+    pub fn set_feedback_brightness(&mut self, brightness: UnitValue) {
+        self.feedback_brightness = brightness;
+        self.instance_feedback_event_sender
+            .send_complaining(InstanceStateChanged::FeedbackBrightness);
+    }
+
+    pub fn value_display_preferences(&self) -> ValueDisplayPreferences {
+        self.value_display_preferences
+    }
+
+    pub fn set_value_display_preferences(&mut self, preferences: ValueDisplayPreferences) {
+        self.value_display_preferences = preferences;
+    }
+
+    pub fn parameter_thinning(&self) -> ParameterThinning {
+        self.parameter_thinning
+    }
+
+    pub fn set_parameter_thinning(&mut self, thinning: ParameterThinning) {
+        self.parameter_thinning = thinning;
+    }
+
+    pub fn feedback_resync_interval(&self) -> Option<Duration> {
+        self.feedback_resync_interval
+    }
+
+    /// Intervals below [`MIN_FEEDBACK_RESYNC_INTERVAL`] are raised to it.
+    pub fn set_feedback_resync_interval(&mut self, interval: Option<Duration>) {
+        self.feedback_resync_interval = interval.map(|i| i.max(MIN_FEEDBACK_RESYNC_INTERVAL));
+    }
+
+    /// Makes the main processor send all feedback again as soon as possible.
+    pub fn request_feedback_resync(&self) {
+        self.instance_feedback_event_sender
+            .send_complaining(InstanceStateChanged::FeedbackResyncRequested);
+    }
+
     pub fn mapping_is_on(&self, id: QualifiedMappingId) -> bool {
         self.on_mappings.get_ref().contains(&id)
     }
 
+    pub fn missing_target_object(&self, id: QualifiedMappingId) -> Option<MissingTargetObject> {
+        self.missing_target_objects.get_ref().get(&id).copied()
+    }
+
     pub fn global_control_and_feedback_state(&self) -> GlobalControlAndFeedbackState {
         self.global_control_and_feedback_state.get()
     }
@@ -548,6 +788,12 @@ impl InstanceState {
         self.on_mappings.changed()
     }
 
+    pub fn missing_target_objects_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.missing_target_objects.changed()
+    }
+
     pub fn global_control_and_feedback_state_changed(
         &self,
     ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
@@ -558,10 +804,149 @@ impl InstanceState {
         self.on_mappings.set(on_mappings);
     }
 
+    pub fn set_missing_target_objects(
+        &mut self,
+        missing_target_objects: HashMap<QualifiedMappingId, MissingTargetObject>,
+    ) {
+        self.missing_target_objects.set(missing_target_objects);
+    }
+
     pub fn set_global_control_and_feedback_state(&mut self, state: GlobalControlAndFeedbackState) {
         self.global_control_and_feedback_state.set(state);
     }
 
+    pub fn real_time_overruns(&self) -> RealTimeOverruns {
+        self.real_time_overruns.get()
+    }
+
+    pub fn real_time_overruns_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.real_time_overruns.changed()
+    }
+
+    pub fn add_real_time_overruns(&mut self, overruns: RealTimeOverruns) {
+        self.real_time_overruns
+            .mut_in_place(|o| o.accumulate(overruns));
+    }
+
+    pub fn midi_input_drops(&self) -> MidiInputDrops {
+        self.midi_input_drops.get()
+    }
+
+    pub fn midi_input_drops_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.midi_input_drops.changed()
+    }
+
+    pub fn add_midi_input_drops(&mut self, drops: MidiInputDrops) {
+        self.midi_input_drops.mut_in_place(|d| d.accumulate(drops));
+    }
+
+    pub fn simulated_feedback(&self) -> Option<&str> {
+        self.simulated_feedback.get_ref().as_deref()
+    }
+
+    pub fn simulated_feedback_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.simulated_feedback.changed()
+    }
+
+    pub fn set_simulated_feedback(&mut self, msg: Option<String>) {
+        self.simulated_feedback.set(msg);
+    }
+
+    pub fn notifications(&self) -> &NotificationLog {
+        self.notifications.get_ref()
+    }
+
+    pub fn notifications_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.notifications.changed()
+    }
+
+    pub fn add_notification(&mut self, notification: Notification) {
+        self.notifications.mut_in_place(|n| n.add(notification));
+    }
+
+    pub fn clear_notifications(&mut self) {
+        self.notifications.mut_in_place(|n| n.clear());
+    }
+
+    pub fn mapping_statistics(&self, id: QualifiedMappingId) -> Option<&MappingStatistics> {
+        self.mapping_statistics.get(&id)
+    }
+
+    /// Forgets the statistics of those mappings in the given compartment which don't exist anymore.
+    pub fn prune_mapping_statistics(
+        &mut self,
+        compartment: Compartment,
+        mapping_exists: impl Fn(QualifiedMappingId) -> bool,
+    ) {
+        self.mapping_statistics
+            .retain(|id, _| id.compartment != compartment || mapping_exists(*id));
+    }
+
+    pub fn record_mapping_matched(&mut self, id: QualifiedMappingId) {
+        let stats = self.mapping_statistics.entry(id).or_default();
+        stats.control_event_count += 1;
+        stats.last_matched = Some(SystemTime::now());
+    }
+
+    /// Attributes the given control processing time to the compartment of the mapping and - if
+    /// mapping metering is enabled - to the mapping itself.
+    pub fn record_mapping_processing_time(&mut self, id: QualifiedMappingId, delta: Duration) {
+        self.compartment_processing_times[id.compartment].record(delta);
+        if self.mapping_metering_enabled {
+            self.mapping_statistics
+                .entry(id)
+                .or_default()
+                .processing_time
+                .record(delta);
+        }
+    }
+
+    pub fn compartment_processing_time(&self, compartment: Compartment) -> ProcessingTime {
+        self.compartment_processing_times[compartment]
+    }
+
+    pub fn mapping_metering_enabled(&self) -> bool {
+        self.mapping_metering_enabled
+    }
+
+    pub fn set_mapping_metering_enabled(&mut self, enabled: bool) {
+        self.mapping_metering_enabled = enabled;
+    }
+
+    /// Returns the metered mappings, most expensive ones (in total) first.
+    pub fn most_expensive_mappings(&self) -> Vec<(QualifiedMappingId, ProcessingTime)> {
+        let mut mappings: Vec<_> = self
+            .mapping_statistics
+            .iter()
+            .filter(|(_, stats)| stats.processing_time.count > 0)
+            .map(|(id, stats)| (*id, stats.processing_time))
+            .collect();
+        mappings.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+        mappings
+    }
+
+    pub fn reset_processing_times(&mut self) {
+        self.compartment_processing_times = Default::default();
+        for stats in self.mapping_statistics.values_mut() {
+            stats.processing_time = Default::default();
+        }
+    }
+
+    pub fn record_mapping_feedback(&mut self, id: QualifiedMappingId, value: AbsoluteValue) {
+        self.mapping_statistics
+            .entry(id)
+            .or_default()
+            .last_feedback_value = Some(value);
+    }
+
     pub fn set_mapping_on(&mut self, id: QualifiedMappingId, is_on: bool) {
         self.on_mappings.mut_in_place(|m| {
             if is_on {
@@ -672,6 +1057,10 @@ pub enum InstanceStateChanged {
     },
     /// For the "ReaLearn: Enable/disable instances" target.
     ActiveInstanceTags,
+    /// For the "ReaLearn: Feedback brightness" target and for resending all feedback.
+    FeedbackBrightness,
+    /// For the "ReaLearn: Resync feedback" target.
+    FeedbackResyncRequested,
     /// For the "ReaLearn: Load mapping snapshot" target.
     MappingSnapshotActivated {
         compartment: Compartment,
@@ -679,6 +1068,9 @@ pub enum InstanceStateChanged {
         snapshot_id: VirtualMappingSnapshotIdForLoad,
     },
     PotStateChanged(PotStateChangedEvent),
+    /// For the "Clip matrix" target with the "Shift clip view ..." actions and for re-resolving
+    /// all clip targets.
+    ClipViewOffset,
 }
 
 #[derive(Debug)]
@@ -699,3 +1091,22 @@ pub enum ClipMatrixRelevance<'a> {
     /// This instance borrows the clip matrix with the given ID.
     Borrows,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_clip_view_offset() {
+        // Given
+        let offset = ClipViewOffset { column: 1, row: 0 };
+        // When
+        let shifted = offset.shifted(1, -1, 3, 8);
+        let clamped = shifted.shifted(1, 10, 3, 8);
+        let back = clamped.shifted(-5, -2, 3, 8);
+        // Then
+        assert_eq!(shifted, ClipViewOffset { column: 2, row: 0 });
+        assert_eq!(clamped, ClipViewOffset { column: 2, row: 7 });
+        assert_eq!(back, ClipViewOffset { column: 0, row: 5 });
+    }
+}
@@ -1,35 +1,43 @@
 use crate::domain::{
-    aggregate_target_values, get_project_options, say, AdditionalFeedbackEvent, BackboneState,
+    aggregate_target_values, calculate_crossfade_value, get_project_options,
+    play_position_in_quarter_notes, say, AdditionalFeedbackEvent, BackboneState,
     ClipMatrixRelevance, Compartment, CompoundChangeEvent, CompoundFeedbackValue,
     CompoundMappingSource, CompoundMappingSourceAddress, CompoundMappingTarget, ControlContext,
-    ControlEvent, ControlEventTimestamp, ControlInput, ControlLogContext, ControlLogEntry,
-    ControlLogEntryKind, ControlMode, ControlOutcome, DeviceFeedbackOutput, DomainEvent,
-    DomainEventHandler, ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackCollector,
-    FeedbackDestinations, FeedbackOutput, FeedbackRealTimeTask, FeedbackResolution,
-    FeedbackSendBehavior, FinalRealFeedbackValue, FinalSourceFeedbackValue,
-    GlobalControlAndFeedbackState, GroupId, HitInstructionContext, HitInstructionResponse,
-    InstanceContainer, InstanceOrchestrationEvent, InstanceStateChanged, IoUpdatedEvent,
-    KeyMessage, LimitedAsciiString, MainMapping, MainSourceMessage, MappingActivationEffect,
-    MappingControlResult, MappingId, MappingInfo, MessageCaptureEvent, MessageCaptureResult,
-    MidiControlInput, MidiDestination, MidiScanResult, NormalRealTimeTask, OrderedMappingIdSet,
-    OrderedMappingMap, OscDeviceId, OscFeedbackTask, PluginParamIndex, PluginParams,
-    PotStateChangedEvent, ProcessorContext, ProjectOptions, ProjectionFeedbackValue,
-    QualifiedClipMatrixEvent, QualifiedMappingId, QualifiedSource, RawParamValue,
-    RealTimeMappingUpdate, RealTimeTargetUpdate, RealearnMonitoringFxParameterValueChangedEvent,
+    ControlElementTouchedEvent, ControlEvent, ControlEventTimestamp, ControlInput,
+    ControlLogContext, ControlLogEntry, ControlLogEntryKind, ControlMode, ControlOutcome,
+    ControlScheduler, ControllerLifecycleMidiData, DeviceFeedbackOutput, DomainEvent,
+    DomainEventHandler, EelTransformationIssue, ExtendedProcessorContext, FeedbackAudioHookTask,
+    FeedbackCollector, FeedbackDestinations, FeedbackEffectEngine, FeedbackGestureTracker,
+    FeedbackOutput, FeedbackRealTimeTask, FeedbackResolution, FeedbackSendBehavior,
+    FinalRealFeedbackValue, FinalSourceFeedbackValue, GlobalControlAndFeedbackState, GroupId,
+    HitInstructionContext, HitInstructionResponse, InstanceContainer, InstanceOrchestrationEvent,
+    InstanceStateChanged, IoUpdatedEvent, KeyMessage, LifecycleMidiMessage, LimitedAsciiString,
+    MainMapping, MainSourceMessage, MappingActivationEffect, MappingControlResult, MappingId,
+    MappingInfo, MessageCaptureEvent, MessageCaptureResult, MidiControlInput, MidiDestination,
+    MidiInputDrops, MidiPassThroughFilter, MidiScanResult, NormalRealTimeTask, Notification,
+    NotificationThrottle, OrderedMappingIdSet, OrderedMappingMap, OscDeviceId, OscFeedbackTask,
+    PluginParamIndex, PluginParams, PotStateChangedEvent, ProcessorContext, ProjectOptions,
+    ProjectionFeedbackValue, PublishedVirtualControlEvent, QualifiedClipMatrixEvent,
+    QualifiedMappingId, QualifiedSource, RawParamValue, RealTimeLogEntry, RealTimeMappingUpdate,
+    RealTimeOverruns, RealTimeTargetUpdate, RealearnMonitoringFxParameterValueChangedEvent,
     RealearnParameterChangePayload, ReaperConfigChange, ReaperMessage, ReaperSourceFeedbackValue,
-    ReaperTarget, SharedInstanceState, SourceReleasedEvent, SpecificCompoundFeedbackValue,
-    TargetControlEvent, TargetValueChangedEvent, UpdatedSingleMappingOnStateEvent,
-    VirtualControlElement, VirtualSourceValue,
+    ReaperTarget, ScheduledControl, SharedInstanceState, SourceReleasedEvent,
+    SpecificCompoundFeedbackValue, TargetControlEvent, TargetValueChangedEvent,
+    UpdatedSingleMappingOnStateEvent, VirtualControlElement, VirtualControlElementSharing,
+    VirtualSourceValue,
 };
 use derive_more::Display;
 use enum_map::EnumMap;
 use helgoboss_learn::{
     AbsoluteValue, AbstractTimestamp, ControlValue, GroupInteraction, MidiSourceValue,
-    MinIsMaxBehavior, ModeControlOptions, RawMidiEvent, SourceContext, Target, BASE_EPSILON,
+    MinIsMaxBehavior, ModeControlOptions, RawMidiEvent, SourceContext, Target, UnitValue,
+    BASE_EPSILON,
 };
 use std::borrow::Cow;
 use std::cell::RefCell;
 
+use crate::base::metrics_util;
+use crate::base::metrics_util::measure_time;
 use crate::base::{NamedChannelSender, SenderToNormalThread, SenderToRealTimeThread};
 use crate::domain::ui_util::{
     format_control_input_with_match_result, format_incoming_midi_message, format_midi_source_value,
@@ -42,7 +50,8 @@ use helgoboss_midi::{ControlChange14BitMessage, ParameterNumberMessage, RawShort
 use playtime_clip_engine::base::ClipMatrixEvent;
 use playtime_clip_engine::rt::{QualifiedSlotChangeEvent, SlotChangeEvent};
 use playtime_clip_engine::{clip_timeline, Timeline};
-use reaper_high::{ChangeEvent, Reaper};
+use realearn_api::persistence::{CrossfadeRole, FeedbackEffect};
+use reaper_high::{ChangeEvent, Project, Reaper};
 use reaper_medium::ReaperNormalizedFxParamValue;
 use rosc::{OscMessage, OscPacket, OscType};
 use slog::{debug, trace};
@@ -51,7 +60,8 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
-use std::{fmt, slice};
+use std::time::{Duration, Instant};
+use std::{fmt, mem, slice};
 
 // This can be come pretty big when multiple track volumes are adjusted at once.
 const FEEDBACK_TASK_QUEUE_SIZE: usize = 20_000;
@@ -59,6 +69,8 @@ const NORMAL_TASK_BULK_SIZE: usize = 32;
 const FEEDBACK_TASK_BULK_SIZE: usize = 64;
 const CONTROL_TASK_BULK_SIZE: usize = 32;
 const PARAMETER_TASK_BULK_SIZE: usize = 32;
+const REAL_TIME_LOG_ENTRY_BULK_SIZE: usize = 100;
+const PUBLISHED_VIRTUAL_CONTROL_EVENT_QUEUE_SIZE: usize = 1000;
 
 pub type SharedMainProcessors<EH> = Rc<RefCell<Vec<MainProcessor<EH>>>>;
 
@@ -68,6 +80,31 @@ pub struct MainProcessor<EH: DomainEventHandler> {
     collections: Collections,
     /// Contains IDs of those mappings who need to be polled as frequently as possible.
     poll_control_mappings: EnumMap<Compartment, OrderedMappingIdSet>,
+    /// Delayed control events and running step sequences.
+    control_scheduler: ControlScheduler,
+    controller_lifecycle: ControllerLifecycle,
+    /// If set, the initial feedback after updating all mappings is held back until this point in
+    /// time (used to stagger feedback of many instances on project load).
+    deferred_feedback_due: Option<Instant>,
+    /// Mappings whose target resolution is deferred until the deferred feedback is due.
+    ///
+    /// On project load, this applies to mappings which don't control anything (their target only
+    /// matters for feedback, which is deferred anyway).
+    deferred_target_resolutions: EnumMap<Compartment, HashSet<MappingId>>,
+    /// Whether the conditions changed while feedback was deferred, so all targets need to be
+    /// refreshed when the deferred feedback is due.
+    conditions_changed_while_deferred: bool,
+    /// When all feedback was sent the last time because of the periodic feedback resync.
+    last_feedback_resync: Instant,
+}
+
+/// Init and shutdown MIDI messages of the controller compartment.
+#[derive(Debug, Default)]
+struct ControllerLifecycle {
+    midi_data: ControllerLifecycleMidiData,
+    /// The feedback output to which the init messages have been sent most recently (and to which
+    /// the shutdown messages should be sent eventually).
+    initialized_output: Option<FeedbackOutput>,
 }
 
 #[derive(Debug)]
@@ -97,6 +134,10 @@ struct Basics<EH: DomainEventHandler> {
         RefCell<HashMap<CompoundMappingSourceAddress, FeedbackChecksum>>,
     target_based_conditional_activation_processors:
         EnumMap<Compartment, TargetBasedConditionalActivationProcessor>,
+    // Also needs to be mutated during feedback processing, see above.
+    feedback_effects: RefCell<FeedbackEffectEngine>,
+    feedback_gestures: RefCell<FeedbackGestureTracker>,
+    notification_throttle: RefCell<NotificationThrottle>,
 }
 
 /// Used for detecting and preventing subsequent duplicate feedback.
@@ -223,6 +264,8 @@ struct Collections {
     ///  could be optimized. However, this is what makes the seek target work currently when
     ///  changing cursor position while stopped.
     milli_dependent_feedback_mappings: EnumMap<Compartment, OrderedMappingIdSet>,
+    /// Contains IDs of those mappings which have a feedback effect (blink, flash).
+    feedback_effect_mappings: EnumMap<Compartment, OrderedMappingIdSet>,
     parameters: PluginParams,
     previous_target_values: EnumMap<Compartment, HashMap<MappingId, AbsoluteValue>>,
 }
@@ -234,10 +277,16 @@ struct Channels {
     normal_task_receiver: crossbeam_channel::Receiver<NormalMainTask>,
     normal_real_time_to_main_thread_task_receiver:
         crossbeam_channel::Receiver<NormalRealTimeToMainThreadTask>,
+    real_time_log_entry_receiver: crossbeam_channel::Receiver<RealTimeLogEntry>,
     feedback_task_receiver: crossbeam_channel::Receiver<FeedbackMainTask>,
     parameter_task_receiver: crossbeam_channel::Receiver<ParameterMainTask>,
     instance_feedback_event_receiver: crossbeam_channel::Receiver<InstanceStateChanged>,
     control_task_receiver: crossbeam_channel::Receiver<ControlMainTask>,
+    /// Given to the virtual control bus when subscribing to virtual control events of other
+    /// instances.
+    published_virtual_control_event_sender: SenderToNormalThread<PublishedVirtualControlEvent>,
+    published_virtual_control_event_receiver:
+        crossbeam_channel::Receiver<PublishedVirtualControlEvent>,
     normal_real_time_task_sender: SenderToRealTimeThread<NormalRealTimeTask>,
     feedback_real_time_task_sender: SenderToRealTimeThread<FeedbackRealTimeTask>,
     feedback_audio_hook_task_sender: SenderToRealTimeThread<FeedbackAudioHookTask>,
@@ -257,6 +306,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         normal_real_time_to_main_thread_task_receiver: crossbeam_channel::Receiver<
             NormalRealTimeToMainThreadTask,
         >,
+        real_time_log_entry_receiver: crossbeam_channel::Receiver<RealTimeLogEntry>,
         parameter_task_receiver: crossbeam_channel::Receiver<ParameterMainTask>,
         control_task_receiver: crossbeam_channel::Receiver<ControlMainTask>,
         instance_feedback_event_receiver: crossbeam_channel::Receiver<InstanceStateChanged>,
@@ -276,6 +326,11 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 "feedback main tasks",
                 FEEDBACK_TASK_QUEUE_SIZE,
             );
+        let (published_virtual_control_event_sender, published_virtual_control_event_receiver) =
+            SenderToNormalThread::new_bounded_channel(
+                "published virtual control events",
+                PUBLISHED_VIRTUAL_CONTROL_EVENT_QUEUE_SIZE,
+            );
         let logger = parent_logger.new(slog::o!("struct" => "MainProcessor"));
         MainProcessor {
             basics: Basics {
@@ -295,10 +350,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     self_normal_sender,
                     normal_task_receiver,
                     normal_real_time_to_main_thread_task_receiver,
+                    real_time_log_entry_receiver,
                     feedback_task_receiver,
                     parameter_task_receiver,
                     instance_feedback_event_receiver,
                     control_task_receiver,
+                    published_virtual_control_event_sender,
+                    published_virtual_control_event_receiver,
                     normal_real_time_task_sender,
                     feedback_real_time_task_sender,
                     feedback_audio_hook_task_sender,
@@ -309,6 +367,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 },
                 last_feedback_checksum_by_address: Default::default(),
                 target_based_conditional_activation_processors: Default::default(),
+                feedback_effects: Default::default(),
+                feedback_gestures: Default::default(),
+                notification_throttle: Default::default(),
             },
             collections: Collections {
                 mappings: Default::default(),
@@ -316,10 +377,17 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 target_touch_dependent_mappings: Default::default(),
                 beat_dependent_feedback_mappings: Default::default(),
                 milli_dependent_feedback_mappings: Default::default(),
+                feedback_effect_mappings: Default::default(),
                 parameters: Default::default(),
                 previous_target_values: Default::default(),
             },
             poll_control_mappings: Default::default(),
+            control_scheduler: Default::default(),
+            controller_lifecycle: Default::default(),
+            deferred_feedback_due: None,
+            deferred_target_resolutions: Default::default(),
+            conditions_changed_while_deferred: false,
+            last_feedback_resync: Instant::now(),
         }
     }
 
@@ -409,9 +477,124 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 break;
             }
         }
+        self.process_published_virtual_control_events(control_is_effectively_enabled);
+        self.process_scheduled_control(control_is_effectively_enabled, timestamp);
         self.poll_control(timestamp);
     }
 
+    /// Processes control events whose processing was delayed and advances running step sequences.
+    fn process_scheduled_control(
+        &mut self,
+        control_is_effectively_enabled: bool,
+        timestamp: ControlEventTimestamp,
+    ) {
+        // Poll in any case so that nothing piles up while control is disabled.
+        let play_position = if self.control_scheduler.is_idle() {
+            None
+        } else {
+            play_position_in_quarter_notes(self.basics.context.project_or_current_project())
+        };
+        let due_items = self.control_scheduler.poll(Instant::now(), play_position);
+        if !control_is_effectively_enabled {
+            return;
+        }
+        for item in due_items {
+            match item {
+                ScheduledControl::Control {
+                    mapping_id,
+                    value,
+                    options,
+                } => {
+                    let _ = self.control_now(
+                        mapping_id.compartment,
+                        mapping_id.id,
+                        ControlEvent::new(value, timestamp),
+                        options,
+                    );
+                }
+                ScheduledControl::SequenceStep { mapping_id, value } => {
+                    let sequence_still_wanted = self.collections.mappings[mapping_id.compartment]
+                        .get(&mapping_id.id)
+                        .map(|m| {
+                            m.control_is_effectively_on()
+                                && m.control_scheduling().step_sequence.is_some()
+                        })
+                        .unwrap_or(false);
+                    if sequence_still_wanted {
+                        self.hit_target(mapping_id, ControlValue::AbsoluteContinuous(value));
+                    } else {
+                        self.control_scheduler.stop_sequence(&mapping_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lets main mappings with virtual sources process virtual control events published by other
+    /// ReaLearn instances (only if this instance subscribed to them).
+    fn process_published_virtual_control_events(&mut self, control_is_effectively_enabled: bool) {
+        // Collect events in any case to not let the channel get full.
+        for published_event in self
+            .basics
+            .channels
+            .published_virtual_control_event_receiver
+            .try_iter()
+            .take(CONTROL_TASK_BULK_SIZE)
+        {
+            if !control_is_effectively_enabled {
+                continue;
+            }
+            self.control_main_mappings_virtually(published_event.event);
+        }
+    }
+
+    /// Lets main mappings with virtual sources process the given virtual control event as if it
+    /// was emitted by a controller mapping of this instance.
+    fn control_main_mappings_virtually(&mut self, event: ControlEvent<VirtualSourceValue>) {
+        let (control_results, match_outcome) =
+            self.basics.process_main_mappings_with_virtual_sources(
+                &mut self.collections.mappings[Compartment::Main],
+                event,
+                ControlOptions::default(),
+                &self.collections.parameters,
+                &mut self.control_scheduler,
+            );
+        if self.basics.settings.virtual_input_logging_enabled {
+            log_virtual_control_input(
+                &self.basics.instance_id,
+                format_control_input_with_match_result(event.payload(), match_outcome),
+            );
+        }
+        for r in control_results {
+            control_mapping_stage_three(
+                &self.basics,
+                &mut self.collections,
+                r.compartment,
+                r.control_result,
+                GroupInteractionProcessing::On(r.group_interaction_input),
+            )
+        }
+    }
+
+    /// Makes sure that this instance receives virtual control events published by other instances
+    /// if and only if it's configured as subscriber.
+    fn sync_virtual_control_bus_subscription(&self) {
+        let backbone_state = BackboneState::get();
+        if self.basics.settings.virtual_control_element_sharing
+            == VirtualControlElementSharing::Subscribe
+        {
+            backbone_state.subscribe_to_virtual_control_events(
+                self.basics.instance_id,
+                self.basics
+                    .channels
+                    .published_virtual_control_event_sender
+                    .clone(),
+            );
+        } else {
+            backbone_state.unsubscribe_from_virtual_control_events(&self.basics.instance_id);
+        }
+    }
+
     fn process_control_task(&mut self, task: ControlMainTask) {
         use ControlMainTask::*;
         match task {
@@ -423,6 +606,25 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             } => {
                 let _ = self.control(compartment, mapping_id, event, options);
             }
+            PublishVirtualControlInput { event } => {
+                BackboneState::get().publish_virtual_control_event(self.basics.instance_id, event);
+            }
+            NotifyControlElementTouched { mapping_id, value } => {
+                if let Some(m) = self
+                    .collections
+                    .mappings_with_virtual_targets
+                    .get(&mapping_id)
+                {
+                    self.basics.process_mapping_match(QualifiedMappingId::new(
+                        Compartment::Controller,
+                        mapping_id,
+                    ));
+                    self.notify_control_element_touched(m, value);
+                }
+            }
+            NotifyRealTimeMappingMatched { mapping_id } => {
+                self.basics.process_mapping_match(mapping_id);
+            }
             LogVirtualControlInput {
                 event: value,
                 match_outcome: match_result,
@@ -471,6 +673,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     fn poll_control(&mut self, timestamp: ControlEventTimestamp) {
+        // Needed by tempo-synced sources such as LFOs.
+        let tempo = self
+            .basics
+            .context
+            .project_or_current_project()
+            .tempo()
+            .bpm();
         for compartment in Compartment::enum_iter() {
             for id in self.poll_control_mappings[compartment].iter() {
                 let (is_source_poll, control_result, group_interaction) = if let Some(m) =
@@ -482,12 +691,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         &self.collections.parameters,
                         control_context,
                     );
-                    let mode_poll_result = if m.mode().wants_to_be_polled() {
+                    let mode_poll_result = if m.mode_wants_to_be_polled() {
                         m.poll_mode(
                             control_context,
                             &self.basics.logger,
                             processor_context,
                             timestamp,
+                            tempo,
                             self.basics.target_control_logger(
                                 ControlLogContext::Polling,
                                 m.qualified_id(),
@@ -511,7 +721,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         (false, mode_poll_result)
                     } else if m.source().wants_to_be_polled() && m.control_is_effectively_on() {
                         // Mode was either not polled at all or without result, poll source.
-                        let res = if let Some(source_control_value) = m.poll_source() {
+                        let res = if let Some(source_control_value) = m.poll_source(tempo) {
                             let control_event = ControlEvent::new(source_control_value, timestamp);
                             control_mapping_stage_one(
                                 &self.basics,
@@ -563,7 +773,6 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                                 ControlValue::AbsoluteContinuous(Default::default()),
                                 timestamp,
                             ),
-                            group_interaction,
                         })
                     } else {
                         GroupInteractionProcessing::Off
@@ -580,10 +789,36 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         mapping_id: MappingId,
         control_event: ControlEvent<ControlValue>,
         options: ControlOptions,
+    ) -> Result<(), &'static str> {
+        let m = self.collections.mappings[compartment]
+            .get(&mapping_id)
+            .ok_or("mapping not found")?;
+        if m.control_is_effectively_on() && m.control_scheduling().is_active() {
+            let project = self.basics.context.project_or_current_project();
+            self.control_scheduler.schedule(
+                m.qualified_id(),
+                m.control_scheduling(),
+                control_event.payload(),
+                options,
+                m.control_scheduling().start(project, Instant::now()),
+            );
+            return Ok(());
+        }
+        self.control_now(compartment, mapping_id, control_event, options)
+    }
+
+    /// Like [`Self::control`] but without taking the control scheduling of the mapping into
+    /// account.
+    fn control_now(
+        &mut self,
+        compartment: Compartment,
+        mapping_id: MappingId,
+        control_event: ControlEvent<ControlValue>,
+        options: ControlOptions,
     ) -> Result<(), &'static str> {
         // Resolving mappings with virtual targets is not necessary anymore. It has
         // been done in the real-time processor already.
-        let (control_result, group_interaction) = {
+        let control_result = {
             let m = self.collections.mappings[compartment]
                 .get_mut(&mapping_id)
                 .ok_or("mapping not found")?;
@@ -597,7 +832,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             if !m.control_is_effectively_on() {
                 return Ok(());
             }
-            let control_result = control_mapping_stage_one_and_two(
+            control_mapping_stage_one_and_two(
                 &self.basics,
                 &self.collections.parameters,
                 m,
@@ -606,8 +841,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 ManualFeedbackProcessing::On {
                     mappings_with_virtual_targets: &self.collections.mappings_with_virtual_targets,
                 },
-            );
-            (control_result, m.group_interaction())
+            )
         };
         control_mapping_stage_three(
             &self.basics,
@@ -617,7 +851,6 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             GroupInteractionProcessing::On(GroupInteractionInput {
                 mapping_id,
                 control_event,
-                group_interaction,
             }),
         );
         Ok(())
@@ -626,104 +859,222 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     /// This should be regularly called by the control surface, even during global target learning.
     pub fn run_essential(&mut self, timestamp: ControlEventTimestamp) {
         self.process_normal_tasks_from_real_time_processor();
+        self.process_log_entries_from_real_time_processor();
         self.process_normal_tasks_from_session(timestamp);
         self.process_parameter_tasks();
+        self.poll_for_feedback_during_gestures();
         self.process_feedback_tasks();
         self.process_instance_feedback_events();
         self.poll_for_feedback();
+        self.poll_fx_parameter_glides();
+        self.send_deferred_feedback_if_due();
+        self.resync_feedback_if_due();
+        self.process_feedback_effects();
+        self.report_eel_transformation_issues();
+    }
+
+    /// Brings problems with EEL transformations (which are just recorded when they occur because
+    /// they might occur in the audio thread) to the attention of the user.
+    ///
+    /// Each kind of problem is reported only once per mapping until the mapping is changed.
+    fn report_eel_transformation_issues(&self) {
+        let all_mappings = Compartment::enum_iter()
+            .flat_map(|compartment| self.collections.mappings[compartment].values())
+            .chain(self.collections.mappings_with_virtual_targets.values());
+        for m in all_mappings {
+            for issue in m.take_eel_transformation_issues() {
+                let notification = match issue {
+                    EelTransformationIssue::NonFiniteOutput => {
+                        Notification::mapping_warning(m.qualified_id(), issue.to_string())
+                    }
+                    EelTransformationIssue::TimeLimitExceeded => {
+                        Notification::mapping_error(m.qualified_id(), issue.to_string())
+                    }
+                };
+                self.basics.notify_user(notification);
+            }
+        }
+    }
+
+    /// Sends all feedback again if a feedback resync interval is set and it has elapsed.
+    ///
+    /// This helps with controllers that occasionally miss messages over unreliable links (e.g.
+    /// Bluetooth MIDI).
+    fn resync_feedback_if_due(&mut self) {
+        let Some(interval) = self
+            .basics
+            .instance_state
+            .borrow()
+            .feedback_resync_interval()
+        else {
+            return;
+        };
+        if self.deferred_feedback_due.is_some() {
+            return;
+        }
+        let now = Instant::now();
+        if now.saturating_duration_since(self.last_feedback_resync) < interval {
+            return;
+        }
+        self.last_feedback_resync = now;
+        measure_time("main_processor.resync_feedback", || {
+            self.send_all_feedback();
+        });
+    }
+
+    /// Lets LEDs blink or flash according to the feedback effects of the mappings.
+    fn process_feedback_effects(&self) {
+        if self.deferred_feedback_due.is_some()
+            || !self.basics.instance_feedback_is_effectively_enabled()
+        {
+            return;
+        }
+        let now = Instant::now();
+        let context = self.basics.control_context();
+        for compartment in Compartment::enum_iter() {
+            for mapping_id in self.collections.feedback_effect_mappings[compartment].iter() {
+                let Some(m) = self.collections.mappings[compartment].get(mapping_id) else {
+                    continue;
+                };
+                let Some(effect) = m.options().feedback_effect else {
+                    continue;
+                };
+                if !m.feedback_is_effectively_on() {
+                    continue;
+                }
+                let target_value = match effect {
+                    FeedbackEffect::Blink(_) => m.current_aggregated_target_value(context),
+                    FeedbackEffect::Flash => None,
+                };
+                let output = self.basics.feedback_effects.borrow_mut().poll(
+                    m.qualified_id(),
+                    &effect,
+                    target_value,
+                    now,
+                );
+                if let Some(output) = output {
+                    self.send_feedback(
+                        FeedbackReason::Normal,
+                        m.feedback_effect_value(output, context),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Resolves the targets and sends the feedback which were held back on project load as soon as
+    /// it's this instance's turn.
+    fn send_deferred_feedback_if_due(&mut self) {
+        let Some(due) = self.deferred_feedback_due else {
+            return;
+        };
+        if Instant::now() < due {
+            return;
+        }
+        self.deferred_feedback_due = None;
+        measure_time("main_processor.resolve_deferred_targets", || {
+            self.resolve_deferred_targets();
+        });
+        measure_time("main_processor.send_deferred_feedback", || {
+            self.send_all_feedback();
+        });
+    }
+
+    fn resolve_deferred_targets(&mut self) {
+        for compartment in Compartment::enum_iter() {
+            let mapping_ids = mem::take(&mut self.deferred_target_resolutions[compartment]);
+            let mut target_updates: Vec<RealTimeTargetUpdate> = vec![];
+            for id in mapping_ids {
+                let Some(m) = self.collections.mappings[compartment].get_mut(&id) else {
+                    continue;
+                };
+                let control_context = self.basics.control_context();
+                let context = ExtendedProcessorContext::new(
+                    &self.basics.context,
+                    &self.collections.parameters,
+                    control_context,
+                );
+                if let Some(target_update) = m.resolve_deferred_target(context, control_context) {
+                    target_updates.push(target_update);
+                }
+                self.basics.notify_user_about_target_resolution_error(m);
+            }
+            self.process_target_updates(compartment, target_updates);
+        }
+        if mem::take(&mut self.conditions_changed_while_deferred) {
+            // Also updates on mappings and missing target objects.
+            self.refresh_all_targets();
+        } else {
+            self.update_on_mappings();
+            self.update_missing_target_objects();
+        }
+    }
+
+    /// Lets FX parameters recalled via "FX: Recall parameter snapshot" glide to their values.
+    fn poll_fx_parameter_glides(&self) {
+        self.basics
+            .instance_state
+            .borrow_mut()
+            .fx_parameter_snapshot_container_mut()
+            .poll_glides(Instant::now());
     }
 
     /// This goes through all mappings that returned "high" feedback resolution - which they do if
     /// there are no appropriate change events to listen to and therefore need feedback polling.
-    #[allow(clippy::float_cmp)]
     fn poll_for_feedback(&mut self) {
         for compartment in Compartment::enum_iter() {
             for mapping_id in self.collections.milli_dependent_feedback_mappings[compartment].iter()
             {
                 if let Some(m) = self.collections.mappings[compartment].get(mapping_id) {
-                    let previous_target_values = &mut self.collections.previous_target_values;
-                    let control_context = self.basics.control_context();
-                    self.basics
-                        .process_feedback_related_reaper_event_for_mapping(
-                            m,
-                            &self.collections.mappings_with_virtual_targets,
-                            &mut |m, t| {
-                                if m.mode().feedback_props_in_use().is_empty() {
-                                    // No feedback props are used, which means we have pure
-                                    // numeric feedback (no textual feedback, no prop-based feedback
-                                    // style settings).
-                                    // Numeric feedback is always in percentages, so we can
-                                    // safely block feedback already here if we encounter
-                                    // duplicate target values. So check for duplicate feedback!
-                                    // TODO-high-discrete Maybe not true anymore with discrete
-                                    //  targets.
-                                    let (affected, new_value) = if let Some(value) =
-                                        t.current_value(control_context)
-                                    {
-                                        // Check if changed
-                                        match previous_target_values[compartment].entry(*mapping_id)
-                                        {
-                                            Entry::Occupied(mut e) => {
-                                                // We really want to resend if there's the slightest
-                                                // difference. It's okay to have direct comparison
-                                                // because we know the source of these two values is
-                                                // the same.
-                                                if e.get().to_unit_value().get()
-                                                    == value.to_unit_value().get()
-                                                {
-                                                    // Value hasn't changed.
-                                                    (false, None)
-                                                } else {
-                                                    // Value has changed.
-                                                    e.insert(value);
-                                                    (true, Some(value))
-                                                }
-                                            }
-                                            Entry::Vacant(e) => {
-                                                // No feedback sent yet for that milli-dependent mapping.
-                                                e.insert(value);
-                                                (true, Some(value))
-                                            }
-                                        }
-                                    } else {
-                                        // Couldn't determine feedback value.
-                                        (false, None)
-                                    };
-                                    if affected {
-                                        m.update_last_non_performance_target_value_if_appropriate(
-                                            new_value,
-                                        );
-                                    }
-                                    (affected, new_value)
-                                } else {
-                                    // We use feedback props. That either means we have numeric
-                                    // feedback with some prop-based feedback style or we have
-                                    // text feedback.
-                                    //
-                                    // Props can change even if the main target value doesn't
-                                    // change!
-                                    //
-                                    // Also, text feedback is not necessarily based on percentages.
-                                    // This means we can have the situation that in terms of
-                                    // percentages (usually relevant for control direction), the
-                                    // current value might be below 0% or above 100%, which would
-                                    // let the percentage (unit value) stay the same. But the
-                                    // text feedback might go beyond that interval, so we should
-                                    // always update it! Example: Seek target with "Use project"
-                                    // enabled.
-
-                                    // We are now required to return the current target value.
-                                    let new_value = t.current_value(control_context);
-                                    (true, new_value)
-                                }
-                            },
-                        );
+                    self.basics.poll_mapping_for_feedback(
+                        m,
+                        &self.collections.mappings_with_virtual_targets,
+                        &mut self.collections.previous_target_values[compartment],
+                    );
                 }
             }
         }
     }
 
+    /// This goes through all mappings whose target value is currently being changed from
+    /// elsewhere (e.g. by mouse or automation) and polls their feedback. It's done before
+    /// processing the other feedback tasks so that LED rings don't lag behind mid-gesture.
+    fn poll_for_feedback_during_gestures(&mut self) {
+        let (ongoing, finished) = self
+            .basics
+            .feedback_gestures
+            .borrow_mut()
+            .poll(Instant::now());
+        for id in ongoing.into_iter().chain(finished.iter().copied()) {
+            if self.collections.milli_dependent_feedback_mappings[id.compartment].contains(&id.id) {
+                // Polled anyway
+                continue;
+            }
+            let Some(m) = self.collections.mappings[id.compartment].get(&id.id) else {
+                continue;
+            };
+            let value_changed = self.basics.poll_mapping_for_feedback(
+                m,
+                &self.collections.mappings_with_virtual_targets,
+                &mut self.collections.previous_target_values[id.compartment],
+            );
+            if value_changed {
+                // The gesture is still going on
+                self.basics.notify_external_target_value_change(m);
+            }
+        }
+        // Settle to normal, event-based feedback
+        for id in finished {
+            if !self.collections.milli_dependent_feedback_mappings[id.compartment].contains(&id.id)
+            {
+                self.collections.previous_target_values[id.compartment].remove(&id.id);
+            }
+        }
+    }
+
     fn process_instance_feedback_events(&mut self) {
+        let mut send_all_feedback_requested = false;
+        let mut clip_view_offset_changed = false;
         for event in self
             .basics
             .channels
@@ -744,6 +1095,16 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     .borrow_mut()
                     .rebuild_pot_indexes();
             }
+            if matches!(
+                event,
+                InstanceStateChanged::FeedbackBrightness
+                    | InstanceStateChanged::FeedbackResyncRequested
+            ) {
+                send_all_feedback_requested = true;
+            }
+            if matches!(event, InstanceStateChanged::ClipViewOffset) {
+                clip_view_offset_changed = true;
+            }
             self.process_feedback_related_reaper_event(|mapping, target| {
                 mapping.process_change_event(
                     target,
@@ -752,6 +1113,54 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 )
             });
         }
+        if clip_view_offset_changed {
+            // Clip targets now address different slots
+            self.refresh_targets_affected_by_clip_view_offset();
+        }
+        if send_all_feedback_requested {
+            // Brightness affects the feedback of all mappings, a resync resends it deliberately
+            self.send_all_feedback();
+        }
+    }
+
+    fn refresh_targets_affected_by_clip_view_offset(&mut self) {
+        for compartment in Compartment::enum_iter() {
+            let mut target_updates: Vec<RealTimeTargetUpdate> = vec![];
+            let mut changed_mappings = vec![];
+            let mut unused_sources = self.currently_feedback_enabled_sources(compartment, true);
+            for m in all_mappings_in_compartment_mut(
+                &mut self.collections.mappings,
+                &mut self.collections.mappings_with_virtual_targets,
+                compartment,
+            ) {
+                if m.target_can_be_affected_by_clip_view_offset() {
+                    let control_context = self.basics.control_context();
+                    let context = ExtendedProcessorContext::new(
+                        &self.basics.context,
+                        &self.collections.parameters,
+                        control_context,
+                    );
+                    if let Some(target_update) = m.refresh_target(context, control_context) {
+                        target_updates.push(target_update);
+                        changed_mappings.push(m.id());
+                        self.basics.notify_user_about_target_resolution_error(m);
+                    }
+                }
+                if m.feedback_is_effectively_on() {
+                    // Mark source as used
+                    if let Some(addr) = m.source().extract_feedback_address() {
+                        unused_sources.remove(&addr);
+                    }
+                }
+            }
+            self.process_mapping_updates_due_to_activation_changes(
+                compartment,
+                vec![],
+                target_updates,
+                unused_sources,
+                changed_mappings.into_iter(),
+            );
+        }
     }
 
     /// Polls the clip matrix of this ReaLearn instance, if existing and only if it's an owned one
@@ -1017,6 +1426,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             .handle_event_ignoring_error(DomainEvent::UpdatedSingleParameterValue { index, value });
         // Determine and process activation effects
         let compartment = Compartment::by_plugin_param_index(index);
+        let project = self.basics.context.project_or_current_project();
         let activation_effects: Vec<MappingActivationEffect> = self
             .all_mappings_in_compartment(compartment)
             .filter_map(|m| {
@@ -1024,6 +1434,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     &self.collections.parameters,
                     index,
                     previous_value,
+                    project,
                 )
             })
             .collect();
@@ -1063,6 +1474,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         // parameters at the same time, we need to separate into READ activation
         // effects and WRITE activation updates.
         // 2. Mapping activation: Write
+        let project = self.basics.context.project();
         let mapping_updates: Vec<RealTimeMappingUpdate> = activation_effects
             .into_iter()
             .filter_map(|eff| {
@@ -1073,7 +1485,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     compartment,
                     eff.id,
                 )?;
-                m.update_activation_from_effect(eff)
+                let update = m.update_activation_from_effect(eff)?;
+                self.basics.invoke_lifecycle_actions(m, &update, project);
+                Some(update)
             })
             .collect();
         // 3. Mappings with real targets: Refresh targets and determine unused sources
@@ -1089,6 +1503,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 if let Some(target_update) = m.refresh_target(context, control_context) {
                     target_updates.push(target_update);
                     changed_mappings.insert(m.id());
+                    self.basics.notify_user_about_target_resolution_error(m);
                 }
             }
             if m.feedback_is_effectively_on() {
@@ -1137,6 +1552,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.basics
             .event_handler
             .handle_event_ignoring_error(DomainEvent::UpdatedAllParameters(params));
+        let project = self.basics.context.project();
         for compartment in Compartment::enum_iter() {
             let mut mapping_updates: Vec<RealTimeMappingUpdate> = vec![];
             let mut target_updates: Vec<RealTimeTargetUpdate> = vec![];
@@ -1148,9 +1564,11 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 compartment,
             ) {
                 if m.activation_can_be_affected_by_parameters() {
-                    if let Some(update) =
-                        m.update_activation_from_params(&self.collections.parameters)
-                    {
+                    if let Some(update) = m.update_activation_from_params(
+                        &self.collections.parameters,
+                        self.basics.context.project_or_current_project(),
+                    ) {
+                        self.basics.invoke_lifecycle_actions(m, &update, project);
                         mapping_updates.push(update);
                         changed_mappings.push(m.id())
                     }
@@ -1164,7 +1582,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     );
                     if let Some(target_update) = m.refresh_target(context, control_context) {
                         target_updates.push(target_update);
-                        changed_mappings.push(m.id())
+                        changed_mappings.push(m.id());
+                        self.basics.notify_user_about_target_resolution_error(m);
                     }
                 }
                 if m.feedback_is_effectively_on() {
@@ -1193,7 +1612,15 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     self.update_settings(settings);
                 }
                 UpdateAllMappings(compartment, mappings) => {
-                    self.update_all_mappings(compartment, mappings);
+                    measure_time("main_processor.update_all_mappings", || {
+                        self.update_all_mappings(compartment, mappings);
+                    });
+                }
+                DeferInitialFeedback { delay } => {
+                    self.deferred_feedback_due = Some(Instant::now() + delay);
+                }
+                UpdateControllerLifecycleMidi(data) => {
+                    self.update_controller_lifecycle_midi(data);
                 }
                 NotifyRealearnInstanceStarted => {
                     let evt = ControlEvent::new(&ReaperMessage::RealearnInstanceStarted, timestamp);
@@ -1202,9 +1629,20 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 HitTarget { id, value } => {
                     self.hit_target(id, value);
                 }
+                SendTestFeedback { id, value } => {
+                    self.send_test_feedback(id, value);
+                }
+                ControlVirtually { value } => {
+                    if self.basics.instance_control_is_effectively_enabled() {
+                        self.control_main_mappings_virtually(ControlEvent::new(value, timestamp));
+                    }
+                }
                 NotifyConditionsChanged => {
                     self.notify_conditions_changed();
                 }
+                NotifyReaperStateChanged => {
+                    self.notify_reaper_state_changed();
+                }
                 UpdateSingleMapping(mapping) => {
                     self.update_single_mapping(mapping);
                 }
@@ -1310,6 +1748,48 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 self.clear_all_feedback_allowing_source_takeover();
             };
         }
+        self.sync_controller_lifecycle_output();
+    }
+
+    fn update_controller_lifecycle_midi(&mut self, data: ControllerLifecycleMidiData) {
+        if data == self.controller_lifecycle.midi_data {
+            // Happens e.g. when loading a main preset. Reinitializing the controller would be
+            // unnecessary and might even make it flicker.
+            return;
+        }
+        debug!(self.basics.logger, "Updating controller lifecycle MIDI...");
+        let old_data = std::mem::replace(&mut self.controller_lifecycle.midi_data, data);
+        if let Some(output) = self.controller_lifecycle.initialized_output {
+            self.basics
+                .send_lifecycle_midi(output, &old_data.shutdown_midi_messages);
+            self.basics.send_lifecycle_midi(
+                output,
+                &self.controller_lifecycle.midi_data.init_midi_messages,
+            );
+        }
+    }
+
+    /// Sends init or shutdown messages if the effective feedback output has changed.
+    fn sync_controller_lifecycle_output(&mut self) {
+        let effective_output = if self.basics.instance_feedback_is_effectively_enabled() {
+            self.basics.settings.feedback_output
+        } else {
+            None
+        };
+        let previous_output = self.controller_lifecycle.initialized_output;
+        if effective_output == previous_output {
+            return;
+        }
+        let midi_data = &self.controller_lifecycle.midi_data;
+        if let Some(output) = previous_output {
+            self.basics
+                .send_lifecycle_midi(output, &midi_data.shutdown_midi_messages);
+        }
+        if let Some(output) = effective_output {
+            self.basics
+                .send_lifecycle_midi(output, &midi_data.init_midi_messages);
+        }
+        self.controller_lifecycle.initialized_output = effective_output;
     }
 
     /// This should be called on events such as track list change, FX focus etc.
@@ -1333,7 +1813,16 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             // another preset is being loaded anyway.
             return;
         }
-        // Refresh all targets
+        if self.deferred_feedback_due.is_some() {
+            // On project load, the conditions change very often. Refresh the targets just once,
+            // when it's our turn.
+            self.conditions_changed_while_deferred = true;
+            return;
+        }
+        self.refresh_all_targets();
+    }
+
+    fn refresh_all_targets(&mut self) {
         debug!(self.basics.logger, "Refreshing all targets...");
         for compartment in Compartment::enum_iter() {
             let mut target_updates: Vec<RealTimeTargetUpdate> = vec![];
@@ -1350,7 +1839,59 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 );
                 if let Some(target_update) = m.refresh_target(context, control_context) {
                     target_updates.push(target_update);
-                    changed_mappings.push(m.id())
+                    changed_mappings.push(m.id());
+                    self.basics.notify_user_about_target_resolution_error(m);
+                }
+                if m.feedback_is_effectively_on() {
+                    // Mark source as used
+                    if let Some(addr) = m.source().extract_feedback_address() {
+                        unused_sources.remove(&addr);
+                    }
+                }
+            }
+            self.process_target_updates(compartment, target_updates);
+            // Important to send IO event first ...
+            self.notify_feedback_dev_usage_might_have_changed(compartment);
+            self.handle_feedback_after_having_updated_particular_mappings(
+                compartment,
+                unused_sources,
+                changed_mappings.into_iter(),
+            );
+        }
+        // Update on mappings
+        self.update_on_mappings();
+        self.update_missing_target_objects();
+        // Notify session (for UI purposes)
+        self.basics
+            .event_handler
+            .handle_event_ignoring_error(DomainEvent::ConditionsChanged);
+    }
+
+    /// This should be called when REAPER state changes which might affect activation conditions
+    /// (e.g. track selection or play state).
+    ///
+    /// It will only re-evaluate the activation of mappings whose conditions depend on REAPER's
+    /// state, not re-resolve any targets.
+    fn notify_reaper_state_changed(&mut self) {
+        let project = self.basics.context.project();
+        for compartment in Compartment::enum_iter() {
+            let mut mapping_updates: Vec<RealTimeMappingUpdate> = vec![];
+            let mut changed_mappings = vec![];
+            let mut unused_sources = self.currently_feedback_enabled_sources(compartment, true);
+            for m in all_mappings_in_compartment_mut(
+                &mut self.collections.mappings,
+                &mut self.collections.mappings_with_virtual_targets,
+                compartment,
+            ) {
+                if m.activation_can_be_affected_by_reaper_state() {
+                    if let Some(update) = m.update_activation_from_params(
+                        &self.collections.parameters,
+                        self.basics.context.project_or_current_project(),
+                    ) {
+                        self.basics.invoke_lifecycle_actions(m, &update, project);
+                        mapping_updates.push(update);
+                        changed_mappings.push(m.id())
+                    }
                 }
                 if m.feedback_is_effectively_on() {
                     // Mark source as used
@@ -1359,27 +1900,24 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     }
                 }
             }
-            self.process_target_updates(compartment, target_updates);
-            // Important to send IO event first ...
-            self.notify_feedback_dev_usage_might_have_changed(compartment);
-            self.handle_feedback_after_having_updated_particular_mappings(
+            if changed_mappings.is_empty() {
+                continue;
+            }
+            self.process_mapping_updates_due_to_activation_changes(
                 compartment,
+                mapping_updates,
+                vec![],
                 unused_sources,
                 changed_mappings.into_iter(),
             );
         }
-        // Update on mappings
-        self.update_on_mappings();
-        // Notify session (for UI purposes)
-        self.basics
-            .event_handler
-            .handle_event_ignoring_error(DomainEvent::ConditionsChanged);
     }
 
     fn update_settings(&mut self, settings: BasicSettings) {
         let any_main_mapping_is_effectively_on = self.any_main_mapping_is_effectively_on();
         self.basics
             .update_settings_internal(settings, any_main_mapping_is_effectively_on);
+        self.sync_virtual_control_bus_subscription();
         self.potentially_enable_or_disable_control_or_feedback(any_main_mapping_is_effectively_on);
     }
 
@@ -1397,9 +1935,19 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.collections.target_touch_dependent_mappings[compartment].clear();
         self.collections.beat_dependent_feedback_mappings[compartment].clear();
         self.collections.milli_dependent_feedback_mappings[compartment].clear();
+        self.collections.feedback_effect_mappings[compartment].clear();
+        self.basics
+            .feedback_effects
+            .borrow_mut()
+            .clear_compartment(compartment);
+        self.basics
+            .feedback_gestures
+            .borrow_mut()
+            .clear_compartment(compartment);
         self.basics.target_based_conditional_activation_processors[compartment].clear();
         self.collections.previous_target_values[compartment].clear();
         self.poll_control_mappings[compartment].clear();
+        self.deferred_target_resolutions[compartment].clear();
         // Refresh and splinter real-time mappings
         let real_time_mappings = mappings
             .iter_mut()
@@ -1410,14 +1958,23 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     .push(m.id());
                 mapping_infos.insert(m.qualified_id(), m.take_mapping_info());
                 let control_context = self.basics.control_context();
-                m.init_target_and_activation(
-                    ExtendedProcessorContext::new(
-                        &self.basics.context,
-                        &self.collections.parameters,
-                        control_context,
-                    ),
+                let context = ExtendedProcessorContext::new(
+                    &self.basics.context,
+                    &self.collections.parameters,
                     control_context,
                 );
+                if self.deferred_feedback_due.is_some()
+                    && !m.control_is_enabled()
+                    && !m.has_virtual_target()
+                {
+                    // The target of this mapping only matters for feedback, which is deferred
+                    // anyway. Resolve it when it's our turn.
+                    m.init_activation(context);
+                    self.deferred_target_resolutions[compartment].insert(m.id());
+                } else {
+                    m.init_target_and_activation(context, control_context);
+                    self.basics.notify_user_about_target_resolution_error(m);
+                }
                 if m.feedback_is_effectively_on() {
                     // Mark source as used
                     if let Some(addr) = m.source().extract_feedback_address() {
@@ -1434,6 +1991,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 if feedback_resolution == Some(FeedbackResolution::High) {
                     self.collections.milli_dependent_feedback_mappings[compartment].insert(m.id());
                 }
+                if m.options().feedback_effect.is_some() {
+                    self.collections.feedback_effect_mappings[compartment].insert(m.id());
+                }
                 if m.wants_to_be_polled_for_control() {
                     self.poll_control_mappings[compartment].insert(m.id());
                 }
@@ -1447,6 +2007,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         // Update instance state
         {
             let mut instance_state = self.basics.instance_state.borrow_mut();
+            instance_state
+                .prune_mapping_statistics(compartment, |id| mapping_infos.contains_key(&id));
             instance_state.set_mappings_by_group(compartment, mappings_by_group);
             instance_state.set_mapping_infos(mapping_infos);
         }
@@ -1476,6 +2038,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         // lower-floor instances.
         self.handle_feedback_after_having_updated_all_mappings(compartment, unused_sources);
         self.update_on_mappings();
+        self.update_missing_target_objects();
         // Evaluate target-based activation conditions. We do it by reporting
         // target value updates for all lead mappings.
         let lead_mapping_ids =
@@ -1521,10 +2084,41 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         format_midi_source_value(&value),
                     );
                 }
+                ReportOverruns(overruns) => {
+                    self.basics.event_handler.handle_event_ignoring_error(
+                        DomainEvent::RealTimeOverrunsDetected(overruns),
+                    );
+                }
+                ReportMidiInputDrops(drops) => {
+                    // Drops are reported repeatedly while the flood lasts. One notification is
+                    // enough, the main panel shows the accumulated numbers.
+                    let is_first_report =
+                        self.basics.instance_state.borrow().midi_input_drops().count == 0;
+                    if is_first_report {
+                        self.basics.notify_user(Notification::instance_warning(
+                            "MIDI input overflow: incoming messages have been dropped",
+                        ));
+                    }
+                    self.basics
+                        .event_handler
+                        .handle_event_ignoring_error(DomainEvent::MidiInputDropsDetected(drops));
+                }
             }
         }
     }
 
+    fn process_log_entries_from_real_time_processor(&self) {
+        for entry in self
+            .basics
+            .channels
+            .real_time_log_entry_receiver
+            .try_iter()
+            .take(REAL_TIME_LOG_ENTRY_BULK_SIZE)
+        {
+            debug!(self.basics.logger, "Real-time processor: {}", entry);
+        }
+    }
+
     fn any_main_mapping_is_effectively_on(&self) -> bool {
         self.collections.mappings[Compartment::Main]
             .values()
@@ -1624,6 +2218,14 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     .self_normal_sender
                     .send_complaining(NormalMainTask::NotifyConditionsChanged);
             }
+            if let AdditionalFeedbackEvent::GlobalParameterChanged(_) = event {
+                // Activation conditions can query global parameters. They are treated like
+                // REAPER state because they are not owned by this instance.
+                self.basics
+                    .channels
+                    .self_normal_sender
+                    .send_complaining(NormalMainTask::NotifyReaperStateChanged);
+            }
             // Okay, not fired that frequently, we can iterate over all mappings
             self.process_feedback_related_reaper_event(|mapping, target| {
                 mapping.process_change_event(
@@ -1696,6 +2298,28 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 .self_normal_sender
                 .send_complaining(NormalMainTask::NotifyConditionsChanged);
         }
+        // Re-evaluate activation conditions depending on REAPER state
+        let we_have_a_potential_reaper_state_change_event = events.iter().any(|evt| {
+            use ChangeEvent::*;
+            matches!(
+                evt,
+                TrackSelectedChanged(_)
+                    | TrackMuteChanged(_)
+                    | TrackSoloChanged(_)
+                    | TrackArmChanged(_)
+                    | TrackNameChanged(_)
+                    | TrackAdded(_)
+                    | TrackRemoved(_)
+                    | PlayStateChanged(_)
+                    | ProjectSwitched(_)
+            )
+        });
+        if we_have_a_potential_reaper_state_change_event {
+            self.basics
+                .channels
+                .self_normal_sender
+                .send_complaining(NormalMainTask::NotifyReaperStateChanged);
+        }
         // Process for feedback
         for event in events {
             self.process_feedback_related_reaper_event(|mapping, target| {
@@ -1726,13 +2350,21 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     /// avoid a redundant query.
     fn process_feedback_related_reaper_event(
         &self,
-        mut f: impl Fn(&MainMapping, &ReaperTarget) -> (bool, Option<AbsoluteValue>),
+        f: impl Fn(&MainMapping, &ReaperTarget) -> (bool, Option<AbsoluteValue>),
     ) {
         for compartment in Compartment::enum_iter() {
             // Mappings with virtual targets don't need to be considered here because they don't
             // cause feedback themselves.
             for m in self.collections.mappings[compartment].values() {
-                self.process_feedback_related_reaper_event_for_mapping(m, &mut f);
+                let mut target_value_changed = false;
+                self.process_feedback_related_reaper_event_for_mapping(m, &mut |m, t| {
+                    let (value_changed, new_value) = f(m, t);
+                    target_value_changed |= value_changed;
+                    (value_changed, new_value)
+                });
+                if target_value_changed {
+                    self.basics.notify_external_target_value_change(m);
+                }
             }
         }
     }
@@ -1784,6 +2416,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 self.basics.settings.feedback_output
             {
                 if payload.output_devices.contains(&dev_id) {
+                    // The device might have lost its state, so initialize it again.
+                    if let Some(output) = self.controller_lifecycle.initialized_output {
+                        self.basics.send_lifecycle_midi(
+                            output,
+                            &self.controller_lifecycle.midi_data.init_midi_messages,
+                        );
+                    }
                     self.basics
                         .channels
                         .self_normal_sender
@@ -1791,6 +2430,27 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 }
             }
         }
+        // Warn if the devices used by this instance disappeared
+        if let ReaperMessage::MidiDevicesDisconnected(payload) = evt.payload() {
+            if let ControlInput::Midi(MidiControlInput::Device(dev_id)) =
+                self.basics.settings.control_input
+            {
+                if payload.input_devices.contains(&dev_id) {
+                    self.basics.notify_user(Notification::instance_warning(
+                        "MIDI control input device disconnected",
+                    ));
+                }
+            }
+            if let Some(FeedbackOutput::Midi(MidiDestination::Device(dev_id))) =
+                self.basics.settings.feedback_output
+            {
+                if payload.output_devices.contains(&dev_id) {
+                    self.basics.notify_user(Notification::instance_warning(
+                        "MIDI feedback output device disconnected",
+                    ));
+                }
+            }
+        }
         // Inform UI of MIDI device changes
         if matches!(
             evt.payload(),
@@ -1818,6 +2478,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 &mut self.collections.mappings[Compartment::Main],
                 evt,
                 &self.collections.parameters,
+                &mut self.control_scheduler,
             );
         for r in control_results {
             control_mapping_stage_three(
@@ -1870,6 +2531,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 &mut self.collections.mappings[Compartment::Main],
                 evt,
                 &self.collections.parameters,
+                &mut self.control_scheduler,
             );
         for r in control_results {
             control_mapping_stage_three(
@@ -1980,6 +2642,17 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     enforce_target_refresh,
                     ..Default::default()
                 };
+                if m.control_scheduling().is_active() {
+                    let project = self.basics.context.project_or_current_project();
+                    self.control_scheduler.schedule(
+                        m.qualified_id(),
+                        m.control_scheduling(),
+                        control_value,
+                        options,
+                        m.control_scheduling().start(project, Instant::now()),
+                    );
+                    continue;
+                }
                 let control_result = control_mapping_stage_one_and_two(
                     &self.basics,
                     &self.collections.parameters,
@@ -1998,7 +2671,6 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     compartment,
                     group_interaction_input: GroupInteractionInput {
                         mapping_id: m.id(),
-                        group_interaction: m.group_interaction(),
                         control_event,
                     },
                 };
@@ -2044,6 +2716,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.process_target_updates(compartment, target_updates);
         // Update on mappings
         self.update_on_mappings();
+        self.update_missing_target_objects();
     }
 
     fn process_target_updates(
@@ -2117,6 +2790,16 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             .handle_event_ignoring_error(DomainEvent::UpdatedOnMappings(on_mappings));
     }
 
+    fn update_missing_target_objects(&self) {
+        let missing_target_objects = self
+            .all_mappings()
+            .filter_map(|m| Some((m.qualified_id(), m.missing_target_object()?)))
+            .collect();
+        self.basics.event_handler.handle_event_ignoring_error(
+            DomainEvent::UpdatedMissingTargetObjects(missing_target_objects),
+        );
+    }
+
     fn send_feedback(
         &self,
         reason: FeedbackReason,
@@ -2223,6 +2906,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         feedback_output: DeviceFeedbackOutput,
     ) {
         self.update_on_mappings();
+        self.update_missing_target_objects();
         if self
             .basics
             .settings
@@ -2306,10 +2990,14 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         compartment: Compartment,
         now_unused_sources: HashMap<CompoundMappingSourceAddress, QualifiedSource>,
     ) {
-        self.send_feedback(
-            FeedbackReason::Normal,
-            self.feedback_all_in_compartment(compartment),
-        );
+        // On project load, the feedback of all mappings is sent later in one go (when it's our
+        // turn). Clearing unused sources is cheap, so we don't defer that.
+        if self.deferred_feedback_due.is_none() {
+            self.send_feedback(
+                FeedbackReason::Normal,
+                self.feedback_all_in_compartment(compartment),
+            );
+        }
         // It's important to send that *after* sending normal feedback since #660 because we might
         // have global source state (e.g. X-Touch Color state) and the order how we apply feedback
         // values to that global source state matters. It must correspond to the order in which
@@ -2436,6 +3124,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             ),
             control_context,
         );
+        self.basics
+            .notify_user_about_target_resolution_error(&mapping);
         let initial_target_value = mapping.initial_target_value();
         let lead_mapping_ids = mapping.activation_can_be_affected_by_target_values();
         // Sync to real-time processor
@@ -2462,6 +3152,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.update_map_entries(compartment, *mapping);
         self.send_diff_feedback(diff_feedback);
         self.update_single_mapping_on_state(id);
+        self.update_missing_target_objects();
         // This could be a lead mapping in terms of target-based conditional activation. If so,
         // the target value probably changed and all follow mappings can be affected.
         self.process_conditional_activation_target_value_change(
@@ -2635,6 +3326,15 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             self.collections.milli_dependent_feedback_mappings[compartment].shift_remove(&m.id());
             self.collections.previous_target_values[compartment].remove(&m.id());
         }
+        if m.options().feedback_effect.is_some() {
+            self.collections.feedback_effect_mappings[compartment].insert(m.id());
+        } else {
+            self.collections.feedback_effect_mappings[compartment].shift_remove(&m.id());
+            self.basics
+                .feedback_effects
+                .borrow_mut()
+                .forget(m.qualified_id());
+        }
         if m.wants_to_be_polled_for_control() {
             self.poll_control_mappings[compartment].insert(m.id());
         } else {
@@ -2656,6 +3356,16 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         relevant_map.insert(m.id(), m);
     }
 
+    fn send_test_feedback(&self, id: QualifiedMappingId, value: UnitValue) {
+        let feedback_value = self
+            .all_mappings_in_compartment(id.compartment)
+            .find(|m| m.id() == id.id)
+            .and_then(|m| m.test_feedback(value, &self.basics.source_context));
+        if let Some(v) = feedback_value {
+            self.send_feedback(FeedbackReason::Normal, Some(v));
+        }
+    }
+
     fn hit_target(&mut self, id: QualifiedMappingId, value: ControlValue) {
         let control_result = if let Some(m) =
             self.collections.mappings[id.compartment].get_mut(&id.id)
@@ -2735,14 +3445,34 @@ pub enum NormalMainTask {
         id: QualifiedMappingId,
         value: ControlValue,
     },
+    /// Sends the given value to the source of the given mapping, bypassing mode and target.
+    SendTestFeedback {
+        id: QualifiedMappingId,
+        value: UnitValue,
+    },
+    /// Lets main mappings process the given virtual control value as if it was emitted by a
+    /// controller mapping (used by the on-screen touch controller).
+    ControlVirtually {
+        value: VirtualSourceValue,
+    },
     /// This should be sent on events such as track list change, FX focus etc.
     ///
     /// It will trigger a refresh of all targets (re-resolve) or even a preset change (if
     /// auto-load is enabled).
     NotifyConditionsChanged,
+    /// This should be sent on events such as track selection change, play state change etc.
+    ///
+    /// It will re-evaluate all activation conditions which depend on REAPER state.
+    NotifyReaperStateChanged,
     UpdateSettings(BasicSettings),
+    UpdateControllerLifecycleMidi(ControllerLifecycleMidiData),
     PotentiallyEnableOrDisableControlOrFeedback,
     SendAllFeedback,
+    /// Holds back the feedback of the next full mapping updates and sends all feedback after the
+    /// given delay instead. Target resolution which is not needed for control is deferred as well.
+    DeferInitialFeedback {
+        delay: Duration,
+    },
     LogDebugInfo,
     LogMapping(Compartment, MappingId),
     StartLearnSource {
@@ -2763,11 +3493,17 @@ pub struct BasicSettings {
     pub virtual_input_logging_enabled: bool,
     pub virtual_output_logging_enabled: bool,
     pub target_control_logging_enabled: bool,
+    /// If `true` and no feedback output is selected, feedback is shown in the UI instead of being
+    /// sent.
+    pub simulate_feedback: bool,
     pub send_feedback_only_if_armed: bool,
     pub let_matched_events_through: bool,
     pub let_unmatched_events_through: bool,
+    pub midi_pass_through_filter: MidiPassThroughFilter,
     pub reset_feedback_when_releasing_source: bool,
+    pub send_midi_clock: bool,
     pub stay_active_when_project_in_background: StayActiveWhenProjectInBackground,
+    pub virtual_control_element_sharing: VirtualControlElementSharing,
 }
 
 #[derive(
@@ -2860,6 +3596,10 @@ pub enum NormalRealTimeToMainThreadTask {
     LogLifecycleOutput {
         value: MidiSourceValue<'static, RawShortMessage>,
     },
+    /// Sent by the real-time processor if processing audio blocks took too long.
+    ReportOverruns(RealTimeOverruns),
+    /// Sent by the real-time processor if incoming MIDI messages had to be dropped.
+    ReportMidiInputDrops(MidiInputDrops),
 }
 
 /// A parameter-related task (which is potentially sent very frequently, just think of automation).
@@ -2896,6 +3636,21 @@ pub enum ControlMainTask {
         event: ControlEvent<ControlValue>,
         options: ControlOptions,
     },
+    /// Sent by the real-time processor if the instance publishes its virtual control elements.
+    PublishVirtualControlInput {
+        event: ControlEvent<VirtualSourceValue>,
+    },
+    /// Sent by the real-time processor when a controller mapping with a virtual target has been
+    /// controlled.
+    NotifyControlElementTouched {
+        mapping_id: MappingId,
+        value: VirtualSourceValue,
+    },
+    /// Sent by the real-time processor when a mapping with a real-time target has been
+    /// controlled.
+    NotifyRealTimeMappingMatched {
+        mapping_id: QualifiedMappingId,
+    },
     LogVirtualControlInput {
         event: ControlEvent<VirtualSourceValue>,
         match_outcome: MatchOutcome,
@@ -2936,11 +3691,18 @@ pub struct ControlOptions {
 impl<EH: DomainEventHandler> Drop for MainProcessor<EH> {
     fn drop(&mut self) {
         debug!(self.basics.logger, "Dropping main processor...");
+        BackboneState::get().unsubscribe_from_virtual_control_events(&self.basics.instance_id);
         if self.basics.instance_feedback_is_effectively_enabled() {
             // We clear feedback right here and now because that's the last chance.
             // Other instances can take over the feedback output afterwards.
             self.clear_all_feedback_preventing_source_takeover();
         }
+        if let Some(output) = self.controller_lifecycle.initialized_output {
+            self.basics.send_lifecycle_midi(
+                output,
+                &self.controller_lifecycle.midi_data.shutdown_midi_messages,
+            );
+        }
         let released_event = self
             .basics
             .io_released_event(self.any_main_mapping_is_effectively_on());
@@ -3007,11 +3769,149 @@ impl FeedbackReason {
 }
 
 impl<EH: DomainEventHandler> Basics<EH> {
+    /// Queries the current target value of the given mapping and sends feedback if it changed.
+    ///
+    /// Returns whether the numeric target value has changed.
+    #[allow(clippy::float_cmp)]
+    fn poll_mapping_for_feedback(
+        &self,
+        m: &MainMapping,
+        mappings_with_virtual_targets: &OrderedMappingMap<MainMapping>,
+        previous_target_values: &mut HashMap<MappingId, AbsoluteValue>,
+    ) -> bool {
+        let control_context = self.control_context();
+        let mut value_changed = false;
+        self.process_feedback_related_reaper_event_for_mapping(
+            m,
+            mappings_with_virtual_targets,
+            &mut |m, t| {
+                if m.mode().feedback_props_in_use().is_empty() {
+                    // No feedback props are used, which means we have pure
+                    // numeric feedback (no textual feedback, no prop-based feedback
+                    // style settings).
+                    // Numeric feedback is always in percentages, so we can
+                    // safely block feedback already here if we encounter
+                    // duplicate target values. So check for duplicate feedback!
+                    // TODO-high-discrete Maybe not true anymore with discrete
+                    //  targets.
+                    let (affected, new_value) = if let Some(value) =
+                        t.current_value(control_context)
+                    {
+                        // Check if changed
+                        match previous_target_values.entry(m.id()) {
+                            Entry::Occupied(mut e) => {
+                                // We really want to resend if there's the slightest
+                                // difference. It's okay to have direct comparison
+                                // because we know the source of these two values is
+                                // the same.
+                                if e.get().to_unit_value().get() == value.to_unit_value().get() {
+                                    // Value hasn't changed.
+                                    (false, None)
+                                } else {
+                                    // Value has changed.
+                                    e.insert(value);
+                                    (true, Some(value))
+                                }
+                            }
+                            Entry::Vacant(e) => {
+                                // No feedback sent yet for that polled mapping.
+                                e.insert(value);
+                                (true, Some(value))
+                            }
+                        }
+                    } else {
+                        // Couldn't determine feedback value.
+                        (false, None)
+                    };
+                    if affected {
+                        value_changed = true;
+                        m.update_last_non_performance_target_value_if_appropriate(new_value);
+                    }
+                    (affected, new_value)
+                } else {
+                    // We use feedback props. That either means we have numeric
+                    // feedback with some prop-based feedback style or we have
+                    // text feedback.
+                    //
+                    // Props can change even if the main target value doesn't
+                    // change!
+                    //
+                    // Also, text feedback is not necessarily based on percentages.
+                    // This means we can have the situation that in terms of
+                    // percentages (usually relevant for control direction), the
+                    // current value might be below 0% or above 100%, which would
+                    // let the percentage (unit value) stay the same. But the
+                    // text feedback might go beyond that interval, so we should
+                    // always update it! Example: Seek target with "Use project"
+                    // enabled.
+
+                    // We are now required to return the current target value.
+                    let new_value = t.current_value(control_context);
+                    (true, new_value)
+                }
+            },
+        );
+        value_changed
+    }
+
+    /// Should be called whenever the target value of the given mapping changed.
+    ///
+    /// If the change was not caused by controlling the mapping itself, this starts or extends a
+    /// change gesture, which makes sure that the feedback of knobs, encoders and faders is polled
+    /// at a high rate until the gesture is finished.
+    fn notify_external_target_value_change(&self, m: &MainMapping) {
+        if m.is_echo() || !m.feedback_is_effectively_on() || !m.source().is_continuous_element() {
+            return;
+        }
+        self.feedback_gestures
+            .borrow_mut()
+            .notify_external_change(m.qualified_id(), Instant::now());
+    }
+
     pub fn celebrate_success(&self) {
         self.event_handler
             .handle_event_ignoring_error(DomainEvent::TimeForCelebratingSuccess);
     }
 
+    pub fn notify_user(&self, notification: Notification) {
+        let admitted = self
+            .notification_throttle
+            .borrow_mut()
+            .admit(&notification, Instant::now());
+        if !admitted {
+            return;
+        }
+        self.event_handler
+            .handle_event_ignoring_error(DomainEvent::Notified(notification));
+    }
+
+    /// Lets the user know if the target of the given mapping couldn't be resolved.
+    ///
+    /// Disabled mappings are not worth a notification.
+    /// Invokes the lifecycle actions of the given mapping and lets the user know if this failed.
+    fn invoke_lifecycle_actions(
+        &self,
+        m: &MainMapping,
+        update: &RealTimeMappingUpdate,
+        project: Option<Project>,
+    ) {
+        if let Err(e) = m.invoke_lifecycle_actions(update, project) {
+            self.notify_user(Notification::mapping_error(
+                m.qualified_id(),
+                format!("Couldn't invoke lifecycle action: {}", e),
+            ));
+        }
+    }
+
+    fn notify_user_about_target_resolution_error(&self, m: &MainMapping) {
+        if !m.control_is_enabled() && !m.feedback_is_enabled() {
+            return;
+        }
+        if let Some(e) = m.target_resolution_error() {
+            self.notify_user(Notification::mapping_warning(m.qualified_id(), e));
+        }
+    }
+
     pub fn target_control_logger(
         &self,
         context: ControlLogContext,
@@ -3029,6 +3929,10 @@ impl<EH: DomainEventHandler> Basics<EH> {
             }
             // Handle logging to console
             console_logger(entry);
+            // Let user know about failure
+            if entry.kind == ControlLogEntryKind::HitFailed {
+                self.notify_user(Notification::mapping_error(mapping_id, entry.error));
+            }
         }
     }
 
@@ -3207,6 +4111,9 @@ impl<EH: DomainEventHandler> Basics<EH> {
         control_event: ControlEvent<ControlValue>,
         control_was_successful: bool,
     ) {
+        if control_was_successful {
+            self.process_crossfade(collections, compartment, mapping_id);
+        }
         if let Some(m) = collections.mappings[compartment].get(&mapping_id) {
             // Group interaction
             let group_id = m.group_id();
@@ -3298,6 +4205,73 @@ impl<EH: DomainEventHandler> Basics<EH> {
         }
     }
 
+    /// If the given mapping is the crossfader of its group, this controls all side-A and side-B
+    /// mappings of that group according to the crossfader's current target value.
+    fn process_crossfade(
+        &self,
+        collections: &mut Collections,
+        compartment: Compartment,
+        mapping_id: MappingId,
+    ) {
+        let m = match collections.mappings[compartment].get(&mapping_id) {
+            Some(m) => m,
+            None => return,
+        };
+        let crossfader_role = match m.options().crossfade_role {
+            Some(r @ (CrossfadeRole::LinearCrossfader | CrossfadeRole::EqualPowerCrossfader)) => r,
+            _ => return,
+        };
+        let context = self.control_context();
+        let crossfader_pos = match m.current_aggregated_target_value(context) {
+            Some(v) => v
+                .normalize(
+                    &m.mode().settings().target_value_interval,
+                    &m.mode().settings().discrete_target_value_interval,
+                    MinIsMaxBehavior::PreferOne,
+                    m.mode().settings().use_discrete_processing,
+                    BASE_EPSILON,
+                )
+                .to_unit_value(),
+            None => return,
+        };
+        let group_id = m.group_id();
+        self.process_other_mappings(
+            collections,
+            compartment,
+            mapping_id,
+            group_id,
+            |other_mapping, basics, parameters| {
+                let side_role = match other_mapping.options().crossfade_role {
+                    Some(r) => r,
+                    None => return Default::default(),
+                };
+                let value =
+                    match calculate_crossfade_value(crossfader_role, side_role, crossfader_pos) {
+                        Some(v) => v,
+                        None => return Default::default(),
+                    };
+                let control_context = basics.control_context();
+                other_mapping.control_from_target_via_group_interaction(
+                    AbsoluteValue::Continuous(value),
+                    ControlOptions {
+                        // Previous mappings in this transaction could affect
+                        // subsequent mappings!
+                        enforce_target_refresh: true,
+                        ..Default::default()
+                    },
+                    control_context,
+                    &basics.logger,
+                    false,
+                    ExtendedProcessorContext::new(&self.context, parameters, control_context),
+                    basics.target_control_logger(
+                        ControlLogContext::GroupInteraction,
+                        other_mapping.qualified_id(),
+                    ),
+                )
+            },
+        );
+    }
+
     fn process_other_mappings(
         &self,
         collections: &mut Collections,
@@ -3396,14 +4370,26 @@ impl<EH: DomainEventHandler> Basics<EH> {
             let with_projection_feedback = mapping_feedback_is_effectively_on;
             let with_source_feedback = self.instance_feedback_is_effectively_enabled()
                 && mapping_feedback_is_effectively_on;
-            let feedback_value = m
-                .feedback_entry_point(
+            let feedback_value = if with_source_feedback
+                && matches!(m.options().feedback_effect, Some(FeedbackEffect::Flash))
+            {
+                let output = self
+                    .feedback_effects
+                    .borrow_mut()
+                    .flash(m.qualified_id(), Instant::now());
+                m.feedback_effect_value(output, self.control_context())
+            } else {
+                m.feedback_entry_point(
                     with_projection_feedback,
                     with_source_feedback,
                     new_value,
                     self.control_context(),
                 )
-                .map(CompoundFeedbackValue::normal);
+                .map(CompoundFeedbackValue::normal)
+            };
+            if feedback_value.is_some() {
+                self.record_mapping_feedback(m, new_value);
+            }
             self.send_feedback(
                 mappings_with_virtual_targets,
                 FeedbackReason::Normal,
@@ -3413,6 +4399,24 @@ impl<EH: DomainEventHandler> Basics<EH> {
         }
     }
 
+    fn record_mapping_processing_time(&self, m: &MainMapping, delta: Duration) {
+        self.instance_state
+            .borrow_mut()
+            .record_mapping_processing_time(m.qualified_id(), delta);
+        // Per-mapping processing times are available via instance state. Labeling the metric
+        // with the mapping would create one time series per mapping ever controlled.
+        if metrics_util::metrics_enabled() {
+            let labels = vec![("compartment", m.compartment().to_string())];
+            metrics_util::record_duration("main_processor.control_mapping", labels, delta);
+        }
+    }
+
+    fn record_mapping_feedback(&self, m: &MainMapping, target_value: AbsoluteValue) {
+        self.instance_state
+            .borrow_mut()
+            .record_mapping_feedback(m.qualified_id(), target_value);
+    }
+
     /// Inform session, e.g. for UI updates, but also for target-based conditional activation.
     fn notify_target_value_changed(&self, m: &MainMapping, new_value: AbsoluteValue) {
         self.process_target_value_change_for_conditional_activation(m.qualified_id(), new_value);
@@ -3447,6 +4451,23 @@ impl<EH: DomainEventHandler> Basics<EH> {
         self.event_handler.handle_event_ignoring_error(event);
     }
 
+    /// Lets the UI know that the given mapping matched and counts the match in the mapping
+    /// statistics.
+    fn process_mapping_match(&self, id: QualifiedMappingId) {
+        self.event_handler
+            .notify_mapping_matched(id.compartment, id.id);
+        self.instance_state.borrow_mut().record_mapping_matched(id);
+    }
+
+    fn notify_control_element_touched(&self, m: &MainMapping, value: VirtualSourceValue) {
+        let event = DomainEvent::ControlElementTouched(ControlElementTouchedEvent {
+            mapping_key: m.key().clone(),
+            control_element: value.control_element(),
+            value: value.control_value(),
+        });
+        self.event_handler.handle_event_ignoring_error(event);
+    }
+
     /// Processes (controller) mappings with virtual targets.
     ///
     /// This also includes controlling the (main) mappings with corresponding virtual sources.
@@ -3458,6 +4479,7 @@ impl<EH: DomainEventHandler> Basics<EH> {
         main_mappings: &mut OrderedMappingMap<MainMapping>,
         evt: ControlEvent<MainSourceMessage>,
         params: &PluginParams,
+        control_scheduler: &mut ControlScheduler,
     ) -> (Vec<ExtendedMappingControlResult>, MatchOutcome) {
         // Control
         let mut match_outcome = MatchOutcome::Unmatched;
@@ -3472,35 +4494,40 @@ impl<EH: DomainEventHandler> Basics<EH> {
                         return vec![];
                     }
                 };
-                self.event_handler
-                    .notify_mapping_matched(Compartment::Controller, m.id());
-                let results = self.process_main_mappings_with_virtual_sources(
-                    main_mappings,
-                    evt.with_payload(virtual_source_value),
-                    ControlOptions {
-                        // We inherit "Send feedback after control" if it's
-                        // enabled for the virtual mapping. That's the easy way to do it.
-                        // Downside: If multiple real control elements are mapped to one
-                        // virtual control element,
-                        // "feedback after control" will be sent to all of
-                        // those, which is technically not
-                        // necessary. It would be enough to just send it
-                        // to the one that was touched. However, it also doesn't really
-                        // hurt.
-                        enforce_send_feedback_after_control: m.options().feedback_send_behavior
-                            == FeedbackSendBehavior::SendFeedbackAfterControl,
-                        mode_control_options: m.mode_control_options(),
-                        // Not yet important at this point because one virtual target can't
-                        // affect a subsequent one.
-                        enforce_target_refresh: false,
-                    },
-                    params,
-                );
-                let child_match_outcome = if results.is_empty() {
-                    MatchOutcome::Unmatched
-                } else {
-                    MatchOutcome::Matched
-                };
+                self.process_mapping_match(m.qualified_id());
+                self.notify_control_element_touched(m, virtual_source_value);
+                if self.settings.virtual_control_element_sharing
+                    == VirtualControlElementSharing::Publish
+                {
+                    BackboneState::get().publish_virtual_control_event(
+                        self.instance_id,
+                        evt.with_payload(virtual_source_value),
+                    );
+                }
+                let (results, child_match_outcome) = self
+                    .process_main_mappings_with_virtual_sources(
+                        main_mappings,
+                        evt.with_payload(virtual_source_value),
+                        ControlOptions {
+                            // We inherit "Send feedback after control" if it's
+                            // enabled for the virtual mapping. That's the easy way to do it.
+                            // Downside: If multiple real control elements are mapped to one
+                            // virtual control element,
+                            // "feedback after control" will be sent to all of
+                            // those, which is technically not
+                            // necessary. It would be enough to just send it
+                            // to the one that was touched. However, it also doesn't really
+                            // hurt.
+                            enforce_send_feedback_after_control: m.options().feedback_send_behavior
+                                == FeedbackSendBehavior::SendFeedbackAfterControl,
+                            mode_control_options: m.mode_control_options(),
+                            // Not yet important at this point because one virtual target can't
+                            // affect a subsequent one.
+                            enforce_target_refresh: false,
+                        },
+                        params,
+                        control_scheduler,
+                    );
                 match_outcome.upgrade_from(child_match_outcome);
                 if self.settings.virtual_input_logging_enabled {
                     log_virtual_control_input(
@@ -3547,6 +4574,7 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     if self.settings.virtual_output_logging_enabled {
                         log_virtual_feedback_output(&self.instance_id, &value);
                     }
+                    let feedback_brightness = self.instance_state.borrow().feedback_brightness();
                     // Iterate over (controller) mappings with virtual targets.
                     for m in mappings_with_virtual_targets
                         .values()
@@ -3555,6 +4583,13 @@ impl<EH: DomainEventHandler> Basics<EH> {
                         // Should always be true.
                         if let Some(t) = m.virtual_target() {
                             if t.control_element() == value.control_element() {
+                                if destinations.only_if_visible_in_projection
+                                    && !m.options().visible_in_projection
+                                {
+                                    // The main mapping is not shown in the projection because
+                                    // the controller mapping is hidden.
+                                    continue;
+                                }
                                 // Virtual source matched virtual target. The following method
                                 // will always produce real target values (because controller
                                 // mappings can't have virtual sources).
@@ -3562,12 +4597,14 @@ impl<EH: DomainEventHandler> Basics<EH> {
                                     // This clone is unavoidable because we are producing
                                     // real feedback values and these will be sent to another
                                     //  thread, so they must be self-contained.
-                                    Cow::Borrowed(value.feedback_value()),
+                                    // Display targets crop text to the display dimensions.
+                                    t.adjust_feedback_value(Cow::Borrowed(value.feedback_value())),
                                     FeedbackDestinations {
                                         with_source_feedback: destinations.with_source_feedback
                                             && m.feedback_is_enabled(),
                                         ..destinations
                                     },
+                                    feedback_brightness,
                                     &self.source_context,
                                 );
                                 if let Some(SpecificCompoundFeedbackValue::Real(
@@ -3608,6 +4645,27 @@ impl<EH: DomainEventHandler> Basics<EH> {
         }
     }
 
+    /// Sends the given lifecycle MIDI messages to the given feedback output (if it's a MIDI one).
+    pub fn send_lifecycle_midi(&self, output: FeedbackOutput, messages: &[LifecycleMidiMessage]) {
+        if !matches!(output, FeedbackOutput::Midi(_)) {
+            return;
+        }
+        for m in messages {
+            let value = match m {
+                LifecycleMidiMessage::Short(msg) => MidiSourceValue::Plain(*msg),
+                // We don't use this as feedback value, at least not in the sense that it
+                // participates in feedback relay.
+                LifecycleMidiMessage::Raw(data) => MidiSourceValue::single_raw(None, **data),
+            };
+            self.send_direct_source_feedback(
+                output,
+                FeedbackReason::Normal,
+                FinalSourceFeedbackValue::Midi(value),
+                false,
+            );
+        }
+    }
+
     pub fn send_direct_source_feedback(
         &self,
         feedback_output: FeedbackOutput,
@@ -3777,7 +4835,34 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     );
                 }
             }
+        } else if self.settings.simulate_feedback {
+            if let Some(source_feedback_value) = feedback_value {
+                self.simulate_source_feedback(feedback_reason, source_feedback_value);
+            }
+        }
+    }
+
+    /// Makes it possible to verify feedback (e.g. feedback expressions) without having a
+    /// controller connected by showing it in the UI instead of sending it.
+    fn simulate_source_feedback(
+        &self,
+        feedback_reason: FeedbackReason,
+        source_feedback_value: FinalSourceFeedbackValue,
+    ) {
+        let msg = match &source_feedback_value {
+            FinalSourceFeedbackValue::Midi(v) => format_midi_source_value(v),
+            FinalSourceFeedbackValue::Osc(msg) => format_osc_message(msg),
+            FinalSourceFeedbackValue::Reaper(v) => format!("{:?}", v),
+        };
+        if self.settings.real_output_logging_enabled {
+            log_real_feedback_output(
+                &self.instance_id,
+                feedback_reason,
+                format!("{} (simulated)", msg),
+            );
         }
+        self.event_handler
+            .handle_event_ignoring_error(DomainEvent::FeedbackSimulated(msg));
     }
 
     pub fn instance_control_is_effectively_enabled(&self) -> bool {
@@ -3798,28 +4883,45 @@ impl<EH: DomainEventHandler> Basics<EH> {
 
     /// Processes main mappings with virtual sources.
     ///
-    /// Returns a list of all invocation results. Empty if no mapping matched at all.
+    /// Returns the results of all immediate invocations and whether at least one mapping matched.
+    ///
+    /// Mappings with control scheduling (delay, quantization, step sequence) are not controlled
+    /// immediately but handed over to the given scheduler, just like mappings with real sources.
     fn process_main_mappings_with_virtual_sources(
         &self,
         main_mappings: &mut OrderedMappingMap<MainMapping>,
         evt: ControlEvent<VirtualSourceValue>,
         options: ControlOptions,
         params: &PluginParams,
-    ) -> Vec<ExtendedMappingControlResult> {
+        control_scheduler: &mut ControlScheduler,
+    ) -> (Vec<ExtendedMappingControlResult>, MatchOutcome) {
         // Controller mappings can't have virtual sources, so for now we only need to check
         // main mappings.
         let mut enforce_target_refresh = false;
-        main_mappings
+        let mut match_outcome = MatchOutcome::Unmatched;
+        let results = main_mappings
             .values_mut()
             .filter(|m| m.control_is_effectively_on())
             .filter_map(|m| {
                 if let CompoundMappingSource::Virtual(s) = &m.source() {
                     let control_value = s.control(&evt.payload())?;
+                    match_outcome = MatchOutcome::Matched;
                     let control_event = evt.with_payload(control_value);
                     let options = ControlOptions {
                         enforce_target_refresh,
                         ..options
                     };
+                    if m.control_scheduling().is_active() {
+                        let project = self.context.project_or_current_project();
+                        control_scheduler.schedule(
+                            m.qualified_id(),
+                            m.control_scheduling(),
+                            control_value,
+                            options,
+                            m.control_scheduling().start(project, Instant::now()),
+                        );
+                        return None;
+                    }
                     let control_result = control_mapping_stage_one_and_two(
                         self,
                         params,
@@ -3834,7 +4936,6 @@ impl<EH: DomainEventHandler> Basics<EH> {
                         compartment: m.compartment(),
                         group_interaction_input: GroupInteractionInput {
                             mapping_id: m.id(),
-                            group_interaction: m.group_interaction(),
                             control_event,
                         },
                     };
@@ -3843,7 +4944,8 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     None
                 }
             })
-            .collect()
+            .collect();
+        (results, match_outcome)
     }
 }
 
@@ -3980,9 +5082,8 @@ fn control_mapping_stage_one<EH: DomainEventHandler>(
     control_event: ControlEvent<ControlValue>,
     options: ControlOptions,
 ) -> MappingControlResult {
-    basics
-        .event_handler
-        .notify_mapping_matched(m.compartment(), m.id());
+    basics.process_mapping_match(m.qualified_id());
+    let start = Instant::now();
     let result = m.control_from_mode(
         control_event,
         options,
@@ -3992,6 +5093,7 @@ fn control_mapping_stage_one<EH: DomainEventHandler>(
         m.last_non_performance_target_value(),
         basics.target_control_logger(ControlLogContext::Normal, m.qualified_id()),
     );
+    basics.record_mapping_processing_time(m, start.elapsed());
     if result.at_least_one_target_caused_effect && result.celebrate_success {
         basics.celebrate_success();
     }
@@ -4011,6 +5113,9 @@ fn control_mapping_stage_two<EH: DomainEventHandler>(
 ) {
     if let Some(new_value) = control_result.new_target_value {
         basics.notify_target_value_changed(m, new_value);
+        if control_result.feedback_value.is_some() {
+            basics.record_mapping_feedback(m, new_value);
+        }
     }
     if let ManualFeedbackProcessing::On {
         mappings_with_virtual_targets,
@@ -4033,7 +5138,7 @@ fn control_mapping_stage_two<EH: DomainEventHandler>(
 ///    when we control "Enable/disable mappings" via "Browse group mappings". However, we should
 ///    stop there in order to prevent infinite loops. If we really need more in future, we can add
 ///    a third pass.  
-/// 2. Processing group interaction (if enabled).
+/// 2. Processing group interaction and crossfades (if enabled).
 fn control_mapping_stage_three<EH: DomainEventHandler>(
     basics: &Basics<EH>,
     collections: &mut Collections,
@@ -4076,15 +5181,15 @@ fn control_mapping_stage_three<EH: DomainEventHandler>(
         }
     }
     if let GroupInteractionProcessing::On(input) = group_interaction_processing {
-        if input.group_interaction != GroupInteraction::None {
-            basics.process_group_interaction(
-                collections,
-                compartment,
-                input.mapping_id,
-                input.control_event,
-                control_result.at_least_one_target_was_reached,
-            );
-        }
+        // Crossfades don't depend on the group interaction setting, so we can't skip this if
+        // group interaction is "None".
+        basics.process_group_interaction(
+            collections,
+            compartment,
+            input.mapping_id,
+            input.control_event,
+            control_result.at_least_one_target_was_reached,
+        );
     }
 }
 
@@ -4108,7 +5213,6 @@ struct ExtendedMappingControlResult {
 
 struct GroupInteractionInput {
     mapping_id: MappingId,
-    group_interaction: GroupInteraction,
     control_event: ControlEvent<ControlValue>,
 }
 
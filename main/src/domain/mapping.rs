@@ -1,15 +1,18 @@
 use crate::domain::{
-    get_prop_value, prop_feedback_resolution, prop_is_affected_by, ActivationChange,
-    ActivationCondition, BoxedHitInstruction, CompartmentParamIndex, CompoundChangeEvent,
-    ControlContext, ControlEvent, ControlEventTimestamp, ControlOptions, ExtendedProcessorContext,
+    accelerate_control_value, get_prop_value, prop_feedback_resolution, prop_is_affected_by,
+    ActivationChange, ActivationCondition, AudioSource, BoxedHitInstruction, CompartmentParamIndex,
+    CompoundChangeEvent, ControlContext, ControlEvent, ControlEventTimestamp, ControlOptions,
+    ControlScheduling, EelTransformationIssue, ExtendedProcessorContext, FeedbackEffectOutput,
     FeedbackResolution, GroupId, HitResponse, KeyMessage, KeySource, MappingActivationEffect,
-    MappingControlContext, MappingData, MappingInfo, MessageCaptureEvent, MidiScanResult,
-    MidiSource, Mode, OscDeviceId, OscScanResult, PersistentMappingProcessingState,
-    PluginParamIndex, PluginParams, RealTimeMappingUpdate, RealTimeReaperTarget,
+    MappingControlContext, MappingData, MappingInfo, MessageCaptureEvent, MidiMachineControlSource,
+    MidiScanResult, MidiSource, MissingTargetObject, Mode, NoteFeedbackOptions, OscDeviceId,
+    OscScanResult, PersistentMappingProcessingState, PitchBendFilter, PitchBendSettings,
+    PluginParamIndex, PluginParams, PropFunctionCalls, RealTimeMappingUpdate, RealTimeReaperTarget,
     RealTimeTargetUpdate, RealearnParameterChangePayload, RealearnParameterSource, RealearnTarget,
-    ReaperMessage, ReaperSource, ReaperSourceFeedbackValue, ReaperTarget, ReaperTargetType, Tag,
-    TargetCharacter, TrackExclusivity, UnresolvedReaperTarget, VirtualControlElement,
-    VirtualFeedbackValue, VirtualSource, VirtualSourceAddress, VirtualSourceValue, VirtualTarget,
+    ReaperMessage, ReaperSource, ReaperSourceFeedbackValue, ReaperTarget, ReaperTargetType,
+    SourceDebounceFilter, SourceDebounceSettings, Tag, TargetCharacter, TempoSyncedTurbo,
+    TrackExclusivity, UnresolvedReaperTarget, VirtualControlElement, VirtualFeedbackValue,
+    VirtualSource, VirtualSourceAddress, VirtualSourceValue, VirtualTarget,
     COMPARTMENT_PARAMETER_COUNT,
 };
 use derive_more::Display;
@@ -30,8 +33,9 @@ use std::cell::Cell;
 use crate::domain::unresolved_reaper_target::UnresolvedReaperTargetDef;
 use indexmap::map::IndexMap;
 use indexmap::set::IndexSet;
-use reaper_high::{Fx, Project, Track, TrackRoute};
-use reaper_medium::MidiInputDeviceId;
+use realearn_api::persistence::{AccelerationCurve, CrossfadeRole, FeedbackEffect};
+use reaper_high::{Action, Fx, Project, Track, TrackRoute};
+use reaper_medium::{Bpm, MidiInputDeviceId};
 use rosc::OscMessage;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -54,6 +58,13 @@ pub struct ProcessorMappingOptions {
     pub feedback_is_enabled: bool,
     pub feedback_send_behavior: FeedbackSendBehavior,
     pub beep_on_success: bool,
+    pub crossfade_role: Option<CrossfadeRole>,
+    /// Executed by the main processor because it needs timing.
+    pub feedback_effect: Option<FeedbackEffect>,
+    pub invert_feedback: bool,
+    pub visible_in_projection: bool,
+    /// If `true`, feedback is only sent while the mapping is shown in the projection.
+    pub feedback_only_if_visible_in_projection: bool,
 }
 
 impl ProcessorMappingOptions {
@@ -62,7 +73,9 @@ impl ProcessorMappingOptions {
     }
 
     pub fn feedback_is_effectively_enabled(&self) -> bool {
-        self.persistent_processing_state.is_enabled && self.feedback_is_enabled
+        self.persistent_processing_state.is_enabled
+            && self.feedback_is_enabled
+            && (self.visible_in_projection || !self.feedback_only_if_visible_in_projection)
     }
 }
 
@@ -149,7 +162,7 @@ impl From<MappingKey> for String {
 
 const MAX_ECHO_FEEDBACK_DELAY: Duration = Duration::from_millis(100);
 
-#[derive(Debug)]
+#[derive(PartialEq, Debug)]
 pub enum LifecycleMidiMessage {
     #[allow(unused)]
     Short(RawShortMessage),
@@ -162,16 +175,41 @@ pub struct LifecycleMidiData {
     pub deactivation_midi_messages: Vec<LifecycleMidiMessage>,
 }
 
+/// MIDI messages which are sent to the feedback output independently of any mapping, e.g. in
+/// order to switch a controller into a particular mode.
+#[derive(PartialEq, Debug, Default)]
+pub struct ControllerLifecycleMidiData {
+    /// Sent when feedback gets enabled and when the feedback output device gets reconnected.
+    pub init_midi_messages: Vec<LifecycleMidiMessage>,
+    /// Sent when feedback gets disabled and when the instance is unloaded.
+    pub shutdown_midi_messages: Vec<LifecycleMidiMessage>,
+}
+
+/// REAPER actions which are invoked when the activation state of a mapping changes at runtime.
+#[derive(Debug, Default)]
+pub struct LifecycleActionData {
+    pub activation_actions: Vec<Action>,
+    pub deactivation_actions: Vec<Action>,
+}
+
 #[derive(Debug, Default)]
 pub struct MappingExtension {
     /// If it's None, it means it's splintered already.
     lifecycle_midi_data: Option<LifecycleMidiData>,
+    lifecycle_action_data: LifecycleActionData,
+    control_scheduling: ControlScheduling,
 }
 
 impl MappingExtension {
-    pub fn new(lifecycle_midi_data: LifecycleMidiData) -> Self {
+    pub fn new(
+        lifecycle_midi_data: LifecycleMidiData,
+        lifecycle_action_data: LifecycleActionData,
+        control_scheduling: ControlScheduling,
+    ) -> Self {
         Self {
             lifecycle_midi_data: Some(lifecycle_midi_data),
+            lifecycle_action_data,
+            control_scheduling,
         }
     }
 }
@@ -192,6 +230,13 @@ pub struct MainMapping {
     unresolved_target: Option<UnresolvedCompoundMappingTarget>,
     /// Is non-empty if the target resolved successfully.
     targets: Vec<CompoundMappingTarget>,
+    /// Is `Some` if the last attempt to resolve the target failed.
+    target_resolution_error: Option<&'static str>,
+    /// Is `Some` if the last attempt to resolve the target failed because the target refers to a
+    /// track or FX which doesn't exist (anymore).
+    missing_target_object: Option<MissingTargetObject>,
+    /// Function calls used in the feedback expression, parsed once when the mapping is created.
+    prop_function_calls: PropFunctionCalls,
     activation_condition_1: ActivationCondition,
     activation_condition_2: ActivationCondition,
     activation_state: ActivationState,
@@ -223,22 +268,33 @@ impl MainMapping {
         name: String,
         tags: Vec<Tag>,
         source: CompoundMappingSource,
+        note_feedback_options: NoteFeedbackOptions,
+        debounce_settings: SourceDebounceSettings,
+        pitch_bend_settings: Option<PitchBendSettings>,
         mode: Mode,
         group_interaction: GroupInteraction,
+        acceleration_curve: AccelerationCurve,
+        tempo_synced_turbo: Option<TempoSyncedTurbo<ControlEventTimestamp>>,
         unresolved_target: Option<UnresolvedCompoundMappingTarget>,
         activation_condition_1: ActivationCondition,
         activation_condition_2: ActivationCondition,
         options: ProcessorMappingOptions,
         extension: MappingExtension,
     ) -> MainMapping {
+        let prop_function_calls = PropFunctionCalls::parse(mode.feedback_props_in_use());
         MainMapping {
             core: MappingCore {
                 compartment,
                 id,
                 group_id,
                 source,
+                note_feedback_options,
+                debounce_filter: SourceDebounceFilter::new(debounce_settings),
+                pitch_bend_filter: pitch_bend_settings.map(PitchBendFilter::new),
                 mode,
                 group_interaction,
+                acceleration_curve,
+                tempo_synced_turbo,
                 options,
                 time_of_last_control: None,
                 invocation_count: 0,
@@ -251,6 +307,9 @@ impl MainMapping {
             tags,
             unresolved_target,
             targets: vec![],
+            target_resolution_error: None,
+            missing_target_object: None,
+            prop_function_calls,
             activation_condition_1,
             activation_condition_2,
             activation_state: Default::default(),
@@ -364,6 +423,7 @@ impl MainMapping {
             compartment: self.core.compartment,
             mapping_key: self.key.clone(),
             source: self.source().clone(),
+            note_feedback_options: self.core.note_feedback_options.clone(),
         }
     }
 
@@ -379,6 +439,14 @@ impl MainMapping {
         QualifiedMappingId::new(self.core.compartment, self.core.id)
     }
 
+    pub fn key(&self) -> &Rc<str> {
+        &self.key
+    }
+
+    pub fn control_scheduling(&self) -> &ControlScheduling {
+        &self.extension.control_scheduling
+    }
+
     pub fn options(&self) -> &ProcessorMappingOptions {
         &self.core.options
     }
@@ -442,6 +510,18 @@ impl MainMapping {
         !self.targets.is_empty()
     }
 
+    pub fn target_resolution_error(&self) -> Option<&'static str> {
+        self.target_resolution_error
+    }
+
+    pub fn missing_target_object(&self) -> Option<MissingTargetObject> {
+        self.missing_target_object
+    }
+
+    pub fn prop_function_calls(&self) -> &PropFunctionCalls {
+        &self.prop_function_calls
+    }
+
     pub fn check_activation_effect_of_target_value_update(
         &self,
         lead_mapping_id: MappingId,
@@ -462,6 +542,7 @@ impl MainMapping {
         params: &PluginParams,
         plugin_param_index: PluginParamIndex,
         previous_value: f32,
+        project: Project,
     ) -> Option<MappingActivationEffect> {
         let compartment_params = params.compartment_params(self.core.compartment);
         let compartment_param_index = self
@@ -472,11 +553,13 @@ impl MainMapping {
             compartment_params,
             compartment_param_index,
             previous_value,
+            project,
         );
         let effect_2 = self.activation_condition_2.process_param_update(
             compartment_params,
             compartment_param_index,
             previous_value,
+            project,
         );
         MappingActivationEffect::new(self.id(), effect_1, effect_2)
     }
@@ -489,6 +572,16 @@ impl MainMapping {
         }
     }
 
+    /// Returns if this target addresses clip slots, columns or rows.
+    pub fn target_can_be_affected_by_clip_view_offset(&self) -> bool {
+        match &self.unresolved_target {
+            Some(UnresolvedCompoundMappingTarget::Reaper(t)) => {
+                t.can_be_affected_by_clip_view_offset()
+            }
+            _ => false,
+        }
+    }
+
     /// Returns if the mapping's activation conditions can be affected by parameter changes in
     /// general.
     pub fn activation_can_be_affected_by_parameters(&self) -> bool {
@@ -496,6 +589,16 @@ impl MainMapping {
             || self.activation_condition_2.can_be_affected_by_parameters()
     }
 
+    /// Returns if the mapping's activation conditions can be affected by changes of REAPER's
+    /// state.
+    pub fn activation_can_be_affected_by_reaper_state(&self) -> bool {
+        self.activation_condition_1
+            .can_be_affected_by_reaper_state()
+            || self
+                .activation_condition_2
+                .can_be_affected_by_reaper_state()
+    }
+
     /// Returns if the mapping's activation conditions can be affected by target value changes
     /// of other mappings.
     ///
@@ -554,7 +657,37 @@ impl MainMapping {
         let (targets, is_active) = self.resolve_target(context, control_context);
         self.targets = targets;
         self.core.options.target_is_active = is_active;
-        self.update_activation_from_params(context.params());
+        self.update_activation_from_params(
+            context.params(),
+            context.context().project_or_current_project(),
+        );
+        self.init_target_value(control_context);
+    }
+
+    /// Like [`Self::init_target_and_activation`] but leaves the target unresolved for now.
+    ///
+    /// The target must be resolved later by calling [`Self::resolve_deferred_target`]. Until then,
+    /// the target is considered inactive.
+    pub fn init_activation(&mut self, context: ExtendedProcessorContext) {
+        self.update_activation_from_params(
+            context.params(),
+            context.context().project_or_current_project(),
+        );
+    }
+
+    /// Resolves the target of a mapping which has been initialized via [`Self::init_activation`].
+    #[must_use]
+    pub fn resolve_deferred_target(
+        &mut self,
+        context: ExtendedProcessorContext,
+        control_context: ControlContext,
+    ) -> Option<RealTimeTargetUpdate> {
+        let update = self.re_resolve_target(context, control_context);
+        self.init_target_value(control_context);
+        update
+    }
+
+    fn init_target_value(&mut self, control_context: ControlContext) {
         let target_value = self.current_aggregated_target_value(control_context);
         self.initial_target_value = target_value;
         self.last_non_performance_target_value = Cell::new(target_value);
@@ -565,11 +698,17 @@ impl MainMapping {
         context: ExtendedProcessorContext,
         control_context: ControlContext,
     ) -> (Vec<CompoundMappingTarget>, bool) {
+        self.target_resolution_error = None;
+        self.missing_target_object = None;
         match self.unresolved_target.as_ref() {
             None => (vec![], false),
-            Some(ut) => match ut.resolve(context, self.core.compartment).ok() {
-                None => (vec![], false),
-                Some(resolved_targets) => {
+            Some(ut) => match ut.resolve(context, self.core.compartment) {
+                Err(e) => {
+                    self.target_resolution_error = Some(e);
+                    self.missing_target_object = ut.missing_object(context, self.core.compartment);
+                    (vec![], false)
+                }
+                Ok(resolved_targets) => {
                     // Successfully resolved.
                     if let Some(t) = resolved_targets.first() {
                         // We have at least one target, great!
@@ -618,7 +757,12 @@ impl MainMapping {
     }
 
     pub fn wants_to_be_polled_for_control(&self) -> bool {
-        self.core.source.wants_to_be_polled() || self.core.mode.wants_to_be_polled()
+        self.core.source.wants_to_be_polled() || self.mode_wants_to_be_polled()
+    }
+
+    /// Returns `true` if the mode or the tempo-synced turbo needs to be polled.
+    pub fn mode_wants_to_be_polled(&self) -> bool {
+        self.core.mode.wants_to_be_polled() || self.core.tempo_synced_turbo.is_some()
     }
 
     /// The boolean return value tells if the resolved target changed in some way, the activation
@@ -637,6 +781,14 @@ impl MainMapping {
                 }
             }
         }
+        self.re_resolve_target(context, control_context)
+    }
+
+    fn re_resolve_target(
+        &mut self,
+        context: ExtendedProcessorContext,
+        control_context: ControlContext,
+    ) -> Option<RealTimeTargetUpdate> {
         let was_effectively_active_before = self.target_is_effectively_active();
         let (targets, is_active) = self.resolve_target(context, control_context);
         let target_changed = targets != self.targets;
@@ -667,11 +819,14 @@ impl MainMapping {
     pub fn update_activation_from_params(
         &mut self,
         params: &PluginParams,
+        project: Project,
     ) -> Option<RealTimeMappingUpdate> {
         let compartment_params = params.compartment_params(self.core.compartment);
         self.update_activation(
-            self.activation_condition_1.is_fulfilled(compartment_params),
-            self.activation_condition_2.is_fulfilled(compartment_params),
+            self.activation_condition_1
+                .is_fulfilled(compartment_params, project),
+            self.activation_condition_2
+                .is_fulfilled(compartment_params, project),
         )
     }
 
@@ -690,6 +845,36 @@ impl MainMapping {
         self.post_process_activation_update(was_active_before)
     }
 
+    /// Invokes the REAPER actions configured for the activation state change contained in the
+    /// given update.
+    ///
+    /// Should only be called for activation changes at runtime, not when the mapping is synced.
+    ///
+    /// If an action can't be invoked, the remaining actions are invoked nevertheless and the last
+    /// error is returned.
+    pub fn invoke_lifecycle_actions(
+        &self,
+        update: &RealTimeMappingUpdate,
+        project: Option<Project>,
+    ) -> Result<(), &'static str> {
+        let change = match &update.activation_change {
+            None => return Ok(()),
+            Some(c) => c,
+        };
+        let actions = if change.is_active {
+            &self.extension.lifecycle_action_data.activation_actions
+        } else {
+            &self.extension.lifecycle_action_data.deactivation_actions
+        };
+        let mut result = Ok(());
+        for action in actions {
+            if let Err(e) = action.invoke_as_trigger(project) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
     /// Doesn't check if explicitly enabled or disabled.
     pub fn is_active_in_terms_of_activation_state(&self) -> bool {
         self.activation_state.is_active()
@@ -750,6 +935,9 @@ impl MainMapping {
     }
 
     /// This makes the button fire modes work (e.g. "Fire after delay").
+    ///
+    /// The tempo is needed for the tempo-synced turbo.
+    #[allow(clippy::too_many_arguments)]
     #[must_use]
     pub fn poll_mode(
         &mut self,
@@ -757,8 +945,26 @@ impl MainMapping {
         logger: &slog::Logger,
         processor_context: ExtendedProcessorContext,
         timestamp: ControlEventTimestamp,
+        tempo: Bpm,
         log_mode_control_result: impl Fn(ControlLogEntry),
     ) -> MappingControlResult {
+        let turbo_value = self
+            .core
+            .tempo_synced_turbo
+            .as_mut()
+            .and_then(|t| t.poll(timestamp, tempo));
+        if let Some(v) = turbo_value {
+            let inverse = self.core.mode.settings().reverse;
+            return self.control_from_target_via_group_interaction(
+                AbsoluteValue::Continuous(v),
+                ControlOptions::default(),
+                context,
+                logger,
+                inverse,
+                processor_context,
+                log_mode_control_result,
+            );
+        }
         self.control_internal(
             ControlOptions::default(),
             context,
@@ -790,6 +996,21 @@ impl MainMapping {
         last_non_performance_target_value: Option<AbsoluteValue>,
         log_mode_control_result: impl Fn(ControlLogEntry),
     ) -> MappingControlResult {
+        let source_control_event = match self.core.process_pitch_bend(source_control_event) {
+            None => return Default::default(),
+            Some(evt) => evt,
+        };
+        let source_control_event = match self.core.debounce(source_control_event) {
+            None => return Default::default(),
+            Some(evt) => evt,
+        };
+        let source_control_event = self.core.accelerate(source_control_event);
+        if let Some(turbo) = &mut self.core.tempo_synced_turbo {
+            turbo.process(
+                source_control_event.payload().to_unit_value(),
+                source_control_event.timestamp(),
+            );
+        }
         let result = self.control_internal(
             options,
             context,
@@ -1147,12 +1368,21 @@ impl MainMapping {
         } else {
             true
         };
+        let feedback_brightness = control_context
+            .instance_state
+            .borrow()
+            .feedback_brightness();
         self.feedback_given_target_value(
             Cow::Owned(feedback_value),
             FeedbackDestinations {
                 with_projection_feedback,
                 with_source_feedback: with_source_feedback && source_feedback_is_okay,
+                only_if_visible_in_projection: self
+                    .core
+                    .options
+                    .feedback_only_if_visible_in_projection,
             },
+            feedback_brightness,
             control_context.source_context,
         )
     }
@@ -1169,6 +1399,20 @@ impl MainMapping {
         &self.core.mode
     }
 
+    /// Returns the issues of the control and feedback transformation which haven't been taken yet.
+    pub fn take_eel_transformation_issues(
+        &self,
+    ) -> impl Iterator<Item = EelTransformationIssue> + '_ {
+        let settings = self.core.mode.settings();
+        [
+            &settings.control_transformation,
+            &settings.feedback_transformation,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|t| t.take_issues())
+    }
+
     pub fn group_id(&self) -> GroupId {
         self.core.group_id
     }
@@ -1178,14 +1422,19 @@ impl MainMapping {
     /// can give us ownership of the feedback value. It's also better than taking an owned value
     /// because it's possible that we don't produce a feedback value at all! In which a consumer
     /// that can't give up ownership would need to make a clone in advance - for nothing!
+    ///
+    /// The feedback brightness is only applied to source feedback of mappings with real sources.
+    /// Feedback of mappings with virtual sources gets dimmed later by the controller mapping.
     pub fn feedback_given_target_value(
         &self,
         feedback_value: Cow<FeedbackValue>,
         destinations: FeedbackDestinations,
+        feedback_brightness: UnitValue,
         source_context: &SourceContext,
     ) -> Option<SpecificCompoundFeedbackValue> {
+        let source_is_virtual = self.core.source.is_virtual();
         let options = ModeFeedbackOptions {
-            source_is_virtual: self.core.source.is_virtual(),
+            source_is_virtual,
             max_discrete_source_value: self.core.source.max_discrete_value(),
         };
         let mode_value = self.core.mode.feedback_with_options_detail(
@@ -1193,25 +1442,94 @@ impl MainMapping {
             options,
             Default::default(),
         )?;
-        self.feedback_given_mode_value(mode_value, destinations, source_context)
+        let mode_value = if self.core.options.invert_feedback {
+            invert_feedback(mode_value)
+        } else {
+            mode_value
+        };
+        self.feedback_given_mode_value(
+            mode_value,
+            destinations,
+            feedback_brightness,
+            source_context,
+        )
     }
 
     fn feedback_given_mode_value(
         &self,
         mode_value: Cow<FeedbackValue>,
         destinations: FeedbackDestinations,
+        feedback_brightness: UnitValue,
         source_context: &SourceContext,
     ) -> Option<SpecificCompoundFeedbackValue> {
         SpecificCompoundFeedbackValue::from_mode_value(
             self.core.compartment,
             self.key.clone(),
             &self.core.source,
+            &self.core.note_feedback_options,
             mode_value,
             destinations,
+            feedback_brightness,
             source_context,
         )
     }
 
+    /// Returns feedback which sends the given value to the source as is, without consulting the
+    /// mode or the target.
+    ///
+    /// Used for testing the feedback behavior of a controller.
+    pub fn test_feedback(
+        &self,
+        value: UnitValue,
+        source_context: &SourceContext,
+    ) -> Option<CompoundFeedbackValue> {
+        let feedback_value = FeedbackValue::Numeric(NumericFeedbackValue::new(
+            Default::default(),
+            AbsoluteValue::Continuous(value),
+        ));
+        self.feedback_given_mode_value(
+            Cow::Owned(feedback_value),
+            FeedbackDestinations {
+                with_projection_feedback: false,
+                with_source_feedback: true,
+                only_if_visible_in_projection: false,
+            },
+            UnitValue::MAX,
+            source_context,
+        )
+        .map(CompoundFeedbackValue::normal)
+    }
+
+    /// Returns feedback reflecting the given output of this mapping's feedback effect.
+    pub fn feedback_effect_value(
+        &self,
+        output: FeedbackEffectOutput,
+        context: ControlContext,
+    ) -> Option<CompoundFeedbackValue> {
+        let feedback_value = match output {
+            FeedbackEffectOutput::Normal => return self.feedback(true, context),
+            FeedbackEffectOutput::On => FeedbackValue::Numeric(NumericFeedbackValue::new(
+                Default::default(),
+                AbsoluteValue::Continuous(UnitValue::MAX),
+            )),
+            FeedbackEffectOutput::Off => FeedbackValue::Off,
+        };
+        self.feedback_given_mode_value(
+            Cow::Owned(feedback_value),
+            FeedbackDestinations {
+                with_projection_feedback: false,
+                with_source_feedback: true,
+                only_if_visible_in_projection: self
+                    .core
+                    .options
+                    .feedback_only_if_visible_in_projection,
+            },
+            context.instance_state.borrow().feedback_brightness(),
+            context.source_context,
+        )
+        .map(CompoundFeedbackValue::normal)
+    }
+
     /// This returns a "lights off" feedback.
     ///
     /// Used when mappings get inactive.
@@ -1223,7 +1541,9 @@ impl MainMapping {
             FeedbackDestinations {
                 with_projection_feedback: true,
                 with_source_feedback: true,
+                only_if_visible_in_projection: false,
             },
+            UnitValue::MAX,
             source_context,
         )
         .map(CompoundFeedbackValue::normal)
@@ -1280,9 +1600,9 @@ impl MainMapping {
     }
 
     /// Polls the source.
-    pub fn poll_source(&mut self) -> Option<ControlValue> {
+    pub fn poll_source(&mut self, tempo: Bpm) -> Option<ControlValue> {
         match &mut self.core.source {
-            CompoundMappingSource::Reaper(s) => s.poll(),
+            CompoundMappingSource::Reaper(s) => s.poll(tempo),
             _ => None,
         }
     }
@@ -1483,11 +1803,7 @@ impl RealTimeMapping {
         if !self.target_is_resolved {
             return None;
         }
-        let control_value = if let CompoundMappingSource::Midi(s) = &self.core.source {
-            s.control(evt.payload())?
-        } else {
-            return None;
-        };
+        let control_value = self.core.source.control_midi(evt.payload())?;
         if let Some(RealTimeCompoundMappingTarget::Virtual(t)) = self.resolved_target.as_ref() {
             match_partially(&mut self.core, t, evt.with_payload(control_value))
                 .map(PartialControlMatch::ProcessVirtual)
@@ -1508,8 +1824,14 @@ pub struct MappingCore {
     id: MappingId,
     group_id: GroupId,
     pub source: CompoundMappingSource,
+    note_feedback_options: NoteFeedbackOptions,
+    debounce_filter: SourceDebounceFilter<ControlEventTimestamp>,
+    pitch_bend_filter: Option<PitchBendFilter>,
     pub mode: Mode,
     group_interaction: GroupInteraction,
+    acceleration_curve: AccelerationCurve,
+    /// Repeats button presses in a tempo-synced rate (fire mode "Fire after timeout, keep firing").
+    tempo_synced_turbo: Option<TempoSyncedTurbo<ControlEventTimestamp>>,
     options: ProcessorMappingOptions,
     /// Used for preventing echo feedback.
     time_of_last_control: Option<Instant>,
@@ -1530,6 +1852,43 @@ impl MappingCore {
         self.invocation_count = self.invocation_count.wrapping_add(1);
     }
 
+    /// Makes pitch wheels usable as bipolar control.
+    ///
+    /// Swallows the event if it doesn't lead to any change (e.g. wheel returning to center in
+    /// relative mode).
+    pub fn process_pitch_bend(
+        &mut self,
+        evt: ControlEvent<ControlValue>,
+    ) -> Option<ControlEvent<ControlValue>> {
+        let filter = match &mut self.pitch_bend_filter {
+            None => return Some(evt),
+            Some(f) => f,
+        };
+        let value = filter.process(evt.payload())?;
+        Some(evt.map_payload(|_| value))
+    }
+
+    /// Swallows the event if it's just value flutter.
+    pub fn debounce(
+        &mut self,
+        evt: ControlEvent<ControlValue>,
+    ) -> Option<ControlEvent<ControlValue>> {
+        if self.debounce_filter.accepts(evt.payload(), evt.timestamp()) {
+            Some(evt)
+        } else {
+            None
+        }
+    }
+
+    /// Applies the acceleration curve to encoder increments if they are going to be converted to
+    /// absolute values.
+    pub fn accelerate(&self, evt: ControlEvent<ControlValue>) -> ControlEvent<ControlValue> {
+        if !self.mode.settings().make_absolute {
+            return evt;
+        }
+        evt.map_payload(|v| accelerate_control_value(v, self.acceleration_curve))
+    }
+
     fn is_echo(&self) -> bool {
         if let Some(t) = self.time_of_last_control {
             t.elapsed() <= MAX_ECHO_FEEDBACK_DELAY
@@ -1557,10 +1916,12 @@ impl MappingCore {
 pub enum CompoundMappingSource {
     Never,
     Midi(MidiSource),
+    MidiMachineControl(MidiMachineControlSource),
     Osc(OscSource),
     Virtual(VirtualSource),
     Reaper(ReaperSource),
     Key(KeySource),
+    Audio(AudioSource),
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -1585,6 +1946,7 @@ pub struct QualifiedSource {
     pub compartment: Compartment,
     pub mapping_key: Rc<str>,
     pub source: CompoundMappingSource,
+    pub note_feedback_options: NoteFeedbackOptions,
 }
 
 impl QualifiedSource {
@@ -1593,11 +1955,14 @@ impl QualifiedSource {
             self.compartment,
             self.mapping_key,
             &self.source,
+            &self.note_feedback_options,
             Cow::Owned(FeedbackValue::Off),
             FeedbackDestinations {
                 with_projection_feedback: true,
                 with_source_feedback: true,
+                only_if_visible_in_projection: false,
             },
+            UnitValue::MAX,
             source_context,
         )
         .map(CompoundFeedbackValue::normal)
@@ -1614,6 +1979,7 @@ impl CompoundMappingSource {
         use CompoundMappingSource::*;
         match self {
             Reaper(s) => s.on_deactivate(),
+            Audio(s) => s.on_deactivate(),
             _ => {}
         }
     }
@@ -1696,6 +2062,9 @@ impl CompoundMappingSource {
         use CompoundMappingSource::*;
         match (self, value) {
             (Midi(s), IncomingCompoundSourceValue::Midi(v)) => s.control_flexible(v),
+            (MidiMachineControl(s), IncomingCompoundSourceValue::Midi(v)) => {
+                s.control(v).map(ControlResult::Processed)
+            }
             (Osc(s), IncomingCompoundSourceValue::Osc(m)) => {
                 s.control(m).map(ControlResult::Processed)
             }
@@ -1743,7 +2112,9 @@ impl CompoundMappingSource {
             Virtual(s) => s.format_control_value(value),
             Osc(s) => s.format_control_value(value),
             Reaper(s) => s.format_control_value(value),
-            Never | Key(_) => Ok(format_percentage_without_unit(value.to_unit_value()?.get())),
+            Never | MidiMachineControl(_) | Key(_) | Audio(_) => {
+                Ok(format_percentage_without_unit(value.to_unit_value()?.get()))
+            }
         }
     }
 
@@ -1754,7 +2125,9 @@ impl CompoundMappingSource {
             Virtual(s) => s.parse_control_value(text),
             Osc(s) => s.parse_control_value(text),
             Reaper(s) => s.parse_control_value(text),
-            Never | Key(_) => parse_percentage_without_unit(text)?.try_into(),
+            Never | MidiMachineControl(_) | Key(_) | Audio(_) => {
+                parse_percentage_without_unit(text)?.try_into()
+            }
         }
     }
 
@@ -1766,7 +2139,22 @@ impl CompoundMappingSource {
             Osc(s) => ExtendedSourceCharacter::Normal(s.character()),
             Reaper(s) => ExtendedSourceCharacter::Normal(s.character()),
             Never => ExtendedSourceCharacter::VirtualContinuous,
-            Key(_) => ExtendedSourceCharacter::Normal(SourceCharacter::MomentaryButton),
+            MidiMachineControl(_) | Key(_) => {
+                ExtendedSourceCharacter::Normal(SourceCharacter::MomentaryButton)
+            }
+            Audio(_) => ExtendedSourceCharacter::Normal(SourceCharacter::RangeElement),
+        }
+    }
+
+    /// Returns whether this source represents a knob, encoder or fader (typically with LED ring
+    /// or motor), as opposed to a button.
+    pub fn is_continuous_element(&self) -> bool {
+        use SourceCharacter::*;
+        match self.character() {
+            ExtendedSourceCharacter::Normal(c) => {
+                matches!(c, RangeElement | Encoder1 | Encoder2 | Encoder3)
+            }
+            ExtendedSourceCharacter::VirtualContinuous => true,
         }
     }
 
@@ -1789,7 +2177,7 @@ impl CompoundMappingSource {
             // This is handled in a special way by consumers.
             Virtual(_) => None,
             // No feedback for other sources.
-            Key(_) | Never => None,
+            MidiMachineControl(_) | Key(_) | Audio(_) | Never => None,
         }
     }
 
@@ -1797,7 +2185,9 @@ impl CompoundMappingSource {
         use CompoundMappingSource::*;
         match self {
             Midi(s) => s.consumes(msg),
-            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) => false,
+            MidiMachineControl(_) | Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | Audio(_) => {
+                false
+            }
         }
     }
 
@@ -1805,13 +2195,25 @@ impl CompoundMappingSource {
         matches!(self, CompoundMappingSource::Virtual(_))
     }
 
+    /// Processes the given incoming MIDI message if this is a source reacting to MIDI.
+    pub fn control_midi(&self, value: &MidiSourceValue<RawShortMessage>) -> Option<ControlValue> {
+        use CompoundMappingSource::*;
+        match self {
+            Midi(s) => s.control(value),
+            MidiMachineControl(s) => s.control(value),
+            _ => None,
+        }
+    }
+
     pub fn max_discrete_value(&self) -> Option<u32> {
         use CompoundMappingSource::*;
         match self {
             Midi(s) => s.max_discrete_value(),
             // TODO-medium OSC will also support discrete values as soon as we allow integers and
             //  configuring max values
-            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) => None,
+            MidiMachineControl(_) | Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | Audio(_) => {
+                None
+            }
         }
     }
 }
@@ -1853,6 +2255,9 @@ pub struct FeedbackDestinations {
     pub with_projection_feedback: bool,
     /// Feedback to controller itself.
     pub with_source_feedback: bool,
+    /// If `true`, virtual feedback is only passed on by controller mappings which are shown in
+    /// the projection.
+    pub only_if_visible_in_projection: bool,
 }
 
 impl FeedbackDestinations {
@@ -1866,8 +2271,10 @@ impl SpecificCompoundFeedbackValue {
         compartment: Compartment,
         mapping_key: Rc<str>,
         source: &CompoundMappingSource,
+        note_feedback_options: &NoteFeedbackOptions,
         mode_value: Cow<FeedbackValue>,
         destinations: FeedbackDestinations,
+        feedback_brightness: UnitValue,
         source_context: &SourceContext,
     ) -> Option<SpecificCompoundFeedbackValue> {
         if destinations.is_all_off() {
@@ -1892,7 +2299,17 @@ impl SpecificCompoundFeedbackValue {
                 None
             };
             let source = if destinations.with_source_feedback {
-                source.feedback(mode_value, source_context)
+                // Dim only what is sent to the controller, not what is shown in the projection.
+                let mode_value = apply_feedback_brightness(mode_value, feedback_brightness);
+                source
+                    .feedback(mode_value, source_context)
+                    .map(|v| match v {
+                        PreliminarySourceFeedbackValue::Midi(mut v) => {
+                            v.final_value = note_feedback_options.process(v.final_value);
+                            PreliminarySourceFeedbackValue::Midi(v)
+                        }
+                        v => v,
+                    })
             } else {
                 None
             };
@@ -2004,6 +2421,18 @@ impl UnresolvedCompoundMappingTarget {
         Ok(resolved_targets)
     }
 
+    pub fn missing_object(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> Option<MissingTargetObject> {
+        use UnresolvedCompoundMappingTarget::*;
+        match self {
+            Reaper(t) => t.missing_object(context, compartment),
+            Virtual(_) => None,
+        }
+    }
+
     pub fn conditions_are_met(&self, targets: &[CompoundMappingTarget]) -> bool {
         use UnresolvedCompoundMappingTarget::*;
         targets.iter().all(|target| match (self, target) {
@@ -2438,6 +2867,14 @@ impl Compartment {
         Compartment::into_enum_iter()
     }
 
+    /// Returns the respective other compartment.
+    pub fn other(self) -> Compartment {
+        match self {
+            Compartment::Controller => Compartment::Main,
+            Compartment::Main => Compartment::Controller,
+        }
+    }
+
     /// Returns the compartment to which the given plug-in parameter index belongs.
     pub fn by_plugin_param_index(plugin_param_index: PluginParamIndex) -> Compartment {
         Self::enum_iter()
@@ -2495,6 +2932,9 @@ fn match_partially(
     // TODO-medium If we want to support fire after timeout and turbo for mappings with
     //  virtual targets one day, we need to poll this in real-time processor and OSC
     //  processing, too!
+    let control_event = core.process_pitch_bend(control_event)?;
+    let control_event = core.debounce(control_event)?;
+    let control_event = core.accelerate(control_event);
     let res = core.mode.control_with_options(
         control_event,
         target,
@@ -2529,6 +2969,34 @@ pub fn aggregate_target_values(
     values.flatten().max()
 }
 
+/// Scales numeric feedback values by the given instance-wide feedback brightness.
+///
+/// Textual feedback and "off" feedback are passed through unchanged.
+fn apply_feedback_brightness(
+    feedback_value: Cow<FeedbackValue>,
+    brightness: UnitValue,
+) -> Cow<FeedbackValue> {
+    if brightness == UnitValue::MAX {
+        return feedback_value;
+    }
+    if let FeedbackValue::Numeric(v) = feedback_value.as_ref() {
+        let mut v = v.clone();
+        let dimmed_value = v.value.to_unit_value().get() * brightness.get();
+        v.value = AbsoluteValue::Continuous(UnitValue::new_clamped(dimmed_value));
+        return Cow::Owned(FeedbackValue::Numeric(v));
+    }
+    feedback_value
+}
+
+fn invert_feedback(feedback_value: Cow<FeedbackValue>) -> Cow<FeedbackValue> {
+    if let FeedbackValue::Numeric(v) = feedback_value.as_ref() {
+        let mut v = v.clone();
+        v.value = v.value.inverse(None);
+        return Cow::Owned(FeedbackValue::Numeric(v));
+    }
+    feedback_value
+}
+
 #[derive(Default)]
 pub struct MappingControlResult {
     /// `true` if target hit or almost hit but left untouched because it already has desired value.
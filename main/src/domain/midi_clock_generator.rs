@@ -0,0 +1,121 @@
+use crate::domain::{MidiEvent, SampleOffset};
+use helgoboss_midi::{RawShortMessage, ShortMessageFactory};
+use reaper_medium::{Bpm, Hz};
+
+/// MIDI clock has a resolution of 24 pulses per quarter note.
+const PULSES_PER_QUARTER_NOTE: f64 = 24.0;
+
+/// Generates MIDI timing clock messages (and start/continue/stop messages) in a sample-accurate
+/// way.
+///
+/// Supposed to be fed with one audio block after the other.
+#[derive(Debug, Default)]
+pub struct MidiClockGenerator {
+    /// Remaining samples until the next clock pulse must be sent.
+    samples_until_next_pulse: f64,
+    transport_state: MidiClockTransportState,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MidiClockTransportState {
+    Stopped,
+    Paused,
+    Playing,
+}
+
+impl Default for MidiClockTransportState {
+    fn default() -> Self {
+        Self::Stopped
+    }
+}
+
+impl MidiClockGenerator {
+    /// Resets the generator so that the next block starts with a clock pulse and a possibly
+    /// necessary start message.
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    /// Generates all messages which need to be sent within the given audio block.
+    pub fn process_block(
+        &mut self,
+        block_length: usize,
+        sample_rate: Hz,
+        tempo: Bpm,
+        transport_state: MidiClockTransportState,
+        mut send: impl FnMut(MidiEvent<RawShortMessage>),
+    ) {
+        use MidiClockTransportState::*;
+        // Transport messages
+        let transport_msg = match (self.transport_state, transport_state) {
+            (Stopped, Playing) => Some(RawShortMessage::start()),
+            (Paused, Playing) => Some(RawShortMessage::r#continue()),
+            (Playing, Stopped | Paused) => Some(RawShortMessage::stop()),
+            _ => None,
+        };
+        self.transport_state = transport_state;
+        if let Some(msg) = transport_msg {
+            send(MidiEvent::without_offset(msg));
+        }
+        // Clock pulses
+        let samples_per_pulse = sample_rate.get() * 60.0 / (tempo.get() * PULSES_PER_QUARTER_NOTE);
+        let block_length = block_length as f64;
+        while self.samples_until_next_pulse < block_length {
+            let offset = SampleOffset::new(self.samples_until_next_pulse.max(0.0).round() as u64);
+            send(MidiEvent::new(offset, RawShortMessage::timing_clock()));
+            self.samples_until_next_pulse += samples_per_pulse;
+        }
+        self.samples_until_next_pulse -= block_length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::{ShortMessage, ShortMessageType};
+
+    fn collect_block(
+        generator: &mut MidiClockGenerator,
+        block_length: usize,
+        transport_state: MidiClockTransportState,
+    ) -> Vec<MidiEvent<RawShortMessage>> {
+        let mut events = vec![];
+        generator.process_block(
+            block_length,
+            Hz::new(48000.0),
+            Bpm::new(120.0),
+            transport_state,
+            |e| events.push(e),
+        );
+        events
+    }
+
+    #[test]
+    fn pulse_interval() {
+        // Given
+        let mut generator = MidiClockGenerator::default();
+        // When
+        // At 120 bpm and 48 kHz, one pulse lasts 1000 samples.
+        let events = collect_block(&mut generator, 2500, MidiClockTransportState::Stopped);
+        // Then
+        let offsets: Vec<_> = events.iter().map(|e| e.offset().get()).collect();
+        assert_eq!(offsets, vec![0, 1000, 2000]);
+        let events = collect_block(&mut generator, 1000, MidiClockTransportState::Stopped);
+        let offsets: Vec<_> = events.iter().map(|e| e.offset().get()).collect();
+        assert_eq!(offsets, vec![500]);
+    }
+
+    #[test]
+    fn transport_messages() {
+        // Given
+        let mut generator = MidiClockGenerator::default();
+        // When
+        let started = collect_block(&mut generator, 10, MidiClockTransportState::Playing);
+        let paused = collect_block(&mut generator, 10, MidiClockTransportState::Paused);
+        let continued = collect_block(&mut generator, 10, MidiClockTransportState::Playing);
+        // Then
+        assert_eq!(started[0].payload().r#type(), ShortMessageType::Start);
+        assert_eq!(paused[0].payload().r#type(), ShortMessageType::Stop);
+        assert_eq!(continued[0].payload().r#type(), ShortMessageType::Continue);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::domain::{AudioBlockProps, IncomingMidiMessage};
+use helgoboss_midi::{RawShortMessage, ShortMessage, ShortMessageType};
+use std::{iter, mem};
+
+/// Maximum number of incoming MIDI messages which are processed immediately within one audio
+/// block. That's plenty for normal controller usage, even for fast 14-bit sweeps.
+const MAX_MESSAGES_PER_BLOCK: u32 = 256;
+
+/// Number of distinct short messages (or 14-bit control change units) which can be postponed to
+/// the next block once the limit is exceeded.
+const POSTPONED_MESSAGE_CAPACITY: usize = 64;
+
+/// Messages which have been postponed to the next block.
+#[derive(Debug)]
+pub struct PostponedMidiMessages {
+    entries: [Option<PostponedEntry>; POSTPONED_MESSAGE_CAPACITY],
+}
+
+impl PostponedMidiMessages {
+    const EMPTY: Self = Self {
+        entries: [None; POSTPONED_MESSAGE_CAPACITY],
+    };
+
+    /// Returns the messages in the order in which they should be processed.
+    pub fn messages(&self) -> impl Iterator<Item = RawShortMessage> + '_ {
+        self.entries.iter().flatten().flat_map(|e| e.messages())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct PostponedEntry {
+    /// `None` if the message must not be coalesced with newer ones.
+    key: Option<CoalescingKey>,
+    msg: RawShortMessage,
+    /// Set if this is the MSB of a 14-bit control change and the LSB has followed.
+    lsb: Option<RawShortMessage>,
+}
+
+impl PostponedEntry {
+    fn new(key: Option<CoalescingKey>, msg: RawShortMessage) -> Self {
+        Self {
+            key,
+            msg,
+            lsb: None,
+        }
+    }
+
+    fn messages(&self) -> impl Iterator<Item = RawShortMessage> {
+        iter::once(self.msg).chain(self.lsb)
+    }
+
+    fn message_count(&self) -> u32 {
+        1 + self.lsb.is_some() as u32
+    }
+}
+
+/// Protects real-time processing from controllers which flood the input (e.g. stuck sys-ex or
+/// extremely dense 14-bit sweeps).
+///
+/// As long as the number of messages per audio block stays within the limit, all messages are
+/// processed immediately. Beyond that, short messages are postponed to the next block. For
+/// continuous control elements (e.g. faders), only the newest message is kept because the newest
+/// absolute value is what matters. The MSB and LSB of a 14-bit control change are treated as a
+/// unit. Everything else is kept as is and in order, e.g. note on and note off (so button presses
+/// don't get lost) and (N)RPN messages (whose data entry bytes belong to the most recently selected
+/// parameter). Messages which don't fit anymore and sys-ex messages beyond the limit are dropped. Drops are collected so that they can
+/// be reported to the main thread from time to time (at most once per second).
+///
+/// Each MIDI input path (FX input, hardware input) needs its own limiter because the paths are
+/// processed in different cycles.
+///
+/// Doesn't allocate, so it's safe to use in the real-time thread.
+#[derive(Debug)]
+pub struct MidiInputLimiter {
+    message_count_in_current_block: u32,
+    postponed_messages: PostponedMidiMessages,
+    /// Index of the most recently postponed entry. An LSB can only complete this one.
+    last_postponed_index: Option<usize>,
+    pending_drops: MidiInputDrops,
+    samples_since_last_report: u64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct MidiInputDrops {
+    /// Number of incoming messages which have not been processed at all.
+    pub count: u32,
+    /// Highest number of incoming messages within one audio block.
+    pub max_messages_per_block: u32,
+}
+
+impl MidiInputDrops {
+    /// Adds the given drops to these ones.
+    pub fn accumulate(&mut self, other: MidiInputDrops) {
+        self.count += other.count;
+        self.max_messages_per_block = self
+            .max_messages_per_block
+            .max(other.max_messages_per_block);
+    }
+}
+
+impl Default for MidiInputLimiter {
+    fn default() -> Self {
+        Self {
+            message_count_in_current_block: 0,
+            postponed_messages: PostponedMidiMessages::EMPTY,
+            last_postponed_index: None,
+            pending_drops: Default::default(),
+            samples_since_last_report: 0,
+        }
+    }
+}
+
+impl MidiInputLimiter {
+    /// Should be called for each incoming MIDI message.
+    ///
+    /// Returns whether the message should be processed right now.
+    pub fn admit(&mut self, msg: &IncomingMidiMessage) -> bool {
+        self.message_count_in_current_block += 1;
+        if self.message_count_in_current_block <= MAX_MESSAGES_PER_BLOCK {
+            return true;
+        }
+        match msg {
+            IncomingMidiMessage::Short(msg) => self.postpone(*msg),
+            IncomingMidiMessage::SysEx(_) => self.pending_drops.count += 1,
+        }
+        false
+    }
+
+    /// Should be called exactly once at the beginning of each audio block, before any message of
+    /// that block is admitted.
+    ///
+    /// Returns the messages which have been postponed in the previous block and should be
+    /// processed now, in the order in which they have been postponed.
+    pub fn start_block(&mut self) -> PostponedMidiMessages {
+        if self.message_count_in_current_block > MAX_MESSAGES_PER_BLOCK {
+            self.pending_drops.max_messages_per_block = self
+                .pending_drops
+                .max_messages_per_block
+                .max(self.message_count_in_current_block);
+        }
+        self.message_count_in_current_block = 0;
+        self.last_postponed_index = None;
+        mem::replace(&mut self.postponed_messages, PostponedMidiMessages::EMPTY)
+    }
+
+    /// Should be called exactly once per audio block.
+    ///
+    /// Returns the drops which should be reported to the main thread now, if any.
+    pub fn poll_drops(&mut self, block_props: AudioBlockProps) -> Option<MidiInputDrops> {
+        self.samples_since_last_report += block_props.block_length as u64;
+        if self.pending_drops.count == 0
+            || (self.samples_since_last_report as f64) < block_props.frame_rate.get()
+        {
+            return None;
+        }
+        self.samples_since_last_report = 0;
+        Some(mem::take(&mut self.pending_drops))
+    }
+
+    fn postpone(&mut self, msg: RawShortMessage) {
+        let key = coalescing_key(&msg);
+        let entries = &mut self.postponed_messages.entries;
+        // An LSB which directly follows the MSB of the same controller completes the 14-bit unit.
+        if let Some(CoalescingKey::ControlChange14BitLsb { status, msb_number }) = key {
+            let last_entry = self
+                .last_postponed_index
+                .and_then(|i| entries[i].as_mut())
+                .filter(|e| {
+                    e.lsb.is_none()
+                        && e.key
+                            == Some(CoalescingKey::ControlChange14BitMsb { status, msb_number })
+                });
+            if let Some(e) = last_entry {
+                e.lsb = Some(msg);
+                return;
+            }
+        }
+        let new_entry = PostponedEntry::new(key, msg);
+        let existing_index = key.and_then(|key| {
+            entries
+                .iter()
+                .position(|e| e.map_or(false, |e| e.key == Some(key)))
+        });
+        let index = if let Some(i) = existing_index {
+            // The older value is superseded by the newer one.
+            let superseded_entry = entries[i].replace(new_entry);
+            self.pending_drops.count += superseded_entry.map_or(0, |e| e.message_count());
+            i
+        } else if let Some(i) = entries.iter().position(|e| e.is_none()) {
+            entries[i] = Some(new_entry);
+            i
+        } else {
+            self.pending_drops.count += 1;
+            self.last_postponed_index = None;
+            return;
+        };
+        self.last_postponed_index = Some(index);
+    }
+}
+
+/// Postponed messages with the same key address the same control element, so only the newest one
+/// counts.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum CoalescingKey {
+    Simple {
+        status: u8,
+        data_1: u8,
+    },
+    ControlChange14BitMsb {
+        status: u8,
+        msb_number: u8,
+    },
+    /// An LSB which doesn't directly follow its MSB.
+    ControlChange14BitLsb {
+        status: u8,
+        msb_number: u8,
+    },
+}
+
+/// Returns `None` for messages which must not be coalesced.
+fn coalescing_key(msg: &RawShortMessage) -> Option<CoalescingKey> {
+    use CoalescingKey::*;
+    use ShortMessageType::*;
+    let status = msg.status_byte();
+    let data_1 = msg.data_byte_1().get();
+    let key = match msg.r#type() {
+        ControlChange => match data_1 {
+            // Data entry, increment/decrement and parameter number selection. The data entry
+            // bytes refer to the most recently selected parameter, so the order must be kept.
+            6 | 38 | 96..=101 => return None,
+            0..=31 => ControlChange14BitMsb {
+                status,
+                msb_number: data_1,
+            },
+            32..=63 => ControlChange14BitLsb {
+                status,
+                msb_number: data_1 - 32,
+            },
+            _ => Simple { status, data_1 },
+        },
+        PolyphonicKeyPressure => Simple { status, data_1 },
+        PitchBendChange | ChannelPressure => Simple { status, data_1: 0 },
+        // Notes (each press and release counts), program changes and system messages
+        _ => return None,
+    };
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::{control_change, note_off, note_on};
+    use reaper_medium::Hz;
+
+    fn block_props() -> AudioBlockProps {
+        // 1000 samples at 48 kHz last about 20.8 ms
+        AudioBlockProps {
+            block_length: 1000,
+            frame_rate: Hz::new(48000.0),
+        }
+    }
+
+    fn postponed_messages(postponed: PostponedMidiMessages) -> Vec<RawShortMessage> {
+        postponed.messages().collect()
+    }
+
+    fn admit_block(limiter: &mut MidiInputLimiter, msgs: &[RawShortMessage]) -> usize {
+        msgs.iter()
+            .filter(|msg| limiter.admit(&IncomingMidiMessage::Short(**msg)))
+            .count()
+    }
+
+    fn full_block() -> Vec<RawShortMessage> {
+        (0..MAX_MESSAGES_PER_BLOCK)
+            .map(|i| control_change(0, 70, (i % 128) as u8))
+            .collect()
+    }
+
+    #[test]
+    fn flood_keeps_newest_values_and_all_notes() {
+        // Given
+        let mut limiter = MidiInputLimiter::default();
+        // When
+        limiter.start_block();
+        let admitted = (0..MAX_MESSAGES_PER_BLOCK)
+            .filter(|i| {
+                let msg = control_change(0, 1, (i % 128) as u8);
+                limiter.admit(&IncomingMidiMessage::Short(msg))
+            })
+            .count();
+        let overflow = [
+            control_change(0, 70, 10),
+            note_on(0, 60, 100),
+            control_change(0, 70, 20),
+            note_off(0, 60, 0),
+        ];
+        let admitted_overflow = overflow
+            .iter()
+            .filter(|msg| limiter.admit(&IncomingMidiMessage::Short(**msg)))
+            .count();
+        let sys_ex_admitted = limiter.admit(&IncomingMidiMessage::SysEx(&[0xf0, 0xf7]));
+        let postponed = limiter.start_block();
+        let reports: Vec<_> = (0..48)
+            .filter_map(|_| {
+                limiter.start_block();
+                limiter.poll_drops(block_props())
+            })
+            .collect();
+        // Then
+        assert_eq!(admitted, MAX_MESSAGES_PER_BLOCK as usize);
+        assert_eq!(admitted_overflow, 0);
+        assert!(!sys_ex_admitted);
+        assert_eq!(
+            postponed_messages(postponed),
+            vec![
+                control_change(0, 70, 20),
+                note_on(0, 60, 100),
+                note_off(0, 60, 0)
+            ]
+        );
+        assert_eq!(
+            reports,
+            vec![MidiInputDrops {
+                count: 2,
+                max_messages_per_block: MAX_MESSAGES_PER_BLOCK + 5
+            }]
+        );
+    }
+
+    #[test]
+    fn enforce_limit_per_block_and_replay_in_order() {
+        // Given
+        let mut limiter = MidiInputLimiter::default();
+        let full_block = full_block();
+        // When
+        let postponed_1 = limiter.start_block();
+        let admitted_1 = admit_block(&mut limiter, &full_block);
+        let admitted_overflow = admit_block(
+            &mut limiter,
+            &[
+                control_change(0, 72, 1),
+                control_change(0, 73, 1),
+                control_change(0, 72, 5),
+            ],
+        );
+        let postponed_2 = limiter.start_block();
+        let admitted_2 = admit_block(&mut limiter, &full_block);
+        let postponed_3 = limiter.start_block();
+        // Then
+        assert!(postponed_messages(postponed_1).is_empty());
+        assert_eq!(admitted_1, MAX_MESSAGES_PER_BLOCK as usize);
+        assert_eq!(admitted_overflow, 0);
+        assert_eq!(
+            postponed_messages(postponed_2),
+            vec![control_change(0, 72, 5), control_change(0, 73, 1)]
+        );
+        assert_eq!(admitted_2, MAX_MESSAGES_PER_BLOCK as usize);
+        assert!(postponed_messages(postponed_3).is_empty());
+    }
+
+    #[test]
+    fn coalesce_14_bit_units_but_keep_parameter_numbers() {
+        // Given
+        let mut limiter = MidiInputLimiter::default();
+        let overflow = [
+            // 14-bit control change (MSB and LSB)
+            control_change(0, 1, 1),
+            control_change(0, 33, 10),
+            // NRPN 1 and 2
+            control_change(0, 99, 0),
+            control_change(0, 98, 1),
+            control_change(0, 6, 5),
+            control_change(0, 99, 0),
+            control_change(0, 98, 2),
+            control_change(0, 6, 7),
+            // Newer value of the same 14-bit control change
+            control_change(0, 1, 2),
+            control_change(0, 33, 20),
+        ];
+        // When
+        limiter.start_block();
+        admit_block(&mut limiter, &full_block());
+        let admitted_overflow = admit_block(&mut limiter, &overflow);
+        let postponed = limiter.start_block();
+        // Then
+        assert_eq!(admitted_overflow, 0);
+        assert_eq!(
+            postponed_messages(postponed),
+            vec![
+                control_change(0, 1, 2),
+                control_change(0, 33, 20),
+                control_change(0, 99, 0),
+                control_change(0, 98, 1),
+                control_change(0, 6, 5),
+                control_change(0, 99, 0),
+                control_change(0, 98, 2),
+                control_change(0, 6, 7),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::{ControlValue, MidiSourceValue, UnitValue};
+use helgoboss_midi::RawShortMessage;
+use realearn_api::persistence::MidiMachineControlCommand;
+
+/// Device ID which addresses all devices ("all-call").
+pub const MMC_ALL_CALL_DEVICE_ID: u8 = 0x7F;
+
+/// Source which reacts to incoming MIDI Machine Control (MMC) command messages.
+///
+/// Not expressed as raw MIDI source because the device ID needs special treatment and locate
+/// messages carry a position.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MidiMachineControlSource {
+    device_id: u8,
+    command: MidiMachineControlCommand,
+}
+
+impl MidiMachineControlSource {
+    pub fn new(device_id: u8, command: MidiMachineControlCommand) -> Self {
+        Self { device_id, command }
+    }
+
+    pub fn device_id(&self) -> u8 {
+        self.device_id
+    }
+
+    pub fn command(&self) -> MidiMachineControlCommand {
+        self.command
+    }
+
+    /// Fires a 100% value if the given message is the command of this source.
+    ///
+    /// Messages sent to all devices are accepted no matter which device ID this source expects.
+    /// If this source expects the all-call device ID, messages sent to any device are accepted.
+    pub fn control(&self, value: &MidiSourceValue<RawShortMessage>) -> Option<ControlValue> {
+        let bytes = match value {
+            MidiSourceValue::BorrowedSysEx(bytes) => *bytes,
+            _ => return None,
+        };
+        let (device_id, command) = parse_midi_machine_control_message(bytes)?;
+        if command != self.command || !self.accepts_device_id(device_id) {
+            return None;
+        }
+        Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+    }
+
+    fn accepts_device_id(&self, device_id: u8) -> bool {
+        self.device_id == MMC_ALL_CALL_DEVICE_ID
+            || device_id == MMC_ALL_CALL_DEVICE_ID
+            || device_id == self.device_id
+    }
+}
+
+/// Parses the given raw MIDI pattern and returns device ID and command if it represents an MMC
+/// command message.
+pub fn parse_midi_machine_control_pattern(
+    pattern: &str,
+) -> Option<(u8, MidiMachineControlCommand)> {
+    let hex: String = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let bytes: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect();
+    parse_midi_machine_control_message(&bytes?)
+}
+
+/// Parses the given sys-ex message and returns device ID and command if it's an MMC command
+/// message.
+///
+/// The position of a locate command is not returned.
+pub fn parse_midi_machine_control_message(bytes: &[u8]) -> Option<(u8, MidiMachineControlCommand)> {
+    match bytes {
+        // Locate with standard time code target ("TARGET" sub command)
+        [0xF0, 0x7F, device_id, 0x06, 0x44, 0x06, 0x01, _, _, _, _, _, 0xF7] => {
+            Some((*device_id, MidiMachineControlCommand::Locate))
+        }
+        [0xF0, 0x7F, device_id, 0x06, code, 0xF7] => {
+            let command = MidiMachineControlCommand::into_enum_iter()
+                .filter(|c| *c != MidiMachineControlCommand::Locate)
+                .find(|c| midi_machine_control_command_code(*c) == *code)?;
+            Some((*device_id, command))
+        }
+        _ => None,
+    }
+}
+
+fn midi_machine_control_command_code(command: MidiMachineControlCommand) -> u8 {
+    use MidiMachineControlCommand::*;
+    match command {
+        Stop => 0x01,
+        Play => 0x02,
+        DeferredPlay => 0x03,
+        FastForward => 0x04,
+        Rewind => 0x05,
+        RecordStrobe => 0x06,
+        RecordExit => 0x07,
+        RecordPause => 0x08,
+        Pause => 0x09,
+        Locate => 0x44,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_patterns() {
+        // Given
+        let play = "F0 7F 7F 06 02 F7";
+        let locate = "F0 7F 10 06 44 06 01 21 02 03 04 00 F7";
+        let short_locate = "F0 7F 7F 06 44 F7";
+        let non_mmc = "F0 00 20 6B 7F 42 02 00 10 77 00 F7";
+        // When
+        let results = [play, locate, short_locate, non_mmc].map(parse_midi_machine_control_pattern);
+        // Then
+        assert_eq!(
+            results,
+            [
+                Some((0x7F, MidiMachineControlCommand::Play)),
+                Some((0x10, MidiMachineControlCommand::Locate)),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn accept_all_call_in_every_device_mode() {
+        // Given
+        let specific = MidiMachineControlSource::new(0x10, MidiMachineControlCommand::Play);
+        let all =
+            MidiMachineControlSource::new(MMC_ALL_CALL_DEVICE_ID, MidiMachineControlCommand::Play);
+        let play_to_all: &[u8] = &[0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7];
+        let play_to_specific: &[u8] = &[0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7];
+        let play_to_other: &[u8] = &[0xF0, 0x7F, 0x11, 0x06, 0x02, 0xF7];
+        let stop_to_all: &[u8] = &[0xF0, 0x7F, 0x7F, 0x06, 0x01, 0xF7];
+        let fires = |source: &MidiMachineControlSource, bytes: &[u8]| {
+            source
+                .control(&MidiSourceValue::BorrowedSysEx(bytes))
+                .is_some()
+        };
+        // When
+        let results = [
+            fires(&specific, play_to_all),
+            fires(&specific, play_to_specific),
+            fires(&specific, play_to_other),
+            fires(&specific, stop_to_all),
+            fires(&all, play_to_all),
+            fires(&all, play_to_specific),
+            fires(&all, play_to_other),
+        ];
+        // Then
+        assert_eq!(results, [true, true, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn fire_on_locate_with_any_position() {
+        // Given
+        let source = MidiMachineControlSource::new(0x10, MidiMachineControlCommand::Locate);
+        let locate: &[u8] = &[
+            0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x21, 0x02, 0x03, 0x04, 0x00, 0xF7,
+        ];
+        // When
+        let value = source.control(&MidiSourceValue::BorrowedSysEx(locate));
+        // Then
+        assert_eq!(
+            value,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+    }
+}
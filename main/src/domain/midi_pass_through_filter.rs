@@ -0,0 +1,299 @@
+use crate::domain::IncomingMidiMessage;
+use helgoboss_midi::{Channel, ShortMessage, ShortMessageType};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Maximum number of rules. Keeps the filter `Copy` so it can be part of the basic settings which
+/// are sent to the real-time processor.
+const MAX_RULE_COUNT: usize = 16;
+
+/// Decides per MIDI message whether it's forwarded from FX input to FX output.
+///
+/// Consists of a small list of rules. The first rule that matches a message decides. If no rule
+/// matches, the message passes. Textual representation (rules separated by semicolons):
+///
+/// ```text
+/// pass note; block cc ch 10; block sysex
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MidiPassThroughFilter {
+    rules: [Option<MidiPassThroughRule>; MAX_RULE_COUNT],
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MidiPassThroughRule {
+    pub action: MidiPassThroughAction,
+    /// `None` means all message kinds.
+    pub kind: Option<MidiPassThroughMessageKind>,
+    /// `None` means all channels. Messages without channel never match a rule with channel.
+    pub channel: Option<Channel>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MidiPassThroughAction {
+    Pass,
+    Block,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MidiPassThroughMessageKind {
+    /// Note on and note off.
+    Note,
+    ControlChange,
+    ProgramChange,
+    PitchBend,
+    /// Channel pressure and polyphonic key pressure.
+    Aftertouch,
+    SysEx,
+    /// All other system common and system real-time messages (e.g. MIDI clock).
+    System,
+}
+
+impl Default for MidiPassThroughFilter {
+    fn default() -> Self {
+        Self::PASS_ALL
+    }
+}
+
+impl MidiPassThroughFilter {
+    /// Doesn't contain any rule, so everything passes.
+    pub const PASS_ALL: Self = Self {
+        rules: [None; MAX_RULE_COUNT],
+    };
+
+    pub fn new(rules: &[MidiPassThroughRule]) -> Result<Self, &'static str> {
+        if rules.len() > MAX_RULE_COUNT {
+            return Err("too many pass-through filter rules");
+        }
+        let mut filter = Self::PASS_ALL;
+        for (slot, rule) in filter.rules.iter_mut().zip(rules) {
+            *slot = Some(*rule);
+        }
+        Ok(filter)
+    }
+
+    pub fn is_pass_all(&self) -> bool {
+        self.rules().next().is_none()
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &MidiPassThroughRule> {
+        self.rules.iter().map_while(|r| r.as_ref())
+    }
+
+    /// Returns whether the given message may be forwarded.
+    pub fn allows(&self, msg: IncomingMidiMessage) -> bool {
+        let (kind, channel) = match msg {
+            IncomingMidiMessage::Short(m) => (classify_short_message(&m), m.channel()),
+            IncomingMidiMessage::SysEx(_) => (MidiPassThroughMessageKind::SysEx, None),
+        };
+        self.allows_message_of_kind(kind, channel)
+    }
+
+    /// Returns whether the given short message may be forwarded.
+    pub fn allows_short(&self, msg: &impl ShortMessage) -> bool {
+        self.allows_message_of_kind(classify_short_message(msg), msg.channel())
+    }
+
+    fn allows_message_of_kind(
+        &self,
+        kind: MidiPassThroughMessageKind,
+        channel: Option<Channel>,
+    ) -> bool {
+        match self.rules().find(|r| r.matches(kind, channel)) {
+            None => true,
+            Some(r) => r.action == MidiPassThroughAction::Pass,
+        }
+    }
+}
+
+impl MidiPassThroughRule {
+    fn matches(&self, kind: MidiPassThroughMessageKind, channel: Option<Channel>) -> bool {
+        if let Some(k) = self.kind {
+            if k != kind {
+                return false;
+            }
+        }
+        match self.channel {
+            None => true,
+            Some(ch) => channel == Some(ch),
+        }
+    }
+}
+
+fn classify_short_message(msg: &impl ShortMessage) -> MidiPassThroughMessageKind {
+    use MidiPassThroughMessageKind as K;
+    use ShortMessageType::*;
+    match msg.r#type() {
+        NoteOn | NoteOff => K::Note,
+        ControlChange => K::ControlChange,
+        ProgramChange => K::ProgramChange,
+        PitchBendChange => K::PitchBend,
+        ChannelPressure | PolyphonicKeyPressure => K::Aftertouch,
+        SystemExclusiveStart | SystemExclusiveEnd => K::SysEx,
+        TimeCodeQuarterFrame
+        | SongPositionPointer
+        | SongSelect
+        | SystemCommonUndefined1
+        | SystemCommonUndefined2
+        | TuneRequest
+        | TimingClock
+        | SystemRealTimeUndefined1
+        | Start
+        | Continue
+        | Stop
+        | SystemRealTimeUndefined2
+        | ActiveSensing
+        | SystemReset => K::System,
+    }
+}
+
+impl MidiPassThroughMessageKind {
+    fn keyword(&self) -> &'static str {
+        use MidiPassThroughMessageKind::*;
+        match self {
+            Note => "note",
+            ControlChange => "cc",
+            ProgramChange => "pc",
+            PitchBend => "pitch",
+            Aftertouch => "aftertouch",
+            SysEx => "sysex",
+            System => "system",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        use MidiPassThroughMessageKind::*;
+        let kind = match keyword {
+            "note" => Note,
+            "cc" => ControlChange,
+            "pc" => ProgramChange,
+            "pitch" => PitchBend,
+            "aftertouch" => Aftertouch,
+            "sysex" => SysEx,
+            "system" => System,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+impl FromStr for MidiPassThroughRule {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        let mut tokens = lower.split_whitespace();
+        let action = match tokens.next() {
+            Some("pass") => MidiPassThroughAction::Pass,
+            Some("block") => MidiPassThroughAction::Block,
+            _ => return Err("rule must start with \"pass\" or \"block\""),
+        };
+        let mut kind = None;
+        let mut channel = None;
+        while let Some(token) = tokens.next() {
+            if token == "ch" {
+                let number: u8 = tokens
+                    .next()
+                    .and_then(|t| t.parse().ok())
+                    .ok_or("\"ch\" must be followed by a channel number")?;
+                let ch = number
+                    .checked_sub(1)
+                    .and_then(|n| Channel::try_from(n).ok())
+                    .ok_or("channel number must be between 1 and 16")?;
+                channel = Some(ch);
+            } else if token == "all" {
+                kind = None;
+            } else {
+                kind = Some(
+                    MidiPassThroughMessageKind::from_keyword(token)
+                        .ok_or("unknown message kind")?,
+                );
+            }
+        }
+        Ok(Self {
+            action,
+            kind,
+            channel,
+        })
+    }
+}
+
+impl Display for MidiPassThroughRule {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let action = match self.action {
+            MidiPassThroughAction::Pass => "pass",
+            MidiPassThroughAction::Block => "block",
+        };
+        f.write_str(action)?;
+        if let Some(kind) = self.kind {
+            write!(f, " {}", kind.keyword())?;
+        }
+        if let Some(ch) = self.channel {
+            write!(f, " ch {}", ch.get() + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for MidiPassThroughFilter {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rules: Result<Vec<MidiPassThroughRule>, _> = s
+            .split(';')
+            .map(|r| r.trim())
+            .filter(|r| !r.is_empty())
+            .map(|r| r.parse())
+            .collect();
+        Self::new(&rules?)
+    }
+}
+
+impl Display for MidiPassThroughFilter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, rule) in self.rules().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            rule.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::{control_change, note_on, timing_clock};
+
+    #[test]
+    fn block_cc_but_pass_notes() {
+        let filter: MidiPassThroughFilter = "block cc".parse().unwrap();
+        assert!(filter.allows_short(&note_on(0, 64, 100)));
+        assert!(!filter.allows_short(&control_change(0, 7, 100)));
+        assert!(filter.allows_short(&timing_clock()));
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let filter: MidiPassThroughFilter = "pass ch 2; block".parse().unwrap();
+        assert!(filter.allows_short(&control_change(1, 7, 100)));
+        assert!(!filter.allows_short(&control_change(0, 7, 100)));
+        assert!(!filter.allows_short(&timing_clock()));
+    }
+
+    #[test]
+    fn parse_and_format() {
+        // Given
+        let text = "pass note; block CC ch 10;; block sysex ";
+        // When
+        let filter: MidiPassThroughFilter = text.parse().unwrap();
+        // Then
+        assert_eq!(filter.to_string(), "pass note; block cc ch 10; block sysex");
+        assert_eq!(filter.to_string().parse(), Ok(filter));
+        assert_eq!("".parse(), Ok(MidiPassThroughFilter::PASS_ALL));
+        assert!("allow note".parse::<MidiPassThroughFilter>().is_err());
+        assert!("block ch 17".parse::<MidiPassThroughFilter>().is_err());
+    }
+}
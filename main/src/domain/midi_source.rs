@@ -1,3 +1,131 @@
 use crate::domain::FlexibleMidiSourceScript;
+use helgoboss_learn::MidiSourceValue;
+use helgoboss_midi::{
+    RawShortMessage, ShortMessage, ShortMessageFactory, StructuredShortMessage, U7,
+};
+use realearn_api::persistence::VelocityFeedback;
 
 pub type MidiSource = helgoboss_learn::MidiSource<FlexibleMidiSourceScript<'static>>;
+
+/// Adjusts the feedback of note velocity and polyphonic key pressure sources.
+///
+/// Many pad controllers choose the pad color (intensity) depending on the velocity of the note-on
+/// message which they receive for the pad's note.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NoteFeedbackOptions {
+    /// If set, the feedback value is translated to a velocity.
+    pub velocity_feedback: Option<VelocityFeedback>,
+    /// If `true`, polyphonic key pressure feedback is sent as note-on message on the same note.
+    pub send_as_note_on: bool,
+}
+
+impl NoteFeedbackOptions {
+    pub fn process(
+        &self,
+        value: MidiSourceValue<'static, RawShortMessage>,
+    ) -> MidiSourceValue<'static, RawShortMessage> {
+        let msg = match value {
+            MidiSourceValue::Plain(msg) => msg,
+            v => return v,
+        };
+        use StructuredShortMessage::*;
+        let (channel, key_number, amount, is_note_on) = match msg.to_structured() {
+            NoteOn {
+                channel,
+                key_number,
+                velocity,
+            } => (channel, key_number, velocity, true),
+            PolyphonicKeyPressure {
+                channel,
+                key_number,
+                pressure_amount,
+            } => (channel, key_number, pressure_amount, self.send_as_note_on),
+            _ => return MidiSourceValue::Plain(msg),
+        };
+        let amount = match &self.velocity_feedback {
+            None => amount,
+            Some(f) => translate_velocity(amount, f),
+        };
+        let msg = if is_note_on {
+            RawShortMessage::note_on(channel, key_number, amount)
+        } else {
+            RawShortMessage::polyphonic_key_pressure(channel, key_number, amount)
+        };
+        MidiSourceValue::Plain(msg)
+    }
+}
+
+/// Velocity 0 is left untouched because it switches the pad off.
+fn translate_velocity(velocity: U7, feedback: &VelocityFeedback) -> U7 {
+    if velocity == U7::MIN {
+        return velocity;
+    }
+    let fraction = velocity.get() as f64 / U7::MAX.get() as f64;
+    let translated = match feedback {
+        VelocityFeedback::Range(r) => {
+            let span = r.max as f64 - r.min as f64;
+            (r.min as f64 + (fraction * span).round()) as u8
+        }
+        VelocityFeedback::Palette(p) => {
+            if p.velocities.is_empty() {
+                return velocity;
+            }
+            let index = (fraction * (p.velocities.len() - 1) as f64).round() as usize;
+            p.velocities[index]
+        }
+    };
+    U7::new(translated.min(U7::MAX.get()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use realearn_api::persistence::{VelocityFeedbackPalette, VelocityFeedbackRange};
+
+    #[test]
+    fn palette() {
+        // Given
+        let options = NoteFeedbackOptions {
+            velocity_feedback: Some(VelocityFeedback::Palette(VelocityFeedbackPalette {
+                velocities: vec![5, 9, 13],
+            })),
+            send_as_note_on: false,
+        };
+        let process = |msg| options.process(MidiSourceValue::Plain(msg));
+        // When
+        // Then
+        assert_eq!(
+            process(note_on(0, 36, 127)),
+            MidiSourceValue::Plain(note_on(0, 36, 13))
+        );
+        assert_eq!(
+            process(note_on(0, 36, 64)),
+            MidiSourceValue::Plain(note_on(0, 36, 9))
+        );
+        assert_eq!(
+            process(note_on(0, 36, 1)),
+            MidiSourceValue::Plain(note_on(0, 36, 5))
+        );
+        assert_eq!(
+            process(note_on(0, 36, 0)),
+            MidiSourceValue::Plain(note_on(0, 36, 0))
+        );
+    }
+
+    #[test]
+    fn poly_key_pressure_as_note_on() {
+        // Given
+        let options = NoteFeedbackOptions {
+            velocity_feedback: Some(VelocityFeedback::Range(VelocityFeedbackRange {
+                min: 1,
+                max: 64,
+            })),
+            send_as_note_on: true,
+        };
+        // When
+        let value = options.process(MidiSourceValue::Plain(polyphonic_key_pressure(2, 40, 127)));
+        // Then
+        assert_eq!(value, MidiSourceValue::Plain(note_on(2, 40, 64)));
+    }
+}
@@ -36,6 +36,11 @@ impl SampleOffset {
         SampleOffset(offset_in_samples)
     }
 
+    pub fn to_frame_offset(self, sample_rate: Hz) -> MidiFrameOffset {
+        let offset_in_secs = self.0 as f64 / sample_rate.get();
+        MidiFrameOffset::new((offset_in_secs * 1024000.0).round() as u32)
+    }
+
     pub fn new(value: u64) -> Self {
         SampleOffset(value)
     }
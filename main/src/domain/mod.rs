@@ -22,6 +22,15 @@ pub use mode::*;
 mod midi_source;
 pub use midi_source::*;
 
+mod midi_machine_control;
+pub use midi_machine_control::*;
+
+mod audio_source;
+pub use audio_source::*;
+
+mod smoothing;
+pub use smoothing::*;
+
 mod eel_transformation;
 pub use eel_transformation::*;
 
@@ -55,9 +64,27 @@ pub use midi_util::*;
 mod midi_source_scanner;
 pub use midi_source_scanner::*;
 
+mod midi_input_limiter;
+pub use midi_input_limiter::*;
+
 mod midi_clock_calculator;
 pub use midi_clock_calculator::*;
 
+mod midi_clock_generator;
+pub use midi_clock_generator::*;
+
+mod real_time_load_monitor;
+pub use real_time_load_monitor::*;
+
+mod real_time_logger;
+pub use real_time_logger::*;
+
+mod virtual_control_bus;
+pub use virtual_control_bus::*;
+
+mod midi_pass_through_filter;
+pub use midi_pass_through_filter::*;
+
 mod conditional_activation;
 pub use conditional_activation::*;
 
@@ -120,12 +147,24 @@ pub use tag::*;
 mod mapping_snapshot;
 pub use mapping_snapshot::*;
 
+mod fx_parameter_snapshot;
+pub use fx_parameter_snapshot::*;
+
 mod organization;
 pub use organization::*;
 
+mod control_scheduler;
+pub use control_scheduler::*;
+
+mod notification;
+pub use notification::*;
+
 mod props;
 pub use props::*;
 
+mod prop_expression;
+pub use prop_expression::*;
+
 mod accelerator;
 pub use accelerator::*;
 
@@ -145,3 +184,32 @@ pub mod pot;
 
 mod sound;
 pub use sound::*;
+
+mod crossfade;
+pub use crossfade::*;
+
+mod feedback_effect;
+pub use feedback_effect::*;
+
+mod feedback_gesture;
+pub use feedback_gesture::*;
+
+mod source_debounce;
+pub use source_debounce::*;
+
+mod value_display;
+pub use value_display::*;
+
+mod parameter_thinning;
+pub use parameter_thinning::*;
+
+mod tempo_synced_turbo;
+pub use tempo_synced_turbo::*;
+
+mod pitch_bend;
+pub use pitch_bend::*;
+
+#[cfg(test)]
+mod real_time_processor_test_harness;
+#[cfg(test)]
+pub use real_time_processor_test_harness::*;
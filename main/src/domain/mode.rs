@@ -1,3 +1,178 @@
 use crate::domain::{ControlEventTimestamp, EelTransformation};
+use helgoboss_learn::{ControlValue, DiscreteIncrement};
+use realearn_api::persistence::{AccelerationCurve, ControlCurve};
 
 pub type Mode = helgoboss_learn::Mode<EelTransformation, ControlEventTimestamp>;
+
+/// Prevents exponential acceleration from producing absurdly large jumps.
+const MAX_ACCELERATED_INCREMENT: i32 = 100;
+
+/// Applies the given acceleration curve to discrete encoder increments.
+///
+/// Other control values are returned unchanged.
+pub fn accelerate_control_value(value: ControlValue, curve: AccelerationCurve) -> ControlValue {
+    match value {
+        ControlValue::RelativeDiscrete(i) => {
+            ControlValue::RelativeDiscrete(accelerate_increment(i, curve))
+        }
+        v => v,
+    }
+}
+
+/// Applies the given acceleration curve to an encoder increment, keeping its direction.
+pub fn accelerate_increment(
+    increment: DiscreteIncrement,
+    curve: AccelerationCurve,
+) -> DiscreteIncrement {
+    let magnitude = increment.get().abs();
+    let accelerated_magnitude = match curve {
+        AccelerationCurve::Off => 1,
+        AccelerationCurve::Linear => magnitude,
+        AccelerationCurve::Exponential(c) => {
+            let exponent = 1.0 + c.sensitivity.max(0.0);
+            let v = (magnitude as f64).powf(exponent).round() as i32;
+            v.clamp(1, MAX_ACCELERATED_INCREMENT)
+        }
+    };
+    DiscreteIncrement::new(increment.get().signum() * accelerated_magnitude)
+}
+
+/// Steepness of logarithmic and exponential control curves with amount 1.0.
+const MAX_CURVE_STEEPNESS: f64 = 8.0;
+
+/// Exponent of the S-curve with amount 1.0.
+const MAX_S_CURVE_EXPONENT: f64 = 4.0;
+
+/// Applies the given control curve to a normalized value (used for previews).
+pub fn apply_control_curve(x: f64, curve: ControlCurve) -> f64 {
+    match curve_shape(curve) {
+        None => x,
+        Some(CurveShape::Exponential(k)) => ((k * x).exp() - 1.0) / (k.exp() - 1.0),
+        Some(CurveShape::Logarithmic(k)) => (1.0 + x * (k.exp() - 1.0)).ln() / k,
+        Some(CurveShape::S(p)) => s_curve(x, p),
+    }
+}
+
+/// Builds a control transformation (x → y) which applies the given curve.
+///
+/// Returns `None` if the curve leaves values as they are.
+pub fn build_control_curve_eel(curve: ControlCurve) -> Option<String> {
+    let code = match curve_shape(curve)? {
+        CurveShape::Exponential(k) => format!("y = (exp({k} * x) - 1) / (exp({k}) - 1);"),
+        CurveShape::Logarithmic(k) => format!("y = log(1 + x * (exp({k}) - 1)) / {k};"),
+        CurveShape::S(p) => format!("y = pow(x, {p}) / (pow(x, {p}) + pow(1 - x, {p}));"),
+    };
+    Some(code)
+}
+
+/// Builds a feedback transformation (y → x) which inverts the given curve, so that the feedback
+/// value corresponds to the position of the control element.
+///
+/// Returns `None` if the curve leaves values as they are.
+pub fn build_feedback_curve_eel(curve: ControlCurve) -> Option<String> {
+    let code = match curve_shape(curve)? {
+        CurveShape::Exponential(k) => format!("x = log(1 + y * (exp({k}) - 1)) / {k};"),
+        CurveShape::Logarithmic(k) => format!("x = (exp({k} * y) - 1) / (exp({k}) - 1);"),
+        CurveShape::S(p) => {
+            let p = 1.0 / p;
+            format!("x = pow(y, {p}) / (pow(y, {p}) + pow(1 - y, {p}));")
+        }
+    };
+    Some(code)
+}
+
+enum CurveShape {
+    /// Contains the steepness.
+    Exponential(f64),
+    /// Contains the steepness.
+    Logarithmic(f64),
+    /// Contains the exponent.
+    S(f64),
+}
+
+fn curve_shape(curve: ControlCurve) -> Option<CurveShape> {
+    let shape = match curve {
+        ControlCurve::Linear => return None,
+        ControlCurve::Logarithmic(c) | ControlCurve::Exponential(c) | ControlCurve::SCurve(c)
+            if c.amount <= 0.0 =>
+        {
+            return None
+        }
+        ControlCurve::Logarithmic(c) => {
+            CurveShape::Logarithmic(c.amount.min(1.0) * MAX_CURVE_STEEPNESS)
+        }
+        ControlCurve::Exponential(c) => {
+            CurveShape::Exponential(c.amount.min(1.0) * MAX_CURVE_STEEPNESS)
+        }
+        ControlCurve::SCurve(c) => {
+            CurveShape::S(1.0 + c.amount.min(1.0) * (MAX_S_CURVE_EXPONENT - 1.0))
+        }
+    };
+    Some(shape)
+}
+
+fn s_curve(x: f64, exponent: f64) -> f64 {
+    let a = x.powf(exponent);
+    let b = (1.0 - x).powf(exponent);
+    a / (a + b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use realearn_api::persistence::{ExponentialAccelerationCurve, ShapedControlCurve};
+
+    fn accelerate(increment: i32, curve: AccelerationCurve) -> i32 {
+        accelerate_increment(DiscreteIncrement::new(increment), curve).get()
+    }
+
+    #[test]
+    fn off_and_linear() {
+        assert_eq!(accelerate(5, AccelerationCurve::Off), 1);
+        assert_eq!(accelerate(-5, AccelerationCurve::Off), -1);
+        assert_eq!(accelerate(5, AccelerationCurve::Linear), 5);
+        assert_eq!(accelerate(-5, AccelerationCurve::Linear), -5);
+    }
+
+    #[test]
+    fn exponential() {
+        let curve =
+            AccelerationCurve::Exponential(ExponentialAccelerationCurve { sensitivity: 1.0 });
+        assert_eq!(accelerate(1, curve), 1);
+        assert_eq!(accelerate(3, curve), 9);
+        assert_eq!(accelerate(-3, curve), -9);
+        assert_eq!(accelerate(50, curve), MAX_ACCELERATED_INCREMENT);
+    }
+
+    #[test]
+    fn control_curves_keep_end_points() {
+        let shaped = ShapedControlCurve { amount: 0.5 };
+        for curve in [
+            ControlCurve::Linear,
+            ControlCurve::Logarithmic(shaped),
+            ControlCurve::Exponential(shaped),
+            ControlCurve::SCurve(shaped),
+        ] {
+            assert!(apply_control_curve(0.0, curve).abs() < 1e-9);
+            assert!((apply_control_curve(1.0, curve) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn control_curve_shapes() {
+        let shaped = ShapedControlCurve { amount: 0.5 };
+        assert!(apply_control_curve(0.5, ControlCurve::Logarithmic(shaped)) > 0.5);
+        assert!(apply_control_curve(0.5, ControlCurve::Exponential(shaped)) < 0.5);
+        assert!(apply_control_curve(0.25, ControlCurve::SCurve(shaped)) < 0.25);
+        assert!(apply_control_curve(0.75, ControlCurve::SCurve(shaped)) > 0.75);
+        let flat = ShapedControlCurve { amount: 0.0 };
+        assert_eq!(
+            apply_control_curve(0.3, ControlCurve::Exponential(flat)),
+            0.3
+        );
+        assert_eq!(
+            build_control_curve_eel(ControlCurve::Exponential(flat)),
+            None
+        );
+    }
+}
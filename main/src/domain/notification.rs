@@ -0,0 +1,203 @@
+use crate::domain::{QualifiedMappingId, RealTimeOverruns};
+use derive_more::Display;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Maximum number of distinct notifications kept per instance.
+const MAX_NOTIFICATION_COUNT: usize = 20;
+
+/// Minimum time between two notifications about the same problem.
+const MIN_REPEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Display)]
+pub enum NotificationSeverity {
+    Warning,
+    Error,
+}
+
+/// What a notification is about.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum NotificationContext {
+    Instance,
+    Mapping(QualifiedMappingId),
+}
+
+/// A recoverable problem which should be brought to the attention of the user.
+///
+/// Domain functions usually just return `&'static str` errors. This wraps such an error with the
+/// information needed to present it in a useful way.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Notification {
+    pub severity: NotificationSeverity,
+    pub context: NotificationContext,
+    pub msg: Cow<'static, str>,
+}
+
+impl Notification {
+    pub fn instance_warning(msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            severity: NotificationSeverity::Warning,
+            context: NotificationContext::Instance,
+            msg: msg.into(),
+        }
+    }
+
+    pub fn mapping_warning(id: QualifiedMappingId, msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            severity: NotificationSeverity::Warning,
+            context: NotificationContext::Mapping(id),
+            msg: msg.into(),
+        }
+    }
+
+    pub fn mapping_error(id: QualifiedMappingId, msg: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            severity: NotificationSeverity::Error,
+            context: NotificationContext::Mapping(id),
+            msg: msg.into(),
+        }
+    }
+
+    /// Returns whether both notifications describe the same problem.
+    fn is_about_same_problem_as(&self, other: &Notification) -> bool {
+        self.context == other.context && self.msg == other.msg
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NotificationLogEntry {
+    pub notification: Notification,
+    /// How often this problem has been reported since it has been added to the log.
+    pub occurrence_count: u32,
+}
+
+/// Decides whether a notification should be passed on to the user.
+///
+/// Problems often occur repeatedly (e.g. each time a mapping is controlled, which can be many times
+/// per second). Each notification ends up in the log, in the UI and possibly at remote clients, so
+/// the same problem is reported at most once within [`MIN_REPEAT_INTERVAL`].
+#[derive(Debug, Default)]
+pub struct NotificationThrottle {
+    last_admissions: HashMap<(NotificationContext, Cow<'static, str>), Instant>,
+}
+
+impl NotificationThrottle {
+    /// Returns whether the given notification should be passed on to the user.
+    pub fn admit(&mut self, notification: &Notification, now: Instant) -> bool {
+        self.last_admissions
+            .retain(|_, last| now.saturating_duration_since(*last) < MIN_REPEAT_INTERVAL);
+        let key = (notification.context, notification.msg.clone());
+        if self.last_admissions.contains_key(&key) {
+            return false;
+        }
+        self.last_admissions.insert(key, now);
+        true
+    }
+}
+
+/// Something which is interesting when monitoring the health of an instance from the outside, e.g.
+/// from a remote dashboard.
+#[derive(Copy, Clone, Debug)]
+pub enum DiagnosticEvent<'a> {
+    Notified(&'a NotificationLogEntry),
+    RealTimeOverrunsDetected(RealTimeOverruns),
+    FeedbackSimulated(&'a str),
+}
+
+/// The most recent notifications, latest first.
+///
+/// Problems often occur repeatedly (e.g. each time a mapping is controlled), so a notification
+/// describing the same problem as an existing one is merged into that one instead of being added.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct NotificationLog {
+    entries: VecDeque<NotificationLogEntry>,
+}
+
+impl NotificationLog {
+    pub fn add(&mut self, notification: Notification) {
+        let existing_index = self
+            .entries
+            .iter()
+            .position(|e| e.notification.is_about_same_problem_as(&notification));
+        let entry = match existing_index.and_then(|i| self.entries.remove(i)) {
+            None => NotificationLogEntry {
+                notification,
+                occurrence_count: 1,
+            },
+            Some(e) => NotificationLogEntry {
+                notification,
+                occurrence_count: e.occurrence_count + 1,
+            },
+        };
+        self.entries.push_front(entry);
+        self.entries.truncate(MAX_NOTIFICATION_COUNT);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn latest(&self) -> Option<&NotificationLogEntry> {
+        self.entries.front()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &NotificationLogEntry> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Compartment, MappingId};
+
+    #[test]
+    fn same_problem_is_merged() {
+        // Given
+        let mut log = NotificationLog::default();
+        let id_1 = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let id_2 = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        // When
+        log.add(Notification::mapping_warning(id_1, "track not found"));
+        log.add(Notification::mapping_warning(id_2, "track not found"));
+        log.add(Notification::mapping_error(id_1, "track not found"));
+        // Then
+        let entries: Vec<_> = log.entries().collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].notification,
+            Notification::mapping_error(id_1, "track not found")
+        );
+        assert_eq!(entries[0].occurrence_count, 2);
+        assert_eq!(entries[1].occurrence_count, 1);
+    }
+
+    #[test]
+    fn same_problem_is_throttled() {
+        // Given
+        let mut throttle = NotificationThrottle::default();
+        let id_1 = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let id_2 = QualifiedMappingId::new(Compartment::Main, MappingId::random());
+        let start = Instant::now();
+        // When
+        let first = throttle.admit(&Notification::mapping_error(id_1, "failed"), start);
+        let repeated = throttle.admit(&Notification::mapping_error(id_1, "failed"), start);
+        let other_mapping = throttle.admit(&Notification::mapping_error(id_2, "failed"), start);
+        let other_msg = throttle.admit(&Notification::mapping_error(id_1, "other"), start);
+        let later = throttle.admit(
+            &Notification::mapping_error(id_1, "failed"),
+            start + MIN_REPEAT_INTERVAL,
+        );
+        // Then
+        assert!(first);
+        assert!(!repeated);
+        assert!(other_mapping);
+        assert!(other_msg);
+        assert!(later);
+    }
+}
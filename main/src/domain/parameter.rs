@@ -257,6 +257,14 @@ impl CompartmentParams {
         }
     }
 
+    /// Returns whether all parameters have the same names as the given ones.
+    pub fn has_same_names_as(&self, other: &CompartmentParams) -> bool {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .all(|(a, b)| a.setting.name == b.setting.name)
+    }
+
     /// Returns a map of all parameter settings that don't correspond to the defaults.
     pub fn non_default_settings(&self) -> Vec<(CompartmentParamIndex, ParamSetting)> {
         self.0
@@ -0,0 +1,230 @@
+use crate::domain::ui_util::{format_as_percentage_without_unit, parse_unit_value_from_percentage};
+use helgoboss_learn::UnitValue;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Limits how densely parameter values are written when controlling FX parameters.
+///
+/// Rapid encoder movements can produce more parameter changes than REAPER can comfortably turn
+/// into automation points. Thinning skips changes that arrive too soon after the previous one or
+/// that differ too little from it.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterThinning {
+    /// Minimum time between two written values in milliseconds. 0 means no limit.
+    #[serde(default)]
+    pub min_interval_millis: u32,
+    /// Minimum difference to the previously written value. 0 means no limit.
+    #[serde(default)]
+    pub min_delta: UnitValue,
+}
+
+impl ParameterThinning {
+    pub fn is_off(&self) -> bool {
+        self.min_interval_millis == 0 && self.min_delta.is_zero()
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_millis(self.min_interval_millis as u64)
+    }
+}
+
+impl Display for ParameterThinning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_off() {
+            return f.write_str("Off");
+        }
+        write!(
+            f,
+            "{} ms, {} %",
+            self.min_interval_millis,
+            format_as_percentage_without_unit(self.min_delta)
+        )
+    }
+}
+
+impl FromStr for ParameterThinning {
+    type Err = &'static str;
+
+    /// Parses something like "50 ms, 0.5 %" or "Off". The delta part is optional.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("off") {
+            return Ok(Default::default());
+        }
+        let mut parts = text.split(',');
+        let interval_part = parts.next().unwrap_or_default();
+        let min_interval_millis = interval_part
+            .trim()
+            .trim_end_matches("ms")
+            .trim()
+            .parse()
+            .map_err(|_| "minimum interval must be a number of milliseconds")?;
+        let min_delta = match parts.next() {
+            None => UnitValue::MIN,
+            Some(delta_part) => {
+                parse_unit_value_from_percentage(delta_part.trim().trim_end_matches('%').trim())?
+            }
+        };
+        if parts.next().is_some() {
+            return Err("parameter thinning must have the form \"50 ms, 0.5 %\"");
+        }
+        let thinning = ParameterThinning {
+            min_interval_millis,
+            min_delta,
+        };
+        Ok(thinning)
+    }
+}
+
+/// Time after which a value which has been held back only because it's too close to the
+/// previously written one is written anyway, provided that no newer value arrived in the meantime.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Applies parameter thinning to a stream of values, remembering the last written one.
+///
+/// Values which are held back are not lost: The most recent one is kept as pending value and
+/// written later (see [`Self::poll`]), so the parameter always ends up where the user left it.
+#[derive(Clone, Debug, Default)]
+pub struct ParameterThinner {
+    last_written: Option<(Instant, UnitValue)>,
+    pending: Option<PendingValue>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct PendingValue {
+    value: UnitValue,
+    due: Instant,
+}
+
+impl ParameterThinner {
+    /// Returns whether the given value should be written now. If yes, it's memorized as the last
+    /// written value. If not, it becomes the pending value.
+    ///
+    /// Values at the very ends of the range always pass, so that sweeping a parameter reliably
+    /// reaches its minimum and maximum.
+    pub fn should_write(
+        &mut self,
+        thinning: ParameterThinning,
+        value: UnitValue,
+        now: Instant,
+    ) -> bool {
+        if !thinning.is_off() && !value.is_zero() && value != UnitValue::MAX {
+            if let Some((last_time, last_value)) = self.last_written {
+                let too_soon = now.saturating_duration_since(last_time) < thinning.min_interval();
+                // The distance to the last *written* value grows with each held back value, so
+                // small relative steps accumulate until they exceed the minimum delta.
+                let too_close = (value.get() - last_value.get()).abs() < thinning.min_delta.get();
+                if too_soon || too_close {
+                    let due = if too_close {
+                        now + SETTLE_TIME.max(thinning.min_interval())
+                    } else {
+                        last_time + thinning.min_interval()
+                    };
+                    self.pending = Some(PendingValue { value, due });
+                    return false;
+                }
+            }
+        }
+        self.last_written = Some((now, value));
+        self.pending = None;
+        true
+    }
+
+    /// Returns the value which has been held back most recently and is not yet written.
+    ///
+    /// Relative control should build on this value instead of the actual parameter value.
+    /// Otherwise, relative steps smaller than the minimum delta would never move the parameter.
+    pub fn pending_value(&self) -> Option<UnitValue> {
+        self.pending.map(|p| p.value)
+    }
+
+    /// Returns the pending value if it's due to be written now. If yes, it's memorized as the last
+    /// written value.
+    pub fn poll(&mut self, now: Instant) -> Option<UnitValue> {
+        let pending = self.pending?;
+        if now < pending.due {
+            return None;
+        }
+        self.pending = None;
+        self.last_written = Some((now, pending.value));
+        Some(pending.value)
+    }
+
+    /// Returns whether this thinner doesn't have any influence anymore and can be discarded.
+    pub fn is_obsolete(&self, now: Instant, max_interval: Duration) -> bool {
+        if self.pending.is_some() {
+            return false;
+        }
+        match self.last_written {
+            None => true,
+            Some((last_time, _)) => now.saturating_duration_since(last_time) > max_interval,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thin_out_rapid_changes() {
+        // Given
+        let thinning: ParameterThinning = "50 ms, 1 %".parse().unwrap();
+        let mut thinner = ParameterThinner::default();
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        // When
+        // Then
+        assert!(thinner.should_write(thinning, UnitValue::new(0.50), at(0)));
+        assert!(!thinner.should_write(thinning, UnitValue::new(0.60), at(10)));
+        assert!(!thinner.should_write(thinning, UnitValue::new(0.505), at(100)));
+        assert!(thinner.should_write(thinning, UnitValue::new(0.60), at(100)));
+        assert!(thinner.should_write(thinning, UnitValue::MAX, at(110)));
+    }
+
+    #[test]
+    fn write_held_back_value_later() {
+        // Given
+        let thinning: ParameterThinning = "50 ms".parse().unwrap();
+        let mut thinner = ParameterThinner::default();
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        // When
+        // Then
+        assert!(thinner.should_write(thinning, UnitValue::new(0.50), at(0)));
+        assert!(!thinner.should_write(thinning, UnitValue::new(0.55), at(10)));
+        assert!(!thinner.should_write(thinning, UnitValue::new(0.60), at(20)));
+        assert_eq!(thinner.poll(at(30)), None);
+        assert_eq!(thinner.poll(at(50)), Some(UnitValue::new(0.60)));
+        assert_eq!(thinner.poll(at(100)), None);
+    }
+
+    #[test]
+    fn accumulate_small_relative_steps() {
+        // Given
+        let thinning: ParameterThinning = "0 ms, 1 %".parse().unwrap();
+        let mut thinner = ParameterThinner::default();
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        let mut param_value = UnitValue::new(0.50);
+        assert!(thinner.should_write(thinning, param_value, at(0)));
+        // When
+        let written: Vec<_> = (1..=10)
+            .filter(|i| {
+                // Relative control builds on the pending value, like the FX parameter target does.
+                let current = thinner.pending_value().unwrap_or(param_value);
+                let next = UnitValue::new(current.get() + 0.004);
+                let write = thinner.should_write(thinning, next, at(*i));
+                if write {
+                    param_value = next;
+                }
+                write
+            })
+            .collect();
+        // Then
+        assert_eq!(written, vec![3, 6, 9]);
+    }
+}
@@ -0,0 +1,135 @@
+use helgoboss_learn::{ControlValue, DiscreteIncrement, UnitValue};
+use realearn_api::persistence::PitchBendMode;
+
+/// 14-bit pitch bend value which represents the center of the wheel.
+const CENTER: f64 = 8192.0;
+/// Maximum 14-bit pitch bend value.
+const MAX: f64 = 16383.0;
+/// Number of increments emitted in relative mode when moving the wheel from center to one end.
+const RELATIVE_STEP_COUNT: f64 = 64.0;
+
+/// Settings for processing values of spring-loaded pitch wheels.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct PitchBendSettings {
+    /// Fraction of the distance between center and end which is treated as center.
+    pub center_dead_zone: f64,
+    pub mode: PitchBendMode,
+}
+
+/// Turns pitch bend values into bipolar values, taking the center into account.
+///
+/// The 14-bit center (8192) is not exactly in the middle of the value range, so without this, the
+/// center would correspond to 50.003% instead of 50%.
+#[derive(Clone, Debug)]
+pub struct PitchBendFilter {
+    settings: PitchBendSettings,
+    /// Last deflection in relative steps (negative means below center).
+    last_step: i32,
+}
+
+impl PitchBendFilter {
+    pub fn new(settings: PitchBendSettings) -> Self {
+        Self {
+            settings,
+            last_step: 0,
+        }
+    }
+
+    /// Returns `None` if the value should be swallowed.
+    ///
+    /// Relative values are let through unchanged.
+    pub fn process(&mut self, value: ControlValue) -> Option<ControlValue> {
+        let value = match value.to_unit_value() {
+            Ok(v) => v,
+            Err(_) => return Some(value),
+        };
+        let deflection = deflection(value.get(), self.settings.center_dead_zone);
+        match self.settings.mode {
+            PitchBendMode::Absolute => Some(ControlValue::AbsoluteContinuous(
+                UnitValue::new_clamped((deflection + 1.0) / 2.0),
+            )),
+            PitchBendMode::RelativeFromCenter => {
+                let step = (deflection * RELATIVE_STEP_COUNT).round() as i32;
+                // Moving back towards the center (spring return) doesn't emit anything
+                let base = if step.signum() == self.last_step.signum() {
+                    self.last_step
+                } else {
+                    0
+                };
+                self.last_step = step;
+                if step.abs() <= base.abs() {
+                    return None;
+                }
+                Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(
+                    step - base,
+                )))
+            }
+        }
+    }
+}
+
+/// Returns the deflection of the wheel from -1.0 (lowest) to 1.0 (highest), 0.0 being the center.
+fn deflection(value: f64, center_dead_zone: f64) -> f64 {
+    let raw = value * MAX;
+    let deflection = if raw >= CENTER {
+        (raw - CENTER) / (MAX - CENTER)
+    } else {
+        (raw - CENTER) / CENTER
+    };
+    let magnitude = deflection.abs();
+    if magnitude <= center_dead_zone || center_dead_zone >= 1.0 {
+        return 0.0;
+    }
+    deflection.signum() * (magnitude - center_dead_zone) / (1.0 - center_dead_zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pitch_bend(raw: u16) -> ControlValue {
+        ControlValue::AbsoluteContinuous(UnitValue::new(raw as f64 / MAX))
+    }
+
+    #[test]
+    fn absolute_with_dead_zone() {
+        // Given
+        let mut filter = PitchBendFilter::new(PitchBendSettings {
+            center_dead_zone: 0.1,
+            mode: PitchBendMode::Absolute,
+        });
+        // When
+        let center = filter.process(pitch_bend(8192));
+        let within_dead_zone = filter.process(pitch_bend(8500));
+        let max = filter.process(pitch_bend(16383));
+        let min = filter.process(pitch_bend(0));
+        // Then
+        assert_eq!(
+            center,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert_eq!(within_dead_zone, center);
+        assert_eq!(max, Some(ControlValue::AbsoluteContinuous(UnitValue::MAX)));
+        assert_eq!(min, Some(ControlValue::AbsoluteContinuous(UnitValue::MIN)));
+    }
+
+    #[test]
+    fn relative_from_center_ignores_spring_return() {
+        // Given
+        let mut filter = PitchBendFilter::new(PitchBendSettings {
+            center_dead_zone: 0.0,
+            mode: PitchBendMode::RelativeFromCenter,
+        });
+        let inc = |i| Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(i)));
+        // When
+        let up = filter.process(pitch_bend(8192 + 1024));
+        let further_up = filter.process(pitch_bend(8192 + 2048));
+        let spring_return = filter.process(pitch_bend(8192));
+        let down = filter.process(pitch_bend(8192 - 1024));
+        // Then
+        assert_eq!(up, inc(8));
+        assert_eq!(further_up, inc(8));
+        assert_eq!(spring_return, None);
+        assert_eq!(down, inc(-8));
+    }
+}
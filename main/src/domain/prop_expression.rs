@@ -0,0 +1,482 @@
+use helgoboss_learn::{NumericValue, PropValue};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// The function calls among the placeholders of a feedback expression, parsed in advance so that
+/// they don't need to be parsed again whenever feedback is sent.
+#[derive(Clone, Debug, Default)]
+pub struct PropFunctionCalls {
+    calls_by_key: HashMap<String, PropFunctionCall>,
+}
+
+impl PropFunctionCalls {
+    /// Parses all given placeholder contents which are function calls.
+    ///
+    /// Invalid function calls are left out, so they resolve to nothing, just like unknown
+    /// properties.
+    pub fn parse(keys: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let calls_by_key = keys
+            .into_iter()
+            .filter_map(|key| {
+                let key = key.as_ref();
+                let call = PropFunctionCall::parse(key)?.ok()?;
+                Some((key.to_string(), call))
+            })
+            .collect();
+        Self { calls_by_key }
+    }
+
+    /// Returns the function call for the given placeholder content or `None` if it's a plain
+    /// property key (or an invalid function call).
+    pub fn get(&self, key: &str) -> Option<&PropFunctionCall> {
+        self.calls_by_key.get(key)
+    }
+
+    /// Returns the value of the given placeholder, evaluating it if it's a function call and
+    /// otherwise looking it up as property via the given function.
+    pub fn get_value(
+        &self,
+        key: &str,
+        get_prop_value: &impl Fn(&str) -> Option<PropValue>,
+    ) -> Option<PropValue> {
+        match self.get(key) {
+            None => get_prop_value(key),
+            Some(call) => {
+                let text = call.evaluate(get_prop_value)?;
+                Some(PropValue::Text(text.into()))
+            }
+        }
+    }
+}
+
+/// A function call within a textual feedback placeholder, e.g.
+/// `{{ format(target.numeric_value, 1) }}`.
+///
+/// Arguments can be properties, number literals, text literals (in double or single quotes) and
+/// nested function calls. The result of a function is always text, so it's not subject to any
+/// further number formatting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropFunctionCall {
+    function: PropFunction,
+    args: Vec<PropExpression>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PropExpression {
+    Prop(String),
+    Number(f64),
+    Text(String),
+    Call(PropFunctionCall),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum PropFunction {
+    /// `round(x)` or `round(x, decimals)`
+    Round,
+    /// `format(x, decimals)`, `format(x, decimals, width)` or `format(x, decimals, width, fill)`
+    Format,
+    /// `percent(x)` or `percent(x, decimals)`, x being a normalized value between 0.0 and 1.0
+    Percent,
+    /// `db(x)` or `db(x, decimals)`, x being a linear amplitude factor
+    Db,
+    /// `if(condition, then)` or `if(condition, then, else)`
+    If,
+    /// `gt(a, b)`
+    GreaterThan,
+    /// `lt(a, b)`
+    LessThan,
+    /// `eq(a, b)`
+    Equal,
+}
+
+impl PropFunctionCall {
+    /// Returns `None` if the given placeholder content is not a function call but (presumably) a
+    /// plain property key.
+    pub fn parse(text: &str) -> Option<Result<Self, &'static str>> {
+        if !text.contains('(') {
+            return None;
+        }
+        let result = Parser::new(text)
+            .parse_complete()
+            .and_then(|expr| match expr {
+                PropExpression::Call(call) => Ok(call),
+                _ => Err("placeholder is not a function call"),
+            });
+        Some(result)
+    }
+
+    /// Calls the given function for each property key that's used within this call (including
+    /// nested ones).
+    pub fn visit_prop_keys<'a>(&'a self, f: &mut impl FnMut(&'a str)) {
+        for arg in &self.args {
+            match arg {
+                PropExpression::Prop(key) => f(key),
+                PropExpression::Call(call) => call.visit_prop_keys(f),
+                PropExpression::Number(_) | PropExpression::Text(_) => {}
+            }
+        }
+    }
+
+    /// Evaluates this call, using the given function to look up property values.
+    ///
+    /// Returns `None` if a property is not available or an argument doesn't have the expected
+    /// type.
+    pub fn evaluate(&self, get_prop_value: &impl Fn(&str) -> Option<PropValue>) -> Option<String> {
+        let arg = |i: usize| -> Option<Value> { self.args.get(i)?.evaluate(get_prop_value) };
+        let number_arg = |i: usize| -> Option<f64> { arg(i)?.to_number() };
+        let count_arg = |i: usize, default: usize| -> Option<usize> {
+            match self.args.get(i) {
+                None => Some(default),
+                Some(_) => {
+                    let n = number_arg(i)?;
+                    if n < 0.0 {
+                        return None;
+                    }
+                    Some(n.round() as usize)
+                }
+            }
+        };
+        use PropFunction::*;
+        let result = match self.function {
+            Round => {
+                let decimals = count_arg(1, 0)?;
+                format!("{:.*}", decimals, number_arg(0)?)
+            }
+            Format => {
+                let decimals = count_arg(1, 0)?;
+                let width = count_arg(2, 0)?;
+                let fill = match self.args.get(3) {
+                    None => ' ',
+                    Some(_) => arg(3)?.to_text()?.chars().next()?,
+                };
+                let formatted = format!("{:.*}", decimals, number_arg(0)?);
+                pad_left(formatted, width, fill)
+            }
+            Percent => {
+                let decimals = count_arg(1, 0)?;
+                format!("{:.*}%", decimals, number_arg(0)? * 100.0)
+            }
+            Db => {
+                let decimals = count_arg(1, 1)?;
+                let factor = number_arg(0)?;
+                if factor <= 0.0 {
+                    "-inf dB".to_string()
+                } else {
+                    format!("{:.*} dB", decimals, 20.0 * factor.log10())
+                }
+            }
+            If => {
+                let branch = if arg(0)?.is_truthy() { 1 } else { 2 };
+                match self.args.get(branch) {
+                    None => String::new(),
+                    Some(_) => arg(branch)?.to_text()?.into_owned(),
+                }
+            }
+            GreaterThan => format_bool(number_arg(0)? > number_arg(1)?),
+            LessThan => format_bool(number_arg(0)? < number_arg(1)?),
+            Equal => {
+                let (a, b) = (arg(0)?, arg(1)?);
+                let equal = match (a.to_number(), b.to_number()) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => a.to_text()? == b.to_text()?,
+                };
+                format_bool(equal)
+            }
+        };
+        Some(result)
+    }
+}
+
+impl PropFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        use PropFunction::*;
+        let f = match name {
+            "round" => Round,
+            "format" => Format,
+            "percent" => Percent,
+            "db" => Db,
+            "if" => If,
+            "gt" => GreaterThan,
+            "lt" => LessThan,
+            "eq" => Equal,
+            _ => return None,
+        };
+        Some(f)
+    }
+
+    fn arg_count_range(&self) -> (usize, usize) {
+        use PropFunction::*;
+        match self {
+            Round | Percent | Db => (1, 2),
+            Format => (2, 4),
+            If => (2, 3),
+            GreaterThan | LessThan | Equal => (2, 2),
+        }
+    }
+}
+
+impl PropExpression {
+    fn evaluate(&self, get_prop_value: &impl Fn(&str) -> Option<PropValue>) -> Option<Value> {
+        let v = match self {
+            PropExpression::Prop(key) => Value::Prop(get_prop_value(key)?),
+            PropExpression::Number(n) => Value::Number(*n),
+            PropExpression::Text(t) => Value::Text(t.clone()),
+            PropExpression::Call(call) => Value::Text(call.evaluate(get_prop_value)?),
+        };
+        Some(v)
+    }
+}
+
+/// Intermediate result when evaluating function arguments.
+enum Value {
+    Prop(PropValue),
+    Number(f64),
+    Text(String),
+}
+
+impl Value {
+    fn to_number(&self) -> Option<f64> {
+        match self {
+            Value::Prop(v) => match v {
+                PropValue::Normalized(v) => Some(v.get()),
+                PropValue::Index(i) => Some(*i as f64),
+                PropValue::Numeric(NumericValue::Decimal(v)) => Some(*v),
+                PropValue::Numeric(NumericValue::Discrete(v)) => Some(*v as f64),
+                PropValue::Text(t) => t.trim().parse().ok(),
+                PropValue::Color(_) => None,
+            },
+            Value::Number(n) => Some(*n),
+            Value::Text(t) => t.trim().parse().ok(),
+        }
+    }
+
+    fn to_text(&self) -> Option<Cow<str>> {
+        let text = match self {
+            Value::Prop(v) => match v {
+                PropValue::Text(t) => t.as_ref().into(),
+                PropValue::Color(_) => return None,
+                _ => self.to_number()?.to_string().into(),
+            },
+            Value::Number(n) => n.to_string().into(),
+            Value::Text(t) => t.as_str().into(),
+        };
+        Some(text)
+    }
+
+    /// Non-zero numbers and non-empty texts are true.
+    fn is_truthy(&self) -> bool {
+        if let Some(n) = self.to_number() {
+            return n != 0.0;
+        }
+        self.to_text().map(|t| !t.is_empty()).unwrap_or(false)
+    }
+}
+
+fn format_bool(value: bool) -> String {
+    let text = if value { "1" } else { "0" };
+    text.to_string()
+}
+
+fn pad_left(text: String, width: usize, fill: char) -> String {
+    let len = text.chars().count();
+    if len >= width {
+        return text;
+    }
+    let mut padded = fill.to_string().repeat(width - len);
+    padded.push_str(&text);
+    padded
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { rest: text }
+    }
+
+    fn parse_complete(mut self) -> Result<PropExpression, &'static str> {
+        let expr = self.parse_expression()?;
+        self.skip_whitespace();
+        if !self.rest.is_empty() {
+            return Err("unexpected characters after expression");
+        }
+        Ok(expr)
+    }
+
+    fn parse_expression(&mut self) -> Result<PropExpression, &'static str> {
+        self.skip_whitespace();
+        match self.rest.chars().next() {
+            None => Err("expression expected"),
+            Some(quote @ ('"' | '\'')) => self.parse_text(quote),
+            Some(_) => {
+                let word = self.parse_word()?;
+                self.skip_whitespace();
+                if self.consume('(') {
+                    let function = PropFunction::from_name(word).ok_or("unknown function")?;
+                    let args = self.parse_args()?;
+                    let (min, max) = function.arg_count_range();
+                    if args.len() < min || args.len() > max {
+                        return Err("wrong number of function arguments");
+                    }
+                    Ok(PropExpression::Call(PropFunctionCall { function, args }))
+                } else if word.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+                    let n = word.parse().map_err(|_| "invalid number")?;
+                    Ok(PropExpression::Number(n))
+                } else {
+                    Ok(PropExpression::Prop(word.to_string()))
+                }
+            }
+        }
+    }
+
+    fn parse_args(&mut self) -> Result<Vec<PropExpression>, &'static str> {
+        let mut args = vec![];
+        self.skip_whitespace();
+        if self.consume(')') {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_expression()?);
+            self.skip_whitespace();
+            if self.consume(')') {
+                return Ok(args);
+            }
+            if !self.consume(',') {
+                return Err("expected \",\" or \")\"");
+            }
+        }
+    }
+
+    fn parse_text(&mut self, quote: char) -> Result<PropExpression, &'static str> {
+        let inner = &self.rest[quote.len_utf8()..];
+        let end = inner.find(quote).ok_or("unterminated text")?;
+        let text = inner[..end].to_string();
+        self.rest = &inner[end + quote.len_utf8()..];
+        Ok(PropExpression::Text(text))
+    }
+
+    fn parse_word(&mut self) -> Result<&'a str, &'static str> {
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '-'))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err("unexpected character");
+        }
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    fn consume(&mut self, c: char) -> bool {
+        if let Some(rest) = self.rest.strip_prefix(c) {
+            self.rest = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Mode;
+    use helgoboss_learn::{FeedbackType, ModeSettings, UnitValue};
+
+    fn eval(text: &str) -> Option<String> {
+        let call = PropFunctionCall::parse(text).unwrap().unwrap();
+        call.evaluate(&|key| match key {
+            "target.numeric_value" => Some(PropValue::Numeric(NumericValue::Decimal(-6.0206))),
+            "target.normalized_value" => Some(PropValue::Normalized(UnitValue::new(0.754))),
+            "target.track.name" => Some(PropValue::Text("Guitar".into())),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn number_formatting() {
+        assert_eq!(eval("round(target.numeric_value)").as_deref(), Some("-6"));
+        assert_eq!(
+            eval("round(target.numeric_value, 1)").as_deref(),
+            Some("-6.0")
+        );
+        assert_eq!(
+            eval("format(target.numeric_value, 2, 7)").as_deref(),
+            Some("  -6.02")
+        );
+        assert_eq!(eval("format(7, 0, 3, '0')").as_deref(), Some("007"));
+        assert_eq!(
+            eval("percent(target.normalized_value)").as_deref(),
+            Some("75%")
+        );
+        assert_eq!(eval("db(0.5)").as_deref(), Some("-6.0 dB"));
+        assert_eq!(eval("db(0)").as_deref(), Some("-inf dB"));
+    }
+
+    #[test]
+    fn conditional_text() {
+        assert_eq!(
+            eval("if(gt(target.normalized_value, 0.5), \"Loud\", \"Quiet\")").as_deref(),
+            Some("Loud")
+        );
+        assert_eq!(
+            eval("if(eq(target.track.name, 'Bass'), 'B', target.track.name)").as_deref(),
+            Some("Guitar")
+        );
+        assert_eq!(eval("if(lt(1, 0), 'yes')").as_deref(), Some(""));
+        // Unavailable property
+        assert_eq!(eval("round(target.fx.name)"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(PropFunctionCall::parse("target.text_value"), None);
+        assert!(PropFunctionCall::parse("foo(1)").unwrap().is_err());
+        assert!(PropFunctionCall::parse("round()").unwrap().is_err());
+        assert!(PropFunctionCall::parse("round(1, 2").unwrap().is_err());
+        assert!(PropFunctionCall::parse("if(1, 'a) ").unwrap().is_err());
+    }
+
+    #[test]
+    fn textual_feedback_expression() {
+        // Given
+        let mode = Mode::new(ModeSettings {
+            feedback_type: FeedbackType::Textual,
+            textual_feedback_expression:
+                "{{ target.track.name }}: {{ db(0.5) }} / {{ percent(target.normalized_value) }}"
+                    .to_string(),
+            ..Default::default()
+        });
+        let calls = PropFunctionCalls::parse(mode.feedback_props_in_use());
+        // When
+        let feedback = mode.query_textual_feedback(&|key| {
+            calls.get_value(key, &|key| match key {
+                "target.normalized_value" => Some(PropValue::Normalized(UnitValue::new(0.754))),
+                "target.track.name" => Some(PropValue::Text("Guitar".into())),
+                _ => None,
+            })
+        });
+        // Then
+        assert_eq!(feedback.text, "Guitar: -6.0 dB / 75%");
+    }
+
+    #[test]
+    fn visit_nested_prop_keys() {
+        // Given
+        let call = PropFunctionCall::parse("if(gt(target.normalized_value, 0), mapping.name)")
+            .unwrap()
+            .unwrap();
+        // When
+        let mut keys = vec![];
+        call.visit_prop_keys(&mut |k| keys.push(k));
+        // Then
+        assert_eq!(keys, vec!["target.normalized_value", "mapping.name"]);
+    }
+}
@@ -16,6 +16,13 @@ pub fn prop_feedback_resolution(
     mapping: &MainMapping,
     target: &UnresolvedCompoundMappingTarget,
 ) -> Option<FeedbackResolution> {
+    if let Some(call) = mapping.prop_function_calls().get(key) {
+        let mut max_resolution = None;
+        call.visit_prop_keys(&mut |k| {
+            max_resolution = max_resolution.max(prop_feedback_resolution(k, mapping, target));
+        });
+        return max_resolution;
+    }
     match key.parse::<Props>().ok() {
         Some(props) => props.feedback_resolution(mapping, target),
         None => {
@@ -35,6 +42,13 @@ pub fn prop_is_affected_by(
     target: &ReaperTarget,
     control_context: ControlContext,
 ) -> bool {
+    if let Some(call) = mapping.prop_function_calls().get(key) {
+        let mut is_affected = false;
+        call.visit_prop_keys(&mut |k| {
+            is_affected |= prop_is_affected_by(k, event, mapping, target, control_context);
+        });
+        return is_affected;
+    }
     match key.parse::<Props>().ok() {
         Some(props) => {
             // TODO-medium Not very consequent? Here we take the first target and for
@@ -59,6 +73,16 @@ pub fn get_prop_value(
     key: &str,
     mapping: &MainMapping,
     control_context: ControlContext,
+) -> Option<PropValue> {
+    mapping
+        .prop_function_calls()
+        .get_value(key, &|k| get_plain_prop_value(k, mapping, control_context))
+}
+
+fn get_plain_prop_value(
+    key: &str,
+    mapping: &MainMapping,
+    control_context: ControlContext,
 ) -> Option<PropValue> {
     match key.parse::<Props>().ok() {
         Some(props) => props.get_value(mapping, mapping.targets().first(), control_context),
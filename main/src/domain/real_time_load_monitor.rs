@@ -0,0 +1,109 @@
+use crate::domain::AudioBlockProps;
+use std::mem;
+use std::time::Duration;
+
+/// Processing of one audio block counts as overrun if it takes longer than this fraction of the
+/// block duration.
+const OVERRUN_THRESHOLD: f64 = 0.5;
+
+/// Measures how long real-time processing takes per audio block and collects overruns, so that
+/// they can be reported to the main thread from time to time (at most once per second).
+#[derive(Debug, Default)]
+pub struct RealTimeLoadMonitor {
+    pending_overruns: RealTimeOverruns,
+    samples_since_last_report: u64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct RealTimeOverruns {
+    /// Number of audio blocks whose processing exceeded the threshold.
+    pub count: u32,
+    /// Highest processing time relative to the block duration, in percent.
+    pub max_load_percent: u32,
+}
+
+impl RealTimeOverruns {
+    /// Adds the given overruns to these ones.
+    pub fn accumulate(&mut self, other: RealTimeOverruns) {
+        self.count += other.count;
+        self.max_load_percent = self.max_load_percent.max(other.max_load_percent);
+    }
+}
+
+impl RealTimeLoadMonitor {
+    /// Records the processing time of one audio block.
+    ///
+    /// Returns the overruns which should be reported to the main thread now, if any.
+    pub fn record_block(
+        &mut self,
+        processing_time: Duration,
+        block_props: AudioBlockProps,
+    ) -> Option<RealTimeOverruns> {
+        let frame_rate = block_props.frame_rate.get();
+        let block_duration_in_secs = block_props.block_length as f64 / frame_rate;
+        if block_duration_in_secs > 0.0 {
+            let load = processing_time.as_secs_f64() / block_duration_in_secs;
+            if load > OVERRUN_THRESHOLD {
+                self.pending_overruns.accumulate(RealTimeOverruns {
+                    count: 1,
+                    max_load_percent: (load * 100.0).round() as u32,
+                });
+            }
+        }
+        self.samples_since_last_report += block_props.block_length as u64;
+        if self.pending_overruns.count == 0 || (self.samples_since_last_report as f64) < frame_rate
+        {
+            return None;
+        }
+        self.samples_since_last_report = 0;
+        Some(mem::take(&mut self.pending_overruns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reaper_medium::Hz;
+
+    fn block_props() -> AudioBlockProps {
+        // 1000 samples at 48 kHz last about 20.8 ms
+        AudioBlockProps {
+            block_length: 1000,
+            frame_rate: Hz::new(48000.0),
+        }
+    }
+
+    #[test]
+    fn no_overrun() {
+        // Given
+        let mut monitor = RealTimeLoadMonitor::default();
+        // When
+        let reports: Vec<_> = (0..100)
+            .filter_map(|_| monitor.record_block(Duration::from_millis(5), block_props()))
+            .collect();
+        // Then
+        assert!(reports.is_empty());
+    }
+
+    #[test]
+    fn overruns_are_reported_at_most_once_per_second() {
+        // Given
+        let mut monitor = RealTimeLoadMonitor::default();
+        // When
+        let first = monitor.record_block(Duration::from_millis(15), block_props());
+        let second = monitor.record_block(Duration::from_millis(25), block_props());
+        let reports: Vec<_> = (0..48)
+            .filter_map(|_| monitor.record_block(Duration::from_millis(1), block_props()))
+            .collect();
+        // Then
+        assert_eq!(first, None);
+        assert_eq!(second, None);
+        assert_eq!(
+            reports,
+            vec![RealTimeOverruns {
+                count: 2,
+                max_load_percent: 120
+            }]
+        );
+    }
+}
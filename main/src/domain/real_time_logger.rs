@@ -0,0 +1,92 @@
+use crate::base::{NamedChannelSender, SenderToNormalThread};
+use crate::domain::{Compartment, MappingId};
+use derive_more::Display;
+
+/// Logger which is safe to use in real-time threads.
+///
+/// It neither formats nor allocates. It just pushes entries into a bounded lock-free queue, which
+/// is drained and formatted in the main thread. If the queue is full, entries are dropped.
+#[derive(Clone, Debug)]
+pub struct RealTimeLogger {
+    sender: SenderToNormalThread<RealTimeLogEntry>,
+}
+
+impl RealTimeLogger {
+    pub fn new(sender: SenderToNormalThread<RealTimeLogEntry>) -> Self {
+        Self { sender }
+    }
+
+    /// Logs the given message on debug level.
+    ///
+    /// The sample count is logged as well in order to be sure about invocation order (the
+    /// timestamp of the main-thread log output is not accurate enough for that).
+    pub fn debug(&self, sample_count: u64, msg: RealTimeLogMessage) {
+        self.sender
+            .send_if_space(RealTimeLogEntry { sample_count, msg });
+    }
+}
+
+#[derive(Copy, Clone, Debug, Display)]
+#[display(fmt = "{} (at {} samples)", msg, sample_count)]
+pub struct RealTimeLogEntry {
+    pub sample_count: u64,
+    pub msg: RealTimeLogMessage,
+}
+
+/// A message logged in the real-time processor.
+///
+/// Carries only the data to be logged, which makes it cheap to send. Formatting is done in the
+/// main thread.
+#[derive(Copy, Clone, Debug, Display)]
+pub enum RealTimeLogMessage {
+    #[display(
+        fmt = "Successfully requested full sync. Discarded {} normal and {} feedback tasks.",
+        discarded_normal_task_count,
+        discarded_feedback_task_count
+    )]
+    FullSyncRequested {
+        discarded_normal_task_count: usize,
+        discarded_feedback_task_count: usize,
+    },
+    #[display(
+        fmt = "Small audio device outage detected but probably related to project load so no action taken."
+    )]
+    AudioDeviceOutageIgnored,
+    #[display(fmt = "Updating {} mappings in {}...", count, compartment)]
+    UpdatingAllMappings {
+        compartment: Compartment,
+        count: usize,
+    },
+    #[display(fmt = "Updating single mapping {:?} in {}...", mapping_id, compartment)]
+    UpdatingSingleMapping {
+        compartment: Compartment,
+        mapping_id: MappingId,
+    },
+    #[display(
+        fmt = "Updating persistent state of {:?} in {}...",
+        mapping_id,
+        compartment
+    )]
+    UpdatingPersistentMappingProcessingState {
+        compartment: Compartment,
+        mapping_id: MappingId,
+    },
+    #[display(fmt = "Update target activations in {}...", _0)]
+    UpdatingTargetActivations(Compartment),
+    #[display(fmt = "Updating mapping activations in {}...", _0)]
+    UpdatingMappingActivations(Compartment),
+    #[display(fmt = "Updating settings...")]
+    UpdatingSettings,
+    #[display(fmt = "Updating sample rate")]
+    UpdatingSampleRate,
+    #[display(fmt = "Start learning source")]
+    StartLearningSource,
+    #[display(fmt = "Disable control")]
+    DisableControl,
+    #[display(fmt = "Return to control mode")]
+    ReturnToControlMode,
+    #[display(fmt = "Received clip record task")]
+    ClipRecordTaskReceived,
+    #[display(fmt = "Clearing clip record task")]
+    ClipRecordTaskCleared,
+}
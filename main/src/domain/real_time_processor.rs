@@ -1,13 +1,15 @@
 use crate::domain::{
-    classify_midi_message, BasicSettings, Compartment, CompoundMappingSource, ControlEvent,
-    ControlEventTimestamp, ControlLogEntry, ControlLogEntryKind, ControlMainTask, ControlMode,
-    ControlOptions, FeedbackSendBehavior, Garbage, GarbageBin, InstanceId, LifecycleMidiMessage,
-    LifecyclePhase, MappingId, MatchOutcome, MidiClockCalculator, MidiEvent,
+    classify_midi_message, AudioChannels, BasicSettings, Compartment, CompoundMappingSource,
+    ControlEvent, ControlEventTimestamp, ControlLogEntry, ControlLogEntryKind, ControlMainTask,
+    ControlMode, ControlOptions, FeedbackSendBehavior, Garbage, GarbageBin, InstanceId,
+    LifecycleMidiMessage, LifecyclePhase, MappingId, MatchOutcome, MidiClockCalculator,
+    MidiClockGenerator, MidiClockTransportState, MidiEvent, MidiInputLimiter,
     MidiMessageClassification, MidiScanResult, MidiScanner, MidiSendTarget,
     NormalRealTimeToMainThreadTask, OrderedMappingMap, OwnedIncomingMidiMessage,
     PartialControlMatch, PersistentMappingProcessingState, QualifiedMappingId,
-    RealTimeCompoundMappingTarget, RealTimeControlContext, RealTimeMapping, RealTimeReaperTarget,
-    SampleOffset, SendMidiDestination, VirtualSourceValue,
+    RealTimeCompoundMappingTarget, RealTimeControlContext, RealTimeLoadMonitor, RealTimeLogMessage,
+    RealTimeLogger, RealTimeMapping, RealTimeOverruns, RealTimeReaperTarget, SampleOffset,
+    VirtualControlElementSharing, VirtualSourceValue,
 };
 use helgoboss_learn::{ControlValue, MidiSourceValue, ModeControlResult, RawMidiEvent};
 use helgoboss_midi::{
@@ -15,12 +17,14 @@ use helgoboss_midi::{
     ParameterNumberMessage, PollingParameterNumberMessageScanner, RawShortMessage, ShortMessage,
     ShortMessageFactory, ShortMessageType,
 };
+use realearn_api::persistence::AudioInputKind;
 use reaper_high::{MidiOutputDevice, Reaper};
 use reaper_medium::{
-    Hz, MidiInputDeviceId, MidiOutputDeviceId, OnAudioBufferArgs, ProjectRef, SendMidiTime,
+    Bpm, Hz, MidiInputDeviceId, MidiOutputDeviceId, OnAudioBufferArgs, ProjectContext, SendMidiTime,
 };
 use slog::{debug, trace};
 
+use crate::base::metrics_util::measure_time;
 use crate::base::{Global, NamedChannelSender, SenderToNormalThread, SenderToRealTimeThread};
 use assert_no_alloc::permit_alloc;
 use enum_map::{enum_map, EnumMap};
@@ -30,7 +34,7 @@ use playtime_clip_engine::rt::{AudioBuf, BasicAudioRequestProps, WeakMatrix};
 use std::convert::TryInto;
 use std::mem;
 use std::ptr::null_mut;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use vst::api::{EventType, Events, SysExEvent};
 use vst::host::Host;
 use vst::plugin::HostCallback;
@@ -42,6 +46,7 @@ const FEEDBACK_BULK_SIZE: usize = 100;
 pub struct RealTimeProcessor {
     instance_id: InstanceId,
     logger: slog::Logger,
+    real_time_logger: RealTimeLogger,
     // Synced processing settings
     settings: BasicSettings,
     control_mode: ControlMode,
@@ -63,6 +68,24 @@ pub struct RealTimeProcessor {
     midi_scanner: MidiScanner,
     // For MIDI timing clock calculations
     midi_clock_calculator: MidiClockCalculator,
+    // For sending MIDI clock to the feedback output
+    midi_clock_generator: MidiClockGenerator,
+    // The project which contains this instance (current project if on the monitoring FX chain)
+    project_context: ProjectContext,
+    // For detecting audio blocks which take too long to process. One per processing path because
+    // FX input and audio hook are processed in different cycles.
+    audio_hook_load_monitor: RealTimeLoadMonitor,
+    fx_input_load_monitor: RealTimeLoadMonitor,
+    // REAPER calls us once per incoming FX input MIDI message and once for the audio, so we need
+    // to sum up the processing time of the current FX input block.
+    fx_input_block_processing_time: Duration,
+    // For protecting against controllers which flood the input. One per input path because
+    // FX input and hardware input are processed in different cycles.
+    fx_input_midi_limiter: MidiInputLimiter,
+    hardware_input_midi_limiter: MidiInputLimiter,
+    // REAPER passes the MIDI events of a block to the plug-in *before* processing the block, so
+    // the next FX input block starts with whatever comes first after processing the current one.
+    fx_input_block_start_is_due: bool,
     sample_rate: Hz,
     clip_matrix: Option<WeakMatrix>,
     clip_matrix_is_owned: bool,
@@ -80,6 +103,7 @@ impl RealTimeProcessor {
     pub fn new(
         instance_id: InstanceId,
         parent_logger: &slog::Logger,
+        real_time_logger: RealTimeLogger,
         normal_task_receiver: crossbeam_channel::Receiver<NormalRealTimeTask>,
         feedback_task_receiver: crossbeam_channel::Receiver<FeedbackRealTimeTask>,
         feedback_task_sender: SenderToRealTimeThread<FeedbackRealTimeTask>,
@@ -91,6 +115,7 @@ impl RealTimeProcessor {
         RealTimeProcessor {
             instance_id,
             logger: parent_logger.new(slog::o!("struct" => "RealTimeProcessor")),
+            real_time_logger,
             settings: Default::default(),
             control_mode: ControlMode::Controlling,
             normal_task_receiver,
@@ -106,6 +131,14 @@ impl RealTimeProcessor {
             cc_14_bit_scanner: Default::default(),
             midi_scanner: Default::default(),
             midi_clock_calculator: Default::default(),
+            midi_clock_generator: Default::default(),
+            project_context: ProjectContext::CurrentProject,
+            audio_hook_load_monitor: Default::default(),
+            fx_input_load_monitor: Default::default(),
+            fx_input_block_processing_time: Duration::ZERO,
+            fx_input_midi_limiter: Default::default(),
+            hardware_input_midi_limiter: Default::default(),
+            fx_input_block_start_is_due: true,
             control_is_globally_enabled: false,
             feedback_is_globally_enabled: false,
             garbage_bin,
@@ -122,6 +155,18 @@ impl RealTimeProcessor {
         is_transport_start: bool,
         host: &HostCallback,
     ) {
+        let start = Instant::now();
+        self.process_incoming_midi_from_vst_internal(event, is_transport_start, host);
+        self.fx_input_block_processing_time += start.elapsed();
+    }
+
+    fn process_incoming_midi_from_vst_internal(
+        &mut self,
+        event: ControlEvent<MidiEvent<IncomingMidiMessage>>,
+        is_transport_start: bool,
+        host: &HostCallback,
+    ) {
+        self.start_fx_input_block_if_due(host);
         if self.settings.midi_control_input() == MidiControlInput::FxInput {
             // TODO-medium Maybe also filter when transport stopping
             if is_transport_start
@@ -140,7 +185,7 @@ impl RealTimeProcessor {
             self.process_incoming_midi(event, Caller::Vst(host));
         } else {
             // #33, #290 If MIDI input device is not set to <FX input>, we want to pass through all
-            // messages that arrive on FX input.
+            // messages that arrive on FX input (unless the pass-through filter says otherwise).
             self.send_incoming_midi_to_fx_output(event.payload(), Caller::Vst(host))
         }
     }
@@ -151,8 +196,38 @@ impl RealTimeProcessor {
         block_props: AudioBlockProps,
         host: &HostCallback,
     ) {
-        self.process_clip_record_task(buffer.split().0, block_props);
-        self.process_feedback_tasks(Caller::Vst(host));
+        let start = Instant::now();
+        measure_time("real_time_processor.run_from_vst", || {
+            let inputs = buffer.split().0;
+            self.start_fx_input_block_if_due(host);
+            self.process_clip_record_task(inputs, block_props);
+            self.control_audio(
+                AudioInputKind::FxInput,
+                &inputs,
+                block_props,
+                ControlEventTimestamp::now(),
+                Caller::Vst(host),
+            );
+            self.process_feedback_tasks(Caller::Vst(host));
+            self.send_midi_clock(block_props, Caller::Vst(host));
+            self.report_midi_input_drops(block_props, Caller::Vst(host));
+        });
+        // Includes the time needed for processing the incoming MIDI messages of this block
+        let processing_time = self.fx_input_block_processing_time + start.elapsed();
+        let overruns = self
+            .fx_input_load_monitor
+            .record_block(processing_time, block_props);
+        self.report_overruns(overruns);
+        self.fx_input_block_start_is_due = true;
+    }
+
+    fn start_fx_input_block_if_due(&mut self, host: &HostCallback) {
+        if !self.fx_input_block_start_is_due {
+            return;
+        }
+        self.fx_input_block_start_is_due = false;
+        self.fx_input_block_processing_time = Duration::ZERO;
+        self.process_postponed_midi(Caller::Vst(host));
     }
 
     /// This should be regularly called by audio hook in normal mode.
@@ -161,9 +236,66 @@ impl RealTimeProcessor {
         block_props: AudioBlockProps,
         might_be_rebirth: bool,
         timestamp: ControlEventTimestamp,
+        hardware_inputs: &impl AudioChannels,
     ) {
-        self.run_from_audio_hook_essential(block_props, might_be_rebirth);
-        self.run_from_audio_hook_control_and_learn(timestamp);
+        let start = Instant::now();
+        measure_time("real_time_processor.run_from_audio_hook", || {
+            self.run_from_audio_hook_essential(block_props, might_be_rebirth);
+            self.run_from_audio_hook_control_and_learn(timestamp);
+            // The audio hook distributes the MIDI events of this block after this call.
+            self.process_postponed_midi(Caller::AudioHook);
+            self.report_midi_input_drops(block_props, Caller::AudioHook);
+            self.control_audio(
+                AudioInputKind::HardwareInput,
+                hardware_inputs,
+                block_props,
+                timestamp,
+                Caller::AudioHook,
+            );
+        });
+        let overruns = self
+            .audio_hook_load_monitor
+            .record_block(start.elapsed(), block_props);
+        self.report_overruns(overruns);
+    }
+
+    fn report_overruns(&self, overruns: Option<RealTimeOverruns>) {
+        if let Some(overruns) = overruns {
+            // If the channel is full, the overruns are simply not reported. Not critical.
+            self.normal_main_task_sender
+                .try_to_send(NormalRealTimeToMainThreadTask::ReportOverruns(overruns));
+        }
+    }
+
+    /// Starts a new block for the MIDI input path of the given caller and processes the messages
+    /// which exceeded the MIDI input limit in the previous block of that path.
+    ///
+    /// Must be called exactly once per block of the input path, before processing its incoming
+    /// messages.
+    fn process_postponed_midi(&mut self, caller: Caller) {
+        let postponed_messages = self.midi_input_limiter(caller).start_block();
+        for msg in postponed_messages.messages() {
+            let event = MidiEvent::new(SampleOffset::ZERO, IncomingMidiMessage::Short(msg));
+            self.process_incoming_midi_normal(
+                ControlEvent::new(event, ControlEventTimestamp::now()),
+                caller,
+            );
+        }
+    }
+
+    fn report_midi_input_drops(&mut self, block_props: AudioBlockProps, caller: Caller) {
+        if let Some(drops) = self.midi_input_limiter(caller).poll_drops(block_props) {
+            // If the channel is full, the drops are simply not reported. Not critical.
+            self.normal_main_task_sender
+                .try_to_send(NormalRealTimeToMainThreadTask::ReportMidiInputDrops(drops));
+        }
+    }
+
+    fn midi_input_limiter(&mut self, caller: Caller) -> &mut MidiInputLimiter {
+        match caller {
+            Caller::Vst(_) => &mut self.fx_input_midi_limiter,
+            Caller::AudioHook => &mut self.hardware_input_midi_limiter,
+        }
     }
 
     pub fn midi_control_input(&self) -> MidiControlInput {
@@ -205,21 +337,12 @@ impl RealTimeProcessor {
                 .try_iter()
                 .map(|t| self.garbage_bin.dispose(Garbage::FeedbackRealTimeTask(t)))
                 .count();
-            permit_alloc(|| {
-                debug!(
-                    self.logger,
-                    "Successfully requested full sync. Discarded {} normal and {} feedback tasks.",
-                    discarded_normal_task_count,
-                    discarded_feedback_task_count
-                );
+            self.log_debug(RealTimeLogMessage::FullSyncRequested {
+                discarded_normal_task_count,
+                discarded_feedback_task_count,
             });
         } else {
-            permit_alloc(|| {
-                debug!(
-                    self.logger,
-                    "Small audio device outage detected but probably related to project load so no action taken.",
-                );
-            });
+            self.log_debug(RealTimeLogMessage::AudioDeviceOutageIgnored);
         }
     }
 
@@ -265,13 +388,9 @@ impl RealTimeProcessor {
                     self.feedback_is_globally_enabled = is_enabled;
                 }
                 UpdateAllMappings(compartment, mut mappings) => {
-                    permit_alloc(|| {
-                        debug!(
-                            self.logger,
-                            "Updating {} mappings in {}...",
-                            mappings.len(),
-                            compartment
-                        );
+                    self.log_debug(RealTimeLogMessage::UpdatingAllMappings {
+                        compartment,
+                        count: mappings.len(),
                     });
                     // Handle deactivation MIDI
                     if self.processor_feedback_is_effectively_on() {
@@ -302,13 +421,9 @@ impl RealTimeProcessor {
                         .expect("must send a mapping when updating single mapping");
                     self.garbage_bin
                         .dispose(Garbage::BoxedRealTimeMapping(mapping));
-                    permit_alloc(|| {
-                        debug!(
-                            self.logger,
-                            "Updating single mapping {:?} in {}...",
-                            m.id(),
-                            compartment,
-                        );
+                    self.log_debug(RealTimeLogMessage::UpdatingSingleMapping {
+                        compartment,
+                        mapping_id: m.id(),
                     });
                     // Send lifecycle MIDI
                     if self.processor_feedback_is_effectively_on() {
@@ -325,12 +440,12 @@ impl RealTimeProcessor {
                     }
                 }
                 UpdatePersistentMappingProcessingState { id, state } => {
-                    permit_alloc(|| {
-                        debug!(
-                            self.logger,
-                            "Updating persistent state of {:?} in {}...", id.id, id.compartment
-                        );
-                    });
+                    self.log_debug(
+                        RealTimeLogMessage::UpdatingPersistentMappingProcessingState {
+                            compartment: id.compartment,
+                            mapping_id: id.id,
+                        },
+                    );
                     // Update
                     let (was_on_before, is_on_now) =
                         if let Some(m) = self.mappings[id.compartment].get_mut(&id.id) {
@@ -348,18 +463,7 @@ impl RealTimeProcessor {
                     }
                 }
                 UpdateTargetsPartially(compartment, mut target_updates) => {
-                    // Also log sample count in order to be sure about invocation order
-                    // (timestamp is not accurate enough on e.g. selection changes).
-                    // TODO-low We should use an own logger and always log the sample count
-                    //  automatically.
-                    permit_alloc(|| {
-                        debug!(
-                            self.logger,
-                            "Update target activations in {} at {} samples...",
-                            compartment,
-                            self.midi_clock_calculator.current_sample_count()
-                        );
-                    });
+                    self.log_debug(RealTimeLogMessage::UpdatingTargetActivations(compartment));
                     // Apply updates
                     for update in target_updates.iter_mut() {
                         if let Some(m) = self.mappings[compartment].get_mut(&update.id) {
@@ -385,9 +489,7 @@ impl RealTimeProcessor {
                         .dispose(Garbage::TargetUpdates(target_updates));
                 }
                 UpdateSettings(settings) => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Updating settings...");
-                    });
+                    self.log_debug(RealTimeLogMessage::UpdatingSettings);
                     let prev_midi_destination = self.settings.midi_destination();
                     let next_midi_destination = settings.midi_destination();
                     self.settings = settings;
@@ -401,19 +503,18 @@ impl RealTimeProcessor {
                         self.send_lifecycle_midi_for_all_mappings(LifecyclePhase::Activation);
                     }
                 }
+                UpdateProject(project_context) => {
+                    self.project_context = project_context;
+                }
                 UpdateSampleRate(sample_rate) => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Updating sample rate");
-                    });
+                    self.log_debug(RealTimeLogMessage::UpdatingSampleRate);
                     self.sample_rate = sample_rate;
                     self.midi_clock_calculator.update_sample_rate(sample_rate);
                 }
                 StartLearnSource {
                     allow_virtual_sources,
                 } => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Start learning source");
-                    });
+                    self.log_debug(RealTimeLogMessage::StartLearningSource);
                     self.control_mode = ControlMode::LearningSource {
                         allow_virtual_sources,
                         osc_arg_index_hint: None,
@@ -421,15 +522,11 @@ impl RealTimeProcessor {
                     self.midi_scanner.reset();
                 }
                 DisableControl => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Disable control");
-                    });
+                    self.log_debug(RealTimeLogMessage::DisableControl);
                     self.control_mode = ControlMode::Disabled;
                 }
                 ReturnToControlMode => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Return to control mode");
-                    });
+                    self.log_debug(RealTimeLogMessage::ReturnToControlMode);
                     self.control_mode = ControlMode::Controlling;
                     self.nrpn_scanner.reset();
                     self.cc_14_bit_scanner.reset();
@@ -441,9 +538,7 @@ impl RealTimeProcessor {
                     self.log_mapping(compartment, mapping_id);
                 }
                 UpdateMappingsPartially(compartment, mapping_updates) => {
-                    permit_alloc(|| {
-                        debug!(self.logger, "Updating mapping activations...");
-                    });
+                    self.log_debug(RealTimeLogMessage::UpdatingMappingActivations(compartment));
                     // Apply updates
                     for update in mapping_updates.iter() {
                         if let Some(m) = self.mappings[compartment].get_mut(&update.id) {
@@ -475,11 +570,65 @@ impl RealTimeProcessor {
                     }
                 }
                 StartClipRecording(task) => {
-                    tracing_debug!("Real-time processor received clip record task");
+                    self.log_debug(RealTimeLogMessage::ClipRecordTaskReceived);
                     self.clip_record_task = Some(task);
                 }
             }
         }
+        self.send_midi_clock(block_props, Caller::AudioHook);
+    }
+
+    /// Sends MIDI clock messages for the current block to the feedback output, if enabled.
+    fn send_midi_clock(&mut self, block_props: AudioBlockProps, caller: Caller) {
+        let destination = match self.settings.midi_destination() {
+            Some(d) if self.settings.send_midi_clock => d,
+            _ => {
+                // Make sure that we start fresh (with a start message) when enabled again.
+                self.midi_clock_generator.reset();
+                return;
+            }
+        };
+        let (tempo, transport_state) =
+            tempo_and_midi_clock_transport_state(self.project_context, caller);
+        let sample_rate = block_props.frame_rate;
+        match destination {
+            MidiDestination::FxOutput => {
+                // Forwarding to FX output is only possible from the VST process method.
+                if !caller.is_vst() {
+                    return;
+                }
+                let mut generator = mem::take(&mut self.midi_clock_generator);
+                generator.process_block(
+                    block_props.block_length,
+                    sample_rate,
+                    tempo,
+                    transport_state,
+                    |evt| self.send_short_midi_to_fx_output(evt, caller),
+                );
+                self.midi_clock_generator = generator;
+            }
+            MidiDestination::Device(dev_id) => {
+                // Sending to a device is done from the audio hook only (for proper ordering).
+                if caller.is_vst() {
+                    return;
+                }
+                let generator = &mut self.midi_clock_generator;
+                MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
+                    if let Some(mo) = mo {
+                        generator.process_block(
+                            block_props.block_length,
+                            sample_rate,
+                            tempo,
+                            transport_state,
+                            |evt| {
+                                let frame_offset = evt.offset().to_frame_offset(sample_rate);
+                                mo.send(evt.payload(), SendMidiTime::AtFrameOffset(frame_offset));
+                            },
+                        );
+                    }
+                });
+            }
+        }
     }
 
     fn process_clip_record_task(
@@ -489,8 +638,8 @@ impl RealTimeProcessor {
     ) {
         if let Some(t) = &mut self.clip_record_task {
             if !process_clip_record_task(t, inputs, block_props.to_playtime()) {
-                tracing_debug!("Clearing clip record task from real-time processor");
                 self.clip_record_task = None;
+                self.log_debug(RealTimeLogMessage::ClipRecordTaskCleared);
             }
         }
     }
@@ -594,6 +743,11 @@ impl RealTimeProcessor {
         }
     }
 
+    fn log_debug(&self, msg: RealTimeLogMessage) {
+        self.real_time_logger
+            .debug(self.midi_clock_calculator.current_sample_count(), msg);
+    }
+
     fn log_debug_info(&self, task_count: usize) {
         // Summary
         permit_alloc(|| {
@@ -670,7 +824,16 @@ impl RealTimeProcessor {
     ) -> MatchOutcome {
         use MidiMessageClassification::*;
         match classify_midi_message(event.payload().payload()) {
-            Normal => self.process_incoming_midi_normal(event, caller),
+            Normal => {
+                if !self
+                    .midi_input_limiter(caller)
+                    .admit(event.payload().payload())
+                {
+                    // Flood protection kicked in. The message is either postponed or dropped.
+                    return MatchOutcome::Consumed;
+                }
+                self.process_incoming_midi_normal(event, caller)
+            }
             Ignored => {
                 // ReaLearn doesn't process those. Forward them if user wants it.
                 self.process_unmatched(event.payload(), caller);
@@ -816,6 +979,7 @@ impl RealTimeProcessor {
                 .to_short_messages::<RawShortMessage>(DataEntryByteOrder::MsbFirst)
                 .iter()
                 .flatten()
+                .filter(|m| self.settings.midi_pass_through_filter.allows_short(*m))
             {
                 self.send_short_midi_to_fx_output(
                     MidiEvent::new(event.payload().offset(), *m),
@@ -914,6 +1078,7 @@ impl RealTimeProcessor {
                 .payload()
                 .to_short_messages::<RawShortMessage>()
                 .iter()
+                .filter(|m| self.settings.midi_pass_through_filter.allows_short(*m))
             {
                 let short_event = MidiEvent::new(midi_event.offset(), *m);
                 self.send_short_midi_to_fx_output(short_event, caller);
@@ -981,6 +1146,8 @@ impl RealTimeProcessor {
                 LogOptions::from_basic_settings(&self.settings),
                 self.clip_matrix.as_ref(),
                 is_rendering,
+                self.settings.virtual_control_element_sharing
+                    == VirtualControlElementSharing::Publish,
             )
         } else {
             unreachable!()
@@ -989,6 +1156,56 @@ impl RealTimeProcessor {
         controller_outcome.merge_with(main_outcome)
     }
 
+    /// Feeds the given audio block into all main mappings with an audio source of the given input
+    /// kind.
+    fn control_audio(
+        &mut self,
+        input: AudioInputKind,
+        channels: &impl AudioChannels,
+        block_props: AudioBlockProps,
+        timestamp: ControlEventTimestamp,
+        caller: Caller,
+    ) {
+        if !matches!(self.control_mode, ControlMode::Controlling)
+            || !self.control_is_globally_enabled
+        {
+            return;
+        }
+        let is_rendering = is_rendering();
+        let compartment = Compartment::Main;
+        for m in self.mappings[compartment]
+            .values_mut()
+            .filter(|m| m.control_is_effectively_on() && m.has_reaper_target())
+        {
+            let control_value = match &mut m.core.source {
+                CompoundMappingSource::Audio(s) if s.input() == input => {
+                    let samples = match channels.channel(s.channel()) {
+                        None => continue,
+                        Some(samples) => samples,
+                    };
+                    match s.process_block(samples, block_props.frame_rate.get()) {
+                        None => continue,
+                        Some(v) => v,
+                    }
+                }
+                _ => continue,
+            };
+            process_real_mapping(
+                m,
+                &self.control_main_task_sender,
+                &self.feedback_task_sender,
+                compartment,
+                ControlEvent::new(MidiEvent::new(SampleOffset::ZERO, control_value), timestamp),
+                Default::default(),
+                caller,
+                self.settings.midi_destination(),
+                LogOptions::from_basic_settings(&self.settings),
+                self.clip_matrix.as_ref(),
+                is_rendering,
+            );
+        }
+    }
+
     fn control_main_mappings_midi(
         &mut self,
         source_value_event: ControlEvent<MidiEvent<&MidiSourceValue<RawShortMessage>>>,
@@ -1003,29 +1220,27 @@ impl RealTimeProcessor {
             // doesn't. Check again that it's a REAPER target.
             .filter(|m| m.control_is_effectively_on() && m.has_reaper_target())
         {
-            if let CompoundMappingSource::Midi(s) = &m.source() {
-                let midi_event = source_value_event.payload();
-                if let Some(control_value) = s.control(midi_event.payload()) {
-                    process_real_mapping(
-                        m,
-                        &self.control_main_task_sender,
-                        &self.feedback_task_sender,
-                        compartment,
-                        source_value_event
-                            .with_payload(MidiEvent::new(midi_event.offset(), control_value)),
-                        ControlOptions {
-                            enforce_target_refresh: match_outcome.matched(),
-                            ..Default::default()
-                        },
-                        caller,
-                        self.settings.midi_destination(),
-                        LogOptions::from_basic_settings(&self.settings),
-                        self.clip_matrix.as_ref(),
-                        is_rendering,
-                    );
-                    // It can't be consumed because we checked this before for all mappings.
-                    match_outcome = MatchOutcome::Matched;
-                }
+            let midi_event = source_value_event.payload();
+            if let Some(control_value) = m.source().control_midi(midi_event.payload()) {
+                process_real_mapping(
+                    m,
+                    &self.control_main_task_sender,
+                    &self.feedback_task_sender,
+                    compartment,
+                    source_value_event
+                        .with_payload(MidiEvent::new(midi_event.offset(), control_value)),
+                    ControlOptions {
+                        enforce_target_refresh: match_outcome.matched(),
+                        ..Default::default()
+                    },
+                    caller,
+                    self.settings.midi_destination(),
+                    LogOptions::from_basic_settings(&self.settings),
+                    self.clip_matrix.as_ref(),
+                    is_rendering,
+                );
+                // It can't be consumed because we checked this before for all mappings.
+                match_outcome = MatchOutcome::Matched;
             }
         }
         match_outcome
@@ -1159,11 +1374,19 @@ impl RealTimeProcessor {
         }
     }
 
+    /// Forwards the given message to the FX output if the pass-through filter allows it.
     fn send_incoming_midi_to_fx_output(
         &self,
         event: MidiEvent<IncomingMidiMessage>,
         caller: Caller,
     ) {
+        if !self
+            .settings
+            .midi_pass_through_filter
+            .allows(event.payload())
+        {
+            return;
+        }
         match event.payload() {
             IncomingMidiMessage::Short(s) => {
                 self.send_short_midi_to_fx_output(MidiEvent::new(event.offset(), s), caller);
@@ -1269,6 +1492,8 @@ pub enum NormalRealTimeTask {
     UpdateMappingsPartially(Compartment, Vec<RealTimeMappingUpdate>),
     LogDebugInfo,
     LogMapping(Compartment, MappingId),
+    /// The project which contains the instance. Only known after the session has been created.
+    UpdateProject(ProjectContext),
     UpdateSampleRate(Hz),
     StartLearnSource {
         allow_virtual_sources: bool,
@@ -1387,6 +1612,7 @@ fn control_controller_mappings_midi(
     log_options: LogOptions,
     matrix: Option<&WeakMatrix>,
     is_rendering: bool,
+    publish_virtual_control_input: bool,
 ) -> MatchOutcome {
     let mut match_outcome = MatchOutcome::Unmatched;
     let mut enforce_target_refresh = false;
@@ -1400,6 +1626,19 @@ fn control_controller_mappings_midi(
             use PartialControlMatch::*;
             let child_match_outcome = match control_match {
                 ProcessVirtual(virtual_source_value) => {
+                    // Projection clients want to know about touched control elements.
+                    main_task_sender.send_if_space(ControlMainTask::NotifyControlElementTouched {
+                        mapping_id: m.id(),
+                        value: virtual_source_value,
+                    });
+                    if publish_virtual_control_input {
+                        // Other instances are reached via main thread only.
+                        main_task_sender.send_if_space(
+                            ControlMainTask::PublishVirtualControlInput {
+                                event: value_event.with_payload(virtual_source_value),
+                            },
+                        );
+                    }
                     let virtual_match_outcome = control_main_mappings_virtual(
                         main_task_sender,
                         rt_feedback_sender,
@@ -1490,7 +1729,20 @@ fn process_real_mapping(
         if reaper_target.wants_real_time_control(caller, is_rendering) {
             // Try to process directly here in real-time.
             mapping.core.increase_invocation_count();
+            // The mapping statistics are kept in the main thread.
+            main_task_sender.send_if_space(ControlMainTask::NotifyRealTimeMappingMatched {
+                mapping_id: QualifiedMappingId::new(compartment, mapping_id),
+            });
             let control_context = RealTimeControlContext { clip_matrix };
+            let pure_control_event = match mapping.core.process_pitch_bend(pure_control_event) {
+                None => return,
+                Some(evt) => evt,
+            };
+            let pure_control_event = match mapping.core.debounce(pure_control_event) {
+                None => return,
+                Some(evt) => evt,
+            };
+            let pure_control_event = mapping.core.accelerate(pure_control_event);
             let mode_control_result = mapping.core.mode.control_with_options(
                 pure_control_event,
                 reaper_target,
@@ -1596,21 +1848,8 @@ fn real_time_target_send_midi(
     // send a MIDI message and this needs to happen in the audio thread.
     // Going to the main thread and back would be such a waste!
     let raw_midi_event = t.pattern().to_concrete_midi_event(v);
-    let midi_destination = match caller {
-        Caller::Vst(_) => match t.destination() {
-            SendMidiDestination::FxOutput => Some(MidiDestination::FxOutput),
-            SendMidiDestination::FeedbackOutput => {
-                Some(midi_feedback_output.ok_or("no feedback output set")?)
-            }
-        },
-        Caller::AudioHook => match t.destination() {
-            SendMidiDestination::FxOutput => Some(MidiDestination::FxOutput),
-            SendMidiDestination::FeedbackOutput => {
-                Some(midi_feedback_output.ok_or("no feedback output set")?)
-            }
-        },
-    };
-    if log_options.output_logging_enabled && midi_destination.is_some() {
+    let midi_destination = t.resolve_destination(midi_feedback_output)?;
+    if log_options.output_logging_enabled {
         permit_alloc(|| {
             main_task_sender.send_complaining(ControlMainTask::LogTargetOutput {
                 event: Box::new(raw_midi_event),
@@ -1618,7 +1857,7 @@ fn real_time_target_send_midi(
         });
     }
     let successful = match midi_destination {
-        Some(MidiDestination::FxOutput) => {
+        MidiDestination::FxOutput => {
             match caller {
                 Caller::Vst(_) => {
                     send_raw_midi_to_fx_output(
@@ -1637,17 +1876,14 @@ fn real_time_target_send_midi(
             }
             true
         }
-        Some(MidiDestination::Device(dev_id)) => {
-            MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
-                if let Some(mo) = mo {
-                    mo.send_msg(raw_midi_event, SendMidiTime::Instantly);
-                    true
-                } else {
-                    false
-                }
-            })
-        }
-        _ => false,
+        MidiDestination::Device(dev_id) => MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
+            if let Some(mo) = mo {
+                mo.send_msg(raw_midi_event, SendMidiTime::Instantly);
+                true
+            } else {
+                false
+            }
+        }),
     };
     if successful {
         t.set_artificial_value(v);
@@ -1950,9 +2186,145 @@ impl LogOptions {
     }
 }
 
+/// Returns the tempo and transport state of the given project.
+///
+/// When called from the VST process method, tempo and play state are taken from the host's time
+/// info, which is sample-accurate and refers to the project in which the FX is processed.
+fn tempo_and_midi_clock_transport_state(
+    project: ProjectContext,
+    caller: Caller,
+) -> (Bpm, MidiClockTransportState) {
+    let reaper = Reaper::get().medium_reaper();
+    let play_state = reaper.get_play_state_ex(project);
+    let host_time_info = match caller {
+        Caller::Vst(host) => host_tempo_and_play_state(host),
+        Caller::AudioHook => None,
+    };
+    let (tempo, is_playing) = host_time_info.unwrap_or_else(|| {
+        let tempo = reaper
+            .time_map_2_get_divided_bpm_at_time(project, reaper.get_play_position_2_ex(project));
+        (tempo, play_state.is_playing)
+    });
+    // VST time info doesn't distinguish between paused and stopped
+    let transport_state = if is_playing {
+        MidiClockTransportState::Playing
+    } else if play_state.is_paused {
+        MidiClockTransportState::Paused
+    } else {
+        MidiClockTransportState::Stopped
+    };
+    (tempo, transport_state)
+}
+
+/// Returns `None` if the host doesn't provide a valid tempo.
+fn host_tempo_and_play_state(host: &HostCallback) -> Option<(Bpm, bool)> {
+    use vst::api::TimeInfoFlags;
+    let time_info = host.get_time_info(TimeInfoFlags::TEMPO_VALID.bits())?;
+    let flags = TimeInfoFlags::from_bits_truncate(time_info.flags);
+    if !flags.contains(TimeInfoFlags::TEMPO_VALID)
+        || !(Bpm::MIN.get()..=Bpm::MAX.get()).contains(&time_info.tempo)
+    {
+        return None;
+    }
+    let is_playing = flags.contains(TimeInfoFlags::TRANSPORT_PLAYING);
+    Some((Bpm::new(time_info.tempo), is_playing))
+}
+
+impl AudioChannels for vst::buffer::Inputs<'_, f64> {
+    fn channel(&self, index: u32) -> Option<&[f64]> {
+        let index = index as usize;
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.get(index))
+    }
+}
+
+#[cfg(not(test))]
 fn is_rendering() -> bool {
     Reaper::get()
         .medium_reaper()
-        .enum_projects(ProjectRef::CurrentlyRendering, 0)
+        .enum_projects(reaper_medium::ProjectRef::CurrentlyRendering, 0)
         .is_some()
 }
+
+/// REAPER is not available in unit tests, so the rendering state is simulated.
+#[cfg(test)]
+fn is_rendering() -> bool {
+    crate::domain::simulated_is_rendering()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{MidiSource, RealTimeProcessorTestHarness};
+    use helgoboss_learn::SourceCharacter;
+    use helgoboss_midi::{ControllerNumber, U7};
+
+    #[test]
+    fn forwards_matched_control_to_main_processor() {
+        // Given
+        let mut harness = RealTimeProcessorTestHarness::default();
+        let mapping_id = harness.add_main_mapping(cc_source(7));
+        // When
+        harness.send_midi_to_fx_input(cc(7, 64));
+        // Then
+        let tasks = harness.take_control_main_tasks();
+        assert_eq!(tasks.len(), 1);
+        assert!(matches!(
+            tasks[0],
+            ControlMainTask::Control { mapping_id: id, .. } if id == mapping_id
+        ));
+        assert!(harness.take_forwarded_midi().is_empty());
+    }
+
+    #[test]
+    fn lets_events_through_according_to_settings() {
+        // Given
+        let mut harness = RealTimeProcessorTestHarness::default();
+        harness.add_main_mapping(cc_source(7));
+        harness.update_settings(BasicSettings {
+            let_unmatched_events_through: true,
+            ..Default::default()
+        });
+        // When
+        harness.send_midi_to_fx_input(cc(7, 64));
+        harness.send_midi_to_fx_input(cc(8, 64));
+        // Then
+        let forwarded: Vec<_> = harness
+            .take_forwarded_midi()
+            .into_iter()
+            .map(|e| e.bytes)
+            .collect();
+        assert_eq!(forwarded, vec![vec![0xB0, 8, 64]]);
+        assert_eq!(harness.take_control_main_tasks().len(), 1);
+    }
+
+    #[test]
+    fn doesnt_forward_control_while_rendering() {
+        // Given
+        let mut harness = RealTimeProcessorTestHarness::default();
+        harness.add_main_mapping(cc_source(7));
+        harness.simulate_rendering(true);
+        // When
+        harness.send_midi_to_fx_input(cc(7, 64));
+        // Then
+        assert!(harness.take_control_main_tasks().is_empty());
+    }
+
+    fn cc_source(controller_number: u8) -> MidiSource {
+        MidiSource::ControlChangeValue {
+            channel: Some(Channel::new(0)),
+            controller_number: Some(ControllerNumber::new(controller_number)),
+            custom_character: SourceCharacter::RangeElement,
+        }
+    }
+
+    fn cc(controller_number: u8, value: u8) -> RawShortMessage {
+        RawShortMessage::control_change(
+            Channel::new(0),
+            ControllerNumber::new(controller_number),
+            U7::new(value),
+        )
+    }
+}
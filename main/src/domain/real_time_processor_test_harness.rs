@@ -0,0 +1,263 @@
+use crate::base::{SenderToNormalThread, SenderToRealTimeThread};
+use crate::domain::{
+    ActivationChange, ActivationCondition, AudioBlockProps, BasicSettings, Compartment,
+    CompoundMappingSource, ControlEvent, ControlEventTimestamp, ControlMainTask, Garbage,
+    GarbageBin, GroupId, IncomingMidiMessage, InstanceId, MainMapping, MappingId, MappingKey,
+    MidiEvent, MidiSource, Mode, NormalRealTimeTask, NormalRealTimeToMainThreadTask,
+    PersistentMappingProcessingState, ProcessorMappingOptions, RealTimeLogEntry, RealTimeLogger,
+    RealTimeMappingUpdate, RealTimeProcessor, UnresolvedCompoundMappingTarget,
+    UnresolvedDummyTarget, UnresolvedReaperTarget,
+};
+use helgoboss_learn::AbstractTimestamp;
+use helgoboss_midi::RawShortMessage;
+use reaper_medium::Hz;
+use std::cell::{Cell, RefCell};
+use std::ffi::c_void;
+use vst::api::{AEffect, EventType, Events, SysExEvent};
+use vst::host::OpCode;
+use vst::plugin::HostCallback;
+
+thread_local! {
+    static FORWARDED_MIDI: RefCell<Vec<ForwardedMidiEvent>> = RefCell::new(vec![]);
+    static SIMULATED_RENDERING: Cell<bool> = Cell::new(false);
+}
+
+/// A MIDI event which the real-time processor sent to the (simulated) FX output.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ForwardedMidiEvent {
+    pub frame_offset: i32,
+    pub bytes: Vec<u8>,
+}
+
+/// Runs a [`RealTimeProcessor`] without REAPER.
+///
+/// The VST host is simulated by a fake host callback which records all MIDI events sent to the
+/// FX output. All channels to and from the main thread are owned by the harness, so tests can
+/// feed scripted MIDI input and assert on the forwarded messages and on the tasks sent to the
+/// main processor.
+///
+/// Only works for mappings whose targets are not processed in real-time (because those might
+/// need REAPER).
+pub struct RealTimeProcessorTestHarness {
+    processor: RealTimeProcessor,
+    host: HostCallback,
+    normal_task_sender: crossbeam_channel::Sender<NormalRealTimeTask>,
+    normal_main_task_receiver: crossbeam_channel::Receiver<NormalRealTimeToMainThreadTask>,
+    control_main_task_receiver: crossbeam_channel::Receiver<ControlMainTask>,
+    garbage_receiver: crossbeam_channel::Receiver<Garbage>,
+    log_receiver: crossbeam_channel::Receiver<RealTimeLogEntry>,
+}
+
+impl Default for RealTimeProcessorTestHarness {
+    fn default() -> Self {
+        let (normal_task_sender, normal_task_receiver) = crossbeam_channel::unbounded();
+        let (feedback_task_sender, feedback_task_receiver) =
+            SenderToRealTimeThread::new_channel("test feedback real-time tasks", 1000);
+        let (normal_main_task_sender, normal_main_task_receiver) =
+            SenderToNormalThread::new_unbounded_channel("test normal main tasks");
+        let (control_main_task_sender, control_main_task_receiver) =
+            SenderToNormalThread::new_unbounded_channel("test control main tasks");
+        let (garbage_sender, garbage_receiver) =
+            SenderToNormalThread::new_bounded_channel("test garbage", 1000);
+        let (log_sender, log_receiver) =
+            SenderToNormalThread::new_bounded_channel("test real-time logs", 1000);
+        let logger = slog::Logger::root(slog::Discard, slog::o!());
+        let processor = RealTimeProcessor::new(
+            InstanceId::random(),
+            &logger,
+            RealTimeLogger::new(log_sender),
+            normal_task_receiver,
+            feedback_task_receiver,
+            feedback_task_sender,
+            normal_main_task_sender,
+            control_main_task_sender,
+            GarbageBin::new(garbage_sender),
+        );
+        let mut harness = Self {
+            processor,
+            host: HostCallback::wrap(fake_host_callback, std::ptr::null_mut()),
+            normal_task_sender,
+            normal_main_task_receiver,
+            control_main_task_receiver,
+            garbage_receiver,
+            log_receiver,
+        };
+        harness.send_task(NormalRealTimeTask::UpdateControlIsGloballyEnabled(true));
+        harness
+    }
+}
+
+impl RealTimeProcessorTestHarness {
+    /// Sends the given task to the processor and lets it process it immediately.
+    pub fn send_task(&mut self, task: NormalRealTimeTask) {
+        self.normal_task_sender.send(task).unwrap();
+        self.run_cycle();
+    }
+
+    pub fn update_settings(&mut self, settings: BasicSettings) {
+        self.send_task(NormalRealTimeTask::UpdateSettings(settings));
+    }
+
+    /// Adds an active main mapping with the given MIDI source and a target which is controlled
+    /// by the main processor.
+    pub fn add_main_mapping(&mut self, source: MidiSource) -> MappingId {
+        let id = MappingId::random();
+        let mut mapping = MainMapping::new(
+            Compartment::Main,
+            id,
+            &MappingKey::random(),
+            GroupId::default(),
+            String::new(),
+            vec![],
+            CompoundMappingSource::Midi(source),
+            Default::default(),
+            Default::default(),
+            None,
+            Mode::new(Default::default()),
+            Default::default(),
+            Default::default(),
+            None,
+            Some(UnresolvedCompoundMappingTarget::Reaper(
+                UnresolvedReaperTarget::Dummy(UnresolvedDummyTarget),
+            )),
+            ActivationCondition::Always,
+            ActivationCondition::Always,
+            ProcessorMappingOptions {
+                target_is_active: true,
+                persistent_processing_state: PersistentMappingProcessingState { is_enabled: true },
+                control_is_enabled: true,
+                feedback_is_enabled: true,
+                feedback_send_behavior: Default::default(),
+                beep_on_success: false,
+                crossfade_role: None,
+                feedback_effect: None,
+                invert_feedback: false,
+                visible_in_projection: true,
+                feedback_only_if_visible_in_projection: false,
+            },
+            Default::default(),
+        );
+        let rt_mapping = mapping.splinter_real_time_mapping();
+        self.send_task(NormalRealTimeTask::UpdateSingleMapping(
+            Compartment::Main,
+            Box::new(Some(rt_mapping)),
+        ));
+        // Activation conditions are evaluated in the main processor, so we need to activate the
+        // mapping explicitly.
+        let update = RealTimeMappingUpdate {
+            id,
+            activation_change: Some(ActivationChange { is_active: true }),
+        };
+        self.send_task(NormalRealTimeTask::UpdateMappingsPartially(
+            Compartment::Main,
+            vec![update],
+        ));
+        id
+    }
+
+    /// Simulates one audio block of the audio hook.
+    pub fn run_cycle(&mut self) {
+        self.processor
+            .run_from_audio_hook_essential(block_props(), false);
+        // Simulate the main thread which would dispose the garbage and the log entries.
+        self.garbage_receiver.try_iter().for_each(drop);
+        self.log_receiver.try_iter().for_each(drop);
+    }
+
+    /// Simulates a MIDI message arriving at the FX input.
+    pub fn send_midi_to_fx_input(&mut self, msg: RawShortMessage) {
+        self.processor
+            .process_incoming_midi_from_vst(create_event(msg), false, &self.host);
+    }
+
+    /// Simulates a MIDI message arriving from a MIDI input device via audio hook.
+    ///
+    /// Returns whether the message would be filtered out from the global MIDI stream.
+    pub fn send_midi_from_device(&mut self, msg: RawShortMessage) -> bool {
+        self.processor
+            .process_incoming_midi_from_audio_hook(create_event(msg))
+    }
+
+    /// Changes what the processor considers as the current rendering state.
+    pub fn simulate_rendering(&self, is_rendering: bool) {
+        SIMULATED_RENDERING.with(|r| r.set(is_rendering));
+    }
+
+    /// Returns all MIDI events sent to the FX output since the last call.
+    pub fn take_forwarded_midi(&self) -> Vec<ForwardedMidiEvent> {
+        FORWARDED_MIDI.with(|f| f.take())
+    }
+
+    /// Returns all control tasks sent to the main processor since the last call.
+    pub fn take_control_main_tasks(&self) -> Vec<ControlMainTask> {
+        self.control_main_task_receiver.try_iter().collect()
+    }
+
+    /// Returns all normal tasks sent to the main thread since the last call.
+    pub fn take_normal_main_tasks(&self) -> Vec<NormalRealTimeToMainThreadTask> {
+        self.normal_main_task_receiver.try_iter().collect()
+    }
+}
+
+/// Replaces the REAPER query while running tests.
+pub fn simulated_is_rendering() -> bool {
+    SIMULATED_RENDERING.with(|r| r.get())
+}
+
+fn block_props() -> AudioBlockProps {
+    AudioBlockProps {
+        block_length: 512,
+        frame_rate: Hz::new(48000.0),
+    }
+}
+
+fn create_event(msg: RawShortMessage) -> ControlEvent<MidiEvent<IncomingMidiMessage<'static>>> {
+    ControlEvent::new(
+        MidiEvent::without_offset(IncomingMidiMessage::Short(msg)),
+        ControlEventTimestamp::now(),
+    )
+}
+
+extern "C" fn fake_host_callback(
+    _effect: *mut AEffect,
+    opcode: i32,
+    _index: i32,
+    _value: isize,
+    ptr: *mut c_void,
+    _opt: f32,
+) -> isize {
+    if opcode != OpCode::ProcessEvents as i32 || ptr.is_null() {
+        return 0;
+    }
+    let events = unsafe { &*(ptr as *const Events) };
+    for i in 0..events.num_events as usize {
+        let event = unsafe { *events.events.as_ptr().add(i) };
+        if let Some(e) = unsafe { decode_vst_event(event) } {
+            FORWARDED_MIDI.with(|f| f.borrow_mut().push(e));
+        }
+    }
+    0
+}
+
+unsafe fn decode_vst_event(event: *const vst::api::Event) -> Option<ForwardedMidiEvent> {
+    match (*event).event_type {
+        EventType::Midi => {
+            let e = &*(event as *const vst::api::MidiEvent);
+            let e = ForwardedMidiEvent {
+                frame_offset: e.delta_frames,
+                bytes: e.midi_data.to_vec(),
+            };
+            Some(e)
+        }
+        EventType::SysEx => {
+            let e = &*(event as *const SysExEvent);
+            let bytes = std::slice::from_raw_parts(e.system_data, e.data_size as usize);
+            let e = ForwardedMidiEvent {
+                frame_offset: e.delta_frames,
+                bytes: bytes.to_vec(),
+            };
+            Some(e)
+        }
+        _ => None,
+    }
+}
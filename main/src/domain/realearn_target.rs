@@ -1,7 +1,6 @@
 use crate::base::{SenderToNormalThread, SenderToRealTimeThread};
 use crate::domain::ui_util::{
-    format_as_percentage_without_unit, format_raw_midi, log_output,
-    parse_unit_value_from_percentage, OutputReason,
+    format_raw_midi, log_output, parse_unit_value_from_percentage, OutputReason,
 };
 use crate::domain::{
     new_set_track_ui_functions_are_available, scoped_track_index, AdditionalFeedbackEvent,
@@ -10,22 +9,27 @@ use crate::domain::{
     InstanceId, InstanceStateChanged, MainMapping, MappingControlResult, MappingId,
     OrderedMappingMap, OscFeedbackTask, ProcessorContext, QualifiedMappingId, RealTimeReaperTarget,
     ReaperTarget, SharedInstanceState, Tag, TagScope, TargetCharacter, TrackExclusivity,
-    ACTION_TARGET, ALL_TRACK_FX_ENABLE_TARGET, ANY_ON_TARGET, AUTOMATION_MODE_OVERRIDE_TARGET,
-    BROWSE_FXS_TARGET, BROWSE_GROUP_MAPPINGS_TARGET, BROWSE_POT_FILTER_ITEMS_TARGET,
-    BROWSE_POT_PRESETS_TARGET, CLIP_COLUMN_TARGET, CLIP_MANAGEMENT_TARGET, CLIP_MATRIX_TARGET,
-    CLIP_ROW_TARGET, CLIP_SEEK_TARGET, CLIP_TRANSPORT_TARGET, CLIP_VOLUME_TARGET, DUMMY_TARGET,
-    ENABLE_INSTANCES_TARGET, ENABLE_MAPPINGS_TARGET, FX_ENABLE_TARGET, FX_ONLINE_TARGET,
-    FX_OPEN_TARGET, FX_PARAMETER_TARGET, FX_PARAMETER_TOUCH_STATE_TARGET, FX_PRESET_TARGET,
-    FX_TOOL_TARGET, GO_TO_BOOKMARK_TARGET, LOAD_FX_SNAPSHOT_TARGET, LOAD_MAPPING_SNAPSHOT_TARGET,
-    LOAD_POT_PRESET_TARGET, MIDI_SEND_TARGET, MOUSE_TARGET, OSC_SEND_TARGET, PLAYRATE_TARGET,
-    PREVIEW_POT_PRESET_TARGET, ROUTE_AUTOMATION_MODE_TARGET, ROUTE_MONO_TARGET, ROUTE_MUTE_TARGET,
+    ValueDisplayPreferences, ACTION_TARGET, ALL_TRACK_FX_ENABLE_TARGET, ANY_ON_TARGET,
+    AUTOMATION_MODE_OVERRIDE_TARGET, BROWSE_FXS_TARGET, BROWSE_GROUP_MAPPINGS_TARGET,
+    BROWSE_POT_FILTER_ITEMS_TARGET, BROWSE_POT_PRESETS_TARGET, BROWSE_PROJECT_TABS_TARGET,
+    CLIP_COLUMN_TARGET, CLIP_MANAGEMENT_TARGET, CLIP_MATRIX_TARGET, CLIP_ROW_TARGET,
+    CLIP_SEEK_TARGET, CLIP_TRANSPORT_TARGET, CLIP_VOLUME_TARGET, DUMMY_TARGET,
+    ENABLE_INSTANCES_TARGET, ENABLE_MAPPINGS_TARGET, FEEDBACK_BRIGHTNESS_TARGET, FX_ENABLE_TARGET,
+    FX_ONLINE_TARGET, FX_OPEN_TARGET, FX_PARAMETER_TARGET, FX_PARAMETER_TOUCH_STATE_TARGET,
+    FX_PRESET_TARGET, FX_TOOL_TARGET, GLOBAL_PARAMETER_VALUE_TARGET, GO_TO_BOOKMARK_TARGET,
+    ITEM_PROPERTY_TARGET, LOAD_FX_SNAPSHOT_TARGET, LOAD_MAPPING_SNAPSHOT_TARGET,
+    LOAD_POT_PRESET_TARGET, LOOP_REGION_TARGET, MIDI_EDITOR_ACTION_TARGET, MIDI_SEND_TARGET,
+    MOUSE_TARGET, NAVIGATE_PROJECT_TABS_TARGET, NAVIGATE_TRACKS_TARGET, OSC_SEND_TARGET,
+    PLAYRATE_TARGET, PREVIEW_POT_PRESET_TARGET, RECALL_FX_PARAMETER_SNAPSHOT_TARGET,
+    RENDER_PROJECT_TARGET, ROUTE_AUTOMATION_MODE_TARGET, ROUTE_MONO_TARGET, ROUTE_MUTE_TARGET,
     ROUTE_PAN_TARGET, ROUTE_PHASE_TARGET, ROUTE_TOUCH_STATE_TARGET, ROUTE_VOLUME_TARGET,
-    SAVE_MAPPING_SNAPSHOT_TARGET, SEEK_TARGET, SELECTED_TRACK_TARGET, TEMPO_TARGET,
+    SAVE_FX_PARAMETER_SNAPSHOT_TARGET, SAVE_MAPPING_SNAPSHOT_TARGET, SEEK_TARGET,
+    SELECTED_TRACK_TARGET, TEMPO_TARGET, TOGGLE_RENDER_QUEUE_TARGET, TOOLBAR_TOGGLE_TARGET,
     TRACK_ARM_TARGET, TRACK_AUTOMATION_MODE_TARGET, TRACK_MONITORING_MODE_TARGET,
     TRACK_MUTE_TARGET, TRACK_PAN_TARGET, TRACK_PARENT_SEND_TARGET, TRACK_PEAK_TARGET,
     TRACK_PHASE_TARGET, TRACK_SELECTION_TARGET, TRACK_SHOW_TARGET, TRACK_SOLO_TARGET,
-    TRACK_TOOL_TARGET, TRACK_TOUCH_STATE_TARGET, TRACK_VOLUME_TARGET, TRACK_WIDTH_TARGET,
-    TRANSPORT_TARGET,
+    TRACK_TOOL_TARGET, TRACK_TOUCH_STATE_TARGET, TRACK_VOLUME_TARGET, TRACK_VOLUME_TRIM_TARGET,
+    TRACK_WIDTH_TARGET, TRANSPORT_TARGET, WRITE_ENVELOPE_POINTS_TARGET,
 };
 use enum_dispatch::enum_dispatch;
 use enum_iterator::IntoEnumIterator;
@@ -160,7 +164,9 @@ pub trait RealearnTarget {
                 .map(|v| v.to_string())
                 .unwrap_or_default(),
             TargetCharacter::Discrete => String::new(),
-            _ => format_as_percentage_without_unit(value),
+            _ => context
+                .value_display_preferences()
+                .format_percentage_without_unit(value),
         }
     }
 
@@ -438,6 +444,10 @@ impl<'a> TransformationInputProvider<AdditionalTransformationInput> for RealTime
 }
 
 impl<'a> ControlContext<'a> {
+    pub fn value_display_preferences(&self) -> ValueDisplayPreferences {
+        self.instance_state.borrow().value_display_preferences()
+    }
+
     pub fn log_outgoing_target_midi(&self, events: &[RawMidiEvent]) {
         if self.output_logging_enabled {
             for e in events {
@@ -596,7 +606,13 @@ pub enum ReaperTargetType {
     // Project targets
     AnyOn = 43,
     BrowseTracks = 14,
+    NavigateTracks = 62,
+    BrowseProjectTabs = 72,
+    NavigateProjectTabs = 73,
+    RenderProject = 74,
+    ToggleRenderQueue = 75,
     Action = 0,
+    ToolbarToggle = 64,
     Transport = 16,
     Seek = 23,
     PlayRate = 11,
@@ -604,6 +620,13 @@ pub enum ReaperTargetType {
 
     // Marker/region targets
     GoToBookmark = 22,
+    LoopRegion = 70,
+
+    // Item targets
+    ItemProperty = 63,
+
+    // MIDI editor targets
+    MidiEditorAction = 76,
 
     // Track targets
     TrackTool = 44,
@@ -620,6 +643,7 @@ pub enum ReaperTargetType {
     TrackPan = 4,
     TrackWidth = 17,
     TrackVolume = 2,
+    TrackVolumeTrim = 71,
     TrackShow = 24,
     TrackSolo = 8,
 
@@ -632,6 +656,8 @@ pub enum ReaperTargetType {
     FxEnable = 12,
     FxOnline = 42,
     LoadFxSnapshot = 19,
+    SaveFxParameterSnapshot = 67,
+    RecallFxParameterSnapshot = 68,
     FxOpen = 27,
 
     // FX parameter targets
@@ -679,6 +705,10 @@ pub enum ReaperTargetType {
     LoadMappingSnapshot = 35,
     TakeMappingSnapshot = 55,
     BrowseGroup = 37,
+    WriteEnvelopePoints = 65,
+    GlobalParameterValue = 66,
+    FeedbackBrightness = 69,
+    ResyncFeedback = 77,
 }
 
 impl Display for ReaperTargetType {
@@ -717,12 +747,21 @@ impl ReaperTargetType {
             AutomationModeOverride => &AUTOMATION_MODE_OVERRIDE_TARGET,
             AnyOn => &ANY_ON_TARGET,
             Action => &ACTION_TARGET,
+            ToolbarToggle => &TOOLBAR_TOGGLE_TARGET,
             Transport => &TRANSPORT_TARGET,
             BrowseTracks => &SELECTED_TRACK_TARGET,
+            NavigateTracks => &NAVIGATE_TRACKS_TARGET,
+            BrowseProjectTabs => &BROWSE_PROJECT_TABS_TARGET,
+            NavigateProjectTabs => &NAVIGATE_PROJECT_TABS_TARGET,
+            RenderProject => &RENDER_PROJECT_TARGET,
+            ToggleRenderQueue => &TOGGLE_RENDER_QUEUE_TARGET,
             Seek => &SEEK_TARGET,
             PlayRate => &PLAYRATE_TARGET,
             Tempo => &TEMPO_TARGET,
             GoToBookmark => &GO_TO_BOOKMARK_TARGET,
+            LoopRegion => &LOOP_REGION_TARGET,
+            ItemProperty => &ITEM_PROPERTY_TARGET,
+            MidiEditorAction => &MIDI_EDITOR_ACTION_TARGET,
             TrackArm => &TRACK_ARM_TARGET,
             TrackParentSend => &TRACK_PARENT_SEND_TARGET,
             AllTrackFxEnable => &ALL_TRACK_FX_ENABLE_TARGET,
@@ -737,6 +776,7 @@ impl ReaperTargetType {
             TrackPan => &TRACK_PAN_TARGET,
             TrackWidth => &TRACK_WIDTH_TARGET,
             TrackVolume => &TRACK_VOLUME_TARGET,
+            TrackVolumeTrim => &TRACK_VOLUME_TRIM_TARGET,
             TrackShow => &TRACK_SHOW_TARGET,
             TrackSolo => &TRACK_SOLO_TARGET,
             FxTool => &FX_TOOL_TARGET,
@@ -744,6 +784,8 @@ impl ReaperTargetType {
             FxEnable => &FX_ENABLE_TARGET,
             FxOnline => &FX_ONLINE_TARGET,
             LoadFxSnapshot => &LOAD_FX_SNAPSHOT_TARGET,
+            SaveFxParameterSnapshot => &SAVE_FX_PARAMETER_SNAPSHOT_TARGET,
+            RecallFxParameterSnapshot => &RECALL_FX_PARAMETER_SNAPSHOT_TARGET,
             FxPreset => &FX_PRESET_TARGET,
             FxOpen => &FX_OPEN_TARGET,
             FxParameterValue => &FX_PARAMETER_TARGET,
@@ -770,6 +812,10 @@ impl ReaperTargetType {
             LoadMappingSnapshot => &LOAD_MAPPING_SNAPSHOT_TARGET,
             TakeMappingSnapshot => &SAVE_MAPPING_SNAPSHOT_TARGET,
             BrowseGroup => &BROWSE_GROUP_MAPPINGS_TARGET,
+            WriteEnvelopePoints => &WRITE_ENVELOPE_POINTS_TARGET,
+            GlobalParameterValue => &GLOBAL_PARAMETER_VALUE_TARGET,
+            FeedbackBrightness => &FEEDBACK_BRIGHTNESS_TARGET,
+            ResyncFeedback => &RESYNC_FEEDBACK_TARGET,
             BrowsePotFilterItems => &BROWSE_POT_FILTER_ITEMS_TARGET,
             BrowsePotPresets => &BROWSE_POT_PRESETS_TARGET,
             PreviewPotPreset => &PREVIEW_POT_PRESET_TARGET,
@@ -1,11 +1,17 @@
 use crate::base::{NamedChannelSender, SenderToNormalThread};
 use crate::domain::{
-    pot, AdditionalFeedbackEvent, FxSnapshotLoadedEvent, ParameterAutomationTouchStateChangedEvent,
-    TouchedTrackParameterType,
+    pot, AdditionalFeedbackEvent, FxSnapshotLoadedEvent, GlobalParameterChangedEvent,
+    ParameterAutomationTouchStateChangedEvent, ParameterThinner, ParameterThinning,
+    TouchedTrackParameterType, VolumeTrimChangedEvent,
 };
-use reaper_high::{Fx, GroupingBehavior, Track};
+use helgoboss_learn::UnitValue;
+use reaper_high::{Fx, FxParameter, GroupingBehavior, Track};
 use reaper_medium::{GangBehavior, MediaTrack};
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Thinners which didn't write anything for this long are discarded.
+const FX_PARAMETER_THINNER_MAX_IDLE_TIME: Duration = Duration::from_secs(10);
 
 /// Feedback for most targets comes from REAPER itself but there are some targets for which ReaLearn
 /// holds the state. It's in this struct.
@@ -35,6 +41,26 @@ pub struct RealearnTargetState {
     ///
     /// Not persistent.
     touched_things: HashSet<TouchedThing>,
+    /// Values of global parameters, shared among all ReaLearn instances.
+    ///
+    /// For "Global parameter value" target and for conditional activation.
+    ///
+    /// Not persistent.
+    global_parameter_values: HashMap<String, UnitValue>,
+    /// Current volume trims in dB, keyed by the instance and the GUIDs of the trimmed tracks.
+    ///
+    /// For "Track: Trim volume (VCA)" target.
+    ///
+    /// Not persistent.
+    volume_trims: HashMap<String, f64>,
+    /// Thinning state for each FX parameter (identified by FX and parameter index) which is
+    /// controlled with parameter thinning.
+    ///
+    /// For "FX parameter: Set value" target. Kept here and not in the target because targets are
+    /// resolved again frequently.
+    ///
+    /// Not persistent.
+    fx_parameter_thinners: HashMap<(Fx, u32), ParameterThinner>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -61,7 +87,55 @@ impl RealearnTargetState {
             fx_snapshot_chunk_hash_by_fx: Default::default(),
             touched_things: Default::default(),
             current_pot_preset_by_fx: Default::default(),
+            global_parameter_values: Default::default(),
+            volume_trims: Default::default(),
+            fx_parameter_thinners: Default::default(),
+        }
+    }
+
+    /// Returns whether the given value should be written to the given FX parameter now.
+    ///
+    /// If not, the value is held back and returned by [`Self::poll_thinned_fx_parameter_values`]
+    /// as soon as it's due.
+    pub fn thin_fx_parameter_value(
+        &mut self,
+        param: &FxParameter,
+        thinning: ParameterThinning,
+        value: UnitValue,
+        now: Instant,
+    ) -> bool {
+        let key = (param.fx().clone(), param.index());
+        if thinning.is_off() {
+            // Make sure that a previously held back value doesn't overwrite this one.
+            self.fx_parameter_thinners.remove(&key);
+            return true;
         }
+        self.fx_parameter_thinners
+            .entry(key)
+            .or_default()
+            .should_write(thinning, value, now)
+    }
+
+    /// Returns the value which has been held back for the given FX parameter, if any.
+    pub fn pending_fx_parameter_value(&self, param: &FxParameter) -> Option<UnitValue> {
+        self.fx_parameter_thinners
+            .get(&(param.fx().clone(), param.index()))?
+            .pending_value()
+    }
+
+    /// Returns the held back FX parameter values which should be written now.
+    pub fn poll_thinned_fx_parameter_values(&mut self, now: Instant) -> Vec<(Fx, u32, UnitValue)> {
+        if self.fx_parameter_thinners.is_empty() {
+            return vec![];
+        }
+        let due_values = self
+            .fx_parameter_thinners
+            .iter_mut()
+            .filter_map(|((fx, index), thinner)| Some((fx.clone(), *index, thinner.poll(now)?)))
+            .collect();
+        self.fx_parameter_thinners
+            .retain(|_, thinner| !thinner.is_obsolete(now, FX_PARAMETER_THINNER_MAX_IDLE_TIME));
+        due_values
     }
 
     pub fn current_fx_preset(&self, fx: &Fx) -> Option<&pot::CurrentPreset> {
@@ -74,6 +148,46 @@ impl RealearnTargetState {
             .send_complaining(AdditionalFeedbackEvent::MappedFxParametersChanged);
     }
 
+    /// Returns the value of the given global parameter (zero if it hasn't been set yet).
+    pub fn global_parameter_value(&self, name: &str) -> UnitValue {
+        self.global_parameter_values
+            .get(name)
+            .copied()
+            .unwrap_or(UnitValue::MIN)
+    }
+
+    pub fn set_global_parameter_value(&mut self, name: &str, value: UnitValue) {
+        let previous_value = self.global_parameter_values.insert(name.to_string(), value);
+        if previous_value == Some(value) {
+            return;
+        }
+        self.additional_feedback_event_sender.send_complaining(
+            AdditionalFeedbackEvent::GlobalParameterChanged(GlobalParameterChangedEvent {
+                name: name.to_string(),
+                new_value: value,
+            }),
+        );
+    }
+
+    /// Returns the current volume trim in dB (zero if it hasn't been set yet).
+    pub fn volume_trim(&self, key: &str) -> f64 {
+        self.volume_trims.get(key).copied().unwrap_or(0.0)
+    }
+
+    /// Sets the volume trim in dB and returns the previous one.
+    pub fn set_volume_trim(&mut self, key: &str, db: f64) -> f64 {
+        let previous_value = self.volume_trims.insert(key.to_string(), db).unwrap_or(0.0);
+        if previous_value != db {
+            self.additional_feedback_event_sender.send_complaining(
+                AdditionalFeedbackEvent::VolumeTrimChanged(VolumeTrimChangedEvent {
+                    key: key.to_string(),
+                    new_value: db,
+                }),
+            );
+        }
+        previous_value
+    }
+
     pub fn current_fx_snapshot_chunk_hash(&self, fx: &Fx) -> Option<u64> {
         self.fx_snapshot_chunk_hash_by_fx.get(fx).copied()
     }
@@ -5,20 +5,25 @@ use helgoboss_learn::{
     format_percentage_without_unit, parse_percentage_without_unit, ControlValue,
     DetailedSourceCharacter, FeedbackValue, SourceCharacter, UnitValue,
 };
-use reaper_medium::{MidiInputDeviceId, MidiOutputDeviceId};
+use realearn_api::persistence::LfoShape;
+use reaper_medium::{Bpm, MidiInputDeviceId, MidiOutputDeviceId};
 use std::collections::HashSet;
 use std::convert::TryInto;
 use std::error::Error;
+use std::f64::consts::PI;
 use std::fmt::{Display, Formatter};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum ReaperSource {
     MidiDeviceChanges,
     RealearnInstanceStart,
     Timer(TimerSource),
     RealearnParameter(RealearnParameterSource),
     Speech(SpeechSource),
+    OneShotTimer(OneShotTimerSource),
+    Lfo(LfoSource),
+    Random(RandomSource),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
@@ -122,6 +127,156 @@ impl TimerSource {
     }
 }
 
+/// Fires once, the given duration after the mapping has been activated.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct OneShotTimerSource {
+    duration: Duration,
+    activated_at: Option<Instant>,
+    fired: bool,
+}
+
+impl OneShotTimerSource {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            activated_at: None,
+            fired: false,
+        }
+    }
+
+    pub fn on_deactivate(&mut self) {
+        self.activated_at = None;
+        self.fired = false;
+    }
+
+    pub fn poll(&mut self) -> Option<ControlValue> {
+        let now = Instant::now();
+        let activated_at = *self.activated_at.get_or_insert(now);
+        if self.fired || now - activated_at < self.duration {
+            return None;
+        }
+        self.fired = true;
+        Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+    }
+}
+
+/// One bar in 4/4.
+pub const DEFAULT_LFO_PERIOD: f64 = 4.0;
+
+/// Low-frequency oscillator whose rate follows the project tempo.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LfoSource {
+    shape: LfoShape,
+    /// Length of one cycle in beats.
+    period: f64,
+    /// Position within the current cycle, from 0.0 to 1.0 (exclusive).
+    phase: f64,
+    last_poll: Option<Instant>,
+}
+
+impl LfoSource {
+    pub fn new(shape: LfoShape, period_in_beats: f64) -> Self {
+        Self {
+            shape,
+            period: period_in_beats,
+            phase: 0.0,
+            last_poll: None,
+        }
+    }
+
+    pub fn on_deactivate(&mut self) {
+        self.phase = 0.0;
+        self.last_poll = None;
+    }
+
+    pub fn poll(&mut self, tempo: Bpm) -> Option<ControlValue> {
+        self.poll_at(Instant::now(), tempo)
+    }
+
+    fn poll_at(&mut self, now: Instant, tempo: Bpm) -> Option<ControlValue> {
+        if let Some(last_poll) = self.last_poll {
+            if self.period > 0.0 {
+                let elapsed_beats = (now - last_poll).as_secs_f64() * tempo.get() / 60.0;
+                self.phase = (self.phase + elapsed_beats / self.period).fract();
+            }
+        }
+        self.last_poll = Some(now);
+        let value = lfo_value(self.shape, self.phase);
+        Some(ControlValue::AbsoluteContinuous(UnitValue::new_clamped(
+            value,
+        )))
+    }
+}
+
+/// All shapes start at their minimum (except "Saw down", which starts at its maximum).
+fn lfo_value(shape: LfoShape, phase: f64) -> f64 {
+    use LfoShape::*;
+    match shape {
+        Sine => (1.0 - (2.0 * PI * phase).cos()) / 2.0,
+        Triangle => 1.0 - (2.0 * phase - 1.0).abs(),
+        SawUp => phase,
+        SawDown => 1.0 - phase,
+        Square => {
+            if phase < 0.5 {
+                0.0
+            } else {
+                1.0
+            }
+        }
+    }
+}
+
+/// Emits a random value every _n_ milliseconds.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct RandomSource {
+    interval: Duration,
+    last_fire: Option<Instant>,
+    /// State of the pseudo-random number generator (xorshift).
+    state: u64,
+}
+
+impl RandomSource {
+    pub fn new(interval: Duration) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Self {
+            interval,
+            last_fire: None,
+            // Xorshift doesn't work with zero state.
+            state: seed | 1,
+        }
+    }
+
+    pub fn on_deactivate(&mut self) {
+        self.last_fire = None;
+    }
+
+    pub fn poll(&mut self) -> Option<ControlValue> {
+        let now = Instant::now();
+        if let Some(last_fire) = self.last_fire {
+            if now - last_fire < self.interval {
+                return None;
+            }
+        }
+        self.last_fire = Some(now);
+        let value = self.next_random() as f64 / u64::MAX as f64;
+        Some(ControlValue::AbsoluteContinuous(UnitValue::new_clamped(
+            value,
+        )))
+    }
+
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
 impl ReaperSource {
     pub fn extract_feedback_address(&self) -> Option<ReaperSourceAddress> {
         use ReaperSource::*;
@@ -135,13 +290,17 @@ impl ReaperSource {
     pub fn on_deactivate(&mut self) {
         match self {
             ReaperSource::Timer(s) => s.on_deactivate(),
+            ReaperSource::OneShotTimer(s) => s.on_deactivate(),
+            ReaperSource::Lfo(s) => s.on_deactivate(),
+            ReaperSource::Random(s) => s.on_deactivate(),
             _ => {}
         }
     }
 
     /// If this returns `true`, the `poll` method should be called, on a regular basis.
     pub fn wants_to_be_polled(&self) -> bool {
-        matches!(self, ReaperSource::Timer(_))
+        use ReaperSource::*;
+        matches!(self, Timer(_) | OneShotTimer(_) | Lfo(_) | Random(_))
     }
 
     pub fn possible_detailed_characters(&self) -> Vec<DetailedSourceCharacter> {
@@ -149,7 +308,8 @@ impl ReaperSource {
         match self {
             MidiDeviceChanges => vec![DetailedSourceCharacter::MomentaryOnOffButton],
             RealearnInstanceStart => vec![DetailedSourceCharacter::MomentaryOnOffButton],
-            Timer(_) => vec![DetailedSourceCharacter::Trigger],
+            Timer(_) | OneShotTimer(_) => vec![DetailedSourceCharacter::Trigger],
+            Lfo(_) | Random(_) => vec![DetailedSourceCharacter::RangeControl],
             RealearnParameter(_) => vec![
                 DetailedSourceCharacter::RangeControl,
                 DetailedSourceCharacter::MomentaryVelocitySensitiveButton,
@@ -172,19 +332,23 @@ impl ReaperSource {
     pub fn character(&self) -> SourceCharacter {
         use ReaperSource::*;
         match self {
-            MidiDeviceChanges | RealearnInstanceStart | Timer(_) => {
+            MidiDeviceChanges | RealearnInstanceStart | Timer(_) | OneShotTimer(_) => {
                 SourceCharacter::MomentaryButton
             }
-            RealearnParameter(_) => SourceCharacter::RangeElement,
+            RealearnParameter(_) | Lfo(_) | Random(_) => SourceCharacter::RangeElement,
             Speech(_) => SourceCharacter::RangeElement,
         }
     }
 
-    pub fn poll(&mut self) -> Option<ControlValue> {
-        if let ReaperSource::Timer(t) = self {
-            t.poll()
-        } else {
-            None
+    /// The tempo is the current tempo of the project.
+    pub fn poll(&mut self, tempo: Bpm) -> Option<ControlValue> {
+        use ReaperSource::*;
+        match self {
+            Timer(s) => s.poll(),
+            OneShotTimer(s) => s.poll(),
+            Lfo(s) => s.poll(tempo),
+            Random(s) => s.poll(),
+            _ => None,
         }
     }
 
@@ -224,7 +388,13 @@ impl ReaperSource {
     pub fn feedback(&self, feedback_value: &FeedbackValue) -> Option<ReaperSourceFeedbackValue> {
         use ReaperSource::*;
         match self {
-            MidiDeviceChanges | RealearnInstanceStart | Timer(_) | RealearnParameter(_) => None,
+            MidiDeviceChanges
+            | RealearnInstanceStart
+            | Timer(_)
+            | RealearnParameter(_)
+            | OneShotTimer(_)
+            | Lfo(_)
+            | Random(_) => None,
             Speech(s) => Some(ReaperSourceFeedbackValue::Speech(
                 s.feedback(feedback_value),
             )),
@@ -292,3 +462,27 @@ impl Display for MidiDeviceChangePayload {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lfo_follows_tempo() {
+        // Given
+        let mut source = LfoSource::new(LfoShape::SawUp, 4.0);
+        let start = Instant::now();
+        let tempo = Bpm::new(120.0);
+        // When
+        let first_value = source.poll_at(start, tempo);
+        // 1 second at 120 bpm = 2 beats = half a cycle
+        let second_value = source.poll_at(start + Duration::from_secs(1), tempo);
+        // Then
+        assert_eq!(
+            first_value,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MIN))
+        );
+        let second_value = second_value.unwrap().to_unit_value().unwrap().get();
+        assert!((second_value - 0.5).abs() < 0.0001);
+    }
+}
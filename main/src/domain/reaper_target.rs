@@ -8,7 +8,8 @@ use enum_iterator::IntoEnumIterator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
     Action, AvailablePanValue, BookmarkType, ChangeEvent, Fx, FxChain, FxParameter,
-    GroupingBehavior, Pan, PlayRate, Project, Reaper, Tempo, Track, TrackRoute, Width,
+    GroupingBehavior, Pan, PlayRate, Project, Reaper, Tempo, Track, TrackRoute, TrackRoutePartner,
+    Width,
 };
 use reaper_medium::{
     AutomationMode, Bpm, GangBehavior, GlobalAutomationModeOverride, NormalizedPlayRate, ParamId,
@@ -31,26 +32,31 @@ use crate::domain::ui_util::convert_bool_to_unit_value;
 use crate::domain::{
     get_reaper_track_area_of_scope, handle_exclusivity, ActionTarget, AdditionalFeedbackEvent,
     AllTrackFxEnableTarget, AutomationModeOverrideTarget, BrowseFxsTarget,
-    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseTracksTarget, Caller,
-    ClipColumnTarget, ClipManagementTarget, ClipMatrixTarget, ClipRowTarget, ClipSeekTarget,
-    ClipTransportTarget, ClipVolumeTarget, ControlContext, DummyTarget, EnigoMouseTarget,
-    FxEnableTarget, FxOnlineTarget, FxOpenTarget, FxParameterTarget, FxParameterTouchStateTarget,
-    FxPresetTarget, FxToolTarget, GoToBookmarkTarget, HierarchyEntry, HierarchyEntryProvider,
-    LoadFxSnapshotTarget, LoadPotPresetTarget, MappingControlContext, MidiSendTarget,
-    OscSendTarget, PlayrateTarget, PreviewPotPresetTarget, RealTimeClipColumnTarget,
-    RealTimeClipMatrixTarget, RealTimeClipRowTarget, RealTimeClipTransportTarget,
-    RealTimeControlContext, RealTimeFxParameterTarget, RouteMuteTarget, RoutePanTarget,
-    RouteTouchStateTarget, RouteVolumeTarget, SeekTarget, TakeMappingSnapshotTarget, TargetTypeDef,
-    TempoTarget, TrackArmTarget, TrackAutomationModeTarget, TrackMonitoringModeTarget,
-    TrackMuteTarget, TrackPanTarget, TrackParentSendTarget, TrackPeakTarget, TrackSelectionTarget,
-    TrackShowTarget, TrackSoloTarget, TrackTouchStateTarget, TrackVolumeTarget, TrackWidthTarget,
+    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseProjectTabsTarget,
+    BrowseTracksTarget, Caller, ClipColumnTarget, ClipManagementTarget, ClipMatrixTarget,
+    ClipRowTarget, ClipSeekTarget, ClipTransportTarget, ClipVolumeTarget, ControlContext,
+    DummyTarget, EnigoMouseTarget, FxEnableTarget, FxOnlineTarget, FxOpenTarget, FxParameterTarget,
+    FxParameterTouchStateTarget, FxPresetTarget, FxToolTarget, GoToBookmarkTarget, HierarchyEntry,
+    HierarchyEntryProvider, ItemPropertyTarget, LoadFxSnapshotTarget, LoadPotPresetTarget,
+    LoopRegionTarget, MappingControlContext, MidiEditorActionTarget, MidiSendTarget,
+    NavigateProjectTabsTarget, NavigateTracksTarget, OscSendTarget, PlayrateTarget,
+    PreviewPotPresetTarget, RealTimeClipColumnTarget, RealTimeClipMatrixTarget,
+    RealTimeClipRowTarget, RealTimeClipTransportTarget, RealTimeControlContext,
+    RealTimeFxParameterTarget, RecallFxParameterSnapshotTarget, RenderProjectTarget,
+    RouteMuteTarget, RoutePanTarget, RouteTouchStateTarget, RouteVolumeTarget,
+    SaveFxParameterSnapshotTarget, SeekTarget, TakeMappingSnapshotTarget, TargetTypeDef,
+    TempoTarget, ToggleRenderQueueTarget, ToolbarToggleTarget, TrackArmTarget,
+    TrackAutomationModeTarget, TrackMonitoringModeTarget, TrackMuteTarget, TrackPanTarget,
+    TrackParentSendTarget, TrackPeakTarget, TrackSelectionTarget, TrackShowTarget, TrackSoloTarget,
+    TrackTouchStateTarget, TrackVolumeTarget, TrackVolumeTrimTarget, TrackWidthTarget,
     TransportTarget,
 };
 use crate::domain::{
     AnyOnTarget, BrowseGroupMappingsTarget, CompoundChangeEvent, EnableInstancesTarget,
-    EnableMappingsTarget, HitResponse, LoadMappingSnapshotTarget, RealearnTarget, ReaperTargetType,
+    EnableMappingsTarget, FeedbackBrightnessTarget, GlobalParameterValueTarget, HitResponse,
+    LoadMappingSnapshotTarget, RealearnTarget, ReaperTargetType, ResyncFeedbackTarget,
     RouteAutomationModeTarget, RouteMonoTarget, RoutePhaseTarget, TrackPhaseTarget,
-    TrackToolTarget,
+    TrackToolTarget, WriteEnvelopePointsTarget,
 };
 
 /// This target character is just used for GUI and auto-correct settings! It doesn't have influence
@@ -104,6 +110,7 @@ pub enum ReaperTarget {
     FxParameter(FxParameterTarget),
     FxParameterTouchState(FxParameterTouchStateTarget),
     TrackVolume(TrackVolumeTarget),
+    TrackVolumeTrim(TrackVolumeTrimTarget),
     TrackTool(TrackToolTarget),
     TrackPeak(TrackPeakTarget),
     TrackRouteVolume(RouteVolumeTarget),
@@ -132,13 +139,24 @@ pub enum ReaperTarget {
     FxOpen(FxOpenTarget),
     FxPreset(FxPresetTarget),
     BrowseTracks(BrowseTracksTarget),
+    NavigateTracks(NavigateTracksTarget),
+    BrowseProjectTabs(BrowseProjectTabsTarget),
+    NavigateProjectTabs(NavigateProjectTabsTarget),
+    RenderProject(RenderProjectTarget),
+    ToggleRenderQueue(ToggleRenderQueueTarget),
     BrowseFxs(BrowseFxsTarget),
     AllTrackFxEnable(AllTrackFxEnableTarget),
     Transport(TransportTarget),
     AnyOn(AnyOnTarget),
     LoadFxSnapshot(LoadFxSnapshotTarget),
+    SaveFxParameterSnapshot(SaveFxParameterSnapshotTarget),
+    RecallFxParameterSnapshot(RecallFxParameterSnapshotTarget),
     TrackAutomationTouchState(TrackTouchStateTarget),
     GoToBookmark(GoToBookmarkTarget),
+    LoopRegion(LoopRegionTarget),
+    ItemProperty(ItemPropertyTarget),
+    MidiEditorAction(MidiEditorActionTarget),
+    ToolbarToggle(ToolbarToggleTarget),
     Seek(SeekTarget),
     SendMidi(MidiSendTarget),
     SendOsc(OscSendTarget),
@@ -152,6 +170,10 @@ pub enum ReaperTarget {
     ClipManagement(ClipManagementTarget),
     LoadMappingSnapshot(LoadMappingSnapshotTarget),
     TakeMappingSnapshot(TakeMappingSnapshotTarget),
+    WriteEnvelopePoints(WriteEnvelopePointsTarget),
+    GlobalParameterValue(GlobalParameterValueTarget),
+    FeedbackBrightness(FeedbackBrightnessTarget),
+    ResyncFeedback(ResyncFeedbackTarget),
     EnableMappings(EnableMappingsTarget),
     EnableInstances(EnableInstancesTarget),
     BrowseGroupMappings(BrowseGroupMappingsTarget),
@@ -182,6 +204,9 @@ pub enum SendMidiDestination {
     #[serde(rename = "feedback-output")]
     #[display(fmt = "Feedback output")]
     FeedbackOutput,
+    #[serde(rename = "device")]
+    #[display(fmt = "Specific device")]
+    Device,
 }
 
 impl Default for SendMidiDestination {
@@ -365,6 +390,7 @@ impl ReaperTarget {
                 TrackSolo(TrackSoloTarget {
                     track: e.track,
                     behavior: Default::default(),
+                    solo_defeat: false,
                     exclusivity: Default::default(),
                     gang_behavior: Default::default(),
                 })
@@ -392,6 +418,8 @@ impl ReaperTarget {
                     param: e.parameter,
                     poll_for_feedback: true,
                     retrigger: false,
+                    low_latency: false,
+                    thinning: None,
                 })
             }
             FxPresetChanged(e) => FxPreset(FxPresetTarget { fx: e.fx }),
@@ -448,6 +476,8 @@ impl ReaperTarget {
                     param,
                     poll_for_feedback: true,
                     retrigger: false,
+                    low_latency: false,
+                    thinning: None,
                 };
                 Some(FxParameter(t).into())
             }))
@@ -531,6 +561,7 @@ impl ReaperTarget {
                         TrackSolo(TrackSoloTarget {
                             track,
                             behavior: Default::default(),
+                            solo_defeat: false,
                             exclusivity: Default::default(),
                             gang_behavior: Default::default(),
                         })
@@ -598,6 +629,7 @@ impl<'a> Target<'a> for ReaperTarget {
             FxParameter(t) => t.current_value(context),
             FxParameterTouchState(t) => t.current_value(context),
             TrackVolume(t) => t.current_value(context),
+            TrackVolumeTrim(t) => t.current_value(context),
             TrackTool(t) => t.current_value(context),
             TrackPan(t) => t.current_value(context),
             TrackWidth(t) => t.current_value(context),
@@ -627,8 +659,15 @@ impl<'a> Target<'a> for ReaperTarget {
             // Discrete
             FxPreset(t) => t.current_value(context),
             LoadFxSnapshot(t) => t.current_value(context),
+            SaveFxParameterSnapshot(t) => t.current_value(context),
+            RecallFxParameterSnapshot(t) => t.current_value(context),
             // Discrete
             BrowseTracks(t) => t.current_value(context),
+            NavigateTracks(t) => t.current_value(context),
+            BrowseProjectTabs(t) => t.current_value(context),
+            NavigateProjectTabs(t) => t.current_value(context),
+            RenderProject(t) => t.current_value(context),
+            ToggleRenderQueue(t) => t.current_value(context),
             // Discrete
             BrowseFxs(t) => t.current_value(context),
             AllTrackFxEnable(t) => t.current_value(context),
@@ -636,6 +675,10 @@ impl<'a> Target<'a> for ReaperTarget {
             AnyOn(t) => t.current_value(context),
             TrackAutomationTouchState(t) => t.current_value(context),
             GoToBookmark(t) => t.current_value(context),
+            LoopRegion(t) => t.current_value(context),
+            ItemProperty(t) => t.current_value(context),
+            MidiEditorAction(t) => t.current_value(context),
+            ToolbarToggle(t) => t.current_value(context),
             Seek(t) => t.current_value(context),
             ClipTransport(t) => t.current_value(context),
             ClipColumn(t) => t.current_value(context),
@@ -646,6 +689,10 @@ impl<'a> Target<'a> for ReaperTarget {
             ClipMatrix(t) => t.current_value(context),
             LoadMappingSnapshot(t) => t.current_value(context),
             TakeMappingSnapshot(t) => t.current_value(context),
+            WriteEnvelopePoints(t) => t.current_value(context),
+            GlobalParameterValue(t) => t.current_value(context),
+            FeedbackBrightness(t) => t.current_value(context),
+            ResyncFeedback(t) => t.current_value(context),
             EnableMappings(t) => t.current_value(context),
             EnableInstances(t) => t.current_value(context),
             BrowseGroupMappings(t) => t.current_value(context),
@@ -1105,6 +1152,42 @@ pub fn pan_unit_value(pan: Pan) -> UnitValue {
     UnitValue::new(pan.normalized_value())
 }
 
+/// Returns whether the given route is affected by a change of `changed_route`.
+///
+/// A change of a track-to-track route can be reported from the perspective of the sending or of
+/// the receiving track. Both describe the same connection, so a route is also affected by a change
+/// of its counterpart on the other track. If there are multiple sends between the same pair of
+/// tracks, all of them are considered as affected. Then the reported value might belong to
+/// another send, so it should only be used if both routes are equal.
+pub fn route_is_affected_by_change_of(route: &TrackRoute, changed_route: &TrackRoute) -> bool {
+    if route == changed_route {
+        return true;
+    }
+    let partner_track = |r: &TrackRoute| match r.partner() {
+        Some(TrackRoutePartner::Track(t)) => Some(t),
+        _ => None,
+    };
+    let partner = partner_track(route);
+    let changed_partner = partner_track(changed_route);
+    is_other_side_of_same_connection(
+        (route.direction(), route.track(), partner.as_ref()),
+        (
+            changed_route.direction(),
+            changed_route.track(),
+            changed_partner.as_ref(),
+        ),
+    )
+}
+
+/// Takes the direction, owning track and partner track of two routes and returns whether they
+/// describe the same connection seen from opposite tracks.
+fn is_other_side_of_same_connection<D: PartialEq, T: PartialEq>(
+    (direction, track, partner): (D, &T, Option<&T>),
+    (other_direction, other_track, other_partner): (D, &T, Option<&T>),
+) -> bool {
+    direction != other_direction && partner == Some(other_track) && other_partner == Some(track)
+}
+
 pub fn width_unit_value(width: Width) -> UnitValue {
     UnitValue::new(width.normalized_value())
 }
@@ -1571,3 +1654,24 @@ fn is_bypass_param(param: &FxParameter) -> bool {
     let bypass_param = param.fx().parameter_by_id(ParamId::Bypass);
     Some(param) == bypass_param.as_ref()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receive_is_other_side_of_send() {
+        // Given
+        let send = ("send", &1, Some(&2));
+        let receive = ("receive", &2, Some(&1));
+        let unrelated_receive = ("receive", &2, Some(&3));
+        let hardware_output = ("send", &1, None);
+        // When
+        // Then
+        assert!(is_other_side_of_same_connection(send, receive));
+        assert!(is_other_side_of_same_connection(receive, send));
+        assert!(!is_other_side_of_same_connection(send, send));
+        assert!(!is_other_side_of_same_connection(send, unrelated_receive));
+        assert!(!is_other_side_of_same_connection(hardware_output, receive));
+    }
+}
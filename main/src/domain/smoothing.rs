@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+/// Lets a value follow a target value gradually (one-pole smoothing).
+///
+/// Used wherever ReaLearn needs to smooth values over time, e.g. for following the level of an
+/// audio source or for gliding FX parameters to their snapshot values.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Smoother {
+    value: f64,
+}
+
+impl Smoother {
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn reset(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Moves the value towards the given target value and returns the new value.
+    ///
+    /// After the given time constant, about 63% of the distance to the target value is covered.
+    /// A time constant of zero makes the value jump to the target value immediately.
+    pub fn process(
+        &mut self,
+        target_value: f64,
+        time_constant: Duration,
+        elapsed: Duration,
+    ) -> f64 {
+        self.value += (target_value - self.value) * smoothing_coefficient(time_constant, elapsed);
+        self.value
+    }
+}
+
+/// Returns how much of the distance to the target value is covered within the given time.
+fn smoothing_coefficient(time_constant: Duration, elapsed: Duration) -> f64 {
+    let time_constant = time_constant.as_secs_f64();
+    if time_constant <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-elapsed.as_secs_f64() / time_constant).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approach_target_value() {
+        // Given
+        let mut smoother = Smoother::new(1.0);
+        let time_constant = Duration::from_millis(100);
+        // When
+        let value = smoother.process(0.0, time_constant, time_constant);
+        let immediate_value = smoother.process(0.5, Duration::ZERO, time_constant);
+        // Then
+        assert!((value - 0.368).abs() < 0.001);
+        assert_eq!(immediate_value, 0.5);
+    }
+}
@@ -0,0 +1,142 @@
+use helgoboss_learn::{ControlValue, BASE_EPSILON};
+use std::ops::Sub;
+use std::time::Duration;
+
+/// Settings for filtering out value flutter of absolute source values.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct SourceDebounceSettings {
+    /// Values which differ less than this from the last accepted value are ignored.
+    pub min_change: f64,
+    /// Values which reverse the direction of movement within this time after the last accepted
+    /// value are ignored.
+    pub hold_time: Duration,
+}
+
+impl SourceDebounceSettings {
+    pub fn is_enabled(&self) -> bool {
+        self.min_change > 0.0 || !self.hold_time.is_zero()
+    }
+}
+
+/// Removes value flutter (e.g. alternating adjacent values sent by cheap controllers) before the
+/// value reaches the mode.
+#[derive(Clone, Debug)]
+pub struct SourceDebounceFilter<T> {
+    settings: SourceDebounceSettings,
+    last_accepted: Option<AcceptedValue<T>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct AcceptedValue<T> {
+    value: f64,
+    timestamp: T,
+    /// `true` if the value went up compared to the previously accepted value.
+    went_up: Option<bool>,
+}
+
+impl<T: Copy + Sub<Output = Duration>> SourceDebounceFilter<T> {
+    pub fn new(settings: SourceDebounceSettings) -> Self {
+        Self {
+            settings,
+            last_accepted: None,
+        }
+    }
+
+    /// Returns whether the given value should be processed.
+    ///
+    /// Relative values are always let through.
+    pub fn accepts(&mut self, value: ControlValue, timestamp: T) -> bool {
+        if !self.settings.is_enabled() {
+            return true;
+        }
+        let value = match value.to_unit_value() {
+            Ok(v) => v.get(),
+            // Relative value
+            Err(_) => return true,
+        };
+        let accepted = match self.last_accepted {
+            None => AcceptedValue {
+                value,
+                timestamp,
+                went_up: None,
+            },
+            Some(last) => {
+                let diff = value - last.value;
+                // Repeated values (e.g. from buttons) are none of our business
+                if diff.abs() < BASE_EPSILON {
+                    return true;
+                }
+                let is_extreme = value < BASE_EPSILON || value > 1.0 - BASE_EPSILON;
+                let went_up = diff > 0.0;
+                if !is_extreme {
+                    if diff.abs() < self.settings.min_change {
+                        return false;
+                    }
+                    let reverses_direction = last.went_up.map(|w| w != went_up).unwrap_or(false);
+                    if reverses_direction && timestamp - last.timestamp < self.settings.hold_time {
+                        return false;
+                    }
+                }
+                AcceptedValue {
+                    value,
+                    timestamp,
+                    went_up: Some(went_up),
+                }
+            }
+        };
+        self.last_accepted = Some(accepted);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::UnitValue;
+    use std::time::Instant;
+
+    fn abs(v: f64) -> ControlValue {
+        ControlValue::AbsoluteContinuous(UnitValue::new(v))
+    }
+
+    #[test]
+    fn min_change() {
+        // Given
+        let mut filter = SourceDebounceFilter::new(SourceDebounceSettings {
+            min_change: 0.02,
+            hold_time: Duration::ZERO,
+        });
+        let now = Instant::now();
+        // When
+        let results: Vec<_> = [0.5, 0.51, 0.5, 0.515, 0.53, 1.0]
+            .into_iter()
+            .map(|v| filter.accepts(abs(v), now))
+            .collect();
+        // Then
+        assert_eq!(results, vec![true, false, true, false, true, true]);
+    }
+
+    #[test]
+    fn hold_time() {
+        // Given
+        let mut filter = SourceDebounceFilter::new(SourceDebounceSettings {
+            min_change: 0.0,
+            hold_time: Duration::from_millis(50),
+        });
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        // When
+        let results = [
+            filter.accepts(abs(0.5), at(0)),
+            filter.accepts(abs(0.6), at(10)),
+            // Flutter back
+            filter.accepts(abs(0.5), at(20)),
+            // Same direction
+            filter.accepts(abs(0.7), at(30)),
+            // Reversal after hold time
+            filter.accepts(abs(0.6), at(100)),
+        ];
+        // Then
+        assert_eq!(results, [true, true, false, true, true]);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::domain::{
+    convert_count_to_step_size, convert_discrete_to_unit_value, convert_unit_to_discrete_value,
+    Compartment, CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target, UnitValue,
+};
+use reaper_high::{ChangeEvent, Project, Reaper};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedBrowseProjectTabsTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedBrowseProjectTabsTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::BrowseProjectTabs(
+            BrowseProjectTabsTarget,
+        )])
+    }
+}
+
+/// Switches to the project tab with a certain index.
+///
+/// Doesn't care about the project in which the ReaLearn instance is located, it always switches
+/// between all open project tabs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrowseProjectTabsTarget;
+
+impl RealearnTarget for BrowseProjectTabsTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(project_tab_count()),
+                is_retriggerable: false,
+            },
+            TargetCharacter::Discrete,
+        )
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn parse_as_step_size(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn convert_unit_value_to_discrete_value(
+        &self,
+        input: UnitValue,
+        _: ControlContext,
+    ) -> Result<u32, &'static str> {
+        Ok(convert_unit_to_discrete_value(input, project_tab_count()) + 1)
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        let index = convert_unit_to_discrete_value(value, project_tab_count());
+        (index + 1).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let index = match value.to_absolute_value()? {
+            AbsoluteValue::Continuous(v) => convert_unit_to_discrete_value(v, project_tab_count()),
+            AbsoluteValue::Discrete(f) => f.actual(),
+        };
+        let project = project_tab_by_index(index).ok_or("project tab not available")?;
+        if project == Reaper::get().current_project() {
+            return Ok(HitResponse::ignored());
+        }
+        switch_to_project_tab(project);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::ProjectSwitched(_)) => (true, None),
+            _ => (false, None),
+        }
+    }
+
+    fn convert_discrete_value_to_unit_value(
+        &self,
+        value: u32,
+        _: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        let index = value.saturating_sub(1);
+        Ok(convert_discrete_to_unit_value(index, project_tab_count()))
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(project_tab_label(Reaper::get().current_project()).into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = current_project_tab_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::BrowseProjectTabs)
+    }
+}
+
+impl<'a> Target<'a> for BrowseProjectTabsTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let index = current_project_tab_index()?;
+        let max_index = project_tab_count().saturating_sub(1);
+        Some(AbsoluteValue::Discrete(Fraction::new(index, max_index)))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const BROWSE_PROJECT_TABS_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Browse project tabs",
+    short_name: "Browse project tabs",
+    hint: "Switches to project tab N",
+    ..DEFAULT_TARGET
+};
+
+pub(crate) fn project_tab_count() -> u32 {
+    Reaper::get().projects().count() as u32
+}
+
+pub(crate) fn project_tab_by_index(index: u32) -> Option<Project> {
+    Reaper::get().projects().nth(index as usize)
+}
+
+pub(crate) fn current_project_tab_index() -> Option<u32> {
+    let current_project = Reaper::get().current_project();
+    Reaper::get()
+        .projects()
+        .position(|p| p == current_project)
+        .map(|i| i as u32)
+}
+
+pub(crate) fn switch_to_project_tab(project: Project) {
+    unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .SelectProjectInstance(project.raw().as_ptr());
+    }
+}
+
+pub(crate) fn project_tab_label(project: Project) -> String {
+    match project.file() {
+        None => "<Unsaved project>".to_string(),
+        Some(path) => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    }
+}
@@ -112,16 +112,7 @@ impl RealearnTarget for BrowseTracksTarget {
                 .ok_or("track not available")?,
         };
         select_track_exclusively_scoped(&track, self.mode);
-        if self.scroll_arrange_view {
-            Reaper::get()
-                .main_section()
-                .action_by_command_id(CommandId::new(40913))
-                .invoke_as_trigger(Some(track.project()))
-                .expect("built-in action should exist");
-        }
-        if self.scroll_mixer {
-            track.scroll_mixer();
-        }
+        scroll_to_track(&track, self.scroll_arrange_view, self.scroll_mixer);
         Ok(HitResponse::processed_with_effect())
     }
 
@@ -233,7 +224,7 @@ impl BrowseTracksTarget {
     }
 }
 
-enum ScopedTrack {
+pub(crate) enum ScopedTrack {
     InScope(Track),
     /// Selected track is out of scope (e.g. we are interested in TCP scope but it#s only visible
     /// in MCP).
@@ -262,7 +253,7 @@ pub fn percentage_for_scoped_track_within_project(
     AbsoluteValue::Discrete(Fraction::new(actual_value, max_value))
 }
 
-fn scoped_track_count(project: Project, scope: TrackScope) -> u32 {
+pub(crate) fn scoped_track_count(project: Project, scope: TrackScope) -> u32 {
     use TrackScope::*;
     match scope {
         AllTracks => project.track_count(),
@@ -281,7 +272,7 @@ fn convert_unit_value_to_track_index(
     convert_unit_to_discrete_value_with_none(value, scoped_track_count(project, scope))
 }
 
-fn select_track_exclusively_scoped(track: &Track, mode: BrowseTracksMode) {
+pub(crate) fn select_track_exclusively_scoped(track: &Track, mode: BrowseTracksMode) {
     use BrowseTracksMode::*;
     match mode {
         AllTracks | TracksVisibleInTcp | TracksVisibleInMcp => {
@@ -301,7 +292,10 @@ fn select_track_exclusively_scoped(track: &Track, mode: BrowseTracksMode) {
     }
 }
 
-fn first_selected_track_scoped(project: Project, mode: BrowseTracksMode) -> Option<ScopedTrack> {
+pub(crate) fn first_selected_track_scoped(
+    project: Project,
+    mode: BrowseTracksMode,
+) -> Option<ScopedTrack> {
     use BrowseTracksMode::*;
     let master_track_behavior = MasterTrackBehavior::ExcludeMasterTrack;
     match mode {
@@ -348,6 +342,19 @@ fn first_selected_track_scoped(project: Project, mode: BrowseTracksMode) -> Opti
     }
 }
 
+pub(crate) fn scroll_to_track(track: &Track, scroll_arrange_view: bool, scroll_mixer: bool) {
+    if scroll_arrange_view {
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(40913))
+            .invoke_as_trigger(Some(track.project()))
+            .expect("built-in action should exist");
+    }
+    if scroll_mixer {
+        track.scroll_mixer();
+    }
+}
+
 fn get_other_track_area(track_area: reaper_medium::TrackArea) -> reaper_medium::TrackArea {
     use reaper_medium::TrackArea::*;
     match track_area {
@@ -1,14 +1,20 @@
+use crate::domain::ui_util::{
+    format_value_as_db, format_value_as_db_without_unit, parse_unit_value_from_percentage,
+    parse_value_from_db, volume_unit_value,
+};
 use crate::domain::{
     format_value_as_on_off, BackboneState, Compartment, CompoundChangeEvent, ControlContext,
-    ExtendedProcessorContext, HitResponse, MappingControlContext, RealTimeControlContext,
-    RealTimeReaperTarget, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
-    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+    ExtendedProcessorContext, HitResponse, InstanceStateChanged, MappingControlContext,
+    RealTimeControlContext, RealTimeReaperTarget, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
 };
-use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
 use playtime_api::persistence::{EvenQuantization, RecordLength};
 use playtime_clip_engine::base::ClipMatrixEvent;
 use playtime_clip_engine::rt::{QualifiedSlotChangeEvent, SlotChangeEvent};
 use realearn_api::persistence::ClipMatrixAction;
+use reaper_high::Volume;
+use reaper_medium::Db;
 use std::borrow::Cow;
 
 #[derive(Debug)]
@@ -39,8 +45,35 @@ impl RealearnTarget for ClipMatrixTarget {
         control_type_and_character(self.action)
     }
 
+    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        if self.action == ClipMatrixAction::ClickVolume {
+            parse_value_from_db(text)
+        } else {
+            parse_unit_value_from_percentage(text)
+        }
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        if self.action == ClipMatrixAction::ClickVolume {
+            format_value_as_db_without_unit(value)
+        } else {
+            self.format_as_discrete_or_percentage(value, context)
+        }
+    }
+
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        match self.character(context) {
+            TargetCharacter::Trigger | TargetCharacter::Discrete | TargetCharacter::Switch => "",
+            _ => "dB",
+        }
+    }
+
     fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
-        format_value_as_on_off(value).to_string()
+        if self.action == ClipMatrixAction::ClickVolume {
+            format_value_as_db(value)
+        } else {
+            format_value_as_on_off(value).to_string()
+        }
     }
 
     fn hit(
@@ -48,10 +81,32 @@ impl RealearnTarget for ClipMatrixTarget {
         value: ControlValue,
         context: MappingControlContext,
     ) -> Result<HitResponse, &'static str> {
+        if let Some((column_delta, row_delta)) = view_shift(self.action) {
+            if !value.is_on() {
+                return Ok(HitResponse::ignored());
+            }
+            let instance_state = context.control_context.instance_state;
+            let (column_count, row_count) = BackboneState::get()
+                .with_clip_matrix(instance_state, |matrix| {
+                    (matrix.column_count(), matrix.row_count())
+                })?;
+            let changed = instance_state.borrow_mut().shift_clip_view(
+                column_delta,
+                row_delta,
+                column_count,
+                row_count,
+            );
+            return if changed {
+                Ok(HitResponse::processed_with_effect())
+            } else {
+                Ok(HitResponse::ignored())
+            };
+        }
         BackboneState::get().with_clip_matrix_mut(
             context.control_context.instance_state,
             |matrix| {
-                if !value.is_on() {
+                let (_, character) = control_type_and_character(self.action);
+                if character == TargetCharacter::Trigger && !value.is_on() {
                     return Ok(HitResponse::ignored());
                 }
                 match self.action {
@@ -82,6 +137,23 @@ impl RealearnTarget for ClipMatrixTarget {
                     ClipMatrixAction::SetRecordDurationToEightBars => {
                         matrix.set_record_duration(record_duration_in_bars(8));
                     }
+                    ClipMatrixAction::ClickOnOffState => {
+                        matrix.set_click_enabled(value.is_on())?;
+                    }
+                    ClipMatrixAction::ClickVolume => {
+                        let volume =
+                            Volume::try_from_soft_normalized_value(value.to_unit_value()?.get())
+                                .unwrap_or_default();
+                        let api_db = playtime_api::persistence::Db::new(volume.db().get())?;
+                        matrix.set_click_volume(api_db)?;
+                    }
+                    ClipMatrixAction::ShiftViewLeft
+                    | ClipMatrixAction::ShiftViewRight
+                    | ClipMatrixAction::ShiftViewUp
+                    | ClipMatrixAction::ShiftViewDown => {
+                        // Handled above because it doesn't modify the matrix
+                        return Ok(HitResponse::ignored());
+                    }
                 }
                 Ok(HitResponse::processed_with_effect())
             },
@@ -119,13 +191,40 @@ impl RealearnTarget for ClipMatrixTarget {
                 }
                 _ => (false, None),
             },
+            ClipMatrixAction::ClickOnOffState | ClipMatrixAction::ClickVolume => match evt {
+                CompoundChangeEvent::ClipMatrix(ClipMatrixEvent::EverythingChanged)
+                | CompoundChangeEvent::ClipMatrix(ClipMatrixEvent::ClickChanged) => (true, None),
+                _ => (false, None),
+            },
+            ClipMatrixAction::ShiftViewLeft
+            | ClipMatrixAction::ShiftViewRight
+            | ClipMatrixAction::ShiftViewUp
+            | ClipMatrixAction::ShiftViewDown => match evt {
+                CompoundChangeEvent::ClipMatrix(ClipMatrixEvent::EverythingChanged)
+                | CompoundChangeEvent::Instance(InstanceStateChanged::ClipViewOffset) => {
+                    (true, None)
+                }
+                _ => (false, None),
+            },
         }
     }
 
     fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        if self.action == ClipMatrixAction::ClickVolume {
+            return Some(self.click_volume(context)?.to_string().into());
+        }
         Some(format_value_as_on_off(self.current_value(context)?.to_unit_value()).into())
     }
 
+    fn numeric_value(&self, context: ControlContext) -> Option<NumericValue> {
+        if self.action == ClipMatrixAction::ClickVolume {
+            return Some(NumericValue::Decimal(
+                self.click_volume(context)?.db().get(),
+            ));
+        }
+        None
+    }
+
     fn reaper_target_type(&self) -> Option<ReaperTargetType> {
         Some(ReaperTargetType::ClipMatrix)
     }
@@ -145,10 +244,25 @@ impl RealearnTarget for ClipMatrixTarget {
     }
 }
 
+impl ClipMatrixTarget {
+    fn click_volume(&self, context: ControlContext) -> Option<Volume> {
+        BackboneState::get()
+            .with_clip_matrix(context.instance_state, |matrix| {
+                Volume::from_db(Db::new(matrix.click_volume().get()))
+            })
+            .ok()
+    }
+}
+
 impl<'a> Target<'a> for ClipMatrixTarget {
     type Context = ControlContext<'a>;
 
     fn current_value(&self, context: ControlContext<'a>) -> Option<AbsoluteValue> {
+        if self.action == ClipMatrixAction::ClickVolume {
+            let volume = self.click_volume(context)?;
+            return Some(AbsoluteValue::Continuous(volume_unit_value(volume)));
+        }
+        let offset = context.instance_state.borrow().clip_view_offset();
         BackboneState::get()
             .with_clip_matrix(context.instance_state, |matrix| {
                 let bool_value = match self.action {
@@ -174,6 +288,13 @@ impl<'a> Target<'a> for ClipMatrixTarget {
                         matrix.settings().clip_record_settings.duration
                             == record_duration_in_bars(8)
                     }
+                    ClipMatrixAction::ClickOnOffState => matrix.click_is_enabled(),
+                    ClipMatrixAction::ClickVolume => return None,
+                    // Lit as long as it's possible to shift in that direction
+                    ClipMatrixAction::ShiftViewLeft => offset.column > 0,
+                    ClipMatrixAction::ShiftViewRight => offset.column + 1 < matrix.column_count(),
+                    ClipMatrixAction::ShiftViewUp => offset.row > 0,
+                    ClipMatrixAction::ShiftViewDown => offset.row + 1 < matrix.row_count(),
                 };
                 Some(AbsoluteValue::from_bool(bool_value))
             })
@@ -249,10 +370,28 @@ fn control_type_and_character(action: ClipMatrixAction) -> (ControlType, TargetC
         | Stop
         | Undo
         | Redo
-        | BuildScene => (
+        | BuildScene
+        | ShiftViewLeft
+        | ShiftViewRight
+        | ShiftViewUp
+        | ShiftViewDown => (
             ControlType::AbsoluteContinuousRetriggerable,
             TargetCharacter::Trigger,
         ),
+        ClickOnOffState => (ControlType::AbsoluteContinuous, TargetCharacter::Switch),
+        ClickVolume => (ControlType::AbsoluteContinuous, TargetCharacter::Continuous),
+    }
+}
+
+/// Returns the column and row delta if the given action shifts the clip view.
+fn view_shift(action: ClipMatrixAction) -> Option<(i32, i32)> {
+    use ClipMatrixAction::*;
+    match action {
+        ShiftViewLeft => Some((-1, 0)),
+        ShiftViewRight => Some((1, 0)),
+        ShiftViewUp => Some((0, -1)),
+        ShiftViewDown => Some((0, 1)),
+        _ => None,
     }
 }
 
@@ -1,11 +1,13 @@
 use crate::domain::{
-    BackboneState, Compartment, ControlContext, ExtendedProcessorContext, HitResponse,
-    MappingControlContext, RealTimeControlContext, RealTimeReaperTarget, RealearnClipMatrix,
-    RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef,
-    UnresolvedReaperTargetDef, VirtualClipRow, DEFAULT_TARGET,
+    BackboneState, Compartment, CompoundChangeEvent, ControlContext, ExtendedProcessorContext,
+    HitResponse, MappingControlContext, RealTimeControlContext, RealTimeReaperTarget,
+    RealearnClipMatrix, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, VirtualClipRow, DEFAULT_TARGET,
 };
-use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, PropValue, RgbColor, Target};
+use playtime_clip_engine::base::{ClipMatrixEvent, QualifiedRowChangeEvent, Row};
 use realearn_api::persistence::ClipRowAction;
+use std::borrow::Cow;
 
 #[derive(Debug)]
 pub struct UnresolvedClipRowTarget {
@@ -46,6 +48,14 @@ impl ClipRowTarget {
     ) -> Result<R, &'static str> {
         BackboneState::get().with_clip_matrix_mut(context.instance_state, f)
     }
+
+    fn with_row<R>(&self, context: ControlContext, f: impl FnOnce(&Row) -> Option<R>) -> Option<R> {
+        BackboneState::get()
+            .with_clip_matrix(context.instance_state, |matrix| {
+                f(matrix.find_row(self.basics.row_index)?)
+            })
+            .ok()?
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -126,10 +136,43 @@ impl RealearnTarget for ClipRowTarget {
         }
     }
 
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::ClipMatrix(ClipMatrixEvent::EverythingChanged) => (true, None),
+            CompoundChangeEvent::ClipMatrix(ClipMatrixEvent::RowChanged(
+                QualifiedRowChangeEvent { row_index, .. },
+            )) if *row_index == self.basics.row_index => (true, None),
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let name = self.with_row(context, |row| Some(row.name()?.to_owned()))?;
+        Some(name.into())
+    }
+
     fn reaper_target_type(&self) -> Option<ReaperTargetType> {
         Some(ReaperTargetType::ClipRow)
     }
 
+    fn prop_value(&self, key: &str, context: ControlContext) -> Option<PropValue> {
+        match key {
+            "clip_row.name" => {
+                let name = self.with_row(context, |row| Some(row.name()?.to_owned()))?;
+                Some(PropValue::Text(name.into()))
+            }
+            "clip_row.color" => {
+                let color = self.with_row(context, |row| row.color())?;
+                Some(PropValue::Color(RgbColor::new(color.0, color.1, color.2)))
+            }
+            _ => None,
+        }
+    }
+
     fn splinter_real_time_target(&self) -> Option<RealTimeReaperTarget> {
         if !matches!(self.basics.action, ClipRowAction::PlayScene) {
             return None;
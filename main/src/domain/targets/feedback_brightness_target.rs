@@ -0,0 +1,86 @@
+use crate::domain::{
+    Compartment, CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    InstanceStateChanged, MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+
+#[derive(Debug)]
+pub struct UnresolvedFeedbackBrightnessTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedFeedbackBrightnessTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::FeedbackBrightness(
+            FeedbackBrightnessTarget,
+        )])
+    }
+}
+
+/// Dims or brightens all feedback sent by this instance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedbackBrightnessTarget;
+
+impl RealearnTarget for FeedbackBrightnessTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        context
+            .control_context
+            .instance_state
+            .borrow_mut()
+            .set_feedback_brightness(value);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        context: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Instance(InstanceStateChanged::FeedbackBrightness) => {
+                (true, self.current_value(context))
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::FeedbackBrightness)
+    }
+}
+
+impl<'a> Target<'a> for FeedbackBrightnessTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, context: Self::Context) -> Option<AbsoluteValue> {
+        let brightness = context.instance_state.borrow().feedback_brightness();
+        Some(AbsoluteValue::Continuous(brightness))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const FEEDBACK_BRIGHTNESS_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "ReaLearn: Feedback brightness",
+    short_name: "Feedback brightness",
+    hint: "Scales all feedback of this instance",
+    ..DEFAULT_TARGET
+};
@@ -1,10 +1,10 @@
 use crate::domain::ui_util::parse_unit_value_from_percentage;
 use crate::domain::{
-    get_fx_params, AdditionalFeedbackEvent, Caller, Compartment, CompoundChangeEvent,
-    ControlContext, ExtendedProcessorContext, FeedbackResolution, FxParameterDescriptor,
-    HitResponse, MappingControlContext, RealTimeControlContext, RealTimeReaperTarget,
-    RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef,
-    UnresolvedReaperTargetDef, DEFAULT_TARGET,
+    get_fx_params, AdditionalFeedbackEvent, BackboneState, Caller, Compartment,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, FeedbackResolution,
+    FxParameterDescriptor, HitResponse, MappingControlContext, ParameterThinning,
+    RealTimeControlContext, RealTimeReaperTarget, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
 };
 use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, PropValue, Target, UnitValue};
 use reaper_high::{ChangeEvent, Fx, FxParameter, FxParameterCharacter, Project, Reaper, Track};
@@ -14,12 +14,17 @@ use reaper_medium::{
 };
 use std::borrow::Cow;
 use std::convert::TryInto;
+use std::time::Instant;
 
 #[derive(Debug)]
 pub struct UnresolvedFxParameterTarget {
     pub fx_parameter_descriptor: FxParameterDescriptor,
     pub poll_for_feedback: bool,
     pub retrigger: bool,
+    /// Controls the parameter directly in the audio thread if possible.
+    pub low_latency: bool,
+    /// `None` means that the instance default is used.
+    pub thinning: Option<ParameterThinning>,
 }
 
 impl UnresolvedReaperTargetDef for UnresolvedFxParameterTarget {
@@ -39,6 +44,8 @@ impl UnresolvedReaperTargetDef for UnresolvedFxParameterTarget {
                     param,
                     poll_for_feedback: self.poll_for_feedback,
                     retrigger: self.retrigger,
+                    low_latency: self.low_latency,
+                    thinning: self.thinning,
                 };
                 ReaperTarget::FxParameter(target)
             })
@@ -51,7 +58,16 @@ impl UnresolvedReaperTargetDef for UnresolvedFxParameterTarget {
     }
 
     fn feedback_resolution(&self) -> Option<FeedbackResolution> {
-        if self.poll_for_feedback {
+        // REAPER doesn't notify us about parameter value changes on the monitoring FX chain and
+        // about values set in the audio thread, so polling is the only way to get feedback in
+        // those cases.
+        if self.poll_for_feedback
+            || self.low_latency
+            || self
+                .fx_parameter_descriptor
+                .fx_descriptor
+                .might_resolve_to_monitoring_fx()
+        {
             Some(FeedbackResolution::High)
         } else {
             None
@@ -59,12 +75,16 @@ impl UnresolvedReaperTargetDef for UnresolvedFxParameterTarget {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FxParameterTarget {
     pub is_real_time_ready: bool,
     pub param: FxParameter,
     pub poll_for_feedback: bool,
     pub retrigger: bool,
+    /// Controls the parameter directly in the audio thread if possible.
+    pub low_latency: bool,
+    /// `None` means that the instance default is used.
+    pub thinning: Option<ParameterThinning>,
 }
 
 impl RealearnTarget for FxParameterTarget {
@@ -132,11 +152,26 @@ impl RealearnTarget for FxParameterTarget {
     fn hit(
         &mut self,
         value: ControlValue,
-        _: MappingControlContext,
+        context: MappingControlContext,
     ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        let thinning = self.thinning.unwrap_or_else(|| {
+            context
+                .control_context
+                .instance_state
+                .borrow()
+                .parameter_thinning()
+        });
+        let should_write = BackboneState::target_state()
+            .borrow_mut()
+            .thin_fx_parameter_value(&self.param, thinning, value, Instant::now());
+        if !should_write {
+            // Held back, will be written later.
+            return Ok(HitResponse::ignored());
+        }
         // It's okay to just convert this to a REAPER-normalized value. We don't support
         // values above the maximum (or buggy plug-ins).
-        let v = ReaperNormalizedFxParamValue::new(value.to_unit_value()?.get());
+        let v = ReaperNormalizedFxParamValue::new(value.get());
         self.param
             .set_reaper_normalized_value(v)
             .map_err(|_| "couldn't set FX parameter value")?;
@@ -164,7 +199,7 @@ impl RealearnTarget for FxParameterTarget {
         evt: CompoundChangeEvent,
         _: ControlContext,
     ) -> (bool, Option<AbsoluteValue>) {
-        if self.poll_for_feedback {
+        if self.poll_for_feedback || self.low_latency {
             return (false, None);
         }
         match evt {
@@ -222,6 +257,7 @@ impl RealearnTarget for FxParameterTarget {
             fx_location: self.param.fx().query_index(),
             param_index: self.param.index(),
             retrigger: self.retrigger,
+            low_latency: self.low_latency,
         };
         Some(RealTimeReaperTarget::FxParameter(target))
     }
@@ -231,10 +267,16 @@ impl<'a> Target<'a> for FxParameterTarget {
     type Context = ControlContext<'a>;
 
     fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
-        Some(fx_parameter_absolute_value(
-            &self.param,
-            self.param.reaper_normalized_value(),
-        ))
+        // A value held back by parameter thinning is the value the parameter is about to have.
+        // Relative control must build on it, otherwise small steps would get lost.
+        let pending_value = BackboneState::target_state()
+            .borrow()
+            .pending_fx_parameter_value(&self.param);
+        let value = match pending_value {
+            None => self.param.reaper_normalized_value(),
+            Some(v) => ReaperNormalizedFxParamValue::new(v.get()),
+        };
+        Some(fx_parameter_absolute_value(&self.param, value))
     }
 
     fn control_type(&self, context: Self::Context) -> ControlType {
@@ -248,6 +290,7 @@ pub struct RealTimeFxParameterTarget {
     fx_location: TrackFxLocation,
     param_index: u32,
     retrigger: bool,
+    low_latency: bool,
 }
 
 unsafe impl Send for RealTimeFxParameterTarget {}
@@ -264,14 +307,12 @@ impl RealTimeFxParameterTarget {
             // from the audio hook (control input = MIDI hardware device).
             return false;
         }
-        if !is_rendering {
-            // We want real-time control only during rendering. Because REAPER won't invoke the
-            // change notifications when called in real-time (ReaLearn and maybe also other
-            // control surface implementations relies on those during normal playing to make
-            // feedback work).
-            return false;
-        }
-        true
+        // Except in low-latency mode, we want real-time control only during rendering. Because
+        // REAPER won't invoke the change notifications when called in real-time (ReaLearn and
+        // maybe also other control surface implementations relies on those during normal playing
+        // to make feedback work). In low-latency mode, the user accepts this in favor of lower
+        // latency and we poll for feedback instead.
+        is_rendering || self.low_latency
     }
 
     pub fn hit(&mut self, value: ControlValue) -> Result<(), &'static str> {
@@ -0,0 +1,100 @@
+use crate::domain::{
+    AdditionalFeedbackEvent, BackboneState, Compartment, CompoundChangeEvent, ControlContext,
+    ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+
+#[derive(Debug)]
+pub struct UnresolvedGlobalParameterValueTarget {
+    pub name: String,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedGlobalParameterValueTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        if self.name.is_empty() {
+            return Err("global parameter name not set");
+        }
+        Ok(vec![ReaperTarget::GlobalParameterValue(
+            GlobalParameterValueTarget {
+                name: self.name.clone(),
+            },
+        )])
+    }
+}
+
+/// Sets the value of a global parameter.
+///
+/// Global parameters are not owned by a particular ReaLearn instance but shared among all of them,
+/// so one instance can switch a mode that mappings in other instances react to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalParameterValueTarget {
+    pub name: String,
+}
+
+impl RealearnTarget for GlobalParameterValueTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        BackboneState::target_state()
+            .borrow_mut()
+            .set_global_parameter_value(&self.name, value);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(AdditionalFeedbackEvent::GlobalParameterChanged(e))
+                if e.name == self.name =>
+            {
+                (true, Some(AbsoluteValue::Continuous(e.new_value)))
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::GlobalParameterValue)
+    }
+}
+
+impl<'a> Target<'a> for GlobalParameterValueTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let value = BackboneState::target_state()
+            .borrow()
+            .global_parameter_value(&self.name);
+        Some(AbsoluteValue::Continuous(value))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const GLOBAL_PARAMETER_VALUE_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "ReaLearn: Global parameter value",
+    short_name: "Global parameter",
+    hint: "Shared among all instances",
+    ..DEFAULT_TARGET
+};
@@ -20,6 +20,7 @@ pub struct UnresolvedGoToBookmarkTarget {
     pub bookmark_type: BookmarkType,
     pub bookmark_anchor_type: BookmarkAnchorType,
     pub bookmark_ref: u32,
+    pub bookmark_name: String,
     pub set_time_selection: bool,
     pub set_loop_points: bool,
     pub seek_behavior: SeekBehavior,
@@ -37,6 +38,7 @@ impl UnresolvedReaperTargetDef for UnresolvedGoToBookmarkTarget {
             self.bookmark_type,
             self.bookmark_anchor_type,
             self.bookmark_ref,
+            &self.bookmark_name,
         )?;
         Ok(vec![ReaperTarget::GoToBookmark(GoToBookmarkTarget {
             project,
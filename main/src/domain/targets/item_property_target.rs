@@ -0,0 +1,314 @@
+use crate::domain::ui_util::{convert_bool_to_unit_value, volume_unit_value};
+use crate::domain::{
+    format_value_as_on_off, format_value_as_playback_speed_factor_without_unit,
+    parse_value_from_playback_speed_factor, playback_speed_factor_span, playrate_unit_value,
+    Compartment, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use c_str_macro::c_str;
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
+use realearn_api::persistence::ItemPropertyKind;
+use reaper_high::{Item, PlayRate, Project, Reaper, Take, Volume};
+use reaper_medium::{NormalizedPlayRate, PlaybackSpeedFactor, ReaperVolumeValue};
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+/// Maximum pitch shift in semitones (in both directions) which can be reached with this target.
+const MAX_PITCH: f64 = 24.0;
+
+#[derive(Debug)]
+pub struct UnresolvedItemPropertyTarget {
+    pub property: ItemPropertyKind,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedItemPropertyTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::ItemProperty(ItemPropertyTarget {
+            project: context.context().project_or_current_project(),
+            property: self.property,
+        })])
+    }
+
+    fn can_be_affected_by_change_events(&self) -> bool {
+        // The selected item is looked up whenever needed, so there's no need to re-resolve.
+        false
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        // REAPER doesn't notify us about item selection and item property changes.
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Controls a property of the first selected item in the project (or of its active take).
+///
+/// The item is not determined at resolve time but whenever the target is hit or queried because
+/// REAPER doesn't notify us when the item selection changes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ItemPropertyTarget {
+    pub project: Project,
+    pub property: ItemPropertyKind,
+}
+
+impl RealearnTarget for ItemPropertyTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        match self.property {
+            ItemPropertyKind::Volume => {
+                (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+            }
+            ItemPropertyKind::Mute => (ControlType::AbsoluteContinuous, TargetCharacter::Switch),
+            ItemPropertyKind::TakePitch => (
+                ControlType::AbsoluteContinuousRoundable {
+                    rounding_step_size: UnitValue::new(1.0 / (2.0 * MAX_PITCH)),
+                },
+                TargetCharacter::Continuous,
+            ),
+            ItemPropertyKind::TakePlayRate => (
+                ControlType::AbsoluteContinuousRoundable {
+                    rounding_step_size: UnitValue::new(
+                        1.0 / (playback_speed_factor_span() * 100.0),
+                    ),
+                },
+                TargetCharacter::Continuous,
+            ),
+        }
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        match self.property {
+            ItemPropertyKind::Volume => {
+                context.value_display_preferences().parse_volume_value(text)
+            }
+            ItemPropertyKind::Mute => Err("not supported"),
+            ItemPropertyKind::TakePitch => {
+                let semitones: f64 = text.parse().map_err(|_| "not a decimal value")?;
+                pitch_to_unit_value(semitones)
+                    .try_into()
+                    .map_err(|_| "not in pitch range")
+            }
+            ItemPropertyKind::TakePlayRate => parse_value_from_playback_speed_factor(text),
+        }
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        match self.property {
+            ItemPropertyKind::Volume => context
+                .value_display_preferences()
+                .format_volume_value_without_unit(value),
+            ItemPropertyKind::Mute => format_value_as_on_off(value).to_string(),
+            ItemPropertyKind::TakePitch => format!("{:.2}", unit_value_to_pitch(value)),
+            ItemPropertyKind::TakePlayRate => {
+                format_value_as_playback_speed_factor_without_unit(value)
+            }
+        }
+    }
+
+    fn hide_formatted_value(&self, _: ControlContext) -> bool {
+        self.property != ItemPropertyKind::Mute
+    }
+
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        match self.property {
+            ItemPropertyKind::Volume => context.value_display_preferences().volume_unit(),
+            ItemPropertyKind::Mute => "",
+            ItemPropertyKind::TakePitch => "st",
+            ItemPropertyKind::TakePlayRate => "x",
+        }
+    }
+
+    fn numeric_value_unit(&self, context: ControlContext) -> &'static str {
+        match self.property {
+            ItemPropertyKind::Volume => "dB",
+            _ => self.value_unit(context),
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        match self.property {
+            ItemPropertyKind::Volume => context
+                .value_display_preferences()
+                .format_volume_value(value),
+            ItemPropertyKind::Mute => format_value_as_on_off(value).to_string(),
+            ItemPropertyKind::TakePitch | ItemPropertyKind::TakePlayRate => {
+                self.format_value_generic(value, context)
+            }
+        }
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        let item = self.selected_item().ok_or("no item selected")?;
+        match self.property {
+            ItemPropertyKind::Volume => {
+                let volume =
+                    Volume::try_from_soft_normalized_value(value.get()).unwrap_or(Volume::MIN);
+                set_item_info_value(item, c_str!("D_VOL"), volume.reaper_value().get());
+            }
+            ItemPropertyKind::Mute => {
+                let mute = if value.is_zero() { 0.0 } else { 1.0 };
+                set_item_info_value(item, c_str!("B_MUTE"), mute);
+            }
+            ItemPropertyKind::TakePitch => {
+                let take = item.active_take().ok_or("item has no active take")?;
+                set_take_info_value(take, c_str!("D_PITCH"), unit_value_to_pitch(value));
+            }
+            ItemPropertyKind::TakePlayRate => {
+                let take = item.active_take().ok_or("item has no active take")?;
+                let play_rate =
+                    PlayRate::from_normalized_value(NormalizedPlayRate::new(value.get()));
+                set_take_info_value(
+                    take,
+                    c_str!("D_PLAYRATE"),
+                    play_rate.playback_speed_factor().get(),
+                );
+            }
+        }
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .low()
+                .UpdateItemInProject(item.raw().as_ptr());
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available() && self.selected_item().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let value = self.current_value(context)?.to_unit_value();
+        Some(self.format_value(value, context).into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let item = self.selected_item()?;
+        let value = match self.property {
+            ItemPropertyKind::Volume => self.volume(item).db().get(),
+            ItemPropertyKind::Mute => return None,
+            ItemPropertyKind::TakePitch => get_take_info_value(item, c_str!("D_PITCH"))?,
+            ItemPropertyKind::TakePlayRate => get_take_info_value(item, c_str!("D_PLAYRATE"))?,
+        };
+        Some(NumericValue::Decimal(value))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::ItemProperty)
+    }
+}
+
+impl ItemPropertyTarget {
+    fn selected_item(&self) -> Option<Item> {
+        self.project.first_selected_item()
+    }
+
+    fn volume(&self, item: Item) -> Volume {
+        let raw = get_item_info_value(item, c_str!("D_VOL"));
+        Volume::from_reaper_value(ReaperVolumeValue::new(raw))
+    }
+}
+
+impl<'a> Target<'a> for ItemPropertyTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let item = self.selected_item()?;
+        let val = match self.property {
+            ItemPropertyKind::Volume => volume_unit_value(self.volume(item)),
+            ItemPropertyKind::Mute => {
+                convert_bool_to_unit_value(get_item_info_value(item, c_str!("B_MUTE")) != 0.0)
+            }
+            ItemPropertyKind::TakePitch => {
+                let semitones = get_take_info_value(item, c_str!("D_PITCH"))?;
+                UnitValue::new_clamped(pitch_to_unit_value(semitones))
+            }
+            ItemPropertyKind::TakePlayRate => {
+                let factor = get_take_info_value(item, c_str!("D_PLAYRATE"))?.clamp(
+                    PlaybackSpeedFactor::MIN.get(),
+                    PlaybackSpeedFactor::MAX.get(),
+                );
+                playrate_unit_value(PlayRate::from_playback_speed_factor(
+                    PlaybackSpeedFactor::new(factor),
+                ))
+            }
+        };
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn pitch_to_unit_value(semitones: f64) -> f64 {
+    (semitones + MAX_PITCH) / (2.0 * MAX_PITCH)
+}
+
+fn unit_value_to_pitch(value: UnitValue) -> f64 {
+    value.get() * 2.0 * MAX_PITCH - MAX_PITCH
+}
+
+/// `key` must be an item attribute name as documented for `GetMediaItemInfo_Value`.
+fn get_item_info_value(item: Item, key: &CStr) -> f64 {
+    unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .GetMediaItemInfo_Value(item.raw().as_ptr(), key.as_ptr())
+    }
+}
+
+fn set_item_info_value(item: Item, key: &CStr, value: f64) {
+    unsafe {
+        Reaper::get().medium_reaper().low().SetMediaItemInfo_Value(
+            item.raw().as_ptr(),
+            key.as_ptr(),
+            value,
+        );
+    }
+}
+
+/// Returns `None` if the item doesn't have an active take.
+fn get_take_info_value(item: Item, key: &CStr) -> Option<f64> {
+    let take = item.active_take()?;
+    let value = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .GetMediaItemTakeInfo_Value(take.raw().as_ptr(), key.as_ptr())
+    };
+    Some(value)
+}
+
+fn set_take_info_value(take: Take, key: &CStr, value: f64) {
+    unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .SetMediaItemTakeInfo_Value(take.raw().as_ptr(), key.as_ptr(), value);
+    }
+}
+
+pub const ITEM_PROPERTY_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Item: Set property",
+    short_name: "Item property",
+    hint: "Affects first selected item",
+    ..DEFAULT_TARGET
+};
@@ -0,0 +1,174 @@
+use crate::application::BookmarkAnchorType;
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    find_bookmark, format_bool_as_on_off, format_value_as_on_off, Compartment, CompoundChangeEvent,
+    ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{BookmarkType, ChangeEvent, Project};
+use reaper_medium::{AutoSeekBehavior, PositionInSeconds, SetEditCurPosOptions};
+use std::borrow::Cow;
+use std::num::NonZeroU32;
+
+#[derive(Debug)]
+pub struct UnresolvedLoopRegionTarget {
+    pub bookmark_anchor_type: BookmarkAnchorType,
+    pub bookmark_ref: u32,
+    pub bookmark_name: String,
+    pub start_playback: bool,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedLoopRegionTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let project = context.context().project_or_current_project();
+        let res = find_bookmark(
+            project,
+            BookmarkType::Region,
+            self.bookmark_anchor_type,
+            self.bookmark_ref,
+            &self.bookmark_name,
+        )?;
+        Ok(vec![ReaperTarget::LoopRegion(LoopRegionTarget {
+            project,
+            position: NonZeroU32::new(res.index_within_type + 1).unwrap(),
+            start_playback: self.start_playback,
+        })])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        // REAPER doesn't notify us when the loop points change, so we need to poll.
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Sets the loop points to a region, e.g. in order to repeat a section of a backing track.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoopRegionTarget {
+    pub project: Project,
+    // Counts only regions. See the go-to-bookmark target for why we don't use the ID.
+    pub position: NonZeroU32,
+    pub start_playback: bool,
+}
+
+impl LoopRegionTarget {
+    fn region_bounds(&self) -> Option<(PositionInSeconds, PositionInSeconds)> {
+        let res = self
+            .project
+            .find_bookmark_by_type_and_index(BookmarkType::Region, self.position.get() - 1)?;
+        let end_pos = res.basic_info.region_end_position?;
+        Some((res.basic_info.position, end_pos))
+    }
+
+    fn loop_equals_region(&self) -> Option<bool> {
+        let (start_pos, end_pos) = self.region_bounds()?;
+        let is_equal = match self.project.loop_points() {
+            None => false,
+            Some(r) => {
+                positions_are_equal(r.start, start_pos) && positions_are_equal(r.end, end_pos)
+            }
+        };
+        Some(is_equal)
+    }
+}
+
+impl RealearnTarget for LoopRegionTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        let (start_pos, end_pos) = self.region_bounds().ok_or("region not found")?;
+        self.project
+            .set_loop_points(start_pos, end_pos, AutoSeekBehavior::DenyAutoSeek);
+        self.project.enable_repeat();
+        // If we are playing already, we let the playhead reach the region naturally.
+        if self.start_playback && !self.project.is_playing() {
+            self.project.set_edit_cursor_position(
+                start_pos,
+                SetEditCurPosOptions {
+                    move_view: false,
+                    seek_play: true,
+                },
+            );
+            self.project.play();
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available() && self.region_bounds().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::BookmarksChanged(e))
+                if e.project == self.project =>
+            {
+                (true, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_bool_as_on_off(self.loop_equals_region()?).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::LoopRegion)
+    }
+}
+
+impl<'a> Target<'a> for LoopRegionTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let is_equal = self.loop_equals_region()?;
+        Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(
+            is_equal,
+        )))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+/// Positions are floating point numbers, so we don't compare them exactly.
+fn positions_are_equal(a: PositionInSeconds, b: PositionInSeconds) -> bool {
+    (a.get() - b.get()).abs() < 0.001
+}
+
+pub const LOOP_REGION_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Marker/region: Loop region",
+    short_name: "Loop region",
+    ..DEFAULT_TARGET
+};
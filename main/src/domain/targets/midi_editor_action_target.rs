@@ -0,0 +1,299 @@
+use crate::domain::ui_util::{convert_bool_to_unit_value, parse_unit_value_from_percentage};
+use crate::domain::{
+    convert_count_to_step_size, convert_discrete_to_unit_value, convert_unit_to_discrete_value,
+    format_bool_as_on_off, format_value_as_on_off, Compartment, ControlContext,
+    ExtendedProcessorContext, FeedbackResolution, HitResponse, MappingControlContext,
+    RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef,
+    UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target, UnitValue,
+};
+use realearn_api::persistence::MidiEditorAction;
+use reaper_high::{Project, Reaper};
+use reaper_medium::{CommandId, MediaItemTake, RequiredViewMode, SectionId};
+use std::borrow::Cow;
+use std::ptr::null_mut;
+
+/// Section ID of the MIDI editor action list.
+const MIDI_EDITOR_SECTION_ID: u32 = 32060;
+/// "View: Zoom in horizontally"
+const ZOOM_IN_COMMAND_ID: u32 = 1012;
+/// "View: Zoom out horizontally"
+const ZOOM_OUT_COMMAND_ID: u32 = 1011;
+/// "Options: MIDI inputs as step input mode"
+const STEP_INPUT_COMMAND_ID: u32 = 40481;
+
+/// Grid sizes offered by the "Grid size" action, expressed in whole notes.
+const GRID_SIZES: [f64; 8] = [
+    1.0 / 128.0,
+    1.0 / 64.0,
+    1.0 / 32.0,
+    1.0 / 16.0,
+    1.0 / 8.0,
+    1.0 / 4.0,
+    1.0 / 2.0,
+    1.0,
+];
+const GRID_SIZE_LABELS: [&str; 8] = ["1/128", "1/64", "1/32", "1/16", "1/8", "1/4", "1/2", "1"];
+
+#[derive(Debug)]
+pub struct UnresolvedMidiEditorActionTarget {
+    pub action: MidiEditorAction,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedMidiEditorActionTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::MidiEditorAction(
+            MidiEditorActionTarget {
+                project: context.context().project_or_current_project(),
+                action: self.action,
+            },
+        )])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        // The MIDI editor doesn't notify us when it's opened, closed or changed.
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Carries out an action in the MIDI editor which currently has focus.
+///
+/// Resolves even if no MIDI editor is open, but is only available while there is one. This way,
+/// the mapping starts to work as soon as the user opens a MIDI editor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MidiEditorActionTarget {
+    pub project: Project,
+    pub action: MidiEditorAction,
+}
+
+impl RealearnTarget for MidiEditorActionTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        use MidiEditorAction::*;
+        match self.action {
+            ZoomIn | ZoomOut => (
+                ControlType::AbsoluteContinuousRetriggerable,
+                TargetCharacter::Trigger,
+            ),
+            GridSize => (
+                ControlType::AbsoluteDiscrete {
+                    atomic_step_size: convert_count_to_step_size(GRID_SIZES.len() as u32),
+                    is_retriggerable: false,
+                },
+                TargetCharacter::Discrete,
+            ),
+            StepInput => (ControlType::AbsoluteContinuous, TargetCharacter::Switch),
+        }
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        if self.action == MidiEditorAction::GridSize {
+            return self.parse_value_from_discrete_value(text, context);
+        }
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        if self.action == MidiEditorAction::GridSize {
+            return self.parse_value_from_discrete_value(text, context);
+        }
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(
+        &self,
+        input: UnitValue,
+        _: ControlContext,
+    ) -> Result<u32, &'static str> {
+        if self.action != MidiEditorAction::GridSize {
+            return Err("not supported");
+        }
+        Ok(convert_unit_to_discrete_value(input, GRID_SIZES.len() as u32) + 1)
+    }
+
+    fn convert_discrete_value_to_unit_value(
+        &self,
+        value: u32,
+        _: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        if self.action != MidiEditorAction::GridSize {
+            return Err("not supported");
+        }
+        let index = value.saturating_sub(1);
+        Ok(convert_discrete_to_unit_value(
+            index,
+            GRID_SIZES.len() as u32,
+        ))
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        if self.action == MidiEditorAction::GridSize {
+            let index = convert_unit_to_discrete_value(value, GRID_SIZES.len() as u32);
+            return GRID_SIZE_LABELS[index as usize].to_string();
+        }
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        use MidiEditorAction::*;
+        if active_midi_editor_take().is_none() {
+            return Err("no MIDI editor open");
+        }
+        match self.action {
+            ZoomIn | ZoomOut => {
+                if value.to_unit_value()?.is_zero() {
+                    return Ok(HitResponse::ignored());
+                }
+                let command_id = if self.action == ZoomIn {
+                    ZOOM_IN_COMMAND_ID
+                } else {
+                    ZOOM_OUT_COMMAND_ID
+                };
+                invoke_midi_editor_command(command_id)?;
+            }
+            GridSize => {
+                let index = match value.to_absolute_value()? {
+                    AbsoluteValue::Continuous(v) => {
+                        convert_unit_to_discrete_value(v, GRID_SIZES.len() as u32)
+                    }
+                    AbsoluteValue::Discrete(f) => f.actual(),
+                };
+                let grid_size = *GRID_SIZES
+                    .get(index as usize)
+                    .ok_or("grid size not available")?;
+                unsafe {
+                    Reaper::get()
+                        .medium_reaper()
+                        .low()
+                        .SetMIDIEditorGrid(self.project.raw().as_ptr(), grid_size);
+                }
+            }
+            StepInput => {
+                let on = !value.to_unit_value()?.is_zero();
+                if on == step_input_is_enabled() {
+                    return Ok(HitResponse::ignored());
+                }
+                invoke_midi_editor_command(STEP_INPUT_COMMAND_ID)?;
+            }
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        active_midi_editor_take().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        use MidiEditorAction::*;
+        match self.action {
+            ZoomIn | ZoomOut => None,
+            GridSize => Some(GRID_SIZE_LABELS[current_grid_size_index()? as usize].into()),
+            StepInput => Some(format_bool_as_on_off(step_input_is_enabled()).into()),
+        }
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        if self.action != MidiEditorAction::GridSize {
+            return None;
+        }
+        let index = current_grid_size_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::MidiEditorAction)
+    }
+}
+
+impl<'a> Target<'a> for MidiEditorActionTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        use MidiEditorAction::*;
+        match self.action {
+            ZoomIn | ZoomOut => None,
+            GridSize => {
+                let index = current_grid_size_index()?;
+                let max_index = GRID_SIZES.len() as u32 - 1;
+                Some(AbsoluteValue::Discrete(Fraction::new(index, max_index)))
+            }
+            StepInput => Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(
+                step_input_is_enabled(),
+            ))),
+        }
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const MIDI_EDITOR_ACTION_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "MIDI editor: Action",
+    short_name: "MIDI editor action",
+    hint: "Affects the focused MIDI editor",
+    ..DEFAULT_TARGET
+};
+
+fn active_midi_editor_take() -> Option<MediaItemTake> {
+    let reaper = Reaper::get().medium_reaper();
+    let editor = reaper.midi_editor_get_active()?;
+    unsafe { reaper.midi_editor_get_take(editor).ok() }
+}
+
+fn invoke_midi_editor_command(command_id: u32) -> Result<(), &'static str> {
+    Reaper::get()
+        .medium_reaper()
+        .midi_editor_last_focused_on_command(CommandId::new(command_id), RequiredViewMode::Normal)
+        .map_err(|_| "couldn't invoke MIDI editor action")
+}
+
+fn step_input_is_enabled() -> bool {
+    Reaper::get()
+        .medium_reaper()
+        .get_toggle_command_state_ex(
+            SectionId::new(MIDI_EDITOR_SECTION_ID),
+            CommandId::new(STEP_INPUT_COMMAND_ID),
+        )
+        .unwrap_or(false)
+}
+
+/// Returns the index of the offered grid size which is closest to the grid size of the focused
+/// MIDI editor.
+fn current_grid_size_index() -> Option<u32> {
+    let take = active_midi_editor_take()?;
+    let grid_in_quarter_notes = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .MIDI_GetGrid(take.as_ptr(), null_mut(), null_mut())
+    };
+    let grid_in_whole_notes = grid_in_quarter_notes / 4.0;
+    let (index, _) = GRID_SIZES.iter().enumerate().min_by(|(_, a), (_, b)| {
+        let diff_a = (**a - grid_in_whole_notes).abs();
+        let diff_b = (**b - grid_in_whole_notes).abs();
+        diff_a.total_cmp(&diff_b)
+    })?;
+    Some(index as u32)
+}
@@ -9,12 +9,15 @@ use helgoboss_learn::{
     create_raw_midi_events_singleton, AbsoluteValue, ControlType, ControlValue, Fraction,
     MidiSourceValue, RawMidiPattern, Target, UnitValue,
 };
+use reaper_medium::MidiOutputDeviceId;
 use std::convert::TryInto;
 
 #[derive(Debug)]
 pub struct UnresolvedMidiSendTarget {
     pub pattern: RawMidiPattern,
     pub destination: SendMidiDestination,
+    /// Only relevant if destination is [`SendMidiDestination::Device`].
+    pub output_device_id: Option<MidiOutputDeviceId>,
 }
 
 impl UnresolvedReaperTargetDef for UnresolvedMidiSendTarget {
@@ -26,6 +29,7 @@ impl UnresolvedReaperTargetDef for UnresolvedMidiSendTarget {
         Ok(vec![ReaperTarget::SendMidi(MidiSendTarget::new(
             self.pattern.clone(),
             self.destination,
+            self.output_device_id,
         ))])
     }
 
@@ -39,16 +43,22 @@ impl UnresolvedReaperTargetDef for UnresolvedMidiSendTarget {
 pub struct MidiSendTarget {
     pattern: RawMidiPattern,
     destination: SendMidiDestination,
+    output_device_id: Option<MidiOutputDeviceId>,
     // For making basic toggle/relative control possible.
     artificial_value: AbsoluteValue,
 }
 
 impl MidiSendTarget {
-    pub fn new(pattern: RawMidiPattern, destination: SendMidiDestination) -> Self {
+    pub fn new(
+        pattern: RawMidiPattern,
+        destination: SendMidiDestination,
+        output_device_id: Option<MidiOutputDeviceId>,
+    ) -> Self {
         let max_discrete_value = pattern.max_discrete_value();
         Self {
             pattern,
             destination,
+            output_device_id,
             artificial_value: AbsoluteValue::Discrete(Fraction::new(0, max_discrete_value as _)),
         }
     }
@@ -61,6 +71,26 @@ impl MidiSendTarget {
         self.destination
     }
 
+    /// Determines where the MIDI message should actually go.
+    ///
+    /// Sending to a specific device works independently from the feedback output, so this target
+    /// can be used to translate controller input into messages for other hardware.
+    pub fn resolve_destination(
+        &self,
+        midi_feedback_output: Option<MidiDestination>,
+    ) -> Result<MidiDestination, &'static str> {
+        match self.destination {
+            SendMidiDestination::FxOutput => Ok(MidiDestination::FxOutput),
+            SendMidiDestination::FeedbackOutput => {
+                midi_feedback_output.ok_or("no MIDI feedback output set")
+            }
+            SendMidiDestination::Device => {
+                let dev_id = self.output_device_id.ok_or("no MIDI output device set")?;
+                Ok(MidiDestination::Device(dev_id))
+            }
+        }
+    }
+
     pub fn set_artificial_value(&mut self, value: AbsoluteValue) {
         self.artificial_value = value;
     }
@@ -159,20 +189,11 @@ impl RealearnTarget for MidiSendTarget {
         // We arrive here only if controlled via OSC, group interaction (as follower), mapping
         // snapshot or autoload. Sending MIDI in response to incoming MIDI messages is handled
         // directly in the real-time processor.
-        let resolved_destination = match self.destination {
-            SendMidiDestination::FxOutput => MidiDestination::FxOutput,
-            SendMidiDestination::FeedbackOutput => {
-                let feedback_output = context
-                    .control_context
-                    .feedback_output
-                    .ok_or("no feedback output set")?;
-                if let FeedbackOutput::Midi(dest) = feedback_output {
-                    dest
-                } else {
-                    return Err("feedback output is not MIDI");
-                }
-            }
+        let midi_feedback_output = match context.control_context.feedback_output {
+            Some(FeedbackOutput::Midi(dest)) => Some(dest),
+            _ => None,
         };
+        let resolved_destination = self.resolve_destination(midi_feedback_output)?;
         self.artificial_value = value;
         let raw_midi_events =
             create_raw_midi_events_singleton(self.pattern.to_concrete_midi_event(value));
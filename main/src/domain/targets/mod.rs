@@ -64,9 +64,33 @@ pub use fx_preset_target::*;
 mod load_fx_snapshot_target;
 pub use load_fx_snapshot_target::*;
 
+mod save_fx_parameter_snapshot_target;
+pub use save_fx_parameter_snapshot_target::*;
+
+mod recall_fx_parameter_snapshot_target;
+pub use recall_fx_parameter_snapshot_target::*;
+
 mod browse_tracks_target;
 pub use browse_tracks_target::*;
 
+mod navigate_tracks_target;
+pub use navigate_tracks_target::*;
+
+mod browse_project_tabs_target;
+pub use browse_project_tabs_target::*;
+
+mod navigate_project_tabs_target;
+pub use navigate_project_tabs_target::*;
+
+mod render_project_target;
+pub use render_project_target::*;
+
+mod toggle_render_queue_target;
+pub use toggle_render_queue_target::*;
+
+mod midi_editor_action_target;
+pub use midi_editor_action_target::*;
+
 mod browse_fxs_target;
 pub use browse_fxs_target::*;
 
@@ -82,12 +106,24 @@ pub use track_touch_state_target::*;
 mod go_to_bookmark_target;
 pub use go_to_bookmark_target::*;
 
+mod loop_region_target;
+pub use loop_region_target::*;
+
+mod item_property_target;
+pub use item_property_target::*;
+
+mod toolbar_toggle_target;
+pub use toolbar_toggle_target::*;
+
 mod seek_target;
 pub use seek_target::*;
 
 mod track_volume_target;
 pub use track_volume_target::*;
 
+mod track_volume_trim_target;
+pub use track_volume_trim_target::*;
+
 mod track_tool_target;
 pub use track_tool_target::*;
 
@@ -154,6 +190,18 @@ pub use load_mapping_snapshot_target::*;
 mod take_mapping_snapshot_target;
 pub use take_mapping_snapshot_target::*;
 
+mod write_envelope_points_target;
+pub use write_envelope_points_target::*;
+
+mod global_parameter_value_target;
+pub use global_parameter_value_target::*;
+
+mod feedback_brightness_target;
+pub use feedback_brightness_target::*;
+
+mod resync_feedback_target;
+pub use resync_feedback_target::*;
+
 mod enable_mappings_target;
 pub use enable_mappings_target::*;
 
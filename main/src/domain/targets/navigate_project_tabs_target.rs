@@ -0,0 +1,157 @@
+use crate::domain::{
+    current_project_tab_index, project_tab_by_index, project_tab_count, project_tab_label,
+    switch_to_project_tab, Compartment, CompoundChangeEvent, ControlContext,
+    ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target};
+use reaper_high::{ChangeEvent, Reaper};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedNavigateProjectTabsTarget {
+    pub wrap_around: bool,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedNavigateProjectTabsTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::NavigateProjectTabs(
+            NavigateProjectTabsTarget {
+                wrap_around: self.wrap_around,
+            },
+        )])
+    }
+}
+
+/// Switches to the next or previous project tab.
+///
+/// Unlike [`BrowseProjectTabsTarget`](crate::domain::BrowseProjectTabsTarget), this target is
+/// relative only.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NavigateProjectTabsTarget {
+    pub wrap_around: bool,
+}
+
+impl RealearnTarget for NavigateProjectTabsTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::Relative, TargetCharacter::Discrete)
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let increment = match value {
+            ControlValue::RelativeDiscrete(i) => i.get(),
+            ControlValue::RelativeContinuous(i) => i.to_discrete_increment().get(),
+            _ => return Err("needs to be controlled relatively"),
+        };
+        let current_index = current_project_tab_index().ok_or("no current project tab")?;
+        let new_index = calculate_new_tab_index(
+            current_index,
+            increment,
+            project_tab_count(),
+            self.wrap_around,
+        );
+        if new_index == current_index {
+            return Ok(HitResponse::ignored());
+        }
+        let project = project_tab_by_index(new_index).ok_or("project tab not available")?;
+        switch_to_project_tab(project);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::ProjectSwitched(_)) => (true, None),
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(project_tab_label(Reaper::get().current_project()).into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = current_project_tab_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::NavigateProjectTabs)
+    }
+}
+
+impl<'a> Target<'a> for NavigateProjectTabsTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let index = current_project_tab_index()?;
+        let max_index = project_tab_count().saturating_sub(1);
+        Some(AbsoluteValue::Discrete(Fraction::new(index, max_index)))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn calculate_new_tab_index(
+    current_index: u32,
+    increment: i32,
+    tab_count: u32,
+    wrap_around: bool,
+) -> u32 {
+    if tab_count == 0 {
+        return 0;
+    }
+    let tab_count = tab_count as i64;
+    let new_index = current_index as i64 + increment as i64;
+    let new_index = if wrap_around {
+        new_index.rem_euclid(tab_count)
+    } else {
+        new_index.clamp(0, tab_count - 1)
+    };
+    new_index as u32
+}
+
+pub const NAVIGATE_PROJECT_TABS_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Navigate project tabs",
+    short_name: "Navigate project tabs",
+    hint: "Switches to next/previous project tab",
+    ..DEFAULT_TARGET
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_tab_index() {
+        // Given
+        let tab_count = 3;
+        // When
+        let next = calculate_new_tab_index(1, 1, tab_count, false);
+        let clamped = calculate_new_tab_index(2, 1, tab_count, false);
+        let wrapped = calculate_new_tab_index(2, 1, tab_count, true);
+        let wrapped_backwards = calculate_new_tab_index(0, -1, tab_count, true);
+        // Then
+        assert_eq!(next, 2);
+        assert_eq!(clamped, 2);
+        assert_eq!(wrapped, 0);
+        assert_eq!(wrapped_backwards, 2);
+    }
+}
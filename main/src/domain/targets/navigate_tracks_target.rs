@@ -0,0 +1,202 @@
+use crate::domain::{
+    first_selected_track_scoped, get_track_by_scoped_index, get_track_name, scoped_track_count,
+    scoped_track_index, scroll_to_track, select_track_exclusively_scoped, Compartment,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, ScopedTrack,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target};
+use realearn_api::persistence::BrowseTracksMode;
+use reaper_high::{ChangeEvent, Project};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedNavigateTracksTarget {
+    pub scroll_arrange_view: bool,
+    pub scroll_mixer: bool,
+    pub mode: BrowseTracksMode,
+    pub bank_size: u32,
+    pub wrap_around: bool,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedNavigateTracksTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::NavigateTracks(NavigateTracksTarget {
+            project: context.context().project_or_current_project(),
+            scroll_arrange_view: self.scroll_arrange_view,
+            scroll_mixer: self.scroll_mixer,
+            mode: self.mode,
+            bank_size: self.bank_size,
+            wrap_around: self.wrap_around,
+        })])
+    }
+}
+
+/// Moves the track selection by a certain number of tracks (the bank size).
+///
+/// Unlike [`BrowseTracksTarget`](crate::domain::BrowseTracksTarget), this target is relative
+/// only. It never selects the master track and doesn't need to know the total number of tracks
+/// in order to work correctly with encoders, which makes it a good fit for MCU-like surfaces.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NavigateTracksTarget {
+    pub project: Project,
+    pub scroll_arrange_view: bool,
+    pub scroll_mixer: bool,
+    pub mode: BrowseTracksMode,
+    pub bank_size: u32,
+    pub wrap_around: bool,
+}
+
+impl RealearnTarget for NavigateTracksTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::Relative, TargetCharacter::Discrete)
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let increment = match value {
+            ControlValue::RelativeDiscrete(i) => i.get(),
+            ControlValue::RelativeContinuous(i) => i.to_discrete_increment().get(),
+            _ => return Err("needs to be controlled relatively"),
+        };
+        let scope = self.mode.scope();
+        let track_count = scoped_track_count(self.project, scope);
+        if track_count == 0 {
+            return Err("no tracks available");
+        }
+        let current_index = self.current_index();
+        let new_index = calculate_new_track_index(
+            current_index,
+            increment,
+            self.bank_size,
+            track_count,
+            self.wrap_around,
+        );
+        if Some(new_index) == current_index {
+            return Ok(HitResponse::ignored());
+        }
+        let track = get_track_by_scoped_index(self.project, new_index, scope)
+            .ok_or("track not available")?;
+        select_track_exclusively_scoped(&track, self.mode);
+        scroll_to_track(&track, self.scroll_arrange_view, self.scroll_mixer);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::TrackSelectedChanged(e))
+                if e.track.project() == self.project =>
+            {
+                (true, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        match first_selected_track_scoped(self.project, self.mode)? {
+            ScopedTrack::InScope(t) => Some(get_track_name(&t, self.mode.scope()).into()),
+            ScopedTrack::OutOfScope { floor_track } => {
+                let name = get_track_name(&floor_track, self.mode.scope());
+                Some(format!("After {}", name).into())
+            }
+        }
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = self.current_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::NavigateTracks)
+    }
+}
+
+impl<'a> Target<'a> for NavigateTracksTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let track_count = scoped_track_count(self.project, self.mode.scope());
+        let index = self.current_index().unwrap_or(0);
+        let fraction = Fraction::new(index, track_count.saturating_sub(1));
+        Some(AbsoluteValue::Discrete(fraction))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+impl NavigateTracksTarget {
+    /// Returns the scoped index of the currently selected track.
+    ///
+    /// If the selected track is out of scope, the index of the closest in-scope track above it
+    /// is returned.
+    fn current_index(&self) -> Option<u32> {
+        let scope = self.mode.scope();
+        match first_selected_track_scoped(self.project, self.mode)? {
+            ScopedTrack::InScope(t) => scoped_track_index(&t, scope),
+            ScopedTrack::OutOfScope { floor_track } => scoped_track_index(&floor_track, scope),
+        }
+    }
+}
+
+/// Calculates the index of the track to be selected next.
+///
+/// If no track is selected at the moment, navigation starts from the first track (forward) or
+/// last track (backward).
+fn calculate_new_track_index(
+    current_index: Option<u32>,
+    increment: i32,
+    bank_size: u32,
+    track_count: u32,
+    wrap_around: bool,
+) -> u32 {
+    let track_count = track_count as i64;
+    let current_index = match current_index {
+        None => {
+            return if increment < 0 {
+                (track_count - 1) as u32
+            } else {
+                0
+            };
+        }
+        Some(i) => i as i64,
+    };
+    let amount = increment as i64 * bank_size.max(1) as i64;
+    let new_index = current_index + amount;
+    let new_index = if wrap_around {
+        new_index.rem_euclid(track_count)
+    } else {
+        new_index.clamp(0, track_count - 1)
+    };
+    new_index as u32
+}
+
+pub const NAVIGATE_TRACKS_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Navigate tracks",
+    short_name: "Navigate tracks",
+    hint: "Moves track selection by bank size",
+    supports_track_scrolling: true,
+    ..DEFAULT_TARGET
+};
@@ -0,0 +1,118 @@
+use crate::domain::{
+    Compartment, ControlContext, ExtendedProcessorContext, FxDescriptor, FxParameterSnapshotId,
+    HitResponse, MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+use reaper_high::{Fx, Project, Track};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct UnresolvedRecallFxParameterSnapshotTarget {
+    pub fx_descriptor: FxDescriptor,
+    pub snapshot_id: FxParameterSnapshotId,
+    pub glide_time: Duration,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedRecallFxParameterSnapshotTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .fx_descriptor
+            .resolve(context, compartment)?
+            .into_iter()
+            .map(|fx| {
+                ReaperTarget::RecallFxParameterSnapshot(RecallFxParameterSnapshotTarget {
+                    fx,
+                    snapshot_id: self.snapshot_id,
+                    glide_time: self.glide_time,
+                })
+            })
+            .collect();
+        Ok(targets)
+    }
+
+    fn fx_descriptor(&self) -> Option<&FxDescriptor> {
+        Some(&self.fx_descriptor)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecallFxParameterSnapshotTarget {
+    pub fx: Fx,
+    pub snapshot_id: FxParameterSnapshotId,
+    pub glide_time: Duration,
+}
+
+impl RealearnTarget for RecallFxParameterSnapshotTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if !value.is_on() {
+            return Ok(HitResponse::ignored());
+        }
+        context
+            .control_context
+            .instance_state
+            .borrow_mut()
+            .fx_parameter_snapshot_container_mut()
+            .recall_snapshot(self.snapshot_id, &self.fx, self.glide_time)?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.fx.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.fx.project()
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.fx.track()
+    }
+
+    fn fx(&self) -> Option<&Fx> {
+        Some(&self.fx)
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::RecallFxParameterSnapshot)
+    }
+}
+
+impl<'a> Target<'a> for RecallFxParameterSnapshotTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        None
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const RECALL_FX_PARAMETER_SNAPSHOT_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "FX: Recall parameter snapshot",
+    short_name: "Recall FX parameter snapshot",
+    supports_track: true,
+    supports_fx: true,
+    ..DEFAULT_TARGET
+};
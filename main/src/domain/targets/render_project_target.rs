@@ -0,0 +1,92 @@
+use crate::domain::{
+    format_value_as_on_off, Compartment, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{Project, Reaper};
+use reaper_medium::CommandId;
+
+/// "File: Render project, using the most recent render settings"
+const RENDER_WITH_MOST_RECENT_SETTINGS_COMMAND_ID: u32 = 41824;
+
+#[derive(Debug)]
+pub struct UnresolvedRenderProjectTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedRenderProjectTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::RenderProject(RenderProjectTarget {
+            project: context.context().project_or_current_project(),
+        })])
+    }
+}
+
+/// Renders the project using the most recent render settings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderProjectTarget {
+    pub project: Project,
+}
+
+impl RealearnTarget for RenderProjectTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(RENDER_WITH_MOST_RECENT_SETTINGS_COMMAND_ID))
+            .invoke_as_trigger(Some(self.project))?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::RenderProject)
+    }
+}
+
+impl<'a> Target<'a> for RenderProjectTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        None
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const RENDER_PROJECT_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Render",
+    short_name: "Render",
+    hint: "Uses most recent render settings",
+    supports_feedback: false,
+    ..DEFAULT_TARGET
+};
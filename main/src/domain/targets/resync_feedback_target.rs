@@ -0,0 +1,76 @@
+use crate::domain::{
+    Compartment, ControlContext, ExtendedProcessorContext, HitResponse, MappingControlContext,
+    RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef,
+    UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+
+#[derive(Debug)]
+pub struct UnresolvedResyncFeedbackTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedResyncFeedbackTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::ResyncFeedback(ResyncFeedbackTarget)])
+    }
+}
+
+/// Sends the feedback of all mappings of this instance again, e.g. after the controller missed
+/// some messages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResyncFeedbackTarget;
+
+impl RealearnTarget for ResyncFeedbackTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        context
+            .control_context
+            .instance_state
+            .borrow()
+            .request_feedback_resync();
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::ResyncFeedback)
+    }
+}
+
+impl<'a> Target<'a> for ResyncFeedbackTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        None
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const RESYNC_FEEDBACK_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "ReaLearn: Resync feedback",
+    short_name: "Resync feedback",
+    hint: "Sends the feedback of all mappings again",
+    ..DEFAULT_TARGET
+};
@@ -1,8 +1,9 @@
 use crate::domain::{
-    format_value_as_pan, get_track_routes, pan_unit_value, parse_value_from_pan, Compartment,
-    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
-    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
-    TargetTypeDef, TrackRouteDescriptor, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+    format_value_as_pan, get_track_routes, pan_unit_value, parse_value_from_pan,
+    route_is_affected_by_change_of, Compartment, CompoundChangeEvent, ControlContext,
+    ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TargetCharacter, TargetTypeDef, TrackRouteDescriptor,
+    UnresolvedReaperTargetDef, DEFAULT_TARGET,
 };
 use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
 use reaper_high::{ChangeEvent, Pan, Project, Track, TrackRoute};
@@ -106,14 +107,17 @@ impl RealearnTarget for RoutePanTarget {
     ) -> (bool, Option<AbsoluteValue>) {
         match evt {
             CompoundChangeEvent::Reaper(ChangeEvent::TrackRoutePanChanged(e))
-                if e.route == self.route =>
+                if route_is_affected_by_change_of(&self.route, &e.route) =>
             {
-                (
-                    true,
+                // The reported value might belong to another send between the same tracks.
+                let value = if e.route == self.route {
                     Some(AbsoluteValue::Continuous(pan_unit_value(
                         Pan::from_reaper_value(e.new_value),
-                    ))),
-                )
+                    )))
+                } else {
+                    None
+                };
+                (true, value)
             }
             _ => (false, None),
         }
@@ -1,11 +1,9 @@
-use crate::domain::ui_util::{
-    format_value_as_db, format_value_as_db_without_unit, parse_value_from_db, volume_unit_value,
-};
+use crate::domain::ui_util::volume_unit_value;
 use crate::domain::{
-    get_track_routes, Compartment, CompoundChangeEvent, ControlContext, ExtendedProcessorContext,
-    HitResponse, MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType,
-    TargetCharacter, TargetTypeDef, TrackRouteDescriptor, UnresolvedReaperTargetDef,
-    DEFAULT_TARGET,
+    get_track_routes, route_is_affected_by_change_of, Compartment, CompoundChangeEvent,
+    ControlContext, ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget,
+    ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef, TrackRouteDescriptor,
+    UnresolvedReaperTargetDef, DEFAULT_TARGET,
 };
 use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
 use reaper_high::{ChangeEvent, Project, Track, TrackRoute, Volume};
@@ -46,12 +44,18 @@ impl RealearnTarget for RouteVolumeTarget {
         (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
     }
 
-    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
-        parse_value_from_db(text)
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        context.value_display_preferences().parse_volume_value(text)
     }
 
-    fn format_value_without_unit(&self, value: UnitValue, _: ControlContext) -> String {
-        format_value_as_db_without_unit(value)
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        context
+            .value_display_preferences()
+            .format_volume_value_without_unit(value)
     }
 
     fn hide_formatted_value(&self, _: ControlContext) -> bool {
@@ -62,12 +66,18 @@ impl RealearnTarget for RouteVolumeTarget {
         true
     }
 
-    fn value_unit(&self, _: ControlContext) -> &'static str {
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        context.value_display_preferences().volume_unit()
+    }
+
+    fn numeric_value_unit(&self, _: ControlContext) -> &'static str {
         "dB"
     }
 
-    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
-        format_value_as_db(value)
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        context
+            .value_display_preferences()
+            .format_volume_value(value)
     }
 
     fn hit(
@@ -105,7 +115,7 @@ impl RealearnTarget for RouteVolumeTarget {
     ) -> (bool, Option<AbsoluteValue>) {
         match evt {
             CompoundChangeEvent::Reaper(ChangeEvent::TrackRouteVolumeChanged(e))
-                if e.route == self.route =>
+                if route_is_affected_by_change_of(&self.route, &e.route) =>
             {
                 (
                     true,
@@ -124,8 +134,9 @@ impl RealearnTarget for RouteVolumeTarget {
         }
     }
 
-    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
-        Some(self.volume().ok()?.to_string().into())
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let prefs = context.value_display_preferences();
+        Some(prefs.format_volume(self.volume().ok()?).into())
     }
 
     fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
@@ -97,8 +97,13 @@ impl RealearnTarget for SeekTarget {
         }
     }
 
-    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
-        Some(format!("{:.3} s", self.corrected_display_pos().get()).into())
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let text = context.value_display_preferences().format_position(
+            self.project,
+            self.corrected_display_pos(),
+            self.project.play_or_edit_cursor_position(),
+        );
+        Some(text.into())
     }
 
     fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
@@ -0,0 +1,128 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    format_bool_as_on_off, format_value_as_on_off, AdditionalFeedbackEvent, Compartment,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{Action, Project, Reaper};
+use reaper_medium::CommandId;
+use std::borrow::Cow;
+
+/// "File: Show queued renders"
+const SHOW_RENDER_QUEUE_COMMAND_ID: u32 = 40929;
+
+#[derive(Debug)]
+pub struct UnresolvedToggleRenderQueueTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedToggleRenderQueueTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::ToggleRenderQueue(
+            ToggleRenderQueueTarget {
+                project: context.context().project_or_current_project(),
+            },
+        )])
+    }
+}
+
+/// Shows or hides REAPER's render queue dialog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToggleRenderQueueTarget {
+    pub project: Project,
+}
+
+impl RealearnTarget for ToggleRenderQueueTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        show_render_queue_action().invoke_as_trigger(Some(self.project))?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(AdditionalFeedbackEvent::ActionInvoked(e))
+                if e.command_id == CommandId::new(SHOW_RENDER_QUEUE_COMMAND_ID) =>
+            {
+                (true, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_bool_as_on_off(render_queue_is_shown()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::ToggleRenderQueue)
+    }
+}
+
+impl<'a> Target<'a> for ToggleRenderQueueTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(
+            render_queue_is_shown(),
+        )))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn show_render_queue_action() -> Action {
+    Reaper::get()
+        .main_section()
+        .action_by_command_id(CommandId::new(SHOW_RENDER_QUEUE_COMMAND_ID))
+}
+
+/// Returns `false` if REAPER doesn't report a toggle state for the render queue action.
+fn render_queue_is_shown() -> bool {
+    show_render_queue_action()
+        .is_on()
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+pub const TOGGLE_RENDER_QUEUE_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Show/hide render queue",
+    short_name: "Render queue",
+    ..DEFAULT_TARGET
+};
@@ -0,0 +1,139 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    format_bool_as_on_off, format_value_as_on_off, AdditionalFeedbackEvent, Compartment,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{Action, Project, Reaper};
+use reaper_medium::CommandId;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedToolbarToggleTarget {
+    pub action: Action,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedToolbarToggleTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::ToolbarToggle(ToolbarToggleTarget {
+            action: self.action.clone(),
+            project: context.context().project_or_current_project(),
+        })])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        // Toggle states can change without the action being invoked (e.g. when loading a project
+        // or when an extension registers its actions after ReaLearn has been loaded). Polling makes
+        // sure the controller always mirrors the toolbar.
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Sets the toggle state of a REAPER action (as displayed by toolbar buttons) to a desired state.
+///
+/// In contrast to the action target, the action is only invoked if its current toggle state
+/// differs from the desired one, so controller and toolbar can't get out of sync.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolbarToggleTarget {
+    pub action: Action,
+    pub project: Project,
+}
+
+impl RealearnTarget for ToolbarToggleTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Switch)
+    }
+
+    fn open(&self, _: ControlContext) {
+        // Just open action window
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(40605))
+            .invoke_as_trigger(Some(self.project))
+            .expect("built-in action should exist");
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let desired_state = !value.to_unit_value()?.is_zero();
+        let current_state = self.toggle_state()?;
+        if current_state == desired_state {
+            return Ok(HitResponse::ignored());
+        }
+        self.action.invoke_as_trigger(Some(self.project))?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.action.is_available() && self.toggle_state().is_ok()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(AdditionalFeedbackEvent::ActionInvoked(e))
+                if Ok(e.command_id) == self.action.command_id() =>
+            {
+                (true, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_bool_as_on_off(self.toggle_state().ok()?).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::ToolbarToggle)
+    }
+}
+
+impl<'a> Target<'a> for ToolbarToggleTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let state = self.toggle_state().ok()?;
+        Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(state)))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+impl ToolbarToggleTarget {
+    fn toggle_state(&self) -> Result<bool, &'static str> {
+        self.action
+            .is_on()
+            .map_err(|_| "action not available")?
+            .ok_or("action doesn't report a toggle state")
+    }
+}
+
+pub const TOOLBAR_TOGGLE_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Project: Set toolbar toggle state",
+    short_name: "Toolbar toggle",
+    hint: "Only for actions with toggle state",
+    ..DEFAULT_TARGET
+};
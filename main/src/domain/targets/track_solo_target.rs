@@ -8,7 +8,8 @@ use crate::domain::{
     UnresolvedReaperTargetDef, DEFAULT_TARGET,
 };
 use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
-use reaper_high::{ChangeEvent, Project, Track};
+use reaper_high::{ChangeEvent, Project, Reaper, Track};
+use reaper_medium::{reaper_str, SoloMode, TrackAttributeKey};
 use std::borrow::Cow;
 
 #[derive(Debug)]
@@ -16,6 +17,7 @@ pub struct UnresolvedTrackSoloTarget {
     pub track_descriptor: TrackDescriptor,
     pub exclusivity: TrackExclusivity,
     pub behavior: SoloBehavior,
+    pub solo_defeat: bool,
     pub gang_behavior: TrackGangBehavior,
 }
 
@@ -33,6 +35,7 @@ impl UnresolvedReaperTargetDef for UnresolvedTrackSoloTarget {
                         track,
                         exclusivity: self.exclusivity,
                         behavior: self.behavior,
+                        solo_defeat: self.solo_defeat,
                         gang_behavior: self.gang_behavior,
                     })
                 })
@@ -49,10 +52,20 @@ impl UnresolvedReaperTargetDef for UnresolvedTrackSoloTarget {
 pub struct TrackSoloTarget {
     pub track: Track,
     pub behavior: SoloBehavior,
+    /// If enabled, the solo defeat flag of the track is set when soloing and cleared when
+    /// unsoloing, so the track is not muted when other tracks are soloed.
+    pub solo_defeat: bool,
     pub exclusivity: TrackExclusivity,
     pub gang_behavior: TrackGangBehavior,
 }
 
+impl TrackSoloTarget {
+    fn is_soloed(&self) -> bool {
+        solo_mode_matches(self.track.solo_mode(), self.behavior)
+            && (!self.solo_defeat || has_solo_defeat(&self.track))
+    }
+}
+
 impl RealearnTarget for TrackSoloTarget {
     fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
         get_control_type_and_character_for_track_exclusivity(self.exclusivity)
@@ -68,12 +81,21 @@ impl RealearnTarget for TrackSoloTarget {
         _: MappingControlContext,
     ) -> Result<HitResponse, &'static str> {
         let value = value.to_unit_value()?;
+        let project = self.track.project();
         with_gang_behavior(
-            self.track.project(),
+            project,
             self.gang_behavior,
             &TRACK_SOLO_TARGET,
             |gang_behavior, grouping_behavior| {
                 with_solo_behavior(self.behavior, || {
+                    // Soloing might affect other tracks as well (gang, grouping, exclusivity), so
+                    // we remember the previous solo states in order to apply solo defeat to all
+                    // of them.
+                    let previously_soloed_tracks = if self.solo_defeat {
+                        project.tracks().filter(|t| t.is_solo()).collect()
+                    } else {
+                        vec![]
+                    };
                     change_track_prop(
                         &self.track,
                         self.exclusivity,
@@ -81,6 +103,9 @@ impl RealearnTarget for TrackSoloTarget {
                         |t| t.solo(gang_behavior, grouping_behavior),
                         |t| t.unsolo(gang_behavior, grouping_behavior),
                     );
+                    if self.solo_defeat {
+                        sync_solo_defeat(project, &self.track, &previously_soloed_tracks);
+                    }
                 });
             },
         )?;
@@ -112,10 +137,12 @@ impl RealearnTarget for TrackSoloTarget {
             CompoundChangeEvent::Reaper(ChangeEvent::TrackSoloChanged(e))
                 if e.track == self.track =>
             {
+                // The event only tells us whether the track is soloed at all, but we are
+                // interested in the particular solo mode.
                 (
                     true,
                     Some(AbsoluteValue::Continuous(track_solo_unit_value(
-                        e.new_value,
+                        self.is_soloed(),
                     ))),
                 )
             }
@@ -136,7 +163,7 @@ impl<'a> Target<'a> for TrackSoloTarget {
     type Context = ControlContext<'a>;
 
     fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
-        let val = track_solo_unit_value(self.track.is_solo());
+        let val = track_solo_unit_value(self.is_soloed());
         Some(AbsoluteValue::Continuous(val))
     }
 
@@ -155,3 +182,55 @@ pub const TRACK_SOLO_TARGET: TargetTypeDef = TargetTypeDef {
     supports_track_grouping_only_gang_behavior: true,
     ..DEFAULT_TARGET
 };
+
+/// Returns whether the given solo mode corresponds to the one which the target would set.
+fn solo_mode_matches(mode: SoloMode, behavior: SoloBehavior) -> bool {
+    use SoloMode::*;
+    let is_in_place = match mode {
+        Off => return false,
+        SoloIgnoreRouting | SafeSoloIgnoreRouting => false,
+        SoloInPlace | SafeSoloInPlace => true,
+        // Unknown solo mode, at least it's soloed
+        _ => return true,
+    };
+    match behavior {
+        SoloBehavior::InPlace => is_in_place,
+        SoloBehavior::IgnoreRouting => !is_in_place,
+        SoloBehavior::ReaperPreference => true,
+    }
+}
+
+/// Sets the solo defeat flag of the given track and of all tracks whose solo state has changed
+/// according to their current solo state.
+fn sync_solo_defeat(project: Project, track: &Track, previously_soloed_tracks: &[Track]) {
+    for t in project.tracks() {
+        let is_soloed = t.is_solo();
+        if &t == track || is_soloed != previously_soloed_tracks.contains(&t) {
+            set_solo_defeat(&t, is_soloed);
+        }
+    }
+}
+
+fn has_solo_defeat(track: &Track) -> bool {
+    let value = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .get_media_track_info_value(track.raw(), solo_defeat_key())
+    };
+    value != 0.0
+}
+
+fn set_solo_defeat(track: &Track, solo_defeat: bool) {
+    let value = if solo_defeat { 1.0 } else { 0.0 };
+    let _ = unsafe {
+        Reaper::get().medium_reaper().set_media_track_info_value(
+            track.raw(),
+            solo_defeat_key(),
+            value,
+        )
+    };
+}
+
+fn solo_defeat_key() -> TrackAttributeKey<'static> {
+    TrackAttributeKey::Custom(Cow::Borrowed(reaper_str!("B_SOLO_DEFEAT")))
+}
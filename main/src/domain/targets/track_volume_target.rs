@@ -1,6 +1,4 @@
-use crate::domain::ui_util::{
-    format_value_as_db, format_value_as_db_without_unit, parse_value_from_db, volume_unit_value,
-};
+use crate::domain::ui_util::volume_unit_value;
 use crate::domain::{
     get_effective_tracks, with_gang_behavior, Compartment, CompoundChangeEvent, ControlContext,
     ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
@@ -52,12 +50,18 @@ impl RealearnTarget for TrackVolumeTarget {
         (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
     }
 
-    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
-        parse_value_from_db(text)
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        context.value_display_preferences().parse_volume_value(text)
     }
 
-    fn format_value_without_unit(&self, value: UnitValue, _: ControlContext) -> String {
-        format_value_as_db_without_unit(value)
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        context
+            .value_display_preferences()
+            .format_volume_value_without_unit(value)
     }
 
     fn hide_formatted_value(&self, _: ControlContext) -> bool {
@@ -68,12 +72,18 @@ impl RealearnTarget for TrackVolumeTarget {
         true
     }
 
-    fn value_unit(&self, _: ControlContext) -> &'static str {
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        context.value_display_preferences().volume_unit()
+    }
+
+    fn numeric_value_unit(&self, _: ControlContext) -> &'static str {
         "dB"
     }
 
-    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
-        format_value_as_db(value)
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        context
+            .value_display_preferences()
+            .format_volume_value(value)
     }
 
     fn hit(
@@ -129,8 +139,9 @@ impl RealearnTarget for TrackVolumeTarget {
         }
     }
 
-    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
-        Some(self.volume().to_string().into())
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let prefs = context.value_display_preferences();
+        Some(prefs.format_volume(self.volume()).into())
     }
 
     fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
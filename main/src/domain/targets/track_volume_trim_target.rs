@@ -0,0 +1,227 @@
+use crate::domain::{
+    get_effective_tracks, AdditionalFeedbackEvent, BackboneState, Compartment, CompoundChangeEvent,
+    ControlContext, ExtendedProcessorContext, HitResponse, InstanceId, MappingControlContext,
+    RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter, TargetTypeDef,
+    TrackDescriptor, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue, BASE_EPSILON,
+};
+use reaper_high::{GroupingBehavior, Project, Track, Volume};
+use reaper_medium::{Db, GangBehavior};
+use std::borrow::Cow;
+
+/// The trim range in both directions.
+const MAX_TRIM_DB: f64 = 24.0;
+
+#[derive(Debug)]
+pub struct UnresolvedTrackVolumeTrimTarget {
+    pub track_descriptor: TrackDescriptor,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTrackVolumeTrimTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        // In contrast to most other track targets, this one resolves to one single target
+        // containing all matching tracks. That's important because the trim amount is a property
+        // of the whole group, not of each track.
+        let tracks = get_effective_tracks(context, &self.track_descriptor.track, compartment)?;
+        let target = TrackVolumeTrimTarget {
+            key: volume_trim_key(context.control_context.instance_id, &tracks),
+            tracks,
+        };
+        Ok(vec![ReaperTarget::TrackVolumeTrim(target)])
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+}
+
+/// Trims the volume of multiple tracks relatively, similar to a VCA fader.
+///
+/// The current trim amount is held by ReaLearn and identified by the instance and the resolved
+/// tracks, so instances don't interfere with each other and a track expression which resolves to
+/// other tracks (e.g. "selected track") doesn't take over the trim of the previous tracks. Whenever it changes, only the difference is applied to each track, so the
+/// balance between the tracks is retained and multiple trims affecting the same track add up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackVolumeTrimTarget {
+    pub tracks: Vec<Track>,
+    pub key: String,
+}
+
+impl RealearnTarget for TrackVolumeTrimTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+    }
+
+    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        let db: f64 = text.parse().map_err(|_| "not a decimal value")?;
+        Ok(trim_db_to_unit_value(db))
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, _: ControlContext) -> String {
+        format!("{:.2}", unit_value_to_trim_db(value))
+    }
+
+    fn hide_formatted_value(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn value_unit(&self, _: ControlContext) -> &'static str {
+        "dB"
+    }
+
+    fn numeric_value_unit(&self, _: ControlContext) -> &'static str {
+        "dB"
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let new_trim = unit_value_to_trim_db(value.to_unit_value()?);
+        let old_trim = BackboneState::target_state()
+            .borrow_mut()
+            .set_volume_trim(&self.key, new_trim);
+        let delta = new_trim - old_trim;
+        if delta.abs() < BASE_EPSILON {
+            return Ok(HitResponse::ignored());
+        }
+        for track in &self.tracks {
+            let old_db = track.volume().db();
+            if old_db == Db::MINUS_INF {
+                // Like with a VCA, a track which is silent stays silent.
+                continue;
+            }
+            let new_db = Db::new((old_db.get() + delta).max(Db::MINUS_INF.get()));
+            track.set_volume(
+                Volume::from_db(new_db),
+                GangBehavior::DenyGang,
+                GroupingBehavior::PreventGrouping,
+            );
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        !self.tracks.is_empty() && self.tracks.iter().all(|t| t.is_available())
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.tracks.first().map(|t| t.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.tracks.first()
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(AdditionalFeedbackEvent::VolumeTrimChanged(e))
+                if e.key == self.key =>
+            {
+                (
+                    true,
+                    Some(AbsoluteValue::Continuous(trim_db_to_unit_value(
+                        e.new_value,
+                    ))),
+                )
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format!("{:+.2} dB", self.trim()).into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        Some(NumericValue::Decimal(self.trim()))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TrackVolumeTrim)
+    }
+}
+
+impl TrackVolumeTrimTarget {
+    fn trim(&self) -> f64 {
+        BackboneState::target_state()
+            .borrow()
+            .volume_trim(&self.key)
+    }
+}
+
+impl<'a> Target<'a> for TrackVolumeTrimTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = trim_db_to_unit_value(self.trim());
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+/// Builds the key under which the trim amount of the given tracks is stored.
+fn volume_trim_key(instance_id: &InstanceId, tracks: &[Track]) -> String {
+    let mut guids: Vec<_> = tracks
+        .iter()
+        .map(|t| t.guid().to_string_without_braces())
+        .collect();
+    // The order of the tracks doesn't matter for the trim.
+    guids.sort();
+    format!("{instance_id}:{}", guids.join(","))
+}
+
+fn unit_value_to_trim_db(value: UnitValue) -> f64 {
+    (value.get() * 2.0 - 1.0) * MAX_TRIM_DB
+}
+
+fn trim_db_to_unit_value(db: f64) -> UnitValue {
+    let clamped = db.clamp(-MAX_TRIM_DB, MAX_TRIM_DB);
+    UnitValue::new_clamped((clamped / MAX_TRIM_DB + 1.0) / 2.0)
+}
+
+pub const TRACK_VOLUME_TRIM_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "Track: Trim volume (VCA)",
+    short_name: "Volume trim",
+    supports_track: true,
+    ..DEFAULT_TARGET
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_conversion() {
+        // Given
+        let center = UnitValue::new(0.5);
+        // When
+        let center_db = unit_value_to_trim_db(center);
+        let max_db = unit_value_to_trim_db(UnitValue::MAX);
+        let back = trim_db_to_unit_value(-12.0);
+        let clamped = trim_db_to_unit_value(100.0);
+        // Then
+        assert_eq!(center_db, 0.0);
+        assert_eq!(max_db, MAX_TRIM_DB);
+        assert_eq!(back, UnitValue::new(0.25));
+        assert_eq!(clamped, UnitValue::MAX);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::domain::{
+    Compartment, CompoundMappingTarget, ControlContext, ExtendedProcessorContext, GroupId,
+    HitInstruction, HitInstructionContext, HitInstructionResponse, HitResponse,
+    MappingControlContext, MappingId, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetTypeDef, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use c_str_macro::c_str;
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target};
+use reaper_high::{Reaper, Track};
+use reaper_low::raw;
+use std::ffi::CStr;
+use std::ptr::null_mut;
+
+#[derive(Debug)]
+pub struct UnresolvedWriteEnvelopePointsTarget;
+
+impl UnresolvedReaperTargetDef for UnresolvedWriteEnvelopePointsTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: Compartment,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::WriteEnvelopePoints(
+            WriteEnvelopePointsTarget,
+        )])
+    }
+}
+
+/// Writes the current target values of all other mappings in the same group as envelope points
+/// at the edit cursor position.
+///
+/// Supports targets which have a corresponding automation envelope: Track volume, track pan and
+/// FX parameter value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriteEnvelopePointsTarget;
+
+impl RealearnTarget for WriteEnvelopePointsTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if !value.is_on() {
+            return Ok(HitResponse::ignored());
+        }
+        let instruction = WriteEnvelopePointsInstruction {
+            group_id: context.mapping_data.group_id,
+            mapping_id: context.mapping_data.mapping_id,
+        };
+        Ok(HitResponse::hit_instruction(Box::new(instruction)))
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::WriteEnvelopePoints)
+    }
+}
+
+impl<'a> Target<'a> for WriteEnvelopePointsTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        None
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const WRITE_ENVELOPE_POINTS_TARGET: TargetTypeDef = TargetTypeDef {
+    name: "ReaLearn: Write envelope points",
+    short_name: "Write envelope points",
+    hint: "Writes values of other mappings in same group",
+    ..DEFAULT_TARGET
+};
+
+struct WriteEnvelopePointsInstruction {
+    group_id: GroupId,
+    /// The mapping which triggered the instruction. Not written.
+    mapping_id: MappingId,
+}
+
+impl HitInstruction for WriteEnvelopePointsInstruction {
+    fn execute(self: Box<Self>, context: HitInstructionContext) -> HitInstructionResponse {
+        let mut point_count = 0;
+        for m in context.mappings.values() {
+            if m.id() == self.mapping_id || m.group_id() != self.group_id || !m.control_is_enabled()
+            {
+                continue;
+            }
+            for t in m.targets() {
+                if let CompoundMappingTarget::Reaper(t) = t {
+                    if write_envelope_point(t).is_ok() {
+                        point_count += 1;
+                    }
+                }
+            }
+        }
+        if point_count == 0 {
+            return HitInstructionResponse::Ignored;
+        }
+        Reaper::get().medium_reaper().update_arrange();
+        HitInstructionResponse::CausedEffect(vec![])
+    }
+}
+
+/// Inserts a point with the current value of the given target at the edit cursor position.
+fn write_envelope_point(target: &ReaperTarget) -> Result<(), &'static str> {
+    let (track, envelope, value) = match target {
+        ReaperTarget::TrackVolume(t) => {
+            let envelope = get_track_envelope_by_name(&t.track, c_str!("Volume"))?;
+            let volume = t.track.volume().reaper_value().get();
+            (&t.track, envelope, scale_to_envelope_mode(envelope, volume))
+        }
+        ReaperTarget::TrackPan(t) => {
+            let envelope = get_track_envelope_by_name(&t.track, c_str!("Pan"))?;
+            // Pan envelopes are inverted (positive values mean left).
+            let pan = -t.track.pan().reaper_value().get();
+            (&t.track, envelope, pan)
+        }
+        ReaperTarget::FxParameter(t) => {
+            let fx = t.param.fx();
+            // Monitoring FX don't have envelopes.
+            let track = fx.track().ok_or("FX not on track")?;
+            let fx_index = fx.query_index().to_raw();
+            let param_index = t.param.index() as i32;
+            let low = Reaper::get().medium_reaper().low();
+            let (envelope, value) = unsafe {
+                let envelope = low.GetFXEnvelope(track.raw().as_ptr(), fx_index, param_index, true);
+                let value = low.TrackFX_GetParam(
+                    track.raw().as_ptr(),
+                    fx_index,
+                    param_index,
+                    null_mut(),
+                    null_mut(),
+                );
+                (envelope, value)
+            };
+            if envelope.is_null() {
+                return Err("couldn't create FX parameter envelope");
+            }
+            (track, envelope, value)
+        }
+        _ => return Err("target doesn't have an envelope"),
+    };
+    let time = track.project().edit_cursor_position().get();
+    let successful = unsafe {
+        Reaper::get().medium_reaper().low().InsertEnvelopePoint(
+            envelope,
+            time,
+            value,
+            0,
+            0.0,
+            false,
+            null_mut(),
+        )
+    };
+    if !successful {
+        return Err("couldn't insert envelope point");
+    }
+    Ok(())
+}
+
+/// `name` must be an envelope name as documented for `GetTrackEnvelopeByName`.
+///
+/// Returns an error if the track doesn't have that envelope yet.
+fn get_track_envelope_by_name(
+    track: &Track,
+    name: &CStr,
+) -> Result<*mut raw::TrackEnvelope, &'static str> {
+    let envelope = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .GetTrackEnvelopeByName(track.raw().as_ptr(), name.as_ptr())
+    };
+    if envelope.is_null() {
+        return Err("track doesn't have this envelope");
+    }
+    Ok(envelope)
+}
+
+/// Converts the given value to the scaling mode of the given envelope (e.g. fader scaling of
+/// volume envelopes).
+fn scale_to_envelope_mode(envelope: *mut raw::TrackEnvelope, value: f64) -> f64 {
+    let low = Reaper::get().medium_reaper().low();
+    unsafe {
+        let scaling_mode = low.GetEnvelopeScalingMode(envelope);
+        low.ScaleToEnvelopeMode(scaling_mode, value)
+    }
+}
@@ -0,0 +1,176 @@
+use helgoboss_learn::UnitValue;
+use realearn_api::persistence::NoteLength;
+use reaper_medium::Bpm;
+use std::ops::Sub;
+use std::time::Duration;
+
+/// Fires a held button repeatedly in a rate which follows the project tempo (tempo-synced turbo).
+///
+/// The first repetition happens one note length after the timeout. The button press itself and
+/// the fire after the timeout are handled by the mode.
+#[derive(Clone, Debug)]
+pub struct TempoSyncedTurbo<T> {
+    timeout: Duration,
+    note_length: NoteLength,
+    held_button: Option<HeldButton<T>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct HeldButton<T> {
+    value: UnitValue,
+    pressed_at: T,
+    repetition_count: u32,
+}
+
+impl<T: Copy + Sub<Output = Duration>> TempoSyncedTurbo<T> {
+    pub fn new(timeout: Duration, note_length: NoteLength) -> Self {
+        Self {
+            timeout,
+            note_length,
+            held_button: None,
+        }
+    }
+
+    /// Should be called with each incoming control value in order to know whether the button is
+    /// held.
+    ///
+    /// Relative values are ignored.
+    pub fn process(&mut self, value: Result<UnitValue, &'static str>, timestamp: T) {
+        let value = match value {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        self.held_button = if value.is_zero() {
+            None
+        } else {
+            Some(HeldButton {
+                value,
+                pressed_at: timestamp,
+                repetition_count: 0,
+            })
+        };
+    }
+
+    /// Returns the press value if the next repetition is due.
+    ///
+    /// Due times are derived from the time of the press, not from the time of polling, so the
+    /// repetitions don't drift.
+    pub fn poll(&mut self, timestamp: T, tempo: Bpm) -> Option<UnitValue> {
+        let note_duration = note_length_duration(self.note_length, tempo)?;
+        let held_button = self.held_button.as_mut()?;
+        let due = self.timeout + note_duration * (held_button.repetition_count + 1);
+        if timestamp - held_button.pressed_at < due {
+            return None;
+        }
+        held_button.repetition_count += 1;
+        Some(held_button.value)
+    }
+}
+
+/// Returns the duration of the given note length at the given tempo (quarter notes per minute).
+///
+/// Returns `None` if the note length is zero.
+pub fn note_length_duration(note_length: NoteLength, tempo: Bpm) -> Option<Duration> {
+    if note_length.numerator == 0 || note_length.denominator == 0 {
+        return None;
+    }
+    let quarter_notes = 4.0 * note_length.numerator as f64 / note_length.denominator as f64;
+    Some(Duration::from_secs_f64(quarter_notes * 60.0 / tempo.get()))
+}
+
+/// Parses a note length such as "1/8" or "1/8T" (triplet).
+pub fn parse_note_length(text: &str) -> Result<NoteLength, &'static str> {
+    let text = text.trim();
+    let (text, is_triplet) = match text.strip_suffix(|c| c == 'T' || c == 't') {
+        None => (text, false),
+        Some(t) => (t, true),
+    };
+    let (numerator, denominator) = text
+        .split_once('/')
+        .ok_or("note length must have the form 1/8")?;
+    let numerator: u32 = numerator
+        .trim()
+        .parse()
+        .map_err(|_| "invalid note length numerator")?;
+    let denominator: u32 = denominator
+        .trim()
+        .parse()
+        .map_err(|_| "invalid note length denominator")?;
+    if numerator == 0 || denominator == 0 {
+        return Err("note length must not be zero");
+    }
+    let note_length = if is_triplet {
+        // Three triplet notes take the time of two normal notes
+        NoteLength {
+            numerator: numerator * 2,
+            denominator: denominator * 3,
+        }
+    } else {
+        NoteLength {
+            numerator,
+            denominator,
+        }
+    };
+    Ok(note_length)
+}
+
+/// Formats the given note length, using the triplet notation if possible (the reverse of
+/// [`parse_note_length`]).
+pub fn format_note_length(note_length: NoteLength) -> String {
+    let NoteLength {
+        numerator,
+        denominator,
+    } = note_length;
+    if numerator % 2 == 0 && denominator % 3 == 0 {
+        format!("{}/{}T", numerator / 2, denominator / 3)
+    } else {
+        format!("{}/{}", numerator, denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeat_in_tempo_while_held() {
+        // Given
+        let eighth = NoteLength {
+            numerator: 1,
+            denominator: 8,
+        };
+        let tempo = Bpm::new(120.0);
+        let mut turbo = TempoSyncedTurbo::new(Duration::from_millis(100), eighth);
+        let ms = Duration::from_millis;
+        // When
+        turbo.process(Ok(UnitValue::MAX), ms(0));
+        let before_due = turbo.poll(ms(349), tempo);
+        let first = turbo.poll(ms(360), tempo);
+        let again_too_early = turbo.poll(ms(370), tempo);
+        let second = turbo.poll(ms(600), tempo);
+        turbo.process(Ok(UnitValue::MIN), ms(610));
+        let after_release = turbo.poll(ms(2000), tempo);
+        // Then
+        assert_eq!(before_due, None);
+        assert_eq!(first, Some(UnitValue::MAX));
+        assert_eq!(again_too_early, None);
+        assert_eq!(second, Some(UnitValue::MAX));
+        assert_eq!(after_release, None);
+    }
+
+    #[test]
+    fn parse_and_format_triplet() {
+        // Given
+        // When
+        let note_length = parse_note_length("1/8T").unwrap();
+        // Then
+        assert_eq!(
+            note_length,
+            NoteLength {
+                numerator: 2,
+                denominator: 24
+            }
+        );
+        assert_eq!(format_note_length(note_length), "1/8T");
+    }
+}
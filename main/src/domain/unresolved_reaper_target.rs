@@ -1,30 +1,36 @@
 use crate::application::BookmarkAnchorType;
 use crate::domain::realearn_target::RealearnTarget;
 use crate::domain::{
-    scoped_track_index, BackboneState, Compartment, CompartmentParamIndex, CompartmentParams,
-    ExtendedProcessorContext, FeedbackResolution, ReaperTarget, UnresolvedActionTarget,
-    UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
+    scoped_track_index, BackboneState, ClipViewOffset, Compartment, CompartmentParamIndex,
+    CompartmentParams, ExtendedProcessorContext, FeedbackResolution, ReaperTarget,
+    UnresolvedActionTarget, UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
     UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget,
     UnresolvedBrowsePotFilterItemsTarget, UnresolvedBrowsePotPresetsTarget,
-    UnresolvedBrowseTracksTarget, UnresolvedClipColumnTarget, UnresolvedClipManagementTarget,
-    UnresolvedClipMatrixTarget, UnresolvedClipRowTarget, UnresolvedClipSeekTarget,
-    UnresolvedClipTransportTarget, UnresolvedClipVolumeTarget, UnresolvedDummyTarget,
-    UnresolvedEnableInstancesTarget, UnresolvedEnableMappingsTarget, UnresolvedFxEnableTarget,
-    UnresolvedFxOnlineTarget, UnresolvedFxOpenTarget, UnresolvedFxParameterTarget,
-    UnresolvedFxParameterTouchStateTarget, UnresolvedFxPresetTarget, UnresolvedFxToolTarget,
-    UnresolvedGoToBookmarkTarget, UnresolvedLastTouchedTarget, UnresolvedLoadFxSnapshotTarget,
-    UnresolvedLoadMappingSnapshotTarget, UnresolvedLoadPotPresetTarget, UnresolvedMidiSendTarget,
-    UnresolvedMouseTarget, UnresolvedOscSendTarget, UnresolvedPlayrateTarget,
-    UnresolvedPreviewPotPresetTarget, UnresolvedRouteAutomationModeTarget,
-    UnresolvedRouteMonoTarget, UnresolvedRouteMuteTarget, UnresolvedRoutePanTarget,
-    UnresolvedRoutePhaseTarget, UnresolvedRouteTouchStateTarget, UnresolvedRouteVolumeTarget,
-    UnresolvedSeekTarget, UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget,
-    UnresolvedTrackArmTarget, UnresolvedTrackAutomationModeTarget,
+    UnresolvedBrowseProjectTabsTarget, UnresolvedBrowseTracksTarget, UnresolvedClipColumnTarget,
+    UnresolvedClipManagementTarget, UnresolvedClipMatrixTarget, UnresolvedClipRowTarget,
+    UnresolvedClipSeekTarget, UnresolvedClipTransportTarget, UnresolvedClipVolumeTarget,
+    UnresolvedDummyTarget, UnresolvedEnableInstancesTarget, UnresolvedEnableMappingsTarget,
+    UnresolvedFeedbackBrightnessTarget, UnresolvedFxEnableTarget, UnresolvedFxOnlineTarget,
+    UnresolvedFxOpenTarget, UnresolvedFxParameterTarget, UnresolvedFxParameterTouchStateTarget,
+    UnresolvedFxPresetTarget, UnresolvedFxToolTarget, UnresolvedGlobalParameterValueTarget,
+    UnresolvedGoToBookmarkTarget, UnresolvedItemPropertyTarget, UnresolvedLastTouchedTarget,
+    UnresolvedLoadFxSnapshotTarget, UnresolvedLoadMappingSnapshotTarget,
+    UnresolvedLoadPotPresetTarget, UnresolvedLoopRegionTarget, UnresolvedMidiEditorActionTarget,
+    UnresolvedMidiSendTarget, UnresolvedMouseTarget, UnresolvedNavigateProjectTabsTarget,
+    UnresolvedNavigateTracksTarget, UnresolvedOscSendTarget, UnresolvedPlayrateTarget,
+    UnresolvedPreviewPotPresetTarget, UnresolvedRecallFxParameterSnapshotTarget,
+    UnresolvedRenderProjectTarget, UnresolvedResyncFeedbackTarget,
+    UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget, UnresolvedRouteMuteTarget,
+    UnresolvedRoutePanTarget, UnresolvedRoutePhaseTarget, UnresolvedRouteTouchStateTarget,
+    UnresolvedRouteVolumeTarget, UnresolvedSaveFxParameterSnapshotTarget, UnresolvedSeekTarget,
+    UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget, UnresolvedToggleRenderQueueTarget,
+    UnresolvedToolbarToggleTarget, UnresolvedTrackArmTarget, UnresolvedTrackAutomationModeTarget,
     UnresolvedTrackMonitoringModeTarget, UnresolvedTrackMuteTarget, UnresolvedTrackPanTarget,
     UnresolvedTrackParentSendTarget, UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget,
     UnresolvedTrackSelectionTarget, UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget,
     UnresolvedTrackToolTarget, UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget,
-    UnresolvedTrackWidthTarget, UnresolvedTransportTarget,
+    UnresolvedTrackVolumeTrimTarget, UnresolvedTrackWidthTarget, UnresolvedTransportTarget,
+    UnresolvedWriteEnvelopePointsTarget,
 };
 use derive_more::{Display, Error};
 use enum_dispatch::enum_dispatch;
@@ -59,6 +65,7 @@ pub enum UnresolvedReaperTarget {
     FxParameter(UnresolvedFxParameterTarget),
     FxParameterTouchState(UnresolvedFxParameterTouchStateTarget),
     TrackVolume(UnresolvedTrackVolumeTarget),
+    TrackVolumeTrim(UnresolvedTrackVolumeTrimTarget),
     TrackTool(UnresolvedTrackToolTarget),
     TrackPeak(UnresolvedTrackPeakTarget),
     TrackSendVolume(UnresolvedRouteVolumeTarget),
@@ -88,12 +95,23 @@ pub enum UnresolvedReaperTarget {
     FxOpen(UnresolvedFxOpenTarget),
     FxPreset(UnresolvedFxPresetTarget),
     SelectedTrack(UnresolvedBrowseTracksTarget),
+    NavigateTracks(UnresolvedNavigateTracksTarget),
+    BrowseProjectTabs(UnresolvedBrowseProjectTabsTarget),
+    NavigateProjectTabs(UnresolvedNavigateProjectTabsTarget),
+    RenderProject(UnresolvedRenderProjectTarget),
+    ToggleRenderQueue(UnresolvedToggleRenderQueueTarget),
     BrowseFxs(UnresolvedBrowseFxsTarget),
     AllTrackFxEnable(UnresolvedAllTrackFxEnableTarget),
     Transport(UnresolvedTransportTarget),
     LoadFxPreset(UnresolvedLoadFxSnapshotTarget),
+    SaveFxParameterSnapshot(UnresolvedSaveFxParameterSnapshotTarget),
+    RecallFxParameterSnapshot(UnresolvedRecallFxParameterSnapshotTarget),
     TrackTouchState(UnresolvedTrackTouchStateTarget),
     GoToBookmark(UnresolvedGoToBookmarkTarget),
+    LoopRegion(UnresolvedLoopRegionTarget),
+    ItemProperty(UnresolvedItemPropertyTarget),
+    MidiEditorAction(UnresolvedMidiEditorActionTarget),
+    ToolbarToggle(UnresolvedToolbarToggleTarget),
     Seek(UnresolvedSeekTarget),
     SendMidi(UnresolvedMidiSendTarget),
     SendOsc(UnresolvedOscSendTarget),
@@ -107,6 +125,10 @@ pub enum UnresolvedReaperTarget {
     ClipMatrix(UnresolvedClipMatrixTarget),
     LoadMappingSnapshot(UnresolvedLoadMappingSnapshotTarget),
     TakeMappingSnapshot(UnresolvedTakeMappingSnapshotTarget),
+    WriteEnvelopePoints(UnresolvedWriteEnvelopePointsTarget),
+    GlobalParameterValue(UnresolvedGlobalParameterValueTarget),
+    FeedbackBrightness(UnresolvedFeedbackBrightnessTarget),
+    ResyncFeedback(UnresolvedResyncFeedbackTarget),
     EnableMappings(UnresolvedEnableMappingsTarget),
     BrowseGroup(UnresolvedBrowseGroupTarget),
     EnableInstances(UnresolvedEnableInstancesTarget),
@@ -149,6 +171,24 @@ impl UnresolvedReaperTarget {
         true
     }
 
+    /// Returns the track or FX which this target refers to by ID but which doesn't exist
+    /// (anymore).
+    pub fn missing_object(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> Option<MissingTargetObject> {
+        let descriptors = self.unpack_descriptors();
+        let track_desc = descriptors.track?;
+        if track_desc.track_is_missing(context, compartment) {
+            return Some(MissingTargetObject::Track);
+        }
+        if descriptors.fx?.fx_is_missing(context, compartment) {
+            return Some(MissingTargetObject::Fx);
+        }
+        None
+    }
+
     /// Should return true if the target should be refreshed (re-resolved) on parameter changes.
     /// Usually true for all targets that use `<Dynamic>` selector.
     pub fn can_be_affected_by_parameters(&self) -> bool {
@@ -191,6 +231,25 @@ impl UnresolvedReaperTarget {
         false
     }
 
+    /// Should return true if the target should be refreshed (re-resolved) when the clip view is
+    /// shifted. True for all targets that address clip slots, columns or rows dynamically because
+    /// only dynamic expressions can refer to the clip view offset.
+    pub fn can_be_affected_by_clip_view_offset(&self) -> bool {
+        let descriptors = self.unpack_descriptors();
+        descriptors
+            .clip_slot
+            .map(|s| s.can_be_affected_by_parameters())
+            .unwrap_or(false)
+            || descriptors
+                .clip_column
+                .map(|c| c.can_be_affected_by_parameters())
+                .unwrap_or(false)
+            || descriptors
+                .clip_row
+                .map(|r| r.can_be_affected_by_parameters())
+                .unwrap_or(false)
+    }
+
     fn unpack_descriptors(&self) -> Descriptors {
         if let Some(d) = self.fx_parameter_descriptor() {
             return Descriptors {
@@ -352,6 +411,29 @@ impl TrackDescriptor {
         };
         Ok(desc)
     }
+
+    /// Returns `true` if this refers to a particular track by ID and that track doesn't exist
+    /// (anymore).
+    pub fn track_is_missing(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> bool {
+        matches!(self.track, VirtualTrack::ById(_))
+            && matches!(
+                self.track.resolve(context, compartment),
+                Err(TrackResolveError::TrackNotFound { .. })
+            )
+    }
+}
+
+/// Object which a target refers to by ID but which doesn't exist (anymore).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+pub enum MissingTargetObject {
+    #[display(fmt = "track")]
+    Track,
+    #[display(fmt = "FX")]
+    Fx,
 }
 
 #[derive(Debug, Default)]
@@ -454,6 +536,50 @@ impl FxDescriptor {
         Ok(desc)
     }
 
+    /// Returns whether this descriptor might resolve to FX on the monitoring FX chain.
+    ///
+    /// By convention, the input FX chain of the master track represents the monitoring FX chain.
+    /// REAPER doesn't notify us about changes on that chain, so this is important to know.
+    pub fn might_resolve_to_monitoring_fx(&self) -> bool {
+        if !self.fx.is_input_fx() {
+            return false;
+        }
+        use VirtualTrack::*;
+        match &self.track_descriptor.track {
+            // <This> resolves to the master track if ReaLearn itself is on the monitoring FX
+            // chain. The others can resolve to the master track anyway.
+            This | Master | Selected { .. } | Dynamic { .. } | Instance => true,
+            ById(_) | ByName { .. } | ByIndex { .. } | ByIdOrName(_, _) | FromClipColumn { .. } => {
+                false
+            }
+        }
+    }
+
+    /// Returns `true` if this refers to a particular FX by ID and that FX doesn't exist (anymore)
+    /// on an existing FX chain.
+    pub fn fx_is_missing(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: Compartment,
+    ) -> bool {
+        let (is_input_fx, chain_fx) = match &self.fx {
+            VirtualFx::ChainFx {
+                is_input_fx,
+                chain_fx: chain_fx @ VirtualChainFx::ById(_, _),
+            } => (*is_input_fx, chain_fx),
+            _ => return false,
+        };
+        match get_fx_chains(
+            context,
+            &self.track_descriptor.track,
+            is_input_fx,
+            compartment,
+        ) {
+            Ok(fx_chains) => chain_fx.resolve(&fx_chains, context, compartment).is_err(),
+            Err(_) => false,
+        }
+    }
+
     // Returns an error if the FX doesn't exist.
     pub fn resolve(
         &self,
@@ -771,11 +897,9 @@ impl VirtualClipSlot {
                 column_evaluator,
                 row_evaluator,
             } => {
-                let compartment_params = context.params().compartment_params(compartment);
                 let column_index =
-                    to_slot_coordinate(column_evaluator.evaluate_with_params(compartment_params))?;
-                let row_index =
-                    to_slot_coordinate(row_evaluator.evaluate_with_params(compartment_params))?;
+                    evaluate_slot_coordinate(column_evaluator, context, compartment)?;
+                let row_index = evaluate_slot_coordinate(row_evaluator, context, compartment)?;
                 ClipSlotAddress::new(column_index, row_index)
             }
         };
@@ -835,10 +959,7 @@ impl VirtualClipColumn {
         let index = match self {
             Selected => return Err("the concept of a selected column is not yet supported"),
             ByIndex(index) => *index,
-            Dynamic(evaluator) => {
-                let compartment_params = context.params().compartment_params(compartment);
-                to_slot_coordinate(evaluator.evaluate_with_params(compartment_params))?
-            }
+            Dynamic(evaluator) => evaluate_slot_coordinate(evaluator, context, compartment)?,
         };
         // let column_exists = BackboneState::get()
         //     .with_clip_matrix(context.control_context.instance_state, |matrix| {
@@ -878,10 +999,7 @@ impl VirtualClipRow {
         let index = match self {
             Selected => return Err("the concept of a selected row is not yet supported"),
             ByIndex(index) => *index,
-            Dynamic(evaluator) => {
-                let compartment_params = context.params().compartment_params(compartment);
-                to_slot_coordinate(evaluator.evaluate_with_params(compartment_params))?
-            }
+            Dynamic(evaluator) => evaluate_slot_coordinate(evaluator, context, compartment)?,
         };
         // let row_exists = BackboneState::get()
         //     .with_clip_matrix_mut(context.control_context.instance_state, |matrix| {
@@ -898,6 +1016,35 @@ impl VirtualClipRow {
     }
 }
 
+/// Evaluates a dynamic clip slot coordinate.
+///
+/// The current clip view offset is available as variables, so each target decides itself whether
+/// it follows the clip view.
+fn evaluate_slot_coordinate(
+    evaluator: &ExpressionEvaluator,
+    context: ExtendedProcessorContext,
+    compartment: Compartment,
+) -> Result<usize, &'static str> {
+    let compartment_params = context.params().compartment_params(compartment);
+    let result = evaluator.evaluate_with_params_and_vars(compartment_params, |name, _| {
+        let offset = match name {
+            "clip_view_column_offset" => clip_view_offset(context).column,
+            "clip_view_row_offset" => clip_view_offset(context).row,
+            _ => return None,
+        };
+        Some(offset as f64)
+    });
+    to_slot_coordinate(result)
+}
+
+fn clip_view_offset(context: ExtendedProcessorContext) -> ClipViewOffset {
+    context
+        .control_context
+        .instance_state
+        .borrow()
+        .clip_view_offset()
+}
+
 /// In clip slot targets, the resolve phase makes sure that the targeted slot actually exists.
 /// So if we get a `None` value from some of the clip slot methods, it's because the slot doesn't
 /// have a clip, which is a valid state and should return *something*. The contract of the target
@@ -2051,6 +2198,7 @@ pub fn find_bookmark(
     bookmark_type: BookmarkType,
     anchor_type: BookmarkAnchorType,
     bookmark_ref: u32,
+    bookmark_name: &str,
 ) -> Result<FindBookmarkResult, &'static str> {
     if !project.is_available() {
         return Err("project not available");
@@ -2062,6 +2210,21 @@ pub fn find_bookmark(
         BookmarkAnchorType::Id => project
             .find_bookmark_by_type_and_id(bookmark_type, BookmarkId::new(bookmark_ref))
             .ok_or("bookmark with that type and ID not found"),
+        BookmarkAnchorType::Name => {
+            let wild_match = WildMatch::new(bookmark_name);
+            let id = project
+                .bookmarks()
+                .map(|b| (b, b.basic_info()))
+                .find(|(b, info)| {
+                    info.bookmark_type() == bookmark_type && wild_match.matches(&b.name())
+                })
+                .ok_or("bookmark with that type and name not found")?
+                .1
+                .id;
+            project
+                .find_bookmark_by_type_and_id(bookmark_type, id)
+                .ok_or("bookmark with that type and name not found")
+        }
     }
 }
 
@@ -0,0 +1,167 @@
+use crate::domain::ui_util::{
+    format_as_percentage_without_unit, format_value_as_db, format_volume_as_db_without_unit,
+    parse_unit_value_from_percentage, parse_value_from_db, volume_unit_value,
+};
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::UnitValue;
+use reaper_high::{Project, Volume};
+use reaper_medium::{Db, PositionInSeconds};
+use serde::{Deserialize, Serialize};
+
+/// Decimal places used for positions if the user didn't choose a precision.
+const DEFAULT_POSITION_DECIMAL_PLACES: usize = 3;
+
+/// Per-instance preferences for displaying and entering target values.
+///
+/// The default preferences correspond to ReaLearn's classic formatting.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueDisplayPreferences {
+    /// `None` means that each kind of value uses its usual precision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_places: Option<u8>,
+    #[serde(default)]
+    pub volume_unit: VolumeDisplayUnit,
+    #[serde(default)]
+    pub time_unit: TimeDisplayUnit,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, IntoEnumIterator, Display)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeDisplayUnit {
+    #[display(fmt = "Decibel (dB)")]
+    Decibel,
+    /// Fader position.
+    #[display(fmt = "Fader position (%)")]
+    Percent,
+}
+
+impl Default for VolumeDisplayUnit {
+    fn default() -> Self {
+        Self::Decibel
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, IntoEnumIterator, Display)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeDisplayUnit {
+    #[display(fmt = "Seconds")]
+    Seconds,
+    #[display(fmt = "Beats")]
+    Beats,
+}
+
+impl Default for TimeDisplayUnit {
+    fn default() -> Self {
+        Self::Seconds
+    }
+}
+
+impl ValueDisplayPreferences {
+    pub fn format_percentage_without_unit(&self, value: UnitValue) -> String {
+        match self.decimal_places {
+            None => format_as_percentage_without_unit(value),
+            Some(n) => format!("{:.*}", n as usize, value.get() * 100.0),
+        }
+    }
+
+    pub fn volume_unit(&self) -> &'static str {
+        match self.volume_unit {
+            VolumeDisplayUnit::Decibel => "dB",
+            VolumeDisplayUnit::Percent => "%",
+        }
+    }
+
+    /// Formats the given soft-normalized volume without unit.
+    pub fn format_volume_value_without_unit(&self, value: UnitValue) -> String {
+        match self.volume_unit {
+            VolumeDisplayUnit::Decibel => {
+                let volume =
+                    Volume::try_from_soft_normalized_value(value.get()).unwrap_or(Volume::MIN);
+                self.format_volume_as_db_without_unit(volume)
+            }
+            VolumeDisplayUnit::Percent => self.format_percentage_without_unit(value),
+        }
+    }
+
+    /// Formats the given soft-normalized volume including unit.
+    pub fn format_volume_value(&self, value: UnitValue) -> String {
+        match (self.volume_unit, self.decimal_places) {
+            (VolumeDisplayUnit::Decibel, None) => format_value_as_db(value),
+            _ => format!(
+                "{} {}",
+                self.format_volume_value_without_unit(value),
+                self.volume_unit()
+            ),
+        }
+    }
+
+    pub fn format_volume(&self, volume: Volume) -> String {
+        match (self.volume_unit, self.decimal_places) {
+            (VolumeDisplayUnit::Decibel, None) => volume.to_string(),
+            _ => self.format_volume_value(volume_unit_value(volume)),
+        }
+    }
+
+    /// Parses the given text as soft-normalized volume (the reverse of
+    /// [`Self::format_volume_value_without_unit`]).
+    pub fn parse_volume_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        match self.volume_unit {
+            VolumeDisplayUnit::Decibel => parse_value_from_db(text),
+            VolumeDisplayUnit::Percent => parse_unit_value_from_percentage(text),
+        }
+    }
+
+    /// Formats the given display position (e.g. relative to the start of the time selection)
+    /// including unit.
+    ///
+    /// The position in the project is needed for converting the display position into beats
+    /// because the tempo can change along the timeline.
+    pub fn format_position(
+        &self,
+        project: Project,
+        display_pos: PositionInSeconds,
+        project_pos: PositionInSeconds,
+    ) -> String {
+        let decimal_places = self
+            .decimal_places
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_POSITION_DECIMAL_PLACES);
+        match self.time_unit {
+            TimeDisplayUnit::Seconds => format!("{:.*} s", decimal_places, display_pos.get()),
+            TimeDisplayUnit::Beats => {
+                let start_pos = PositionInSeconds::new(project_pos.get() - display_pos.get());
+                let beats = project.beat_info_at(project_pos).full_beats.get()
+                    - project.beat_info_at(start_pos).full_beats.get();
+                format!("{:.*} beats", decimal_places, beats)
+            }
+        }
+    }
+
+    fn format_volume_as_db_without_unit(&self, volume: Volume) -> String {
+        match self.decimal_places {
+            None => format_volume_as_db_without_unit(volume),
+            Some(_) if volume.db() == Db::MINUS_INF => "-inf".to_string(),
+            Some(n) => format!("{:.*}", n as usize, volume.db().get()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_percentage_with_custom_precision() {
+        // Given
+        let prefs = ValueDisplayPreferences {
+            decimal_places: Some(3),
+            ..Default::default()
+        };
+        // When
+        let text = prefs.format_percentage_without_unit(UnitValue::new(0.123456));
+        // Then
+        assert_eq!(text, "12.346");
+    }
+}
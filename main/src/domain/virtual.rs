@@ -2,8 +2,11 @@ use crate::domain::ui_util::{format_as_percentage_without_unit, parse_unit_value
 use crate::domain::{ExtendedSourceCharacter, SmallAsciiString, TargetCharacter};
 use ascii::{AsciiString, ToAsciiChar};
 use helgoboss_learn::{
-    AbsoluteValue, ControlType, ControlValue, FeedbackValue, SourceCharacter, Target, UnitValue,
+    AbsoluteValue, ControlType, ControlValue, FeedbackValue, SourceCharacter, Target,
+    TextualFeedbackValue, UnitValue,
 };
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -11,26 +14,143 @@ use std::str::FromStr;
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct VirtualTarget {
     control_element: VirtualControlElement,
+    display_layout: VirtualDisplayLayout,
 }
 
 impl VirtualTarget {
     pub fn new(control_element: VirtualControlElement) -> VirtualTarget {
-        VirtualTarget { control_element }
+        VirtualTarget {
+            control_element,
+            display_layout: Default::default(),
+        }
+    }
+
+    pub fn with_display_layout(self, display_layout: VirtualDisplayLayout) -> VirtualTarget {
+        VirtualTarget {
+            display_layout,
+            ..self
+        }
     }
 
     pub fn control_element(&self) -> VirtualControlElement {
         self.control_element
     }
 
+    pub fn display_layout(&self) -> VirtualDisplayLayout {
+        self.display_layout
+    }
+
+    /// Fits textual feedback coming from main mappings into the display described by this target.
+    ///
+    /// Only affects display elements. All other feedback values are passed through unchanged.
+    pub fn adjust_feedback_value<'a>(
+        &self,
+        feedback_value: Cow<'a, FeedbackValue<'a>>,
+    ) -> Cow<'a, FeedbackValue<'a>> {
+        if !matches!(self.control_element, VirtualControlElement::Display(_))
+            || self.display_layout.is_unrestricted()
+        {
+            return feedback_value;
+        }
+        match feedback_value.as_ref() {
+            FeedbackValue::Textual(v) => {
+                let fitted_text = self.display_layout.fit_text(&v.text);
+                Cow::Owned(FeedbackValue::Textual(TextualFeedbackValue::new(
+                    v.style,
+                    fitted_text.into(),
+                )))
+            }
+            _ => feedback_value,
+        }
+    }
+
     pub fn character(&self) -> TargetCharacter {
         use VirtualControlElement::*;
         match self.control_element {
-            Multi(_) => TargetCharacter::VirtualMulti,
+            // A display doesn't have a control character of its own, so we treat it like a multi
+            // (in case someone uses it for control anyway).
+            Multi(_) | Display(_) => TargetCharacter::VirtualMulti,
             Button(_) => TargetCharacter::VirtualButton,
         }
     }
 }
 
+/// Describes the dimensions of a text display on the controller.
+///
+/// `None` means unrestricted.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualDisplayLayout {
+    pub line_count: Option<u32>,
+    pub line_length: Option<u32>,
+}
+
+impl VirtualDisplayLayout {
+    pub fn is_unrestricted(&self) -> bool {
+        self.line_count.is_none() && self.line_length.is_none()
+    }
+
+    /// Drops lines exceeding the line count and crops lines exceeding the line length.
+    pub fn fit_text(&self, text: &str) -> String {
+        let line_count = self.line_count.map(|c| c as usize).unwrap_or(usize::MAX);
+        let lines = text
+            .lines()
+            .take(line_count)
+            .map(|line| match self.line_length {
+                None => line.to_string(),
+                Some(l) => line.chars().take(l as usize).collect(),
+            });
+        lines.collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl Display for VirtualDisplayLayout {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let format_dimension = |d: Option<u32>| match d {
+            None => "*".to_string(),
+            Some(d) => d.to_string(),
+        };
+        write!(
+            f,
+            "{} x {}",
+            format_dimension(self.line_count),
+            format_dimension(self.line_length)
+        )
+    }
+}
+
+impl FromStr for VirtualDisplayLayout {
+    type Err = &'static str;
+
+    /// Parses something like "2 x 56" (2 lines with 56 characters each). A "*" or an empty
+    /// dimension means unrestricted.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let parse_dimension = |d: &str| -> Result<Option<u32>, &'static str> {
+            let d = d.trim();
+            if d.is_empty() || d == "*" {
+                return Ok(None);
+            }
+            let d: u32 = d.parse().map_err(|_| "display dimension is not a number")?;
+            if d == 0 {
+                return Err("display dimension must be greater than zero");
+            }
+            Ok(Some(d))
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(Default::default());
+        }
+        let (line_count, line_length) = text
+            .split_once(|c| c == 'x' || c == 'X')
+            .ok_or("display layout must have the form \"lines x length\"")?;
+        let layout = VirtualDisplayLayout {
+            line_count: parse_dimension(line_count)?,
+            line_length: parse_dimension(line_length)?,
+        };
+        Ok(layout)
+    }
+}
+
 impl<'a> Target<'a> for VirtualTarget {
     type Context = ();
 
@@ -41,7 +161,7 @@ impl<'a> Target<'a> for VirtualTarget {
     fn control_type(&self, _: ()) -> ControlType {
         use VirtualControlElement::*;
         match self.control_element {
-            Multi(_) => ControlType::VirtualMulti,
+            Multi(_) | Display(_) => ControlType::VirtualMulti,
             Button(_) => ControlType::VirtualButton,
         }
     }
@@ -105,7 +225,7 @@ impl VirtualSource {
         use VirtualControlElement::*;
         match self.control_element {
             Button(_) => ExtendedSourceCharacter::Normal(SourceCharacter::MomentaryButton),
-            Multi(_) => ExtendedSourceCharacter::VirtualContinuous,
+            Multi(_) | Display(_) => ExtendedSourceCharacter::VirtualContinuous,
         }
     }
 }
@@ -186,6 +306,11 @@ impl VirtualFeedbackValue {
 pub enum VirtualControlElement {
     Multi(VirtualControlElementId),
     Button(VirtualControlElementId),
+    /// A text display, e.g. an LCD or a scribble strip.
+    ///
+    /// Main mappings send textual feedback to it. It's the job of the controller mapping to
+    /// translate the text into the protocol of the actual hardware.
+    Display(VirtualControlElementId),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
@@ -234,6 +359,7 @@ impl Display for VirtualControlElement {
         match self {
             Multi(id) => write!(f, "Multi {}", id),
             Button(id) => write!(f, "Button {}", id),
+            Display(id) => write!(f, "Display {}", id),
         }
     }
 }
@@ -252,11 +378,69 @@ impl VirtualControlElement {
     pub fn id(&self) -> VirtualControlElementId {
         use VirtualControlElement::*;
         match self {
-            Multi(i) | Button(i) => *i,
+            Multi(i) | Button(i) | Display(i) => *i,
         }
     }
 }
 
+/// Part of a two-dimensional control surface (XY pad), e.g. on a tablet or in an OSC app.
+///
+/// An XY pad named "XY1" consists of the virtual control elements "XY1/X" and "XY1/Y" (multis)
+/// and "XY1/touch" (button). Both axes share the touch element, which is on as long as the pad is
+/// touched.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum XyPadRole {
+    X,
+    Y,
+    Touch,
+}
+
+impl XyPadRole {
+    pub const ALL: [XyPadRole; 3] = [XyPadRole::X, XyPadRole::Y, XyPadRole::Touch];
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            XyPadRole::X => "X",
+            XyPadRole::Y => "Y",
+            XyPadRole::Touch => "touch",
+        }
+    }
+
+    /// Returns the virtual control element which plays this role in the XY pad with the given
+    /// name.
+    pub fn control_element(self, pad_name: &str) -> Result<VirtualControlElement, &'static str> {
+        let name = format!("{}/{}", pad_name, self.suffix());
+        if name.len() > SmallAsciiString::MAX_LENGTH {
+            return Err("XY pad name too long");
+        }
+        let id = VirtualControlElementId::Named(create_control_element_name_lossy(&name)?);
+        let element = match self {
+            XyPadRole::X | XyPadRole::Y => VirtualControlElement::Multi(id),
+            XyPadRole::Touch => VirtualControlElement::Button(id),
+        };
+        Ok(element)
+    }
+
+    /// Returns the name of the XY pad and the role if the given control element is part of an XY
+    /// pad.
+    pub fn parse(element: VirtualControlElement) -> Option<(String, XyPadRole)> {
+        let VirtualControlElementId::Named(name) = element.id() else {
+            return None;
+        };
+        let (pad_name, suffix) = name.as_ascii_str().as_str().rsplit_once('/')?;
+        if pad_name.is_empty() {
+            return None;
+        }
+        let role = match (element, suffix) {
+            (VirtualControlElement::Multi(_), "X") => XyPadRole::X,
+            (VirtualControlElement::Multi(_), "Y") => XyPadRole::Y,
+            (VirtualControlElement::Button(_), "touch") => XyPadRole::Touch,
+            _ => return None,
+        };
+        Some((pad_name.to_string(), role))
+    }
+}
+
 pub mod control_element_domains {
     pub mod daw {
         pub const PREDEFINED_VIRTUAL_MULTI_NAMES: &[&str] = &[
@@ -411,9 +595,30 @@ pub mod control_element_domains {
             "cursor-up",
             "cursor-down",
         ];
+
+        pub const PREDEFINED_VIRTUAL_DISPLAY_NAMES: &[&str] = &[
+            "main/display",
+            "ch1/display/upper",
+            "ch1/display/lower",
+            "ch2/display/upper",
+            "ch2/display/lower",
+            "ch3/display/upper",
+            "ch3/display/lower",
+            "ch4/display/upper",
+            "ch4/display/lower",
+            "ch5/display/upper",
+            "ch5/display/lower",
+            "ch6/display/upper",
+            "ch6/display/lower",
+            "ch7/display/upper",
+            "ch7/display/lower",
+            "ch8/display/upper",
+            "ch8/display/lower",
+        ];
     }
     pub mod grid {
         pub const PREDEFINED_VIRTUAL_MULTI_NAMES: &[&str] = &[];
+        pub const PREDEFINED_VIRTUAL_DISPLAY_NAMES: &[&str] = &[];
         pub const PREDEFINED_VIRTUAL_BUTTON_NAMES: &[&str] = &[
             "col1/stop",
             "col2/stop",
@@ -524,3 +729,36 @@ pub mod control_element_domains {
         ];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_text_into_display() {
+        // Given
+        let layout: VirtualDisplayLayout = "2 x 5".parse().unwrap();
+        // When
+        let fitted_text = layout.fit_text("Volume\n-6.0 dB\nignored");
+        // Then
+        assert_eq!(fitted_text, "Volum\n-6.0 ");
+    }
+
+    #[test]
+    fn recognize_xy_pad_elements() {
+        // Given
+        let x = XyPadRole::X.control_element("XY1").unwrap();
+        let touch = XyPadRole::Touch.control_element("XY1").unwrap();
+        let fader: VirtualControlElement =
+            VirtualControlElement::Multi("ch1/fader".parse().unwrap());
+        // When
+        // Then
+        assert_eq!(x.to_string(), "Multi XY1/X");
+        assert_eq!(XyPadRole::parse(x), Some(("XY1".to_string(), XyPadRole::X)));
+        assert_eq!(
+            XyPadRole::parse(touch),
+            Some(("XY1".to_string(), XyPadRole::Touch))
+        );
+        assert_eq!(XyPadRole::parse(fader), None);
+    }
+}
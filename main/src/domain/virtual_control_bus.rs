@@ -0,0 +1,132 @@
+use crate::base::{NamedChannelSender, SenderToNormalThread};
+use crate::domain::{ControlEvent, InstanceId, VirtualSourceValue};
+use std::collections::HashMap;
+
+/// Decides whether a ReaLearn instance shares virtual control elements with other instances.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+    enum_iterator::IntoEnumIterator,
+    derive_more::Display,
+)]
+pub enum VirtualControlElementSharing {
+    /// Virtual control elements are only used within this instance.
+    #[display(fmt = "Off")]
+    Off,
+    /// Virtual control events produced by the controller compartment of this instance are
+    /// additionally sent to all subscribed instances.
+    #[display(fmt = "Publish to other instances")]
+    Publish,
+    /// The main compartment of this instance additionally receives virtual control events
+    /// published by other instances.
+    #[display(fmt = "Subscribe to other instances")]
+    Subscribe,
+}
+
+impl Default for VirtualControlElementSharing {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+/// A virtual control event published by one ReaLearn instance.
+#[derive(Copy, Clone, Debug)]
+pub struct PublishedVirtualControlEvent {
+    pub publisher: InstanceId,
+    pub event: ControlEvent<VirtualSourceValue>,
+}
+
+/// Inter-instance event bus which distributes virtual control events from publishing instances to
+/// subscribed instances.
+///
+/// Publishing never calls into other instances directly. The events are just put into the
+/// channels of the subscribers, which process them in their next main loop cycle. That way, an
+/// instance never needs to borrow another instance's main processor, no matter in which order the
+/// instances are processed and even if one of them is just being removed.
+#[derive(Debug, Default)]
+pub struct VirtualControlBus {
+    subscribers: HashMap<InstanceId, SenderToNormalThread<PublishedVirtualControlEvent>>,
+}
+
+impl VirtualControlBus {
+    pub fn subscribe(
+        &mut self,
+        subscriber: InstanceId,
+        sender: SenderToNormalThread<PublishedVirtualControlEvent>,
+    ) {
+        self.subscribers.insert(subscriber, sender);
+    }
+
+    pub fn unsubscribe(&mut self, subscriber: &InstanceId) {
+        self.subscribers.remove(subscriber);
+    }
+
+    /// Sends the given event to all subscribers except the publisher itself.
+    pub fn publish(&self, publisher: InstanceId, event: ControlEvent<VirtualSourceValue>) {
+        let published_event = PublishedVirtualControlEvent { publisher, event };
+        for (subscriber, sender) in &self.subscribers {
+            if *subscriber == publisher {
+                continue;
+            }
+            // If a subscriber doesn't keep up, we rather drop events than blocking.
+            sender.send_if_space(published_event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ControlEventTimestamp, VirtualControlElement, VirtualControlElementId};
+    use helgoboss_learn::{AbstractTimestamp, ControlValue, UnitValue};
+
+    fn event() -> ControlEvent<VirtualSourceValue> {
+        let value = VirtualSourceValue::new(
+            VirtualControlElement::Multi(VirtualControlElementId::Indexed(0)),
+            ControlValue::AbsoluteContinuous(UnitValue::MAX),
+        );
+        ControlEvent::new(value, ControlEventTimestamp::now())
+    }
+
+    #[test]
+    fn publish_to_subscribers_only() {
+        // Given
+        let mut bus = VirtualControlBus::default();
+        let publisher = InstanceId::random();
+        let subscriber = InstanceId::random();
+        let (publisher_sender, publisher_receiver) =
+            SenderToNormalThread::new_unbounded_channel("publisher");
+        let (subscriber_sender, subscriber_receiver) =
+            SenderToNormalThread::new_unbounded_channel("subscriber");
+        bus.subscribe(publisher, publisher_sender);
+        bus.subscribe(subscriber, subscriber_sender);
+        // When
+        let evt = event();
+        bus.publish(publisher, evt);
+        // Then
+        assert_eq!(publisher_receiver.try_iter().count(), 0);
+        let received: Vec<_> = subscriber_receiver.try_iter().collect();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].publisher, publisher);
+        assert_eq!(received[0].event.payload(), evt.payload());
+    }
+
+    #[test]
+    fn unsubscribe() {
+        // Given
+        let mut bus = VirtualControlBus::default();
+        let subscriber = InstanceId::random();
+        let (sender, receiver) = SenderToNormalThread::new_unbounded_channel("subscriber");
+        bus.subscribe(subscriber, sender);
+        // When
+        bus.unsubscribe(&subscriber);
+        bus.publish(InstanceId::random(), event());
+        // Then
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::infrastructure::api::convert::from_data::{
-    convert_group, convert_mapping, convert_parameter, ConversionStyle,
+    convert_group, convert_lifecycle_model, convert_mapping, convert_parameter, ConversionStyle,
 };
 use crate::infrastructure::api::convert::{convert_multiple, ConversionResult};
 use crate::infrastructure::data::CompartmentModelData;
@@ -36,6 +36,9 @@ pub fn convert_compartment(
         },
         custom_data: style.required_value(data.custom_data),
         notes: style.required_value(data.notes),
+        on_init: convert_lifecycle_model(data.lifecycle.on_init, style)?,
+        on_shutdown: convert_lifecycle_model(data.lifecycle.on_shutdown, style)?,
+        message_templates: style.required_value(data.message_templates.into_templates()),
     };
     Ok(compartment)
 }
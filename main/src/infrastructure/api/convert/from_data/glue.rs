@@ -102,6 +102,8 @@ pub fn convert_glue(
             };
             style.required_value(v)
         },
+        acceleration_curve: style.required_value(data.acceleration_curve),
+        control_curve: style.required_value(data.control_curve),
         interaction: {
             use persistence::Interaction as T;
             use GroupInteraction::*;
@@ -168,6 +170,7 @@ pub fn convert_glue(
                             data.turbo_rate as _,
                             defaults::FIRE_MODE_RATE,
                         ),
+                        synced_rate: data.synced_turbo_rate,
                     })
                 }
                 OnSinglePress => T::OnSinglePress(persistence::OnSinglePressFireMode {
@@ -205,7 +208,7 @@ fn convert_unit_interval(min: UnitValue, max: UnitValue) -> persistence::Interva
     persistence::Interval(min.get(), max.get())
 }
 
-fn convert_virtual_color(v: VirtualColor) -> persistence::VirtualColor {
+pub fn convert_virtual_color(v: VirtualColor) -> persistence::VirtualColor {
     use persistence::VirtualColor as T;
     use VirtualColor::*;
     match v {
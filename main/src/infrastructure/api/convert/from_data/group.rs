@@ -1,5 +1,5 @@
 use crate::infrastructure::api::convert::from_data::{
-    convert_activation_condition, convert_tags, ConversionStyle,
+    convert_activation_condition, convert_tags, convert_virtual_color, ConversionStyle,
 };
 use crate::infrastructure::api::convert::{defaults, ConversionResult};
 use crate::infrastructure::data::GroupModelData;
@@ -28,6 +28,7 @@ pub fn convert_group(
             defaults::GROUP_FEEDBACK_ENABLED,
         ),
         activation_condition: convert_activation_condition(data.activation_condition_data),
+        feedback_color: data.feedback_color.map(convert_virtual_color),
     };
     Ok(group)
 }
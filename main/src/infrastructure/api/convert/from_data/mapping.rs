@@ -24,6 +24,10 @@ pub fn convert_mapping(
             data.visible_in_projection,
             defaults::MAPPING_VISIBLE_IN_PROJECTION,
         ),
+        feedback_only_if_visible_in_projection: style.required_value_with_default(
+            data.feedback_only_if_visible_in_projection,
+            defaults::MAPPING_FEEDBACK_ONLY_IF_VISIBLE_IN_PROJECTION,
+        ),
         enabled: style.required_value_with_default(data.is_enabled, defaults::MAPPING_ENABLED),
         control_enabled: style.required_value_with_default(
             data.enabled_data.control_is_enabled,
@@ -46,6 +50,10 @@ pub fn convert_mapping(
         glue: style.required_value(convert_glue(data.mode, style)?),
         target: style.required_value(convert_target(data.target, style)?),
         success_audio_feedback: data.success_audio_feedback,
+        crossfade_role: data.crossfade_role,
+        feedback_effect: data.feedback_effect,
+        invert_feedback: style
+            .required_value_with_default(data.invert_feedback, defaults::MAPPING_INVERT_FEEDBACK),
         unprocessed: style.optional_value(advanced.unprocessed),
     };
     Ok(mapping)
@@ -110,7 +118,7 @@ fn convert_extension_model(
     Ok(desc)
 }
 
-fn convert_lifecycle_model(
+pub fn convert_lifecycle_model(
     lifecycle_model: LifecycleModel,
     style: ConversionStyle,
 ) -> ConversionResult<Option<persistence::LifecycleHook>> {
@@ -123,6 +131,7 @@ fn convert_lifecycle_model(
                 .collect();
             style.required_value(actions?)
         },
+        invoke_reaper_actions: style.required_value(lifecycle_model.invoke_reaper_actions),
     };
     Ok(style.required_value(hook))
 }
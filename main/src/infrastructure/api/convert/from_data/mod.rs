@@ -42,6 +42,7 @@ fn convert_control_element_kind(
     let res = match v {
         Multi => T::Multi,
         Button => T::Button,
+        Display => T::Display,
     };
     style.required_value(res)
 }
@@ -216,5 +217,8 @@ fn convert_activation_condition(
             };
             Some(T::TargetValue(condition))
         }
+        WhilePlaying => Some(T::Playing),
+        WhileRecording => Some(T::Recording),
+        WhileStopped => Some(T::Stopped),
     }
 }
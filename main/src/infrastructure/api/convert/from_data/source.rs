@@ -47,6 +47,7 @@ pub fn convert_source(
                         controller_number: convert_controller_number(data.number),
                         character: convert_character(data.character, style),
                         fourteen_bit: data.is_14_bit,
+                        debounce: data.debounce,
                     };
                     persistence::Source::MidiControlChangeValue(s)
                 }
@@ -55,6 +56,7 @@ pub fn convert_source(
                         feedback_behavior,
                         channel: convert_channel(data.channel),
                         key_number: convert_key_number(data.number),
+                        velocity_feedback: data.velocity_feedback,
                     };
                     persistence::Source::MidiNoteVelocity(s)
                 }
@@ -69,6 +71,8 @@ pub fn convert_source(
                     let s = persistence::MidiPitchBendChangeValueSource {
                         feedback_behavior,
                         channel: convert_channel(data.channel),
+                        debounce: data.debounce,
+                        behavior: data.pitch_bend_behavior,
                     };
                     persistence::Source::MidiPitchBendChangeValue(s)
                 }
@@ -76,6 +80,7 @@ pub fn convert_source(
                     let s = persistence::MidiChannelPressureAmountSource {
                         feedback_behavior,
                         channel: convert_channel(data.channel),
+                        debounce: data.debounce,
                     };
                     persistence::Source::MidiChannelPressureAmount(s)
                 }
@@ -102,6 +107,7 @@ pub fn convert_source(
                         fourteen_bit: data.is_14_bit,
                         registered: data.is_registered,
                         character: convert_character(data.character, style),
+                        debounce: data.debounce,
                     };
                     persistence::Source::MidiParameterNumberValue(s)
                 }
@@ -110,6 +116,9 @@ pub fn convert_source(
                         feedback_behavior,
                         channel: convert_channel(data.channel),
                         key_number: convert_key_number(data.number),
+                        velocity_feedback: data.velocity_feedback,
+                        feedback_as_note_on: style.required_value(data.feedback_as_note_on),
+                        debounce: data.debounce,
                     };
                     persistence::Source::MidiPolyphonicKeyPressureAmount(s)
                 }
@@ -123,6 +132,13 @@ pub fn convert_source(
                     };
                     persistence::Source::MidiClockTransport(s)
                 }
+                MachineControl => {
+                    let s = persistence::MidiMachineControlSource {
+                        device_id: convert_device_id(data.number),
+                        command: style.required_value(data.mmc_command),
+                    };
+                    persistence::Source::MidiMachineControl(s)
+                }
                 Raw => {
                     let s = persistence::MidiRawSource {
                         feedback_behavior,
@@ -232,6 +248,7 @@ pub fn convert_source(
                     defaults::SOURCE_OSC_IS_RELATIVE,
                 ),
                 feedback_arguments: style.required_value(data.osc_feedback_args),
+                debounce: data.debounce,
             };
             persistence::Source::Osc(s)
         }
@@ -253,6 +270,18 @@ pub fn convert_source(
                     })
                 }
                 Speech => persistence::Source::Speech(persistence::SpeechSource {}),
+                OneShotTimer => {
+                    persistence::Source::OneShotTimer(persistence::OneShotTimerSource {
+                        duration: data.timer_millis,
+                    })
+                }
+                Lfo => persistence::Source::Lfo(persistence::LfoSource {
+                    shape: style.required_value(data.lfo_shape),
+                    period: data.lfo_period,
+                }),
+                Random => persistence::Source::Random(persistence::RandomSource {
+                    interval: data.timer_millis,
+                }),
             }
         }
         Virtual => {
@@ -268,6 +297,16 @@ pub fn convert_source(
             };
             persistence::Source::Key(s)
         }
+        Audio => {
+            let s = persistence::AudioInputSource {
+                input: style.required_value(data.audio_input_kind),
+                channel: style.required_value(data.audio_channel),
+                measurement: style.required_value(data.audio_level_measurement),
+                attack: data.audio_attack_millis,
+                release: data.audio_release_millis,
+            };
+            persistence::Source::AudioInput(s)
+        }
     };
     Ok(source)
 }
@@ -292,6 +331,10 @@ fn convert_program_number(v: Option<U14>) -> Option<u8> {
     Some(v?.get() as _)
 }
 
+fn convert_device_id(v: Option<U14>) -> Option<u8> {
+    Some(v?.get() as _)
+}
+
 fn convert_character(
     v: SourceCharacter,
     style: ConversionStyle,
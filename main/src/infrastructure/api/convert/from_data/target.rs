@@ -15,28 +15,32 @@ use crate::infrastructure::api::convert::from_data::{
 use crate::infrastructure::api::convert::{defaults, ConversionResult};
 use crate::infrastructure::data::{
     deserialize_fx, deserialize_fx_parameter, deserialize_track, deserialize_track_route,
-    MigrationDescriptor, TargetModelData, TrackData,
+    BookmarkData, MigrationDescriptor, TargetModelData, TrackData,
 };
 use realearn_api::persistence;
 use realearn_api::persistence::{
     AllTrackFxOnOffStateTarget, AnyOnTarget, AutomationModeOverrideTarget,
     BackwardCompatibleMappingSnapshotDescForTake, BookmarkDescriptor, BookmarkRef,
     BrowseFxChainTarget, BrowseFxPresetsTarget, BrowseGroupMappingsTarget,
-    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseTracksTarget, ClipColumnDescriptor,
-    ClipColumnTarget, ClipManagementTarget, ClipMatrixTarget, ClipRowTarget, ClipSeekTarget,
-    ClipTransportActionTarget, ClipVolumeTarget, DummyTarget, EnableInstancesTarget,
-    EnableMappingsTarget, FxOnOffStateTarget, FxOnlineOfflineStateTarget,
-    FxParameterAutomationTouchStateTarget, FxParameterValueTarget, FxToolTarget,
-    FxVisibilityTarget, GoToBookmarkTarget, LastTouchedTarget, LoadFxSnapshotTarget,
-    LoadMappingSnapshotTarget, LoadPotPresetTarget, MouseTarget, PlayRateTarget,
-    PreviewPotPresetTarget, ReaperActionTarget, RouteAutomationModeTarget, RouteMonoStateTarget,
+    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseProjectTabsTarget,
+    BrowseTracksTarget, ClipColumnDescriptor, ClipColumnTarget, ClipManagementTarget,
+    ClipMatrixTarget, ClipRowTarget, ClipSeekTarget, ClipTransportActionTarget, ClipVolumeTarget,
+    DummyTarget, EnableInstancesTarget, EnableMappingsTarget, FeedbackBrightnessTarget,
+    FxOnOffStateTarget, FxOnlineOfflineStateTarget, FxParameterAutomationTouchStateTarget,
+    FxParameterValueTarget, FxToolTarget, FxVisibilityTarget, GlobalParameterValueTarget,
+    GoToBookmarkTarget, ItemPropertyTarget, LastTouchedTarget, LoadFxSnapshotTarget,
+    LoadMappingSnapshotTarget, LoadPotPresetTarget, LoopRegionTarget, MidiEditorActionTarget,
+    MouseTarget, NavigateProjectTabsTarget, NavigateTracksTarget, PlayRateTarget,
+    PreviewPotPresetTarget, ReaperActionTarget, RecallFxParameterSnapshotTarget,
+    RenderProjectTarget, ResyncFeedbackTarget, RouteAutomationModeTarget, RouteMonoStateTarget,
     RouteMuteStateTarget, RoutePanTarget, RoutePhaseTarget, RouteTouchStateTarget,
-    RouteVolumeTarget, SeekTarget, SendMidiTarget, SendOscTarget, TakeMappingSnapshotTarget,
-    TempoTarget, TrackArmStateTarget, TrackAutomationModeTarget, TrackAutomationTouchStateTarget,
+    RouteVolumeTarget, SaveFxParameterSnapshotTarget, SeekTarget, SendMidiTarget, SendOscTarget,
+    TakeMappingSnapshotTarget, TempoTarget, ToggleRenderQueueTarget, ToolbarToggleTarget,
+    TrackArmStateTarget, TrackAutomationModeTarget, TrackAutomationTouchStateTarget,
     TrackMonitoringModeTarget, TrackMuteStateTarget, TrackPanTarget, TrackParentSendStateTarget,
     TrackPeakTarget, TrackPhaseTarget, TrackSelectionStateTarget, TrackSoloStateTarget,
-    TrackToolTarget, TrackVisibilityTarget, TrackVolumeTarget, TrackWidthTarget,
-    TransportActionTarget,
+    TrackToolTarget, TrackVisibilityTarget, TrackVolumeTarget, TrackVolumeTrimTarget,
+    TrackWidthTarget, TransportActionTarget, WriteEnvelopePointsTarget,
 };
 
 pub fn convert_target(
@@ -75,17 +79,7 @@ fn convert_real_target(
         }
         Action => T::ReaperAction(ReaperActionTarget {
             commons,
-            command: {
-                if let Some(n) = data.command_name {
-                    let v = match n.parse::<u32>() {
-                        Ok(id) => persistence::ReaperCommand::Id(id),
-                        Err(_) => persistence::ReaperCommand::Name(n),
-                    };
-                    Some(v)
-                } else {
-                    None
-                }
-            },
+            command: data.command_name.map(convert_reaper_command),
             invocation: {
                 use persistence::ActionInvocationKind as T;
                 use ActionInvocationType::*;
@@ -108,6 +102,10 @@ fn convert_real_target(
                 None
             },
         }),
+        ToolbarToggle => T::ToolbarToggle(ToolbarToggleTarget {
+            commons,
+            command: data.command_name.map(convert_reaper_command),
+        }),
         Transport => T::TransportAction(TransportActionTarget {
             commons,
             action: convert_transport_action(data.transport_action),
@@ -116,17 +114,18 @@ fn convert_real_target(
             commons,
             parameter: convert_any_on_parameter(data.any_on_parameter),
         }),
+        ItemProperty => T::ItemProperty(ItemPropertyTarget {
+            commons,
+            property: style.required_value(data.item_property),
+        }),
+        MidiEditorAction => T::MidiEditorAction(MidiEditorActionTarget {
+            commons,
+            action: style.required_value(data.midi_editor_action),
+        }),
         GoToBookmark => T::GoToBookmark(GoToBookmarkTarget {
             commons,
             bookmark: {
-                let bookmark_ref = match data.bookmark_data.anchor {
-                    BookmarkAnchorType::Id => BookmarkRef::ById {
-                        id: data.bookmark_data.r#ref,
-                    },
-                    BookmarkAnchorType::Index => BookmarkRef::ByIndex {
-                        index: data.bookmark_data.r#ref,
-                    },
-                };
+                let bookmark_ref = convert_bookmark_ref(&data.bookmark_data);
                 if data.bookmark_data.is_region {
                     BookmarkDescriptor::Region(bookmark_ref)
                 } else {
@@ -143,6 +142,14 @@ fn convert_real_target(
             ),
             seek_behavior: style.optional_value(data.seek_behavior),
         }),
+        LoopRegion => T::LoopRegion(LoopRegionTarget {
+            commons,
+            region: convert_bookmark_ref(&data.bookmark_data),
+            start_playback: style.required_value_with_default(
+                data.seek_options.seek_play,
+                defaults::TARGET_LOOP_REGION_START_PLAYBACK,
+            ),
+        }),
         TrackAutomationMode => T::TrackAutomationMode(TrackAutomationModeTarget {
             commons,
             track: convert_track_descriptor(
@@ -218,6 +225,14 @@ fn convert_real_target(
             ),
             retrigger: style
                 .required_value_with_default(data.retrigger, defaults::TARGET_RETRIGGER),
+            low_latency: style
+                .required_value_with_default(data.low_latency, defaults::TARGET_LOW_LATENCY),
+            thinning: data
+                .parameter_thinning
+                .map(|t| persistence::ParameterThinning {
+                    min_interval: Some(t.min_interval_millis),
+                    min_delta: Some(t.min_delta.get()),
+                }),
             parameter: convert_fx_parameter_descriptor(data, style),
         }),
         FxParameterTouchState => {
@@ -331,6 +346,12 @@ fn convert_real_target(
                 let dest = match data.send_midi_destination {
                     FxOutput => T::FxOutput,
                     FeedbackOutput => T::FeedbackOutput,
+                    // Without a device, the message can't go anywhere else than the feedback output
+                    // (same as with OSC).
+                    Device => match data.midi_output_device_id {
+                        None => T::FeedbackOutput,
+                        Some(id) => T::Device { id },
+                    },
                 };
                 style.required_value(dest)
             },
@@ -348,6 +369,36 @@ fn convert_real_target(
             ),
             mode: style.required_value(data.browse_tracks_mode),
         }),
+        NavigateTracks => T::NavigateTracks(NavigateTracksTarget {
+            commons,
+            scroll_arrange_view: style.required_value_with_default(
+                data.scroll_arrange_view,
+                defaults::TARGET_TRACK_SELECTION_SCROLL_ARRANGE_VIEW,
+            ),
+            scroll_mixer: style.required_value_with_default(
+                data.scroll_mixer,
+                defaults::TARGET_TRACK_SELECTION_SCROLL_MIXER,
+            ),
+            mode: style.required_value(data.browse_tracks_mode),
+            bank_size: style.optional_value_with_default(
+                data.track_bank_size,
+                defaults::TARGET_NAVIGATE_TRACKS_BANK_SIZE,
+            ),
+            wrap_around: style.required_value_with_default(
+                data.wrap_around,
+                defaults::TARGET_NAVIGATE_TRACKS_WRAP_AROUND,
+            ),
+        }),
+        BrowseProjectTabs => T::BrowseProjectTabs(BrowseProjectTabsTarget { commons }),
+        RenderProject => T::RenderProject(RenderProjectTarget { commons }),
+        ToggleRenderQueue => T::ToggleRenderQueue(ToggleRenderQueueTarget { commons }),
+        NavigateProjectTabs => T::NavigateProjectTabs(NavigateProjectTabsTarget {
+            commons,
+            wrap_around: style.required_value_with_default(
+                data.wrap_around,
+                defaults::TARGET_NAVIGATE_PROJECT_TABS_WRAP_AROUND,
+            ),
+        }),
         Seek => T::Seek(SeekTarget {
             commons,
             use_time_selection: style.required_value_with_default(
@@ -542,6 +593,15 @@ fn convert_real_target(
                 defaults::TARGET_USE_SELECTION_GANGING,
             ),
         }),
+        TrackVolumeTrim => T::TrackVolumeTrim(TrackVolumeTrimTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+        }),
         TrackTool => T::TrackTool(TrackToolTarget {
             commons,
             track: convert_track_descriptor(
@@ -572,6 +632,8 @@ fn convert_real_target(
                 });
                 style.optional_value(v)
             },
+            solo_defeat: style
+                .optional_value_with_default(data.solo_defeat, defaults::TARGET_TRACK_SOLO_DEFEAT),
             use_track_grouping: style.optional_value_with_default(
                 data.use_track_grouping,
                 defaults::TARGET_USE_TRACK_GROUPING,
@@ -611,6 +673,28 @@ fn convert_real_target(
             },
             fx: convert_fx_descriptor(data, style),
         }),
+        SaveFxParameterSnapshot => T::SaveFxParameterSnapshot(SaveFxParameterSnapshotTarget {
+            commons,
+            fx: convert_fx_descriptor(data, style),
+            snapshot: style.optional_value_with_default(
+                data.fx_parameter_snapshot,
+                defaults::TARGET_FX_PARAMETER_SNAPSHOT,
+            ),
+        }),
+        RecallFxParameterSnapshot => {
+            T::RecallFxParameterSnapshot(RecallFxParameterSnapshotTarget {
+                commons,
+                fx: convert_fx_descriptor(data, style),
+                snapshot: style.optional_value_with_default(
+                    data.fx_parameter_snapshot,
+                    defaults::TARGET_FX_PARAMETER_SNAPSHOT,
+                ),
+                glide_time: style.optional_value_with_default(
+                    data.glide_time,
+                    defaults::TARGET_FX_PARAMETER_SNAPSHOT_GLIDE_TIME,
+                ),
+            })
+        }
         FxPreset => T::BrowseFxPresets(BrowseFxPresetsTarget {
             commons,
             fx: convert_fx_descriptor(data, style),
@@ -680,6 +764,13 @@ fn convert_real_target(
                 .map(BackwardCompatibleMappingSnapshotDescForTake::New)
                 .unwrap_or_default(),
         }),
+        WriteEnvelopePoints => T::WriteEnvelopePoints(WriteEnvelopePointsTarget { commons }),
+        GlobalParameterValue => T::GlobalParameterValue(GlobalParameterValueTarget {
+            commons,
+            name: data.global_parameter_name,
+        }),
+        FeedbackBrightness => T::FeedbackBrightness(FeedbackBrightnessTarget { commons }),
+        ResyncFeedback => T::ResyncFeedback(ResyncFeedbackTarget { commons }),
         BrowseGroup => T::BrowseGroupMappings(BrowseGroupMappingsTarget {
             commons,
             exclusivity: {
@@ -738,6 +829,14 @@ fn convert_automation_mode_override(
     }
 }
 
+/// Built-in actions are saved with their numeric command ID, all others with their command name.
+fn convert_reaper_command(command_name: String) -> persistence::ReaperCommand {
+    match command_name.parse::<u32>() {
+        Ok(id) => persistence::ReaperCommand::Id(id),
+        Err(_) => persistence::ReaperCommand::Name(command_name),
+    }
+}
+
 fn convert_transport_action(transport_action: TransportAction) -> persistence::TransportAction {
     use persistence::TransportAction as T;
     use TransportAction::*;
@@ -806,6 +905,8 @@ fn convert_virtual_target(data: TargetModelData, style: ConversionStyle) -> pers
     persistence::Target::Virtual(persistence::VirtualTarget {
         id: convert_control_element_id(data.control_element_index),
         character: convert_control_element_kind(data.control_element_type, style),
+        display_line_count: data.display_layout.line_count,
+        display_line_length: data.display_layout.line_length,
     })
 }
 
@@ -1021,3 +1122,13 @@ fn convert_feedback_resolution(
     };
     style.required_value(v)
 }
+
+fn convert_bookmark_ref(data: &BookmarkData) -> BookmarkRef {
+    match data.anchor {
+        BookmarkAnchorType::Id => BookmarkRef::ById { id: data.r#ref },
+        BookmarkAnchorType::Index => BookmarkRef::ByIndex { index: data.r#ref },
+        BookmarkAnchorType::Name => BookmarkRef::ByName {
+            name: data.name.clone(),
+        },
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
+use crate::application::{CompartmentLifecycleModel, MessageTemplatesModel};
 use crate::domain::{CompartmentParamIndex, ParamSetting};
 use crate::infrastructure::api::convert::to_data::group::convert_group;
 use crate::infrastructure::api::convert::to_data::parameter::convert_parameter;
-use crate::infrastructure::api::convert::to_data::{convert_mapping, ApiToDataConversionContext};
+use crate::infrastructure::api::convert::to_data::{
+    convert_lifecycle_hook, convert_mapping, ApiToDataConversionContext,
+};
 use crate::infrastructure::api::convert::{convert_multiple, ConversionResult};
 use crate::infrastructure::data::{CompartmentModelData, GroupModelData};
 use realearn_api::persistence::*;
@@ -45,6 +48,8 @@ pub fn convert_compartment(c: Compartment) -> ConversionResult<CompartmentModelD
         convert_group(g, false, |key| param_index_by_key(&parameters, key))
     })?;
     let context = ConversionContext { parameters, groups };
+    let message_templates = MessageTemplatesModel::new(c.message_templates.unwrap_or_default());
+    message_templates.validate()?;
     let data = CompartmentModelData {
         default_group: Some(convert_group(
             c.default_group.unwrap_or_default(),
@@ -62,6 +67,11 @@ pub fn convert_compartment(c: Compartment) -> ConversionResult<CompartmentModelD
         groups: context.groups,
         custom_data: c.custom_data.unwrap_or_default(),
         notes: c.notes.unwrap_or_default(),
+        lifecycle: CompartmentLifecycleModel {
+            on_init: convert_lifecycle_hook(c.on_init)?,
+            on_shutdown: convert_lifecycle_hook(c.on_shutdown)?,
+        },
+        message_templates,
     };
     Ok(data)
 }
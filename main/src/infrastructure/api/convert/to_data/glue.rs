@@ -1,9 +1,8 @@
 use crate::infrastructure::api::convert::defaults;
 use crate::infrastructure::api::convert::ConversionResult;
 use crate::infrastructure::data::ModeModelData;
-use helgoboss_learn::{DiscreteIncrement, SoftSymmetricUnitValue, UnitValue};
 use realearn_api::persistence::*;
-use std::convert::TryInto;
+use realearn_api_convert::*;
 
 pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
     let source_interval =
@@ -52,49 +51,9 @@ pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
             },
         }
     };
-    let (min_press_millis, max_press_millis) = {
-        use FireMode::*;
-        match &fire_mode {
-            Normal(m) => {
-                let api_interval = m
-                    .press_duration_interval
-                    .unwrap_or(defaults::FIRE_MODE_PRESS_DURATION_INTERVAL);
-                let interval = helgoboss_learn::Interval::try_new(
-                    api_interval.0 as u64,
-                    api_interval.1 as u64,
-                )?;
-                (interval.min_val(), interval.max_val())
-            }
-            OnSinglePress(m) => {
-                let max = m
-                    .max_duration
-                    .unwrap_or(defaults::FIRE_MODE_SINGLE_PRESS_MAX_DURATION)
-                    as u64;
-                (0, max)
-            }
-            AfterTimeout(m) => {
-                let min = m.timeout.unwrap_or(defaults::FIRE_MODE_TIMEOUT) as u64;
-                (min, min)
-            }
-            AfterTimeoutKeepFiring(m) => {
-                let min = m.timeout.unwrap_or(defaults::FIRE_MODE_TIMEOUT) as u64;
-                (min, min)
-            }
-            OnDoublePress(_) => (0, 0),
-        }
-    };
+    let press_duration_interval = convert_press_duration_interval(&fire_mode)?;
     let data = ModeModelData {
-        r#type: {
-            use helgoboss_learn::AbsoluteMode as T;
-            use AbsoluteMode::*;
-            match g.absolute_mode.unwrap_or_default() {
-                Normal => T::Normal,
-                IncrementalButton => T::IncrementalButton,
-                ToggleButton => T::ToggleButton,
-                MakeRelative => T::MakeRelative,
-                PerformanceControl => T::PerformanceControl,
-            }
-        },
+        r#type: convert_absolute_mode(g.absolute_mode),
         min_source_value: source_interval.min_val(),
         max_source_value: source_interval.max_val(),
         min_target_value: target_interval.min_val(),
@@ -105,14 +64,12 @@ pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
         max_step_size: step_size_interval.max_val(),
         min_step_factor: Some(step_factor_interval.min_val()),
         max_step_factor: Some(step_factor_interval.max_val()),
-        min_press_millis,
-        max_press_millis,
-        turbo_rate: {
-            use FireMode::*;
-            match &fire_mode {
-                AfterTimeoutKeepFiring(m) => m.rate.unwrap_or(defaults::FIRE_MODE_RATE) as u64,
-                _ => 0,
-            }
+        min_press_millis: press_duration_interval.min_val().as_millis() as u64,
+        max_press_millis: press_duration_interval.max_val().as_millis() as u64,
+        turbo_rate: convert_turbo_rate(&fire_mode).as_millis() as u64,
+        synced_turbo_rate: match &fire_mode {
+            FireMode::AfterTimeoutKeepFiring(m) => m.synced_rate,
+            _ => None,
         },
         eel_control_transformation: g.control_transformation.unwrap_or_default(),
         eel_feedback_transformation: fb_data.transformation,
@@ -120,83 +77,20 @@ pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
         feedback_color: fb_data.commons.color,
         feedback_background_color: fb_data.commons.background_color,
         ignore_out_of_range_source_values_is_enabled: false,
-        out_of_range_behavior: {
-            use helgoboss_learn::OutOfRangeBehavior as T;
-            use OutOfRangeBehavior::*;
-            match g.out_of_range_behavior.unwrap_or_default() {
-                MinOrMax => T::MinOrMax,
-                Min => T::Min,
-                Ignore => T::Ignore,
-            }
-        },
-        fire_mode: {
-            use helgoboss_learn::FireMode as T;
-            use FireMode::*;
-            match &fire_mode {
-                Normal(_) => T::Normal,
-                AfterTimeout(_) => T::AfterTimeout,
-                AfterTimeoutKeepFiring(_) => T::AfterTimeoutKeepFiring,
-                OnSinglePress(_) => T::OnSinglePress,
-                OnDoublePress(_) => T::OnDoublePress,
-            }
-        },
+        out_of_range_behavior: convert_out_of_range_behavior(g.out_of_range_behavior),
+        fire_mode: convert_fire_mode(&fire_mode),
         round_target_value: g
             .round_target_value
             .unwrap_or(defaults::GLUE_ROUND_TARGET_VALUE),
         scale_mode_enabled: false,
-        takeover_mode: {
-            use helgoboss_learn::TakeoverMode as T;
-            use TakeoverMode::*;
-            match g.takeover_mode.unwrap_or_default() {
-                Off => T::Off,
-                PickUpTolerant => T::PickupTolerant,
-                PickUp => T::Pickup,
-                LongTimeNoSee => T::LongTimeNoSee,
-                Parallel => T::Parallel,
-                CatchUp => T::CatchUp,
-            }
-        },
-        button_usage: {
-            use helgoboss_learn::ButtonUsage as T;
-            if let Some(f) = g.button_filter {
-                use ButtonFilter::*;
-                match f {
-                    PressOnly => T::PressOnly,
-                    ReleaseOnly => T::ReleaseOnly,
-                }
-            } else {
-                T::Both
-            }
-        },
-        encoder_usage: {
-            use helgoboss_learn::EncoderUsage as T;
-            if let Some(f) = g.encoder_filter {
-                use EncoderFilter::*;
-                match f {
-                    IncrementOnly => T::IncrementOnly,
-                    DecrementOnly => T::DecrementOnly,
-                }
-            } else {
-                T::Both
-            }
-        },
+        takeover_mode: convert_takeover_mode(g.takeover_mode),
+        button_usage: convert_button_usage(g.button_filter),
+        encoder_usage: convert_encoder_usage(g.encoder_filter),
         rotate_is_enabled: g.wrap.unwrap_or(defaults::GLUE_WRAP),
         make_absolute_enabled: g.relative_mode.unwrap_or_default() == RelativeMode::MakeAbsolute,
-        group_interaction: {
-            use helgoboss_learn::GroupInteraction as T;
-            if let Some(i) = g.interaction {
-                use Interaction::*;
-                match i {
-                    SameControl => T::SameControl,
-                    SameTargetValue => T::SameTargetValue,
-                    InverseControl => T::InverseControl,
-                    InverseTargetValue => T::InverseTargetValue,
-                    InverseTargetValueOnOnly => T::InverseTargetValueOnOnly,
-                }
-            } else {
-                T::None
-            }
-        },
+        acceleration_curve: g.acceleration_curve.unwrap_or_default(),
+        control_curve: g.control_curve.unwrap_or_default(),
+        group_interaction: convert_group_interaction(g.interaction),
         target_value_sequence: if let Some(s) = g.target_value_sequence {
             s.parse()?
         } else {
@@ -207,45 +101,3 @@ pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
     };
     Ok(data)
 }
-
-fn convert_step_factor_interval(
-    i: Interval<i32>,
-) -> ConversionResult<helgoboss_learn::Interval<DiscreteIncrement>> {
-    let result = helgoboss_learn::Interval::try_new(
-        i.0.try_into().unwrap_or(DiscreteIncrement::POSITIVE_MIN),
-        i.1.try_into().unwrap_or(DiscreteIncrement::POSITIVE_MIN),
-    )?;
-    Ok(result)
-}
-
-fn convert_step_size_interval(
-    i: Interval<f64>,
-) -> ConversionResult<helgoboss_learn::Interval<SoftSymmetricUnitValue>> {
-    let uv_interval = convert_unit_value_interval(i)?;
-    let result = helgoboss_learn::Interval::try_new(
-        uv_interval.min_val().to_symmetric(),
-        uv_interval.max_val().to_symmetric(),
-    )?;
-    Ok(result)
-}
-
-fn convert_unit_value_interval(
-    interval: Interval<f64>,
-) -> ConversionResult<helgoboss_learn::Interval<UnitValue>> {
-    let result =
-        helgoboss_learn::Interval::try_new(interval.0.try_into()?, interval.1.try_into()?)?;
-    Ok(result)
-}
-
-fn convert_virtual_color(color: VirtualColor) -> helgoboss_learn::VirtualColor {
-    use helgoboss_learn::VirtualColor as T;
-    use VirtualColor::*;
-    match color {
-        Rgb(c) => T::Rgb(convert_rgb_color(c)),
-        Prop(c) => T::Prop { prop: c.prop },
-    }
-}
-
-fn convert_rgb_color(color: RgbColor) -> helgoboss_learn::RgbColor {
-    helgoboss_learn::RgbColor::new(color.0, color.1, color.2)
-}
@@ -3,6 +3,7 @@ use crate::infrastructure::api::convert::to_data::{convert_activation, convert_t
 use crate::infrastructure::api::convert::{defaults, ConversionResult};
 use crate::infrastructure::data::{EnabledData, GroupModelData};
 use realearn_api::persistence::*;
+use realearn_api_convert::convert_virtual_color;
 
 pub fn convert_group(
     g: Group,
@@ -30,6 +31,7 @@ pub fn convert_group(
         } else {
             Default::default()
         },
+        feedback_color: g.feedback_color.map(convert_virtual_color),
     };
     Ok(data)
 }
@@ -73,7 +73,15 @@ pub fn convert_mapping(
         visible_in_projection: m
             .visible_in_projection
             .unwrap_or(defaults::MAPPING_VISIBLE_IN_PROJECTION),
+        feedback_only_if_visible_in_projection: m
+            .feedback_only_if_visible_in_projection
+            .unwrap_or(defaults::MAPPING_FEEDBACK_ONLY_IF_VISIBLE_IN_PROJECTION),
         success_audio_feedback: m.success_audio_feedback,
+        crossfade_role: m.crossfade_role,
+        feedback_effect: m.feedback_effect,
+        invert_feedback: m
+            .invert_feedback
+            .unwrap_or(defaults::MAPPING_INVERT_FEEDBACK),
     };
     Ok(v)
 }
@@ -105,6 +113,8 @@ fn convert_advanced(
     let extension_model = MappingExtensionModel {
         on_activate: convert_lifecycle_hook(on_activate)?,
         on_deactivate: convert_lifecycle_hook(on_deactivate)?,
+        // Not part of the API yet, ends up in the unprocessed properties
+        on_control: Default::default(),
     };
     let value = serde_yaml::to_value(&extension_model)?;
     let mut mapping = into_yaml_mapping(value);
@@ -118,11 +128,11 @@ fn convert_advanced(
     Ok(Some(mapping))
 }
 
-fn convert_lifecycle_hook(hook: Option<LifecycleHook>) -> ConversionResult<LifecycleModel> {
+pub fn convert_lifecycle_hook(hook: Option<LifecycleHook>) -> ConversionResult<LifecycleModel> {
+    let hook = hook.unwrap_or_default();
     let v = LifecycleModel {
         send_midi_feedback: {
             let actions: Result<Vec<_>, _> = hook
-                .unwrap_or_default()
                 .send_midi_feedback
                 .unwrap_or_default()
                 .into_iter()
@@ -130,6 +140,7 @@ fn convert_lifecycle_hook(hook: Option<LifecycleHook>) -> ConversionResult<Lifec
                 .collect();
             actions?
         },
+        invoke_reaper_actions: hook.invoke_reaper_actions.unwrap_or_default(),
     };
     Ok(v)
 }
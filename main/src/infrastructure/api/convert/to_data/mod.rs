@@ -30,6 +30,7 @@ fn convert_control_element_type(
     match s {
         Multi => T::Multi,
         Button => T::Button,
+        Display => T::Display,
     }
 }
 
@@ -141,6 +142,18 @@ fn convert_activation(
             eel_condition: c.condition,
             ..Default::default()
         },
+        Playing => ActivationConditionData {
+            activation_type: ActivationType::WhilePlaying,
+            ..Default::default()
+        },
+        Recording => ActivationConditionData {
+            activation_type: ActivationType::WhileRecording,
+            ..Default::default()
+        },
+        Stopped => ActivationConditionData {
+            activation_type: ActivationType::WhileStopped,
+            ..Default::default()
+        },
     };
     Ok(data)
 }
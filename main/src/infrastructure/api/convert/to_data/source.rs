@@ -8,6 +8,7 @@ use crate::infrastructure::data::SourceModelData;
 use helgoboss_learn::DisplayType;
 use helgoboss_midi::{Channel, U14};
 use realearn_api::persistence::*;
+use realearn_api_convert::{convert_channel, convert_character};
 use std::convert::TryInto;
 
 pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
@@ -36,6 +37,10 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             MidiClockTransport(s) => convert_midi_clock_transport_message(s.message),
             _ => Default::default(),
         },
+        mmc_command: match &s {
+            MidiMachineControl(s) => s.command.unwrap_or_default(),
+            _ => Default::default(),
+        },
         raw_midi_pattern: match &s {
             MidiRaw(s) => s.pattern.as_ref().cloned().unwrap_or_default(),
             _ => Default::default(),
@@ -83,6 +88,48 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             SiniConE24Display(s) => s.item_index,
             _ => None,
         },
+        velocity_feedback: match &s {
+            MidiNoteVelocity(s) => s.velocity_feedback.clone(),
+            MidiPolyphonicKeyPressureAmount(s) => s.velocity_feedback.clone(),
+            _ => None,
+        },
+        feedback_as_note_on: match &s {
+            MidiPolyphonicKeyPressureAmount(s) => s.feedback_as_note_on.unwrap_or(false),
+            _ => false,
+        },
+        debounce: match &s {
+            MidiControlChangeValue(s) => s.debounce,
+            MidiPitchBendChangeValue(s) => s.debounce,
+            MidiChannelPressureAmount(s) => s.debounce,
+            MidiPolyphonicKeyPressureAmount(s) => s.debounce,
+            MidiParameterNumberValue(s) => s.debounce,
+            Osc(s) => s.debounce,
+            _ => None,
+        },
+        pitch_bend_behavior: match &s {
+            MidiPitchBendChangeValue(s) => s.behavior,
+            _ => None,
+        },
+        audio_input_kind: match &s {
+            AudioInput(s) => s.input.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        audio_channel: match &s {
+            AudioInput(s) => s.channel.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        audio_level_measurement: match &s {
+            AudioInput(s) => s.measurement.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        audio_attack_millis: match &s {
+            AudioInput(s) => s.attack,
+            _ => None,
+        },
+        audio_release_millis: match &s {
+            AudioInput(s) => s.release,
+            _ => None,
+        },
         osc_address_pattern: match &s {
             Osc(s) => s.address.as_ref().cloned().unwrap_or_default(),
             _ => Default::default(),
@@ -127,12 +174,25 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             RealearnInstanceStart(_) => ReaperSourceType::RealearnInstanceStart,
             Timer(_) => ReaperSourceType::Timer,
             RealearnParameter(_) => ReaperSourceType::RealearnParameter,
+            OneShotTimer(_) => ReaperSourceType::OneShotTimer,
+            Lfo(_) => ReaperSourceType::Lfo,
+            Random(_) => ReaperSourceType::Random,
             _ => Default::default(),
         },
         timer_millis: match &s {
             Timer(t) => t.duration,
+            OneShotTimer(t) => t.duration,
+            Random(s) => s.interval,
             _ => Default::default(),
         },
+        lfo_shape: match &s {
+            Lfo(s) => s.shape.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        lfo_period: match &s {
+            Lfo(s) => s.period,
+            _ => None,
+        },
         parameter_index: match &s {
             RealearnParameter(s) => s.parameter_index.try_into()?,
             _ => Default::default(),
@@ -149,7 +209,10 @@ fn convert_category(s: &Source) -> SourceCategory {
         | RealearnInstanceStart(_)
         | Timer(_)
         | RealearnParameter(_)
-        | Speech(_) => SourceCategory::Reaper,
+        | Speech(_)
+        | OneShotTimer(_)
+        | Lfo(_)
+        | Random(_) => SourceCategory::Reaper,
         MidiNoteVelocity(_)
         | MidiNoteKeyNumber(_)
         | MidiPolyphonicKeyPressureAmount(_)
@@ -161,6 +224,7 @@ fn convert_category(s: &Source) -> SourceCategory {
         | MidiParameterNumberValue(_)
         | MidiClockTempo(_)
         | MidiClockTransport(_)
+        | MidiMachineControl(_)
         | MidiRaw(_)
         | MidiScript(_)
         | MackieLcd(_)
@@ -169,6 +233,7 @@ fn convert_category(s: &Source) -> SourceCategory {
         | SiniConE24Display(_)
         | SlKeyboardDisplay(_)
         | LaunchpadProScrollingTextDisplay(_) => SourceCategory::Midi,
+        AudioInput(_) => SourceCategory::Audio,
         Osc(_) => SourceCategory::Osc,
         Key(_) => SourceCategory::Keyboard,
         Virtual(_) => SourceCategory::Virtual,
@@ -189,6 +254,7 @@ fn convert_midi_source_type(s: &Source) -> MidiSourceType {
         MidiParameterNumberValue(_) => MidiSourceType::ParameterNumberValue,
         MidiClockTempo(_) => MidiSourceType::ClockTempo,
         MidiClockTransport(_) => MidiSourceType::ClockTransport,
+        MidiMachineControl(_) => MidiSourceType::MachineControl,
         MidiRaw(_) => MidiSourceType::Raw,
         MidiScript(_) => MidiSourceType::Script,
         MackieLcd(_) | XTouchMackieLcd(_) | MackieSevenSegmentDisplay(_) | SiniConE24Display(_) => {
@@ -212,12 +278,7 @@ fn convert_midi_channel(s: &Source) -> ConversionResult<Option<Channel>> {
         MidiParameterNumberValue(s) => s.channel,
         _ => None,
     };
-    if let Some(ch) = ch {
-        let ch: Channel = ch.try_into()?;
-        Ok(Some(ch))
-    } else {
-        Ok(None)
-    }
+    convert_channel(ch)
 }
 
 fn convert_midi_number(s: &Source) -> ConversionResult<Option<U14>> {
@@ -227,6 +288,7 @@ fn convert_midi_number(s: &Source) -> ConversionResult<Option<U14>> {
         MidiPolyphonicKeyPressureAmount(s) => s.key_number.map(|n| n as u16),
         MidiControlChangeValue(s) => s.controller_number.map(|n| n as u16),
         MidiSpecificProgramChange(s) => s.program_number.map(|n| n as u16),
+        MidiMachineControl(s) => s.device_id.map(|n| n as u16),
         MidiParameterNumberValue(s) => s.number,
         _ => None,
     };
@@ -238,19 +300,6 @@ fn convert_midi_number(s: &Source) -> ConversionResult<Option<U14>> {
     }
 }
 
-fn convert_character(s: Option<SourceCharacter>) -> helgoboss_learn::SourceCharacter {
-    use helgoboss_learn::SourceCharacter as T;
-    use SourceCharacter::*;
-    match s.unwrap_or_default() {
-        Range => T::RangeElement,
-        Button => T::MomentaryButton,
-        Relative1 => T::Encoder1,
-        Relative2 => T::Encoder2,
-        Relative3 => T::Encoder3,
-        StatefulButton => T::ToggleButton,
-    }
-}
-
 fn convert_midi_clock_transport_message(
     s: Option<MidiClockTransportMessage>,
 ) -> helgoboss_learn::MidiClockTransportMessage {
@@ -6,7 +6,7 @@ use crate::application::{
 };
 use crate::domain::{
     ActionInvocationType, Exclusivity, FxDisplayType, ReaperTargetType, SeekOptions,
-    SendMidiDestination, TouchedRouteParameterType, TrackRouteType,
+    SendMidiDestination, TouchedRouteParameterType, TrackRouteType, VirtualDisplayLayout,
 };
 use crate::infrastructure::api::convert::to_data::{
     convert_control_element_id, convert_control_element_type, convert_osc_arg_type,
@@ -18,6 +18,7 @@ use crate::infrastructure::data::{
     FxData, FxParameterData, TargetModelData, TrackData, TrackRouteData,
 };
 use crate::{application, domain};
+use helgoboss_learn::UnitValue;
 use realearn_api::persistence::*;
 use reaper_high::Guid;
 use std::rc::Rc;
@@ -65,10 +66,7 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             TargetModelData {
                 category: TargetCategory::Reaper,
                 r#type: ReaperTargetType::Action,
-                command_name: d.command.map(|cmd| match cmd {
-                    ReaperCommand::Id(id) => id.to_string(),
-                    ReaperCommand::Name(n) => n,
-                }),
+                command_name: d.command.map(convert_reaper_command),
                 invocation_type: {
                     use ActionInvocationKind as K;
                     use ActionInvocationType as T;
@@ -92,6 +90,12 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::ToolbarToggle(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::ToolbarToggle,
+            command_name: d.command.map(convert_reaper_command),
+            ..init(d.commons)
+        },
         Target::TransportAction(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::Transport,
@@ -116,6 +120,45 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             browse_tracks_mode: d.mode.unwrap_or_default(),
             ..init(d.commons)
         },
+        Target::NavigateTracks(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::NavigateTracks,
+            scroll_arrange_view: d
+                .scroll_arrange_view
+                .unwrap_or(defaults::TARGET_TRACK_SELECTION_SCROLL_ARRANGE_VIEW),
+            scroll_mixer: d
+                .scroll_mixer
+                .unwrap_or(defaults::TARGET_TRACK_SELECTION_SCROLL_MIXER),
+            browse_tracks_mode: d.mode.unwrap_or_default(),
+            track_bank_size: d.bank_size,
+            wrap_around: d
+                .wrap_around
+                .unwrap_or(defaults::TARGET_NAVIGATE_TRACKS_WRAP_AROUND),
+            ..init(d.commons)
+        },
+        Target::BrowseProjectTabs(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::BrowseProjectTabs,
+            ..init(d.commons)
+        },
+        Target::RenderProject(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::RenderProject,
+            ..init(d.commons)
+        },
+        Target::ToggleRenderQueue(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::ToggleRenderQueue,
+            ..init(d.commons)
+        },
+        Target::NavigateProjectTabs(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::NavigateProjectTabs,
+            wrap_around: d
+                .wrap_around
+                .unwrap_or(defaults::TARGET_NAVIGATE_PROJECT_TABS_WRAP_AROUND),
+            ..init(d.commons)
+        },
         Target::Seek(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::Seek,
@@ -147,28 +190,24 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             r#type: ReaperTargetType::Tempo,
             ..init(d.commons)
         },
+        Target::ItemProperty(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::ItemProperty,
+            item_property: d.property.unwrap_or_default(),
+            ..init(d.commons)
+        },
+        Target::MidiEditorAction(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::MidiEditorAction,
+            midi_editor_action: d.action.unwrap_or_default(),
+            ..init(d.commons)
+        },
         Target::GoToBookmark(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::GoToBookmark,
-            bookmark_data: {
-                match d.bookmark {
-                    BookmarkDescriptor::Marker(r) => {
-                        let (anchor, r#ref) = convert_bookmark_ref(r);
-                        BookmarkData {
-                            anchor,
-                            r#ref,
-                            is_region: false,
-                        }
-                    }
-                    BookmarkDescriptor::Region(r) => {
-                        let (anchor, r#ref) = convert_bookmark_ref(r);
-                        BookmarkData {
-                            anchor,
-                            r#ref,
-                            is_region: true,
-                        }
-                    }
-                }
+            bookmark_data: match d.bookmark {
+                BookmarkDescriptor::Marker(r) => convert_bookmark_ref(r, false),
+                BookmarkDescriptor::Region(r) => convert_bookmark_ref(r, true),
             },
             seek_options: SeekOptions {
                 use_time_selection: d
@@ -183,6 +222,18 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             seek_behavior: d.seek_behavior,
             ..init(d.commons)
         },
+        Target::LoopRegion(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::LoopRegion,
+            bookmark_data: convert_bookmark_ref(d.region, true),
+            seek_options: SeekOptions {
+                seek_play: d
+                    .start_playback
+                    .unwrap_or(defaults::TARGET_LOOP_REGION_START_PLAYBACK),
+                ..Default::default()
+            },
+            ..init(d.commons)
+        },
         Target::TrackArmState(d) => {
             let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
             TargetModelData {
@@ -410,6 +461,17 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::TrackVolumeTrim(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackVolumeTrim,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
         Target::TrackTool(d) => {
             let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
             TargetModelData {
@@ -460,6 +522,7 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                     };
                     Some(v)
                 },
+                solo_defeat: d.solo_defeat.unwrap_or(defaults::TARGET_TRACK_SOLO_DEFEAT),
                 use_track_grouping: Some(
                     d.use_track_grouping
                         .unwrap_or(defaults::TARGET_USE_TRACK_GROUPING),
@@ -554,6 +617,37 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::SaveFxParameterSnapshot(d) => {
+            let fx_desc = convert_fx_desc(d.fx.unwrap_or_default())?;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::SaveFxParameterSnapshot,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_parameter_snapshot: d.snapshot,
+                ..init(d.commons)
+            }
+        }
+        Target::RecallFxParameterSnapshot(d) => {
+            let fx_desc = convert_fx_desc(d.fx.unwrap_or_default())?;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::RecallFxParameterSnapshot,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_parameter_snapshot: d.snapshot,
+                glide_time: d.glide_time,
+                ..init(d.commons)
+            }
+        }
         Target::BrowseFxPresets(d) => {
             let fx_desc = convert_fx_desc(d.fx.unwrap_or_default())?;
             let track_desc = fx_desc.chain_desc.track_desc;
@@ -600,6 +694,11 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                     .poll_for_feedback
                     .unwrap_or(defaults::TARGET_POLL_FOR_FEEDBACK),
                 retrigger: d.retrigger.unwrap_or(defaults::TARGET_RETRIGGER),
+                low_latency: d.low_latency.unwrap_or(defaults::TARGET_LOW_LATENCY),
+                parameter_thinning: d.thinning.map(|t| domain::ParameterThinning {
+                    min_interval_millis: t.min_interval.unwrap_or(0),
+                    min_delta: UnitValue::new_clamped(t.min_delta.unwrap_or(0.0)),
+                }),
                 ..init(d.commons)
             }
         }
@@ -787,16 +886,22 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             clip_matrix_action: d.action,
             ..init(d.commons)
         },
-        Target::SendMidi(d) => TargetModelData {
-            category: TargetCategory::Reaper,
-            r#type: ReaperTargetType::SendMidi,
-            raw_midi_pattern: d.message.unwrap_or_default(),
-            send_midi_destination: match d.destination.unwrap_or_default() {
-                MidiDestination::FxOutput => SendMidiDestination::FxOutput,
-                MidiDestination::FeedbackOutput => SendMidiDestination::FeedbackOutput,
-            },
-            ..init(d.commons)
-        },
+        Target::SendMidi(d) => {
+            let (send_midi_destination, midi_output_device_id) =
+                match d.destination.unwrap_or_default() {
+                    MidiDestination::FxOutput => (SendMidiDestination::FxOutput, None),
+                    MidiDestination::FeedbackOutput => (SendMidiDestination::FeedbackOutput, None),
+                    MidiDestination::Device { id } => (SendMidiDestination::Device, Some(id)),
+                };
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::SendMidi,
+                raw_midi_pattern: d.message.unwrap_or_default(),
+                send_midi_destination,
+                midi_output_device_id,
+                ..init(d.commons)
+            }
+        }
         Target::SendOsc(d) => {
             let (osc_arg_index, osc_arg_type, osc_arg_value_range) = if let Some(a) = d.argument {
                 (
@@ -885,6 +990,27 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             },
             ..init(d.commons)
         },
+        Target::WriteEnvelopePoints(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::WriteEnvelopePoints,
+            ..init(d.commons)
+        },
+        Target::GlobalParameterValue(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::GlobalParameterValue,
+            global_parameter_name: d.name,
+            ..init(d.commons)
+        },
+        Target::FeedbackBrightness(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::FeedbackBrightness,
+            ..init(d.commons)
+        },
+        Target::ResyncFeedback(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::ResyncFeedback,
+            ..init(d.commons)
+        },
         Target::BrowseGroupMappings(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::BrowseGroup,
@@ -933,6 +1059,10 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             category: TargetCategory::Virtual,
             control_element_type: convert_control_element_type(d.character.unwrap_or_default()),
             control_element_index: convert_control_element_id(d.id),
+            display_layout: VirtualDisplayLayout {
+                line_count: d.display_line_count,
+                line_length: d.display_line_length,
+            },
             ..Default::default()
         },
     };
@@ -1363,6 +1493,13 @@ fn convert_fx_parameter_desc(t: FxParameterDescriptor) -> ConversionResult<FxPar
     Ok(desc)
 }
 
+fn convert_reaper_command(command: ReaperCommand) -> String {
+    match command {
+        ReaperCommand::Id(id) => id.to_string(),
+        ReaperCommand::Name(n) => n,
+    }
+}
+
 fn convert_transport_action(transport_action: TransportAction) -> domain::TransportAction {
     use domain::TransportAction as T;
     use TransportAction::*;
@@ -1396,11 +1533,18 @@ fn convert_feedback_resolution(r: FeedbackResolution) -> domain::FeedbackResolut
     }
 }
 
-fn convert_bookmark_ref(r: BookmarkRef) -> (BookmarkAnchorType, u32) {
+fn convert_bookmark_ref(r: BookmarkRef, is_region: bool) -> BookmarkData {
     use BookmarkAnchorType as T;
-    match r {
-        BookmarkRef::ById { id } => (T::Id, id),
-        BookmarkRef::ByIndex { index } => (T::Index, index),
+    let (anchor, r#ref, name) = match r {
+        BookmarkRef::ById { id } => (T::Id, id, String::new()),
+        BookmarkRef::ByIndex { index } => (T::Index, index, String::new()),
+        BookmarkRef::ByName { name } => (T::Name, 0, name),
+    };
+    BookmarkData {
+        anchor,
+        r#ref,
+        name,
+        is_region,
     }
 }
 
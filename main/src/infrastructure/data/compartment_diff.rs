@@ -0,0 +1,150 @@
+use crate::infrastructure::data::{CompartmentModelData, MappingModelData};
+use std::fmt;
+use std::fmt::Display;
+
+/// Differences between the compartment as saved in a preset and the compartment as it is now.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CompartmentDiff {
+    pub added_mappings: Vec<String>,
+    pub removed_mappings: Vec<String>,
+    pub changed_mappings: Vec<String>,
+    /// Labels of other compartment aspects which changed (groups, parameters, ...).
+    pub other_changes: Vec<&'static str>,
+}
+
+impl CompartmentDiff {
+    pub fn compute(preset: &CompartmentModelData, current: &CompartmentModelData) -> Self {
+        let mapping_diff = diff_by_key(&preset.mappings, &current.mappings, mapping_key);
+        let mut other_changes = vec![];
+        if preset.default_group != current.default_group || preset.groups != current.groups {
+            other_changes.push("Groups");
+        }
+        if preset.parameters != current.parameters {
+            other_changes.push("Parameters");
+        }
+        if preset.notes != current.notes {
+            other_changes.push("Notes");
+        }
+        if preset.custom_data != current.custom_data
+            || preset.lifecycle != current.lifecycle
+            || preset.message_templates != current.message_templates
+        {
+            other_changes.push("Controller settings");
+        }
+        Self {
+            added_mappings: mapping_diff.added.into_iter().map(mapping_label).collect(),
+            removed_mappings: mapping_diff
+                .removed
+                .into_iter()
+                .map(mapping_label)
+                .collect(),
+            changed_mappings: mapping_diff
+                .changed
+                .into_iter()
+                .map(mapping_label)
+                .collect(),
+            other_changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_mappings.is_empty()
+            && self.removed_mappings.is_empty()
+            && self.changed_mappings.is_empty()
+            && self.other_changes.is_empty()
+    }
+}
+
+impl Display for CompartmentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("No differences.");
+        }
+        let sections = [
+            ("Changed mappings", &self.changed_mappings),
+            ("Added mappings", &self.added_mappings),
+            ("Removed mappings", &self.removed_mappings),
+        ];
+        for (title, labels) in sections {
+            if labels.is_empty() {
+                continue;
+            }
+            writeln!(f, "{} ({}):", title, labels.len())?;
+            for label in labels {
+                writeln!(f, "- {}", label)?;
+            }
+            writeln!(f)?;
+        }
+        if !self.other_changes.is_empty() {
+            writeln!(f, "Other changes: {}", self.other_changes.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+struct KeyedDiff<'a, T> {
+    added: Vec<&'a T>,
+    removed: Vec<&'a T>,
+    changed: Vec<&'a T>,
+}
+
+/// Matches old and new items by key. Items whose key appears in both lists but which are not
+/// equal count as changed.
+fn diff_by_key<'a, T: PartialEq>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&T) -> Option<String>,
+) -> KeyedDiff<'a, T> {
+    let find = |items: &'a [T], k: &Option<String>| {
+        k.as_ref()
+            .and_then(|k| items.iter().find(|i| key(i).as_ref() == Some(k)))
+    };
+    let mut diff = KeyedDiff {
+        added: vec![],
+        removed: vec![],
+        changed: vec![],
+    };
+    for new_item in new {
+        match find(old, &key(new_item)) {
+            None => diff.added.push(new_item),
+            Some(old_item) if old_item != new_item => diff.changed.push(new_item),
+            Some(_) => {}
+        }
+    }
+    for old_item in old {
+        if find(new, &key(old_item)).is_none() {
+            diff.removed.push(old_item);
+        }
+    }
+    diff
+}
+
+fn mapping_key(m: &MappingModelData) -> Option<String> {
+    m.id.as_ref().map(|id| id.to_string())
+}
+
+fn mapping_label(m: &MappingModelData) -> String {
+    match (&m.id, m.name.is_empty()) {
+        (_, false) => m.name.clone(),
+        (Some(id), true) => format!("<Unnamed> ({})", id),
+        (None, true) => "<Unnamed>".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_items_by_key() {
+        // Given
+        let old = [("a", 1), ("b", 2), ("c", 3)];
+        let new = [("a", 1), ("b", 5), ("d", 4)];
+        // When
+        let diff = diff_by_key(&old, &new, |i| Some(i.0.to_string()));
+        // Then
+        assert_eq!(diff.added, vec![&("d", 4)]);
+        assert_eq!(diff.removed, vec![&("c", 3)]);
+        assert_eq!(diff.changed, vec![&("b", 5)]);
+    }
+}
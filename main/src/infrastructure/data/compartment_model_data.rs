@@ -1,4 +1,7 @@
-use crate::application::{CompartmentInSession, CompartmentModel, GroupModel, Session};
+use crate::application::{
+    CompartmentInSession, CompartmentLifecycleModel, CompartmentModel, GroupModel,
+    MessageTemplatesModel, Session,
+};
 use crate::base::default_util::{deserialize_null_default, is_default};
 use crate::domain::{
     Compartment, CompartmentParamIndex, GroupId, GroupKey, MappingId, MappingKey, ParamSetting,
@@ -55,6 +58,20 @@ pub struct CompartmentModelData {
         skip_serializing_if = "is_default"
     )]
     pub notes: String,
+    /// At the moment, lifecycle messages are only used in the controller compartment.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub lifecycle: CompartmentLifecycleModel,
+    /// At the moment, message templates are only used in the controller compartment.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub message_templates: MessageTemplatesModel,
 }
 
 impl ModelToDataConversionContext for CompartmentModel {
@@ -90,6 +107,8 @@ impl CompartmentModelData {
                 .collect(),
             custom_data: model.custom_data.clone(),
             notes: model.notes.clone(),
+            lifecycle: model.lifecycle.clone(),
+            message_templates: model.message_templates.clone(),
         }
     }
 
@@ -149,6 +168,8 @@ impl CompartmentModelData {
             groups,
             custom_data: self.custom_data.clone(),
             notes: self.notes.clone(),
+            lifecycle: self.lifecycle.clone(),
+            message_templates: self.message_templates.clone(),
         };
         Ok(model)
     }
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Rules which automatically set up new ReaLearn instances depending on which MIDI devices are
+/// present.
+///
+/// Saved as JSON file next to the app configuration. It's read whenever the rules are applied, so
+/// changes take effect without restarting REAPER.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControllerAutoAttachConfig {
+    #[serde(default)]
+    pub rules: Vec<ControllerAutoAttachRule>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControllerAutoAttachRule {
+    /// Name of the MIDI input device whose presence triggers this rule.
+    ///
+    /// This device is also used as control input.
+    pub device_name: String,
+    /// ID of the controller preset to be loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller_preset_id: Option<String>,
+    /// Name of the MIDI output device which should be used as feedback output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback_output_device_name: Option<String>,
+}
+
+impl ControllerAutoAttachConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|_| "couldn't read controller auto-attach config file".to_string())?;
+        serde_json::from_str(&json).map_err(|e| {
+            format!(
+                "Controller auto-attach config file isn't valid. Details:\n\n{}",
+                e
+            )
+        })
+    }
+
+    /// Returns the first rule whose device is among the given present device names.
+    pub fn find_matching_rule<'a>(
+        &self,
+        present_device_names: impl IntoIterator<Item = &'a str> + Clone,
+    ) -> Option<&ControllerAutoAttachRule> {
+        self.rules.iter().find(|rule| {
+            present_device_names
+                .clone()
+                .into_iter()
+                .any(|name| device_names_match(&rule.device_name, name))
+        })
+    }
+}
+
+/// Device names are compared case-insensitively and without surrounding whitespace because
+/// drivers are not always consistent in that regard.
+pub fn device_names_match(rule_device_name: &str, actual_device_name: &str) -> bool {
+    rule_device_name
+        .trim()
+        .eq_ignore_ascii_case(actual_device_name.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(device_name: &str) -> ControllerAutoAttachRule {
+        ControllerAutoAttachRule {
+            device_name: device_name.to_string(),
+            controller_preset_id: Some(format!("{}-preset", device_name)),
+            feedback_output_device_name: None,
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        // Given
+        let config = ControllerAutoAttachConfig {
+            rules: vec![rule("X-Touch"), rule("Launchpad"), rule("launchpad ")],
+        };
+        // When
+        let matching_rule = config.find_matching_rule(["Some device", "LAUNCHPAD"]);
+        // Then
+        assert_eq!(matching_rule, Some(&config.rules[1]));
+    }
+
+    #[test]
+    fn no_matching_rule() {
+        // Given
+        let config = ControllerAutoAttachConfig {
+            rules: vec![rule("X-Touch")],
+        };
+        // When
+        let matching_rule = config.find_matching_rule(["Launchpad"]);
+        // Then
+        assert_eq!(matching_rule, None);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::application::{ControllerPreset, Preset, PresetManager};
 use crate::domain::Compartment;
 use crate::infrastructure::data::{
-    CompartmentModelData, ExtendedPresetManager, FileBasedPresetManager, PresetData, PresetInfo,
+    CompartmentModelData, ExtendedPresetManager, FileBasedPresetManager, PresetBackupInfo,
+    PresetData, PresetInfo,
 };
 
 use crate::base::default_util::{deserialize_null_default, is_default};
@@ -40,6 +41,14 @@ impl ExtendedPresetManager for SharedControllerPresetManager {
     fn preset_infos(&self) -> Vec<PresetInfo> {
         self.borrow().preset_infos()
     }
+
+    fn preset_backups(&self, id: &str) -> Vec<PresetBackupInfo> {
+        self.borrow().preset_backups(id)
+    }
+
+    fn restore_preset_backup(&mut self, id: &str, backup_id: &str) -> Result<(), &'static str> {
+        self.borrow_mut().restore_preset_backup(id, backup_id)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,124 @@
+use crate::application::{
+    MappingModel, Session, SourceCategory, TargetCategory, TargetModelFormatMultiLine,
+};
+use crate::domain::Compartment;
+use crate::infrastructure::plugin::App;
+use askama::Template;
+use itertools::Itertools;
+
+/// Printable cheat sheet which explains what the controls do in a particular compartment.
+///
+/// Main mappings with virtual sources are described using the names of the controller mappings
+/// which feed the corresponding virtual control elements. That way, the controls appear with the
+/// names given to them in the controller preset (e.g. "Fader 1").
+#[derive(Template)]
+#[template(path = "controls-documentation.html")]
+pub struct ControlsDocumentation {
+    title: String,
+    subtitle: String,
+    groups: Vec<ControlsDocumentationGroup>,
+}
+
+struct ControlsDocumentationGroup {
+    name: String,
+    entries: Vec<ControlsDocumentationEntry>,
+}
+
+struct ControlsDocumentationEntry {
+    control: String,
+    mapping: String,
+    target_lines: Vec<String>,
+    is_enabled: bool,
+}
+
+impl ControlsDocumentation {
+    pub fn from_session(session: &Session, compartment: Compartment) -> Self {
+        let preset_name = session.active_preset_id(compartment).and_then(|id| {
+            App::get()
+                .preset_manager(compartment)
+                .preset_infos()
+                .into_iter()
+                .find(|info| info.id == id)
+                .map(|info| info.name)
+        });
+        let groups = session
+            .groups_sorted(compartment)
+            .filter_map(|g| {
+                let g = g.borrow();
+                let entries: Vec<_> = session
+                    .mappings(compartment)
+                    .filter_map(|m| {
+                        let m = m.borrow();
+                        if m.group_id() != g.id() || !m.visible_in_projection() {
+                            return None;
+                        }
+                        Some(ControlsDocumentationEntry::from_mapping(session, &m))
+                    })
+                    .collect();
+                if entries.is_empty() {
+                    return None;
+                }
+                let group = ControlsDocumentationGroup {
+                    name: g.effective_name().to_string(),
+                    entries,
+                };
+                Some(group)
+            })
+            .collect();
+        Self {
+            title: preset_name.unwrap_or_else(|| format!("ReaLearn {}", session.id())),
+            subtitle: format!("Controls in {}", compartment),
+            groups,
+        }
+    }
+
+    pub fn render_as_html(&self) -> Result<String, &'static str> {
+        self.render()
+            .map_err(|_| "couldn't render controls documentation")
+    }
+}
+
+impl ControlsDocumentationEntry {
+    fn from_mapping(session: &Session, mapping: &MappingModel) -> Self {
+        let target_lines = TargetModelFormatMultiLine::new(
+            &mapping.target_model,
+            session.extended_context(),
+            mapping.compartment(),
+        )
+        .to_string()
+        .lines()
+        .map(|l| l.to_string())
+        .collect();
+        Self {
+            control: describe_control(session, mapping),
+            mapping: mapping.effective_name(),
+            target_lines,
+            is_enabled: mapping.is_enabled(),
+        }
+    }
+}
+
+fn describe_control(session: &Session, mapping: &MappingModel) -> String {
+    if mapping.compartment() == Compartment::Main
+        && mapping.source_model.category() == SourceCategory::Virtual
+    {
+        let control_element = mapping.source_model.create_control_element();
+        let controller_mapping_names = session
+            .mappings(Compartment::Controller)
+            .filter_map(|m| {
+                let m = m.borrow();
+                let feeds_control_element = m.target_model.category() == TargetCategory::Virtual
+                    && m.target_model.create_control_element() == control_element;
+                if feeds_control_element {
+                    Some(m.effective_name())
+                } else {
+                    None
+                }
+            })
+            .join(", ");
+        if !controller_mapping_names.is_empty() {
+            return controller_mapping_names;
+        }
+    }
+    mapping.source_model.to_string().lines().join(" / ")
+}
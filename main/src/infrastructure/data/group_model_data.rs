@@ -5,9 +5,10 @@ use crate::infrastructure::data::{
     ActivationConditionData, DataToModelConversionContext, EnabledData,
     ModelToDataConversionContext,
 };
+use helgoboss_learn::VirtualColor;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupModelData {
     /// Doesn't have to be a UUID since 2.11.0-pre.13 and corresponds to the model *key* instead!
@@ -38,6 +39,13 @@ pub struct GroupModelData {
     pub enabled_data: EnabledData,
     #[serde(flatten)]
     pub activation_condition_data: ActivationConditionData,
+    /// New since ReaLearn v2.15.0-pre.1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub feedback_color: Option<VirtualColor>,
 }
 
 impl GroupModelData {
@@ -57,6 +65,7 @@ impl GroupModelData {
                 model.activation_condition_model(),
                 conversion_context,
             ),
+            feedback_color: model.feedback_color().cloned(),
         }
     }
 
@@ -98,6 +107,7 @@ impl GroupModelData {
         model.change(GroupCommand::SetFeedbackIsEnabled(
             self.enabled_data.feedback_is_enabled,
         ));
+        model.change(GroupCommand::SetFeedbackColor(self.feedback_color.clone()));
         self.activation_condition_data
             .apply_to_model(&mut model.activation_condition_model, conversion_context);
     }
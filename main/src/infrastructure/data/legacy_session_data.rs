@@ -0,0 +1,146 @@
+use crate::infrastructure::data::SessionData;
+use helgoboss_learn::AbsoluteMode;
+use serde_json::Value;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::error::Error;
+
+/// Returns `true` if the given plug-in state has been saved by the legacy ReaLearn written in C++
+/// (< v1.10).
+///
+/// ReaLearn C++ saved some IPlug binary data in front of the actual JSON object, whereas all
+/// later versions save pure JSON.
+pub fn is_legacy_session_data(data: &[u8]) -> bool {
+    data.first().map(|b| *b != b'{').unwrap_or(false)
+}
+
+/// Imports the plug-in state of the legacy ReaLearn written in C++ (< v1.10), e.g. taken from the
+/// VST chunk in an old REAPER project or from a legacy preset.
+///
+/// The JSON structure of ReaLearn C++ is very similar to the current one, so the result is
+/// deserialized as usual after converting the parts which changed their meaning. Everything that
+/// can't be converted exactly is converted on a best-effort basis. The returned session data
+/// doesn't contain a version, so all migrations for old versions will be applied when loading it.
+pub fn import_legacy_session_data(data: &[u8]) -> Result<SessionData, Box<dyn Error>> {
+    let left_json_object_brace = data
+        .iter()
+        .position(|b| *b == b'{')
+        .ok_or("couldn't find left JSON brace in legacy data")?;
+    let mut json: Value = serde_json::from_slice(&data[left_json_object_brace..])?;
+    convert_legacy_json(&mut json)?;
+    let session_data = serde_json::from_value(json)?;
+    Ok(session_data)
+}
+
+fn convert_legacy_json(json: &mut Value) -> Result<(), Box<dyn Error>> {
+    let mappings = match json.get_mut("mappings").and_then(|m| m.as_array_mut()) {
+        None => return Ok(()),
+        Some(m) => m,
+    };
+    for mapping in mappings {
+        if let Some(mode) = mapping.get_mut("mode") {
+            convert_legacy_mode(mode)?;
+        }
+    }
+    Ok(())
+}
+
+/// Mode types of ReaLearn C++.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize_repr, Deserialize_repr)]
+#[repr(usize)]
+enum LegacyModeType {
+    Absolute = 0,
+    Relative = 1,
+    Toggle = 2,
+}
+
+fn convert_legacy_mode(mode: &mut Value) -> Result<(), Box<dyn Error>> {
+    let mode = match mode.as_object_mut() {
+        None => return Ok(()),
+        Some(m) => m,
+    };
+    let legacy_type: LegacyModeType = match mode.get("type") {
+        None | Some(Value::Null) => LegacyModeType::Absolute,
+        Some(t) => serde_json::from_value(t.clone())?,
+    };
+    let new_type = match legacy_type {
+        LegacyModeType::Absolute => AbsoluteMode::Normal,
+        // Relative mode doesn't exist anymore. It was mostly used with buttons in order to
+        // increase the target value by the step size with each press, which is exactly what
+        // "Incremental button" does. Encoders are still treated as relative because this is
+        // determined by the source character nowadays. The step sizes are kept.
+        LegacyModeType::Relative => AbsoluteMode::IncrementalButton,
+        LegacyModeType::Toggle => AbsoluteMode::ToggleButton,
+    };
+    mode.insert("type".to_string(), serde_json::to_value(new_type)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::data::ModeModelData;
+    use serde_json::json;
+
+    #[test]
+    fn legacy_mode_types() {
+        // Given
+        let mut json = json!({
+            "mappings": [
+                {
+                    "mode": {
+                        "type": 1,
+                        "minStepSize": 0.05
+                    }
+                },
+                {
+                    "mode": {
+                        "type": 2
+                    }
+                }
+            ]
+        });
+        // When
+        convert_legacy_json(&mut json).unwrap();
+        // Then
+        let expected_json = json!({
+            "mappings": [
+                {
+                    "mode": {
+                        "type": serde_json::to_value(AbsoluteMode::IncrementalButton).unwrap(),
+                        "minStepSize": 0.05
+                    }
+                },
+                {
+                    "mode": {
+                        "type": serde_json::to_value(AbsoluteMode::ToggleButton).unwrap()
+                    }
+                }
+            ]
+        });
+        assert_eq!(json, expected_json);
+    }
+
+    #[test]
+    fn legacy_relative_mode_becomes_incremental_button() {
+        // Given
+        let mut mode = json!({
+            "type": 1,
+            "minStepSize": 0.01,
+            "maxStepSize": 0.05
+        });
+        // When
+        convert_legacy_mode(&mut mode).unwrap();
+        // Then
+        let data: ModeModelData = serde_json::from_value(mode).unwrap();
+        assert_eq!(data.r#type, AbsoluteMode::IncrementalButton);
+        assert_eq!(data.min_step_size.get(), 0.01);
+        assert_eq!(data.max_step_size.get(), 0.05);
+    }
+
+    #[test]
+    fn detect_legacy_data() {
+        assert!(is_legacy_session_data(b"\x01\x00\x00\x00{\"mappings\":[]}"));
+        assert!(!is_legacy_session_data(b"{\"mappings\":[]}"));
+        assert!(!is_legacy_session_data(b""));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::application::{
+    share_mapping, IssueSeverity, MappingIssue, MappingIssueCode, MappingValidationContext,
+};
+use crate::domain::{Compartment, GroupId, GroupKey, MappingId, MappingKey};
+use crate::infrastructure::data::{
+    ensure_no_duplicate_compartment_data, CompartmentModelData, ControllerPresetData,
+    DataToModelConversionContext, GroupModelData, MainPresetData, MappingModelData, SessionData,
+    SimpleDataToModelConversionContext,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Kind of JSON document which should be linted.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintSubject {
+    MainPreset,
+    ControllerPreset,
+    Session,
+}
+
+impl Default for LintSubject {
+    fn default() -> Self {
+        Self::MainPreset
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub severity: IssueSeverity,
+    /// `None` if the issue concerns the document as a whole (e.g. schema errors).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<MappingIssueCode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compartment: Option<Compartment>,
+    /// Name or key of the affected mapping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mapping: Option<String>,
+    pub message: String,
+}
+
+/// Checks the given preset or session JSON without loading it.
+///
+/// Reports schema errors, duplicate IDs, mappings which refer to non-existing groups, mappings
+/// with identical sources and source/target categories which are not supported in the mapping's
+/// compartment.
+pub fn lint_json(subject: LintSubject, json: &str) -> LintReport {
+    let mut report = LintReport::default();
+    match subject {
+        LintSubject::MainPreset => {
+            if let Some(data) = parse_preset::<MainPresetData>(json, &mut report) {
+                lint_compartment(Compartment::Main, &data.groups, &data.mappings, &mut report);
+            }
+        }
+        LintSubject::ControllerPreset => {
+            if let Some(data) = parse_preset::<ControllerPresetData>(json, &mut report) {
+                lint_compartment(
+                    Compartment::Controller,
+                    &data.groups,
+                    &data.mappings,
+                    &mut report,
+                );
+            }
+        }
+        LintSubject::Session => match serde_json::from_str::<SessionData>(json) {
+            Ok(data) => {
+                for compartment in [Compartment::Controller, Compartment::Main] {
+                    let (groups, mappings) = data.compartment_groups_and_mappings(compartment);
+                    lint_compartment(compartment, groups, mappings, &mut report);
+                }
+            }
+            Err(e) => report.add_schema_error(e),
+        },
+    }
+    report
+}
+
+/// Parses the preset first as a whole (to detect schema errors) and then just its compartment
+/// part (which is what we lint).
+fn parse_preset<T: DeserializeOwned>(
+    json: &str,
+    report: &mut LintReport,
+) -> Option<CompartmentModelData> {
+    let result = serde_json::from_str::<T>(json)
+        .and_then(|_| serde_json::from_str::<CompartmentModelData>(json));
+    match result {
+        Ok(data) => Some(data),
+        Err(e) => {
+            report.add_schema_error(e);
+            None
+        }
+    }
+}
+
+fn lint_compartment(
+    compartment: Compartment,
+    groups: &[GroupModelData],
+    mappings: &[MappingModelData],
+    report: &mut LintReport,
+) {
+    if let Err(msg) = ensure_no_duplicate_compartment_data(mappings, groups, std::iter::empty()) {
+        report.add_document_error(Some(compartment), msg);
+    }
+    // Convert to models in order to run exactly the same checks as for loaded mappings
+    let conversion_context = LintConversionContext(
+        SimpleDataToModelConversionContext::from_session_or_random(groups, mappings, None),
+    );
+    let group_ids: HashSet<_> = groups
+        .iter()
+        .filter_map(|g| conversion_context.group_id_by_key(&g.id))
+        .collect();
+    let mut models = Vec::with_capacity(mappings.len());
+    let mut labels = Vec::with_capacity(mappings.len());
+    for (i, m) in mappings.iter().enumerate() {
+        let label = mapping_label(i, m);
+        match m.to_model(compartment, &conversion_context, None, None) {
+            Ok(model) => {
+                models.push(share_mapping(model));
+                labels.push(label);
+            }
+            Err(msg) => report.add_mapping_error(compartment, label, msg),
+        }
+    }
+    let context = MappingValidationContext {
+        group_ids: Some(&group_ids),
+        compartment_mappings: Some(&models),
+        ..Default::default()
+    };
+    for (model, label) in models.iter().zip(labels) {
+        for issue in model.borrow().validate(context) {
+            report.add(compartment, label.clone(), issue);
+        }
+    }
+}
+
+/// Unlike when loading, a mapping which refers to a non-existing group must not end up in the
+/// default group. Otherwise we couldn't report it.
+struct LintConversionContext(SimpleDataToModelConversionContext);
+
+impl DataToModelConversionContext for LintConversionContext {
+    fn non_default_group_id_by_key(&self, key: &GroupKey) -> Option<GroupId> {
+        let id = self
+            .0
+            .non_default_group_id_by_key(key)
+            .unwrap_or_else(GroupId::random);
+        Some(id)
+    }
+
+    fn mapping_id_by_key(&self, key: &MappingKey) -> Option<MappingId> {
+        self.0.mapping_id_by_key(key)
+    }
+}
+
+fn mapping_label(index: usize, mapping: &MappingModelData) -> String {
+    if !mapping.name.is_empty() {
+        format!("\"{}\"", mapping.name)
+    } else if let Some(id) = &mapping.id {
+        format!("\"{id}\"")
+    } else {
+        format!("mapping #{}", index + 1)
+    }
+}
+
+impl LintReport {
+    fn add(&mut self, compartment: Compartment, mapping: String, issue: MappingIssue) {
+        let issue = LintIssue {
+            severity: issue.severity,
+            code: Some(issue.code),
+            compartment: Some(compartment),
+            mapping: Some(mapping),
+            message: issue.message,
+        };
+        self.issues.push(issue);
+    }
+
+    fn add_mapping_error(&mut self, compartment: Compartment, mapping: String, message: &str) {
+        let issue = LintIssue {
+            severity: IssueSeverity::Error,
+            code: None,
+            compartment: Some(compartment),
+            mapping: Some(mapping),
+            message: message.to_owned(),
+        };
+        self.issues.push(issue);
+    }
+
+    fn add_document_error(&mut self, compartment: Option<Compartment>, message: String) {
+        let issue = LintIssue {
+            severity: IssueSeverity::Error,
+            code: None,
+            compartment,
+            mapping: None,
+            message,
+        };
+        self.issues.push(issue);
+    }
+
+    fn add_schema_error(&mut self, error: serde_json::Error) {
+        self.add_document_error(None, format!("Schema error: {error}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dangling_group_and_duplicate_source() {
+        // Given
+        let json = r#"{
+            "name": "Test",
+            "groups": [{ "id": "a", "name": "A" }],
+            "mappings": [
+                { "name": "One", "groupId": "a", "source": { "type": 0, "number": 7 }, "mode": {}, "target": {} },
+                { "name": "Two", "groupId": "a", "source": { "type": 0, "number": 7 }, "mode": {}, "target": {} },
+                { "name": "Three", "groupId": "b", "source": { "type": 0, "number": 8 }, "mode": {}, "target": {} }
+            ]
+        }"#;
+        // When
+        let report = lint_json(LintSubject::MainPreset, json);
+        // Then
+        let summary: Vec<_> = report
+            .issues
+            .iter()
+            .map(|i| (i.severity, i.mapping.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (IssueSeverity::Warning, Some("\"Two\"")),
+                (IssueSeverity::Error, Some("\"Three\"")),
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_error() {
+        // When
+        let report = lint_json(LintSubject::ControllerPreset, r#"{ "mappings": 5 }"#);
+        // Then
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].severity, IssueSeverity::Error);
+    }
+}
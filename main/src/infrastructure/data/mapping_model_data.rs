@@ -8,7 +8,7 @@ use crate::infrastructure::data::{
     ActivationConditionData, DataToModelConversionContext, EnabledData, MigrationDescriptor,
     ModeModelData, ModelToDataConversionContext, SourceModelData, TargetModelData,
 };
-use realearn_api::persistence::SuccessAudioFeedback;
+use realearn_api::persistence::{CrossfadeRole, FeedbackEffect, SuccessAudioFeedback};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
@@ -78,7 +78,31 @@ pub struct MappingModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub feedback_only_if_visible_in_projection: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub success_audio_feedback: Option<SuccessAudioFeedback>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub crossfade_role: Option<CrossfadeRole>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub feedback_effect: Option<FeedbackEffect>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub invert_feedback: bool,
 }
 
 impl MappingModelData {
@@ -113,11 +137,15 @@ impl MappingModelData {
             ),
             advanced: model.advanced_settings().cloned(),
             visible_in_projection: model.visible_in_projection(),
+            feedback_only_if_visible_in_projection: model.feedback_only_if_visible_in_projection(),
             success_audio_feedback: if model.beep_on_success() {
                 Some(SuccessAudioFeedback::Simple)
             } else {
                 None
             },
+            crossfade_role: model.crossfade_role(),
+            feedback_effect: model.feedback_effect(),
+            invert_feedback: model.invert_feedback(),
         }
     }
 
@@ -254,7 +282,13 @@ impl MappingModelData {
         model.change(P::SetFeedbackSendBehavior(feedback_send_behavior));
         let _ = model.set_advanced_settings(self.advanced.clone());
         model.change(P::SetVisibleInProjection(self.visible_in_projection));
+        model.change(P::SetFeedbackOnlyIfVisibleInProjection(
+            self.feedback_only_if_visible_in_projection,
+        ));
         model.change(P::SetBeepOnSuccess(self.success_audio_feedback.is_some()));
+        model.change(P::SetCrossfadeRole(self.crossfade_role));
+        model.change(P::SetFeedbackEffect(self.feedback_effect));
+        model.change(P::SetInvertFeedback(self.invert_feedback));
         Ok(())
     }
 }
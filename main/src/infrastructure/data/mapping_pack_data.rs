@@ -0,0 +1,286 @@
+use crate::application::{ActivationType, Change, CompartmentModel, MappingCommand, Session};
+use crate::base::default_util::{deserialize_null_default, is_default};
+use crate::domain::{
+    extract_referenced_param_indexes, Compartment, CompartmentParamIndex, GroupId, GroupKey,
+    ParamSetting,
+};
+use crate::infrastructure::data::{ActivationConditionData, GroupModelData, MappingModelData};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// A group together with its mappings, meant to be shared between sessions as a functional
+/// building block (e.g. a "transport section").
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingPackData {
+    pub group: GroupModelData,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub mappings: Vec<MappingModelData>,
+    /// Settings (e.g. names) of the compartment parameters which are referenced by the activation
+    /// conditions of the group or its mappings.
+    // String key workaround, see `CompartmentModelData`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub parameters: HashMap<String, ParamSetting>,
+}
+
+/// What to do when importing a mapping pack whose group name is already taken.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MappingPackConflictResolution {
+    /// Adds the group under a new, unique name.
+    Rename,
+    /// Adds the mappings to the existing group and leaves the group settings untouched.
+    Merge,
+}
+
+impl MappingPackData {
+    pub fn from_model(model: &CompartmentModel, group_id: GroupId) -> Result<Self, &'static str> {
+        let group = model
+            .groups
+            .iter()
+            .find(|g| g.id() == group_id)
+            .ok_or("only non-default groups can be exported as mapping pack")?;
+        let group_data = GroupModelData::from_model(group, model);
+        let mapping_datas: Vec<_> = model
+            .mappings
+            .iter()
+            .filter(|m| m.group_id() == group_id)
+            .map(|m| MappingModelData::from_model(m, model))
+            .collect();
+        let referenced_params = referenced_params(&group_data, &mapping_datas);
+        let data = Self {
+            group: group_data,
+            mappings: mapping_datas,
+            parameters: model
+                .parameters
+                .iter()
+                .filter(|(index, _)| referenced_params.contains(index))
+                .map(|(index, setting)| (index.to_string(), setting.clone()))
+                .collect(),
+        };
+        Ok(data)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.group.name
+    }
+
+    /// Adds the group and its mappings to the given compartment.
+    ///
+    /// Parameter settings are only applied to parameters which don't have a name yet, so that
+    /// existing parameters are never overwritten.
+    pub fn import_into_session(
+        &self,
+        session: &mut Session,
+        compartment: Compartment,
+        version: Option<&Version>,
+        conflict_resolution: MappingPackConflictResolution,
+    ) -> Result<MappingPackImport, String> {
+        // Convert the mappings first, so nothing is added if one of them is invalid
+        let mappings: Vec<_> = {
+            let conversion_context = session.compartment_in_session(compartment);
+            let mappings: Result<Vec<_>, _> = self
+                .mappings
+                .iter()
+                .map(|m| {
+                    let mut data = m.clone();
+                    // The group is assigned as soon as it exists
+                    data.group_id = GroupKey::default();
+                    if let Some(key) = &data.id {
+                        if session.find_mapping_id_by_key(compartment, key).is_some() {
+                            data.id = None;
+                        }
+                    }
+                    data.to_model(
+                        compartment,
+                        &conversion_context,
+                        Some(session.extended_context()),
+                        version,
+                    )
+                })
+                .collect();
+            mappings?
+        };
+        let conflicting_params = self.find_conflicting_params(session, compartment);
+        let existing_group = find_group_id_by_name(session, compartment, self.name());
+        let group_id = match (existing_group, conflict_resolution) {
+            (Some(id), MappingPackConflictResolution::Merge) => id,
+            (existing_group, _) => {
+                let mut group_data = self.group.clone();
+                if existing_group.is_some() {
+                    group_data.name = unique_group_name(session, compartment, &group_data.name);
+                }
+                if group_data.id.is_empty()
+                    || session
+                        .find_group_by_key(compartment, &group_data.id)
+                        .is_some()
+                {
+                    group_data.id = GroupKey::random();
+                }
+                let group = group_data.to_model(
+                    compartment,
+                    false,
+                    &session.compartment_in_session(compartment),
+                );
+                session.add_group(compartment, group)
+            }
+        };
+        let mappings = mappings.into_iter().map(|mut m| {
+            let _ = m.change(MappingCommand::SetGroupId(group_id));
+            m
+        });
+        session.insert_mappings_at(compartment, usize::MAX, mappings);
+        let param_settings: Vec<_> = {
+            let compartment_params = session.params().compartment_params(compartment);
+            self.parameters
+                .iter()
+                .filter_map(|(key, setting)| {
+                    let index: CompartmentParamIndex = key.parse::<u32>().ok()?.try_into().ok()?;
+                    if !compartment_params.at(index).setting().name.is_empty() {
+                        return None;
+                    }
+                    Some((index, setting.clone()))
+                })
+                .collect()
+        };
+        if !param_settings.is_empty() {
+            session.update_certain_param_settings(compartment, param_settings);
+        }
+        let import = MappingPackImport {
+            group_id,
+            conflicting_params,
+        };
+        Ok(import)
+    }
+
+    /// Returns the parameters which the pack refers to but which have a different name in the
+    /// given compartment, which means they are probably used for something else already.
+    fn find_conflicting_params(
+        &self,
+        session: &Session,
+        compartment: Compartment,
+    ) -> Vec<CompartmentParamIndex> {
+        let compartment_params = session.params().compartment_params(compartment);
+        let mut conflicting_params: Vec<_> = referenced_params(&self.group, &self.mappings)
+            .into_iter()
+            .filter(|index| {
+                let existing_name = &compartment_params.at(*index).setting().name;
+                let name_in_pack = self
+                    .parameters
+                    .get(&index.to_string())
+                    .map(|s| s.name.as_str())
+                    .unwrap_or_default();
+                !existing_name.is_empty() && existing_name != name_in_pack
+            })
+            .collect();
+        conflicting_params.sort_by_key(|i| i.get());
+        conflicting_params
+    }
+}
+
+/// Result of importing a mapping pack.
+pub struct MappingPackImport {
+    /// ID of the group which now contains the mappings.
+    pub group_id: GroupId,
+    /// Parameters which the pack refers to but which are already used for something else in the
+    /// compartment. They are left untouched, so the affected mappings might not work as intended.
+    pub conflicting_params: Vec<CompartmentParamIndex>,
+}
+
+/// Returns the compartment parameters which the given group and mappings refer to, either in
+/// their activation conditions or in the expressions of dynamic targets.
+fn referenced_params(
+    group: &GroupModelData,
+    mappings: &[MappingModelData],
+) -> HashSet<CompartmentParamIndex> {
+    let mapping_params = mappings.iter().flat_map(|m| {
+        let target_params = m
+            .target
+            .dynamic_expressions()
+            .flat_map(extract_referenced_param_indexes);
+        referenced_params_of_activation_condition(&m.activation_condition_data).chain(target_params)
+    });
+    referenced_params_of_activation_condition(&group.activation_condition_data)
+        .chain(mapping_params)
+        .collect()
+}
+
+fn referenced_params_of_activation_condition(
+    data: &ActivationConditionData,
+) -> impl Iterator<Item = CompartmentParamIndex> {
+    let indexes = match data.activation_type {
+        ActivationType::Modifiers => [
+            data.modifier_condition_1.param_index,
+            data.modifier_condition_2.param_index,
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        ActivationType::Bank => vec![data.program_condition.param_index],
+        ActivationType::Eel | ActivationType::Expression => {
+            extract_referenced_param_indexes(&data.eel_condition)
+                .into_iter()
+                .collect()
+        }
+        _ => vec![],
+    };
+    indexes.into_iter()
+}
+
+fn find_group_id_by_name(
+    session: &Session,
+    compartment: Compartment,
+    name: &str,
+) -> Option<GroupId> {
+    let group = session
+        .groups(compartment)
+        .find(|g| g.borrow().name() == name)?;
+    let id = group.borrow().id();
+    Some(id)
+}
+
+fn unique_group_name(session: &Session, compartment: Compartment, name: &str) -> String {
+    (2..)
+        .map(|i| format!("{} ({})", name, i))
+        .find(|n| find_group_id_by_name(session, compartment, n).is_none())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn referenced_params_include_scripts_and_dynamic_targets() {
+        // Given
+        let json = r#"{
+            "group": {
+                "id": "a",
+                "name": "A",
+                "activationType": "modifiers",
+                "modifierCondition1": { "paramIndex": 0, "isOn": true }
+            },
+            "mappings": [
+                { "activationType": "eel", "eelCondition": "y = p2 > 0", "source": {}, "mode": {}, "target": {} },
+                { "activationType": "expression", "eelCondition": "p[2] > 0", "source": {}, "mode": {}, "target": {} },
+                { "source": {}, "mode": {}, "target": { "trackExpression": "p[3] * 10" } },
+                { "eelCondition": "y = p5 > 0", "source": {}, "mode": {}, "target": {} }
+            ]
+        }"#;
+        let pack: MappingPackData = serde_json::from_str(json).unwrap();
+        // When
+        let params = referenced_params(&pack.group, &pack.mappings);
+        // Then
+        let mut indexes: Vec<_> = params.into_iter().map(|i| i.get()).collect();
+        indexes.sort();
+        assert_eq!(indexes, vec![0, 1, 2, 3]);
+    }
+}
@@ -1,12 +1,18 @@
 mod compartment_model_data;
 pub use compartment_model_data::*;
 
+mod compartment_diff;
+pub use compartment_diff::*;
+
 mod mapping_model_data;
 pub use mapping_model_data::*;
 
 mod group_model_data;
 pub use group_model_data::*;
 
+mod mapping_pack_data;
+pub use mapping_pack_data::*;
+
 mod mode_model_data;
 pub use mode_model_data::*;
 
@@ -49,10 +55,25 @@ pub use migration::*;
 mod osc_device_management;
 pub use osc_device_management::*;
 
+mod controller_auto_attach;
+pub use controller_auto_attach::*;
+
+mod setup;
+pub use setup::*;
+
 mod virtual_control;
 pub use virtual_control::*;
 
 mod clip_legacy;
 
+mod legacy_session_data;
+pub use legacy_session_data::*;
+
 mod common;
 pub use common::*;
+
+mod controls_documentation;
+pub use controls_documentation::*;
+
+mod lint;
+pub use lint::*;
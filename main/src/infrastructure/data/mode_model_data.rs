@@ -9,7 +9,7 @@ use helgoboss_learn::{
     GroupInteraction, Interval, OutOfRangeBehavior, SoftSymmetricUnitValue, TakeoverMode,
     UnitValue, ValueSequence, VirtualColor,
 };
-use realearn_api::persistence::FeedbackValueTable;
+use realearn_api::persistence::{AccelerationCurve, ControlCurve, FeedbackValueTable, NoteLength};
 use serde::{Deserialize, Serialize};
 use slog::debug;
 use std::time::Duration;
@@ -92,6 +92,8 @@ pub struct ModeModelData {
         skip_serializing_if = "is_default"
     )]
     pub turbo_rate: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_turbo_rate: Option<NoteLength>,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -187,6 +189,20 @@ pub struct ModeModelData {
         skip_serializing_if = "is_default"
     )]
     pub make_absolute_enabled: bool,
+    /// New since ReaLearn v2.15.0-pre.1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub acceleration_curve: AccelerationCurve,
+    /// New since ReaLearn v2.15.0-pre.1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub control_curve: ControlCurve,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -244,6 +260,7 @@ impl ModeModelData {
             min_press_millis: model.press_duration_interval().min_val().as_millis() as _,
             max_press_millis: model.press_duration_interval().max_val().as_millis() as _,
             turbo_rate: model.turbo_rate().as_millis() as _,
+            synced_turbo_rate: model.synced_turbo_rate(),
             eel_control_transformation: model.eel_control_transformation().to_owned(),
             eel_feedback_transformation: if model.feedback_type().is_textual() {
                 model.textual_feedback_expression().to_owned()
@@ -265,6 +282,8 @@ impl ModeModelData {
             encoder_usage: model.encoder_usage(),
             rotate_is_enabled: model.rotate(),
             make_absolute_enabled: model.make_absolute(),
+            acceleration_curve: model.acceleration_curve(),
+            control_curve: model.control_curve(),
             group_interaction: model.group_interaction(),
             target_value_sequence: model.target_value_sequence().clone(),
             feedback_type: model.feedback_type(),
@@ -318,6 +337,7 @@ impl ModeModelData {
             Duration::from_millis(self.max_press_millis),
         )));
         model.change(P::SetTurboRate(Duration::from_millis(self.turbo_rate)));
+        model.change(P::SetSyncedTurboRate(self.synced_turbo_rate));
         let has_custom_jump_interval =
             self.min_target_jump.get() > 0.0 || self.max_target_jump.get() < 1.0;
         let (legacy_jump_interval, takeover_mode) = if has_custom_jump_interval {
@@ -393,6 +413,8 @@ impl ModeModelData {
         model.change(P::SetEncoderUsage(self.encoder_usage));
         model.change(P::SetRotate(self.rotate_is_enabled));
         model.change(P::SetMakeAbsolute(self.make_absolute_enabled));
+        model.change(P::SetAccelerationCurve(self.acceleration_curve));
+        model.change(P::SetControlCurve(self.control_curve));
         model.change(P::SetGroupInteraction(self.group_interaction));
         model.change(P::SetTargetValueSequence(
             self.target_value_sequence.clone(),
@@ -13,6 +13,17 @@ use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
+/// Number of previous versions which are kept per preset.
+const MAX_PRESET_BACKUP_COUNT: usize = 5;
+
+/// Backups are kept in a hidden directory within the preset directory, so they are not picked up
+/// as presets themselves.
+const PRESET_BACKUP_DIR_NAME: &str = ".backups";
+
+/// Includes milliseconds, so that saving a preset twice within the same second doesn't overwrite
+/// the first backup.
+const PRESET_BACKUP_FILE_NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S-%3f";
+
 #[derive(Debug)]
 pub struct FileBasedPresetManager<P: Preset, PD: PresetData<P = P>> {
     preset_dir_path: PathBuf,
@@ -29,6 +40,12 @@ pub trait ExtendedPresetManager {
     fn find_id_by_index(&self, index: usize) -> Option<String>;
     fn remove_preset(&mut self, id: &str) -> Result<(), &'static str>;
     fn preset_infos(&self) -> Vec<PresetInfo>;
+    /// Returns the previous versions of the given preset, newest first.
+    fn preset_backups(&self, id: &str) -> Vec<PresetBackupInfo>;
+    /// Replaces the given preset with the given previous version.
+    ///
+    /// The current version is backed up as well, so restoring can be undone.
+    fn restore_preset_backup(&mut self, id: &str, backup_id: &str) -> Result<(), &'static str>;
 }
 
 pub struct PresetInfo {
@@ -36,6 +53,13 @@ pub struct PresetInfo {
     pub name: String,
 }
 
+pub struct PresetBackupInfo {
+    /// File stem of the backup file.
+    pub id: String,
+    /// Human-readable time of the backup.
+    pub label: String,
+}
+
 impl<P: Preset, PD: PresetData<P = P>> FileBasedPresetManager<P, PD> {
     pub fn new(preset_dir_path: PathBuf) -> FileBasedPresetManager<P, PD> {
         let mut manager = FileBasedPresetManager {
@@ -103,6 +127,7 @@ impl<P: Preset, PD: PresetData<P = P>> FileBasedPresetManager<P, PD> {
         // We don't want to have the ID in the file - because the file name itself is the ID
         data.clear_id();
         let json = serde_json::to_string_pretty(&data).map_err(|_| "couldn't serialize preset")?;
+        self.back_up_preset_file_if_exists(preset.id())?;
         fs::write(path, json).map_err(|_| "couldn't write preset file")?;
         let _ = self.load_presets();
         Ok(())
@@ -136,6 +161,55 @@ impl<P: Preset, PD: PresetData<P = P>> FileBasedPresetManager<P, PD> {
         self.preset_dir_path.join(format!("{}.json", id))
     }
 
+    fn get_preset_backup_dir_path(&self, id: &str) -> PathBuf {
+        self.preset_dir_path.join(PRESET_BACKUP_DIR_NAME).join(id)
+    }
+
+    /// Copies the current preset file (if any) to the backup directory and removes backups which
+    /// exceed the maximum count.
+    fn back_up_preset_file_if_exists(&self, id: &str) -> Result<(), &'static str> {
+        let path = self.get_preset_file_path(id);
+        if !path.exists() {
+            return Ok(());
+        }
+        let backup_dir_path = self.get_preset_backup_dir_path(id);
+        fs::create_dir_all(&backup_dir_path)
+            .map_err(|_| "couldn't create preset backup directory")?;
+        let backup_id = chrono::Local::now()
+            .format(PRESET_BACKUP_FILE_NAME_FORMAT)
+            .to_string();
+        fs::copy(&path, backup_dir_path.join(format!("{}.json", backup_id)))
+            .map_err(|_| "couldn't back up preset file")?;
+        for outdated_backup in self
+            .preset_backup_ids(id)
+            .into_iter()
+            .skip(MAX_PRESET_BACKUP_COUNT)
+        {
+            let _ = fs::remove_file(backup_dir_path.join(format!("{}.json", outdated_backup)));
+        }
+        Ok(())
+    }
+
+    /// Returns the IDs of all backups of the given preset, newest first.
+    fn preset_backup_ids(&self, id: &str) -> Vec<String> {
+        let entries = match fs::read_dir(self.get_preset_backup_dir_path(id)) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+        let mut ids: Vec<_> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension() != Some(std::ffi::OsStr::new("json")) {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().to_string())
+            })
+            .collect();
+        // The timestamp format makes sure that lexicographic order is chronological order
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids
+    }
+
     fn load_preset(&self, path: &Path) -> Result<P, String> {
         let relative_path = path
             .parent()
@@ -222,6 +296,36 @@ impl<P: Preset, PD: PresetData<P = P>> ExtendedPresetManager for FileBasedPreset
             })
             .collect()
     }
+
+    fn preset_backups(&self, id: &str) -> Vec<PresetBackupInfo> {
+        self.preset_backup_ids(id)
+            .into_iter()
+            .map(|backup_id| {
+                let label = chrono::NaiveDateTime::parse_from_str(
+                    &backup_id,
+                    PRESET_BACKUP_FILE_NAME_FORMAT,
+                )
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|_| backup_id.clone());
+                PresetBackupInfo {
+                    id: backup_id,
+                    label,
+                }
+            })
+            .collect()
+    }
+
+    fn restore_preset_backup(&mut self, id: &str, backup_id: &str) -> Result<(), &'static str> {
+        let backup_path = self
+            .get_preset_backup_dir_path(id)
+            .join(format!("{}.json", backup_id));
+        // Read first because backing up the current version might prune the one to be restored
+        let json = fs::read_to_string(backup_path).map_err(|_| "couldn't read preset backup")?;
+        self.back_up_preset_file_if_exists(id)?;
+        fs::write(self.get_preset_file_path(id), json).map_err(|_| "couldn't write preset file")?;
+        let _ = self.load_presets();
+        Ok(())
+    }
 }
 
 impl<P: Preset + Clone, PD: PresetData<P = P>> PresetManager for FileBasedPresetManager<P, PD> {
@@ -1,14 +1,17 @@
 use crate::application::{
     reaper_supports_global_midi_filter, CompartmentCommand, CompartmentInSession,
-    FxPresetLinkConfig, GroupModel, MainPresetAutoLoadMode, Session, SessionCommand,
+    CompartmentLifecycleModel, FxPresetLinkConfig, GroupModel, MainPresetAutoLoadMode,
+    MessageTemplatesModel, Session, SessionCommand, WindowLayout,
 };
 use crate::base::default_util::{bool_true, deserialize_null_default, is_bool_true, is_default};
 use crate::domain::{
     compartment_param_index_iter, pot, BackboneState, ClipMatrixRef, Compartment,
-    CompartmentParamIndex, CompartmentParams, ControlInput, FeedbackOutput, GroupId, GroupKey,
-    InstanceState, MappingId, MappingKey, MappingSnapshotContainer, MappingSnapshotId,
-    MidiControlInput, MidiDestination, OscDeviceId, Param, PluginParams,
-    StayActiveWhenProjectInBackground, Tag,
+    CompartmentParamIndex, CompartmentParams, ControlInput, FeedbackOutput, FxParameterSnapshot,
+    FxParameterSnapshotContainer, FxParameterSnapshotId, GroupId, GroupKey, InstanceState,
+    MappingId, MappingKey, MappingSnapshotContainer, MappingSnapshotId, MidiControlInput,
+    MidiDestination, MidiPassThroughFilter, Notification, OscDeviceId, Param, ParameterThinning,
+    PluginParams, StayActiveWhenProjectInBackground, Tag, ValueDisplayPreferences,
+    VirtualControlElementSharing,
 };
 use crate::infrastructure::data::{
     convert_target_value_to_api, convert_target_value_to_model,
@@ -21,17 +24,20 @@ use crate::infrastructure::api::convert::to_data::ApiToDataConversionContext;
 use crate::infrastructure::data::clip_legacy::{
     create_clip_matrix_from_legacy_slots, QualifiedSlotDescriptor,
 };
+use helgoboss_learn::UnitValue;
 use playtime_api::persistence::Matrix;
 use realearn_api::persistence::{
     FxDescriptor, MappingInSnapshot, MappingSnapshot, TrackDescriptor,
 };
+use reaper_high::Guid;
 use reaper_medium::{MidiInputDeviceId, MidiOutputDeviceId};
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryInto;
 use std::error::Error;
 use std::ops::Deref;
+use std::time::Duration;
 
 /// This is the structure for loading and saving a ReaLearn session.
 ///
@@ -64,6 +70,15 @@ pub struct SessionData {
     let_matched_events_through: bool,
     #[serde(default = "bool_true", skip_serializing_if = "is_bool_true")]
     let_unmatched_events_through: bool,
+    /// Textual representation of the MIDI pass-through filter rules.
+    ///
+    /// New since ReaLearn v2.15.0-pre.1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    midi_pass_through_filter: String,
     /// Introduced with ReaLearn 2.14.0-pre.1. Before that "Always".
     #[serde(
         default,
@@ -79,6 +94,13 @@ pub struct SessionData {
         skip_serializing_if = "is_default"
     )]
     lives_on_upper_floor: bool,
+    /// New since ReaLearn v2.15.0-pre.1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    virtual_control_element_sharing: VirtualControlElementSharing,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -89,6 +111,12 @@ pub struct SessionData {
     send_feedback_only_if_armed: bool,
     #[serde(default = "bool_true", skip_serializing_if = "is_bool_true")]
     reset_feedback_when_releasing_source: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    send_midi_clock: bool,
     /// `None` means "<FX input>"
     #[serde(
         default,
@@ -159,6 +187,18 @@ pub struct SessionData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    controller_lifecycle: CompartmentLifecycleModel,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    controller_message_templates: MessageTemplatesModel,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     main_notes: String,
     #[serde(
         default,
@@ -232,6 +272,32 @@ pub struct SessionData {
         skip_serializing_if = "is_default"
     )]
     active_instance_tags: HashSet<Tag>,
+    /// `None` means full brightness.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    feedback_brightness: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    value_display: ValueDisplayPreferences,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    parameter_thinning: ParameterThinning,
+    /// Interval of the periodic feedback resync in milliseconds. `None` means off.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    feedback_resync_interval_millis: Option<u64>,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -264,6 +330,13 @@ pub struct SessionData {
         skip_serializing_if = "is_default"
     )]
     controller_mapping_snapshots: Vec<MappingSnapshot>,
+    /// FX parameter snapshots, keyed by snapshot number.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    fx_parameter_snapshots: BTreeMap<FxParameterSnapshotId, FxParameterSnapshotData>,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -276,6 +349,12 @@ pub struct SessionData {
         skip_serializing_if = "is_default"
     )]
     memorized_main_compartment: Option<CompartmentModelData>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    window_layout: WindowLayout,
 }
 
 fn focused_fx_descriptor() -> FxDescriptor {
@@ -289,6 +368,42 @@ enum ClipMatrixRefData {
     Foreign(String),
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct FxParameterSnapshotData {
+    /// GUID of the FX from which the snapshot was taken.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    fx_id: Option<String>,
+    /// Normalized parameter values.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    values: Vec<f64>,
+}
+
+impl FxParameterSnapshotData {
+    fn from_model(snapshot: &FxParameterSnapshot) -> Self {
+        Self {
+            fx_id: snapshot.fx_guid.map(|g| g.to_string_without_braces()),
+            values: snapshot.values.clone(),
+        }
+    }
+
+    fn to_model(&self) -> FxParameterSnapshot {
+        let fx_guid = self
+            .fx_id
+            .as_ref()
+            .and_then(|id| Guid::from_string_without_braces(id).ok());
+        FxParameterSnapshot::new(fx_guid, self.values.clone())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 struct CompartmentState {
@@ -354,14 +469,17 @@ impl Default for SessionData {
             id: None,
             let_matched_events_through: session_defaults::LET_MATCHED_EVENTS_THROUGH,
             let_unmatched_events_through: session_defaults::LET_UNMATCHED_EVENTS_THROUGH,
+            midi_pass_through_filter: session_defaults::MIDI_PASS_THROUGH_FILTER.to_string(),
             stay_active_when_project_in_background: Some(
                 session_defaults::STAY_ACTIVE_WHEN_PROJECT_IN_BACKGROUND,
             ),
             always_auto_detect_mode: session_defaults::AUTO_CORRECT_SETTINGS,
             lives_on_upper_floor: session_defaults::LIVES_ON_UPPER_FLOOR,
+            virtual_control_element_sharing: session_defaults::VIRTUAL_CONTROL_ELEMENT_SHARING,
             send_feedback_only_if_armed: session_defaults::SEND_FEEDBACK_ONLY_IF_ARMED,
             reset_feedback_when_releasing_source:
                 session_defaults::RESET_FEEDBACK_WHEN_RELEASING_SOURCE,
+            send_midi_clock: session_defaults::SEND_MIDI_CLOCK,
             control_device_id: None,
             feedback_device_id: None,
             default_group: None,
@@ -372,6 +490,8 @@ impl Default for SessionData {
             controller_mappings: vec![],
             controller_custom_data: Default::default(),
             controller_notes: Default::default(),
+            controller_lifecycle: Default::default(),
+            controller_message_templates: Default::default(),
             main_notes: Default::default(),
             active_controller_id: None,
             active_main_preset_id: None,
@@ -384,14 +504,20 @@ impl Default for SessionData {
             controller: Default::default(),
             main: Default::default(),
             active_instance_tags: Default::default(),
+            feedback_brightness: None,
+            value_display: Default::default(),
+            parameter_thinning: Default::default(),
+            feedback_resync_interval_millis: None,
             instance_preset_link_config: Default::default(),
             use_instance_preset_links_only: false,
             instance_track: Default::default(),
             instance_fx: session_defaults::INSTANCE_FX_DESCRIPTOR,
             mapping_snapshots: vec![],
             controller_mapping_snapshots: vec![],
+            fx_parameter_snapshots: Default::default(),
             pot_state: Default::default(),
             memorized_main_compartment: None,
+            window_layout: Default::default(),
         }
     }
 }
@@ -430,15 +556,18 @@ impl SessionData {
             id: Some(session.id().to_string()),
             let_matched_events_through: session.let_matched_events_through.get(),
             let_unmatched_events_through: session.let_unmatched_events_through.get(),
+            midi_pass_through_filter: session.midi_pass_through_filter.get().to_string(),
             stay_active_when_project_in_background: Some(
                 session.stay_active_when_project_in_background.get(),
             ),
             always_auto_detect_mode: session.auto_correct_settings.get(),
             lives_on_upper_floor: session.lives_on_upper_floor.get(),
+            virtual_control_element_sharing: session.virtual_control_element_sharing.get(),
             send_feedback_only_if_armed: session.send_feedback_only_if_armed.get(),
             reset_feedback_when_releasing_source: session
                 .reset_feedback_when_releasing_source
                 .get(),
+            send_midi_clock: session.send_midi_clock.get(),
             control_device_id: {
                 match session.control_input() {
                     ControlInput::Midi(MidiControlInput::FxInput) => None,
@@ -474,6 +603,12 @@ impl SessionData {
             controller_notes: session
                 .compartment_notes(Compartment::Controller)
                 .to_owned(),
+            controller_lifecycle: session
+                .compartment_lifecycle(Compartment::Controller)
+                .clone(),
+            controller_message_templates: session
+                .compartment_message_templates(Compartment::Controller)
+                .clone(),
             main_notes: session.compartment_notes(Compartment::Main).to_owned(),
             active_controller_id: session
                 .active_preset_id(Compartment::Controller)
@@ -505,6 +640,19 @@ impl SessionData {
             ),
             main: CompartmentState::from_instance_state(&instance_state, Compartment::Main),
             active_instance_tags: instance_state.active_instance_tags().clone(),
+            feedback_brightness: {
+                let brightness = instance_state.feedback_brightness();
+                if brightness == UnitValue::MAX {
+                    None
+                } else {
+                    Some(brightness.get())
+                }
+            },
+            value_display: instance_state.value_display_preferences(),
+            parameter_thinning: instance_state.parameter_thinning(),
+            feedback_resync_interval_millis: instance_state
+                .feedback_resync_interval()
+                .map(|i| i.as_millis() as u64),
             instance_preset_link_config: session.instance_preset_link_config().clone(),
             use_instance_preset_links_only: session.use_instance_preset_links_only(),
             instance_track: session.instance_track_descriptor().clone(),
@@ -519,10 +667,17 @@ impl SessionData {
                 &instance_state,
                 Compartment::Controller,
             ),
+            fx_parameter_snapshots: instance_state
+                .fx_parameter_snapshot_container()
+                .snapshots()
+                .iter()
+                .map(|(id, snapshot)| (*id, FxParameterSnapshotData::from_model(snapshot)))
+                .collect(),
             pot_state: instance_state.save_pot_unit(),
             memorized_main_compartment: session
                 .memorized_main_compartment()
                 .map(CompartmentModelData::from_model),
+            window_layout: session.window_layout(),
         }
     }
 
@@ -612,12 +767,33 @@ impl SessionData {
             .auto_correct_settings
             .set(self.always_auto_detect_mode);
         session.lives_on_upper_floor.set(self.lives_on_upper_floor);
+        session
+            .virtual_control_element_sharing
+            .set_without_notification(self.virtual_control_element_sharing);
         session
             .send_feedback_only_if_armed
             .set_without_notification(self.send_feedback_only_if_armed);
         session
             .reset_feedback_when_releasing_source
             .set_without_notification(self.reset_feedback_when_releasing_source);
+        session
+            .send_midi_clock
+            .set_without_notification(self.send_midi_clock);
+        let midi_pass_through_filter = match self.midi_pass_through_filter.parse() {
+            Ok(f) => f,
+            Err(e) => {
+                // Not worth failing the whole instance for. Letting everything pass is what
+                // ReaLearn did before this setting existed.
+                session.notify_user(Notification::instance_warning(format!(
+                    "MIDI pass-through filter \"{}\" is invalid and has been reset to let everything pass: {e}",
+                    self.midi_pass_through_filter
+                )));
+                MidiPassThroughFilter::PASS_ALL
+            }
+        };
+        session
+            .midi_pass_through_filter
+            .set_without_notification(midi_pass_through_filter);
         session
             .control_input
             .set_without_notification(control_input);
@@ -741,6 +917,14 @@ impl SessionData {
             Compartment::Controller,
             CompartmentCommand::SetNotes(self.controller_notes.clone()),
         ));
+        session.set_compartment_lifecycle_without_notification(
+            Compartment::Controller,
+            self.controller_lifecycle.clone(),
+        );
+        session.set_compartment_message_templates_without_notification(
+            Compartment::Controller,
+            self.controller_message_templates.clone(),
+        );
         let _ = session.change(SessionCommand::ChangeCompartment(
             Compartment::Main,
             CompartmentCommand::SetNotes(self.main_notes.clone()),
@@ -764,6 +948,7 @@ impl SessionData {
                 None
             };
         session.set_memorized_main_compartment_without_notification(memorized_main_compartment);
+        session.set_window_layout(self.window_layout.clone());
         // Instance state (don't borrow sooner because the session methods might also borrow it)
         {
             let instance_state = session.instance_state().clone();
@@ -814,6 +999,17 @@ impl SessionData {
             }
             instance_state
                 .set_active_instance_tags_without_notification(self.active_instance_tags.clone());
+            let feedback_brightness = self
+                .feedback_brightness
+                .map(UnitValue::new_clamped)
+                .unwrap_or(UnitValue::MAX);
+            instance_state.set_feedback_brightness_without_notification(feedback_brightness);
+            instance_state.set_value_display_preferences(self.value_display);
+            instance_state.set_parameter_thinning(self.parameter_thinning);
+            instance_state.set_feedback_resync_interval(
+                self.feedback_resync_interval_millis
+                    .map(Duration::from_millis),
+            );
             // Compartment-specific
             // Active mapping by group
             instance_state.set_active_mapping_by_group(
@@ -838,6 +1034,15 @@ impl SessionData {
                 Compartment::Controller,
                 controller_mapping_snapshot_container,
             );
+            // FX parameter snapshots
+            let fx_parameter_snapshots = self
+                .fx_parameter_snapshots
+                .iter()
+                .map(|(id, data)| (*id, data.to_model()))
+                .collect();
+            instance_state.set_fx_parameter_snapshot_container(FxParameterSnapshotContainer::new(
+                fx_parameter_snapshots,
+            ));
             // Pot state
             instance_state.restore_pot_unit(self.pot_state.clone());
         }
@@ -874,6 +1079,17 @@ impl SessionData {
         Ok(())
     }
 
+    /// Returns the groups and mappings of the given compartment.
+    pub fn compartment_groups_and_mappings(
+        &self,
+        compartment: Compartment,
+    ) -> (&[GroupModelData], &[MappingModelData]) {
+        match compartment {
+            Compartment::Controller => (&self.controller_groups, &self.controller_mappings),
+            Compartment::Main => (&self.groups, &self.mappings),
+        }
+    }
+
     pub fn create_params(&self) -> PluginParams {
         let mut params = PluginParams::default();
         fill_compartment_params(
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Named bundles of presets, device selections and server options which can be applied to a
+/// ReaLearn instance with one click.
+///
+/// Saved as JSON file next to the app configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupConfig {
+    #[serde(default)]
+    pub setups: Vec<Setup>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Setup {
+    /// Unique name, shown in the header panel menu.
+    pub name: String,
+    /// ID of the controller preset to be loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller_preset_id: Option<String>,
+    /// ID of the main preset to be loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main_preset_id: Option<String>,
+    /// Name of the MIDI input device which should be used as control input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_input_device_name: Option<String>,
+    /// Name of the MIDI output device which should be used as feedback output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback_output_device_name: Option<String>,
+    /// Whether the projection server should be running. `None` leaves it as it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_enabled: Option<bool>,
+}
+
+impl SetupConfig {
+    /// Returns an empty config if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        let json = fs::read_to_string(path).map_err(|_| "couldn't read setup file".to_string())?;
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Setup file isn't valid. Details:\n\n{}", e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), &'static str> {
+        let json = serde_json::to_string_pretty(self).map_err(|_| "couldn't serialize setups")?;
+        fs::create_dir_all(path.parent().ok_or("invalid setup file path")?)
+            .map_err(|_| "couldn't create setup directory")?;
+        fs::write(path, json).map_err(|_| "couldn't write setup file")?;
+        Ok(())
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&Setup> {
+        self.setups.iter().find(|s| s.name == name)
+    }
+
+    /// Adds the given setup or replaces the existing one with the same name.
+    pub fn upsert(&mut self, setup: Setup) {
+        match self.setups.iter_mut().find(|s| s.name == setup.name) {
+            None => self.setups.push(setup),
+            Some(existing) => *existing = setup,
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.setups.retain(|s| s.name != name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(name: &str, main_preset_id: &str) -> Setup {
+        Setup {
+            name: name.to_string(),
+            main_preset_id: Some(main_preset_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_setup_with_same_name() {
+        // Given
+        let mut config = SetupConfig {
+            setups: vec![setup("Studio", "mixing"), setup("Live", "playtime")],
+        };
+        // When
+        config.upsert(setup("Studio", "mastering"));
+        config.upsert(setup("Home", "mixing"));
+        // Then
+        assert_eq!(
+            config.setups,
+            vec![
+                setup("Studio", "mastering"),
+                setup("Live", "playtime"),
+                setup("Home", "mixing")
+            ]
+        );
+    }
+}
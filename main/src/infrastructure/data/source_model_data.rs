@@ -5,12 +5,18 @@ use crate::application::{
 };
 use crate::base::default_util::{deserialize_null_default, is_default};
 use crate::base::notification;
-use crate::domain::{Compartment, CompartmentParamIndex, Keystroke};
+use crate::domain::{
+    Compartment, CompartmentParamIndex, Keystroke, DEFAULT_AUDIO_ATTACK, DEFAULT_AUDIO_RELEASE,
+    DEFAULT_LFO_PERIOD,
+};
 use crate::infrastructure::data::common::OscValueRange;
 use crate::infrastructure::data::VirtualControlElementIdData;
 use helgoboss_learn::{DisplayType, MidiClockTransportMessage, OscTypeTag, SourceCharacter};
 use helgoboss_midi::{Channel, U14, U7};
-use realearn_api::persistence::MidiScriptKind;
+use realearn_api::persistence::{
+    AudioInputKind, AudioLevelMeasurement, LfoShape, MidiMachineControlCommand, MidiScriptKind,
+    PitchBendBehavior, SourceDebounce, VelocityFeedback,
+};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
@@ -75,6 +81,12 @@ pub struct SourceModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub mmc_command: MidiMachineControlCommand,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub raw_midi_pattern: String,
     #[serde(
         default,
@@ -106,6 +118,63 @@ pub struct SourceModelData {
         skip_serializing_if = "is_default"
     )]
     pub line: Option<u8>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub velocity_feedback: Option<VelocityFeedback>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub feedback_as_note_on: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub debounce: Option<SourceDebounce>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub pitch_bend_behavior: Option<PitchBendBehavior>,
+    // Audio
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub audio_input_kind: AudioInputKind,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub audio_channel: u32,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub audio_level_measurement: AudioLevelMeasurement,
+    /// `None` means default attack.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub audio_attack_millis: Option<u64>,
+    /// `None` means default release.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub audio_release_millis: Option<u64>,
     // OSC
     #[serde(
         default,
@@ -181,6 +250,19 @@ pub struct SourceModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub lfo_shape: LfoShape,
+    /// `None` means default period.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub lfo_period: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub parameter_index: CompartmentParamIndex,
 }
 
@@ -199,12 +281,24 @@ impl SourceModelData {
             is_registered: model.is_registered(),
             is_14_bit: model.is_14_bit(),
             message: model.midi_clock_transport_message(),
+            mmc_command: model.midi_machine_control_command(),
             raw_midi_pattern: model.raw_midi_pattern().to_owned(),
             midi_script_kind: model.midi_script_kind(),
             midi_script: model.midi_script().to_owned(),
             display_type: model.display_type(),
             display_id: model.display_id(),
             line: model.line(),
+            velocity_feedback: model.velocity_feedback().cloned(),
+            feedback_as_note_on: model.feedback_as_note_on(),
+            debounce: model.debounce().copied(),
+            pitch_bend_behavior: model.pitch_bend_behavior().copied(),
+            audio_input_kind: model.audio_input_kind(),
+            audio_channel: model.audio_channel(),
+            audio_level_measurement: model.audio_level_measurement(),
+            audio_attack_millis: Some(model.audio_attack_millis())
+                .filter(|v| *v != DEFAULT_AUDIO_ATTACK.as_millis() as u64),
+            audio_release_millis: Some(model.audio_release_millis())
+                .filter(|v| *v != DEFAULT_AUDIO_RELEASE.as_millis() as u64),
             osc_address_pattern: model.osc_address_pattern().to_owned(),
             osc_arg_index: model.osc_arg_index(),
             osc_arg_type: model.osc_arg_type_tag(),
@@ -218,6 +312,8 @@ impl SourceModelData {
             ),
             reaper_source_type: model.reaper_source_type(),
             timer_millis: model.timer_millis(),
+            lfo_shape: model.lfo_shape(),
+            lfo_period: Some(model.lfo_period()).filter(|p| *p != DEFAULT_LFO_PERIOD),
             parameter_index: model.parameter_index(),
         }
     }
@@ -278,12 +374,28 @@ impl SourceModelData {
         model.change(P::SetIsRegistered(self.is_registered));
         model.change(P::SetIs14Bit(self.is_14_bit));
         model.change(P::SetMidiClockTransportMessage(self.message));
+        model.change(P::SetMidiMachineControlCommand(self.mmc_command));
         model.change(P::SetRawMidiPattern(self.raw_midi_pattern.clone()));
         model.change(P::SetMidiScriptKind(self.midi_script_kind));
         model.change(P::SetMidiScript(self.midi_script.clone()));
         model.change(P::SetDisplayType(self.display_type));
         model.change(P::SetDisplayId(self.display_id));
         model.change(P::SetLine(self.line));
+        model.change(P::SetVelocityFeedback(self.velocity_feedback.clone()));
+        model.change(P::SetFeedbackAsNoteOn(self.feedback_as_note_on));
+        model.change(P::SetDebounce(self.debounce));
+        model.change(P::SetPitchBendBehavior(self.pitch_bend_behavior));
+        model.change(P::SetAudioInputKind(self.audio_input_kind));
+        model.change(P::SetAudioChannel(self.audio_channel));
+        model.change(P::SetAudioLevelMeasurement(self.audio_level_measurement));
+        model.change(P::SetAudioAttackMillis(
+            self.audio_attack_millis
+                .unwrap_or(DEFAULT_AUDIO_ATTACK.as_millis() as _),
+        ));
+        model.change(P::SetAudioReleaseMillis(
+            self.audio_release_millis
+                .unwrap_or(DEFAULT_AUDIO_RELEASE.as_millis() as _),
+        ));
         model.change(P::SetOscAddressPattern(self.osc_address_pattern.clone()));
         model.change(P::SetOscArgIndex(self.osc_arg_index));
         model.change(P::SetOscArgTypeTag(self.osc_arg_type));
@@ -298,6 +410,10 @@ impl SourceModelData {
         ));
         model.change(P::SetReaperSourceType(self.reaper_source_type));
         model.change(P::SetTimerMillis(self.timer_millis));
+        model.change(P::SetLfoShape(self.lfo_shape));
+        model.change(P::SetLfoPeriod(
+            self.lfo_period.unwrap_or(DEFAULT_LFO_PERIOD),
+        ));
         model.change(P::SetParameterIndex(self.parameter_index));
         model.change(P::SetKeystroke(self.keystroke));
     }
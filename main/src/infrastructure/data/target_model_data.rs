@@ -1,6 +1,7 @@
 use super::f32_as_u32;
 use super::none_if_minus_one;
 use reaper_high::{BookmarkType, Fx, Guid, Reaper};
+use reaper_medium::MidiOutputDeviceId;
 
 use crate::application::{
     AutomationModeOverrideType, BookmarkAnchorType, Change, FxParameterPropValues, FxPropValues,
@@ -15,9 +16,10 @@ use crate::base::default_util::{
 use crate::base::notification;
 use crate::domain::{
     get_fx_chains, ActionInvocationType, AnyOnParameter, Compartment, Exclusivity,
-    ExtendedProcessorContext, FxDisplayType, GroupKey, OscDeviceId, ReaperTargetType, SeekOptions,
-    SendMidiDestination, SoloBehavior, Tag, TouchedRouteParameterType, TouchedTrackParameterType,
-    TrackExclusivity, TrackGangBehavior, TrackRouteType, TransportAction, VirtualTrack,
+    ExtendedProcessorContext, FxDisplayType, GroupKey, OscDeviceId, ParameterThinning,
+    ReaperTargetType, SeekOptions, SendMidiDestination, SoloBehavior, Tag,
+    TouchedRouteParameterType, TouchedTrackParameterType, TrackExclusivity, TrackGangBehavior,
+    TrackRouteType, TransportAction, VirtualDisplayLayout, VirtualTrack,
 };
 use crate::infrastructure::data::common::OscValueRange;
 use crate::infrastructure::data::{
@@ -30,13 +32,14 @@ use playtime_api::persistence::{ClipPlayStartTiming, ClipPlayStopTiming};
 use realearn_api::persistence::{
     BrowseTracksMode, ClipColumnAction, ClipColumnDescriptor, ClipColumnTrackContext,
     ClipManagementAction, ClipMatrixAction, ClipRowAction, ClipRowDescriptor, ClipSlotDescriptor,
-    ClipTransportAction, FxToolAction, MappingSnapshotDescForLoad, MappingSnapshotDescForTake,
-    MonitoringMode, MouseAction, PotFilterItemKind, SeekBehavior, TargetValue, TrackScope,
-    TrackToolAction,
+    ClipTransportAction, FxToolAction, ItemPropertyKind, MappingSnapshotDescForLoad,
+    MappingSnapshotDescForTake, MidiEditorAction, MonitoringMode, MouseAction, PotFilterItemKind,
+    SeekBehavior, TargetValue, TrackScope, TrackToolAction,
 };
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
+use std::time::Duration;
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -191,6 +194,12 @@ pub struct TargetModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub display_layout: VirtualDisplayLayout,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub fx_snapshot: Option<FxSnapshot>,
     #[serde(
         default,
@@ -277,6 +286,12 @@ pub struct TargetModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub midi_output_device_id: Option<u8>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub raw_midi_pattern: String,
     // Send OSC
     #[serde(
@@ -320,6 +335,15 @@ pub struct TargetModelData {
     pub poll_for_feedback: bool,
     #[serde(default, skip_serializing_if = "is_default")]
     pub retrigger: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub low_latency: bool,
+    /// `None` means that the instance default is used.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub parameter_thinning: Option<ParameterThinning>,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -351,6 +375,26 @@ pub struct TargetModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub global_parameter_name: String,
+    /// `None` means snapshot 1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub fx_parameter_snapshot: Option<u32>,
+    /// Glide time in milliseconds. `None` means no glide.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub glide_time: Option<u64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub exclusivity: Exclusivity,
     #[serde(
         default,
@@ -483,6 +527,40 @@ pub struct TargetModelData {
         skip_serializing_if = "is_default"
     )]
     pub pot_filter_item_kind: PotFilterItemKind,
+    /// New since ReaLearn v2.15.0-pre.1. `None` means a bank size of 1.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub track_bank_size: Option<u32>,
+    /// New since ReaLearn v2.15.0-pre.1
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub wrap_around: bool,
+    /// New since ReaLearn v2.15.0-pre.1
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub item_property: ItemPropertyKind,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub midi_editor_action: MidiEditorAction,
+    /// New since ReaLearn v2.15.0-pre.1
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub solo_defeat: bool,
 }
 
 impl TargetModelData {
@@ -516,6 +594,7 @@ impl TargetModelData {
             fx_parameter_data: serialize_fx_parameter(model.fx_parameter()),
             select_exclusively: None,
             solo_behavior: Some(model.solo_behavior()),
+            solo_defeat: model.solo_defeat(),
             seek_behavior: Some(model.seek_behavior()),
             track_exclusivity: model.track_exclusivity(),
             track_tool_action: model.track_tool_action(),
@@ -526,12 +605,14 @@ impl TargetModelData {
             control_element_index: VirtualControlElementIdData::from_model(
                 model.control_element_id(),
             ),
+            display_layout: model.display_layout(),
             fx_snapshot: model.fx_snapshot().cloned(),
             touched_parameter_type: model.touched_track_parameter_type(),
             touched_route_parameter_type: model.touched_route_parameter_type(),
             bookmark_data: BookmarkData {
                 anchor: model.bookmark_anchor_type(),
                 r#ref: model.bookmark_ref(),
+                name: model.bookmark_name().to_owned(),
                 is_region: model.bookmark_type() == BookmarkType::Region,
             },
             seek_options: model.seek_options(),
@@ -543,7 +624,15 @@ impl TargetModelData {
             fx_display_type: model.fx_display_type(),
             scroll_arrange_view: model.scroll_arrange_view(),
             scroll_mixer: model.scroll_mixer(),
+            track_bank_size: match model.track_bank_size() {
+                1 => None,
+                s => Some(s),
+            },
+            wrap_around: model.wrap_around(),
+            item_property: model.item_property(),
+            midi_editor_action: model.midi_editor_action(),
             send_midi_destination: model.send_midi_destination(),
+            midi_output_device_id: model.midi_output_device_id().map(|id| id.get()),
             raw_midi_pattern: model.raw_midi_pattern().to_owned(),
             osc_address_pattern: model.osc_address_pattern().to_owned(),
             osc_arg_index: model.osc_arg_index(),
@@ -556,12 +645,24 @@ impl TargetModelData {
             buffered: false,
             poll_for_feedback: model.poll_for_feedback(),
             retrigger: model.retrigger(),
+            low_latency: model.low_latency(),
+            parameter_thinning: model.parameter_thinning(),
             tags: model.tags().to_vec(),
             mapping_snapshot: model.mapping_snapshot_desc_for_load(),
             take_mapping_snapshot: Some(model.mapping_snapshot_desc_for_take()),
             mapping_snapshot_default_value: model
                 .mapping_snapshot_default_value()
                 .map(convert_target_value_to_api),
+            global_parameter_name: model.global_parameter_name().to_owned(),
+            fx_parameter_snapshot: match model.fx_parameter_snapshot_id() {
+                1 => None,
+                id => Some(id),
+            },
+            glide_time: if model.glide_time().is_zero() {
+                None
+            } else {
+                Some(model.glide_time().as_millis() as u64)
+            },
             exclusivity: model.exclusivity(),
             group_id: conversion_context
                 .group_key_by_id(model.group_id())
@@ -591,6 +692,39 @@ impl TargetModelData {
         }
     }
 
+    /// Returns the expressions which are used for resolving tracks, FX, parameters, routes and
+    /// clip slots dynamically.
+    pub fn dynamic_expressions(&self) -> impl Iterator<Item = &str> {
+        let (slot_column_expression, slot_row_expression) = match &self.clip_slot {
+            Some(ClipSlotDescriptor::Dynamic {
+                column_expression,
+                row_expression,
+            }) => (Some(column_expression), Some(row_expression)),
+            _ => (None, None),
+        };
+        let column_expression = match &self.clip_column {
+            ClipColumnDescriptor::Dynamic { expression } => Some(expression),
+            _ => None,
+        };
+        let row_expression = match &self.clip_row {
+            ClipRowDescriptor::Dynamic { expression } => Some(expression),
+            _ => None,
+        };
+        [
+            self.track_data.expression.as_ref(),
+            self.fx_data.expression.as_ref(),
+            self.fx_parameter_data.expression.as_ref(),
+            self.track_route_data.expression.as_ref(),
+            slot_column_expression,
+            slot_row_expression,
+            column_expression,
+            row_expression,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|e| e.as_str())
+    }
+
     pub fn apply_to_model(
         &self,
         model: &mut TargetModel,
@@ -629,7 +763,12 @@ impl TargetModelData {
         model.change(C::SetCategory(final_category));
         model.change(C::SetUnit(self.unit));
         model.change(C::SetTargetType(self.r#type));
-        if self.category == TargetCategory::Reaper && self.r#type == ReaperTargetType::Action {
+        if self.category == TargetCategory::Reaper
+            && matches!(
+                self.r#type,
+                ReaperTargetType::Action | ReaperTargetType::ToolbarToggle
+            )
+        {
             let reaper = Reaper::get();
             let action = match self.command_name.as_ref() {
                 None => None,
@@ -734,6 +873,7 @@ impl TargetModelData {
             }
         });
         model.change(C::SetSoloBehavior(solo_behavior));
+        model.change(C::SetSoloDefeat(self.solo_defeat));
         let seek_behavior = self.seek_behavior.unwrap_or_else(|| {
             // Older version didn't have an explicit seek behavior. Determine old behavior.
             match self.r#type {
@@ -758,6 +898,7 @@ impl TargetModelData {
         model.change(C::SetControlElementId(
             self.control_element_index.to_model(),
         ));
+        model.change(C::SetDisplayLayout(self.display_layout));
         model.change(C::SetFxSnapshot(self.fx_snapshot.clone()));
         model.change(C::SetTouchedTrackParameterType(self.touched_parameter_type));
         model.change(C::SetTouchedRouteParameterType(
@@ -771,6 +912,7 @@ impl TargetModelData {
         model.change(C::SetBookmarkType(bookmark_type));
         model.change(C::SetBookmarkAnchorType(self.bookmark_data.anchor));
         model.change(C::SetBookmarkRef(self.bookmark_data.r#ref));
+        model.change(C::SetBookmarkName(self.bookmark_data.name.clone()));
         let _ = model.set_seek_options(self.seek_options);
         model.change(C::SetTrackArea(self.track_area));
         model.change(C::SetAutomationMode(self.track_automation_mode));
@@ -795,7 +937,14 @@ impl TargetModelData {
             self.scroll_mixer
         };
         model.change(C::SetScrollMixer(scroll_mixer));
+        model.change(C::SetTrackBankSize(self.track_bank_size.unwrap_or(1)));
+        model.change(C::SetWrapAround(self.wrap_around));
+        model.change(C::SetItemProperty(self.item_property));
+        model.change(C::SetMidiEditorAction(self.midi_editor_action));
         model.change(C::SetSendMidiDestination(self.send_midi_destination));
+        model.change(C::SetMidiOutputDeviceId(
+            self.midi_output_device_id.map(MidiOutputDeviceId::new),
+        ));
         model.change(C::SetRawMidiPattern(self.raw_midi_pattern.clone()));
         model.change(C::SetOscAddressPattern(self.osc_address_pattern.clone()));
         model.change(C::SetOscArgIndex(self.osc_arg_index));
@@ -806,6 +955,8 @@ impl TargetModelData {
         model.change(C::SetOscDevId(self.osc_dev_id));
         model.change(C::SetPollForFeedback(self.poll_for_feedback));
         model.change(C::SetRetrigger(self.retrigger));
+        model.change(C::SetLowLatency(self.low_latency));
+        model.change(C::SetParameterThinning(self.parameter_thinning));
         model.change(C::SetTags(self.tags.clone()));
         model.change(C::SetExclusivity(self.exclusivity));
         let group_id = conversion_context
@@ -894,6 +1045,15 @@ impl TargetModelData {
         model.change(C::SetMappingSnapshotId(
             mapping_snapshot_id_for_load.or(mapping_snapshot_id_for_take),
         ));
+        model.change(C::SetGlobalParameterName(
+            self.global_parameter_name.clone(),
+        ));
+        model.change(C::SetFxParameterSnapshotId(
+            self.fx_parameter_snapshot.unwrap_or(1),
+        ));
+        model.change(C::SetGlideTime(Duration::from_millis(
+            self.glide_time.unwrap_or(0),
+        )));
         model.set_mouse_action_without_notification(self.mouse_action);
         model.change(C::SetPotFilterItemKind(self.pot_filter_item_kind));
         Ok(())
@@ -1765,6 +1925,13 @@ pub struct BookmarkData {
         skip_serializing_if = "is_default"
     )]
     pub r#ref: u32,
+    #[serde(
+        rename = "bookmarkName",
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub name: String,
     #[serde(
         rename = "bookmarkIsRegion",
         default,
@@ -8,10 +8,11 @@ use crate::base::{
 };
 use crate::domain::{
     ActionInvokedEvent, AdditionalFeedbackEvent, BackboneState, ChangeInstanceFxArgs,
-    ChangeInstanceTrackArgs, Compartment, EnableInstancesArgs, Exclusivity, FeedbackAudioHookTask,
-    Garbage, GarbageBin, GroupId, InputDescriptor, InstanceContainer, InstanceContainerCommonArgs,
-    InstanceFxChangeRequest, InstanceId, InstanceOrchestrationEvent, InstanceTrackChangeRequest,
-    MainProcessor, MessageCaptureEvent, MessageCaptureResult, MidiScanResult, NormalAudioHookTask,
+    ChangeInstanceTrackArgs, Compartment, ControlInput, EnableInstancesArgs, Exclusivity,
+    FeedbackAudioHookTask, FeedbackOutput, Garbage, GarbageBin, GroupId, InputDescriptor,
+    InstanceContainer, InstanceContainerCommonArgs, InstanceFxChangeRequest, InstanceId,
+    InstanceOrchestrationEvent, InstanceTrackChangeRequest, MainProcessor, MessageCaptureEvent,
+    MessageCaptureResult, MidiControlInput, MidiDestination, MidiScanResult, NormalAudioHookTask,
     OscDeviceId, OscFeedbackProcessor, OscFeedbackTask, OscScanResult, QualifiedClipMatrixEvent,
     QualifiedMappingId, RealearnAccelerator, RealearnAudioHook, RealearnClipMatrix,
     RealearnControlSurfaceMainTask, RealearnControlSurfaceMiddleware, RealearnTarget,
@@ -19,14 +20,15 @@ use crate::domain::{
     SharedRealTimeProcessor, Tag,
 };
 use crate::infrastructure::data::{
-    ExtendedPresetManager, FileBasedControllerPresetManager, FileBasedMainPresetManager,
-    FileBasedPresetLinkManager, OscDevice, OscDeviceManager, SharedControllerPresetManager,
+    device_names_match, ControllerAutoAttachConfig, ExtendedPresetManager,
+    FileBasedControllerPresetManager, FileBasedMainPresetManager, FileBasedPresetLinkManager,
+    OscDevice, OscDeviceManager, Setup, SetupConfig, SharedControllerPresetManager,
     SharedMainPresetManager, SharedOscDeviceManager, SharedPresetLinkManager,
 };
-use crate::infrastructure::plugin::debug_util;
+use crate::infrastructure::plugin::{check_for_update_at_startup_if_enabled, debug_util};
 use crate::infrastructure::server;
 use crate::infrastructure::server::{
-    MetricsReporter, RealearnServer, SharedRealearnServer, COMPANION_WEB_APP_URL,
+    MetricsReporter, RealearnServer, SharedRealearnServer, WebhookDispatcher, COMPANION_WEB_APP_URL,
 };
 use crate::infrastructure::ui::MessagePanel;
 
@@ -53,11 +55,12 @@ use rxrust::prelude::*;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use slog::{debug, Drain, Logger};
-use std::cell::{Ref, RefCell};
+use std::cell::{Cell, Ref, RefCell};
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 use swell_ui::{SharedView, View, ViewManager, Window};
 use tempfile::TempDir;
 use url::Url;
@@ -73,6 +76,10 @@ const FEEDBACK_AUDIO_HOOK_TASK_QUEUE_SIZE: usize = 100_000;
 // that high. If one day this gets important, we need to measure.
 const GARBAGE_QUEUE_SIZE: usize = 50_000;
 const NORMAL_AUDIO_HOOK_TASK_QUEUE_SIZE: usize = 2000;
+// On project load, the initial feedback of each instance is deferred until the load has settled
+// and then sent one instance after the other, so that it doesn't add to the load stall.
+const DEFERRED_FEEDBACK_INITIAL_DELAY: Duration = Duration::from_millis(200);
+const DEFERRED_FEEDBACK_STAGGER: Duration = Duration::from_millis(30);
 
 make_available_globally_in_main_thread!(App);
 
@@ -92,6 +99,7 @@ pub struct App {
     preset_link_manager: SharedPresetLinkManager,
     osc_device_manager: SharedOscDeviceManager,
     server: SharedRealearnServer,
+    webhook_dispatcher: WebhookDispatcher,
     config: RefCell<AppConfig>,
     changed_subject: RefCell<LocalSubject<'static, (), ()>>,
     recently_focused_fx_container: Rc<RefCell<RecentlyFocusedFxContainer>>,
@@ -104,6 +112,11 @@ pub struct App {
     instance_orchestration_event_sender: SenderToNormalThread<InstanceOrchestrationEvent>,
     audio_hook_task_sender: SenderToRealTimeThread<NormalAudioHookTask>,
     sessions: RefCell<Vec<WeakSession>>,
+    /// Point in time at which the most recently loaded instance is going to send its deferred
+    /// initial feedback.
+    last_deferred_feedback_slot: Cell<Option<Instant>>,
+    /// Fresh sessions for which no controller auto-attach rule matched yet.
+    sessions_awaiting_controller_auto_attach: RefCell<Vec<WeakSession>>,
     sessions_changed_subject: RefCell<LocalSubject<'static, (), ()>>,
     message_panel: SharedView<MessagePanel>,
     osc_feedback_processor: Rc<RefCell<OscFeedbackProcessor>>,
@@ -290,6 +303,7 @@ impl App {
                 App::server_resource_dir_path().join("certificates"),
                 MetricsReporter::new(),
             ))),
+            webhook_dispatcher: Default::default(),
             config: RefCell::new(config),
             changed_subject: Default::default(),
             recently_focused_fx_container: Default::default(),
@@ -302,6 +316,8 @@ impl App {
             instance_orchestration_event_sender,
             audio_hook_task_sender,
             sessions: Default::default(),
+            last_deferred_feedback_slot: Default::default(),
+            sessions_awaiting_controller_auto_attach: Default::default(),
             sessions_changed_subject: Default::default(),
             message_panel: Default::default(),
             osc_feedback_processor: Rc::new(RefCell::new(OscFeedbackProcessor::new(
@@ -425,6 +441,7 @@ impl App {
                 .start()
                 .unwrap_or_else(warn_about_failed_server_start);
         }
+        check_for_update_at_startup_if_enabled();
         let mut session = Reaper::get().medium_session();
         // Action hooks
         session
@@ -766,6 +783,10 @@ impl App {
         &self.server
     }
 
+    pub fn webhook_dispatcher(&self) -> &WebhookDispatcher {
+        &self.webhook_dispatcher
+    }
+
     pub fn config(&self) -> Ref<AppConfig> {
         self.config.borrow()
     }
@@ -785,6 +806,14 @@ impl App {
         self.server.borrow_mut().stop();
     }
 
+    pub fn set_compact_mapping_panel_persistently(&self, compact: bool) {
+        self.change_config(|config| config.set_compact_mapping_panel(compact));
+    }
+
+    pub fn set_update_check_enabled_persistently(&self, enabled: bool) {
+        self.change_config(|config| config.set_update_check_enabled(enabled));
+    }
+
     /// Logging debug info is always initiated by a particular session.
     pub fn log_debug_info(&self, session_id: &str) {
         let msg = format!(
@@ -859,10 +888,30 @@ impl App {
         Self::realearn_data_dir_path().join("auto-load-configs")
     }
 
+    pub fn realearn_mapping_pack_dir_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("mapping-packs")
+    }
+
+    pub fn realearn_autosave_dir_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("autosave")
+    }
+
     pub fn realearn_osc_device_config_file_path() -> PathBuf {
         App::realearn_resource_dir_path().join("osc.json")
     }
 
+    pub fn realearn_webhook_config_file_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("webhooks.json")
+    }
+
+    pub fn realearn_controller_auto_attach_config_file_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("controller-auto-attach.json")
+    }
+
+    pub fn realearn_setups_file_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("setups.json")
+    }
+
     // We need this to be static because we need it at plugin construction time, so we don't have
     // REAPER API access yet. App needs REAPER API to be constructed (e.g. in order to
     // know where's the resource directory that contains the app configuration).
@@ -996,6 +1045,21 @@ impl App {
         })
     }
 
+    /// Returns the delay after which a loading instance should send its initial feedback.
+    ///
+    /// Each call reserves the next slot, so instances loaded together send their feedback one
+    /// after the other instead of all at once.
+    pub fn reserve_deferred_feedback_slot(&self) -> Duration {
+        let now = Instant::now();
+        let earliest = now + DEFERRED_FEEDBACK_INITIAL_DELAY;
+        let slot = match self.last_deferred_feedback_slot.get() {
+            Some(last) => earliest.max(last + DEFERRED_FEEDBACK_STAGGER),
+            None => earliest,
+        };
+        self.last_deferred_feedback_slot.set(Some(slot));
+        slot - now
+    }
+
     pub fn register_session(&self, session: WeakSession) {
         let mut sessions = self.sessions.borrow_mut();
         debug!(Reaper::get().logger(), "Registering new session...");
@@ -1027,6 +1091,257 @@ impl App {
         self.notify_sessions_changed();
     }
 
+    /// Sets up the given freshly created session according to the first controller auto-attach
+    /// rule whose MIDI input device is present.
+    ///
+    /// If no such device is present yet, this is retried whenever MIDI devices appear, as long as
+    /// the user hasn't chosen a control input or controller preset in the meantime.
+    pub fn auto_attach_controller(&self, session: &SharedSession) {
+        if self.try_to_auto_attach_controller(session) {
+            self.sessions_awaiting_controller_auto_attach
+                .borrow_mut()
+                .push(Rc::downgrade(session));
+        }
+    }
+
+    /// Retries auto-attaching controllers to sessions for which no rule matched so far.
+    pub fn auto_attach_controllers_to_waiting_sessions(&self) {
+        if self
+            .sessions_awaiting_controller_auto_attach
+            .borrow()
+            .is_empty()
+        {
+            return;
+        }
+        let waiting_sessions = self
+            .sessions_awaiting_controller_auto_attach
+            .replace(Vec::new());
+        let still_waiting_sessions: Vec<_> = waiting_sessions
+            .into_iter()
+            .filter(|weak_session| {
+                let Some(session) = weak_session.upgrade() else {
+                    return false;
+                };
+                let Ok(s) = session.try_borrow() else {
+                    // Try again next time.
+                    return true;
+                };
+                let still_unconfigured = s.control_input()
+                    == ControlInput::Midi(MidiControlInput::FxInput)
+                    && s.active_controller_preset_id().is_none();
+                drop(s);
+                still_unconfigured && self.try_to_auto_attach_controller(&session)
+            })
+            .collect();
+        self.sessions_awaiting_controller_auto_attach
+            .borrow_mut()
+            .extend(still_waiting_sessions);
+    }
+
+    /// Returns `true` if auto-attaching should be retried as soon as new devices appear.
+    fn try_to_auto_attach_controller(&self, session: &SharedSession) -> bool {
+        let config = match ControllerAutoAttachConfig::load(
+            &App::realearn_controller_auto_attach_config_file_path(),
+        ) {
+            Ok(c) => c,
+            Err(msg) => {
+                debug!(App::logger(), "{}", msg);
+                return false;
+            }
+        };
+        if config.rules.is_empty() {
+            return false;
+        }
+        let input_devices: Vec<_> = Reaper::get()
+            .midi_input_devices()
+            .filter(|d| d.is_connected())
+            .map(|d| (d.id(), d.name().into_inner().to_string_lossy().to_string()))
+            .collect();
+        let Some(rule) =
+            config.find_matching_rule(input_devices.iter().map(|(_, name)| name.as_str()))
+        else {
+            return true;
+        };
+        let Some((input_device_id, _)) = input_devices
+            .iter()
+            .find(|(_, name)| device_names_match(&rule.device_name, name))
+        else {
+            return true;
+        };
+        let feedback_output = rule.feedback_output_device_name.as_ref().and_then(|name| {
+            Reaper::get()
+                .midi_output_devices()
+                .filter(|d| d.is_connected())
+                .find(|d| device_names_match(name, &d.name().into_inner().to_string_lossy()))
+                .map(|d| FeedbackOutput::Midi(MidiDestination::Device(d.id())))
+        });
+        let mut session = session.borrow_mut();
+        session
+            .control_input
+            .set(ControlInput::Midi(MidiControlInput::Device(
+                *input_device_id,
+            )));
+        if feedback_output.is_some() {
+            session.feedback_output.set(feedback_output);
+        }
+        if let Some(preset_id) = &rule.controller_preset_id {
+            if self
+                .controller_preset_manager
+                .find_index_by_id(preset_id)
+                .is_some()
+            {
+                session.activate_controller_preset(Some(preset_id.clone()));
+            } else {
+                notification::warn(format!(
+                    "Controller auto-attach rule for device \"{}\" refers to controller \
+                    preset \"{}\", which doesn't exist.",
+                    rule.device_name, preset_id
+                ));
+            }
+        }
+        false
+    }
+
+    pub fn setups(&self) -> Result<SetupConfig, String> {
+        SetupConfig::load(&App::realearn_setups_file_path())
+    }
+
+    /// Saves the given setup, replacing an existing one with the same name.
+    pub fn save_setup(&self, setup: Setup) -> Result<(), String> {
+        let mut config = self.setups()?;
+        config.upsert(setup);
+        config.save(&App::realearn_setups_file_path())?;
+        Ok(())
+    }
+
+    pub fn remove_setup(&self, name: &str) -> Result<(), String> {
+        let mut config = self.setups()?;
+        config.remove(name);
+        config.save(&App::realearn_setups_file_path())?;
+        Ok(())
+    }
+
+    /// Captures presets and devices of the given session as well as the server state.
+    pub fn capture_setup(&self, session: &SharedSession, name: String) -> Setup {
+        let session = session.borrow();
+        let control_input_device_name = match session.control_input() {
+            ControlInput::Midi(MidiControlInput::Device(id)) => Some(
+                Reaper::get()
+                    .midi_input_device_by_id(id)
+                    .name()
+                    .into_inner()
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        let feedback_output_device_name = match session.feedback_output() {
+            Some(FeedbackOutput::Midi(MidiDestination::Device(id))) => Some(
+                Reaper::get()
+                    .midi_output_device_by_id(id)
+                    .name()
+                    .into_inner()
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            _ => None,
+        };
+        Setup {
+            name,
+            controller_preset_id: session
+                .active_controller_preset_id()
+                .map(|id| id.to_string()),
+            main_preset_id: session
+                .active_preset_id(Compartment::Main)
+                .map(|id| id.to_string()),
+            control_input_device_name,
+            feedback_output_device_name,
+            server_enabled: Some(self.config().server_is_enabled()),
+        }
+    }
+
+    /// Applies the setup with the given name to the given session.
+    ///
+    /// Parts of the setup which can't be applied (e.g. because a device is not connected) are
+    /// skipped and reported as notification.
+    pub fn apply_setup(&self, session: &SharedSession, name: &str) -> Result<(), String> {
+        let config = self.setups()?;
+        let setup = config.find_by_name(name).ok_or("setup doesn't exist")?;
+        if let Some(enabled) = setup.server_enabled {
+            if enabled && !self.server_is_running() {
+                if let Err(e) = self.start_server_persistently() {
+                    notification::warn(format!("Couldn't start server: {}", e));
+                }
+            } else if !enabled && self.config().server_is_enabled() {
+                self.stop_server_persistently();
+            }
+        }
+        let mut session = session.borrow_mut();
+        if let Some(device_name) = &setup.control_input_device_name {
+            let device = Reaper::get()
+                .midi_input_devices()
+                .filter(|d| d.is_connected())
+                .find(|d| {
+                    device_names_match(device_name, &d.name().into_inner().to_string_lossy())
+                });
+            match device {
+                None => notification::warn(format!(
+                    "Setup \"{}\" refers to MIDI input device \"{}\", which is not connected.",
+                    setup.name, device_name
+                )),
+                Some(d) => session
+                    .control_input
+                    .set(ControlInput::Midi(MidiControlInput::Device(d.id()))),
+            }
+        }
+        if let Some(device_name) = &setup.feedback_output_device_name {
+            let device = Reaper::get()
+                .midi_output_devices()
+                .filter(|d| d.is_connected())
+                .find(|d| {
+                    device_names_match(device_name, &d.name().into_inner().to_string_lossy())
+                });
+            match device {
+                None => notification::warn(format!(
+                    "Setup \"{}\" refers to MIDI output device \"{}\", which is not connected.",
+                    setup.name, device_name
+                )),
+                Some(d) => session
+                    .feedback_output
+                    .set(Some(FeedbackOutput::Midi(MidiDestination::Device(d.id())))),
+            }
+        }
+        if let Some(preset_id) = &setup.controller_preset_id {
+            if self
+                .controller_preset_manager
+                .find_index_by_id(preset_id)
+                .is_some()
+            {
+                session.activate_controller_preset(Some(preset_id.clone()));
+            } else {
+                notification::warn(format!(
+                    "Setup \"{}\" refers to controller preset \"{}\", which doesn't exist.",
+                    setup.name, preset_id
+                ));
+            }
+        }
+        if let Some(preset_id) = &setup.main_preset_id {
+            if self
+                .main_preset_manager
+                .find_index_by_id(preset_id)
+                .is_some()
+            {
+                session.activate_main_preset(Some(preset_id.clone()));
+            } else {
+                notification::warn(format!(
+                    "Setup \"{}\" refers to main preset \"{}\", which doesn't exist.",
+                    setup.name, preset_id
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn notify_sessions_changed(&self) {
         self.sessions_changed_subject.borrow_mut().next(());
     }
@@ -1579,6 +1894,25 @@ impl AppConfig {
         self.main.server_enabled > 0
     }
 
+    pub fn set_compact_mapping_panel(&mut self, compact: bool) {
+        self.main.compact_mapping_panel = compact.into();
+    }
+
+    /// If enabled, the mapping panel hides rarely used mode sections as long as they are at their
+    /// defaults.
+    pub fn compact_mapping_panel_is_enabled(&self) -> bool {
+        self.main.compact_mapping_panel > 0
+    }
+
+    pub fn set_update_check_enabled(&mut self, enabled: bool) {
+        self.main.update_check_enabled = enabled.into();
+    }
+
+    /// If enabled, ReaLearn checks for a newer version when the first instance is loaded.
+    pub fn update_check_is_enabled(&self) -> bool {
+        self.main.update_check_enabled > 0
+    }
+
     pub fn companion_web_app_url(&self) -> url::Url {
         Url::parse(&self.main.companion_web_app_url).expect("invalid companion web app URL")
     }
@@ -1612,6 +1946,10 @@ struct MainConfig {
         skip_serializing_if = "is_default_companion_web_app_url"
     )]
     companion_web_app_url: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    compact_mapping_panel: u8,
+    #[serde(default, skip_serializing_if = "is_default")]
+    update_check_enabled: u8,
 }
 
 const DEFAULT_SERVER_HTTP_PORT: u16 = 39080;
@@ -1658,6 +1996,8 @@ impl Default for MainConfig {
             server_https_port: default_server_https_port(),
             server_grpc_port: default_server_grpc_port(),
             companion_web_app_url: default_companion_web_app_url(),
+            compact_mapping_panel: Default::default(),
+            update_check_enabled: Default::default(),
         }
     }
 }
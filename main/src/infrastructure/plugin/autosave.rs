@@ -0,0 +1,165 @@
+//! Periodically saves the state of each ReaLearn instance into a sidecar file, so that mapping
+//! work done since the last project save is not lost if REAPER crashes.
+//!
+//! The autosave file is deleted when the instance is shut down properly. So if an autosave file
+//! exists on project load, has been written after the project was saved and its content differs
+//! from what has been loaded, we can assume that the previous run ended unexpectedly and offer
+//! recovery.
+use crate::base::hash_util::calculate_non_crypto_hash;
+use crate::base::notification;
+use crate::base::Global;
+use crate::infrastructure::data::SessionData;
+use crate::infrastructure::plugin::{App, RealearnPluginParameters};
+use reaper_high::Reaper;
+use reaper_medium::{MessageBoxResult, MessageBoxType};
+use rxrust::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Content of an autosave file.
+#[derive(Serialize, Deserialize)]
+struct Autosave {
+    /// When the autosave has been written. Used to find out whether it's more recent than the
+    /// project file.
+    saved_at: SystemTime,
+    session: SessionData,
+}
+
+/// Keeps autosaving the state of the given instance until it's gone.
+pub fn keep_autosaving(plugin_parameters: &Arc<RealearnPluginParameters>) {
+    let weak_plugin_parameters = Arc::downgrade(plugin_parameters);
+    let mut last_autosave_time = Instant::now();
+    let mut last_autosave_content: Option<String> = None;
+    Global::control_surface_rx()
+        .main_thread_idle()
+        .map(move |_| weak_plugin_parameters.upgrade())
+        .take_while(|plugin_parameters| plugin_parameters.is_some())
+        .subscribe(move |plugin_parameters| {
+            let plugin_parameters = match plugin_parameters {
+                None => return,
+                Some(p) => p,
+            };
+            // Recovery must be handled before autosaving. Otherwise we would overwrite the
+            // autosave file before the user had the chance to restore it.
+            if let Some(autosaved_data) = plugin_parameters.take_pending_autosave_recovery() {
+                offer_recovery(&plugin_parameters, &autosaved_data);
+            }
+            if last_autosave_time.elapsed() < AUTOSAVE_INTERVAL {
+                return;
+            }
+            last_autosave_time = Instant::now();
+            if let Err(e) = autosave(&plugin_parameters, &mut last_autosave_content) {
+                notification::warn(format!("Couldn't autosave ReaLearn instance: {}", e));
+            }
+        });
+}
+
+/// Returns the autosaved data for the given session if it has been written after the project
+/// file was saved and differs from the given current content.
+pub fn load_newer_autosave(
+    session_id: &str,
+    project_file: Option<&Path>,
+    current_content: &str,
+) -> Option<SessionData> {
+    let autosave_content = fs::read_to_string(autosave_file_path(session_id, project_file)).ok()?;
+    let autosave: Autosave = serde_json::from_str(&autosave_content).ok()?;
+    if let Some(project_file) = project_file {
+        let project_saved_at = fs::metadata(project_file).ok()?.modified().ok()?;
+        if autosave.saved_at <= project_saved_at {
+            return None;
+        }
+    }
+    let autosaved_content = serde_json::to_string(&autosave.session).ok()?;
+    if autosaved_content == current_content {
+        return None;
+    }
+    Some(autosave.session)
+}
+
+/// Deletes the autosave file of the given instance. Should be called on proper shutdown.
+pub fn discard_autosave(plugin_parameters: &RealearnPluginParameters) {
+    if let Some(file_path) = plugin_parameters.set_autosave_file_path(None) {
+        let _ = fs::remove_file(file_path);
+    }
+}
+
+fn autosave(
+    plugin_parameters: &RealearnPluginParameters,
+    last_autosave_content: &mut Option<String>,
+) -> Result<(), String> {
+    let session_id = match plugin_parameters.session_id() {
+        None => return Ok(()),
+        Some(id) => id,
+    };
+    let file_path = autosave_file_path(&session_id, plugin_parameters.project_file().as_deref());
+    let session_data = plugin_parameters.create_session_data();
+    let content = serde_json::to_string(&session_data).map_err(|e| e.to_string())?;
+    if last_autosave_content.as_ref() == Some(&content)
+        && plugin_parameters.autosave_file_path().as_ref() == Some(&file_path)
+    {
+        return Ok(());
+    }
+    let autosave = Autosave {
+        saved_at: SystemTime::now(),
+        session: session_data,
+    };
+    let autosave_content = serde_json::to_string(&autosave).map_err(|e| e.to_string())?;
+    fs::create_dir_all(App::realearn_autosave_dir_path()).map_err(|e| e.to_string())?;
+    // Write to a temporary file first so that a crash while writing can't corrupt the last
+    // good autosave.
+    let temp_file_path = file_path.with_extension("json.tmp");
+    fs::write(&temp_file_path, autosave_content).map_err(|e| e.to_string())?;
+    fs::rename(&temp_file_path, &file_path).map_err(|e| e.to_string())?;
+    // The project might have been saved under another name in the meantime
+    let previous_file_path = plugin_parameters.set_autosave_file_path(Some(file_path.clone()));
+    if let Some(previous_file_path) = previous_file_path {
+        if previous_file_path != file_path {
+            let _ = fs::remove_file(previous_file_path);
+        }
+    }
+    *last_autosave_content = Some(content);
+    Ok(())
+}
+
+fn offer_recovery(plugin_parameters: &RealearnPluginParameters, autosaved_data: &SessionData) {
+    let session_id = plugin_parameters.session_id().unwrap_or_default();
+    let msg = format!(
+        "ReaLearn instance \"{}\" has autosaved changes which are more recent than the \
+        state saved in the project. This usually means that REAPER didn't shut down properly \
+        last time.\n\nDo you want to restore the autosaved changes?",
+        session_id
+    );
+    let result =
+        Reaper::get()
+            .medium_reaper()
+            .show_message_box(msg, "ReaLearn", MessageBoxType::YesNo);
+    if result == MessageBoxResult::Yes {
+        plugin_parameters.apply_session_data(autosaved_data);
+    }
+}
+
+/// Session IDs are only unique within a project, so the file name also contains a hash of the
+/// project file path.
+fn autosave_file_path(session_id: &str, project_file: Option<&Path>) -> PathBuf {
+    let sanitized_session_id: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let project_hash =
+        calculate_non_crypto_hash(&project_file.map(|f| f.to_string_lossy().to_string()));
+    App::realearn_autosave_dir_path().join(format!(
+        "{}-{:016x}.json",
+        sanitized_session_id, project_hash
+    ))
+}
@@ -4,6 +4,9 @@ mod tracing_util;
 use realearn_editor::*;
 mod app;
 pub use app::*;
+mod autosave;
+mod update_check;
+pub use update_check::*;
 mod realearn_plugin;
 mod realearn_plugin_parameters;
 pub use realearn_plugin_parameters::*;
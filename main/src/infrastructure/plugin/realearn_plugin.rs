@@ -11,7 +11,8 @@ use crate::domain::{
     AudioBlockProps, BackboneState, ControlEvent, ControlEventTimestamp, ControlMainTask,
     FeedbackRealTimeTask, InstanceId, MainProcessor, MidiEvent, NormalMainTask,
     NormalRealTimeToMainThreadTask, ParameterMainTask, PluginParamIndex, ProcessorContext,
-    RealTimeProcessorLocker, SharedRealTimeProcessor, PLUGIN_PARAMETER_COUNT,
+    RealTimeLogEntry, RealTimeLogger, RealTimeProcessorLocker, SharedRealTimeProcessor,
+    PLUGIN_PARAMETER_COUNT,
 };
 use crate::domain::{NormalRealTimeTask, RealTimeProcessor};
 use crate::infrastructure::plugin::realearn_plugin_parameters::RealearnPluginParameters;
@@ -21,7 +22,7 @@ use assert_no_alloc::*;
 use lazycell::LazyCell;
 use reaper_high::{Reaper, ReaperGuard};
 use reaper_low::{reaper_vst_plugin, static_vst_plugin_context, PluginContext};
-use reaper_medium::{Hz, ReaperStr};
+use reaper_medium::{Hz, ProjectContext, ReaperStr};
 
 use slog::{debug, o};
 use std::cell::RefCell;
@@ -35,6 +36,7 @@ use std::sync::{Arc, Mutex};
 
 use crate::application::{Session, SharedSession};
 use crate::infrastructure::plugin::app::App;
+use crate::infrastructure::plugin::autosave;
 
 use crate::base::notification;
 use crate::infrastructure::server::http::keep_informing_clients_about_session_events;
@@ -51,6 +53,7 @@ const FEEDBACK_REAL_TIME_TASK_QUEUE_SIZE: usize = 2000;
 const NORMAL_REAL_TIME_TO_MAIN_TASK_QUEUE_SIZE: usize = 10_000;
 const CONTROL_MAIN_TASK_QUEUE_SIZE: usize = 5000;
 const PARAMETER_MAIN_TASK_QUEUE_SIZE: usize = 5000;
+const REAL_TIME_LOG_ENTRY_QUEUE_SIZE: usize = 1000;
 
 reaper_vst_plugin!();
 
@@ -80,6 +83,8 @@ pub struct RealearnPlugin {
     // Will be cloned to session as soon as it gets created.
     normal_rt_to_main_task_receiver: crossbeam_channel::Receiver<NormalRealTimeToMainThreadTask>,
     // Will be cloned to session as soon as it gets created.
+    real_time_log_entry_receiver: crossbeam_channel::Receiver<RealTimeLogEntry>,
+    // Will be cloned to session as soon as it gets created.
     parameter_main_task_receiver: crossbeam_channel::Receiver<ParameterMainTask>,
     // Will be cloned to session as soon as it gets created.
     normal_real_time_task_sender: SenderToRealTimeThread<NormalRealTimeTask>,
@@ -124,6 +129,11 @@ impl Plugin for RealearnPlugin {
                     "normal real-time to main tasks",
                     NORMAL_REAL_TIME_TO_MAIN_TASK_QUEUE_SIZE,
                 );
+            let (real_time_log_entry_sender, real_time_log_entry_receiver) =
+                SenderToNormalThread::new_bounded_channel(
+                    "real-time log entries",
+                    REAL_TIME_LOG_ENTRY_QUEUE_SIZE,
+                );
             let (control_main_task_sender, control_main_task_receiver) =
                 SenderToNormalThread::new_bounded_channel(
                     "control main tasks",
@@ -136,11 +146,14 @@ impl Plugin for RealearnPlugin {
                 );
             let instance_id = InstanceId::random();
             let logger = App::logger().new(o!("instance" => instance_id.to_string()));
-            let plugin_parameters =
-                Arc::new(RealearnPluginParameters::new(parameter_main_task_sender));
+            let plugin_parameters = Arc::new(RealearnPluginParameters::new(
+                host,
+                parameter_main_task_sender,
+            ));
             let real_time_processor = RealTimeProcessor::new(
                 instance_id,
                 &logger,
+                RealTimeLogger::new(real_time_log_entry_sender),
                 normal_real_time_task_receiver,
                 feedback_real_time_task_receiver,
                 feedback_real_time_task_sender.clone(),
@@ -169,6 +182,7 @@ impl Plugin for RealearnPlugin {
                 parameter_main_task_receiver,
                 control_main_task_receiver,
                 normal_rt_to_main_task_receiver,
+                real_time_log_entry_receiver,
                 was_playing_in_last_cycle: false,
                 sample_rate: Default::default(),
             }
@@ -398,6 +412,7 @@ impl RealearnPlugin {
         let control_main_task_receiver = self.control_main_task_receiver.clone();
         let parameter_main_task_receiver = self.parameter_main_task_receiver.clone();
         let normal_rt_to_main_task_receiver = self.normal_rt_to_main_task_receiver.clone();
+        let real_time_log_entry_receiver = self.real_time_log_entry_receiver.clone();
         let logger = self.logger.clone();
         let instance_id = self.instance_id;
         Global::task_support()
@@ -409,6 +424,12 @@ impl RealearnPlugin {
                         return;
                     }
                 };
+                let project_context = match processor_context.project() {
+                    None => ProjectContext::CurrentProject,
+                    Some(p) => p.context(),
+                };
+                normal_real_time_task_sender
+                    .send_complaining(NormalRealTimeTask::UpdateProject(project_context));
                 // Instance state (domain - shared)
                 let (instance_feedback_event_sender, instance_feedback_event_receiver) =
                     SenderToNormalThread::new_unbounded_channel("instance state change events");
@@ -458,6 +479,7 @@ impl RealearnPlugin {
                     normal_main_task_channel.0.clone(),
                     normal_main_task_channel.1,
                     normal_rt_to_main_task_receiver,
+                    real_time_log_entry_receiver,
                     parameter_main_task_receiver,
                     control_main_task_receiver,
                     instance_feedback_event_receiver,
@@ -479,8 +501,15 @@ impl RealearnPlugin {
                 );
                 shared_session.borrow_mut().activate(weak_session.clone());
                 main_panel.notify_session_is_available(weak_session.clone());
-                plugin_parameters.notify_session_is_available(weak_session);
+                let state_loaded = plugin_parameters.notify_session_is_available(weak_session);
+                autosave::keep_autosaving(&plugin_parameters);
                 shared_session.borrow().notify_realearn_instance_started();
+                App::get()
+                    .webhook_dispatcher()
+                    .notify_instance_created(&shared_session.borrow(), state_loaded);
+                if !state_loaded {
+                    App::get().auto_attach_controller(&shared_session);
+                }
                 // RealearnPlugin is the main owner of the session. Everywhere else the session is
                 // just temporarily upgraded, never stored as Rc, only as Weak.
                 session_container.fill(shared_session).unwrap();
@@ -621,6 +650,8 @@ impl Drop for RealearnPlugin {
         if let Some(session) = self.session.borrow() {
             App::get().unregister_processor_couple(self.instance_id);
             App::get().unregister_session(session.as_ptr());
+            // Proper shutdown, so the autosave is not needed for recovery anymore
+            autosave::discard_autosave(&self.plugin_parameters);
             debug!(
                 self.logger,
                 "{} pointers are still referring to this session",
@@ -1,3 +1,4 @@
+use crate::base::metrics_util::measure_time;
 use crate::base::{notification, NamedChannelSender, SendOrSyncWhatever, SenderToNormalThread};
 
 use lazycell::AtomicLazyCell;
@@ -10,18 +11,34 @@ use crate::domain::{
     Compartment, CompartmentParams, ParameterMainTask, PluginParamIndex, PluginParams,
     RawParamValue,
 };
-use crate::infrastructure::data::SessionData;
-use crate::infrastructure::plugin::App;
+use crate::infrastructure::data::{
+    import_legacy_session_data, is_legacy_session_data, SessionData,
+};
+use crate::infrastructure::plugin::{autosave, App};
+use derivative::Derivative;
 use reaper_medium::ProjectRef;
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use vst::plugin::PluginParameters;
+use vst::host::Host;
+use vst::plugin::{HostCallback, PluginParameters};
 
-#[derive(Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct RealearnPluginParameters {
+    /// Used for letting the host know that parameter names have changed. We only use it in the
+    /// main thread.
+    #[derivative(Debug = "ignore")]
+    host: SendOrSyncWhatever<HostCallback>,
     session: AtomicLazyCell<SendOrSyncWhatever<WeakSession>>,
     // We may have to cache some data that the host wants us to load because we are not ready
     // for loading data as long as the session is not available.
     data_to_be_loaded: RwLock<Option<Vec<u8>>>,
+    // Autosaved data that differs from the data loaded from the project. The user will be asked
+    // whether to restore it as soon as possible.
+    pending_autosave_recovery: RwLock<Option<SessionData>>,
+    /// File into which the state has been autosaved most recently.
+    autosave_file_path: RwLock<Option<PathBuf>>,
     parameter_main_task_sender: SenderToNormalThread<ParameterMainTask>,
     /// Canonical parameters.
     ///
@@ -32,16 +49,24 @@ pub struct RealearnPluginParameters {
 }
 
 impl RealearnPluginParameters {
-    pub fn new(parameter_main_task_channel: SenderToNormalThread<ParameterMainTask>) -> Self {
+    pub fn new(
+        host: HostCallback,
+        parameter_main_task_channel: SenderToNormalThread<ParameterMainTask>,
+    ) -> Self {
         Self {
+            host: unsafe { SendOrSyncWhatever::new(host) },
             session: AtomicLazyCell::new(),
             data_to_be_loaded: Default::default(),
+            pending_autosave_recovery: Default::default(),
+            autosave_file_path: Default::default(),
             parameter_main_task_sender: parameter_main_task_channel,
             params: Default::default(),
         }
     }
 
-    pub fn notify_session_is_available(&self, session: WeakSession) {
+    /// Returns `true` if the host had already passed data which has now been loaded into the
+    /// session (that is the case when loading a project but not when adding a fresh instance).
+    pub fn notify_session_is_available(&self, session: WeakSession) -> bool {
         // We will never access the session in another thread than the main thread because
         // REAPER calls the GetData/SetData functions in main thread only! So, Send or Sync,
         // whatever ... we don't care!
@@ -52,6 +77,12 @@ impl RealearnPluginParameters {
         if let Some(data) = guard.as_ref() {
             self.load_bank_data(data);
             *guard = None;
+            // Only on project load. Undo/redo and preset loads also pass data to
+            // `load_bank_data`, but it's not the right moment to offer crash recovery then.
+            self.check_for_autosave();
+            true
+        } else {
+            false
         }
     }
 
@@ -73,6 +104,55 @@ impl RealearnPluginParameters {
         self.apply_session_data_internal(&session_data);
     }
 
+    /// Returns the ID of the session if the session is already available.
+    pub fn session_id(&self) -> Option<String> {
+        let session = self.session()?;
+        let session = session.borrow();
+        Some(session.id().to_owned())
+    }
+
+    /// Returns the file of the project which contains this instance (if the project is saved).
+    pub fn project_file(&self) -> Option<PathBuf> {
+        let session = self.session()?;
+        let session = session.borrow();
+        session.processor_context().project()?.file()
+    }
+
+    pub fn take_pending_autosave_recovery(&self) -> Option<SessionData> {
+        self.pending_autosave_recovery.write().unwrap().take()
+    }
+
+    pub fn autosave_file_path(&self) -> Option<PathBuf> {
+        self.autosave_file_path.read().unwrap().clone()
+    }
+
+    /// Returns the previous autosave file path.
+    pub fn set_autosave_file_path(&self, path: Option<PathBuf>) -> Option<PathBuf> {
+        std::mem::replace(&mut *self.autosave_file_path.write().unwrap(), path)
+    }
+
+    /// Checks whether there's autosaved data which is more recent than the data which has just
+    /// been loaded from the project (indicates a crash).
+    fn check_for_autosave(&self) {
+        let session_id = match self.session_id() {
+            None => return,
+            Some(id) => id,
+        };
+        let current_content = match serde_json::to_string(&self.create_session_data_internal()) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let project_file = self.project_file();
+        if let Some(data) =
+            autosave::load_newer_autosave(&session_id, project_file.as_deref(), &current_content)
+        {
+            self.pending_autosave_recovery
+                .write()
+                .unwrap()
+                .replace(data);
+        }
+    }
+
     fn create_session_data_internal(&self) -> SessionData {
         let session = self.session().expect("session gone");
         let session = session.borrow();
@@ -80,6 +160,20 @@ impl RealearnPluginParameters {
         SessionData::from_model(&session, &params)
     }
 
+    /// Applies session data passed by the host, e.g. on project load.
+    fn load_session_data(&self, session_data: &SessionData) {
+        // When many instances are loaded at once (e.g. on project load), resolving all targets and
+        // sending the feedback of all mappings right away causes stalls. Hold back what's not
+        // needed for control and catch up when it's our turn.
+        if let Some(session) = self.session() {
+            let delay = App::get().reserve_deferred_feedback_slot();
+            session.borrow().defer_initial_feedback(delay);
+        }
+        measure_time("plugin_parameters.load_session_data", || {
+            self.apply_session_data_internal(session_data);
+        });
+    }
+
     fn apply_session_data_internal(&self, session_data: &SessionData) {
         // Update session
         let shared_session = self.session().expect("session should exist already");
@@ -97,6 +191,8 @@ impl RealearnPluginParameters {
                 ));
             }
         }
+        // Applying the data changes lots of things, each of which could trigger a mapping sync.
+        session.begin_bulk_edit();
         let params = session_data.create_params();
         if let Err(e) = session_data.apply_to_model(&mut session, &params) {
             notification::warn(e.to_string());
@@ -107,6 +203,16 @@ impl RealearnPluginParameters {
         *self.params_mut() = params;
         // Notify
         session.notify_everything_has_changed();
+        session.end_bulk_edit(Rc::downgrade(&shared_session));
+        self.notify_host_about_changed_param_names();
+    }
+
+    /// Makes the host query the parameter names again.
+    ///
+    /// Must not be called while holding the parameter lock because the host will call
+    /// `get_parameter_name` synchronously.
+    fn notify_host_about_changed_param_names(&self) {
+        self.host.update_display();
     }
 
     fn session(&self) -> Option<SharedSession> {
@@ -188,14 +294,12 @@ impl PluginParameters for RealearnPluginParameters {
                     .replace(data.to_vec());
                 return;
             }
-            let left_json_object_brace = data
-                .iter()
-                .position(|b| *b == 0x7b)
-                .expect("couldn't find left JSON brace in bank data");
-            // ReaLearn C++ saved some IPlug binary data in front of the actual JSON object. Find
-            // start of JSON data.
-            let data = &data[left_json_object_brace..];
-            let session_data: SessionData = match serde_json::from_slice(data) {
+            let session_data = if is_legacy_session_data(data) {
+                import_legacy_session_data(data)
+            } else {
+                serde_json::from_slice(data).map_err(|e| e.into())
+            };
+            let session_data: SessionData = match session_data {
                 Ok(d) => d,
                 Err(e) => {
                     panic!(
@@ -205,7 +309,7 @@ impl PluginParameters for RealearnPluginParameters {
                     )
                 }
             };
-            self.apply_session_data_internal(&session_data);
+            self.load_session_data(&session_data);
         });
     }
 
@@ -279,12 +383,19 @@ impl PluginParameters for RealearnPluginParameters {
 
 impl ParamContainer for Arc<RealearnPluginParameters> {
     fn update_compartment_params(&mut self, compartment: Compartment, params: CompartmentParams) {
-        let mut plugin_params = self.params_mut();
-        let compartment_params = plugin_params.compartment_params_mut(compartment);
-        *compartment_params = params;
-        // Propagate
-        self.parameter_main_task_sender
-            .send_complaining(ParameterMainTask::UpdateAllParams(plugin_params.clone()));
+        let names_changed = {
+            let mut plugin_params = self.params_mut();
+            let compartment_params = plugin_params.compartment_params_mut(compartment);
+            let names_changed = !compartment_params.has_same_names_as(&params);
+            *compartment_params = params;
+            // Propagate
+            self.parameter_main_task_sender
+                .send_complaining(ParameterMainTask::UpdateAllParams(plugin_params.clone()));
+            names_changed
+        };
+        if names_changed {
+            self.notify_host_about_changed_param_names();
+        }
     }
 }
 
@@ -0,0 +1,144 @@
+use crate::base::{notification, Global};
+use crate::infrastructure::plugin::App;
+use hyper::{Body, Client, Method, Request};
+use reaper_high::Reaper;
+use semver::Version;
+use serde::Deserialize;
+use slog::debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Describes the latest stable ReaLearn release (pre-releases are not included).
+const RELEASE_FEED_URL: &str = "https://api.github.com/repos/helgoboss/realearn/releases/latest";
+
+/// Who asked for the update check.
+///
+/// Checks at startup stay silent if there's no update or the check fails. Checks initiated by
+/// the user always report the outcome.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UpdateCheckTrigger {
+    Startup,
+    User,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    /// The changelog (Markdown).
+    #[serde(default)]
+    body: String,
+}
+
+impl Release {
+    fn version(&self) -> Result<Version, String> {
+        let version_string = self.tag_name.trim_start_matches('v');
+        Version::parse(version_string)
+            .map_err(|_| format!("release has invalid version {}", self.tag_name))
+    }
+}
+
+/// Checks for an update in the background if the user enabled this in the app configuration.
+///
+/// Checks at most once per REAPER session, even if ReaLearn goes to sleep and wakes up again.
+pub fn check_for_update_at_startup_if_enabled() {
+    static ALREADY_CHECKED: AtomicBool = AtomicBool::new(false);
+    if !App::get().config().update_check_is_enabled() {
+        return;
+    }
+    if ALREADY_CHECKED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    check_for_update(UpdateCheckTrigger::Startup);
+}
+
+/// Queries the release feed in a separate thread and reports the result in the main thread.
+///
+/// Doesn't block. If there's a newer version, its changelog and download link are shown in the
+/// REAPER console.
+pub fn check_for_update(trigger: UpdateCheckTrigger) {
+    let spawn_result = std::thread::Builder::new()
+        .name("ReaLearn update check".to_string())
+        .spawn(move || {
+            let result = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| format!("couldn't create runtime: {}", e))
+                .and_then(|runtime| runtime.block_on(fetch_latest_release()));
+            // Fails only if ReaLearn is being shut down, in which case nobody is interested in
+            // the result anymore.
+            let _ = Global::task_support()
+                .do_later_in_main_thread_asap(move || process_release(result, trigger));
+        });
+    if spawn_result.is_err() {
+        report_failure("couldn't start update check thread".to_string(), trigger);
+    }
+}
+
+fn process_release(result: Result<Release, String>, trigger: UpdateCheckTrigger) {
+    let release = match result {
+        Ok(r) => r,
+        Err(msg) => {
+            report_failure(msg, trigger);
+            return;
+        }
+    };
+    let latest_version = match release.version() {
+        Ok(v) => v,
+        Err(msg) => {
+            report_failure(msg, trigger);
+            return;
+        }
+    };
+    let current_version = App::version();
+    if &latest_version > current_version {
+        let msg = format!(
+            "\n\nReaLearn {} is available (you are using {}).\n\nDownload: {}\n\nChangelog:\n\n{}\n\n",
+            latest_version,
+            current_version,
+            release.html_url,
+            release.body.trim()
+        );
+        Reaper::get().show_console_msg(msg);
+    } else if trigger == UpdateCheckTrigger::User {
+        notification::alert(format!(
+            "You are using the latest version of ReaLearn ({}).",
+            current_version
+        ));
+    }
+}
+
+fn report_failure(msg: String, trigger: UpdateCheckTrigger) {
+    match trigger {
+        UpdateCheckTrigger::Startup => {
+            debug!(App::logger(), "Update check failed: {}", msg);
+        }
+        UpdateCheckTrigger::User => {
+            notification::alert(format!("Couldn't check for updates: {}", msg));
+        }
+    }
+}
+
+async fn fetch_latest_release() -> Result<Release, String> {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_webpki_roots()
+        .https_only()
+        .enable_http1()
+        .build();
+    let client: Client<_, Body> = Client::builder().build(connector);
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(RELEASE_FEED_URL)
+        // GitHub rejects requests without user agent.
+        .header("user-agent", format!("ReaLearn/{}", App::version()))
+        .header("accept", "application/vnd.github+json")
+        .body(Body::empty())
+        .map_err(|e| e.to_string())?;
+    let response = client.request(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("release feed responded with {}", response.status()));
+    }
+    let bytes = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("release feed is invalid: {}", e))
+}
@@ -1,23 +1,44 @@
 //! Contains the actual application interface and implementation without any HTTP-specific stuff.
 
 use crate::application::{
-    ControllerPreset, Preset, PresetManager, Session, SourceCategory, TargetCategory,
+    ControllerPreset, MappingIssue, MappingValidationContext, Preset, PresetManager, Session,
+    SourceCategory, TargetCategory,
+};
+use crate::domain::{
+    BackboneState, Compartment, CompoundMappingTarget, ControlElementTouchedEvent, DiagnosticEvent,
+    MappingKey, NotificationContext, NotificationLogEntry, NotificationSeverity,
+    ProjectionFeedbackValue, QualifiedMappingId, RealearnTarget, ReaperTarget,
+    VirtualControlElement, XyPadRole,
+};
+use crate::infrastructure::data::{
+    ControllerPresetData, ControlsDocumentation, LintSubject, PresetData,
 };
-use crate::domain::{BackboneState, Compartment, MappingKey, ProjectionFeedbackValue};
-use crate::infrastructure::data::{ControllerPresetData, PresetData};
 use crate::infrastructure::plugin::App;
-use helgoboss_learn::UnitValue;
+use helgoboss_learn::{ControlValue, UnitValue};
 use maplit::hashmap;
+use qrcode::QrCode;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::rc::Rc;
+use std::time::UNIX_EPOCH;
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 // Right now just a placeholder
 pub struct SessionResponseData {}
 
+/// Everything the Companion app needs to know in order to connect to a particular session.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingResponseData {
+    host: String,
+    http_port: u16,
+    https_port: u16,
+    session_id: String,
+    companion_app_url: String,
+}
+
 pub enum DataError {
     SessionNotFound,
     SessionHasNoActiveController,
@@ -26,6 +47,9 @@ pub enum DataError {
     OnlyCustomDataKeyIsSupportedAsPatchPath,
     ControllerUpdateFailed,
     ClipMatrixNotFound,
+    ServerHostUnknown,
+    QrCodeGenerationFailed,
+    ControlsDocumentationRenderingFailed,
 }
 
 pub enum DataErrorCategory {
@@ -48,6 +72,9 @@ impl DataError {
             }
             ControllerUpdateFailed => "couldn't update controller",
             ClipMatrixNotFound => "clip matrix not found",
+            ServerHostUnknown => "local IP address of server could not be determined",
+            QrCodeGenerationFailed => "couldn't generate QR code",
+            ControlsDocumentationRenderingFailed => "couldn't render controls documentation",
         }
     }
 
@@ -60,7 +87,10 @@ impl DataError {
             | ClipMatrixNotFound => DataErrorCategory::NotFound,
             OnlyPatchReplaceIsSupported => DataErrorCategory::MethodNotAllowed,
             OnlyCustomDataKeyIsSupportedAsPatchPath => DataErrorCategory::BadRequest,
-            ControllerUpdateFailed => DataErrorCategory::InternalServerError,
+            ControllerUpdateFailed
+            | ServerHostUnknown
+            | QrCodeGenerationFailed
+            | ControlsDocumentationRenderingFailed => DataErrorCategory::InternalServerError,
         }
     }
 }
@@ -83,6 +113,19 @@ enum PatchRequestOp {
 pub struct ControllerRouting {
     main_preset: Option<LightMainPresetData>,
     routes: HashMap<MappingKey, Vec<TargetDescriptor>>,
+    /// Lets projection clients render the controller mappings of an XY pad as one pad.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    xy_pads: Vec<XyPadDescriptor>,
+}
+
+/// Controller mappings which make up an XY pad (see `XyPadRole`).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct XyPadDescriptor {
+    name: String,
+    x: Option<MappingKey>,
+    y: Option<MappingKey>,
+    touch: Option<MappingKey>,
 }
 
 #[derive(Serialize)]
@@ -98,6 +141,121 @@ struct TargetDescriptor {
     label: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveTargetsResponseData {
+    mappings: Vec<MappingEffectiveTargets>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingEffectiveTargets {
+    compartment: Compartment,
+    key: MappingKey,
+    name: String,
+    is_on: bool,
+    /// Empty if the target couldn't be resolved at the moment.
+    targets: Vec<EffectiveTargetDescriptor>,
+}
+
+/// Describes a concrete target as it's resolved right now (with the current project state).
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EffectiveTargetDescriptor {
+    r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    control_element: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_guid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fx_guid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fx_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    param_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    param_name: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingValidationResponseData {
+    /// Contains only mappings which have issues.
+    mappings: Vec<MappingValidationEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingValidationEntry {
+    compartment: Compartment,
+    key: MappingKey,
+    name: String,
+    issues: Vec<MappingIssue>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingStatisticsResponseData {
+    mappings: Vec<MappingStatisticsEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingStatisticsEntry {
+    compartment: Compartment,
+    key: MappingKey,
+    name: String,
+    is_on: bool,
+    control_event_count: u64,
+    /// Milliseconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_matched: Option<u64>,
+    /// Normalized target value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_feedback_value: Option<f64>,
+    /// Total time spent controlling this mapping in microseconds (only if mapping metering is
+    /// enabled).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processing_time_micros: Option<u64>,
+}
+
+impl EffectiveTargetDescriptor {
+    fn from_target(target: &CompoundMappingTarget) -> Self {
+        match target {
+            CompoundMappingTarget::Virtual(t) => Self {
+                r#type: "Virtual".to_string(),
+                control_element: Some(t.control_element().to_string()),
+                ..Default::default()
+            },
+            CompoundMappingTarget::Reaper(t) => {
+                let track = t.track();
+                let fx = t.fx();
+                let param = match t {
+                    ReaperTarget::FxParameter(t) => Some(&t.param),
+                    _ => None,
+                };
+                Self {
+                    r#type: t
+                        .reaper_target_type()
+                        .map(|t| t.definition().name.to_string())
+                        .unwrap_or_default(),
+                    control_element: None,
+                    track_guid: track.map(|t| t.guid().to_string_without_braces()),
+                    track_name: track.and_then(|t| t.name()).map(|n| n.into_string()),
+                    fx_guid: fx
+                        .and_then(|fx| fx.guid())
+                        .map(|g| g.to_string_without_braces()),
+                    fx_name: fx.map(|fx| fx.name().into_string()),
+                    param_index: param.map(|p| p.index()),
+                    param_name: param.map(|p| p.name().into_string()),
+                }
+            }
+        }
+    }
+}
+
 pub fn get_session_data(session_id: String) -> Result<SessionResponseData, DataError> {
     let _ = App::get()
         .find_session_by_id(&session_id)
@@ -105,6 +263,37 @@ pub fn get_session_data(session_id: String) -> Result<SessionResponseData, DataE
     Ok(SessionResponseData {})
 }
 
+pub fn get_pairing_data(session_id: String) -> Result<PairingResponseData, DataError> {
+    let _ = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let server = App::get().server().borrow();
+    let host = server.local_ip().ok_or(DataError::ServerHostUnknown)?;
+    Ok(PairingResponseData {
+        host: host.to_string(),
+        http_port: server.http_port(),
+        https_port: server.https_port(),
+        companion_app_url: server.generate_full_companion_app_url(&session_id, false),
+        session_id,
+    })
+}
+
+/// Returns a PNG image of a QR code which lets the Companion app connect to the given session.
+pub fn get_pairing_qr_code_png(session_id: String) -> Result<Vec<u8>, DataError> {
+    let pairing_data = get_pairing_data(session_id)?;
+    let code = QrCode::new(&pairing_data.companion_app_url)
+        .map_err(|_| DataError::QrCodeGenerationFailed)?;
+    let image = code
+        .render::<image::Luma<u8>>()
+        .min_dimensions(250, 250)
+        .build();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut png, image::ImageOutputFormat::PNG)
+        .map_err(|_| DataError::QrCodeGenerationFailed)?;
+    Ok(png)
+}
+
 pub fn get_clip_matrix_data(
     session_id: &str,
 ) -> Result<playtime_api::persistence::Matrix, DataError> {
@@ -127,6 +316,65 @@ pub fn get_controller_routing_by_session_id(
     Ok(routing)
 }
 
+pub fn get_effective_targets_by_session_id(
+    session_id: String,
+) -> Result<EffectiveTargetsResponseData, DataError> {
+    let session = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let data = get_effective_targets(&session.borrow());
+    Ok(data)
+}
+
+pub fn get_mapping_statistics_by_session_id(
+    session_id: String,
+) -> Result<MappingStatisticsResponseData, DataError> {
+    let session = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let data = get_mapping_statistics(&session.borrow());
+    Ok(data)
+}
+
+pub fn get_mapping_validation_by_session_id(
+    session_id: String,
+) -> Result<MappingValidationResponseData, DataError> {
+    let session = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let data = get_mapping_validation(&session.borrow());
+    Ok(data)
+}
+
+#[derive(Deserialize)]
+pub struct ControlsDocumentationQuery {
+    #[serde(default = "default_documented_compartment")]
+    compartment: Compartment,
+}
+
+fn default_documented_compartment() -> Compartment {
+    Compartment::Main
+}
+
+pub fn get_controls_documentation_by_session_id(
+    session_id: String,
+    query: ControlsDocumentationQuery,
+) -> Result<String, DataError> {
+    let session = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let session = session.borrow();
+    ControlsDocumentation::from_session(&session, query.compartment)
+        .render_as_html()
+        .map_err(|_| DataError::ControlsDocumentationRenderingFailed)
+}
+
+#[derive(Deserialize)]
+pub struct LintQuery {
+    #[serde(default)]
+    pub kind: LintSubject,
+}
+
 pub fn get_controller_preset_data(session_id: String) -> Result<ControllerPresetData, DataError> {
     let session = App::get()
         .find_session_by_id(&session_id)
@@ -187,7 +435,135 @@ pub fn get_controller_routing(session: &Session) -> ControllerRouting {
     ControllerRouting {
         main_preset,
         routes,
+        xy_pads: get_xy_pads(session),
+    }
+}
+
+fn get_xy_pads(session: &Session) -> Vec<XyPadDescriptor> {
+    let mut pads: Vec<XyPadDescriptor> = vec![];
+    for m in session.mappings(Compartment::Controller) {
+        let m = m.borrow();
+        if !m.visible_in_projection() || m.target_model.category() != TargetCategory::Virtual {
+            continue;
+        }
+        let Some((name, role)) = XyPadRole::parse(m.target_model.create_control_element()) else {
+            continue;
+        };
+        let pad = match pads.iter().position(|p| p.name == name) {
+            None => {
+                pads.push(XyPadDescriptor {
+                    name,
+                    x: None,
+                    y: None,
+                    touch: None,
+                });
+                pads.last_mut().unwrap()
+            }
+            Some(i) => &mut pads[i],
+        };
+        let key = Some(m.key().clone());
+        match role {
+            XyPadRole::X => pad.x = key,
+            XyPadRole::Y => pad.y = key,
+            XyPadRole::Touch => pad.touch = key,
+        }
+    }
+    pads
+}
+
+/// Resolves the targets of all mappings in both compartments as they are right now.
+pub fn get_effective_targets(session: &Session) -> EffectiveTargetsResponseData {
+    let mappings = Compartment::enum_iter()
+        .flat_map(|compartment| session.mappings(compartment))
+        .map(|m| {
+            let m = m.borrow();
+            let targets = m
+                .target_model
+                .with_context(session.extended_context(), m.compartment())
+                .resolve()
+                .unwrap_or_default();
+            MappingEffectiveTargets {
+                compartment: m.compartment(),
+                key: m.key().clone(),
+                name: m.effective_name(),
+                is_on: session
+                    .instance_state()
+                    .borrow()
+                    .mapping_is_on(m.qualified_id()),
+                targets: targets
+                    .iter()
+                    .map(EffectiveTargetDescriptor::from_target)
+                    .collect(),
+            }
+        })
+        .collect();
+    EffectiveTargetsResponseData { mappings }
+}
+
+/// Collects the usage statistics of all mappings in both compartments.
+///
+/// Mappings which have never been used are included as well, that's the whole point.
+pub fn get_mapping_statistics(session: &Session) -> MappingStatisticsResponseData {
+    let instance_state = session.instance_state().borrow();
+    let mappings = Compartment::enum_iter()
+        .flat_map(|compartment| session.mappings(compartment))
+        .map(|m| {
+            let m = m.borrow();
+            let stats = instance_state
+                .mapping_statistics(m.qualified_id())
+                .cloned()
+                .unwrap_or_default();
+            MappingStatisticsEntry {
+                compartment: m.compartment(),
+                key: m.key().clone(),
+                name: m.effective_name(),
+                is_on: instance_state.mapping_is_on(m.qualified_id()),
+                control_event_count: stats.control_event_count,
+                last_matched: stats
+                    .last_matched
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as u64),
+                last_feedback_value: stats.last_feedback_value.map(|v| v.to_unit_value().get()),
+                processing_time_micros: if stats.processing_time.count > 0 {
+                    Some(stats.processing_time.total.as_micros() as u64)
+                } else {
+                    None
+                },
+            }
+        })
+        .collect();
+    MappingStatisticsResponseData { mappings }
+}
+
+pub fn get_mapping_validation(session: &Session) -> MappingValidationResponseData {
+    let instance_state = session.instance_state().borrow();
+    let mut mappings = vec![];
+    for compartment in Compartment::enum_iter() {
+        let group_ids: HashSet<_> = session
+            .groups(compartment)
+            .map(|g| g.borrow().id())
+            .collect();
+        let context = MappingValidationContext {
+            instance_state: Some(&*instance_state),
+            group_ids: Some(&group_ids),
+            compartment_mappings: Some(session.mapping_slice(compartment)),
+        };
+        for m in session.mappings(compartment) {
+            let m = m.borrow();
+            let issues = m.validate(context);
+            if issues.is_empty() {
+                continue;
+            }
+            let entry = MappingValidationEntry {
+                compartment,
+                key: m.key().clone(),
+                name: m.effective_name(),
+                issues,
+            };
+            mappings.push(entry);
+        }
     }
+    MappingValidationResponseData { mappings }
 }
 
 pub fn patch_controller(controller_id: String, req: PatchRequest) -> Result<(), DataError> {
@@ -250,6 +626,7 @@ pub enum Topic {
     ActiveController { session_id: String },
     ControllerRouting { session_id: String },
     Feedback { session_id: String },
+    Diagnostics { session_id: String },
 }
 
 impl TryFrom<&str> for Topic {
@@ -267,6 +644,9 @@ impl TryFrom<&str> for Topic {
             ["realearn", "session", id, "feedback"] => Topic::Feedback {
                 session_id: id.to_string(),
             },
+            ["realearn", "session", id, "diagnostics"] => Topic::Diagnostics {
+                session_id: id.to_string(),
+            },
             ["realearn", "session", id] => Topic::Session {
                 session_id: id.to_string(),
             },
@@ -304,6 +684,87 @@ pub fn get_projection_feedback_event(
     )
 }
 
+pub fn get_control_element_touched_event(
+    session_id: &str,
+    event: ControlElementTouchedEvent,
+) -> Event<ControlElementTouchedData> {
+    let (element_kind, element_id) = match event.control_element {
+        VirtualControlElement::Multi(id) => (ControlElementKind::Multi, id),
+        VirtualControlElement::Button(id) => (ControlElementKind::Button, id),
+        VirtualControlElement::Display(id) => (ControlElementKind::Display, id),
+    };
+    let (value, increment) = match event.value {
+        ControlValue::RelativeDiscrete(i) => (None, Some(i.get())),
+        ControlValue::RelativeContinuous(i) => (None, Some(i.to_discrete_increment().get())),
+        v => (v.to_unit_value().ok(), None),
+    };
+    let data = ControlElementTouchedData {
+        mapping_key: event.mapping_key,
+        element_kind,
+        element_id: element_id.to_string(),
+        value,
+        increment,
+    };
+    Event::patch(
+        format!("/realearn/session/{}/control-element-touched", session_id),
+        data,
+    )
+}
+
+pub fn get_diagnostic_event(
+    session: &Session,
+    event: DiagnosticEvent,
+) -> Event<DiagnosticEventData> {
+    let body = match event {
+        DiagnosticEvent::Notified(entry) => get_notification_data(session, entry),
+        DiagnosticEvent::RealTimeOverrunsDetected(overruns) => {
+            DiagnosticEventData::RealTimeOverruns {
+                count: overruns.count,
+                max_load_percent: overruns.max_load_percent,
+            }
+        }
+        DiagnosticEvent::FeedbackSimulated(msg) => DiagnosticEventData::SimulatedFeedback {
+            message: msg.to_string(),
+        },
+    };
+    Event::patch(
+        format!("/realearn/session/{}/diagnostics", session.id()),
+        body,
+    )
+}
+
+fn get_notification_data(session: &Session, entry: &NotificationLogEntry) -> DiagnosticEventData {
+    let notification = &entry.notification;
+    let mapping = match notification.context {
+        NotificationContext::Instance => None,
+        NotificationContext::Mapping(id) => get_mapping_descriptor(session, id),
+    };
+    let severity = match notification.severity {
+        NotificationSeverity::Warning => DiagnosticSeverity::Warning,
+        NotificationSeverity::Error => DiagnosticSeverity::Error,
+    };
+    DiagnosticEventData::Notification {
+        severity,
+        mapping,
+        message: notification.msg.to_string(),
+        occurrence_count: entry.occurrence_count,
+    }
+}
+
+fn get_mapping_descriptor(
+    session: &Session,
+    id: QualifiedMappingId,
+) -> Option<DiagnosticMappingDescriptor> {
+    let (_, mapping) = session.find_mapping_and_index_by_qualified_id(id)?;
+    let mapping = mapping.borrow();
+    let descriptor = DiagnosticMappingDescriptor {
+        compartment: id.compartment,
+        key: mapping.key().clone(),
+        name: mapping.effective_name(),
+    };
+    Some(descriptor)
+}
+
 pub fn get_session_updated_event(
     session_id: &str,
     session_data: Option<SessionResponseData>,
@@ -321,6 +782,69 @@ pub fn get_controller_routing_updated_event(
     )
 }
 
+/// Pushed whenever a control element has been touched so that the projection can highlight it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlElementTouchedData {
+    /// Key of the controller mapping which represents the control element.
+    mapping_key: Rc<str>,
+    element_kind: ControlElementKind,
+    element_id: String,
+    /// Set if the control element sent an absolute value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<UnitValue>,
+    /// Set if the control element sent a relative value (e.g. an encoder).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    increment: Option<i32>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlElementKind {
+    Multi,
+    Button,
+    Display,
+}
+
+/// A warning, error or health event of a ReaLearn instance, streamed via the diagnostics topic.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DiagnosticEventData {
+    #[serde(rename_all = "camelCase")]
+    Notification {
+        severity: DiagnosticSeverity,
+        /// `None` if the notification concerns the instance as a whole.
+        mapping: Option<DiagnosticMappingDescriptor>,
+        message: String,
+        /// How often this problem occurred since it has been added to the notification log.
+        occurrence_count: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    RealTimeOverruns {
+        /// Number of new overruns since the last report.
+        count: u32,
+        max_load_percent: u32,
+    },
+    /// Feedback which would have been sent if a feedback output was selected.
+    #[serde(rename_all = "camelCase")]
+    SimulatedFeedback { message: String },
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticMappingDescriptor {
+    compartment: Compartment,
+    key: MappingKey,
+    name: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Event<T> {
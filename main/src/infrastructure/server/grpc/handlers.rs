@@ -16,9 +16,9 @@ use playtime_clip_engine::proto::{
     GetOccasionalTrackUpdatesRequest, OccasionalMatrixUpdate, OccasionalTrackUpdate,
     QualifiedOccasionalSlotUpdate, QualifiedOccasionalTrackUpdate, SetClipDataRequest,
     SetClipNameRequest, SetColumnVolumeRequest, SetMatrixPanRequest, SetMatrixTempoRequest,
-    SetMatrixVolumeRequest, SlotAddress, TriggerColumnAction, TriggerColumnRequest,
-    TriggerMatrixAction, TriggerMatrixRequest, TriggerRowAction, TriggerRowRequest,
-    TriggerSlotAction, TriggerSlotRequest,
+    SetMatrixVolumeRequest, SetRowDataRequest, SlotAddress, TriggerColumnAction,
+    TriggerColumnRequest, TriggerMatrixAction, TriggerMatrixRequest, TriggerRowAction,
+    TriggerRowRequest, TriggerSlotAction, TriggerSlotRequest,
 };
 use playtime_clip_engine::rt::ColumnPlayClipOptions;
 use reaper_high::{GroupingBehavior, Guid, OrCurrentProject, Pan, Reaper, Tempo, Track, Volume};
@@ -365,6 +365,19 @@ impl clip_engine_server::ClipEngine for RealearnClipEngine {
         })
     }
 
+    async fn set_row_data(
+        &self,
+        request: Request<SetRowDataRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let req = request.into_inner();
+        let row =
+            serde_json::from_str(&req.data).map_err(|e| Status::invalid_argument(e.to_string()))?;
+        handle_row_command(&req.row_address, |matrix, row_index| {
+            matrix.set_row_data(row_index, row);
+            Ok(())
+        })
+    }
+
     async fn set_matrix_tempo(
         &self,
         request: Request<SetMatrixTempoRequest>,
@@ -1,15 +1,19 @@
 use crate::base::Global;
-use crate::infrastructure::data::ControllerPresetData;
+use crate::infrastructure::data::{lint_json, ControllerPresetData, LintReport};
 use crate::infrastructure::server::data::{
     get_clip_matrix_data, get_controller_preset_data, get_controller_routing_by_session_id,
-    patch_controller, ControllerRouting, DataError, DataErrorCategory, PatchRequest,
-    SessionResponseData, Topics,
+    get_controls_documentation_by_session_id, get_effective_targets_by_session_id,
+    get_mapping_statistics_by_session_id, get_mapping_validation_by_session_id, get_pairing_data,
+    get_pairing_qr_code_png, patch_controller, ControllerRouting, ControlsDocumentationQuery,
+    DataError, DataErrorCategory, EffectiveTargetsResponseData, LintQuery,
+    MappingStatisticsResponseData, MappingValidationResponseData, PairingResponseData,
+    PatchRequest, SessionResponseData, Topics,
 };
 use crate::infrastructure::server::http::{send_initial_events, ServerClients, WebSocketClient};
 use crate::infrastructure::server::MetricsReporter;
 use axum::body::{boxed, Body, BoxBody};
 use axum::extract::ws::{Message, WebSocket};
-use axum::extract::Path;
+use axum::extract::{ContentLengthLimit, Path, Query};
 use axum::http::{Response, StatusCode};
 use axum::response::Html;
 use axum::Json;
@@ -32,6 +36,27 @@ pub async fn session_handler(
     Ok(Json(session_data))
 }
 
+/// Needs to be executed in the main thread!
+pub async fn pairing_handler(
+    Path(session_id): Path<String>,
+) -> Result<Json<PairingResponseData>, SimpleResponse> {
+    let pairing_data = get_pairing_data(session_id).map_err(translate_data_error)?;
+    Ok(Json(pairing_data))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn pairing_qr_code_handler(
+    Path(session_id): Path<String>,
+) -> Result<Response<BoxBody>, SimpleResponse> {
+    let png = get_pairing_qr_code_png(session_id).map_err(translate_data_error)?;
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "image/png")
+        .body(boxed(Body::from(png)))
+        .unwrap();
+    Ok(response)
+}
+
 /// Needs to be executed in the main thread!
 pub async fn clip_matrix_handler(
     Path(session_id): Path<String>,
@@ -57,6 +82,58 @@ pub async fn controller_routing_handler(
     Ok(Json(controller_routing))
 }
 
+/// Needs to be executed in the main thread!
+pub async fn effective_targets_handler(
+    Path(session_id): Path<String>,
+) -> Result<Json<EffectiveTargetsResponseData>, SimpleResponse> {
+    let effective_targets =
+        get_effective_targets_by_session_id(session_id).map_err(translate_data_error)?;
+    Ok(Json(effective_targets))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn mapping_statistics_handler(
+    Path(session_id): Path<String>,
+) -> Result<Json<MappingStatisticsResponseData>, SimpleResponse> {
+    let stats = get_mapping_statistics_by_session_id(session_id).map_err(translate_data_error)?;
+    Ok(Json(stats))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn mapping_validation_handler(
+    Path(session_id): Path<String>,
+) -> Result<Json<MappingValidationResponseData>, SimpleResponse> {
+    let data = get_mapping_validation_by_session_id(session_id).map_err(translate_data_error)?;
+    Ok(Json(data))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn controls_documentation_handler(
+    Path(session_id): Path<String>,
+    Query(query): Query<ControlsDocumentationQuery>,
+) -> Result<Html<String>, SimpleResponse> {
+    let html = get_controls_documentation_by_session_id(session_id, query)
+        .map_err(translate_data_error)?;
+    Ok(Html(html))
+}
+
+/// Maximum size of a document to be linted (in bytes).
+///
+/// Linting blocks the main thread, so this also limits how long it can take.
+pub const MAX_LINT_BODY_SIZE: u64 = 5_000_000;
+
+/// Needs to be executed in the main thread! Linting converts the mappings to models in order to
+/// run the same checks as after loading them, and converting action targets looks up the actions
+/// in REAPER.
+///
+/// Takes the document as plain text (not as JSON) so that schema errors end up in the report.
+pub async fn lint_handler(
+    Query(query): Query<LintQuery>,
+    ContentLengthLimit(body): ContentLengthLimit<String, MAX_LINT_BODY_SIZE>,
+) -> Json<LintReport> {
+    Json(lint_json(query.kind, &body))
+}
+
 /// Needs to be executed in the main thread!
 pub async fn patch_controller_handler(
     Path(controller_id): Path<String>,
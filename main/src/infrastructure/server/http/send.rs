@@ -1,12 +1,12 @@
 //! Contains functions for sending data to WebSocket clients.
 use crate::application::{Session, SharedSession};
 use crate::base::{when, Global};
-use crate::domain::ProjectionFeedbackValue;
+use crate::domain::{ControlElementTouchedEvent, DiagnosticEvent, ProjectionFeedbackValue};
 use crate::infrastructure::plugin::App;
 use crate::infrastructure::server::data::{
-    get_active_controller_updated_event, get_controller_routing_updated_event,
-    get_projection_feedback_event, get_session_updated_event, send_initial_feedback,
-    SessionResponseData, Topic,
+    get_active_controller_updated_event, get_control_element_touched_event,
+    get_controller_routing_updated_event, get_diagnostic_event, get_projection_feedback_event,
+    get_session_updated_event, send_initial_feedback, SessionResponseData, Topic,
 };
 use crate::infrastructure::server::http::client::WebSocketClient;
 use rxrust::prelude::*;
@@ -32,6 +32,7 @@ fn send_initial_events_for_topic(
             send_initial_feedback(session_id);
             Ok(())
         }
+        Diagnostics { session_id } => send_initial_diagnostics(client, session_id),
     }
 }
 
@@ -68,6 +69,24 @@ fn send_initial_controller(client: &WebSocketClient, session_id: &str) -> Result
     client.send(&event)
 }
 
+/// Sends the notifications which are currently in the log, oldest first.
+fn send_initial_diagnostics(
+    client: &WebSocketClient,
+    session_id: &str,
+) -> Result<(), &'static str> {
+    let session = App::get()
+        .find_session_by_id(session_id)
+        .ok_or("session not found")?;
+    let session = session.borrow();
+    let instance_state = session.instance_state().borrow();
+    let entries: Vec<_> = instance_state.notifications().entries().collect();
+    for entry in entries.into_iter().rev() {
+        let event = get_diagnostic_event(&session, DiagnosticEvent::Notified(entry));
+        client.send(&event)?;
+    }
+    Ok(())
+}
+
 pub fn send_updated_active_controller(session: &Session) -> Result<(), &'static str> {
     send_to_clients_subscribed_to(
         &Topic::ActiveController {
@@ -108,6 +127,30 @@ pub fn send_projection_feedback_to_subscribed_clients(
     )
 }
 
+pub fn send_control_element_touched_to_subscribed_clients(
+    session_id: &str,
+    event: ControlElementTouchedEvent,
+) -> Result<(), &'static str> {
+    send_to_clients_subscribed_to(
+        &Topic::Feedback {
+            session_id: session_id.to_string(),
+        },
+        || Some(get_control_element_touched_event(session_id, event)),
+    )
+}
+
+pub fn send_diagnostic_event_to_subscribed_clients(
+    session: &Session,
+    event: DiagnosticEvent,
+) -> Result<(), &'static str> {
+    send_to_clients_subscribed_to(
+        &Topic::Diagnostics {
+            session_id: session.id().to_string(),
+        },
+        || Some(get_diagnostic_event(session, event)),
+    )
+}
+
 fn send_to_clients_subscribed_to<T: Serialize>(
     topic: &Topic,
     create_message: impl FnOnce() -> Option<T>,
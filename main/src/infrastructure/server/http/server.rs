@@ -4,7 +4,7 @@ use axum::extract::{Query, WebSocketUpgrade};
 use axum::handler::Handler;
 use axum::http::header::CONTENT_TYPE;
 use axum::http::Method;
-use axum::routing::{get, patch};
+use axum::routing::{get, patch, post};
 use axum::Router;
 use std::io;
 use std::net::SocketAddr;
@@ -67,6 +67,14 @@ fn create_router(
             "/realearn/session/:id",
             get(session_handler.layer(MainThreadLayer)),
         )
+        .route(
+            "/realearn/session/:id/pairing",
+            get(pairing_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/pairing-qr-code.png",
+            get(pairing_qr_code_handler.layer(MainThreadLayer)),
+        )
         .route(
             "/realearn/session/:id/controller",
             get(session_controller_handler.layer(MainThreadLayer)),
@@ -75,6 +83,22 @@ fn create_router(
             "/realearn/session/:id/controller-routing",
             get(controller_routing_handler.layer(MainThreadLayer)),
         )
+        .route(
+            "/realearn/session/:id/effective-targets",
+            get(effective_targets_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/stats",
+            get(mapping_statistics_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/validation",
+            get(mapping_validation_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/controls-documentation",
+            get(controls_documentation_handler.layer(MainThreadLayer)),
+        )
         .route(
             "/realearn/session/:id/clip-matrix",
             get(clip_matrix_handler.layer(MainThreadLayer)),
@@ -83,6 +107,7 @@ fn create_router(
             "/realearn/controller/:id",
             patch(patch_controller_handler.layer(MainThreadLayer)),
         )
+        .route("/realearn/lint", post(lint_handler.layer(MainThreadLayer)))
         .route(
             "/realearn/metrics",
             get(move || async move { create_metrics_response(metrics_reporter).await }),
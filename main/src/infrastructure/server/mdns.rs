@@ -0,0 +1,67 @@
+//! Announces the ReaLearn server in the local network via mDNS/DNS-SD (Bonjour), so that the
+//! Companion app can discover it without the user having to type in IP addresses.
+use crate::infrastructure::plugin::App;
+use derivative::Derivative;
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+const SERVICE_TYPE: &str = "_realearn._tcp.local.";
+
+/// Keeps the server announced as long as it's alive.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct MdnsAnnouncement {
+    #[derivative(Debug = "ignore")]
+    daemon: ServiceDaemon,
+    full_name: String,
+}
+
+impl MdnsAnnouncement {
+    pub fn start(
+        ip: IpAddr,
+        local_hostname: Option<String>,
+        http_port: u16,
+        https_port: u16,
+    ) -> Result<Self, &'static str> {
+        let daemon = ServiceDaemon::new().map_err(|_| "couldn't start mDNS daemon")?;
+        let instance_name = format!(
+            "ReaLearn on {}",
+            local_hostname.unwrap_or_else(|| ip.to_string())
+        );
+        let host_name = format!(
+            "realearn-{}.local.",
+            ip.to_string()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "-")
+        );
+        let properties: HashMap<String, String> = [
+            ("version", App::version().to_string()),
+            ("http-port", http_port.to_string()),
+            ("https-port", https_port.to_string()),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let service_info = ServiceInfo::new(
+            SERVICE_TYPE,
+            &instance_name,
+            &host_name,
+            ip.to_string().as_str(),
+            http_port,
+            Some(properties),
+        )
+        .map_err(|_| "couldn't create mDNS service info")?;
+        let full_name = service_info.get_fullname().to_string();
+        daemon
+            .register(service_info)
+            .map_err(|_| "couldn't register mDNS service")?;
+        Ok(Self { daemon, full_name })
+    }
+}
+
+impl Drop for MdnsAnnouncement {
+    fn drop(&mut self) {
+        let _ = self.daemon.unregister(&self.full_name);
+        let _ = self.daemon.shutdown();
+    }
+}
@@ -5,6 +5,7 @@ use crate::infrastructure::plugin::App;
 use rcgen::{BasicConstraints, CertificateParams, DistinguishedName, DnType, IsCa, SanType};
 use reaper_high::Reaper;
 use rxrust::prelude::*;
+use slog::debug;
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::fs;
@@ -19,6 +20,7 @@ use url::Url;
 use crate::infrastructure::server::grpc::start_grpc_server;
 use crate::infrastructure::server::http::start_http_server;
 use crate::infrastructure::server::http::ServerClients;
+use crate::infrastructure::server::mdns::MdnsAnnouncement;
 use derivative::Derivative;
 use std::thread::JoinHandle;
 use std::time::Duration;
@@ -29,6 +31,9 @@ mod data;
 pub mod grpc;
 pub mod http;
 mod layers;
+mod mdns;
+mod webhooks;
+pub use webhooks::*;
 
 #[derive(Debug)]
 pub struct RealearnServer {
@@ -40,6 +45,8 @@ pub struct RealearnServer {
     changed_subject: LocalSubject<'static, (), ()>,
     local_ip: Option<IpAddr>,
     metrics_reporter: MetricsReporter,
+    /// Only set while the server is running and the local IP is known.
+    mdns_announcement: Option<MdnsAnnouncement>,
 }
 
 /// Responsible for reporting application metrics.
@@ -129,6 +136,7 @@ impl RealearnServer {
             changed_subject: Default::default(),
             local_ip: get_local_ip(),
             metrics_reporter,
+            mdns_announcement: None,
         }
     }
 
@@ -191,12 +199,33 @@ impl RealearnServer {
             std::mem::replace(&mut self.state, ServerState::Stopped)
         {
             self.state = ServerState::Running(runtime_data);
+            self.announce_via_mdns();
         }
         self.notify_changed();
     }
 
+    fn announce_via_mdns(&mut self) {
+        let ip = match self.local_ip() {
+            None => return,
+            Some(ip) => ip,
+        };
+        match MdnsAnnouncement::start(ip, self.local_hostname(), self.http_port, self.https_port) {
+            Ok(a) => {
+                self.mdns_announcement = Some(a);
+            }
+            Err(e) => {
+                // Not critical. The user can still connect by scanning the QR code.
+                debug!(
+                    Reaper::get().logger(),
+                    "Couldn't announce server via mDNS: {}", e
+                );
+            }
+        }
+    }
+
     /// Idempotent.
     pub fn stop(&mut self) {
+        self.mdns_announcement = None;
         let old_state = std::mem::replace(&mut self.state, ServerState::Stopped);
         let runtime_data = match old_state {
             ServerState::Running(runtime_data) | ServerState::Starting(runtime_data) => {
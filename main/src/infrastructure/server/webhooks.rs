@@ -0,0 +1,390 @@
+use crate::application::{Session, SessionEvent};
+use crate::domain::Compartment;
+use crate::infrastructure::plugin::App;
+use futures_timer::Delay;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Method, Request, Uri};
+use hyper_rustls::HttpsConnector;
+use once_cell::unsync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use slog::warn;
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// How often a webhook request is attempted before giving up.
+const MAX_ATTEMPT_COUNT: u32 = 4;
+/// Delay before the first retry. Doubled with each further retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// HTTP endpoints which are notified about certain events, e.g. to integrate ReaLearn with an
+/// external show-control system.
+///
+/// Saved as JSON file next to the app configuration. It's reloaded as soon as the file has been
+/// modified, so changes take effect without restarting REAPER.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// URL to which the event is sent via HTTP POST (`http` or `https`).
+    pub url: String,
+    /// Events which should be sent. Empty means all events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<WebhookEventType>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEventType {
+    InstanceCreated,
+    PresetLoaded,
+    MappingLearned,
+}
+
+impl WebhookConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|_| "couldn't read webhook config file".to_string())?;
+        Self::from_json(&json)
+    }
+
+    fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| format!("Webhook config file isn't valid. Details:\n\n{}", e))?;
+        for webhook in &config.webhooks {
+            check_webhook_url(&webhook.url)?;
+        }
+        Ok(config)
+    }
+
+    fn urls_interested_in(&self, event_type: WebhookEventType) -> impl Iterator<Item = &str> {
+        self.webhooks
+            .iter()
+            .filter(move |w| w.events.is_empty() || w.events.contains(&event_type))
+            .map(|w| w.url.as_str())
+    }
+}
+
+fn check_webhook_url(url: &str) -> Result<(), String> {
+    let uri: Uri = url
+        .parse()
+        .map_err(|e| format!("Webhook URL {} isn't valid: {}", url, e))?;
+    match uri.scheme_str() {
+        Some("http" | "https") => Ok(()),
+        _ => Err(format!(
+            "Webhook URL {} isn't supported (must start with http:// or https://)",
+            url
+        )),
+    }
+}
+
+/// Keeps the webhook config in memory and reloads it only if the file has been modified.
+#[derive(Debug, Default)]
+struct WebhookConfigCache {
+    /// Modification time of the file when it was loaded the last time.
+    ///
+    /// `None` if the file doesn't exist or hasn't been loaded yet.
+    modified: Option<SystemTime>,
+    config: WebhookConfig,
+}
+
+impl WebhookConfigCache {
+    /// Reloads the config using the given function if the given modification time of the file
+    /// differs from the one of the last load (`None` means that the file doesn't exist).
+    ///
+    /// If loading fails, the error is returned once and the cache falls back to an empty config
+    /// until the file is modified again.
+    fn update(
+        &mut self,
+        modified: Option<SystemTime>,
+        load: impl FnOnce() -> Result<WebhookConfig, String>,
+    ) -> Result<(), String> {
+        if modified.is_some() && modified == self.modified {
+            return Ok(());
+        }
+        self.modified = modified;
+        if modified.is_none() {
+            self.config = Default::default();
+            return Ok(());
+        }
+        match load() {
+            Ok(config) => {
+                self.config = config;
+                Ok(())
+            }
+            Err(msg) => {
+                self.config = Default::default();
+                Err(msg)
+            }
+        }
+    }
+
+    fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+}
+
+type WebhookClient = Client<HttpsConnector<HttpConnector>>;
+
+#[derive(Debug)]
+struct WebhookRequest {
+    url: String,
+    body: String,
+}
+
+/// Sends webhook requests in a separate thread, retrying failed requests with exponential backoff.
+///
+/// The thread is started not before the first request needs to be sent.
+#[derive(Debug, Default)]
+pub struct WebhookDispatcher {
+    config_cache: RefCell<WebhookConfigCache>,
+    sender: OnceCell<async_channel::Sender<WebhookRequest>>,
+}
+
+impl WebhookDispatcher {
+    /// `restored` should be `true` if the instance has been loaded from a project (as opposed to
+    /// being freshly added).
+    pub fn notify_instance_created(&self, session: &Session, restored: bool) {
+        let payload = json!({
+            "event": WebhookEventType::InstanceCreated,
+            "instanceId": session.id(),
+            "restored": restored,
+        });
+        self.notify(WebhookEventType::InstanceCreated, payload);
+    }
+
+    pub fn notify_session_event(&self, session: &Session, event: &SessionEvent) {
+        let (event_type, payload) = match event {
+            SessionEvent::PresetLoaded {
+                compartment,
+                preset_id,
+            } => (
+                WebhookEventType::PresetLoaded,
+                json!({
+                    "event": WebhookEventType::PresetLoaded,
+                    "instanceId": session.id(),
+                    "compartment": compartment_key(*compartment),
+                    "presetId": preset_id,
+                }),
+            ),
+            SessionEvent::MappingLearned { mapping_id, part } => {
+                let mapping_name = session
+                    .find_mapping_and_index_by_qualified_id(*mapping_id)
+                    .map(|(_, m)| m.borrow().effective_name());
+                (
+                    WebhookEventType::MappingLearned,
+                    json!({
+                        "event": WebhookEventType::MappingLearned,
+                        "instanceId": session.id(),
+                        "compartment": compartment_key(mapping_id.compartment),
+                        "mappingId": mapping_id.id.to_string(),
+                        "mappingName": mapping_name,
+                        "learned": part.to_string(),
+                    }),
+                )
+            }
+        };
+        self.notify(event_type, payload);
+    }
+
+    fn notify(&self, event_type: WebhookEventType, payload: serde_json::Value) {
+        let path = App::realearn_webhook_config_file_path();
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let mut config_cache = self.config_cache.borrow_mut();
+        if let Err(msg) = config_cache.update(modified, || WebhookConfig::load(&path)) {
+            warn!(App::logger(), "{}", msg);
+        }
+        let body = payload.to_string();
+        for url in config_cache.config().urls_interested_in(event_type) {
+            let request = WebhookRequest {
+                url: url.to_string(),
+                body: body.clone(),
+            };
+            let sender = match self.sender.get_or_try_init(start_webhook_worker) {
+                Ok(s) => s,
+                Err(msg) => {
+                    warn!(App::logger(), "Couldn't start webhook worker: {}", msg);
+                    return;
+                }
+            };
+            let _ = sender.try_send(request);
+        }
+    }
+}
+
+fn compartment_key(compartment: Compartment) -> &'static str {
+    match compartment {
+        Compartment::Controller => "controller",
+        Compartment::Main => "main",
+    }
+}
+
+/// The runtime is created before spawning the thread, so that the sender is only handed out if
+/// the worker can actually process requests.
+fn start_webhook_worker() -> Result<async_channel::Sender<WebhookRequest>, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("couldn't create runtime: {}", e))?;
+    let (sender, receiver) = async_channel::bounded::<WebhookRequest>(500);
+    std::thread::Builder::new()
+        .name("ReaLearn webhooks".to_string())
+        .spawn(move || {
+            runtime.block_on(async move {
+                let connector = hyper_rustls::HttpsConnectorBuilder::new()
+                    .with_webpki_roots()
+                    .https_or_http()
+                    .enable_http1()
+                    .build();
+                let client: WebhookClient = Client::builder().build(connector);
+                while let Ok(request) = receiver.recv().await {
+                    tokio::spawn(send_with_retries(client.clone(), request));
+                }
+            });
+        })
+        .map_err(|e| format!("couldn't start thread: {}", e))?;
+    Ok(sender)
+}
+
+async fn send_with_retries(client: WebhookClient, request: WebhookRequest) {
+    let mut delay = INITIAL_RETRY_DELAY;
+    for attempt in 1..=MAX_ATTEMPT_COUNT {
+        match send(&client, &request).await {
+            Ok(_) => return,
+            Err(msg) => {
+                if attempt < MAX_ATTEMPT_COUNT {
+                    tracing::debug!(
+                        "Webhook request to {} failed (attempt {}): {}",
+                        request.url,
+                        attempt,
+                        msg
+                    );
+                } else {
+                    tracing::warn!(
+                        "Webhook request to {} failed {} times, giving up: {}",
+                        request.url,
+                        attempt,
+                        msg
+                    );
+                }
+            }
+        }
+        if attempt < MAX_ATTEMPT_COUNT {
+            Delay::new(delay).await;
+            delay *= 2;
+        }
+    }
+}
+
+async fn send(client: &WebhookClient, request: &WebhookRequest) -> Result<(), String> {
+    let http_request = Request::builder()
+        .method(Method::POST)
+        .uri(&request.url)
+        .header("content-type", "application/json")
+        .body(Body::from(request.body.clone()))
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .request(http_request)
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("server responded with {}", response.status()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_urls_by_event_type() {
+        // Given
+        let config = WebhookConfig::from_json(
+            r#"{
+                "webhooks": [
+                    { "url": "http://localhost:3000/all" },
+                    { "url": "https://example.com/presets", "events": ["presetLoaded"] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        // When
+        let preset_urls: Vec<_> = config
+            .urls_interested_in(WebhookEventType::PresetLoaded)
+            .collect();
+        let learn_urls: Vec<_> = config
+            .urls_interested_in(WebhookEventType::MappingLearned)
+            .collect();
+        // Then
+        assert_eq!(
+            preset_urls,
+            ["http://localhost:3000/all", "https://example.com/presets"]
+        );
+        assert_eq!(learn_urls, ["http://localhost:3000/all"]);
+    }
+
+    #[test]
+    fn reject_unsupported_urls() {
+        // Given
+        let json = r#"{ "webhooks": [{ "url": "ftp://example.com" }] }"#;
+        // When
+        let result = WebhookConfig::from_json(json);
+        // Then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reload_config_only_if_modified() {
+        // Given
+        let mut cache = WebhookConfigCache::default();
+        let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let t2 = SystemTime::UNIX_EPOCH + Duration::from_secs(2);
+        let mut load_count = 0;
+        let mut load = |url: &str| {
+            load_count += 1;
+            WebhookConfig::from_json(&format!(r#"{{ "webhooks": [{{ "url": "{url}" }}] }}"#))
+        };
+        // When
+        cache.update(Some(t1), || load("http://a")).unwrap();
+        cache.update(Some(t1), || load("http://b")).unwrap();
+        let url_after_unchanged = cache.config().webhooks[0].url.clone();
+        cache.update(Some(t2), || load("http://c")).unwrap();
+        // Then
+        assert_eq!(load_count, 2);
+        assert_eq!(url_after_unchanged, "http://a");
+        assert_eq!(cache.config().webhooks[0].url, "http://c");
+    }
+
+    #[test]
+    fn fall_back_to_empty_config() {
+        // Given
+        let mut cache = WebhookConfigCache::default();
+        let t1 = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        cache
+            .update(Some(t1), || {
+                WebhookConfig::from_json(r#"{ "webhooks": [{ "url": "http://a" }] }"#)
+            })
+            .unwrap();
+        // When
+        let invalid_result = cache.update(Some(t1 + Duration::from_secs(1)), || {
+            WebhookConfig::from_json("{")
+        });
+        let invalid_webhook_count = cache.config().webhooks.len();
+        let removed_result = cache.update(None, || unreachable!());
+        // Then
+        assert!(invalid_result.is_err());
+        assert_eq!(invalid_webhook_count, 0);
+        assert!(removed_result.is_ok());
+        assert!(cache.config().webhooks.is_empty());
+    }
+}
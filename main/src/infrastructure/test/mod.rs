@@ -51,6 +51,11 @@ impl Test {
         )
         .await;
         self.step("Toggle mode", toggle_mode()).await;
+        self.step(
+            "Defer feedback on project load",
+            defer_feedback_on_project_load(),
+        )
+        .await;
         self.step(
             "Send feedback after control - Normal mode - Arm",
             send_feedback_after_control_normal_mode_arm(),
@@ -215,6 +220,28 @@ async fn basics() {
     );
 }
 
+async fn defer_feedback_on_project_load() {
+    // Given
+    let realearn = setup().await;
+    // When
+    load_realearn_state_like_project(&realearn, include_str!("presets/basics.json"));
+    millis(50).await;
+    // Then
+    assert_eq!(
+        realearn.pop_feedback(),
+        vec![],
+        "feedback should be held back right after project load"
+    );
+    // When
+    millis(500).await;
+    // Then
+    assert_eq!(
+        realearn.pop_feedback(),
+        vec![Midi(Plain(note_on(0, 64, 91)))],
+        "held back feedback should be sent when it's the instance's turn"
+    );
+}
+
 async fn nrpn_test() {
     // Given
     let realearn = setup().await;
@@ -1324,6 +1351,14 @@ fn load_realearn_preset(realearn: &RealearnTestInstance, json: &str) {
         .unwrap();
 }
 
+/// Passes the state to ReaLearn the same way as REAPER does it when loading a project.
+fn load_realearn_state_like_project(realearn: &RealearnTestInstance, json: &str) {
+    realearn
+        .fx
+        .set_vst_chunk(json.as_bytes())
+        .expect("couldn't set VST chunk");
+}
+
 async fn send_midi(message: impl ShortMessage) {
     Reaper::get().stuff_midi_message(StuffMidiMessageTarget::VirtualMidiKeyboardQueue, message);
     moment().await;
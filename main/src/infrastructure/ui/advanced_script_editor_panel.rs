@@ -1,5 +1,6 @@
 use crate::base::blocking_lock;
 use crate::infrastructure::ui::bindings::root;
+use crate::infrastructure::ui::egui_views;
 use crate::infrastructure::ui::egui_views::advanced_script_editor;
 use crate::infrastructure::ui::egui_views::advanced_script_editor::Toolbox;
 use crate::infrastructure::ui::ScriptEditorInput;
@@ -108,7 +109,7 @@ impl View for AdvancedScriptEditorPanel {
             state,
             |ctx: &egui::Context, _queue: &mut egui_baseview::Queue, _state: &mut State| {
                 firewall(|| {
-                    advanced_script_editor::init_ui(ctx, Window::dark_mode_is_enabled());
+                    egui_views::init_ui(ctx, Window::dark_mode_is_enabled());
                 });
             },
             |ctx: &egui::Context, _queue: &mut egui_baseview::Queue, state: &mut State| {
@@ -0,0 +1,82 @@
+use crate::infrastructure::ui::bindings::root;
+use crate::infrastructure::ui::egui_views;
+use derivative::Derivative;
+use reaper_low::{firewall, raw};
+use std::cell::RefCell;
+use swell_ui::{SharedView, View, ViewContext, Window};
+
+/// Panel which shows an egui view filling the whole window.
+///
+/// The state is handed over to the egui window when the panel is opened, so each panel instance
+/// can be opened only once.
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct EguiPanel<S> {
+    view: ViewContext,
+    title: &'static str,
+    #[derivative(Debug = "ignore")]
+    state: RefCell<Option<S>>,
+    #[derivative(Debug = "ignore")]
+    run_ui: fn(&egui::Context, &mut S),
+}
+
+impl<S> EguiPanel<S> {
+    pub fn new(title: &'static str, state: S, run_ui: fn(&egui::Context, &mut S)) -> Self {
+        Self {
+            view: Default::default(),
+            title,
+            state: RefCell::new(Some(state)),
+            run_ui,
+        }
+    }
+}
+
+impl<S: Send + 'static> View for EguiPanel<S> {
+    fn dialog_resource_id(&self) -> u32 {
+        root::ID_EMPTY_PANEL
+    }
+
+    fn view_context(&self) -> &ViewContext {
+        &self.view
+    }
+
+    fn opened(self: SharedView<Self>, window: Window) -> bool {
+        let window_size = window.size();
+        let dpi_factor = window.dpi_scaling_factor();
+        let window_width = window_size.width.get() as f64 / dpi_factor;
+        let window_height = window_size.height.get() as f64 / dpi_factor;
+        let state = self.state.take().expect("state already in use");
+        let settings = baseview::WindowOpenOptions {
+            title: self.title.into(),
+            size: baseview::Size::new(window_width, window_height),
+            scale: baseview::WindowScalePolicy::SystemScaleFactor,
+            gl_config: Some(Default::default()),
+        };
+        let run_ui = self.run_ui;
+        egui_baseview::EguiWindow::open_parented(
+            &self.view.require_window(),
+            settings,
+            state,
+            |ctx: &egui::Context, _queue: &mut egui_baseview::Queue, _state: &mut S| {
+                firewall(|| {
+                    egui_views::init_ui(ctx, Window::dark_mode_is_enabled());
+                });
+            },
+            move |ctx: &egui::Context, _queue: &mut egui_baseview::Queue, state: &mut S| {
+                firewall(|| {
+                    run_ui(ctx, state);
+                });
+            },
+        );
+        true
+    }
+
+    #[allow(clippy::single_match)]
+    fn button_clicked(self: SharedView<Self>, resource_id: u32) {
+        match resource_id {
+            // Escape key
+            raw::IDCANCEL => self.close(),
+            _ => {}
+        }
+    }
+}
@@ -1 +1,16 @@
+use egui::{Context, Visuals};
+
 pub mod advanced_script_editor;
+pub mod test_feedback;
+pub mod touch_controller;
+
+/// Applies the style which is common to all egui views.
+pub fn init_ui(ctx: &Context, dark_mode_is_enabled: bool) {
+    let mut style: egui::Style = (*ctx.style()).clone();
+    style.visuals = if dark_mode_is_enabled {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    };
+    ctx.set_style(style);
+}
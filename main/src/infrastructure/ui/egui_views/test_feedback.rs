@@ -0,0 +1,94 @@
+use crate::domain::QualifiedMappingId;
+use egui::{CentralPanel, ComboBox, Context, DragValue, Slider};
+use helgoboss_learn::UnitValue;
+
+pub fn run_ui(ctx: &Context, state: &mut State) {
+    CentralPanel::default().show(ctx, |ui| {
+        if state.sources.is_empty() {
+            ui.label("The controller compartment doesn't contain any mappings.");
+            return;
+        }
+        let mut value_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Source");
+            let selected_label = state
+                .sources
+                .get(state.selected_source_index)
+                .map(|s| s.label.as_str())
+                .unwrap_or_default();
+            ComboBox::from_id_source("source")
+                .width(ui.available_width())
+                .selected_text(selected_label)
+                .show_ui(ui, |ui| {
+                    for (i, source) in state.sources.iter().enumerate() {
+                        let response = ui.selectable_value(
+                            &mut state.selected_source_index,
+                            i,
+                            source.label.as_str(),
+                        );
+                        // Immediately send the current value to the newly selected source
+                        value_changed |= response.changed();
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Value");
+            let slider = Slider::new(&mut state.value_in_percent, 0.0..=100.0).show_value(false);
+            value_changed |= ui.add(slider).changed();
+            let drag_value = DragValue::new(&mut state.value_in_percent)
+                .clamp_range(0.0..=100.0)
+                .speed(0.5)
+                .suffix(" %");
+            value_changed |= ui.add(drag_value).changed();
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Off").clicked() {
+                state.value_in_percent = 0.0;
+                value_changed = true;
+            }
+            if ui.button("On").clicked() {
+                state.value_in_percent = 100.0;
+                value_changed = true;
+            }
+        });
+        if value_changed {
+            state.send_current_value();
+        }
+    });
+}
+
+pub struct State {
+    sources: Vec<TestFeedbackSource>,
+    selected_source_index: usize,
+    value_in_percent: f64,
+    send: Box<dyn Fn(QualifiedMappingId, UnitValue) + Send>,
+}
+
+/// A source which can receive test feedback, represented by the mapping that contains it.
+pub struct TestFeedbackSource {
+    pub mapping_id: QualifiedMappingId,
+    pub label: String,
+}
+
+impl State {
+    pub fn new(
+        sources: Vec<TestFeedbackSource>,
+        send: impl Fn(QualifiedMappingId, UnitValue) + Send + 'static,
+    ) -> Self {
+        Self {
+            sources,
+            selected_source_index: 0,
+            value_in_percent: 0.0,
+            send: Box::new(send),
+        }
+    }
+
+    fn send_current_value(&self) {
+        let source = match self.sources.get(self.selected_source_index) {
+            None => return,
+            Some(s) => s,
+        };
+        let value = UnitValue::new_clamped(self.value_in_percent / 100.0);
+        (self.send)(source.mapping_id, value);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::domain::{VirtualControlElement, VirtualSourceValue, XyPadRole};
+use egui::{
+    pos2, vec2, Button, CentralPanel, Context, Pos2, Rect, ScrollArea, Sense, Slider, Stroke, Ui,
+};
+use helgoboss_learn::{ControlValue, UnitValue};
+
+pub fn run_ui(ctx: &Context, state: &mut State) {
+    CentralPanel::default().show(ctx, |ui| {
+        if state.elements.is_empty() && state.xy_pads.is_empty() {
+            ui.label(
+                "The controller compartment doesn't contain any mappings with virtual targets.",
+            );
+            return;
+        }
+        ScrollArea::vertical().show(ui, |ui| {
+            for pad in &mut state.xy_pads {
+                for (control_element, control_value) in run_xy_pad_ui(ui, pad) {
+                    (state.send)(VirtualSourceValue::new(control_element, control_value));
+                }
+            }
+            for element in &mut state.elements {
+                let value_to_send = match element.control_element {
+                    VirtualControlElement::Multi(_) => {
+                        ui.horizontal(|ui| {
+                            let slider = Slider::new(&mut element.value_in_percent, 0.0..=100.0)
+                                .suffix(" %")
+                                .text(element.label.as_str());
+                            if ui.add(slider).changed() {
+                                Some(element.value_in_percent / 100.0)
+                            } else {
+                                None
+                            }
+                        })
+                        .inner
+                    }
+                    VirtualControlElement::Button(_) => {
+                        // Buttons need to send a value on press *and* on release, like a real
+                        // momentary button.
+                        let button =
+                            Button::new(element.label.as_str()).sense(Sense::click_and_drag());
+                        let is_pressed = ui.add(button).is_pointer_button_down_on();
+                        if is_pressed == element.is_pressed {
+                            None
+                        } else {
+                            element.is_pressed = is_pressed;
+                            Some(if is_pressed { 1.0 } else { 0.0 })
+                        }
+                    }
+                    VirtualControlElement::Display(_) => {
+                        // Displays only receive feedback, there's nothing to control.
+                        ui.label(element.label.as_str());
+                        None
+                    }
+                };
+                if let Some(v) = value_to_send {
+                    let control_value = ControlValue::AbsoluteContinuous(UnitValue::new_clamped(v));
+                    (state.send)(VirtualSourceValue::new(
+                        element.control_element,
+                        control_value,
+                    ));
+                }
+            }
+        });
+    });
+}
+
+/// Renders a two-dimensional pad and returns the values to be sent.
+///
+/// Sends "touch on" when the pad is pressed, then X and Y while dragging and finally
+/// "touch off" on release.
+fn run_xy_pad_ui(ui: &mut Ui, pad: &mut TouchXyPad) -> Vec<(VirtualControlElement, ControlValue)> {
+    let mut values = vec![];
+    ui.label(pad.label.as_str());
+    let (rect, response) = ui.allocate_exact_size(vec2(XY_PAD_SIZE, XY_PAD_SIZE), Sense::drag());
+    let is_pressed = response.is_pointer_button_down_on();
+    if is_pressed != pad.is_pressed {
+        pad.is_pressed = is_pressed;
+        if let Some(e) = pad.touch {
+            values.push((e, convert_bool_to_control_value(is_pressed)));
+        }
+    }
+    if let Some(pos) = response.interact_pointer_pos().filter(|_| is_pressed) {
+        let x = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0) as f64;
+        // Bottom is the minimum, like on a fader
+        let y = ((rect.bottom() - pos.y) / rect.height()).clamp(0.0, 1.0) as f64;
+        if x != pad.x {
+            pad.x = x;
+            if let Some(e) = pad.x_element {
+                values.push((e, convert_unit_to_control_value(x)));
+            }
+        }
+        if y != pad.y {
+            pad.y = y;
+            if let Some(e) = pad.y_element {
+                values.push((e, convert_unit_to_control_value(y)));
+            }
+        }
+    }
+    let visuals = ui.visuals();
+    let painter = ui.painter();
+    painter.rect_filled(rect, 4.0, visuals.extreme_bg_color);
+    painter.rect_stroke(rect, 4.0, visuals.widgets.inactive.fg_stroke);
+    let handle_pos = pos2(
+        rect.left() + pad.x as f32 * rect.width(),
+        rect.bottom() - pad.y as f32 * rect.height(),
+    );
+    let handle_color = if pad.is_pressed {
+        visuals.selection.bg_fill
+    } else {
+        visuals.widgets.inactive.fg_stroke.color
+    };
+    draw_crosshair(painter, rect, handle_pos, Stroke::new(1.0, handle_color));
+    painter.circle_filled(handle_pos, 6.0, handle_color);
+    ui.add_space(8.0);
+    values
+}
+
+fn draw_crosshair(painter: &egui::Painter, rect: Rect, center: Pos2, stroke: Stroke) {
+    painter.line_segment(
+        [pos2(rect.left(), center.y), pos2(rect.right(), center.y)],
+        stroke,
+    );
+    painter.line_segment(
+        [pos2(center.x, rect.top()), pos2(center.x, rect.bottom())],
+        stroke,
+    );
+}
+
+fn convert_unit_to_control_value(v: f64) -> ControlValue {
+    ControlValue::AbsoluteContinuous(UnitValue::new_clamped(v))
+}
+
+fn convert_bool_to_control_value(on: bool) -> ControlValue {
+    convert_unit_to_control_value(if on { 1.0 } else { 0.0 })
+}
+
+const XY_PAD_SIZE: f32 = 200.0;
+
+pub struct State {
+    elements: Vec<TouchControlElement>,
+    xy_pads: Vec<TouchXyPad>,
+    send: Box<dyn Fn(VirtualSourceValue) + Send>,
+}
+
+/// An on-screen control which emits values for a virtual control element.
+pub struct TouchControlElement {
+    control_element: VirtualControlElement,
+    label: String,
+    value_in_percent: f64,
+    is_pressed: bool,
+}
+
+impl TouchControlElement {
+    pub fn new(control_element: VirtualControlElement, label: String) -> Self {
+        Self {
+            control_element,
+            label,
+            value_in_percent: 0.0,
+            is_pressed: false,
+        }
+    }
+}
+
+/// An on-screen pad which emits values for the virtual control elements of an XY pad.
+///
+/// Elements are optional because the controller compartment might not define all of them.
+pub struct TouchXyPad {
+    label: String,
+    x_element: Option<VirtualControlElement>,
+    y_element: Option<VirtualControlElement>,
+    touch: Option<VirtualControlElement>,
+    x: f64,
+    y: f64,
+    is_pressed: bool,
+}
+
+impl TouchXyPad {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            x_element: None,
+            y_element: None,
+            touch: None,
+            x: 0.5,
+            y: 0.5,
+            is_pressed: false,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_element(&mut self, role: XyPadRole, element: VirtualControlElement) {
+        match role {
+            XyPadRole::X => self.x_element = Some(element),
+            XyPadRole::Y => self.y_element = Some(element),
+            XyPadRole::Touch => self.touch = Some(element),
+        }
+    }
+}
+
+impl State {
+    pub fn new(
+        elements: Vec<TouchControlElement>,
+        xy_pads: Vec<TouchXyPad>,
+        send: impl Fn(VirtualSourceValue) + Send + 'static,
+    ) -> Self {
+        Self {
+            elements,
+            xy_pads,
+            send: Box::new(send),
+        }
+    }
+}
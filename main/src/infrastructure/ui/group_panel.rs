@@ -1,15 +1,21 @@
 use crate::application::{
-    Affected, CompartmentProp, GroupProp, SessionProp, WeakGroup, WeakSession,
+    Affected, CompartmentProp, GroupCommand, GroupProp, Session, SessionProp, WeakGroup,
+    WeakSession,
 };
 use crate::infrastructure::ui::bindings::root;
 use crate::infrastructure::ui::util::MAPPING_PANEL_SCALING;
 use crate::infrastructure::ui::{ItemProp, MappingHeaderPanel};
+use helgoboss_learn::{RgbColor, VirtualColor};
+use reaper_high::Reaper;
 use reaper_low::raw;
+use reaper_medium::WindowContext;
 use swell_ui::{DialogUnits, Point, SharedView, View, ViewContext, Window};
 
 #[derive(Debug)]
 pub struct GroupPanel {
     view: ViewContext,
+    session: WeakSession,
+    group: WeakGroup,
     mapping_header_panel: SharedView<MappingHeaderPanel>,
 }
 
@@ -17,6 +23,8 @@ impl GroupPanel {
     pub fn new(session: WeakSession, group: WeakGroup) -> GroupPanel {
         GroupPanel {
             view: Default::default(),
+            session: session.clone(),
+            group: group.clone(),
             mapping_header_panel: SharedView::new(MappingHeaderPanel::new(
                 session,
                 Point::new(DialogUnits(7), DialogUnits(5)).scale(MAPPING_PANEL_SCALING),
@@ -25,6 +33,36 @@ impl GroupPanel {
         }
     }
 
+    fn invalidate_feedback_color_button(&self) {
+        let group = match self.group.upgrade() {
+            None => return,
+            Some(g) => g,
+        };
+        let label = match group.borrow().feedback_color() {
+            None => "Feedback color: <Default>".to_string(),
+            Some(VirtualColor::Rgb(c)) => {
+                format!("Feedback color: #{:02X}{:02X}{:02X}", c.r(), c.g(), c.b())
+            }
+            Some(VirtualColor::Prop { prop }) => format!("Feedback color: {}", prop),
+        };
+        self.view
+            .require_control(root::ID_GROUP_PANEL_FEEDBACK_COLOR_BUTTON)
+            .set_text(label);
+    }
+
+    fn change_feedback_color(&self) -> Result<(), &'static str> {
+        let group = self.group.upgrade().ok_or("group gone")?;
+        let current_color = group.borrow().feedback_color().cloned();
+        let color = prompt_for_feedback_color(self.view.require_window(), current_color)?;
+        Session::change_group_from_ui_simple(
+            self.session.clone(),
+            &mut group.borrow_mut(),
+            GroupCommand::SetFeedbackColor(color),
+            None,
+        );
+        Ok(())
+    }
+
     #[allow(clippy::single_match)]
     pub fn handle_affected(
         self: &SharedView<Self>,
@@ -38,6 +76,7 @@ impl GroupPanel {
             One(InCompartment(_, One(InGroup(_, affected)))) => match affected {
                 Multiple => {
                     self.mapping_header_panel.invalidate_controls();
+                    self.invalidate_feedback_color_button();
                 }
                 One(prop) => {
                     use GroupProp as P;
@@ -62,6 +101,9 @@ impl GroupPanel {
                                 initiator,
                             );
                         }
+                        P::FeedbackColor => {
+                            self.invalidate_feedback_color_button();
+                        }
                         P::InActivationCondition(p) => match p {
                             Multiple => {
                                 self.mapping_header_panel.invalidate_controls();
@@ -91,6 +133,7 @@ impl View for GroupPanel {
 
     fn opened(self: SharedView<Self>, window: Window) -> bool {
         self.mapping_header_panel.clone().open(window);
+        self.invalidate_feedback_color_button();
         true
     }
 
@@ -101,7 +144,58 @@ impl View for GroupPanel {
             ID_GROUP_PANEL_OK | raw::IDCANCEL => {
                 self.close();
             }
+            ID_GROUP_PANEL_FEEDBACK_COLOR_BUTTON => {
+                let _ = self.change_feedback_color();
+            }
             _ => unreachable!(),
         }
     }
 }
+
+/// Lets the user choose the feedback color of a group.
+///
+/// Returns `None` if the group shouldn't define a color.
+fn prompt_for_feedback_color(
+    window: Window,
+    current_color: Option<VirtualColor>,
+) -> Result<Option<VirtualColor>, &'static str> {
+    enum MenuAction {
+        Default,
+        OpenColorPicker,
+    }
+    let pure_menu = {
+        use swell_ui::menu_tree::*;
+        root_menu(vec![
+            item_with_opts(
+                "<Default color>",
+                ItemOpts {
+                    enabled: true,
+                    checked: current_color.is_none(),
+                },
+                || MenuAction::Default,
+            ),
+            item_with_opts(
+                "<Pick color...>",
+                ItemOpts {
+                    enabled: true,
+                    checked: matches!(current_color, Some(VirtualColor::Rgb(_))),
+                },
+                || MenuAction::OpenColorPicker,
+            ),
+        ])
+    };
+    let action = window
+        .open_simple_popup_menu(pure_menu, Window::cursor_pos())
+        .ok_or("color selection cancelled")?;
+    match action {
+        MenuAction::Default => Ok(None),
+        MenuAction::OpenColorPicker => {
+            let reaper = Reaper::get().medium_reaper();
+            let native_color = reaper
+                .gr_select_color(WindowContext::Win(window.raw_non_null()))
+                .ok_or("color picking cancelled")?;
+            let reaper_medium::RgbColor { r, g, b } = reaper.color_from_native(native_color);
+            Ok(Some(VirtualColor::Rgb(RgbColor::new(r, g, b))))
+        }
+    }
+}
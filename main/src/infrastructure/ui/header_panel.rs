@@ -7,8 +7,9 @@ use rxrust::prelude::*;
 use std::{iter, sync};
 
 use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::UnitValue;
 
-use reaper_high::{MidiInputDevice, MidiOutputDevice, Reaper};
+use reaper_high::{MidiInputDevice, MidiOutputDevice, Project, Reaper, Track};
 
 use reaper_medium::{MidiInputDeviceId, MidiOutputDeviceId, ReaperString};
 use slog::debug;
@@ -16,24 +17,33 @@ use slog::debug;
 use swell_ui::{Pixels, Point, SharedView, View, ViewContext, Window};
 
 use crate::application::{
-    reaper_supports_global_midi_filter, Affected, CompartmentCommand, CompartmentProp,
-    ControllerPreset, FxId, FxPresetLinkConfig, MainPreset, MainPresetAutoLoadMode, MappingCommand,
-    MappingModel, Preset, PresetLinkMutator, PresetManager, SessionCommand, SessionProp,
-    SharedMapping, SharedSession, VirtualControlElementType, WeakSession,
+    check_fx_parameter_range, find_missing_preset_references, fx_substitute_candidates,
+    get_track_label, reaper_supports_global_midi_filter, track_substitute_candidates, Affected,
+    CompartmentCommand, CompartmentLifecycleModel, CompartmentProp, ControllerPreset, FxId,
+    FxPresetLinkConfig, MainPreset, MainPresetAutoLoadMode, MappingCommand, MappingModel,
+    MessageTemplatesModel, MissingPresetReference, Preset, PresetLinkMutator, PresetManager,
+    PresetSubstitutions, SessionCommand, SessionProp, SharedMapping, SharedSession, TargetCategory,
+    VirtualControlElementType, WeakSession,
 };
-use crate::base::{when, Global};
+use crate::base::{notification, when, Global};
+use crate::domain::ui_util::{format_as_percentage_without_unit, parse_unit_value_from_percentage};
 use crate::domain::{
     convert_compartment_param_index_range_to_iter, BackboneState, ClipMatrixRef, Compartment,
-    CompartmentParamIndex, ControlInput, FeedbackOutput, GroupId, MessageCaptureEvent, OscDeviceId,
-    ParamSetting, ReaperTarget, StayActiveWhenProjectInBackground, COMPARTMENT_PARAMETER_COUNT,
+    CompartmentParamIndex, ControlInput, FeedbackOutput, GroupId, MessageCaptureEvent,
+    MissingTargetObject, OscDeviceId, ParamSetting, ProcessingTime, ReaperTarget,
+    StayActiveWhenProjectInBackground, TimeDisplayUnit, ValueDisplayPreferences,
+    VirtualControlElementSharing, VolumeDisplayUnit, XyPadRole, COMPARTMENT_PARAMETER_COUNT,
+    MIN_FEEDBACK_RESYNC_INTERVAL,
 };
 use crate::domain::{MidiControlInput, MidiDestination};
 use crate::infrastructure::data::{
-    CompartmentModelData, ExtendedPresetManager, FileBasedMainPresetManager, MappingModelData,
+    CompartmentDiff, CompartmentModelData, ControlsDocumentation, ExtendedPresetManager,
+    FileBasedMainPresetManager, MappingModelData, MappingPackConflictResolution, MappingPackData,
     OscDevice,
 };
 use crate::infrastructure::plugin::{
-    warn_about_failed_server_start, App, RealearnPluginParameters,
+    check_for_update, warn_about_failed_server_start, App, RealearnPluginParameters,
+    UpdateCheckTrigger,
 };
 
 use crate::infrastructure::ui::bindings::root;
@@ -41,15 +51,19 @@ use crate::infrastructure::ui::bindings::root;
 use crate::base::notification::notify_processing_result;
 use crate::infrastructure::api::convert::from_data::ConversionStyle;
 use crate::infrastructure::ui::dialog_util::add_group_via_dialog;
+use crate::infrastructure::ui::egui_views::test_feedback;
+use crate::infrastructure::ui::egui_views::test_feedback::TestFeedbackSource;
+use crate::infrastructure::ui::egui_views::touch_controller;
+use crate::infrastructure::ui::egui_views::touch_controller::{TouchControlElement, TouchXyPad};
 use crate::infrastructure::ui::util::{open_in_browser, open_in_file_manager};
 use crate::infrastructure::ui::{
     add_firewall_rule, copy_text_to_clipboard, deserialize_api_object_from_lua,
     deserialize_data_object, deserialize_data_object_from_json, dry_run_lua_script,
     get_text_from_clipboard, serialize_data_object, serialize_data_object_to_json,
-    serialize_data_object_to_lua, DataObject, GroupFilter, GroupPanel, IndependentPanelManager,
-    MappingRowsPanel, PlainTextEngine, ScriptEditorInput, SearchExpression, SerializationFormat,
-    SharedIndependentPanelManager, SharedMainState, SimpleScriptEditorPanel, SourceFilter,
-    UntaggedDataObject,
+    serialize_data_object_to_lua, DataObject, EguiPanel, GroupFilter, GroupPanel,
+    IndependentPanelManager, MappingRowsPanel, PlainTextEngine, ScriptEditorInput,
+    SearchExpression, SerializationFormat, SharedIndependentPanelManager, SharedMainState,
+    SimpleScriptEditorPanel, SourceFilter, UntaggedDataObject, YamlEditorPanel,
 };
 use crate::infrastructure::ui::{dialog_util, CompanionAppPresenter};
 use itertools::Itertools;
@@ -59,10 +73,13 @@ use std::cell::{Cell, RefCell};
 use std::error::Error;
 use std::net::Ipv4Addr;
 use std::ops::{DerefMut, RangeInclusive};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const OSC_INDEX_OFFSET: isize = 1000;
 const KEYBOARD_INDEX_OFFSET: isize = 2000;
 const PARAM_BATCH_SIZE: u32 = 5;
+const MAX_VALUE_DISPLAY_DECIMAL_PLACES: u8 = 10;
 
 /// The upper part of the main panel, containing buttons such as "Add mapping".
 #[derive(Debug)]
@@ -75,6 +92,10 @@ pub struct HeaderPanel {
     panel_manager: Weak<RefCell<IndependentPanelManager>>,
     group_panel: RefCell<Option<SharedView<GroupPanel>>>,
     notes_editor: RefCell<Option<SharedView<SimpleScriptEditorPanel>>>,
+    lifecycle_editor: RefCell<Option<SharedView<YamlEditorPanel>>>,
+    message_templates_editor: RefCell<Option<SharedView<YamlEditorPanel>>>,
+    test_feedback_panel: RefCell<Option<SharedView<EguiPanel<test_feedback::State>>>>,
+    touch_controller_panel: RefCell<Option<SharedView<EguiPanel<touch_controller::State>>>>,
     is_invoked_programmatically: Cell<bool>,
 }
 
@@ -94,6 +115,10 @@ impl HeaderPanel {
             panel_manager,
             group_panel: Default::default(),
             notes_editor: Default::default(),
+            lifecycle_editor: Default::default(),
+            message_templates_editor: Default::default(),
+            test_feedback_panel: Default::default(),
+            touch_controller_panel: Default::default(),
             is_invoked_programmatically: false.into(),
         }
     }
@@ -133,6 +158,282 @@ impl HeaderPanel {
         shared_editor.open(self.view.require_window());
     }
 
+    fn edit_controller_lifecycle(&self) {
+        let compartment = Compartment::Controller;
+        let initial_value = {
+            let session = self.session();
+            let session = session.borrow();
+            let lifecycle = session.compartment_lifecycle(compartment);
+            if lifecycle == &CompartmentLifecycleModel::default() {
+                None
+            } else {
+                serde_yaml::to_value(lifecycle)
+                    .ok()
+                    .and_then(|v| v.as_mapping().cloned())
+            }
+        };
+        let weak_session = self.session.clone();
+        let editor = YamlEditorPanel::new(initial_value, move |yaml_mapping| {
+            let session = match weak_session.upgrade() {
+                None => return,
+                Some(s) => s,
+            };
+            let lifecycle: CompartmentLifecycleModel = match yaml_mapping {
+                None => Default::default(),
+                Some(m) => match serde_yaml::from_value(serde_yaml::Value::Mapping(m)) {
+                    Ok(l) => l,
+                    Err(e) => {
+                        notification::alert(format!(
+                            "Your changes couldn't be applied because of the following error:\n\n{}",
+                            e
+                        ));
+                        return;
+                    }
+                },
+            };
+            if let Err(e) = lifecycle.create_controller_lifecycle_midi_data() {
+                notification::alert(format!(
+                    "Your changes couldn't be applied because of the following error:\n\n{}",
+                    e
+                ));
+                return;
+            }
+            session
+                .borrow_mut()
+                .set_compartment_lifecycle(compartment, lifecycle);
+        });
+        let editor = SharedView::new(editor);
+        if let Some(existing_editor) = self.lifecycle_editor.replace(Some(editor.clone())) {
+            existing_editor.close();
+        };
+        editor.open(self.view.require_window());
+    }
+
+    fn edit_controller_message_templates(&self) {
+        let compartment = Compartment::Controller;
+        let initial_value = {
+            let session = self.session();
+            let session = session.borrow();
+            let templates = session.compartment_message_templates(compartment);
+            if templates.is_empty() {
+                None
+            } else {
+                serde_yaml::to_value(templates)
+                    .ok()
+                    .and_then(|v| v.as_mapping().cloned())
+            }
+        };
+        let weak_session = self.session.clone();
+        let editor = YamlEditorPanel::new(initial_value, move |yaml_mapping| {
+            let session = match weak_session.upgrade() {
+                None => return,
+                Some(s) => s,
+            };
+            let templates: MessageTemplatesModel = match yaml_mapping {
+                None => Default::default(),
+                Some(m) => match serde_yaml::from_value(serde_yaml::Value::Mapping(m)) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        notification::alert(format!(
+                            "Your changes couldn't be applied because of the following error:\n\n{}",
+                            e
+                        ));
+                        return;
+                    }
+                },
+            };
+            if let Err(e) = templates.validate() {
+                notification::alert(format!(
+                    "Your changes couldn't be applied because of the following error:\n\n{}",
+                    e
+                ));
+                return;
+            }
+            session
+                .borrow_mut()
+                .set_compartment_message_templates(compartment, templates);
+        });
+        let editor = SharedView::new(editor);
+        if let Some(existing_editor) = self.message_templates_editor.replace(Some(editor.clone())) {
+            existing_editor.close();
+        };
+        editor.open(self.view.require_window());
+    }
+
+    fn open_test_feedback_panel(&self) {
+        let state = {
+            let session = self.session();
+            let session = session.borrow();
+            let sources = session
+                .mappings(Compartment::Controller)
+                .map(|m| {
+                    let m = m.borrow();
+                    TestFeedbackSource {
+                        mapping_id: m.qualified_id(),
+                        label: format!("{} ({})", m.effective_name(), m.source_model),
+                    }
+                })
+                .collect();
+            test_feedback::State::new(sources, session.test_feedback_sender())
+        };
+        let panel = SharedView::new(EguiPanel::new(
+            "Test feedback",
+            state,
+            test_feedback::run_ui,
+        ));
+        if let Some(existing_panel) = self.test_feedback_panel.replace(Some(panel.clone())) {
+            existing_panel.close();
+        };
+        panel.open(self.view.require_window());
+    }
+
+    fn export_controls_documentation(&self) -> Result<(), &'static str> {
+        let html = {
+            let session = self.session();
+            let session = session.borrow();
+            ControlsDocumentation::from_session(&session, self.active_compartment())
+                .render_as_html()?
+        };
+        let dir = App::get_temp_dir().ok_or("couldn't create temporary directory")?;
+        let file = dir.path().join("controls-documentation.html");
+        std::fs::write(&file, html).map_err(|_| "couldn't write controls documentation")?;
+        open_in_browser(&file.to_string_lossy());
+        Ok(())
+    }
+
+    fn show_processing_times(&self) {
+        let msg = {
+            let session = self.session();
+            let session = session.borrow();
+            let instance_state = session.instance_state().borrow();
+            let format_time = |t: ProcessingTime| {
+                format!(
+                    "{:.1} ms in total, {} invocations, {} µs on average, {} µs max",
+                    t.total.as_secs_f64() * 1000.0,
+                    t.count,
+                    t.average().as_micros(),
+                    t.max.as_micros()
+                )
+            };
+            let compartment_lines: Vec<_> = Compartment::enum_iter()
+                .map(|c| {
+                    format!(
+                        "- {}: {}",
+                        c,
+                        format_time(instance_state.compartment_processing_time(c))
+                    )
+                })
+                .collect();
+            let mapping_text = if instance_state.mapping_metering_enabled() {
+                let lines: Vec<_> = instance_state
+                    .most_expensive_mappings()
+                    .into_iter()
+                    .take(MAX_LISTED_EXPENSIVE_MAPPINGS)
+                    .map(|(id, t)| {
+                        let name = session
+                            .find_mapping_and_index_by_qualified_id(id)
+                            .map(|(_, m)| m.borrow().effective_name())
+                            .unwrap_or_else(|| "<Removed>".to_string());
+                        format!("- {} ({}): {}", name, id.compartment, format_time(t))
+                    })
+                    .collect();
+                format!("Most expensive mappings:\n\n{}", lines.join("\n"))
+            } else {
+                "Enable \"Meter processing time of individual mappings\" in order to see which \
+                mappings are the most expensive ones."
+                    .to_string()
+            };
+            format!(
+                "Time spent controlling mappings since the instance was loaded:\n\n{}\n\n{}\n\n\
+                Do you want to reset the processing times?",
+                compartment_lines.join("\n"),
+                mapping_text
+            )
+        };
+        if self.view.require_window().confirm("ReaLearn", msg) {
+            self.session()
+                .borrow()
+                .instance_state()
+                .borrow_mut()
+                .reset_processing_times();
+        }
+    }
+
+    fn show_notifications(&self) {
+        let msg = {
+            let session = self.session();
+            let session = session.borrow();
+            let instance_state = session.instance_state().borrow();
+            let lines: Vec<_> = instance_state
+                .notifications()
+                .entries()
+                .map(|e| {
+                    let description = session.describe_notification(&e.notification);
+                    if e.occurrence_count > 1 {
+                        format!("- {} ({}x)", description, e.occurrence_count)
+                    } else {
+                        format!("- {}", description)
+                    }
+                })
+                .collect();
+            format!(
+                "Recent problems (latest first):\n\n{}\n\nDo you want to clear this list?",
+                lines.join("\n")
+            )
+        };
+        if self.view.require_window().confirm("ReaLearn", msg) {
+            self.session()
+                .borrow()
+                .instance_state()
+                .borrow_mut()
+                .clear_notifications();
+        }
+    }
+
+    fn open_touch_controller_panel(&self) {
+        let state = {
+            let session = self.session();
+            let session = session.borrow();
+            let mut control_elements = vec![];
+            for m in session.mappings(Compartment::Controller) {
+                let m = m.borrow();
+                if m.target_model.category() != TargetCategory::Virtual {
+                    continue;
+                }
+                let control_element = m.target_model.create_control_element();
+                if !control_elements.contains(&control_element) {
+                    control_elements.push(control_element);
+                }
+            }
+            let mut elements = vec![];
+            let mut xy_pads: Vec<TouchXyPad> = vec![];
+            for e in control_elements {
+                if let Some((pad_name, role)) = XyPadRole::parse(e) {
+                    let pad = match xy_pads.iter().position(|p| p.label() == pad_name) {
+                        None => {
+                            xy_pads.push(TouchXyPad::new(pad_name));
+                            xy_pads.last_mut().unwrap()
+                        }
+                        Some(i) => &mut xy_pads[i],
+                    };
+                    pad.set_element(role, e);
+                } else {
+                    elements.push(TouchControlElement::new(e, e.to_string()));
+                }
+            }
+            touch_controller::State::new(elements, xy_pads, session.virtual_control_sender())
+        };
+        let panel = SharedView::new(EguiPanel::new(
+            "Touch controller",
+            state,
+            touch_controller::run_ui,
+        ));
+        if let Some(existing_panel) = self.touch_controller_panel.replace(Some(panel.clone())) {
+            existing_panel.close();
+        };
+        panel.open(self.view.require_window());
+    }
+
     pub fn handle_changed_midi_devices(&self) {
         if !self.is_open() {
             return;
@@ -271,6 +572,7 @@ impl HeaderPanel {
             let preset_link_manager = preset_link_manager.borrow();
             let main_preset_manager = App::get().main_preset_manager();
             let main_preset_manager = main_preset_manager.borrow();
+            let setups = app.setups().unwrap_or_default().setups;
             let text_from_clipboard = Rc::new(get_text_from_clipboard().unwrap_or_default());
             let text_from_clipboard_clone = text_from_clipboard.clone();
             let data_object_from_clipboard = if text_from_clipboard.is_empty() {
@@ -289,6 +591,11 @@ impl HeaderPanel {
                 .is_some();
             let compartment = self.active_compartment();
             let group_id = self.active_group_id();
+            let mapping_pack_files = find_mapping_pack_files();
+            let preset_backups = match session.active_preset_id(compartment) {
+                None => vec![],
+                Some(id) => App::get().preset_manager(compartment).preset_backups(id),
+            };
             let last_focused_fx_id = App::get().previously_focused_fx().and_then(|fx| {
                 if fx.is_available() {
                     FxId::from_fx(&fx, true).ok()
@@ -322,6 +629,25 @@ impl HeaderPanel {
                 item("Make targets of listed mappings sticky", || {
                     MainMenuAction::MakeTargetsOfListedMappingsSticky
                 }),
+                item("Fix FX references of listed mappings", || {
+                    MainMenuAction::FixFxReferencesOfListedMappings
+                }),
+                item_with_opts(
+                    "Map parameters of last focused FX...",
+                    ItemOpts {
+                        enabled: compartment == Compartment::Main && last_focused_fx_id.is_some(),
+                        checked: false,
+                    },
+                    || MainMenuAction::MapParametersOfLastFocusedFx,
+                ),
+                item_with_opts(
+                    "Add OSC XY pad...",
+                    ItemOpts {
+                        enabled: compartment == Compartment::Controller,
+                        checked: false,
+                    },
+                    || MainMenuAction::AddOscXyPad,
+                ),
                 menu(
                     "Move listed mappings to group",
                     iter::once(item("<New group>", || {
@@ -341,6 +667,43 @@ impl HeaderPanel {
                     }))
                     .collect(),
                 ),
+                menu(
+                    "Mapping packs",
+                    vec![
+                        item_with_opts(
+                            "Export current group as mapping pack",
+                            ItemOpts {
+                                enabled: group_id.map(|id| !id.is_default()).unwrap_or(false),
+                                checked: false,
+                            },
+                            move || {
+                                MainMenuAction::ExportGroupAsMappingPack(
+                                    group_id.unwrap_or_default(),
+                                )
+                            },
+                        ),
+                        menu(
+                            "Import mapping pack",
+                            if mapping_pack_files.is_empty() {
+                                vec![disabled_item("<No mapping packs found>")]
+                            } else {
+                                mapping_pack_files
+                                    .into_iter()
+                                    .map(|path| {
+                                        let label = path
+                                            .file_stem()
+                                            .map(|s| s.to_string_lossy().to_string())
+                                            .unwrap_or_default();
+                                        item(label, move || MainMenuAction::ImportMappingPack(path))
+                                    })
+                                    .collect()
+                            },
+                        ),
+                        item("Open mapping pack folder", || {
+                            MainMenuAction::OpenMappingPackFolder
+                        }),
+                    ],
+                ),
                 menu(
                     "Advanced",
                     vec![
@@ -418,6 +781,117 @@ impl HeaderPanel {
                             },
                             || MainMenuAction::ToggleResetFeedbackWhenReleasingSource,
                         ),
+                        item_with_opts(
+                            "Send MIDI clock to feedback output",
+                            ItemOpts {
+                                enabled: true,
+                                checked: session.send_midi_clock.get(),
+                            },
+                            || MainMenuAction::ToggleSendMidiClock,
+                        ),
+                        item_with_opts(
+                            "MIDI pass-through filter...",
+                            ItemOpts {
+                                enabled: true,
+                                checked: !session.midi_pass_through_filter.get().is_pass_all(),
+                            },
+                            || MainMenuAction::EditMidiPassThroughFilter,
+                        ),
+                        item_with_opts(
+                            "Feedback brightness...",
+                            ItemOpts {
+                                enabled: true,
+                                checked: session.instance_state().borrow().feedback_brightness()
+                                    != UnitValue::MAX,
+                            },
+                            || MainMenuAction::EditFeedbackBrightness,
+                        ),
+                        item_with_opts(
+                            "Feedback resync interval...",
+                            ItemOpts {
+                                enabled: true,
+                                checked: session
+                                    .instance_state()
+                                    .borrow()
+                                    .feedback_resync_interval()
+                                    .is_some(),
+                            },
+                            || MainMenuAction::EditFeedbackResyncInterval,
+                        ),
+                        item_with_opts(
+                            "Parameter thinning...",
+                            ItemOpts {
+                                enabled: true,
+                                checked: !session
+                                    .instance_state()
+                                    .borrow()
+                                    .parameter_thinning()
+                                    .is_off(),
+                            },
+                            || MainMenuAction::EditParameterThinning,
+                        ),
+                        {
+                            let prefs = session
+                                .instance_state()
+                                .borrow()
+                                .value_display_preferences();
+                            menu(
+                                "Value display",
+                                iter::once(item_with_opts(
+                                    "Decimal places...",
+                                    ItemOpts {
+                                        enabled: true,
+                                        checked: prefs.decimal_places.is_some(),
+                                    },
+                                    || MainMenuAction::EditValueDisplayDecimalPlaces,
+                                ))
+                                .chain(iter::once(menu(
+                                    "Volume unit",
+                                    VolumeDisplayUnit::into_enum_iter()
+                                        .map(|option| {
+                                            item_with_opts(
+                                                option.to_string(),
+                                                ItemOpts {
+                                                    enabled: true,
+                                                    checked: prefs.volume_unit == option,
+                                                },
+                                                move || {
+                                                    MainMenuAction::SetValueDisplayPreferences(
+                                                        ValueDisplayPreferences {
+                                                            volume_unit: option,
+                                                            ..prefs
+                                                        },
+                                                    )
+                                                },
+                                            )
+                                        })
+                                        .collect(),
+                                )))
+                                .chain(iter::once(menu(
+                                    "Time unit",
+                                    TimeDisplayUnit::into_enum_iter()
+                                        .map(|option| {
+                                            item_with_opts(
+                                                option.to_string(),
+                                                ItemOpts {
+                                                    enabled: true,
+                                                    checked: prefs.time_unit == option,
+                                                },
+                                                move || {
+                                                    MainMenuAction::SetValueDisplayPreferences(
+                                                        ValueDisplayPreferences {
+                                                            time_unit: option,
+                                                            ..prefs
+                                                        },
+                                                    )
+                                                },
+                                            )
+                                        })
+                                        .collect(),
+                                )))
+                                .collect(),
+                            )
+                        },
                         item_with_opts(
                             "Make instance superior",
                             ItemOpts {
@@ -456,6 +930,24 @@ impl HeaderPanel {
                                 })
                                 .collect(),
                         ),
+                        menu(
+                            "Share virtual control elements",
+                            VirtualControlElementSharing::into_enum_iter()
+                                .map(|option| {
+                                    item_with_opts(
+                                        option.to_string(),
+                                        ItemOpts {
+                                            enabled: true,
+                                            checked: session.virtual_control_element_sharing.get()
+                                                == option,
+                                        },
+                                        move || {
+                                            MainMenuAction::SetVirtualControlElementSharing(option)
+                                        },
+                                    )
+                                })
+                                .collect(),
+                        ),
                     ],
                 ),
                 menu(
@@ -492,6 +984,25 @@ impl HeaderPanel {
                         })
                         .collect(),
                 ),
+                item_with_opts(
+                    "Controller init/shutdown messages...",
+                    ItemOpts {
+                        enabled: compartment == Compartment::Controller,
+                        checked: session.compartment_lifecycle(Compartment::Controller)
+                            != &CompartmentLifecycleModel::default(),
+                    },
+                    || MainMenuAction::EditControllerLifecycle,
+                ),
+                item_with_opts(
+                    "Controller message templates...",
+                    ItemOpts {
+                        enabled: compartment == Compartment::Controller,
+                        checked: !session
+                            .compartment_message_templates(Compartment::Controller)
+                            .is_empty(),
+                    },
+                    || MainMenuAction::EditControllerMessageTemplates,
+                ),
                 menu(
                     "Instance-wide FX-to-preset links",
                     generate_fx_to_preset_links_menu_entries(
@@ -502,6 +1013,36 @@ impl HeaderPanel {
                     ),
                 ),
                 separator(),
+                menu(
+                    "Setups",
+                    setups
+                        .iter()
+                        .map(|setup| {
+                            let name = setup.name.clone();
+                            item(setup.name.clone(), move || MainMenuAction::ApplySetup(name))
+                        })
+                        .chain([
+                            separator(),
+                            item("Save current setup as...", || MainMenuAction::SaveSetup),
+                            if setups.is_empty() {
+                                disabled_item("Remove setup")
+                            } else {
+                                menu(
+                                    "Remove setup",
+                                    setups
+                                        .iter()
+                                        .map(|setup| {
+                                            let name = setup.name.clone();
+                                            item(setup.name.clone(), move || {
+                                                MainMenuAction::RemoveSetup(name)
+                                            })
+                                        })
+                                        .collect(),
+                                )
+                            },
+                        ])
+                        .collect(),
+                ),
                 menu(
                     "Server",
                     vec![
@@ -573,6 +1114,29 @@ impl HeaderPanel {
                 item("Reload all presets from disk", || {
                     MainMenuAction::ReloadAllPresets
                 }),
+                item_with_opts(
+                    "Compare with preset...",
+                    ItemOpts {
+                        enabled: session.active_preset_id(compartment).is_some(),
+                        checked: false,
+                    },
+                    || MainMenuAction::CompareWithPreset,
+                ),
+                if preset_backups.is_empty() {
+                    disabled_item("Restore previous version of preset")
+                } else {
+                    menu(
+                        "Restore previous version of preset",
+                        preset_backups
+                            .into_iter()
+                            .map(|backup| {
+                                item(backup.label, move || {
+                                    MainMenuAction::RestorePresetBackup(backup.id)
+                                })
+                            })
+                            .collect(),
+                    )
+                },
                 separator(),
                 menu(
                     "Logging",
@@ -618,9 +1182,47 @@ impl HeaderPanel {
                             },
                             || MainMenuAction::ToggleRealOutputLogging,
                         ),
+                        separator(),
+                        item_with_opts(
+                            "Meter processing time of individual mappings",
+                            ItemOpts {
+                                enabled: true,
+                                checked: session
+                                    .instance_state()
+                                    .borrow()
+                                    .mapping_metering_enabled(),
+                            },
+                            || MainMenuAction::ToggleMappingMetering,
+                        ),
+                        item("Show processing times...", || {
+                            MainMenuAction::ShowProcessingTimes
+                        }),
                     ],
                 ),
                 item("Send feedback now", || MainMenuAction::SendFeedbackNow),
+                item("Test feedback...", || MainMenuAction::OpenTestFeedbackPanel),
+                item_with_opts(
+                    "Simulate feedback if no output selected",
+                    ItemOpts {
+                        enabled: true,
+                        checked: session.simulate_feedback.get(),
+                    },
+                    || MainMenuAction::ToggleSimulateFeedback,
+                ),
+                item("Touch controller...", || {
+                    MainMenuAction::OpenTouchControllerPanel
+                }),
+                item("Export controls documentation", || {
+                    MainMenuAction::ExportControlsDocumentation
+                }),
+                item_with_opts(
+                    "Show notifications...",
+                    ItemOpts {
+                        enabled: !session.instance_state().borrow().notifications().is_empty(),
+                        checked: false,
+                    },
+                    || MainMenuAction::ShowNotifications,
+                ),
             ];
             root_menu(entries)
         };
@@ -644,12 +1246,32 @@ impl HeaderPanel {
             MainMenuAction::MakeTargetsOfListedMappingsSticky => {
                 self.make_targets_of_listed_mappings_sticky()
             }
+            MainMenuAction::FixFxReferencesOfListedMappings => {
+                self.fix_fx_references_of_listed_mappings()
+            }
+            MainMenuAction::MapParametersOfLastFocusedFx => {
+                let result = self.map_parameters_of_last_focused_fx();
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
+            MainMenuAction::AddOscXyPad => {
+                let result = self.add_osc_xy_pad();
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
             MainMenuAction::MoveListedMappingsToGroup(group_id) => {
                 let _ = self.move_listed_mappings_to_group(group_id);
             }
             MainMenuAction::PasteReplaceAllInGroup(mapping_datas) => {
                 self.paste_replace_all_in_group(mapping_datas)
             }
+            MainMenuAction::ExportGroupAsMappingPack(group_id) => {
+                let result = self.export_group_as_mapping_pack(group_id);
+                self.notify_user_on_error(result);
+            }
+            MainMenuAction::ImportMappingPack(path) => {
+                let result = self.import_mapping_pack_from_file(&path);
+                self.notify_user_on_error(result);
+            }
+            MainMenuAction::OpenMappingPackFolder => self.open_mapping_pack_folder(),
             MainMenuAction::CopyListedMappingsAsLua(style) => {
                 self.copy_listed_mappings_as_lua(style).unwrap()
             }
@@ -676,6 +1298,10 @@ impl HeaderPanel {
             MainMenuAction::EditCompartmentParameter(compartment, range) => {
                 let _ = edit_compartment_parameter(self.session(), compartment, range);
             }
+            MainMenuAction::EditControllerLifecycle => self.edit_controller_lifecycle(),
+            MainMenuAction::EditControllerMessageTemplates => {
+                self.edit_controller_message_templates()
+            }
             MainMenuAction::FreezeClipMatrix => {
                 self.freeze_clip_matrix();
             }
@@ -683,18 +1309,35 @@ impl HeaderPanel {
             MainMenuAction::ToggleRealInputLogging => self.toggle_real_input_logging(),
             MainMenuAction::ToggleVirtualInputLogging => self.toggle_virtual_input_logging(),
             MainMenuAction::ToggleRealOutputLogging => self.toggle_real_output_logging(),
+            MainMenuAction::ToggleSimulateFeedback => self.toggle_simulate_feedback(),
             MainMenuAction::ToggleVirtualOutputLogging => self.toggle_virtual_output_logging(),
             MainMenuAction::ToggleTargetControlLogging => self.toggle_target_control_logging(),
+            MainMenuAction::ToggleMappingMetering => self.toggle_mapping_metering(),
+            MainMenuAction::ShowProcessingTimes => self.show_processing_times(),
             MainMenuAction::ToggleSendFeedbackOnlyIfTrackArmed => {
                 self.toggle_send_feedback_only_if_armed()
             }
             MainMenuAction::ToggleResetFeedbackWhenReleasingSource => {
                 self.toggle_reset_feedback_when_releasing_source()
             }
+            MainMenuAction::ToggleSendMidiClock => self.toggle_send_midi_clock(),
+            MainMenuAction::EditMidiPassThroughFilter => self.edit_midi_pass_through_filter(),
+            MainMenuAction::EditFeedbackBrightness => self.edit_feedback_brightness(),
+            MainMenuAction::EditFeedbackResyncInterval => self.edit_feedback_resync_interval(),
+            MainMenuAction::EditParameterThinning => self.edit_parameter_thinning(),
+            MainMenuAction::EditValueDisplayDecimalPlaces => {
+                self.edit_value_display_decimal_places()
+            }
+            MainMenuAction::SetValueDisplayPreferences(prefs) => {
+                self.set_value_display_preferences(prefs)
+            }
             MainMenuAction::ToggleUpperFloorMembership => self.toggle_upper_floor_membership(),
             MainMenuAction::SetStayActiveWhenProjectInBackground(option) => {
                 self.set_stay_active_when_project_in_background(option)
             }
+            MainMenuAction::SetVirtualControlElementSharing(option) => {
+                self.set_virtual_control_element_sharing(option)
+            }
             MainMenuAction::ToggleServer => {
                 if app.server_is_running() {
                     app.stop_server_persistently();
@@ -733,9 +1376,36 @@ impl HeaderPanel {
                 self.view.require_window().alert("ReaLearn", msg);
             }
             MainMenuAction::ChangeSessionId => self.change_session_id(),
+            MainMenuAction::ApplySetup(name) => {
+                let result = app.apply_setup(&self.session(), &name);
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
+            MainMenuAction::SaveSetup => {
+                let result = self.save_current_setup();
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
+            MainMenuAction::RemoveSetup(name) => {
+                let result = app.remove_setup(&name);
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
             MainMenuAction::ReloadAllPresets => self.reload_all_presets(),
+            MainMenuAction::RestorePresetBackup(backup_id) => {
+                let result = self.restore_active_preset_backup(&backup_id);
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
+            MainMenuAction::CompareWithPreset => {
+                let result = self.compare_with_preset();
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
             MainMenuAction::OpenPresetFolder => self.open_preset_folder(),
             MainMenuAction::SendFeedbackNow => self.session().borrow().send_all_feedback(),
+            MainMenuAction::OpenTestFeedbackPanel => self.open_test_feedback_panel(),
+            MainMenuAction::OpenTouchControllerPanel => self.open_touch_controller_panel(),
+            MainMenuAction::ExportControlsDocumentation => {
+                let result = self.export_controls_documentation();
+                self.notify_user_on_error(result.map_err(|e| e.into()));
+            }
+            MainMenuAction::ShowNotifications => self.show_notifications(),
             MainMenuAction::LogDebugInfo => self.log_debug_info(),
             MainMenuAction::EditPresetLinkFxId(scope, fx_id) => {
                 with_scoped_preset_link_mutator(scope, &self.session, |m| {
@@ -771,6 +1441,16 @@ impl HeaderPanel {
                 item("Contact developer", || HelpMenuAction::ContactDeveloper),
                 item("Website", || HelpMenuAction::OpenWebsite),
                 item("Donate", || HelpMenuAction::Donate),
+                separator(),
+                item("Check for updates now", || HelpMenuAction::CheckForUpdates),
+                item_with_opts(
+                    "Check for updates on startup",
+                    ItemOpts {
+                        enabled: true,
+                        checked: App::get().config().update_check_is_enabled(),
+                    },
+                    || HelpMenuAction::ToggleUpdateCheck,
+                ),
             ];
             root_menu(entries)
         };
@@ -787,6 +1467,12 @@ impl HeaderPanel {
             HelpMenuAction::ContactDeveloper => self.contact_developer(),
             HelpMenuAction::OpenWebsite => self.open_website(),
             HelpMenuAction::Donate => self.donate(),
+            HelpMenuAction::CheckForUpdates => check_for_update(UpdateCheckTrigger::User),
+            HelpMenuAction::ToggleUpdateCheck => {
+                let app = App::get();
+                let enabled = app.config().update_check_is_enabled();
+                app.set_update_check_enabled_persistently(!enabled);
+            }
         };
         Ok(())
     }
@@ -869,6 +1555,7 @@ impl HeaderPanel {
         }
         let session = self.session();
         let mut session = session.borrow_mut();
+        session.begin_bulk_edit();
         for m in listed_mappings {
             let mut mapping = m.borrow_mut();
             let new_name = get_name(&mapping);
@@ -879,6 +1566,7 @@ impl HeaderPanel {
                 self.session.clone(),
             );
         }
+        session.end_bulk_edit(self.session.clone());
     }
 
     fn make_sources_of_main_mappings_virtual(&self) {
@@ -896,6 +1584,105 @@ impl HeaderPanel {
         self.notify_user_on_error(result.map_err(|e| e.into()));
     }
 
+    fn map_parameters_of_last_focused_fx(&self) -> Result<(), &'static str> {
+        let fx = App::get()
+            .previously_focused_fx()
+            .filter(|fx| fx.is_available())
+            .ok_or("last focused FX not available")?;
+        let param_count = fx.parameter_count();
+        let control_element_type = match self.prompt_for_control_element_type() {
+            None => return Ok(()),
+            Some(t) => t,
+        };
+        let csv = match Reaper::get().medium_reaper().get_user_inputs(
+            "ReaLearn",
+            4,
+            "First control element (e.g. 1),First parameter (e.g. 1),Parameter count,New group name (optional),separator=;,extrawidth=80",
+            format!("1;1;{};", param_count),
+            512,
+        ) {
+            None => return Ok(()),
+            Some(csv) => csv,
+        };
+        let splitted: Vec<_> = csv.to_str().split(';').collect();
+        let (first_element, first_param, count, group_name) =
+            if let [first_element, first_param, count, group_name] = splitted.as_slice() {
+                (*first_element, *first_param, *count, group_name.trim())
+            } else {
+                return Err("couldn't split");
+            };
+        let parse_position = |text: &str| -> Result<u32, &'static str> {
+            match text.trim().parse::<u32>() {
+                Ok(pos) if pos > 0 => Ok(pos - 1),
+                _ => Err("control element and parameter must be positive numbers"),
+            }
+        };
+        let first_element_index = parse_position(first_element)?;
+        let first_param_index = parse_position(first_param)?;
+        let count: u32 = count
+            .trim()
+            .parse()
+            .map_err(|_| "invalid parameter count")?;
+        let param_indexes = first_param_index..first_param_index + count;
+        // Validate before creating the group, otherwise we would leave an empty group behind.
+        check_fx_parameter_range(&fx, &param_indexes)?;
+        let session = self.session();
+        let group_id = if group_name.is_empty() {
+            self.active_group_id().unwrap_or_default()
+        } else {
+            session
+                .borrow_mut()
+                .add_group_with_default_values(Compartment::Main, group_name.to_string())
+        };
+        self.main_state.borrow_mut().clear_all_filters();
+        session.borrow_mut().add_mappings_for_fx_parameters(
+            group_id,
+            &fx,
+            param_indexes,
+            control_element_type,
+            first_element_index,
+        )
+    }
+
+    fn add_osc_xy_pad(&self) -> Result<(), &'static str> {
+        let session = self.session();
+        let pad_count = session
+            .borrow()
+            .mappings(Compartment::Controller)
+            .filter(|m| {
+                let m = m.borrow();
+                m.target_model.is_virtual()
+                    && XyPadRole::parse(m.target_model.create_control_element())
+                        .map(|(_, role)| role == XyPadRole::Touch)
+                        .unwrap_or(false)
+            })
+            .count();
+        let csv = match Reaper::get().medium_reaper().get_user_inputs(
+            "ReaLearn",
+            2,
+            "XY pad name,OSC address,separator=;,extrawidth=80",
+            format!("XY{};/xy{}", pad_count + 1, pad_count + 1),
+            512,
+        ) {
+            None => return Ok(()),
+            Some(csv) => csv,
+        };
+        let splitted: Vec<_> = csv.to_str().split(';').collect();
+        let (pad_name, osc_address) = if let [pad_name, osc_address] = splitted.as_slice() {
+            (pad_name.trim(), osc_address.trim())
+        } else {
+            return Err("couldn't split");
+        };
+        if pad_name.is_empty() {
+            return Err("XY pad name must not be empty");
+        }
+        let group_id = self.active_group_id().unwrap_or_default();
+        self.main_state.borrow_mut().clear_all_filters();
+        session
+            .borrow_mut()
+            .add_osc_xy_pad_mappings(group_id, pad_name, osc_address)
+    }
+
     fn make_targets_of_listed_mappings_sticky(&self) {
         let compartment = self.active_compartment();
         let listed_mappings = self.get_listened_mappings(compartment);
@@ -933,6 +1720,37 @@ impl HeaderPanel {
         }
     }
 
+    fn fix_fx_references_of_listed_mappings(&self) {
+        let compartment = self.active_compartment();
+        let ids: Vec<_> = self
+            .get_listened_mappings(compartment)
+            .iter()
+            .map(|m| m.borrow().qualified_id())
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+        let session = self.session();
+        let mut session = session.borrow_mut();
+        let errors: Vec<_> = session
+            .fix_fx_references_of_mapping_targets(ids, self.session.clone())
+            .into_iter()
+            .map(|(id, e)| {
+                let name = session
+                    .find_mapping_and_index_by_qualified_id(id)
+                    .map(|(_, m)| m.borrow().effective_name())
+                    .unwrap_or_default();
+                format!(
+                    "Couldn't fix FX reference of mapping {} because {}",
+                    name, e
+                )
+            })
+            .collect();
+        if !errors.is_empty() {
+            notify_processing_result("Errors occurred when fixing FX references", errors);
+        }
+    }
+
     fn move_listed_mappings_to_group(&self, group_id: Option<GroupId>) -> Result<(), &'static str> {
         let group_id = group_id
             .or_else(|| self.add_group_internal().ok())
@@ -1158,41 +1976,206 @@ impl HeaderPanel {
             );
     }
 
-    // TODO-high-clip-matrix As soon as we implement this, we need to fix the clippy error.
-    #[allow(clippy::await_holding_refcell_ref)]
-    fn freeze_clip_matrix(&self) {
-        let weak_session = self.session.clone();
-        Global::future_support().spawn_in_main_thread_from_main_thread(async move {
-            let shared_session = weak_session.upgrade().expect("session gone");
-            let shared_instance_state = { shared_session.borrow().instance_state().clone() };
-            shared_instance_state
-                .borrow_mut()
-                .owned_clip_matrix_mut()
-                .expect("this instance has no clip matrix")
-                .freeze()
-                .await;
-        });
+    // TODO-high-clip-matrix As soon as we implement this, we need to fix the clippy error.
+    #[allow(clippy::await_holding_refcell_ref)]
+    fn freeze_clip_matrix(&self) {
+        let weak_session = self.session.clone();
+        Global::future_support().spawn_in_main_thread_from_main_thread(async move {
+            let shared_session = weak_session.upgrade().expect("session gone");
+            let shared_instance_state = { shared_session.borrow().instance_state().clone() };
+            shared_instance_state
+                .borrow_mut()
+                .owned_clip_matrix_mut()
+                .expect("this instance has no clip matrix")
+                .freeze()
+                .await;
+        });
+    }
+
+    fn toggle_send_feedback_only_if_armed(&self) {
+        self.session()
+            .borrow_mut()
+            .send_feedback_only_if_armed
+            .set_with(|prev| !*prev);
+    }
+
+    fn set_stay_active_when_project_in_background(&self, value: StayActiveWhenProjectInBackground) {
+        self.session()
+            .borrow_mut()
+            .stay_active_when_project_in_background
+            .set(value);
+    }
+
+    fn set_virtual_control_element_sharing(&self, value: VirtualControlElementSharing) {
+        self.session()
+            .borrow_mut()
+            .virtual_control_element_sharing
+            .set(value);
+    }
+
+    fn toggle_reset_feedback_when_releasing_source(&self) {
+        self.session()
+            .borrow_mut()
+            .reset_feedback_when_releasing_source
+            .set_with(|prev| !*prev);
+    }
+
+    fn toggle_send_midi_clock(&self) {
+        self.session()
+            .borrow_mut()
+            .send_midi_clock
+            .set_with(|prev| !*prev);
+    }
+
+    fn edit_midi_pass_through_filter(&self) {
+        let current_filter = self.session().borrow().midi_pass_through_filter.get();
+        let mut text = current_filter.to_string();
+        loop {
+            text = match dialog_util::prompt_for("Filter (e.g. block cc; pass ch 1)", &text) {
+                None => return,
+                Some(t) => t,
+            };
+            match text.parse() {
+                Ok(filter) => {
+                    self.session()
+                        .borrow_mut()
+                        .midi_pass_through_filter
+                        .set(filter);
+                    return;
+                }
+                Err(msg) => {
+                    self.view
+                        .require_window()
+                        .alert("ReaLearn", format!("Invalid filter: {}", msg));
+                }
+            }
+        }
+    }
+
+    fn edit_feedback_brightness(&self) {
+        let instance_state = self.session().borrow().instance_state().clone();
+        let current_brightness = instance_state.borrow().feedback_brightness();
+        let mut text = format_as_percentage_without_unit(current_brightness);
+        loop {
+            text = match dialog_util::prompt_for("Feedback brightness (%)", &text) {
+                None => return,
+                Some(t) => t,
+            };
+            match parse_unit_value_from_percentage(&text) {
+                Ok(brightness) => {
+                    instance_state
+                        .borrow_mut()
+                        .set_feedback_brightness(brightness);
+                    return;
+                }
+                Err(msg) => {
+                    self.view
+                        .require_window()
+                        .alert("ReaLearn", format!("Invalid brightness: {}", msg));
+                }
+            }
+        }
+    }
+
+    fn edit_parameter_thinning(&self) {
+        let instance_state = self.session().borrow().instance_state().clone();
+        let current_thinning = instance_state.borrow().parameter_thinning();
+        let mut text = current_thinning.to_string();
+        loop {
+            text = match dialog_util::prompt_for("Parameter thinning (ms, %)", &text) {
+                None => return,
+                Some(t) => t,
+            };
+            match text.parse() {
+                Ok(thinning) => {
+                    instance_state.borrow_mut().set_parameter_thinning(thinning);
+                    return;
+                }
+                Err(msg) => {
+                    self.view
+                        .require_window()
+                        .alert("ReaLearn", format!("Invalid parameter thinning: {}", msg));
+                }
+            }
+        }
     }
 
-    fn toggle_send_feedback_only_if_armed(&self) {
-        self.session()
-            .borrow_mut()
-            .send_feedback_only_if_armed
-            .set_with(|prev| !*prev);
+    fn edit_feedback_resync_interval(&self) {
+        let instance_state = self.session().borrow().instance_state().clone();
+        let current_interval = instance_state.borrow().feedback_resync_interval();
+        let mut text = match current_interval {
+            None => "Off".to_string(),
+            Some(i) => i.as_secs_f64().to_string(),
+        };
+        loop {
+            text = match dialog_util::prompt_for("Feedback resync interval (s)", &text) {
+                None => return,
+                Some(t) => t,
+            };
+            match parse_feedback_resync_interval(&text) {
+                Ok(interval) => {
+                    instance_state
+                        .borrow_mut()
+                        .set_feedback_resync_interval(interval);
+                    return;
+                }
+                Err(msg) => {
+                    self.view.require_window().alert(
+                        "ReaLearn",
+                        format!("Invalid feedback resync interval: {}", msg),
+                    );
+                }
+            }
+        }
     }
 
-    fn set_stay_active_when_project_in_background(&self, value: StayActiveWhenProjectInBackground) {
-        self.session()
-            .borrow_mut()
-            .stay_active_when_project_in_background
-            .set(value);
+    fn edit_value_display_decimal_places(&self) {
+        let instance_state = self.session().borrow().instance_state().clone();
+        let prefs = instance_state.borrow().value_display_preferences();
+        let mut text = prefs
+            .decimal_places
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        loop {
+            text = match dialog_util::prompt_for("Decimal places (empty = default)", &text) {
+                None => return,
+                Some(t) => t,
+            };
+            let trimmed = text.trim();
+            let decimal_places = if trimmed.is_empty() {
+                Ok(None)
+            } else {
+                match trimmed.parse::<u8>() {
+                    Ok(n) if n <= MAX_VALUE_DISPLAY_DECIMAL_PLACES => Ok(Some(n)),
+                    _ => Err("must be a number between 0 and 10"),
+                }
+            };
+            match decimal_places {
+                Ok(decimal_places) => {
+                    self.set_value_display_preferences(ValueDisplayPreferences {
+                        decimal_places,
+                        ..prefs
+                    });
+                    return;
+                }
+                Err(msg) => {
+                    self.view
+                        .require_window()
+                        .alert("ReaLearn", format!("Invalid decimal places: {}", msg));
+                }
+            }
+        }
     }
 
-    fn toggle_reset_feedback_when_releasing_source(&self) {
-        self.session()
+    fn set_value_display_preferences(&self, prefs: ValueDisplayPreferences) {
+        let session = self.session();
+        let mut session = session.borrow_mut();
+        session
+            .instance_state()
             .borrow_mut()
-            .reset_feedback_when_releasing_source
-            .set_with(|prev| !*prev);
+            .set_value_display_preferences(prefs);
+        // Refreshes displayed values and textual feedback
+        session.notify_everything_has_changed();
     }
 
     fn toggle_always_auto_detect(&self) {
@@ -1223,6 +2206,13 @@ impl HeaderPanel {
             .set_with(|prev| !*prev);
     }
 
+    fn toggle_simulate_feedback(&self) {
+        self.session()
+            .borrow_mut()
+            .simulate_feedback
+            .set_with(|prev| !*prev);
+    }
+
     fn toggle_virtual_output_logging(&self) {
         self.session()
             .borrow_mut()
@@ -1237,6 +2227,14 @@ impl HeaderPanel {
             .set_with(|prev| !*prev);
     }
 
+    fn toggle_mapping_metering(&self) {
+        let session = self.session();
+        let session = session.borrow();
+        let mut instance_state = session.instance_state().borrow_mut();
+        let enabled = instance_state.mapping_metering_enabled();
+        instance_state.set_mapping_metering_enabled(!enabled);
+    }
+
     fn toggle_use_instance_preset_links_only(&self) {
         let session = self.session();
         let mut session = session.borrow_mut();
@@ -1274,6 +2272,7 @@ impl HeaderPanel {
         self.invalidate_add_one_button();
         self.invalidate_learn_many_button();
         self.invalidate_notes_button();
+        self.invalidate_audio_overload_button();
     }
 
     fn invalidate_let_through_controls(&self) {
@@ -1435,10 +2434,23 @@ impl HeaderPanel {
     }
 
     fn invalidate_preset_label_text(&self) {
-        let text = match self.active_compartment() {
+        let compartment = self.active_compartment();
+        let label = match compartment {
             Compartment::Controller => "Controller preset",
             Compartment::Main => "Main preset",
         };
+        // Mark the label if the loaded preset has been modified
+        let preset_is_modified = {
+            let session = self.session();
+            let session = session.borrow();
+            session.active_preset_id(compartment).is_some()
+                && session.compartment_or_preset_is_dirty(compartment)
+        };
+        let text = if preset_is_modified {
+            format!("{}*", label)
+        } else {
+            label.to_string()
+        };
         self.view
             .require_control(root::ID_PRESET_LABEL_TEXT)
             .set_text(text);
@@ -1889,15 +2901,103 @@ impl HeaderPanel {
             i if i >= 0 => preset_manager.find_id_by_index(i as usize),
             _ => unreachable!(),
         };
-        let mut session = session.borrow_mut();
         match compartment {
             Compartment::Controller => {
-                session.activate_controller_preset(preset_id);
+                session.borrow_mut().activate_controller_preset(preset_id);
+            }
+            Compartment::Main => {
+                if let Some(id) = preset_id.as_ref() {
+                    let substitutions = self.ask_for_preset_substitutions(id);
+                    session.borrow_mut().add_preset_substitutions(substitutions);
+                }
+                session.borrow_mut().activate_main_preset(preset_id);
             }
-            Compartment::Main => session.activate_main_preset(preset_id),
         };
     }
 
+    /// If the given main preset refers to tracks or FX which don't exist in this project, lets
+    /// the user choose substitutes for them.
+    fn ask_for_preset_substitutions(&self, preset_id: &str) -> PresetSubstitutions {
+        let mut substitutions = PresetSubstitutions::default();
+        let Some(preset) = App::get().main_preset_manager().find_by_id(preset_id) else {
+            return substitutions;
+        };
+        let project = self
+            .session()
+            .borrow()
+            .processor_context()
+            .project_or_current_project();
+        let missing_references = find_missing_preset_references(preset.data(), project);
+        if missing_references.is_empty() {
+            return substitutions;
+        }
+        let names: Vec<_> = missing_references
+            .iter()
+            .map(|r| format!("- {} \"{}\"", r.object, r.name))
+            .collect();
+        let msg = format!(
+            "The preset refers to the following tracks and FX which don't exist in this project:\n\n{}\n\nDo you want to choose substitutes for them? Otherwise the affected mappings won't work.",
+            names.join("\n")
+        );
+        if !self.view.require_window().confirm("ReaLearn", msg) {
+            return substitutions;
+        }
+        for reference in missing_references {
+            self.ask_for_preset_substitution(project, reference, &mut substitutions);
+        }
+        substitutions
+    }
+
+    fn ask_for_preset_substitution(
+        &self,
+        project: Project,
+        reference: MissingPresetReference,
+        substitutions: &mut PresetSubstitutions,
+    ) {
+        enum Substitute {
+            Track(Track),
+            Fx(String),
+        }
+        let pure_menu = {
+            use swell_ui::menu_tree::*;
+            let candidates: Vec<_> = match reference.object {
+                MissingTargetObject::Track => track_substitute_candidates(project, &reference.name)
+                    .into_iter()
+                    .map(|t| {
+                        let label = get_track_label(&t);
+                        item(label, move || Some(Substitute::Track(t)))
+                    })
+                    .collect(),
+                MissingTargetObject::Fx => fx_substitute_candidates(project, &reference.name)
+                    .into_iter()
+                    .map(|name| item(name.clone(), move || Some(Substitute::Fx(name))))
+                    .collect(),
+            };
+            let entries = [
+                disabled_item(format!(
+                    "Substitute {} \"{}\" with...",
+                    reference.object, reference.name
+                )),
+                separator(),
+            ]
+            .into_iter()
+            .chain(candidates)
+            .chain([separator(), item("<Keep as is>", || None)])
+            .collect();
+            root_menu(entries)
+        };
+        let substitute = self
+            .view
+            .require_window()
+            .open_simple_popup_menu(pure_menu, Window::cursor_pos())
+            .flatten();
+        match substitute {
+            None => {}
+            Some(Substitute::Track(track)) => substitutions.substitute_track(reference.name, track),
+            Some(Substitute::Fx(fx_name)) => substitutions.substitute_fx(reference.name, fx_name),
+        }
+    }
+
     fn mappings_are_read_only(&self) -> bool {
         self.session()
             .borrow()
@@ -1928,6 +3028,33 @@ impl HeaderPanel {
         button.set_text(text);
     }
 
+    fn invalidate_audio_overload_button(&self) {
+        let overruns = self
+            .session()
+            .borrow()
+            .instance_state()
+            .borrow()
+            .real_time_overruns();
+        let button = self.view.require_control(root::ID_AUDIO_OVERLOAD_BUTTON);
+        button.set_visible(overruns.count > 0);
+    }
+
+    fn show_audio_overload_details(&self) {
+        let overruns = self
+            .session()
+            .borrow()
+            .instance_state()
+            .borrow()
+            .real_time_overruns();
+        let msg = format!(
+            "Processing audio blocks took too long {} times since this instance was loaded \
+            (max {}% of the block duration). This can cause audio dropouts. Consider reducing \
+            the number of mappings or increasing the audio buffer size.",
+            overruns.count, overruns.max_load_percent
+        );
+        self.view.require_window().alert("ReaLearn", msg);
+    }
+
     fn invalidate_add_one_button(&self) {
         self.view
             .require_control(root::ID_ADD_MAPPING_BUTTON)
@@ -2047,6 +3174,9 @@ impl HeaderPanel {
             Tagged(DataObject::Mapping{..}) => {
                 return Err("The clipboard contains just one single mapping. Please import it using the context menus.".into())
             }
+            Tagged(DataObject::MappingPack(envelope)) => {
+                self.import_mapping_pack(envelope)?;
+            }
             _ => {
                 return Err("The clipboard contains only a part of a mapping. Please import it using the context menus in the mapping area.".into())
             }
@@ -2217,6 +3347,92 @@ impl HeaderPanel {
         Ok(())
     }
 
+    fn restore_active_preset_backup(&self, backup_id: &str) -> Result<(), &'static str> {
+        if !self.view.require_window().confirm(
+            "ReaLearn",
+            "Do you really want to replace the preset with this previous version? Unsaved changes in the preset will be lost.",
+        ) {
+            return Ok(());
+        }
+        let session = self.session();
+        let mut session = session.borrow_mut();
+        let compartment = self.active_compartment();
+        let active_preset_id = session
+            .active_preset_id(compartment)
+            .ok_or("no preset selected")?
+            .to_string();
+        App::get()
+            .preset_manager(compartment)
+            .restore_preset_backup(&active_preset_id, backup_id)?;
+        // Load the restored version
+        match compartment {
+            Compartment::Controller => session.activate_controller_preset(Some(active_preset_id)),
+            Compartment::Main => session.activate_main_preset(Some(active_preset_id)),
+        };
+        Ok(())
+    }
+
+    fn compare_with_preset(&self) -> Result<(), &'static str> {
+        let session = self.session();
+        let compartment = self.active_compartment();
+        let (preset_id, diff) = {
+            let session = session.borrow();
+            let preset_id = session
+                .active_preset_id(compartment)
+                .ok_or("no preset selected")?
+                .to_string();
+            let preset_model = match compartment {
+                Compartment::Controller => App::get()
+                    .controller_preset_manager()
+                    .find_by_id(&preset_id)
+                    .map(|p| p.data().clone()),
+                Compartment::Main => App::get()
+                    .main_preset_manager()
+                    .find_by_id(&preset_id)
+                    .map(|p| p.data().clone()),
+            }
+            .ok_or("preset doesn't exist anymore")?;
+            let diff = CompartmentDiff::compute(
+                &CompartmentModelData::from_model(&preset_model),
+                &CompartmentModelData::from_model(&session.extract_compartment_model(compartment)),
+            );
+            (preset_id, diff)
+        };
+        if diff.is_empty() {
+            self.view.require_window().alert(
+                "ReaLearn",
+                format!(
+                    "The {} compartment doesn't differ from preset \"{}\".",
+                    compartment, preset_id
+                ),
+            );
+            return Ok(());
+        }
+        let msg = format!(
+            "Differences between the {} compartment and preset \"{}\":\n\n{}\n\
+            Yes = Save as new preset\n\
+            No = Revert to preset (discards changes)\n\
+            Cancel = Keep as is",
+            compartment, preset_id, diff
+        );
+        match self
+            .view
+            .require_window()
+            .ask_yes_no_or_cancel("ReaLearn", msg)
+        {
+            None => {}
+            Some(true) => self.save_as_preset()?,
+            Some(false) => {
+                let mut session = session.borrow_mut();
+                match compartment {
+                    Compartment::Controller => session.activate_controller_preset(Some(preset_id)),
+                    Compartment::Main => session.activate_main_preset(Some(preset_id)),
+                };
+            }
+        }
+        Ok(())
+    }
+
     fn reload_all_presets(&self) {
         let _ = App::get()
             .controller_preset_manager()
@@ -2231,6 +3447,146 @@ impl HeaderPanel {
         self.notify_user_on_error(result);
     }
 
+    fn open_mapping_pack_folder(&self) {
+        let path = App::realearn_mapping_pack_dir_path();
+        let _ = std::fs::create_dir_all(&path);
+        let result = open_in_file_manager(&path).map_err(|e| e.into());
+        self.notify_user_on_error(result);
+    }
+
+    fn export_group_as_mapping_pack(&self, group_id: GroupId) -> Result<(), Box<dyn Error>> {
+        let pack = {
+            let session = self.session();
+            let session = session.borrow();
+            let model = session.extract_compartment_model(self.active_compartment());
+            MappingPackData::from_model(&model, group_id)?
+        };
+        let file_name: String = pack
+            .name()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || " -_()".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if file_name.trim().is_empty() {
+            return Err("group needs a name in order to be exported as mapping pack".into());
+        }
+        let dir = App::realearn_mapping_pack_dir_path();
+        let file = dir.join(format!("{}.json", file_name.trim()));
+        if file.exists()
+            && !self.view.require_window().confirm(
+                "ReaLearn",
+                format!(
+                    "There's already a mapping pack named \"{}\". Do you want to overwrite it?",
+                    file_name.trim()
+                ),
+            )
+        {
+            return Ok(());
+        }
+        let data_object = DataObject::MappingPack(App::create_envelope(Box::new(pack)));
+        let json = serialize_data_object_to_json(data_object)?;
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&file, json)?;
+        self.view.require_window().alert(
+            "ReaLearn",
+            format!("Exported mapping pack to:\n\n{}", file.to_string_lossy()),
+        );
+        Ok(())
+    }
+
+    fn import_mapping_pack_from_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        match deserialize_data_object_from_json(&json)? {
+            DataObject::MappingPack(envelope) => self.import_mapping_pack(envelope),
+            _ => Err("This file doesn't contain a mapping pack.".into()),
+        }
+    }
+
+    fn import_mapping_pack(
+        &self,
+        envelope: Envelope<Box<MappingPackData>>,
+    ) -> Result<(), Box<dyn Error>> {
+        App::warn_if_envelope_version_higher(envelope.version.as_ref());
+        let pack = envelope.value;
+        let compartment = self.active_compartment();
+        let session = self.session();
+        let name_is_taken = session
+            .borrow()
+            .groups(compartment)
+            .any(|g| g.borrow().name() == pack.name());
+        let conflict_resolution = if name_is_taken {
+            match self.prompt_for_mapping_pack_conflict_resolution(pack.name()) {
+                None => return Ok(()),
+                Some(r) => r,
+            }
+        } else {
+            MappingPackConflictResolution::Rename
+        };
+        let import = pack.import_into_session(
+            &mut session.borrow_mut(),
+            compartment,
+            envelope.version.as_ref(),
+            conflict_resolution,
+        )?;
+        self.main_state
+            .borrow_mut()
+            .set_displayed_group_for_active_compartment(Some(GroupFilter(import.group_id)));
+        if !import.conflicting_params.is_empty() {
+            let session = session.borrow();
+            let compartment_params = session.params().compartment_params(compartment);
+            let param_names: Vec<_> = import
+                .conflicting_params
+                .iter()
+                .map(|i| {
+                    format!(
+                        "- {}: {}",
+                        i.get() + 1,
+                        compartment_params.get_parameter_name(*i)
+                    )
+                })
+                .collect();
+            let msg = format!(
+                "The imported mappings refer to the following parameters which are already in use for something else:\n\n{}\n\nPlease check the activation conditions and dynamic targets of the imported mappings.",
+                param_names.join("\n")
+            );
+            self.view.require_window().alert("ReaLearn", msg);
+        }
+        Ok(())
+    }
+
+    fn prompt_for_mapping_pack_conflict_resolution(
+        &self,
+        group_name: &str,
+    ) -> Option<MappingPackConflictResolution> {
+        let menu = {
+            use swell_ui::menu_tree::*;
+            root_menu(vec![
+                item(
+                    format!(
+                        "Group \"{}\" exists already: Import as renamed group",
+                        group_name
+                    ),
+                    || MappingPackConflictResolution::Rename,
+                ),
+                item(
+                    format!(
+                        "Group \"{}\" exists already: Merge mappings into existing group",
+                        group_name
+                    ),
+                    || MappingPackConflictResolution::Merge,
+                ),
+            ])
+        };
+        self.view
+            .require_window()
+            .open_simple_popup_menu(menu, Window::cursor_pos())
+    }
+
     fn make_mappings_project_independent_if_desired(&self) {
         let session = self.session();
         let compartment = self.active_compartment();
@@ -2278,6 +3634,19 @@ impl HeaderPanel {
         Ok(())
     }
 
+    fn save_current_setup(&self) -> Result<(), String> {
+        let Some(name) = dialog_util::prompt_for("Setup name", "") else {
+            return Ok(());
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(());
+        }
+        let app = App::get();
+        let setup = app.capture_setup(&self.session(), name.to_string());
+        app.save_setup(setup)
+    }
+
     fn change_session_id(&self) {
         self.view.require_window().alert(
             "ReaLearn",
@@ -2459,6 +3828,15 @@ impl HeaderPanel {
         self.when(session.group_list_changed(), |view, _| {
             view.invalidate_group_controls();
         });
+        self.when(
+            session
+                .instance_state()
+                .borrow()
+                .real_time_overruns_changed(),
+            |view, _| {
+                view.invalidate_audio_overload_button();
+            },
+        );
         when(
             App::get()
                 .controller_preset_manager()
@@ -2483,7 +3861,7 @@ impl HeaderPanel {
             view.invalidate_control_input_combo_box();
             view.invalidate_feedback_output_combo_box();
         });
-        // Enables/disables save button depending on dirty state.
+        // Enables/disables save button and shows modified indicator depending on dirty state.
         when(
             session.compartment_is_dirty[Compartment::Controller]
                 .changed()
@@ -2492,6 +3870,7 @@ impl HeaderPanel {
         )
         .with(Rc::downgrade(&self))
         .do_sync(move |view, _| {
+            view.invalidate_preset_label_text();
             view.invalidate_preset_buttons();
         });
     }
@@ -2576,6 +3955,7 @@ impl View for HeaderPanel {
             root::ID_PRESET_SAVE_BUTTON => {
                 self.save_active_preset().unwrap();
             }
+            root::ID_AUDIO_OVERLOAD_BUTTON => self.show_audio_overload_details(),
             root::ID_PROJECTION_BUTTON => {
                 self.companion_app_presenter.show_app_info();
             }
@@ -2627,7 +4007,7 @@ fn get_midi_input_device_label(dev: MidiInputDevice) -> String {
     )
 }
 
-fn get_midi_output_device_label(dev: MidiOutputDevice) -> String {
+pub fn get_midi_output_device_label(dev: MidiOutputDevice) -> String {
     get_midi_device_label(
         dev.name(),
         dev.id().get(),
@@ -2899,10 +4279,49 @@ const COMPARTMENT_CHANGES_WARNING_TEXT: &str = "Mapping/group/parameter changes
 
 const EMPTY_CLIP_MATRIX_LABEL: &str = "empty clip matrix";
 
+const MAX_LISTED_EXPENSIVE_MAPPINGS: usize = 10;
+
 fn get_clip_matrix_label(column_count: usize) -> String {
     format!("clip matrix with {} columns", column_count)
 }
 
+/// Returns the mapping pack files in the mapping pack folder, sorted by name.
+fn find_mapping_pack_files() -> Vec<PathBuf> {
+    let dir = App::realearn_mapping_pack_dir_path();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().map(|ext| ext == "json").unwrap_or(false))
+        .sorted()
+        .collect()
+}
+
+/// Parses something like "5" or "1.5" (seconds). "Off", 0 or an empty text turn the resync off.
+fn parse_feedback_resync_interval(text: &str) -> Result<Option<Duration>, &'static str> {
+    let text = text.trim().trim_end_matches('s').trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let secs: f64 = text
+        .parse()
+        .map_err(|_| "interval must be a number of seconds")?;
+    if !secs.is_finite() || secs < 0.0 {
+        return Err("interval must not be negative");
+    }
+    if secs == 0.0 {
+        return Ok(None);
+    }
+    let interval = Duration::from_secs_f64(secs);
+    if interval < MIN_FEEDBACK_RESYNC_INTERVAL {
+        return Err("interval must be at least 1 second");
+    }
+    Ok(Some(interval))
+}
+
 enum MainMenuAction {
     None,
     CopyListedMappingsAsJson,
@@ -2910,9 +4329,15 @@ enum MainMenuAction {
     AutoNameListedMappings,
     NameListedMappingsAfterSource,
     MakeTargetsOfListedMappingsSticky,
+    FixFxReferencesOfListedMappings,
+    MapParametersOfLastFocusedFx,
+    AddOscXyPad,
     MakeSourcesOfMainMappingsVirtual,
     MoveListedMappingsToGroup(Option<GroupId>),
     PasteReplaceAllInGroup(Envelope<Vec<MappingModelData>>),
+    ExportGroupAsMappingPack(GroupId),
+    ImportMappingPack(PathBuf),
+    OpenMappingPackFolder,
     PasteFromLuaReplaceAllInGroup(Rc<String>),
     DryRunLuaScript(Rc<String>),
     FreezeClipMatrix,
@@ -2920,16 +4345,32 @@ enum MainMenuAction {
     ToggleRealInputLogging,
     ToggleVirtualInputLogging,
     ToggleRealOutputLogging,
+    ToggleSimulateFeedback,
     ToggleVirtualOutputLogging,
     ToggleTargetControlLogging,
+    ToggleMappingMetering,
+    ShowProcessingTimes,
     ToggleSendFeedbackOnlyIfTrackArmed,
     ToggleResetFeedbackWhenReleasingSource,
+    ToggleSendMidiClock,
+    EditMidiPassThroughFilter,
+    EditFeedbackBrightness,
+    EditFeedbackResyncInterval,
+    EditParameterThinning,
+    EditValueDisplayDecimalPlaces,
+    SetValueDisplayPreferences(ValueDisplayPreferences),
     ToggleUpperFloorMembership,
     SetStayActiveWhenProjectInBackground(StayActiveWhenProjectInBackground),
+    SetVirtualControlElementSharing(VirtualControlElementSharing),
     ToggleServer,
     ToggleUseInstancePresetLinksOnly,
     AddFirewallRule,
     ChangeSessionId,
+    ApplySetup(String),
+    SaveSetup,
+    RemoveSetup(String),
+    RestorePresetBackup(String),
+    CompareWithPreset,
     EditPresetLinkFxId(PresetLinkScope, FxId),
     RemovePresetLink(PresetLinkScope, FxId),
     LinkToPreset(PresetLinkScope, FxId, String),
@@ -2942,7 +4383,13 @@ enum MainMenuAction {
     ToggleOscDeviceFeedback(OscDeviceId),
     ToggleOscDeviceBundles(OscDeviceId),
     EditCompartmentParameter(Compartment, RangeInclusive<CompartmentParamIndex>),
+    EditControllerLifecycle,
+    EditControllerMessageTemplates,
     SendFeedbackNow,
+    OpenTestFeedbackPanel,
+    OpenTouchControllerPanel,
+    ExportControlsDocumentation,
+    ShowNotifications,
     LogDebugInfo,
 }
 
@@ -2954,6 +4401,8 @@ enum HelpMenuAction {
     ContactDeveloper,
     OpenWebsite,
     Donate,
+    CheckForUpdates,
+    ToggleUpdateCheck,
 }
 
 impl Default for MainMenuAction {
@@ -10,8 +10,8 @@ use crate::infrastructure::api::convert::from_data::ConversionStyle;
 use crate::infrastructure::api::convert::to_data::ApiToDataConversionContext;
 use crate::infrastructure::api::convert::{from_data, to_data};
 use crate::infrastructure::data::{
-    ActivationConditionData, CompartmentModelData, MappingModelData, ModeModelData, SessionData,
-    SourceModelData, TargetModelData,
+    ActivationConditionData, CompartmentModelData, MappingModelData, MappingPackData,
+    ModeModelData, SessionData, SourceModelData, TargetModelData,
 };
 use crate::infrastructure::plugin::App;
 use crate::infrastructure::ui::lua_serializer;
@@ -48,6 +48,7 @@ pub enum DataObject {
     ControllerCompartment(Envelope<Box<CompartmentModelData>>),
     Mappings(Envelope<Vec<MappingModelData>>),
     Mapping(Envelope<Box<MappingModelData>>),
+    MappingPack(Envelope<Box<MappingPackData>>),
     Source(Envelope<Box<SourceModelData>>),
     #[serde(alias = "Mode")]
     Glue(Envelope<Box<ModeModelData>>),
@@ -142,8 +143,7 @@ impl DataObject {
             }
             _ => {
                 return Err(
-                    "conversion from source/mode/target data object not supported at the moment"
-                        .into(),
+                    "conversion from this kind of data object not supported at the moment".into(),
                 )
             }
         };
@@ -159,6 +159,7 @@ impl DataObject {
             ControllerCompartment(v) => v.version.as_ref(),
             Mappings(v) => v.version.as_ref(),
             Mapping(v) => v.version.as_ref(),
+            MappingPack(v) => v.version.as_ref(),
             Source(v) => v.version.as_ref(),
             Glue(v) => v.version.as_ref(),
             Target(v) => v.version.as_ref(),
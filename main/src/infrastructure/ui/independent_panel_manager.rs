@@ -148,9 +148,11 @@ impl IndependentPanelManager {
     }
 
     fn create_new_panel(&mut self) -> SharedView<MappingPanel> {
+        let window_name = format!("mapping-panel-{}", self.mapping_panels.len() + 1);
         let panel = SharedView::new(MappingPanel::new(
             self.session.clone(),
             self.main_panel.clone(),
+            window_name,
         ));
         let panel_clone_1 = panel.clone();
         let panel_clone_2 = panel.clone();
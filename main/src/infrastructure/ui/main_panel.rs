@@ -10,15 +10,15 @@ use slog::debug;
 use std::cell::{Cell, RefCell};
 
 use crate::application::{
-    get_virtual_fx_label, get_virtual_track_label, Affected, CompartmentProp, Session, SessionProp,
-    SessionUi, VirtualFxType, WeakSession,
+    get_virtual_fx_label, get_virtual_track_label, Affected, CompartmentProp, Session,
+    SessionEvent, SessionProp, SessionUi, VirtualFxType, WeakSession,
 };
-use crate::base::when;
+use crate::base::{when, Global};
 use crate::domain::ui_util::format_tags_as_csv;
 use crate::domain::{
-    Compartment, MappingId, MappingMatchedEvent, PanExt, ProjectionFeedbackValue,
-    QualifiedMappingId, RealearnClipMatrix, SoundPlayer, TargetControlEvent,
-    TargetValueChangedEvent,
+    Compartment, ControlElementTouchedEvent, DiagnosticEvent, MappingId, MappingMatchedEvent,
+    PanExt, ProjectionFeedbackValue, QualifiedMappingId, RealearnClipMatrix, SoundPlayer,
+    TargetControlEvent, TargetValueChangedEvent,
 };
 use crate::infrastructure::plugin::{App, RealearnPluginParameters};
 use crate::infrastructure::server::grpc::{
@@ -27,7 +27,9 @@ use crate::infrastructure::server::grpc::{
     OccasionalTrackUpdateBatch,
 };
 use crate::infrastructure::server::http::{
-    send_projection_feedback_to_subscribed_clients, send_updated_controller_routing,
+    send_control_element_touched_to_subscribed_clients,
+    send_diagnostic_event_to_subscribed_clients, send_projection_feedback_to_subscribed_clients,
+    send_updated_controller_routing,
 };
 use crate::infrastructure::ui::util::{header_panel_height, parse_tags_from_csv};
 use playtime_api::persistence::EvenQuantization;
@@ -232,6 +234,17 @@ impl MainPanel {
             if !control_and_feedback_state.feedback_active {
                 text.push_str(" | FEEDBACK off");
             }
+            let midi_input_drops = instance_state.midi_input_drops();
+            if midi_input_drops.count > 0 {
+                let _ = write!(
+                    &mut text,
+                    " | MIDI OVERFLOW {} dropped (max {}/block)",
+                    midi_input_drops.count, midi_input_drops.max_messages_per_block
+                );
+            }
+            if let Some(msg) = instance_state.simulated_feedback() {
+                let _ = write!(&mut text, " | SIMULATED FEEDBACK {:.40}", msg);
+            }
             let label = self.view.require_control(root::ID_MAIN_PANEL_STATUS_2_TEXT);
             label.disable();
             label.set_text(text.as_str());
@@ -254,10 +267,22 @@ impl MainPanel {
         Err("session not available")
     }
 
+    /// Shows the version and - if there are any - the latest notification.
     fn invalidate_version_text(&self) {
+        let mut text = format!("ReaLearn {}", App::detailed_version_label());
+        let _ = self.do_with_session(|session| {
+            let instance_state = session.instance_state().borrow();
+            if let Some(entry) = instance_state.notifications().latest() {
+                text = format!(
+                    "{} | {}",
+                    text,
+                    session.describe_notification(&entry.notification)
+                );
+            }
+        });
         self.view
             .require_control(root::ID_MAIN_PANEL_VERSION_TEXT)
-            .set_text(format!("ReaLearn {}", App::detailed_version_label()));
+            .set_text(text);
     }
 
     fn invalidate_all_controls(&self) {
@@ -284,11 +309,17 @@ impl MainPanel {
             });
             let instance_state = session.instance_state().borrow();
             self.when(
-                instance_state.global_control_and_feedback_state_changed(),
+                instance_state
+                    .global_control_and_feedback_state_changed()
+                    .merge(instance_state.midi_input_drops_changed())
+                    .merge(instance_state.simulated_feedback_changed()),
                 |view| {
                     view.invalidate_status_2_text();
                 },
             );
+            self.when(instance_state.notifications_changed(), |view| {
+                view.invalidate_version_text();
+            });
         });
     }
 
@@ -367,6 +398,12 @@ impl MainPanel {
         if let Some(data) = self.active_data.borrow() {
             data.header_panel.handle_changed_midi_devices();
         }
+        // The session is borrowed while we are notified, so attach a bit later.
+        Global::task_support()
+            .do_later_in_main_thread_from_main_thread_asap(|| {
+                App::get().auto_attach_controllers_to_waiting_sessions();
+            })
+            .unwrap();
     }
 
     fn handle_changed_conditions(&self) {
@@ -513,6 +550,14 @@ impl SessionUi for Weak<MainPanel> {
         let _ = send_projection_feedback_to_subscribed_clients(session.id(), value);
     }
 
+    fn send_control_element_touched(&self, session: &Session, event: ControlElementTouchedEvent) {
+        let _ = send_control_element_touched_to_subscribed_clients(session.id(), event);
+    }
+
+    fn send_diagnostic_event(&self, session: &Session, event: DiagnosticEvent) {
+        let _ = send_diagnostic_event_to_subscribed_clients(session, event);
+    }
+
     fn clip_matrix_changed(
         &self,
         session: &Session,
@@ -567,6 +612,12 @@ impl SessionUi for Weak<MainPanel> {
         // Update primary GUI
         upgrade_panel(self).handle_affected(affected, initiator);
     }
+
+    fn session_event_occurred(&self, session: &Session, event: SessionEvent) {
+        App::get()
+            .webhook_dispatcher()
+            .notify_session_event(session, &event);
+    }
 }
 
 fn send_occasional_matrix_updates_caused_by_matrix(
@@ -582,11 +633,14 @@ fn send_occasional_matrix_updates_caused_by_matrix(
     let updates: Vec<_> = events
         .iter()
         .filter_map(|event| match event {
-            ClipMatrixEvent::EverythingChanged => Some(OccasionalMatrixUpdate {
-                update: Some(occasional_matrix_update::Update::complete_persistent_data(
-                    matrix,
-                )),
-            }),
+            // Row (scene) properties are part of the persistent matrix data
+            ClipMatrixEvent::EverythingChanged | ClipMatrixEvent::RowChanged(_) => {
+                Some(OccasionalMatrixUpdate {
+                    update: Some(occasional_matrix_update::Update::complete_persistent_data(
+                        matrix,
+                    )),
+                })
+            }
             ClipMatrixEvent::HistoryChanged => Some(OccasionalMatrixUpdate {
                 update: Some(occasional_matrix_update::Update::history_state(matrix)),
             }),
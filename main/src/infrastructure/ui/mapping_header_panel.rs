@@ -13,7 +13,7 @@ use crate::application::{
     SharedSession, WeakSession,
 };
 use crate::domain::ui_util::format_tags_as_csv;
-use crate::domain::{Compartment, MappingId, Tag};
+use crate::domain::{Compartment, ExpressionCondition, MappingId, Tag};
 use std::fmt::Debug;
 use swell_ui::{DialogUnits, Point, SharedView, View, ViewContext, Window};
 
@@ -256,6 +256,16 @@ impl MappingHeaderPanel {
                 button.set_text(text);
                 Some("Mapping")
             }
+            Expression => {
+                button.hide();
+                check_box.hide();
+                // Show a validation hint as long as the expression can't be compiled
+                if ExpressionCondition::compile(item.script()).is_ok() {
+                    None
+                } else {
+                    Some("Invalid expression!")
+                }
+            }
             _ => {
                 button.hide();
                 check_box.hide();
@@ -330,7 +340,7 @@ impl MappingHeaderPanel {
                 edit_control.set_text(item.script());
                 Some("Ex: p[0] == 2")
             }
-            Always => {
+            Always | WhilePlaying | WhileRecording | WhileStopped => {
                 button.hide();
                 check_box.hide();
                 edit_control.hide();
@@ -589,7 +599,10 @@ impl MappingHeaderPanel {
                         self.invalidate_activation_setting_1_controls(item);
                         self.invalidate_activation_setting_2_controls(item, initiator);
                     }
-                    Script => self.invalidate_activation_setting_2_controls(item, initiator),
+                    Script => {
+                        self.invalidate_activation_setting_1_controls(item);
+                        self.invalidate_activation_setting_2_controls(item, initiator);
+                    }
                     MappingId => self.invalidate_activation_setting_1_controls(item),
                 };
             });
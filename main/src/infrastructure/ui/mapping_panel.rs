@@ -1,6 +1,7 @@
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::convert::TryInto;
+use std::error::Error;
 use std::ptr::null;
 use std::rc::Rc;
 use std::time::Duration;
@@ -14,7 +15,9 @@ use reaper_high::{
     BookmarkType, Fx, FxChain, Project, Reaper, SendPartnerType, Track, TrackRoutePartner,
 };
 use reaper_low::raw;
-use reaper_medium::{InitialAction, PromptForActionResult, SectionId, WindowContext};
+use reaper_medium::{
+    InitialAction, MidiOutputDeviceId, PromptForActionResult, SectionId, WindowContext,
+};
 use rxrust::prelude::*;
 
 use helgoboss_learn::{
@@ -27,25 +30,30 @@ use helgoboss_learn::{
     DEFAULT_OSC_ARG_VALUE_RANGE,
 };
 use realearn_api::persistence::{
-    Axis, BrowseTracksMode, FxToolAction, MidiScriptKind, MonitoringMode, MouseButton,
-    PotFilterItemKind, SeekBehavior, TrackToolAction,
+    AccelerationCurve, AudioInputKind, AudioLevelMeasurement, Axis, BrowseTracksMode, ControlCurve,
+    Envelope, ExponentialAccelerationCurve, FxToolAction, ItemPropertyKind, LfoShape,
+    MidiEditorAction, MidiMachineControlCommand, MidiScriptKind, MonitoringMode, MouseButton,
+    PitchBendBehavior, PitchBendMode, PotFilterItemKind, SeekBehavior, ShapedControlCurve,
+    SourceDebounce, TrackToolAction, VelocityFeedback, VelocityFeedbackPalette,
+    VelocityFeedbackRange,
 };
 use swell_ui::{
-    DialogUnits, Point, SharedView, SwellStringArg, View, ViewContext, WeakView, Window,
+    DialogUnits, Pixels, Point, SharedView, SwellStringArg, View, ViewContext, WeakView, Window,
 };
 
 use crate::application::{
     format_osc_feedback_args, get_bookmark_label_by_id, get_fx_label, get_fx_param_label,
     get_non_present_bookmark_label, get_optional_fx_label, get_route_label,
-    parse_osc_feedback_args, Affected, AutomationModeOverrideType, BookmarkAnchorType, Change,
-    CompartmentProp, ConcreteFxInstruction, ConcreteTrackInstruction, MappingChangeContext,
-    MappingCommand, MappingModel, MappingProp, MappingSnapshotTypeForLoad,
+    parse_osc_feedback_args, Affected, AutomationModeOverrideType, BookmarkAnchorType,
+    ButtonBehavior, Change, CompartmentProp, ConcreteFxInstruction, ConcreteTrackInstruction,
+    MappingChangeContext, MappingCommand, MappingModel, MappingProp, MappingSnapshotTypeForLoad,
     MappingSnapshotTypeForTake, MidiSourceType, ModeCommand, ModeModel, ModeProp,
-    RealearnAutomationMode, RealearnTrackArea, ReaperSourceType, Session, SessionProp,
-    SharedMapping, SharedSession, SourceCategory, SourceCommand, SourceModel, SourceProp,
-    TargetCategory, TargetCommand, TargetModel, TargetModelWithContext, TargetProp, TargetUnit,
-    TrackRouteSelectorType, VirtualControlElementType, VirtualFxParameterType, VirtualFxType,
-    VirtualTrackType, WeakSession, KEY_UNDEFINED_LABEL,
+    ProjectionVisibility, RealearnAutomationMode, RealearnTrackArea, ReaperSourceType, Session,
+    SessionProp, SharedMapping, SharedSession, SourceCategory, SourceCommand, SourceModel,
+    SourceProp, TargetCategory, TargetCommand, TargetModel, TargetModelFormatMultiLine,
+    TargetModelWithContext, TargetProp, TargetUnit, TrackRouteSelectorType,
+    VirtualControlElementType, VirtualFxParameterType, VirtualFxType, VirtualTrackType,
+    WeakSession, KEY_UNDEFINED_LABEL,
 };
 use crate::base::Global;
 use crate::base::{notification, when, Prop};
@@ -53,10 +61,11 @@ use crate::domain::ui_util::{
     format_as_percentage_without_unit, format_tags_as_csv, parse_unit_value_from_percentage,
 };
 use crate::domain::{
-    control_element_domains, AnyOnParameter, ControlContext, Exclusivity, FeedbackSendBehavior,
-    KeyStrokePortability, MouseActionType, PortabilityIssue, ReaperTargetType, SendMidiDestination,
-    SimpleExclusivity, TargetControlEvent, TouchedRouteParameterType, TrackGangBehavior,
-    WithControlContext,
+    accelerate_increment, apply_control_curve, control_element_domains, format_note_length,
+    parse_note_length, AnyOnParameter, BackboneState, ControlContext, Exclusivity,
+    FeedbackSendBehavior, KeyStrokePortability, MouseActionType, PortabilityIssue, ReaperTarget,
+    ReaperTargetType, SendMidiDestination, SimpleExclusivity, TargetControlEvent,
+    TouchedRouteParameterType, TrackGangBehavior, WithControlContext,
 };
 use crate::domain::{
     get_non_present_virtual_route_label, get_non_present_virtual_track_label,
@@ -65,16 +74,22 @@ use crate::domain::{
     RealearnTarget, SoloBehavior, TargetCharacter, TouchedTrackParameterType, TrackExclusivity,
     TrackRouteType, TransportAction, VirtualControlElement, VirtualControlElementId, VirtualFx,
 };
+use crate::infrastructure::data::MappingModelData;
 use crate::infrastructure::plugin::App;
 use crate::infrastructure::ui::bindings::root;
 use crate::infrastructure::ui::util::{
-    compartment_parameter_dropdown_contents, parse_tags_from_csv, symbols, MAPPING_PANEL_SCALING,
+    compartment_parameter_dropdown_contents, memorize_window_bounds, open_in_browser,
+    open_in_text_editor, parse_tags_from_csv, restore_window_bounds, symbols,
+    MAPPING_PANEL_SCALING,
 };
 use crate::infrastructure::ui::{
-    AdvancedScriptEditorPanel, EelControlTransformationEngine, EelFeedbackTransformationEngine,
-    EelMidiScriptEngine, ItemProp, LuaMidiScriptEngine, MainPanel, MappingHeaderPanel,
-    MappingRowsPanel, OscFeedbackArgumentsEngine, RawMidiScriptEngine, ScriptEditorInput,
-    ScriptEngine, SimpleScriptEditorPanel, TextualFeedbackExpressionEngine, YamlEditorPanel,
+    copy_mapping_object, deserialize_data_object_from_json, get_midi_output_device_label,
+    get_text_from_clipboard, paste_data_object_in_place, serialize_data_object_to_json,
+    AdvancedScriptEditorPanel, DataObject, EelControlTransformationEngine,
+    EelFeedbackTransformationEngine, EelMidiScriptEngine, ItemProp, LuaMidiScriptEngine, MainPanel,
+    MappingHeaderPanel, MappingRowsPanel, MappingTriple, ObjectType, OscFeedbackArgumentsEngine,
+    RawMidiScriptEngine, ScriptEditorInput, ScriptEngine, SerializationFormat,
+    SimpleScriptEditorPanel, TextualFeedbackExpressionEngine, YamlEditorPanel,
     CONTROL_TRANSFORMATION_TEMPLATES,
 };
 
@@ -84,6 +99,8 @@ pub struct MappingPanel {
     session: WeakSession,
     mapping: RefCell<Option<SharedMapping>>,
     main_panel: WeakView<MainPanel>,
+    /// Name under which the window bounds are memorized in the session.
+    window_name: String,
     mapping_header_panel: SharedView<MappingHeaderPanel>,
     is_invoked_programmatically: Cell<bool>,
     window_cache: RefCell<Option<WindowCache>>,
@@ -127,12 +144,17 @@ struct WindowCache {
 }
 
 impl MappingPanel {
-    pub fn new(session: WeakSession, main_panel: WeakView<MainPanel>) -> MappingPanel {
+    pub fn new(
+        session: WeakSession,
+        main_panel: WeakView<MainPanel>,
+        window_name: String,
+    ) -> MappingPanel {
         MappingPanel {
             view: Default::default(),
             session: session.clone(),
             mapping: None.into(),
             main_panel,
+            window_name,
             mapping_header_panel: SharedView::new(MappingHeaderPanel::new(
                 session,
                 Point::new(DialogUnits(7 + 5), DialogUnits(12)).scale(MAPPING_PANEL_SCALING),
@@ -223,13 +245,16 @@ impl MappingPanel {
                                 P::IsEnabled => {
                                     view.invalidate_mapping_enabled_check_box();
                                 }
-                                P::VisibleInProjection => {
-                                    view.invalidate_mapping_visible_in_projection_check_box();
+                                P::VisibleInProjection | P::FeedbackOnlyIfVisibleInProjection => {
+                                    view.invalidate_mapping_projection_combo_box();
                                 }
                                 P::FeedbackSendBehavior => {
                                     view.invalidate_mapping_feedback_send_behavior_combo_box();
                                 }
-                                P::GroupId => {}
+                                P::GroupId
+                                | P::CrossfadeRole
+                                | P::FeedbackEffect
+                                | P::InvertFeedback => {}
                                 P::InActivationCondition(p) => match p {
                                     Multiple => {
                                         view.panel.mapping_header_panel.invalidate_controls();
@@ -280,7 +305,8 @@ impl MappingPanel {
                                                 view.invalidate_mode_controls();
                                                 view.invalidate_help();
                                             }
-                                            P::MidiClockTransportMessage => {
+                                            P::MidiClockTransportMessage |
+                                            P::MidiMachineControlCommand => {
                                                 view.invalidate_source_line_3_combo_box_2();
                                             }
                                             P::IsRegistered => {
@@ -299,7 +325,19 @@ impl MappingPanel {
                                             P::Line => {
                                                 view.invalidate_source_line_5_combo_box();
                                             }
-                                            P::OscAddressPattern | P::TimerMillis => {
+                                            P::VelocityFeedback => {
+                                                view.invalidate_source_line_5_edit_control(initiator);
+                                            }
+                                            P::FeedbackAsNoteOn => {
+                                                view.invalidate_source_check_box_2();
+                                            }
+                                            P::Debounce => {
+                                                view.invalidate_source_debounce_button();
+                                            }
+                                            P::PitchBendBehavior => {
+                                                view.invalidate_source_line_4_button();
+                                            }
+                                            P::OscAddressPattern | P::TimerMillis | P::LfoPeriod => {
                                                 view.invalidate_source_line_3_edit_control(initiator);
                                             }
                                             P::RawMidiPattern  => {
@@ -325,6 +363,18 @@ impl MappingPanel {
                                             P::Keystroke => {
                                                 view.invalidate_source_line_3(initiator);
                                             }
+                                            P::AudioInputKind => {
+                                                view.invalidate_source_type_combo_box_value();
+                                            }
+                                            P::AudioChannel => {
+                                                view.invalidate_source_line_3_combo_box_1();
+                                            }
+                                            P::AudioLevelMeasurement | P::LfoShape => {
+                                                view.invalidate_source_line_4_combo_box_1();
+                                            }
+                                            P::AudioAttackMillis | P::AudioReleaseMillis => {
+                                                view.invalidate_source_line_5_edit_control(initiator);
+                                            }
                                         }
                                     }
                                 }
@@ -353,9 +403,14 @@ impl MappingPanel {
                                             }
                                             P::Reverse => {
                                                 view.invalidate_mode_reverse_check_box();
+                                                view.invalidate_mode_button_behavior_combo_box();
                                             }
-                                            P::PressDurationInterval | P::FireMode | P::TurboRate => {
+                                            P::PressDurationInterval
+                                            | P::FireMode
+                                            | P::TurboRate
+                                            | P::SyncedTurboRate => {
                                                 view.invalidate_mode_fire_controls(initiator);
+                                                view.invalidate_mode_button_behavior_combo_box();
                                             }
                                             P::OutOfRangeBehavior => {
                                                 view.invalidate_mode_out_of_range_behavior_combo_box();
@@ -368,6 +423,7 @@ impl MappingPanel {
                                             }
                                             P::ButtonUsage => {
                                                 view.invalidate_mode_button_usage_combo_box();
+                                                view.invalidate_mode_button_behavior_combo_box();
                                             }
                                             P::EncoderUsage => {
                                                 view.invalidate_mode_encoder_usage_combo_box();
@@ -388,6 +444,14 @@ impl MappingPanel {
                                                 view.invalidate_mode_controls();
                                                 view.invalidate_help();
                                             }
+                                            P::AccelerationCurve => {
+                                                view.invalidate_mode_acceleration_curve_combo_box();
+                                                view.show_acceleration_curve_preview();
+                                            }
+                                            P::ControlCurve => {
+                                                view.invalidate_mode_control_curve_combo_box();
+                                                view.show_control_curve_preview();
+                                            }
                                             P::GroupInteraction => {
                                                 view.invalidate_mode_group_interaction_combo_box();
                                             }
@@ -428,7 +492,7 @@ impl MappingPanel {
                                             }
                                             P::TrackType | P::TrackIndex | P::TrackId | P::TrackName
                                             | P::TrackExpression | P::BookmarkType | P::BookmarkAnchorType
-                                            | P::BookmarkRef | P::TransportAction | P::AnyOnParameter
+                                            | P::BookmarkRef | P::BookmarkName | P::TransportAction | P::AnyOnParameter
                                             | P::Action => {
                                                 view.invalidate_window_title();
                                                 view.invalidate_target_controls(initiator);
@@ -440,6 +504,16 @@ impl MappingPanel {
                                             P::MappingSnapshotTypeForLoad | P::MappingSnapshotTypeForTake | P::MappingSnapshotId => {
                                                 view.invalidate_target_line_2(initiator);
                                             }
+                                            P::GlobalParameterName => {
+                                                view.invalidate_window_title();
+                                                view.invalidate_target_line_2(initiator);
+                                            }
+                                            P::FxParameterSnapshotId => {
+                                                view.invalidate_target_line_4(initiator);
+                                            }
+                                            P::GlideTime | P::ParameterThinning => {
+                                                view.invalidate_target_line_5(initiator);
+                                            }
                                             P::MappingSnapshotDefaultValue => {
                                                 view.invalidate_target_line_3(initiator);
                                             }
@@ -447,6 +521,9 @@ impl MappingPanel {
                                                 view.invalidate_window_title();
                                                 view.invalidate_target_line_2(initiator);
                                             }
+                                            P::DisplayLayout => {
+                                                view.invalidate_target_line_3(initiator);
+                                            }
                                             P::Unit => {
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
@@ -468,7 +545,7 @@ impl MappingPanel {
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
                                             }
-                                            P::ActionInvocationType => {
+                                            P::ActionInvocationType | P::ItemProperty | P::MidiEditorAction => {
                                                 view.invalidate_target_line_3(None);
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
@@ -527,6 +604,12 @@ impl MappingPanel {
                                             P::MouseButton => {
                                                 view.invalidate_target_line_4(initiator);
                                             }
+                                            P::TrackBankSize => {
+                                                view.invalidate_target_line_3(initiator);
+                                            }
+                                            P::WrapAround | P::SoloDefeat => {
+                                                view.invalidate_target_check_boxes();
+                                            }
                                             P::ScrollArrangeView | P::SeekPlay => {
                                                 view.invalidate_target_check_boxes();
                                                 view.invalidate_target_value_controls();
@@ -546,7 +629,10 @@ impl MappingPanel {
                                             P::UseRegions => {
                                                 view.invalidate_target_check_boxes();
                                             }
-                                            P::UseLoopPoints | P::PollForFeedback | P::Retrigger => {
+                                            P::UseLoopPoints
+                                            | P::PollForFeedback
+                                            | P::Retrigger
+                                            | P::LowLatency => {
                                                 view.invalidate_target_check_boxes();
                                             }
                                             P::UseTimeSelection => {
@@ -559,7 +645,7 @@ impl MappingPanel {
                                                 view.invalidate_target_line_3(initiator);
                                                 view.invalidate_mode_controls();
                                             }
-                                            P::SendMidiDestination | P::OscDevId => {
+                                            P::SendMidiDestination | P::MidiOutputDeviceId | P::OscDevId => {
                                                 view.invalidate_target_line_2(None);
                                             }
                                             P::Tags => {
@@ -675,6 +761,9 @@ impl MappingPanel {
 
     fn handle_source_line_4_button_press(&self) -> Result<(), &'static str> {
         let mapping = self.displayed_mapping().ok_or("no mapping set")?;
+        if mapping.borrow().source_model.supports_pitch_bend_behavior() {
+            return self.edit_pitch_bend_behavior();
+        }
         let control_element_type = mapping.borrow().source_model.control_element_type();
         let window = self.view.require_window();
         let controller_mappings: Vec<_> = {
@@ -697,6 +786,47 @@ impl MappingPanel {
         Ok(())
     }
 
+    fn edit_pitch_bend_behavior(&self) -> Result<(), &'static str> {
+        let mapping = self.displayed_mapping().ok_or("no mapping set")?;
+        let initial_csv =
+            format_pitch_bend_behavior(mapping.borrow().source_model.pitch_bend_behavior());
+        let csv = Reaper::get()
+            .medium_reaper()
+            .get_user_inputs(
+                "ReaLearn",
+                2,
+                "Center dead zone (%),Mode (absolute/relative),separator=;",
+                initial_csv,
+                256,
+            )
+            .ok_or("cancelled")?;
+        let behavior = parse_pitch_bend_behavior(csv.to_str())?;
+        self.change_mapping(MappingCommand::ChangeSource(
+            SourceCommand::SetPitchBendBehavior(behavior),
+        ));
+        Ok(())
+    }
+
+    fn handle_source_debounce_button_press(&self) -> Result<(), &'static str> {
+        let mapping = self.displayed_mapping().ok_or("no mapping set")?;
+        let initial_csv = format_source_debounce(mapping.borrow().source_model.debounce());
+        let csv = Reaper::get()
+            .medium_reaper()
+            .get_user_inputs(
+                "ReaLearn",
+                2,
+                "Min change (%),Hold time (ms),separator=;",
+                initial_csv,
+                256,
+            )
+            .ok_or("cancelled")?;
+        let debounce = parse_source_debounce(csv.to_str())?;
+        self.change_mapping(MappingCommand::ChangeSource(SourceCommand::SetDebounce(
+            debounce,
+        )));
+        Ok(())
+    }
+
     fn feedback_type_button_pressed(&self) -> Result<(), &'static str> {
         let mapping = self.displayed_mapping().ok_or("no mapping set")?;
         let current_color = mapping.borrow().mode_model.feedback_color().cloned();
@@ -743,7 +873,7 @@ impl MappingPanel {
         let mapping = self.displayed_mapping().ok_or("no mapping set")?;
         let target_type = mapping.borrow().target_model.target_type();
         match target_type {
-            ReaperTargetType::Action => {
+            ReaperTargetType::Action | ReaperTargetType::ToolbarToggle => {
                 let reaper = Reaper::get().medium_reaper();
                 use InitialAction::*;
                 let initial_action = match mapping.borrow().target_model.action() {
@@ -1213,7 +1343,11 @@ impl MappingPanel {
 
     pub fn hide(&self) {
         self.stop_party();
-        self.view.require_window().hide();
+        let window = self.view.require_window();
+        if self.mapping.borrow().is_some() {
+            self.memorize_window_bounds(window);
+        }
+        window.hide();
         self.mapping.replace(None);
         if let Some(p) = self.yaml_editor.replace(None) {
             p.close();
@@ -1227,6 +1361,16 @@ impl MappingPanel {
         self.mapping_header_panel.clear_item();
     }
 
+    fn memorize_window_bounds(&self, window: Window) {
+        let Some(session) = self.session.upgrade() else {
+            return;
+        };
+        // The session might be borrowed mutably already if hiding is a reaction to a change.
+        if let Ok(session) = session.try_borrow() {
+            memorize_window_bounds(window, &session, &self.window_name);
+        }
+    }
+
     pub fn navigate_in_mappings(
         self: SharedView<Self>,
         direction: isize,
@@ -1271,6 +1415,148 @@ impl MappingPanel {
         window.show();
     }
 
+    /// Offers copying and pasting of single mapping sections (source, glue, target) and editing
+    /// the raw mapping data.
+    fn open_context_menu(
+        self: SharedView<Self>,
+        location: Point<Pixels>,
+    ) -> Result<(), &'static str> {
+        enum MenuAction {
+            None,
+            CopyPart(ObjectType),
+            PasteObjectInPlace(DataObject),
+            EditRawData,
+            ToggleCompactLayout,
+        }
+        impl Default for MenuAction {
+            fn default() -> Self {
+                Self::None
+            }
+        }
+        let mapping = self.displayed_mapping().ok_or("no mapping displayed")?;
+        let pure_menu = {
+            use swell_ui::menu_tree::*;
+            let data_object_from_clipboard = get_text_from_clipboard()
+                .and_then(|text| deserialize_data_object_from_json(&text).ok());
+            let paste_desc = match data_object_from_clipboard {
+                Some(DataObject::Source(Envelope { value: s, version })) => Some((
+                    format!("Paste source ({})", s.category),
+                    DataObject::Source(Envelope { value: s, version }),
+                )),
+                Some(DataObject::Glue(Envelope { value: m, version })) => Some((
+                    "Paste glue".to_owned(),
+                    DataObject::Glue(Envelope { value: m, version }),
+                )),
+                Some(DataObject::Target(Envelope { value: t, version })) => Some((
+                    format!("Paste target ({})", t.category),
+                    DataObject::Target(Envelope { value: t, version }),
+                )),
+                _ => None,
+            };
+            let compact = App::get().config().compact_mapping_panel_is_enabled();
+            let entries = vec![
+                item("Copy source", || MenuAction::CopyPart(ObjectType::Source)),
+                item("Copy glue", || MenuAction::CopyPart(ObjectType::Glue)),
+                item("Copy target", || MenuAction::CopyPart(ObjectType::Target)),
+                separator(),
+                if let Some((label, obj)) = paste_desc {
+                    item(label, move || MenuAction::PasteObjectInPlace(obj))
+                } else {
+                    disabled_item("Paste source, glue or target")
+                },
+                separator(),
+                item_with_opts(
+                    "Compact layout (hide rarely used settings)",
+                    ItemOpts {
+                        enabled: true,
+                        checked: compact,
+                    },
+                    || MenuAction::ToggleCompactLayout,
+                ),
+                item("Advanced: Edit raw data (JSON)", || MenuAction::EditRawData),
+            ];
+            root_menu(entries)
+        };
+        let result = self
+            .view
+            .require_window()
+            .open_simple_popup_menu(pure_menu, location)
+            .ok_or("no entry selected")?;
+        let triple = {
+            let m = mapping.borrow();
+            MappingTriple {
+                compartment: m.compartment(),
+                mapping_id: m.id(),
+                group_id: m.group_id(),
+            }
+        };
+        match result {
+            MenuAction::None => {}
+            MenuAction::CopyPart(object_type) => {
+                let _ = copy_mapping_object(
+                    self.session(),
+                    triple.compartment,
+                    triple.mapping_id,
+                    object_type,
+                    SerializationFormat::JsonDataObject,
+                );
+            }
+            MenuAction::PasteObjectInPlace(data_object) => {
+                paste_data_object_in_place(data_object, self.session(), triple)?;
+                // Sections have been replaced as a whole, so refresh everything
+                self.show(mapping);
+            }
+            MenuAction::EditRawData => match self.edit_raw_data(&mapping, triple) {
+                Ok(true) => {
+                    // Everything might have changed, so refresh everything
+                    self.show(mapping);
+                }
+                Ok(false) => {}
+                Err(e) => notification::alert(format!(
+                    "Your changes have not been applied because of the following error:\n\n{}",
+                    e
+                )),
+            },
+            MenuAction::ToggleCompactLayout => {
+                let app = App::get();
+                let compact = app.config().compact_mapping_panel_is_enabled();
+                app.set_compact_mapping_panel_persistently(!compact);
+                self.show(mapping);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lets the user edit the complete mapping as JSON in a text editor.
+    ///
+    /// Useful for tweaking properties which are not exposed in the user interface and for
+    /// obtaining accurate snippets for bug reports. Returns whether changes have been applied.
+    fn edit_raw_data(
+        &self,
+        mapping: &SharedMapping,
+        triple: MappingTriple,
+    ) -> Result<bool, Box<dyn Error>> {
+        let initial_text = {
+            let session = self.session();
+            let session = session.borrow();
+            let compartment_in_session = session.compartment_in_session(triple.compartment);
+            let data = MappingModelData::from_model(&mapping.borrow(), &compartment_in_session);
+            serialize_data_object_to_json(DataObject::Mapping(App::create_envelope(Box::new(
+                data,
+            ))))?
+        };
+        let edited_text = open_in_text_editor(&initial_text, self.view.require_window(), ".json")?;
+        if edited_text == initial_text {
+            return Ok(false);
+        }
+        let data_object = deserialize_data_object_from_json(&edited_text)?;
+        if !matches!(data_object, DataObject::Mapping(_)) {
+            return Err("raw data doesn't describe a mapping".into());
+        }
+        paste_data_object_in_place(data_object, self.session(), triple)?;
+        Ok(true)
+    }
+
     /// If you know a function in this view can be invoked by something else than the dialog
     /// process, wrap your function body with this. Basically all pub functions!
     ///
@@ -1504,6 +1790,26 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(Affected::Multiple)
                     });
                 }
+                ReaperTargetType::LoopRegion => {
+                    let project = self
+                        .session
+                        .processor_context()
+                        .project_or_current_project();
+                    let Some(region_index) = project.current_bookmark().region_index else {
+                        return;
+                    };
+                    let region_id = project
+                        .find_bookmark_by_index(region_index)
+                        .unwrap()
+                        .basic_info()
+                        .id;
+                    self.change_target_with_closure(None, |ctx| {
+                        let target = &mut ctx.mapping.target_model;
+                        target.change(TargetCommand::SetBookmarkAnchorType(BookmarkAnchorType::Id));
+                        target.change(TargetCommand::SetBookmarkRef(region_id.get()));
+                        Some(Affected::Multiple)
+                    });
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -1562,12 +1868,19 @@ impl<'a> MutableMappingPanel<'a> {
         self.change_mapping(MappingCommand::SetIsEnabled(checked));
     }
 
-    fn update_mapping_is_visible_in_projection(&mut self) {
-        let checked = self
+    fn update_mapping_projection_visibility(&mut self) {
+        let visibility: ProjectionVisibility = self
             .view
-            .require_control(root::ID_MAPPING_SHOW_IN_PROJECTION_CHECK_BOX)
-            .is_checked();
-        self.change_mapping(MappingCommand::SetVisibleInProjection(checked));
+            .require_control(root::ID_MAPPING_PROJECTION_COMBO_BOX)
+            .selected_combo_box_item_index()
+            .try_into()
+            .expect("invalid projection visibility");
+        self.change_mapping(MappingCommand::SetVisibleInProjection(
+            visibility.is_visible(),
+        ));
+        self.change_mapping(MappingCommand::SetFeedbackOnlyIfVisibleInProjection(
+            visibility.restricts_feedback(),
+        ));
     }
 
     fn update_mode_hint(&self, mode_parameter: ModeParameter) {
@@ -1595,16 +1908,21 @@ impl<'a> MutableMappingPanel<'a> {
         use SourceCategory::*;
         match self.mapping.source_model.category() {
             Midi => {
-                self.change_mapping(MappingCommand::ChangeSource(SourceCommand::SetIs14Bit(
-                    Some(checked),
-                )));
+                let cmd = if self.mapping.source_model.midi_source_type()
+                    == MidiSourceType::PolyphonicKeyPressureAmount
+                {
+                    SourceCommand::SetFeedbackAsNoteOn(checked)
+                } else {
+                    SourceCommand::SetIs14Bit(Some(checked))
+                };
+                self.change_mapping(MappingCommand::ChangeSource(cmd));
             }
             Osc => {
                 self.change_mapping(MappingCommand::ChangeSource(
                     SourceCommand::SetOscArgIsRelative(checked),
                 ));
             }
-            Reaper | Virtual | Never | Keyboard => {}
+            Reaper | Virtual | Never | Keyboard | Audio => {}
         };
     }
 
@@ -1643,6 +1961,12 @@ impl<'a> MutableMappingPanel<'a> {
                 }
                 _ => b.hide(),
             },
+            Audio => {
+                let channel = b.selected_combo_box_item_index() as u32;
+                self.change_mapping(MappingCommand::ChangeSource(
+                    SourceCommand::SetAudioChannel(channel),
+                ));
+            }
             _ => {}
         };
     }
@@ -1660,6 +1984,20 @@ impl<'a> MutableMappingPanel<'a> {
                     index,
                 )));
             }
+            Reaper if self.mapping.source_model.reaper_source_type() == ReaperSourceType::Lfo => {
+                let i = combo.selected_combo_box_item_index();
+                let shape = i.try_into().expect("invalid LFO shape");
+                self.change_mapping(MappingCommand::ChangeSource(SourceCommand::SetLfoShape(
+                    shape,
+                )));
+            }
+            Audio => {
+                let i = combo.selected_combo_box_item_index();
+                let measurement = i.try_into().expect("invalid audio level measurement");
+                self.change_mapping(MappingCommand::ChangeSource(
+                    SourceCommand::SetAudioLevelMeasurement(measurement),
+                ));
+            }
             _ => {}
         }
     }
@@ -1782,6 +2120,12 @@ impl<'a> MutableMappingPanel<'a> {
                     SourceCommand::SetControlElementType(element_type),
                 ));
             }
+            Audio => {
+                let input_kind = i.try_into().expect("invalid audio input kind");
+                self.change_mapping(MappingCommand::ChangeSource(
+                    SourceCommand::SetAudioInputKind(input_kind),
+                ));
+            }
             _ => {}
         };
     }
@@ -1801,6 +2145,13 @@ impl<'a> MutableMappingPanel<'a> {
                         SourceCommand::SetMidiClockTransportMessage(msg_type),
                     ));
                 }
+                MidiSourceType::MachineControl => {
+                    let i = b.selected_combo_box_item_index();
+                    let command = i.try_into().expect("invalid MMC command");
+                    self.change_mapping(MappingCommand::ChangeSource(
+                        SourceCommand::SetMidiMachineControlCommand(command),
+                    ));
+                }
                 MidiSourceType::Display => {
                     let i = b.selected_combo_box_item_index();
                     let display_type = i.try_into().expect("invalid display type");
@@ -1836,7 +2187,7 @@ impl<'a> MutableMappingPanel<'a> {
                     Some(edit_control_id),
                 );
             }
-            Reaper | Never | Keyboard | Osc => {}
+            Reaper | Never | Keyboard | Osc | Audio => {}
         };
     }
 
@@ -1847,6 +2198,16 @@ impl<'a> MutableMappingPanel<'a> {
         let text = c.text().unwrap_or_default();
         use SourceCategory::*;
         match self.mapping.source_model.category() {
+            Midi => {
+                // Invalid (e.g. incomplete) text keeps the previous setting. The user is warned
+                // when leaving the edit control.
+                if let Ok(v) = parse_velocity_feedback(&text) {
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeSource(SourceCommand::SetVelocityFeedback(v)),
+                        Some(edit_control_id),
+                    );
+                }
+            }
             Osc => {
                 let v = parse_osc_arg_value_range(&text);
                 self.change_mapping_with_initiator(
@@ -1854,6 +2215,18 @@ impl<'a> MutableMappingPanel<'a> {
                     Some(edit_control_id),
                 );
             }
+            Audio => {
+                if let Some((attack, release)) = parse_attack_release(&text) {
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeSource(SourceCommand::SetAudioAttackMillis(attack)),
+                        Some(edit_control_id),
+                    );
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeSource(SourceCommand::SetAudioReleaseMillis(release)),
+                        Some(edit_control_id),
+                    );
+                }
+            }
             _ => {}
         };
     }
@@ -1872,16 +2245,30 @@ impl<'a> MutableMappingPanel<'a> {
                     );
                 }
                 Reaper => match self.mapping.source_model.reaper_source_type() {
-                    ReaperSourceType::Timer => {
+                    ReaperSourceType::Timer
+                    | ReaperSourceType::OneShotTimer
+                    | ReaperSourceType::Random => {
                         let value = value.parse().unwrap_or_default();
                         self.change_mapping_with_initiator(
                             MappingCommand::ChangeSource(SourceCommand::SetTimerMillis(value)),
                             Some(edit_control_id),
                         )
                     }
+                    ReaperSourceType::Lfo => {
+                        if let Ok(value) = value.parse::<f64>() {
+                            if value > 0.0 {
+                                self.change_mapping_with_initiator(
+                                    MappingCommand::ChangeSource(SourceCommand::SetLfoPeriod(
+                                        value,
+                                    )),
+                                    Some(edit_control_id),
+                                )
+                            }
+                        }
+                    }
                     _ => {}
                 },
-                Midi | Virtual | Never | Keyboard => {}
+                Midi | Virtual | Never | Keyboard | Audio => {}
             }
         }
     }
@@ -2025,6 +2412,19 @@ impl<'a> MutableMappingPanel<'a> {
         )));
     }
 
+    fn update_button_behavior(&mut self) {
+        let index = self
+            .view
+            .require_control(root::ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX)
+            .selected_combo_box_item_index();
+        // The last item might be "<Custom>". Keep the settings then.
+        if let Ok(behavior) = ButtonBehavior::try_from(index) {
+            self.change_mapping(MappingCommand::ChangeMode(ModeCommand::SetButtonBehavior(
+                behavior,
+            )));
+        }
+    }
+
     fn update_encoder_usage(&mut self) {
         self.update_mode_hint(ModeParameter::RelativeFilter);
         let mode = self
@@ -2038,6 +2438,32 @@ impl<'a> MutableMappingPanel<'a> {
         )));
     }
 
+    fn update_mode_acceleration_curve(&mut self) {
+        let index = self
+            .view
+            .require_control(root::ID_MODE_ACCELERATION_CURVE_COMBO_BOX)
+            .selected_combo_box_item_index();
+        // The last item might be a custom curve which was set via API. Keep it.
+        if let Some((_, curve)) = ACCELERATION_CURVE_PRESETS.get(index) {
+            self.change_mapping(MappingCommand::ChangeMode(
+                ModeCommand::SetAccelerationCurve(*curve),
+            ));
+        }
+    }
+
+    fn update_mode_control_curve(&mut self) {
+        let index = self
+            .view
+            .require_control(root::ID_MODE_CONTROL_CURVE_COMBO_BOX)
+            .selected_combo_box_item_index();
+        // The last item might be a custom curve which was set via API. Keep it.
+        if let Some((_, curve)) = CONTROL_CURVE_PRESETS.get(index) {
+            self.change_mapping(MappingCommand::ChangeMode(ModeCommand::SetControlCurve(
+                *curve,
+            )));
+        }
+    }
+
     fn update_mode_reverse(&mut self) {
         self.update_mode_hint(ModeParameter::Reverse);
         let checked = self
@@ -2227,6 +2653,25 @@ impl<'a> MutableMappingPanel<'a> {
 
     fn handle_mode_fire_line_3_edit_control_change(&mut self) {
         let control_id = root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL;
+        if self.mapping.mode_model.fire_mode() == FireMode::AfterTimeoutKeepFiring {
+            // A note length such as "1/8" makes the turbo rate follow the project tempo
+            let text = self
+                .view
+                .require_control(control_id)
+                .text()
+                .unwrap_or_default();
+            if text.contains('/') {
+                if let Ok(note_length) = parse_note_length(&text) {
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeMode(ModeCommand::SetSyncedTurboRate(Some(
+                            note_length,
+                        ))),
+                        Some(control_id),
+                    );
+                }
+                return;
+            }
+        }
         let value = self
             .get_value_from_duration_edit_control(control_id)
             .unwrap_or_else(|| Duration::from_millis(0));
@@ -2383,6 +2828,10 @@ impl<'a> MutableMappingPanel<'a> {
             }
             FireMode::AfterTimeout => {}
             FireMode::AfterTimeoutKeepFiring => {
+                self.change_mapping_with_initiator(
+                    MappingCommand::ChangeMode(ModeCommand::SetSyncedTurboRate(None)),
+                    initiator,
+                );
                 self.change_mapping_with_initiator(
                     MappingCommand::ChangeMode(ModeCommand::SetTurboRate(value)),
                     initiator,
@@ -2429,7 +2878,7 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetScrollArrangeView(is_checked),
                     ));
                 }
-                ReaperTargetType::Seek => {
+                ReaperTargetType::Seek | ReaperTargetType::LoopRegion => {
                     self.change_mapping(MappingCommand::ChangeTarget(TargetCommand::SetSeekPlay(
                         is_checked,
                     )));
@@ -2549,6 +2998,13 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetUseLoopPoints(is_checked),
                     ));
                 }
+                ReaperTargetType::NavigateTracks | ReaperTargetType::NavigateProjectTabs => self
+                    .change_mapping(MappingCommand::ChangeTarget(TargetCommand::SetWrapAround(
+                        is_checked,
+                    ))),
+                ReaperTargetType::TrackSolo => self.change_mapping(MappingCommand::ChangeTarget(
+                    TargetCommand::SetSoloDefeat(is_checked),
+                )),
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -2568,6 +3024,9 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetUseTimeSelection(is_checked),
                     ));
                 }
+                ReaperTargetType::FxParameterValue => self.change_mapping(
+                    MappingCommand::ChangeTarget(TargetCommand::SetLowLatency(is_checked)),
+                ),
                 t if self.mapping.target_model.supports_gang_grouping() => {
                     let gang_behavior = TrackGangBehavior::from_bools(
                         t.definition(),
@@ -2611,6 +3070,28 @@ impl<'a> MutableMappingPanel<'a> {
         )));
     }
 
+    fn pick_target_from_history(&mut self) {
+        let index = self
+            .view
+            .require_control(root::ID_TARGET_HISTORY_COMBO_BOX)
+            .selected_combo_box_item_index();
+        // The first item is just a placeholder
+        let target = match index.checked_sub(1).and_then(|i| {
+            relevant_touched_target_history(self.session)
+                .into_iter()
+                .nth(i)
+        }) {
+            None => return,
+            Some(t) => t,
+        };
+        let compartment = self.mapping.compartment();
+        self.change_target_with_closure(None, |ctx| {
+            ctx.mapping
+                .target_model
+                .apply_from_target(&target, ctx.extended_context, compartment)
+        });
+    }
+
     fn update_target_type(&mut self) {
         let b = self.view.require_control(root::ID_TARGET_TYPE_COMBO_BOX);
         use TargetCategory::*;
@@ -2639,7 +3120,7 @@ impl<'a> MutableMappingPanel<'a> {
         let combo = self.view.require_control(combo_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => {
                     let bookmark_anchor_type = combo
                         .selected_combo_box_item_index()
                         .try_into()
@@ -2799,7 +3280,7 @@ impl<'a> MutableMappingPanel<'a> {
         let combo = self.view.require_control(combo_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => {
                     let value: u32 = combo.selected_combo_box_item_data() as _;
                     self.change_mapping(MappingCommand::ChangeTarget(
                         TargetCommand::SetBookmarkRef(value),
@@ -2846,10 +3327,20 @@ impl<'a> MutableMappingPanel<'a> {
                     )));
                 }
                 ReaperTargetType::SendMidi => {
-                    let i = combo.selected_combo_box_item_index();
-                    let v = i.try_into().expect("invalid send MIDI destination");
+                    let (destination, dev_id) = match combo.selected_combo_box_item_data() {
+                        -2 => (SendMidiDestination::FxOutput, None),
+                        -1 => (SendMidiDestination::FeedbackOutput, None),
+                        i if i >= 0 => (
+                            SendMidiDestination::Device,
+                            Some(MidiOutputDeviceId::new(i as _)),
+                        ),
+                        _ => return,
+                    };
                     self.change_mapping(MappingCommand::ChangeTarget(
-                        TargetCommand::SetSendMidiDestination(v),
+                        TargetCommand::SetMidiOutputDeviceId(dev_id),
+                    ));
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetSendMidiDestination(destination),
                     ));
                 }
                 ReaperTargetType::SendOsc => {
@@ -2866,7 +3357,7 @@ impl<'a> MutableMappingPanel<'a> {
                         dev_id,
                     )));
                 }
-                ReaperTargetType::BrowseTracks => {
+                ReaperTargetType::BrowseTracks | ReaperTargetType::NavigateTracks => {
                     let i = combo.selected_combo_box_item_index();
                     let v = i.try_into().expect("invalid track indexing policy");
                     self.change_mapping(MappingCommand::ChangeTarget(
@@ -2975,6 +3466,20 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetMonitoringMode(v),
                     ));
                 }
+                ReaperTargetType::ItemProperty => {
+                    let i = combo.selected_combo_box_item_index();
+                    let v = i.try_into().expect("invalid item property");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetItemProperty(v),
+                    ));
+                }
+                ReaperTargetType::MidiEditorAction => {
+                    let i = combo.selected_combo_box_item_index();
+                    let v = i.try_into().expect("invalid MIDI editor action");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetMidiEditorAction(v),
+                    ));
+                }
                 ReaperTargetType::TrackTouchState => {
                     let i = combo.selected_combo_box_item_index();
                     let v = i.try_into().expect("invalid touched track parameter type");
@@ -3099,15 +3604,19 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
-                    let human_value: u32 = control
-                        .text()
-                        .unwrap_or_default()
-                        .parse()
-                        .unwrap_or_default();
-                    let internal_value = human_value.saturating_sub(1);
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => {
+                    let text = control.text().unwrap_or_default();
+                    let command = match self.mapping.target_model.bookmark_anchor_type() {
+                        BookmarkAnchorType::Name => {
+                            TargetCommand::SetBookmarkName(text.trim().to_owned())
+                        }
+                        _ => {
+                            let human_value: u32 = text.parse().unwrap_or_default();
+                            TargetCommand::SetBookmarkRef(human_value.saturating_sub(1))
+                        }
+                    };
                     self.change_mapping_with_initiator(
-                        MappingCommand::ChangeTarget(TargetCommand::SetBookmarkRef(internal_value)),
+                        MappingCommand::ChangeTarget(command),
                         Some(edit_control_id),
                     );
                 }
@@ -3118,6 +3627,13 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::GlobalParameterValue => {
+                    let name = control.text().unwrap_or_default().trim().to_owned();
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetGlobalParameterName(name)),
+                        Some(edit_control_id),
+                    );
+                }
                 _ if self.mapping.target_model.supports_track() => {
                     match self.mapping.target_model.track_type() {
                         t if t.is_dynamic() => {
@@ -3178,6 +3694,14 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::NavigateTracks => {
+                    let text = control.text().unwrap_or_default();
+                    let bank_size = text.parse().unwrap_or(1);
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetTrackBankSize(bank_size)),
+                        Some(edit_control_id),
+                    );
+                }
                 ReaperTargetType::LoadMappingSnapshot => {
                     let text = control.text().unwrap_or_default();
                     let value = parse_unit_value_from_percentage(&text)
@@ -3218,7 +3742,19 @@ impl<'a> MutableMappingPanel<'a> {
                 },
                 _ => {}
             },
-            TargetCategory::Virtual => {}
+            TargetCategory::Virtual => {
+                if self.mapping.target_model.control_element_type()
+                    == VirtualControlElementType::Display
+                {
+                    let text = control.text().unwrap_or_default();
+                    if let Ok(layout) = text.parse() {
+                        self.change_mapping_with_initiator(
+                            MappingCommand::ChangeTarget(TargetCommand::SetDisplayLayout(layout)),
+                            Some(edit_control_id),
+                        );
+                    }
+                }
+            }
         }
     }
 
@@ -3227,6 +3763,15 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::SaveFxParameterSnapshot
+                | ReaperTargetType::RecallFxParameterSnapshot => {
+                    let text = control.text().unwrap_or_default();
+                    let id = text.parse().unwrap_or(1);
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetFxParameterSnapshotId(id)),
+                        Some(edit_control_id),
+                    );
+                }
                 t if t.supports_fx_parameter() => match self.mapping.target_model.param_type() {
                     VirtualFxParameterType::Dynamic => {
                         let expression = control.text().unwrap_or_default();
@@ -3307,6 +3852,32 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::RecallFxParameterSnapshot => {
+                    let text = control.text().unwrap_or_default();
+                    let millis = text.parse().unwrap_or(0);
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetGlideTime(
+                            Duration::from_millis(millis),
+                        )),
+                        Some(edit_control_id),
+                    );
+                }
+                ReaperTargetType::FxParameterValue => {
+                    let text = control.text().unwrap_or_default();
+                    // Empty means "use instance default"
+                    let thinning = if text.trim().is_empty() {
+                        None
+                    } else {
+                        match text.parse() {
+                            Ok(t) => Some(t),
+                            Err(_) => return,
+                        }
+                    };
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetParameterThinning(thinning)),
+                        Some(edit_control_id),
+                    );
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -3361,12 +3932,16 @@ impl<'a> ImmutableMappingPanel<'a> {
 
     fn fill_all_controls(&self) {
         self.fill_mapping_feedback_send_behavior_combo_box();
+        self.fill_mapping_projection_combo_box();
         self.fill_source_category_combo_box();
         self.fill_mode_out_of_range_behavior_combo_box();
         self.fill_mode_group_interaction_combo_box();
         self.fill_mode_takeover_mode_combo_box();
         self.fill_mode_button_usage_combo_box();
+        self.fill_mode_button_behavior_combo_box();
         self.fill_mode_encoder_usage_combo_box();
+        self.fill_mode_acceleration_curve_combo_box();
+        self.fill_mode_control_curve_combo_box();
         self.fill_mode_fire_mode_combo_box();
         self.fill_mode_feedback_type_combo_box();
         self.fill_target_category_combo_box();
@@ -3378,7 +3953,7 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_beep_on_success_checkbox();
         self.invalidate_mapping_enabled_check_box();
         self.invalidate_mapping_feedback_send_behavior_combo_box();
-        self.invalidate_mapping_visible_in_projection_check_box();
+        self.invalidate_mapping_projection_combo_box();
         self.invalidate_mapping_advanced_settings_button();
         self.invalidate_source_controls();
         self.invalidate_target_controls(None);
@@ -3420,7 +3995,18 @@ impl<'a> ImmutableMappingPanel<'a> {
                 self.invalidate_help_from_source_character();
                 true
             } else {
-                false
+                self.clear_help();
+                self.view
+                    .require_control(root::ID_MAPPING_HELP_SUBJECT_LABEL)
+                    .set_text(format!("Help: {}", mode_parameter));
+                self.view
+                    .require_control(root::ID_MAPPING_HELP_CONTENT_LABEL)
+                    .set_text(format!(
+                        "{} is not relevant for {}.",
+                        mode_parameter,
+                        self.describe_possible_source_characters()
+                    ));
+                true
             }
         } else {
             false
@@ -3430,6 +4016,16 @@ impl<'a> ImmutableMappingPanel<'a> {
         }
     }
 
+    /// Describes the kinds of source values which the current source can emit, e.g. for telling
+    /// the user why a glue parameter is not relevant.
+    fn describe_possible_source_characters(&self) -> String {
+        let characters = self.mapping.source_model.possible_detailed_characters();
+        if characters.is_empty() {
+            return "this source".to_string();
+        }
+        characters.into_iter().map(|ch| ch.to_string()).join(" or ")
+    }
+
     fn invalidate_help_from_source_character(&self) {
         let success = if let Some(source_character) =
             self.panel.last_touched_source_character.borrow().get()
@@ -3510,9 +4106,71 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.view
             .require_control(root::ID_MAPPING_HELP_SUBJECT_LABEL)
             .set_text("Help");
+        // Without a touched glue parameter, tell the user why some glue controls are hidden.
+        let irrelevant_parameters = self.mapping.irrelevant_mode_parameters();
+        let content = if irrelevant_parameters.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "Not relevant for {}: {}",
+                self.describe_possible_source_characters(),
+                irrelevant_parameters.iter().join(", ")
+            )
+        };
+        self.view
+            .require_control(root::ID_MAPPING_HELP_CONTENT_LABEL)
+            .set_text(content);
+    }
+
+    fn show_acceleration_curve_preview(&self) {
+        self.view
+            .require_control(root::ID_MAPPING_HELP_APPLICABLE_TO_LABEL)
+            .hide();
+        self.view
+            .require_control(root::ID_MAPPING_HELP_APPLICABLE_TO_COMBO_BOX)
+            .hide();
+        let curve = self.mapping.mode_model.acceleration_curve();
+        let steps: Vec<_> = [1, 2, 3, 5, 10]
+            .into_iter()
+            .map(|i| {
+                let accelerated = accelerate_increment(DiscreteIncrement::new(i), curve);
+                format!("{} → {}", i, accelerated.get())
+            })
+            .collect();
+        let content = format!("Encoder increment → steps:\n{}", steps.join(", "));
+        self.view
+            .require_control(root::ID_MAPPING_HELP_SUBJECT_LABEL)
+            .set_text("Help: Acceleration curve");
+        self.view
+            .require_control(root::ID_MAPPING_HELP_CONTENT_LABEL)
+            .set_multi_line_text(content);
+    }
+
+    fn show_control_curve_preview(&self) {
+        self.view
+            .require_control(root::ID_MAPPING_HELP_APPLICABLE_TO_LABEL)
+            .hide();
+        self.view
+            .require_control(root::ID_MAPPING_HELP_APPLICABLE_TO_COMBO_BOX)
+            .hide();
+        let curve = self.mapping.mode_model.control_curve();
+        let points: Vec<_> = [0.1, 0.25, 0.5, 0.75, 0.9]
+            .into_iter()
+            .map(|x| {
+                format!(
+                    "{:.0}% → {:.0}%",
+                    x * 100.0,
+                    apply_control_curve(x, curve) * 100.0
+                )
+            })
+            .collect();
+        let content = format!("Source position → target position:\n{}", points.join(", "));
+        self.view
+            .require_control(root::ID_MAPPING_HELP_SUBJECT_LABEL)
+            .set_text("Help: Control curve");
         self.view
             .require_control(root::ID_MAPPING_HELP_CONTENT_LABEL)
-            .set_text("");
+            .set_multi_line_text(content);
     }
 
     fn invalidate_window_title(&self) {
@@ -3547,11 +4205,11 @@ impl<'a> ImmutableMappingPanel<'a> {
             .set_checked(self.mapping.is_enabled());
     }
 
-    fn invalidate_mapping_visible_in_projection_check_box(&self) {
-        let cb = self
-            .view
-            .require_control(root::ID_MAPPING_SHOW_IN_PROJECTION_CHECK_BOX);
-        cb.set_checked(self.mapping.visible_in_projection());
+    fn invalidate_mapping_projection_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MAPPING_PROJECTION_COMBO_BOX)
+            .select_combo_box_item_by_index(self.mapping.projection_visibility().into())
+            .unwrap();
     }
 
     fn invalidate_mapping_advanced_settings_button(&self) {
@@ -3575,6 +4233,7 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_source_line_5(None);
         self.invalidate_source_check_box_2();
         self.invalidate_source_line_7(None);
+        self.invalidate_source_debounce_button();
     }
 
     fn invalidate_source_control_visibilities(&self) {
@@ -3619,6 +4278,24 @@ impl<'a> ImmutableMappingPanel<'a> {
             .unwrap();
     }
 
+    fn invalidate_target_history_combo_box(&self) {
+        let context = self.session.extended_context();
+        let compartment = self.mapping.compartment();
+        let labels = relevant_touched_target_history(self.session)
+            .into_iter()
+            .map(|t| {
+                let mut model = TargetModel::default();
+                let _ = model.apply_from_target(&t, context, compartment);
+                TargetModelFormatMultiLine::new(&model, context, compartment)
+                    .to_string()
+                    .lines()
+                    .join(" / ")
+            });
+        let combo = self.view.require_control(root::ID_TARGET_HISTORY_COMBO_BOX);
+        combo.fill_combo_box_indexed(iter::once("<Recently touched>".to_string()).chain(labels));
+        combo.select_combo_box_item_by_index(0).unwrap();
+    }
+
     fn invalidate_source_line_2(&self) {
         self.invalidate_source_line_2_combo_box();
     }
@@ -3634,6 +4311,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             Midi => self.source.midi_source_type().into(),
             Reaper => self.source.reaper_source_type().into(),
             Virtual => self.source.control_element_type().into(),
+            Audio => self.source.audio_input_kind().into(),
             _ => return,
         };
         let b = self.view.require_control(root::ID_SOURCE_TYPE_COMBO_BOX);
@@ -3678,11 +4356,15 @@ impl<'a> ImmutableMappingPanel<'a> {
             },
             Osc => Some("Address"),
             Reaper => match self.source.reaper_source_type() {
-                ReaperSourceType::Timer => Some("Millis"),
+                ReaperSourceType::Timer
+                | ReaperSourceType::OneShotTimer
+                | ReaperSourceType::Random => Some("Millis"),
+                ReaperSourceType::Lfo => Some("Beats"),
                 ReaperSourceType::RealearnParameter => Some("Param"),
                 _ => None,
             },
             Keyboard => Some("Keystroke"),
+            Audio => Some("Channel"),
             _ => None,
         };
         self.view
@@ -3696,6 +4378,7 @@ impl<'a> ImmutableMappingPanel<'a> {
         let text = match self.source.category() {
             Midi => match self.source.midi_source_type() {
                 MidiSourceType::ClockTransport => Some("Message"),
+                MidiSourceType::MachineControl => Some("Command"),
                 MidiSourceType::Display => Some("Protocol"),
                 _ => None,
             },
@@ -3748,6 +4431,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                 }
                 _ => b.hide(),
             },
+            Audio => {
+                b.fill_combo_box_with_data_small(
+                    (0..MAX_AUDIO_CHANNEL_COUNT).map(|i| (i as isize, (i + 1).to_string())),
+                );
+                b.show();
+                b.select_combo_box_item_by_index(self.source.audio_channel() as usize)
+                    .unwrap();
+            }
             _ => {
                 b.hide();
             }
@@ -3768,6 +4459,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                                 .unwrap_or(false),
                         ))
                     }
+                    MidiSourceType::PolyphonicKeyPressureAmount => {
+                        Some(("Feedback as note-on", self.source.feedback_as_note_on()))
+                    }
                     _ => None,
                 }
             }
@@ -3810,6 +4504,13 @@ impl<'a> ImmutableMappingPanel<'a> {
         use SourceCategory::*;
         let text = match self.source.category() {
             Virtual => Some("Pick!"),
+            _ if self.source.supports_pitch_bend_behavior() => {
+                if self.source.pitch_bend_behavior().is_some() {
+                    Some("Wheel (on)...")
+                } else {
+                    Some("Wheel...")
+                }
+            }
             _ => None,
         };
         self.view
@@ -3841,6 +4542,8 @@ impl<'a> ImmutableMappingPanel<'a> {
             }
             Virtual => Some("ID"),
             Osc => Some("Argument"),
+            Reaper if self.source.reaper_source_type() == ReaperSourceType::Lfo => Some("Shape"),
+            Audio => Some("Level"),
             _ => None,
         };
         self.view
@@ -3858,6 +4561,18 @@ impl<'a> ImmutableMappingPanel<'a> {
             Osc => {
                 invalidate_with_osc_arg_index(b, self.source.osc_arg_index());
             }
+            Reaper if self.source.reaper_source_type() == ReaperSourceType::Lfo => {
+                b.fill_combo_box_indexed(LfoShape::into_enum_iter());
+                b.show();
+                b.select_combo_box_item_by_index(self.source.lfo_shape().into())
+                    .unwrap();
+            }
+            Audio => {
+                b.fill_combo_box_indexed(AudioLevelMeasurement::into_enum_iter());
+                b.show();
+                b.select_combo_box_item_by_index(self.source.audio_level_measurement().into())
+                    .unwrap();
+            }
             _ => {
                 b.hide();
             }
@@ -3908,8 +4623,13 @@ impl<'a> ImmutableMappingPanel<'a> {
                         }
                     }
                     t if t.supports_midi_message_number() => {
+                        let none_label = if t == MachineControl {
+                            "<All-call>"
+                        } else {
+                            "<Any> (no feedback)"
+                        };
                         b.fill_combo_box_with_data_vec(
-                            iter::once((-1isize, "<Any> (no feedback)".to_string()))
+                            iter::once((-1isize, none_label.to_string()))
                                 .chain((0..128).map(|i| (i as isize, i.to_string())))
                                 .collect(),
                         );
@@ -3982,6 +4702,19 @@ impl<'a> ImmutableMappingPanel<'a> {
             .set_text_or_hide(text);
     }
 
+    fn invalidate_source_debounce_button(&self) {
+        let text = if !self.source.supports_debounce() {
+            None
+        } else if self.source.debounce().is_some() {
+            Some("Debounce (on)...")
+        } else {
+            Some("Debounce...")
+        };
+        self.view
+            .require_control(root::ID_SOURCE_DEBOUNCE_BUTTON)
+            .set_text_or_hide(text);
+    }
+
     fn invalidate_source_line_7_label(&self) {
         use SourceCategory::*;
         let text = match self.source.category() {
@@ -4008,7 +4741,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         let content = match self.source.category() {
             Osc => Some((self.source.osc_address_pattern().to_owned(), true)),
             Reaper => match self.source.reaper_source_type() {
-                ReaperSourceType::Timer => Some((self.source.timer_millis().to_string(), true)),
+                ReaperSourceType::Timer
+                | ReaperSourceType::OneShotTimer
+                | ReaperSourceType::Random => Some((self.source.timer_millis().to_string(), true)),
+                ReaperSourceType::Lfo => Some((self.source.lfo_period().to_string(), true)),
                 _ => None,
             },
             Keyboard => {
@@ -4098,10 +4834,12 @@ impl<'a> ImmutableMappingPanel<'a> {
                         }
                     }
                     t if t.supports_custom_character() => Some("Character"),
+                    t if t.supports_velocity_feedback() => Some("Velocities"),
                     _ => None,
                 }
             }
             Osc if self.source.supports_osc_arg_value_range() => Some("Range"),
+            Audio => Some("Attack/rel."),
             _ => None,
         };
         self.view
@@ -4162,6 +4900,9 @@ impl<'a> ImmutableMappingPanel<'a> {
         }
         use SourceCategory::*;
         let text = match self.source.category() {
+            Midi if self.source.midi_source_type().supports_velocity_feedback() => {
+                Some(format_velocity_feedback(self.source.velocity_feedback()))
+            }
             Osc if self.source.supports_osc_arg_value_range() => {
                 let text = format_osc_arg_value_range(
                     self.source.osc_arg_value_range(),
@@ -4169,6 +4910,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                 );
                 Some(text)
             }
+            Audio => Some(format!(
+                "{} - {}",
+                self.source.audio_attack_millis(),
+                self.source.audio_release_millis()
+            )),
             _ => None,
         };
         self.view
@@ -4176,6 +4922,24 @@ impl<'a> ImmutableMappingPanel<'a> {
             .set_text_or_hide(text);
     }
 
+    fn warn_about_invalid_source_line_5_text(&self) {
+        if self.source.category() != SourceCategory::Midi {
+            return;
+        }
+        let text = self
+            .view
+            .require_control(root::ID_SOURCE_LINE_5_EDIT_CONTROL)
+            .text()
+            .unwrap_or_default();
+        if let Err(msg) = parse_velocity_feedback(&text) {
+            notification::warn(format!(
+                "Invalid velocity feedback \"{}\" ({}), keeping the previous setting",
+                text.trim(),
+                msg
+            ));
+        }
+    }
+
     fn invalidate_source_line_3_combo_box_2(&self) {
         let b = self
             .view
@@ -4191,6 +4955,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                     )
                     .unwrap();
                 }
+                MidiSourceType::MachineControl => {
+                    b.show();
+                    b.fill_combo_box_indexed(MidiMachineControlCommand::into_enum_iter());
+                    b.select_combo_box_item_by_index(
+                        self.source.midi_machine_control_command().into(),
+                    )
+                    .unwrap();
+                }
                 MidiSourceType::Display => {
                     b.show();
                     b.fill_combo_box_indexed(DisplayType::into_enum_iter());
@@ -4209,6 +4981,7 @@ impl<'a> ImmutableMappingPanel<'a> {
 
     fn invalidate_target_controls(&self, initiator: Option<u32>) {
         self.invalidate_target_category_combo_box();
+        self.invalidate_target_history_combo_box();
         self.invalidate_target_type_combo_box();
         self.invalidate_target_line_2(initiator);
         self.invalidate_target_line_3(initiator);
@@ -4289,16 +5062,19 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::Transport => Some("Action"),
                 ReaperTargetType::AnyOn => Some("Parameter"),
                 ReaperTargetType::AutomationModeOverride => Some("Behavior"),
-                ReaperTargetType::GoToBookmark => match self.target.bookmark_type() {
-                    BookmarkType::Marker => Some("Marker"),
-                    BookmarkType::Region => Some("Region"),
-                },
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => {
+                    match self.target.bookmark_type() {
+                        BookmarkType::Marker => Some("Marker"),
+                        BookmarkType::Region => Some("Region"),
+                    }
+                }
                 ReaperTargetType::SendMidi => Some("Output"),
                 ReaperTargetType::SendOsc => Some("Output"),
                 ReaperTargetType::LoadMappingSnapshot => Some("Snapshot"),
                 ReaperTargetType::TakeMappingSnapshot => Some("Snapshot ID"),
+                ReaperTargetType::GlobalParameterValue => Some("Name"),
                 ReaperTargetType::BrowseGroup => Some("Group"),
-                ReaperTargetType::BrowseTracks => Some("Scope"),
+                ReaperTargetType::BrowseTracks | ReaperTargetType::NavigateTracks => Some("Scope"),
                 t if t.supports_feedback_resolution() => Some("Feedback"),
                 _ if self.target.supports_track() => Some("Track"),
                 _ => None,
@@ -4339,7 +5115,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                         .select_combo_box_item_by_index(self.target.track_type().into())
                         .unwrap();
                 }
-                ReaperTargetType::GoToBookmark => {
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => {
                     combo.show();
                     combo.fill_combo_box_indexed(BookmarkAnchorType::into_enum_iter());
                     combo
@@ -4436,7 +5212,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                         )
                         .unwrap();
                 }
-                ReaperTargetType::GoToBookmark
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion
                     if self.target.bookmark_anchor_type() == BookmarkAnchorType::Id =>
                 {
                     combo.show();
@@ -4448,6 +5224,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                         combo,
                         self.target.bookmark_anchor_type(),
                         self.target.bookmark_ref(),
+                        self.target.bookmark_name(),
                     );
                 }
                 ReaperTargetType::BrowseGroup => {
@@ -4477,12 +5254,44 @@ impl<'a> ImmutableMappingPanel<'a> {
                 }
                 ReaperTargetType::SendMidi => {
                     combo.show();
-                    combo.fill_combo_box_indexed(SendMidiDestination::into_enum_iter());
-                    combo
-                        .select_combo_box_item_by_index(
-                            self.mapping.target_model.send_midi_destination().into(),
-                        )
-                        .unwrap();
+                    combo.fill_combo_box_with_data_small(
+                        [
+                            (-2isize, "<FX output>".to_string()),
+                            (-1isize, "<Feedback output>".to_string()),
+                        ]
+                        .into_iter()
+                        .chain(
+                            Reaper::get()
+                                .midi_output_devices()
+                                .filter(|d| d.is_available())
+                                .map(|dev| {
+                                    (dev.id().get() as isize, get_midi_output_device_label(dev))
+                                }),
+                        ),
+                    );
+                    let target = &self.mapping.target_model;
+                    match target.send_midi_destination() {
+                        SendMidiDestination::FxOutput => {
+                            combo.select_combo_box_item_by_data(-2).unwrap();
+                        }
+                        SendMidiDestination::FeedbackOutput => {
+                            combo.select_combo_box_item_by_data(-1).unwrap();
+                        }
+                        SendMidiDestination::Device => match target.midi_output_device_id() {
+                            None => combo.select_new_combo_box_item("<No device>"),
+                            Some(dev_id) => {
+                                if combo
+                                    .select_combo_box_item_by_data(dev_id.get() as isize)
+                                    .is_err()
+                                {
+                                    combo.select_new_combo_box_item(format!(
+                                        "<Not present> ({})",
+                                        dev_id.get()
+                                    ));
+                                }
+                            }
+                        },
+                    }
                 }
                 ReaperTargetType::SendOsc => {
                     combo.show();
@@ -4510,7 +5319,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                         combo.select_combo_box_item_by_data(-1).unwrap();
                     };
                 }
-                ReaperTargetType::BrowseTracks => {
+                ReaperTargetType::BrowseTracks | ReaperTargetType::NavigateTracks => {
                     combo.show();
                     combo.fill_combo_box_indexed(BrowseTracksMode::into_enum_iter());
                     combo
@@ -4596,11 +5405,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                     };
                     control.set_text(text);
                 }
-                ReaperTargetType::GoToBookmark
-                    if self.target.bookmark_anchor_type() == BookmarkAnchorType::Index =>
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion
+                    if self.target.bookmark_anchor_type() != BookmarkAnchorType::Id =>
                 {
                     control.show();
-                    let text = (self.target.bookmark_ref() + 1).to_string();
+                    let text = match self.target.bookmark_anchor_type() {
+                        BookmarkAnchorType::Name => self.target.bookmark_name().to_owned(),
+                        _ => (self.target.bookmark_ref() + 1).to_string(),
+                    };
                     control.set_text(text);
                 }
                 _ if self.mapping.target_model.supports_mapping_snapshot_id() => {
@@ -4612,6 +5424,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                         .unwrap_or_default();
                     control.set_text(text);
                 }
+                ReaperTargetType::GlobalParameterValue => {
+                    control.show();
+                    control.set_text(self.target.global_parameter_name());
+                }
                 _ => {
                     control.hide();
                 }
@@ -4637,7 +5453,7 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_line_2_button(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => Some("Now!"),
+                ReaperTargetType::GoToBookmark | ReaperTargetType::LoopRegion => Some("Now!"),
                 _ => None,
             },
             TargetCategory::Virtual => Some("Pick!"),
@@ -4694,7 +5510,7 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_line_4_button(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::Action => Some("Pick!"),
+                ReaperTargetType::Action | ReaperTargetType::ToolbarToggle => Some("Pick!"),
                 ReaperTargetType::LoadFxSnapshot => Some("Take!"),
                 _ => None,
             },
@@ -4736,6 +5552,15 @@ impl<'a> ImmutableMappingPanel<'a> {
                     );
                     Some(text)
                 }
+                ReaperTargetType::RecallFxParameterSnapshot => {
+                    Some(self.target.glide_time().as_millis().to_string())
+                }
+                ReaperTargetType::FxParameterValue => Some(
+                    self.target
+                        .parameter_thinning()
+                        .map(|t| t.to_string())
+                        .unwrap_or_default(),
+                ),
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -4754,6 +5579,11 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_4_EDIT_CONTROL);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::SaveFxParameterSnapshot
+                | ReaperTargetType::RecallFxParameterSnapshot => {
+                    control.set_text(self.target.fx_parameter_snapshot_id().to_string());
+                    control.show();
+                }
                 t if t.supports_fx_parameter() => {
                     let text = match self.target.param_type() {
                         VirtualFxParameterType::Dynamic => {
@@ -4819,6 +5649,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                     let text = self.target.osc_address_pattern().to_owned();
                     (Some(text), false)
                 }
+                ReaperTargetType::NavigateTracks => {
+                    let text = self.target.track_bank_size().to_string();
+                    (Some(text), false)
+                }
                 ReaperTargetType::LoadMappingSnapshot => {
                     let text = self
                         .target
@@ -4845,7 +5679,13 @@ impl<'a> ImmutableMappingPanel<'a> {
                 }
                 _ => (None, false),
             },
-            TargetCategory::Virtual => (None, false),
+            TargetCategory::Virtual => {
+                if self.target.control_element_type() == VirtualControlElementType::Display {
+                    (Some(self.target.display_layout().to_string()), false)
+                } else {
+                    (None, false)
+                }
+            }
         };
         c.set_text_or_hide(value_text);
         c.set_enabled(!read_only);
@@ -4864,14 +5704,23 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::SendMidi => Some("Pattern"),
                 ReaperTargetType::SendOsc => Some("Address"),
                 ReaperTargetType::TrackMonitoringMode => Some("Mode"),
+                ReaperTargetType::ItemProperty => Some("Property"),
+                ReaperTargetType::MidiEditorAction => Some("Action"),
                 ReaperTargetType::LoadMappingSnapshot => Some("Default"),
+                ReaperTargetType::NavigateTracks => Some("Bank size"),
                 _ if self.target.supports_automation_mode() => Some("Mode"),
                 t if t.supports_fx() => Some("FX"),
                 t if t.supports_seek_behavior() => Some("Behavior"),
                 t if t.supports_send() => Some("Kind"),
                 _ => None,
             },
-            TargetCategory::Virtual => None,
+            TargetCategory::Virtual => {
+                if self.target.control_element_type() == VirtualControlElementType::Display {
+                    Some("Layout")
+                } else {
+                    None
+                }
+            }
         };
         self.view
             .require_control(root::ID_TARGET_LINE_3_LABEL_1)
@@ -4884,6 +5733,8 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::SendOsc if self.target.supports_osc_arg_value_range() => {
                     Some("Range")
                 }
+                ReaperTargetType::RecallFxParameterSnapshot => Some("Glide (ms)"),
+                ReaperTargetType::FxParameterValue => Some("Thinning"),
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -4899,8 +5750,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::Mouse if self.mapping.target_model.supports_mouse_button() => {
                     Some("Button")
                 }
-                ReaperTargetType::Action => Some("Action"),
-                ReaperTargetType::LoadFxSnapshot => Some("Snapshot"),
+                ReaperTargetType::Action | ReaperTargetType::ToolbarToggle => Some("Action"),
+                ReaperTargetType::LoadFxSnapshot
+                | ReaperTargetType::SaveFxParameterSnapshot
+                | ReaperTargetType::RecallFxParameterSnapshot => Some("Snapshot"),
                 ReaperTargetType::SendOsc => Some("Argument"),
                 ReaperTargetType::TrackTool | ReaperTargetType::FxTool => Some("Act/Tags"),
                 t if t.supports_fx_parameter() => Some("Parameter"),
@@ -4931,7 +5784,9 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_line_4_label_2(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::Action => Some(self.target.action_name_label().to_string()),
+                ReaperTargetType::Action | ReaperTargetType::ToolbarToggle => {
+                    Some(self.target.action_name_label().to_string())
+                }
                 ReaperTargetType::LoadFxSnapshot => {
                     let label = if let Some(snapshot) = self.target.fx_snapshot() {
                         snapshot.to_string()
@@ -5144,6 +5999,20 @@ impl<'a> ImmutableMappingPanel<'a> {
                         .select_combo_box_item_by_index(self.target.monitoring_mode().into())
                         .unwrap();
                 }
+                ReaperTargetType::ItemProperty => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(ItemPropertyKind::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(self.target.item_property().into())
+                        .unwrap();
+                }
+                ReaperTargetType::MidiEditorAction => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(MidiEditorAction::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(self.target.midi_editor_action().into())
+                        .unwrap();
+                }
                 _ if self.target.supports_automation_mode() => {
                     combo.show();
                     combo.fill_combo_box_indexed(RealearnAutomationMode::into_enum_iter());
@@ -5287,6 +6156,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     Some(("Regions", is_regions))
                 }
                 ReaperTargetType::Seek => Some(("Seek play", self.target.seek_play())),
+                ReaperTargetType::LoopRegion => Some(("Start playback", self.target.seek_play())),
                 _ if self.target.supports_fx_chain() => {
                     let is_input_fx = self.target.fx_is_input_fx();
                     let label = if self.target.track_type() == VirtualTrackType::Master {
@@ -5385,6 +6255,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::GoToBookmark => {
                     Some(("Set loop points", self.target.use_loop_points()))
                 }
+                ReaperTargetType::NavigateTracks | ReaperTargetType::NavigateProjectTabs => {
+                    Some(("Wrap", self.target.wrap_around()))
+                }
+                ReaperTargetType::TrackSolo => Some(("Solo defeat", self.target.solo_defeat())),
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -5401,6 +6275,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::GoToBookmark => {
                     Some(("Set time selection", self.target.use_time_selection()))
                 }
+                ReaperTargetType::FxParameterValue => {
+                    Some(("Low latency", self.target.low_latency()))
+                }
                 _ if self.target.supports_gang_grouping() => Some((
                     "Respect grouping",
                     self.target.fixed_gang_behavior().use_track_grouping(),
@@ -5615,6 +6492,12 @@ impl<'a> ImmutableMappingPanel<'a> {
                 view.invalidate_target_learn_button();
             },
         );
+        self.panel.when(
+            BackboneState::get().touched_target_history_changed(),
+            |view, _| {
+                view.invalidate_target_history_combo_box();
+            },
+        );
     }
 
     fn invalidate_mode_controls(&self) {
@@ -5638,7 +6521,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_mode_round_target_value_check_box();
         self.invalidate_mode_takeover_mode_combo_box();
         self.invalidate_mode_button_usage_combo_box();
+        self.invalidate_mode_button_behavior_combo_box();
         self.invalidate_mode_encoder_usage_combo_box();
+        self.invalidate_mode_acceleration_curve_combo_box();
+        self.invalidate_mode_control_curve_combo_box();
         self.invalidate_mode_reverse_check_box();
         self.invalidate_mode_target_value_sequence_edit_control(initiator);
         self.invalidate_mode_eel_control_transformation_edit_control(initiator);
@@ -5695,6 +6581,9 @@ impl<'a> ImmutableMappingPanel<'a> {
             .map(|t| t.control_type(self.session.control_context()).is_relative())
             .unwrap_or_default();
         let feedback_is_on = self.mapping.feedback_is_enabled_and_supported();
+        // In compact layout, rarely used sections are only shown if they deviate from the defaults
+        let compact = App::get().config().compact_mapping_panel_is_enabled();
+        let mode = self.mode;
         // For all source characters
         {
             let show_source_min_max = is_relevant(ModeParameter::SourceMinMax);
@@ -5739,7 +6628,8 @@ impl<'a> ImmutableMappingPanel<'a> {
             };
             let show_target_value_sequence = target_controls_make_sense
                 && is_relevant(ModeParameter::TargetValueSequence)
-                && real_target.is_some();
+                && real_target.is_some()
+                && (!compact || !mode.target_value_sequence().is_empty());
             self.enable_if(
                 show_target_value_sequence,
                 &[
@@ -5766,8 +6656,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                     root::ID_SETTINGS_MAX_TARGET_VALUE_TEXT,
                 ],
             );
-            let show_feedback_transformation = is_relevant(ModeParameter::FeedbackTransformation)
-                || is_relevant(ModeParameter::TextualFeedbackExpression);
+            let show_feedback_transformation = (is_relevant(ModeParameter::FeedbackTransformation)
+                || is_relevant(ModeParameter::TextualFeedbackExpression))
+                && (!compact
+                    || !mode.eel_feedback_transformation().is_empty()
+                    || !mode.textual_feedback_expression().is_empty());
             self.enable_if(
                 show_feedback_transformation,
                 &[
@@ -5795,7 +6688,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 show_takeover,
                 &[root::ID_MODE_TAKEOVER_LABEL, root::ID_MODE_TAKEOVER_MODE],
             );
-            let show_control_transformation = is_relevant(ModeParameter::ControlTransformation);
+            let control_transformation_is_relevant =
+                is_relevant(ModeParameter::ControlTransformation);
+            let show_control_transformation = control_transformation_is_relevant
+                && (!compact || !mode.eel_control_transformation().is_empty());
             self.enable_if(
                 show_control_transformation,
                 &[
@@ -5804,6 +6700,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                     root::ID_MODE_EEL_CONTROL_TRANSFORMATION_DETAIL_BUTTON,
                 ],
             );
+            let show_control_curve = control_transformation_is_relevant
+                && self.target_with_context().is_known_to_be_continuous();
+            self.enable_if(show_control_curve, &[root::ID_MODE_CONTROL_CURVE_COMBO_BOX]);
             let show_absolute_mode = is_relevant(ModeParameter::AbsoluteMode);
             self.enable_if(
                 show_absolute_mode,
@@ -5816,6 +6715,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 show_round_controls
                     || show_takeover
                     || show_control_transformation
+                    || show_control_curve
                     || show_absolute_mode,
                 &[root::ID_MODE_KNOB_FADER_GROUP_BOX],
             );
@@ -5859,6 +6759,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 show_make_absolute,
                 &[root::ID_SETTINGS_MAKE_ABSOLUTE_CHECK_BOX],
             );
+            self.enable_if(
+                show_make_absolute && self.mode.make_absolute(),
+                &[root::ID_MODE_ACCELERATION_CURVE_COMBO_BOX],
+            );
             let show_relative_filter = is_relevant(ModeParameter::RelativeFilter);
             self.enable_if(
                 show_relative_filter,
@@ -5877,7 +6781,18 @@ impl<'a> ImmutableMappingPanel<'a> {
         {
             let show_button_filter = is_relevant(ModeParameter::ButtonFilter);
             self.enable_if(show_button_filter, &[root::ID_MODE_BUTTON_FILTER_COMBO_BOX]);
-            let show_fire_mode = is_relevant(ModeParameter::FireMode);
+            let show_button_behavior = show_button_filter
+                && is_relevant(ModeParameter::AbsoluteMode)
+                && is_relevant(ModeParameter::FireMode);
+            self.enable_if(
+                show_button_behavior,
+                &[
+                    root::ID_MODE_BUTTON_BEHAVIOR_LABEL,
+                    root::ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX,
+                ],
+            );
+            let show_fire_mode = is_relevant(ModeParameter::FireMode)
+                && (!compact || !mode_has_default_fire_settings(mode));
             self.enable_if(
                 show_fire_mode,
                 &[
@@ -6086,6 +7001,19 @@ impl<'a> ImmutableMappingPanel<'a> {
                 value,
                 initiator,
             );
+            if let (FireMode::AfterTimeoutKeepFiring, Some(note_length)) = (
+                self.mapping.mode_model.fire_mode(),
+                self.mode.synced_turbo_rate(),
+            ) {
+                if initiator != Some(root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL) {
+                    self.view
+                        .require_control(root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL)
+                        .set_text(format_note_length(note_length));
+                }
+                self.view
+                    .require_control(root::ID_MODE_FIRE_LINE_3_LABEL_2)
+                    .set_text("note");
+            }
         }
         self.show_if(
             option.is_some(),
@@ -6252,6 +7180,18 @@ impl<'a> ImmutableMappingPanel<'a> {
             .unwrap();
     }
 
+    fn invalidate_mode_button_behavior_combo_box(&self) {
+        let combo = self
+            .view
+            .require_control(root::ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX);
+        // Refill in order to get rid of a custom item that was added before
+        self.fill_mode_button_behavior_combo_box();
+        match self.mode.button_behavior() {
+            Some(b) => combo.select_combo_box_item_by_index(b.into()).unwrap(),
+            None => combo.select_new_combo_box_item("<Custom>".to_string()),
+        }
+    }
+
     fn invalidate_mode_encoder_usage_combo_box(&self) {
         let usage = self.mode.encoder_usage();
         self.view
@@ -6260,6 +7200,52 @@ impl<'a> ImmutableMappingPanel<'a> {
             .unwrap();
     }
 
+    fn invalidate_mode_acceleration_curve_combo_box(&self) {
+        let curve = self.mode.acceleration_curve();
+        let combo = self
+            .view
+            .require_control(root::ID_MODE_ACCELERATION_CURVE_COMBO_BOX);
+        // Refill in order to get rid of a custom item that was added before
+        self.fill_mode_acceleration_curve_combo_box();
+        match ACCELERATION_CURVE_PRESETS
+            .iter()
+            .position(|(_, preset)| *preset == curve)
+        {
+            Some(i) => combo.select_combo_box_item_by_index(i).unwrap(),
+            None => {
+                let label = match curve {
+                    AccelerationCurve::Exponential(c) => format!("Exp. {:.2}", c.sensitivity),
+                    _ => "<Custom>".to_string(),
+                };
+                combo.select_new_combo_box_item(label);
+            }
+        }
+    }
+
+    fn invalidate_mode_control_curve_combo_box(&self) {
+        let curve = self.mode.control_curve();
+        let combo = self
+            .view
+            .require_control(root::ID_MODE_CONTROL_CURVE_COMBO_BOX);
+        // Refill in order to get rid of a custom item that was added before
+        self.fill_mode_control_curve_combo_box();
+        match CONTROL_CURVE_PRESETS
+            .iter()
+            .position(|(_, preset)| *preset == curve)
+        {
+            Some(i) => combo.select_combo_box_item_by_index(i).unwrap(),
+            None => {
+                let label = match curve {
+                    ControlCurve::Linear => "Linear".to_string(),
+                    ControlCurve::Logarithmic(c) => format!("Log. {:.2}", c.amount),
+                    ControlCurve::Exponential(c) => format!("Exp. {:.2}", c.amount),
+                    ControlCurve::SCurve(c) => format!("S-curve {:.2}", c.amount),
+                };
+                combo.select_new_combo_box_item(label);
+            }
+        }
+    }
+
     fn invalidate_mode_reverse_check_box(&self) {
         self.view
             .require_control(root::ID_SETTINGS_REVERSE_CHECK_BOX)
@@ -6320,11 +7306,12 @@ impl<'a> ImmutableMappingPanel<'a> {
         let b = self
             .view
             .require_control(root::ID_SOURCE_CATEGORY_COMBO_BOX);
-        let is_main_mapping = self.mapping.compartment() == Compartment::Main;
+        let compartment = self.mapping.compartment();
         b.fill_combo_box_small(
             SourceCategory::into_enum_iter()
-                // Don't allow controller mappings to have virtual source
-                .filter(|c| is_main_mapping || *c != SourceCategory::Virtual),
+                // Don't allow controller mappings to have virtual or audio sources. Those are the
+                // last ones, so combo box indexes still correspond to the category indexes.
+                .filter(|c| c.is_allowed_in(compartment)),
         );
     }
 
@@ -6335,6 +7322,12 @@ impl<'a> ImmutableMappingPanel<'a> {
         b.fill_combo_box_indexed(FeedbackSendBehavior::into_enum_iter());
     }
 
+    fn fill_mapping_projection_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MAPPING_PROJECTION_COMBO_BOX)
+            .fill_combo_box_indexed(ProjectionVisibility::into_enum_iter());
+    }
+
     fn fill_target_category_combo_box(&self) {
         let b = self
             .view
@@ -6349,6 +7342,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             Midi => b.fill_combo_box_indexed(MidiSourceType::into_enum_iter()),
             Reaper => b.fill_combo_box_indexed(ReaperSourceType::into_enum_iter()),
             Virtual => b.fill_combo_box_indexed(VirtualControlElementType::into_enum_iter()),
+            Audio => b.fill_combo_box_indexed(AudioInputKind::into_enum_iter()),
             Osc | Never | Keyboard => {}
         };
     }
@@ -6408,12 +7402,30 @@ impl<'a> ImmutableMappingPanel<'a> {
             .fill_combo_box_indexed(ButtonUsage::into_enum_iter());
     }
 
+    fn fill_mode_button_behavior_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX)
+            .fill_combo_box_indexed(ButtonBehavior::into_enum_iter());
+    }
+
     fn fill_mode_encoder_usage_combo_box(&self) {
         self.view
             .require_control(root::ID_MODE_RELATIVE_FILTER_COMBO_BOX)
             .fill_combo_box_indexed(EncoderUsage::into_enum_iter());
     }
 
+    fn fill_mode_acceleration_curve_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_ACCELERATION_CURVE_COMBO_BOX)
+            .fill_combo_box_indexed(ACCELERATION_CURVE_PRESETS.iter().map(|(label, _)| label));
+    }
+
+    fn fill_mode_control_curve_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_CONTROL_CURVE_COMBO_BOX)
+            .fill_combo_box_indexed(CONTROL_CURVE_PRESETS.iter().map(|(label, _)| label));
+    }
+
     fn fill_target_type_combo_box(&self) {
         let b = self.view.require_control(root::ID_TARGET_TYPE_COMBO_BOX);
         use TargetCategory::*;
@@ -6448,6 +7460,7 @@ impl View for MappingPanel {
     fn opened(self: SharedView<Self>, window: Window) -> bool {
         self.init_controls();
         self.mapping_header_panel.clone().open(window);
+        restore_window_bounds(window, &self.session().borrow(), &self.window_name);
         true
     }
 
@@ -6456,6 +7469,10 @@ impl View for MappingPanel {
         true
     }
 
+    fn context_menu_wanted(self: SharedView<Self>, location: Point<Pixels>) {
+        let _ = self.open_context_menu(location);
+    }
+
     fn closed(self: SharedView<Self>, _window: Window) {
         self.window_cache.replace(None);
     }
@@ -6469,15 +7486,16 @@ impl View for MappingPanel {
             root::IDC_MAPPING_ENABLED_CHECK_BOX => {
                 self.write(|p| p.update_mapping_is_enabled());
             }
-            root::ID_MAPPING_SHOW_IN_PROJECTION_CHECK_BOX => {
-                self.write(|p| p.update_mapping_is_visible_in_projection());
-            }
             root::ID_MAPPING_ADVANCED_BUTTON => {
                 self.edit_advanced_settings();
             }
             root::ID_MAPPING_FIND_IN_LIST_BUTTON => {
                 self.force_scroll_to_mapping_in_main_panel();
             }
+            root::ID_MAPPING_SECTION_HELP_BUTTON => open_user_guide_section("mapping"),
+            root::ID_SOURCE_SECTION_HELP_BUTTON => open_user_guide_section("source"),
+            root::ID_TARGET_SECTION_HELP_BUTTON => open_user_guide_section("target"),
+            root::ID_GLUE_SECTION_HELP_BUTTON => open_user_guide_section("glue"),
             // IDCANCEL is escape button
             root::ID_MAPPING_PANEL_OK | raw::IDCANCEL => {
                 self.hide();
@@ -6499,6 +7517,9 @@ impl View for MappingPanel {
             root::ID_SOURCE_LINE_4_BUTTON => {
                 let _ = self.handle_source_line_4_button_press();
             }
+            root::ID_SOURCE_DEBOUNCE_BUTTON => {
+                let _ = self.handle_source_debounce_button_press();
+            }
             root::ID_MODE_EEL_CONTROL_TRANSFORMATION_DETAIL_BUTTON => {
                 self.edit_control_transformation()
             }
@@ -6551,6 +7572,9 @@ impl View for MappingPanel {
             root::ID_MAPPING_FEEDBACK_SEND_BEHAVIOR_COMBO_BOX => {
                 self.write(|p| p.update_mapping_feedback_send_behavior())
             }
+            root::ID_MAPPING_PROJECTION_COMBO_BOX => {
+                self.write(|p| p.update_mapping_projection_visibility())
+            }
             // Source
             root::ID_SOURCE_CATEGORY_COMBO_BOX => self.write(|p| p.update_source_category()),
             root::ID_SOURCE_TYPE_COMBO_BOX => {
@@ -6581,11 +7605,17 @@ impl View for MappingPanel {
             }
             root::ID_MODE_TAKEOVER_MODE => self.write(|p| p.update_takeover_mode()),
             root::ID_MODE_BUTTON_FILTER_COMBO_BOX => self.write(|p| p.update_button_usage()),
+            root::ID_MODE_BUTTON_BEHAVIOR_COMBO_BOX => self.write(|p| p.update_button_behavior()),
             root::ID_MODE_RELATIVE_FILTER_COMBO_BOX => self.write(|p| p.update_encoder_usage()),
+            root::ID_MODE_ACCELERATION_CURVE_COMBO_BOX => {
+                self.write(|p| p.update_mode_acceleration_curve())
+            }
+            root::ID_MODE_CONTROL_CURVE_COMBO_BOX => self.write(|p| p.update_mode_control_curve()),
             root::ID_MODE_FIRE_COMBO_BOX => self.write(|p| p.update_mode_fire_mode()),
             root::IDC_MODE_FEEDBACK_TYPE_COMBO_BOX => self.write(|p| p.update_mode_feedback_type()),
             // Target
             root::ID_TARGET_CATEGORY_COMBO_BOX => self.write(|p| p.update_target_category()),
+            root::ID_TARGET_HISTORY_COMBO_BOX => self.write(|p| p.pick_target_from_history()),
             root::ID_TARGET_TYPE_COMBO_BOX => self.write(|p| p.update_target_type()),
             root::ID_TARGET_LINE_2_COMBO_BOX_1 => {
                 self.write(|p| p.handle_target_line_2_combo_box_1_change())
@@ -6740,6 +7770,9 @@ impl View for MappingPanel {
         // manipulated very frequently, we just invalidate all controls.
         // If this fails (because the mapping is not filled anymore), it's not a problem.
         let _ = view.read(|p| {
+            if resource_id == root::ID_SOURCE_LINE_5_EDIT_CONTROL {
+                p.warn_about_invalid_source_line_5_text();
+            }
             p.invalidate_all_controls();
         });
         false
@@ -6760,6 +7793,68 @@ impl View for MappingPanel {
 
 const SOURCE_MATCH_INDICATOR_TIMER_ID: usize = 570;
 
+/// Number of channels offered in the audio input channel selector.
+const MAX_AUDIO_CHANNEL_COUNT: u32 = 64;
+
+/// Acceleration curves offered in the "Make absolute" curve selector.
+const ACCELERATION_CURVE_PRESETS: [(&str, AccelerationCurve); 5] = [
+    ("Linear", AccelerationCurve::Linear),
+    ("No accel.", AccelerationCurve::Off),
+    (
+        "Exp. soft",
+        AccelerationCurve::Exponential(ExponentialAccelerationCurve { sensitivity: 0.5 }),
+    ),
+    (
+        "Exp. medium",
+        AccelerationCurve::Exponential(ExponentialAccelerationCurve { sensitivity: 1.0 }),
+    ),
+    (
+        "Exp. strong",
+        AccelerationCurve::Exponential(ExponentialAccelerationCurve { sensitivity: 2.0 }),
+    ),
+];
+
+/// Curves offered as alternative to a hand-written control transformation.
+const CONTROL_CURVE_PRESETS: [(&str, ControlCurve); 10] = [
+    ("Linear curve", ControlCurve::Linear),
+    (
+        "Log. soft",
+        ControlCurve::Logarithmic(ShapedControlCurve { amount: 0.25 }),
+    ),
+    (
+        "Log. medium",
+        ControlCurve::Logarithmic(ShapedControlCurve { amount: 0.5 }),
+    ),
+    (
+        "Log. strong",
+        ControlCurve::Logarithmic(ShapedControlCurve { amount: 1.0 }),
+    ),
+    (
+        "Exp. soft",
+        ControlCurve::Exponential(ShapedControlCurve { amount: 0.25 }),
+    ),
+    (
+        "Exp. medium",
+        ControlCurve::Exponential(ShapedControlCurve { amount: 0.5 }),
+    ),
+    (
+        "Exp. strong",
+        ControlCurve::Exponential(ShapedControlCurve { amount: 1.0 }),
+    ),
+    (
+        "S-curve soft",
+        ControlCurve::SCurve(ShapedControlCurve { amount: 0.25 }),
+    ),
+    (
+        "S-curve medium",
+        ControlCurve::SCurve(ShapedControlCurve { amount: 0.5 }),
+    ),
+    (
+        "S-curve strong",
+        ControlCurve::SCurve(ShapedControlCurve { amount: 1.0 }),
+    ),
+];
+
 trait WindowExt {
     fn slider_unit_value(&self) -> UnitValue;
     fn slider_discrete_increment(&self) -> DiscreteIncrement;
@@ -6988,7 +8083,12 @@ fn bookmark_combo_box_entries(
         })
 }
 
-fn select_bookmark_in_combo_box(combo: Window, anchor_type: BookmarkAnchorType, bookmark_ref: u32) {
+fn select_bookmark_in_combo_box(
+    combo: Window,
+    anchor_type: BookmarkAnchorType,
+    bookmark_ref: u32,
+    bookmark_name: &str,
+) {
     let successful = match anchor_type {
         BookmarkAnchorType::Id => combo
             .select_combo_box_item_by_data(bookmark_ref as _)
@@ -6996,10 +8096,11 @@ fn select_bookmark_in_combo_box(combo: Window, anchor_type: BookmarkAnchorType,
         BookmarkAnchorType::Index => combo
             .select_combo_box_item_by_index(bookmark_ref as _)
             .is_ok(),
+        BookmarkAnchorType::Name => false,
     };
     if !successful {
         combo.select_new_combo_box_item(
-            get_non_present_bookmark_label(anchor_type, bookmark_ref).as_str(),
+            get_non_present_bookmark_label(anchor_type, bookmark_ref, bookmark_name).as_str(),
         );
     }
 }
@@ -7180,6 +8281,9 @@ fn prompt_for_predefined_control_element_name(
             VirtualControlElementType::Button => {
                 control_element_domains::daw::PREDEFINED_VIRTUAL_BUTTON_NAMES
             }
+            VirtualControlElementType::Display => {
+                control_element_domains::daw::PREDEFINED_VIRTUAL_DISPLAY_NAMES
+            }
         };
         let grid_control_names = match r#type {
             VirtualControlElementType::Multi => {
@@ -7188,6 +8292,9 @@ fn prompt_for_predefined_control_element_name(
             VirtualControlElementType::Button => {
                 control_element_domains::grid::PREDEFINED_VIRTUAL_BUTTON_NAMES
             }
+            VirtualControlElementType::Display => {
+                control_element_domains::grid::PREDEFINED_VIRTUAL_DISPLAY_NAMES
+            }
         };
         let entries = vec![
             menu(
@@ -7292,7 +8399,7 @@ fn show_feedback_popup_menu(
                         move || OpenColorPicker(color_target),
                     ),
                 ].into_iter()
-                    .chain(["target.track.color", "target.bookmark.color"].into_iter().map(|key| {
+                    .chain(["target.track.color", "target.bookmark.color", "target.clip_row.color"].into_iter().map(|key| {
                         item_with_opts(
                             key,
                             ItemOpts {
@@ -7529,10 +8636,141 @@ fn parse_osc_arg_value_range(text: &str) -> Interval<f64> {
         .unwrap_or(DEFAULT_OSC_ARG_VALUE_RANGE)
 }
 
+/// Formats velocity feedback as range ("1 - 64") or as palette ("5 9 13").
+fn format_velocity_feedback(feedback: Option<&VelocityFeedback>) -> String {
+    match feedback {
+        None => "".to_owned(),
+        Some(VelocityFeedback::Range(r)) => format!("{} - {}", r.min, r.max),
+        Some(VelocityFeedback::Palette(p)) => p.velocities.iter().join(" "),
+    }
+}
+
+/// Parses a velocity range ("10 - 100") or palette ("0 5 127"). Empty text means no velocity
+/// feedback.
+fn parse_velocity_feedback(text: &str) -> Result<Option<VelocityFeedback>, &'static str> {
+    let parse_velocity = |t: &str| {
+        t.trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|v| *v <= 127)
+            .ok_or("velocities must be numbers between 0 and 127")
+    };
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    if let Some((min, max)) = text.split_once('-') {
+        let range = VelocityFeedbackRange {
+            min: parse_velocity(min)?,
+            max: parse_velocity(max)?,
+        };
+        return Ok(Some(VelocityFeedback::Range(range)));
+    }
+    let velocities: Vec<u8> = text
+        .split_whitespace()
+        .map(parse_velocity)
+        .collect::<Result<_, _>>()?;
+    Ok(Some(VelocityFeedback::Palette(VelocityFeedbackPalette {
+        velocities,
+    })))
+}
+
+/// Parses attack and release time in milliseconds ("10 - 300").
+fn parse_attack_release(text: &str) -> Option<(u64, u64)> {
+    let (attack, release) = text.split_once('-')?;
+    Some((attack.trim().parse().ok()?, release.trim().parse().ok()?))
+}
+
+/// Formats source debounce settings as CSV for the user input dialog ("2;30").
+fn format_source_debounce(debounce: Option<&SourceDebounce>) -> String {
+    let debounce = debounce.copied().unwrap_or_default();
+    let min_change = debounce
+        .min_change
+        .map(format_percentage_without_unit)
+        .unwrap_or_default();
+    let hold_time = debounce
+        .hold_time
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    format!("{};{}", min_change, hold_time)
+}
+
+fn parse_source_debounce(csv: &str) -> Result<Option<SourceDebounce>, &'static str> {
+    let (min_change, hold_time) = csv.split_once(';').ok_or("couldn't split")?;
+    let min_change = match min_change.trim() {
+        "" => None,
+        t => {
+            let percent: f64 = t.parse().map_err(|_| "invalid min change")?;
+            Some(percent / 100.0).filter(|v| *v > 0.0)
+        }
+    };
+    let hold_time = match hold_time.trim() {
+        "" => None,
+        t => Some(t.parse::<u64>().map_err(|_| "invalid hold time")?).filter(|v| *v > 0),
+    };
+    if min_change.is_none() && hold_time.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(SourceDebounce {
+        min_change,
+        hold_time,
+    }))
+}
+
+/// Formats pitch bend behavior as CSV for the user input dialog ("5;relative").
+fn format_pitch_bend_behavior(behavior: Option<&PitchBendBehavior>) -> String {
+    let behavior = match behavior {
+        None => return ";".to_string(),
+        Some(b) => b,
+    };
+    let center_dead_zone = behavior
+        .center_dead_zone
+        .map(format_percentage_without_unit)
+        .unwrap_or_default();
+    let mode = match behavior.mode.unwrap_or_default() {
+        PitchBendMode::Absolute => "absolute",
+        PitchBendMode::RelativeFromCenter => "relative",
+    };
+    format!("{};{}", center_dead_zone, mode)
+}
+
+fn parse_pitch_bend_behavior(csv: &str) -> Result<Option<PitchBendBehavior>, &'static str> {
+    let (center_dead_zone, mode) = csv.split_once(';').ok_or("couldn't split")?;
+    let center_dead_zone = match center_dead_zone.trim() {
+        "" => None,
+        t => {
+            let percent: f64 = t.parse().map_err(|_| "invalid center dead zone")?;
+            if !(0.0..100.0).contains(&percent) {
+                return Err("center dead zone must be at least 0% and less than 100%");
+            }
+            Some(percent / 100.0).filter(|v| *v > 0.0)
+        }
+    };
+    let mode = match mode.trim().to_lowercase().chars().next() {
+        None => None,
+        Some('a') => Some(PitchBendMode::Absolute),
+        Some('r') => Some(PitchBendMode::RelativeFromCenter),
+        _ => return Err("mode must be absolute or relative"),
+    };
+    if center_dead_zone.is_none() && mode.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(PitchBendBehavior {
+        center_dead_zone,
+        mode,
+    }))
+}
+
 fn extract_first_line(text: &str) -> &str {
     text.lines().next().unwrap_or_default()
 }
 
+fn mode_has_default_fire_settings(mode: &ModeModel) -> bool {
+    let defaults = ModeModel::default();
+    mode.fire_mode() == defaults.fire_mode()
+        && mode.press_duration_interval() == defaults.press_duration_interval()
+        && mode.turbo_rate() == defaults.turbo_rate()
+}
+
 fn has_multiple_lines(text: &str) -> bool {
     text.lines().count() > 1
 }
@@ -7555,3 +8793,24 @@ fn get_relevant_target_fx(mapping: &MappingModel, session: &Session) -> Option<F
             .ok()
     }
 }
+
+/// Returns the recently touched targets which can be picked in the given session.
+///
+/// Targets from other project tabs are left out (same as when learning targets).
+fn relevant_touched_target_history(session: &Session) -> Vec<ReaperTarget> {
+    let project = session.processor_context().project_or_current_project();
+    BackboneState::get()
+        .touched_target_history()
+        .into_iter()
+        .filter(|t| t.project().map(|p| p == project).unwrap_or(true))
+        .collect()
+}
+
+/// Opens the section of the online user guide which describes the given part of the mapping panel.
+fn open_user_guide_section(anchor: &str) {
+    let url = format!(
+        "https://github.com/helgoboss/realearn/blob/master/doc/user-guide.adoc#{}",
+        anchor
+    );
+    open_in_browser(&url);
+}
@@ -1,12 +1,15 @@
 use crate::application::{
-    Affected, CompartmentProp, MappingCommand, MappingModel, MappingProp, Session, SessionProp,
-    SharedMapping, SharedSession, SourceCategory, TargetCategory, TargetModelFormatMultiLine,
-    WeakSession,
+    Affected, CompartmentProp, ConcreteFxInstruction, ConcreteTrackInstruction, GroupCommand,
+    MappingCommand, MappingIssue, MappingModel, MappingProp, MappingValidationContext,
+    RemapCandidate, Session, SessionProp, SharedGroup, SharedMapping, SharedSession,
+    SourceCategory, TargetCategory, TargetModelFormatMultiLine, WeakSession,
 };
 use crate::base::when;
 use crate::domain::{Compartment, GroupId, GroupKey, MappingId, QualifiedMappingId};
 
-use crate::domain::ui_util::format_tags_as_csv;
+use crate::domain::ui_util::{
+    format_as_percentage_without_unit, format_tags_as_csv, parse_unit_value_from_percentage,
+};
 use crate::infrastructure::api::convert::from_data::ConversionStyle;
 use crate::infrastructure::data::{
     ActivationConditionData, MappingModelData, ModeModelData, SourceModelData, TargetModelData,
@@ -17,6 +20,7 @@ use crate::infrastructure::ui::bindings::root::{
     IDC_MAPPING_ROW_ENABLED_CHECK_BOX, ID_MAPPING_ROW_CONTROL_CHECK_BOX,
     ID_MAPPING_ROW_FEEDBACK_CHECK_BOX,
 };
+use crate::infrastructure::ui::dialog_util;
 use crate::infrastructure::ui::dialog_util::add_group_via_dialog;
 use crate::infrastructure::ui::util::{mapping_row_panel_height, symbols};
 use crate::infrastructure::ui::{
@@ -25,7 +29,11 @@ use crate::infrastructure::ui::{
     SerializationFormat, SharedMainState,
 };
 use core::iter;
-use realearn_api::persistence::{ApiObject, Envelope};
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::UnitValue;
+use realearn_api::persistence::{
+    ApiObject, BlinkFeedbackEffect, CrossfadeRole, Envelope, FeedbackEffect, Interval,
+};
 use reaper_high::Reaper;
 use reaper_low::raw;
 use rxrust::prelude::*;
@@ -53,6 +61,8 @@ pub struct MappingRowPanel {
     // mappings than the fixed number, some rows remain unused. In this case their mapping is
     // `None`, which will make the row hide itself.
     mapping: RefCell<Option<SharedMapping>>,
+    // Set instead of the mapping if this row acts as header of a group.
+    group_header: RefCell<Option<GroupHeader>>,
     // Fires when a mapping is about to change.
     party_is_over_subject: RefCell<LocalSubject<'static, (), ()>>,
     panel_manager: Weak<RefCell<IndependentPanelManager>>,
@@ -73,6 +83,7 @@ impl MappingRowPanel {
             row_index,
             party_is_over_subject: Default::default(),
             mapping: None.into(),
+            group_header: None.into(),
             panel_manager,
             is_last_row,
         }
@@ -84,6 +95,15 @@ impl MappingRowPanel {
         use Affected::*;
         use CompartmentProp::*;
         use SessionProp::*;
+        if let Some(header) = self.group_header.borrow().as_ref() {
+            let group = header.group.borrow();
+            if let One(InCompartment(compartment, One(InGroup(group_id, _)))) = affected {
+                if *compartment == group.compartment() && *group_id == group.id() {
+                    self.invalidate_group_header_controls(header);
+                }
+            }
+            return;
+        }
         self.with_mapping(|_, m| {
             match affected {
                 One(InCompartment(compartment, One(InGroup(_, _))))
@@ -156,17 +176,96 @@ impl MappingRowPanel {
         Some(mapping.id())
     }
 
-    pub fn set_mapping(self: &SharedView<Self>, mapping: Option<SharedMapping>) {
+    pub fn set_item(self: &SharedView<Self>, item: Option<MappingRowItem>) {
         self.party_is_over_subject.borrow_mut().next(());
-        match &mapping {
-            None => self.view.require_window().hide(),
-            Some(m) => {
-                self.view.require_window().show();
-                self.invalidate_all_controls(&m.borrow());
-                self.register_listeners();
-            }
+        let (mapping, group_header) = match item {
+            None => (None, None),
+            Some(MappingRowItem::Mapping(m)) => (Some(m), None),
+            Some(MappingRowItem::GroupHeader(h)) => (None, Some(h)),
+        };
+        if mapping.is_none() && group_header.is_none() {
+            self.view.require_window().hide();
+        } else {
+            self.view.require_window().show();
+            self.show_mapping_controls(mapping.is_some());
+        }
+        if let Some(m) = &mapping {
+            self.invalidate_all_controls(&m.borrow());
+            self.register_listeners();
+        }
+        if let Some(h) = &group_header {
+            self.invalidate_group_header_controls(h);
         }
         self.mapping.replace(mapping);
+        self.group_header.replace(group_header);
+    }
+
+    /// Switches between displaying a mapping and displaying a group header.
+    fn show_mapping_controls(&self, show: bool) {
+        let mapping_only_controls = [
+            root::IDC_MAPPING_ROW_ENABLED_CHECK_BOX,
+            root::ID_MAPPING_ROW_EDIT_BUTTON,
+            root::ID_MAPPING_ROW_DUPLICATE_BUTTON,
+            root::ID_MAPPING_ROW_REMOVE_BUTTON,
+            root::ID_MAPPING_ROW_LEARN_SOURCE_BUTTON,
+            root::ID_MAPPING_ROW_LEARN_TARGET_BUTTON,
+            root::ID_MAPPING_ROW_SOURCE_LABEL_TEXT,
+            root::ID_MAPPING_ROW_TARGET_LABEL_TEXT,
+            root::IDC_MAPPING_ROW_MATCHED_INDICATOR_TEXT,
+            root::ID_DOWN_BUTTON,
+        ];
+        for id in mapping_only_controls {
+            self.view.require_control(id).set_visible(show);
+        }
+        if show {
+            // In a group header, the up button is used for expanding/collapsing the group
+            self.view
+                .require_control(root::ID_UP_BUTTON)
+                .set_text(symbols::arrow_up_symbol().to_string());
+        }
+    }
+
+    fn invalidate_group_header_controls(&self, header: &GroupHeader) {
+        let group = header.group.borrow();
+        let mapping_label = if header.mapping_count == 1 {
+            "mapping"
+        } else {
+            "mappings"
+        };
+        self.view
+            .require_control(root::ID_MAPPING_ROW_MAPPING_LABEL)
+            .set_text(format!(
+                "{} ({} {})",
+                group.effective_name(),
+                header.mapping_count,
+                mapping_label
+            ));
+        self.view
+            .require_control(root::ID_MAPPING_ROW_GROUP_LABEL)
+            .set_text(format_tags_as_csv(group.tags()));
+        self.view
+            .require_control(root::ID_UP_BUTTON)
+            .set_text(if header.is_collapsed { "+" } else { "-" });
+        self.view
+            .require_control(root::ID_MAPPING_ROW_CONTROL_CHECK_BOX)
+            .set_checked(group.control_is_enabled());
+        self.view
+            .require_control(root::ID_MAPPING_ROW_FEEDBACK_CHECK_BOX)
+            .set_checked(group.feedback_is_enabled());
+        for id in [
+            root::ID_MAPPING_ROW_MAPPING_LABEL,
+            root::ID_MAPPING_ROW_GROUP_LABEL,
+        ] {
+            self.view.require_control(id).enable();
+        }
+        let enabled = !self.mappings_are_read_only();
+        for id in [
+            root::ID_MAPPING_ROW_CONTROL_CHECK_BOX,
+            root::ID_MAPPING_ROW_FEEDBACK_CHECK_BOX,
+        ] {
+            self.view.require_control(id).set_enabled(enabled);
+        }
+        self.view.require_control(root::ID_UP_BUTTON).enable();
     }
 
     fn invalidate_all_controls(&self, mapping: &MappingModel) {
@@ -272,6 +371,19 @@ impl MappingRowPanel {
         } else {
             plain_label
         };
+        let session = self.session();
+        let session = session.borrow();
+        let validation_context = MappingValidationContext {
+            compartment_mappings: Some(session.mapping_slice(mapping.compartment())),
+            ..Default::default()
+        };
+        let rich_label = prefix_with_issue_labels(
+            rich_label,
+            mapping
+                .validate(validation_context)
+                .into_iter()
+                .filter(|i| i.code.concerns_source()),
+        );
         self.view
             .require_window()
             .require_control(root::ID_MAPPING_ROW_SOURCE_LABEL_TEXT)
@@ -293,6 +405,18 @@ impl MappingRowPanel {
         let target_model_string =
             TargetModelFormatMultiLine::new(&mapping.target_model, context, mapping.compartment())
                 .to_string();
+        let instance_state = session.instance_state().borrow();
+        let validation_context = MappingValidationContext {
+            instance_state: Some(&*instance_state),
+            ..Default::default()
+        };
+        let target_model_string = prefix_with_issue_labels(
+            target_model_string,
+            mapping
+                .validate(validation_context)
+                .into_iter()
+                .filter(|i| i.code.concerns_target()),
+        );
         self.view
             .require_window()
             .require_control(root::ID_MAPPING_ROW_TARGET_LABEL_TEXT)
@@ -423,6 +547,9 @@ impl MappingRowPanel {
         self.when(instance_state.on_mappings_changed(), |view| {
             view.with_mapping(Self::invalidate_on_indicator);
         });
+        self.when(instance_state.missing_target_objects_changed(), |view| {
+            view.with_mapping(Self::invalidate_target_label);
+        });
         self.when(
             session
                 .main_preset_auto_load_mode
@@ -476,13 +603,19 @@ impl MappingRowPanel {
         // When we route keyboard input to ReaLearn and press space, it presses the "Up" button,
         // even if we don't display the rows. Don't know why, but suppress a panic here.
         let mapping = self.optional_mapping().ok_or("row has no mapping")?;
+        let compartment = self.active_compartment();
+        let session = self.session();
+        // If the list is grouped (because there are groups), a mapping can only be moved within
+        // its group. Otherwise it would be moved across mappings of other groups, which is not
+        // visible in the list.
         let within_same_group = self
             .main_state
             .borrow()
             .displayed_group_for_active_compartment()
-            .is_some();
-        let _ = self.session().borrow_mut().move_mapping_within_list(
-            self.active_compartment(),
+            .is_some()
+            || session.borrow().groups(compartment).next().is_some();
+        let _ = session.borrow_mut().move_mapping_within_list(
+            compartment,
             mapping.borrow().id(),
             within_same_group,
             increment,
@@ -528,6 +661,31 @@ impl MappingRowPanel {
             .toggle_learning_target(&shared_session, self.require_qualified_mapping_id());
     }
 
+    fn toggle_group_collapsed(&self) {
+        let group_id = match self.group_header.borrow().as_ref() {
+            None => return,
+            Some(h) => h.group.borrow().id(),
+        };
+        let mut main_state = self.main_state.borrow_mut();
+        let compartment = main_state.active_compartment.get();
+        main_state.toggle_group_collapsed(compartment, group_id);
+    }
+
+    fn change_group(&self, create_cmd: impl FnOnce(bool) -> GroupCommand, checkbox_id: u32) {
+        let group = match self.group_header.borrow().as_ref() {
+            None => return,
+            Some(h) => h.group.clone(),
+        };
+        let checked = self.view.require_control(checkbox_id).is_checked();
+        let mut group = group.borrow_mut();
+        Session::change_group_from_ui_simple(
+            self.session.clone(),
+            &mut group,
+            create_cmd(checked),
+            None,
+        );
+    }
+
     fn update_is_enabled(&self) {
         let checked = self
             .view
@@ -558,6 +716,66 @@ impl MappingRowPanel {
         Session::change_mapping_from_ui_simple(self.session.clone(), &mut mapping, cmd, None);
     }
 
+    fn edit_blink_feedback_effect(&self) {
+        // Don't keep anything borrowed while the prompt is open
+        let current_effect = self.require_mapping().borrow().feedback_effect();
+        let current_interval = match current_effect {
+            Some(FeedbackEffect::Blink(e)) => e.target_interval,
+            _ => None,
+        };
+        let mut text = current_interval
+            .map(|i| {
+                format!(
+                    "{} - {}",
+                    format_as_percentage_without_unit(UnitValue::new_clamped(i.0)),
+                    format_as_percentage_without_unit(UnitValue::new_clamped(i.1))
+                )
+            })
+            .unwrap_or_default();
+        loop {
+            text = match dialog_util::prompt_for(
+                "Blink while target value in % (e.g. 50 - 100; empty = above 0)",
+                &text,
+            ) {
+                None => return,
+                Some(t) => t,
+            };
+            match parse_blink_interval(&text) {
+                Ok(target_interval) => {
+                    let effect = FeedbackEffect::Blink(BlinkFeedbackEffect { target_interval });
+                    self.change_mapping(MappingCommand::SetFeedbackEffect(Some(effect)));
+                    return;
+                }
+                Err(msg) => {
+                    self.view
+                        .require_window()
+                        .alert("ReaLearn", format!("Invalid interval: {}", msg));
+                }
+            }
+        }
+    }
+
+    fn remap_missing_object(&self, candidate: RemapCandidate) {
+        let mapping = self.require_mapping();
+        let mut mapping = mapping.borrow_mut();
+        let shared_session = self.session();
+        let mut session = shared_session.borrow_mut();
+        session.change_target_with_closure(&mut mapping, None, self.session.clone(), |ctx| {
+            match candidate {
+                RemapCandidate::Track(track) => ctx.mapping.target_model.set_concrete_track(
+                    ConcreteTrackInstruction::ByIdWithTrack(track),
+                    false,
+                    true,
+                ),
+                RemapCandidate::Fx(fx) => ctx.mapping.target_model.set_concrete_fx(
+                    ConcreteFxInstruction::ByIdWithFx(fx),
+                    false,
+                    true,
+                ),
+            }
+        });
+    }
+
     fn notify_user_on_error(&self, result: Result<(), Box<dyn Error>>) {
         if let Err(e) = result {
             self.view.require_window().alert("ReaLearn", e.to_string());
@@ -619,6 +837,12 @@ impl MappingRowPanel {
             PasteMappings(Envelope<Vec<MappingModelData>>),
             CopyPart(ObjectType),
             MoveMappingToGroup(Option<GroupId>),
+            MoveMappingToCompartment(Compartment),
+            SetCrossfadeRole(Option<CrossfadeRole>),
+            SetFeedbackEffect(Option<FeedbackEffect>),
+            EditBlinkFeedbackEffect,
+            SetInvertFeedback(bool),
+            RemapMissingObject(RemapCandidate),
             CopyMappingAsLua(ConversionStyle),
             PasteFromLuaReplace(String),
             PasteFromLuaInsertBelow(String),
@@ -646,6 +870,19 @@ impl MappingRowPanel {
             let text_from_clipboard_clone = text_from_clipboard.clone();
             let data_object_from_clipboard_clone = data_object_from_clipboard.clone();
             let group_id = mapping.group_id();
+            let crossfade_role = mapping.crossfade_role();
+            let feedback_effect = mapping.feedback_effect();
+            let invert_feedback = mapping.invert_feedback();
+            let target_with_context = mapping
+                .target_model
+                .with_context(session.extended_context(), compartment);
+            let missing_object = session
+                .instance_state()
+                .borrow()
+                .missing_target_object(mapping.qualified_id());
+            let remap_candidates = missing_object
+                .map(|o| target_with_context.remap_candidates(o))
+                .unwrap_or_default();
             let entries = vec![
                 item("Copy", || MenuAction::CopyPart(ObjectType::Mapping)),
                 {
@@ -729,6 +966,80 @@ impl MappingRowPanel {
                         }))
                         .collect(),
                 ),
+                {
+                    let other_compartment = compartment.other();
+                    item(format!("Move to {}", other_compartment), move || {
+                        MenuAction::MoveMappingToCompartment(other_compartment)
+                    })
+                },
+                menu(
+                    "Crossfade role",
+                    iter::once(None)
+                        .chain(CrossfadeRole::into_enum_iter().map(Some))
+                        .map(|role| {
+                            let label = match role {
+                                None => "<None>".to_string(),
+                                Some(r) => r.to_string(),
+                            };
+                            item_with_opts(
+                                label,
+                                ItemOpts {
+                                    enabled: true,
+                                    checked: role == crossfade_role,
+                                },
+                                move || MenuAction::SetCrossfadeRole(role),
+                            )
+                        })
+                        .collect(),
+                ),
+                menu(
+                    "Feedback effect",
+                    vec![
+                        item_with_opts(
+                            "<None>",
+                            ItemOpts {
+                                enabled: true,
+                                checked: feedback_effect.is_none(),
+                            },
+                            || MenuAction::SetFeedbackEffect(None),
+                        ),
+                        item_with_opts(
+                            "Blink...",
+                            ItemOpts {
+                                enabled: true,
+                                checked: matches!(feedback_effect, Some(FeedbackEffect::Blink(_))),
+                            },
+                            || MenuAction::EditBlinkFeedbackEffect,
+                        ),
+                        item_with_opts(
+                            "Flash on change",
+                            ItemOpts {
+                                enabled: true,
+                                checked: matches!(feedback_effect, Some(FeedbackEffect::Flash)),
+                            },
+                            || MenuAction::SetFeedbackEffect(Some(FeedbackEffect::Flash)),
+                        ),
+                        separator(),
+                        item_with_opts(
+                            "Invert feedback",
+                            ItemOpts {
+                                enabled: true,
+                                checked: invert_feedback,
+                            },
+                            move || MenuAction::SetInvertFeedback(!invert_feedback),
+                        ),
+                    ],
+                ),
+                match missing_object {
+                    Some(o) if !remap_candidates.is_empty() => menu(
+                        format!("Remap missing {}", o),
+                        remap_candidates
+                            .into_iter()
+                            .map(|c| item(c.label(), move || MenuAction::RemapMissingObject(c)))
+                            .collect(),
+                    ),
+                    _ => disabled_item("Remap missing track/FX"),
+                },
                 menu(
                     "Advanced",
                     vec![
@@ -819,6 +1130,30 @@ impl MappingRowPanel {
                     group_id,
                 );
             }
+            MenuAction::MoveMappingToCompartment(dest_compartment) => {
+                let result = move_mapping_to_compartment(
+                    self.session(),
+                    triple.compartment,
+                    triple.mapping_id,
+                    dest_compartment,
+                );
+                self.notify_user_on_error(result);
+            }
+            MenuAction::SetCrossfadeRole(role) => {
+                self.change_mapping(MappingCommand::SetCrossfadeRole(role));
+            }
+            MenuAction::SetFeedbackEffect(effect) => {
+                self.change_mapping(MappingCommand::SetFeedbackEffect(effect));
+            }
+            MenuAction::EditBlinkFeedbackEffect => {
+                self.edit_blink_feedback_effect();
+            }
+            MenuAction::SetInvertFeedback(value) => {
+                self.change_mapping(MappingCommand::SetInvertFeedback(value));
+            }
+            MenuAction::RemapMissingObject(candidate) => {
+                self.remap_missing_object(candidate);
+            }
             MenuAction::LogDebugInfo => {
                 let _ = self
                     .session()
@@ -840,6 +1175,22 @@ impl MappingRowPanel {
     }
 }
 
+/// What a row in the mapping list displays.
+#[derive(Clone, Debug)]
+pub enum MappingRowItem {
+    GroupHeader(GroupHeader),
+    Mapping(SharedMapping),
+}
+
+/// Header which precedes the mappings of a group when the mapping list shows all groups.
+#[derive(Clone, Debug)]
+pub struct GroupHeader {
+    pub group: SharedGroup,
+    /// Number of displayed mappings in this group (matching the filter, if any).
+    pub mapping_count: usize,
+    pub is_collapsed: bool,
+}
+
 impl View for MappingRowPanel {
     fn dialog_resource_id(&self) -> u32 {
         root::ID_MAPPING_ROW_PANEL
@@ -864,6 +1215,9 @@ impl View for MappingRowPanel {
         match resource_id {
             root::IDC_MAPPING_ROW_ENABLED_CHECK_BOX => self.update_is_enabled(),
             root::ID_MAPPING_ROW_EDIT_BUTTON => self.edit_mapping(),
+            root::ID_UP_BUTTON if self.group_header.borrow().is_some() => {
+                self.toggle_group_collapsed();
+            }
             root::ID_UP_BUTTON => {
                 let _ = self.move_mapping_within_list(-1);
             }
@@ -874,6 +1228,18 @@ impl View for MappingRowPanel {
             root::ID_MAPPING_ROW_DUPLICATE_BUTTON => self.duplicate_mapping(),
             root::ID_MAPPING_ROW_LEARN_SOURCE_BUTTON => self.toggle_learn_source(),
             root::ID_MAPPING_ROW_LEARN_TARGET_BUTTON => self.toggle_learn_target(),
+            root::ID_MAPPING_ROW_CONTROL_CHECK_BOX if self.group_header.borrow().is_some() => {
+                self.change_group(
+                    GroupCommand::SetControlIsEnabled,
+                    root::ID_MAPPING_ROW_CONTROL_CHECK_BOX,
+                );
+            }
+            root::ID_MAPPING_ROW_FEEDBACK_CHECK_BOX if self.group_header.borrow().is_some() => {
+                self.change_group(
+                    GroupCommand::SetFeedbackIsEnabled,
+                    root::ID_MAPPING_ROW_FEEDBACK_CHECK_BOX,
+                );
+            }
             root::ID_MAPPING_ROW_CONTROL_CHECK_BOX => self.update_control_is_enabled(),
             root::ID_MAPPING_ROW_FEEDBACK_CHECK_BOX => self.update_feedback_is_enabled(),
             _ => unreachable!(),
@@ -930,7 +1296,63 @@ fn move_mapping_to_group(
     Ok(())
 }
 
-fn copy_mapping_object(
+/// Moves the given mapping to the end of another compartment.
+///
+/// Groups are compartment-specific, so the moved mapping ends up in the default group.
+fn move_mapping_to_compartment(
+    session: SharedSession,
+    src_compartment: Compartment,
+    mapping_id: MappingId,
+    dest_compartment: Compartment,
+) -> Result<(), Box<dyn Error>> {
+    let mut session = session.borrow_mut();
+    let (_, mapping) = session
+        .find_mapping_and_index_by_id(src_compartment, mapping_id)
+        .ok_or("mapping not found")?;
+    let mut data = MappingModelData::from_model(
+        &mapping.borrow(),
+        &session.compartment_in_session(src_compartment),
+    );
+    data.group_id = GroupKey::default();
+    adjust_mapping_data_for_compartment(&mut data, dest_compartment);
+    let new_mapping = data.to_model(
+        dest_compartment,
+        &session.compartment_in_session(dest_compartment),
+        Some(session.extended_context()),
+        Some(App::version()),
+    )?;
+    session.remove_mapping(QualifiedMappingId::new(src_compartment, mapping_id));
+    let index = session.mapping_count(dest_compartment);
+    session.insert_mappings_at(dest_compartment, index, iter::once(new_mapping));
+    Ok(())
+}
+
+/// Adjusts source and target categories so that they are allowed in the given compartment.
+///
+/// A virtual target in the controller compartment corresponds to a virtual source in the main
+/// compartment and vice versa, so the control element is carried over. The other side of the
+/// mapping can't be inferred and is reset to its default.
+fn adjust_mapping_data_for_compartment(data: &mut MappingModelData, compartment: Compartment) {
+    if !data.target.category.is_allowed_in(compartment) {
+        data.source = SourceModelData {
+            category: SourceCategory::Virtual,
+            control_element_type: data.target.control_element_type,
+            control_element_index: data.target.control_element_index.clone(),
+            ..Default::default()
+        };
+        data.target = TargetModelData::default();
+    } else if !data.source.category.is_allowed_in(compartment) {
+        data.target = TargetModelData {
+            category: TargetCategory::Virtual,
+            control_element_type: data.source.control_element_type,
+            control_element_index: data.source.control_element_index.clone(),
+            ..Default::default()
+        };
+        data.source = SourceModelData::default();
+    }
+}
+
+pub fn copy_mapping_object(
     session: SharedSession,
     compartment: Compartment,
     mapping_id: MappingId,
@@ -970,7 +1392,7 @@ fn copy_mapping_object(
     Ok(())
 }
 
-enum ObjectType {
+pub enum ObjectType {
     Mapping,
     Source,
     Glue,
@@ -978,7 +1400,7 @@ enum ObjectType {
     ActivationCondition,
 }
 
-fn paste_data_object_in_place(
+pub fn paste_data_object_in_place(
     data_object: DataObject,
     shared_session: SharedSession,
     triple: MappingTriple,
@@ -1095,8 +1517,29 @@ pub fn paste_mappings(
 
 const SOURCE_MATCH_INDICATOR_TIMER_ID: usize = 571;
 
-struct MappingTriple {
-    compartment: Compartment,
-    mapping_id: MappingId,
-    group_id: GroupId,
+pub struct MappingTriple {
+    pub compartment: Compartment,
+    pub mapping_id: MappingId,
+    pub group_id: GroupId,
+}
+
+fn parse_blink_interval(text: &str) -> Result<Option<Interval<f64>>, &'static str> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let (min, max) = text
+        .split_once('-')
+        .ok_or("expected min and max separated by -")?;
+    let min = parse_unit_value_from_percentage(min.trim())?;
+    let max = parse_unit_value_from_percentage(max.trim())?;
+    if min.get() > max.get() {
+        return Err("min must not be greater than max");
+    }
+    Ok(Some(Interval(min.get(), max.get())))
+}
+
+/// Prepends the short labels of the given issues, e.g. "[track missing] ".
+fn prefix_with_issue_labels(label: String, issues: impl Iterator<Item = MappingIssue>) -> String {
+    let prefix: String = issues.map(|i| format!("[{}] ", i.code.label())).collect();
+    prefix + &label
 }
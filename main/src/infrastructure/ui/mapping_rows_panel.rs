@@ -4,8 +4,8 @@ use std::rc::{Rc, Weak};
 use crate::base::when;
 use crate::infrastructure::ui::{
     bindings::root, deserialize_data_object_from_json, get_text_from_clipboard, paste_mappings,
-    util, DataObject, IndependentPanelManager, MainState, MappingRowPanel, ScrollStatus,
-    SharedIndependentPanelManager, SharedMainState,
+    util, DataObject, GroupHeader, IndependentPanelManager, MainState, MappingRowItem,
+    MappingRowPanel, ScrollStatus, SharedIndependentPanelManager, SharedMainState,
 };
 use realearn_api::persistence::Envelope;
 use reaper_high::Reaper;
@@ -13,11 +13,12 @@ use reaper_low::raw;
 use rxrust::prelude::*;
 use slog::debug;
 use std::cmp;
+use std::collections::{HashMap, HashSet};
 
 use crate::application::{
     Affected, Session, SessionProp, SharedMapping, SharedSession, WeakSession,
 };
-use crate::domain::{Compartment, MappingId, MappingMatchedEvent, QualifiedMappingId};
+use crate::domain::{Compartment, GroupId, MappingId, MappingMatchedEvent, QualifiedMappingId};
 use swell_ui::{DialogUnits, Pixels, Point, SharedView, View, ViewContext, Window};
 
 #[derive(Debug)]
@@ -95,21 +96,24 @@ impl MappingRowsPanel {
     ///
     /// Really tries to get mapping on top.
     pub fn force_scroll_to_mapping(&self, id: QualifiedMappingId) {
-        let shared_session = self.session();
-        let session = shared_session.borrow();
-        let index = match session.index_of_mapping(id.compartment, id.id) {
-            None => return,
-            Some(i) => i,
-        };
-        if !self.is_open() {
-            session.show_in_floating_window();
-        }
         {
+            let shared_session = self.session();
+            let session = shared_session.borrow();
+            let group_id = match session.find_mapping_and_index_by_id(id.compartment, id.id) {
+                None => return,
+                Some((_, m)) => m.borrow().group_id(),
+            };
+            if !self.is_open() {
+                session.show_in_floating_window();
+            }
             let mut main_state = self.main_state.borrow_mut();
             main_state.active_compartment.set(id.compartment);
             main_state.clear_all_filters_and_displayed_group();
+            main_state.expand_group(id.compartment, group_id);
+        }
+        if let Some(index) = self.determine_index_of_mapping_in_list(id.compartment, id.id) {
+            self.scroll(index);
         }
-        self.scroll(index);
     }
 
     /// Doesn't switch compartment!
@@ -152,8 +156,12 @@ impl MappingRowsPanel {
         let shared_session = self.session();
         let session = shared_session.borrow();
         let main_state = self.main_state.borrow();
-        let mut mappings = Self::filtered_mappings(&session, &main_state, compartment, false);
-        mappings.position(|m| m.borrow().id() == mapping_id)
+        Self::list_items(&session, &main_state, compartment)
+            .iter()
+            .position(|item| match item {
+                MappingRowItem::Mapping(m) => m.borrow().id() == mapping_id,
+                MappingRowItem::GroupHeader(_) => false,
+            })
     }
 
     pub fn edit_mapping(&self, compartment: Compartment, mapping_id: MappingId) {
@@ -275,13 +283,7 @@ impl MappingRowsPanel {
         let shared_session = self.session();
         let session = shared_session.borrow();
         let main_state = self.main_state.borrow();
-        if !main_state.filter_and_displayed_group_is_active() {
-            return session.mapping_count(self.active_compartment());
-        }
-        session
-            .mappings(self.active_compartment())
-            .filter(|m| Self::mapping_matches_filter(&session, &main_state, m, false))
-            .count()
+        Self::list_items(&session, &main_state, self.active_compartment()).len()
     }
 
     // TODO-low Document all those scrolling functions. It needs explanation.
@@ -333,6 +335,62 @@ impl MappingRowsPanel {
         })
     }
 
+    /// Returns the items to be displayed in the mapping list.
+    ///
+    /// When showing all groups of a compartment which has groups, the mappings are grouped and each
+    /// group is preceded by a header. Mappings of collapsed groups are left out.
+    fn list_items(
+        session: &Session,
+        main_state: &MainState,
+        compartment: Compartment,
+    ) -> Vec<MappingRowItem> {
+        let filtered_mappings = Self::filtered_mappings(session, main_state, compartment, false);
+        let show_group_headers = main_state.displayed_group[compartment].get_ref().is_none()
+            && session.groups(compartment).next().is_some();
+        if !show_group_headers {
+            return filtered_mappings
+                .map(|m| MappingRowItem::Mapping(m.clone()))
+                .collect();
+        }
+        let existing_group_ids: HashSet<_> = session
+            .groups(compartment)
+            .map(|g| g.borrow().id())
+            .collect();
+        let mut mappings_by_group: HashMap<GroupId, Vec<&SharedMapping>> = HashMap::new();
+        for m in filtered_mappings {
+            let group_id = m.borrow().group_id();
+            // Mappings in non-existing groups are listed in the default group
+            let group_id = if existing_group_ids.contains(&group_id) {
+                group_id
+            } else {
+                GroupId::default()
+            };
+            mappings_by_group.entry(group_id).or_default().push(m);
+        }
+        let mut items = vec![];
+        for group in session.groups_sorted(compartment) {
+            let group_id = group.borrow().id();
+            let Some(group_mappings) = mappings_by_group.remove(&group_id) else {
+                continue;
+            };
+            let is_collapsed = main_state.group_is_collapsed(compartment, group_id);
+            let header = GroupHeader {
+                group: group.clone(),
+                mapping_count: group_mappings.len(),
+                is_collapsed,
+            };
+            items.push(MappingRowItem::GroupHeader(header));
+            if !is_collapsed {
+                items.extend(
+                    group_mappings
+                        .into_iter()
+                        .map(|m| MappingRowItem::Mapping(m.clone())),
+                );
+            }
+        }
+        items
+    }
+
     /// Let mapping rows reflect the correct mappings.
     fn invalidate_mapping_rows(&self) {
         let mut row_index = 0;
@@ -340,31 +398,24 @@ impl MappingRowsPanel {
         let session = shared_session.borrow();
         let main_state = self.main_state.borrow();
         let compartment = main_state.active_compartment.get();
-        let filtered_mappings: Vec<_> =
-            Self::filtered_mappings(&session, &main_state, compartment, false).collect();
+        let items = Self::list_items(&session, &main_state, compartment);
+        let item_count = items.len();
         let scroll_pos = self.scroll_position.get();
-        if scroll_pos < filtered_mappings.len() {
-            for mapping in &filtered_mappings[scroll_pos..] {
-                if row_index >= self.rows.len() {
-                    break;
-                }
-                self.rows
-                    .get(row_index)
-                    .expect("impossible")
-                    .set_mapping(Some((*mapping).clone()));
-                row_index += 1;
+        for item in items.into_iter().skip(scroll_pos) {
+            if row_index >= self.rows.len() {
+                break;
             }
+            self.rows
+                .get(row_index)
+                .expect("impossible")
+                .set_item(Some(item));
+            row_index += 1;
         }
         // If there are unused rows, clear them
         for i in row_index..self.rows.len() {
-            self.rows.get(i).expect("impossible").set_mapping(None);
+            self.rows.get(i).expect("impossible").set_item(None);
         }
-        self.invalidate_empty_group_controls(
-            &session,
-            &main_state,
-            compartment,
-            filtered_mappings.len(),
-        );
+        self.invalidate_empty_group_controls(&session, &main_state, compartment, item_count);
     }
 
     fn mapping_matches_filter(
@@ -514,6 +565,14 @@ impl MappingRowsPanel {
                 view.invalidate_scroll_info();
             },
         );
+        // Don't scroll to the top when expanding/collapsing a group
+        self.when(
+            main_state.collapsed_groups_for_any_compartment_changed(),
+            |view, _| {
+                view.invalidate_mapping_rows();
+                view.invalidate_scroll_info();
+            },
+        );
     }
 
     fn fix_empty_mapping_list(&self) -> Result<(), &'static str> {
@@ -40,6 +40,9 @@ pub use simple_script_editor_panel::*;
 mod advanced_script_editor_panel;
 pub use advanced_script_editor_panel::*;
 
+mod egui_panel;
+pub use egui_panel::*;
+
 #[allow(dead_code)]
 mod control_transformation_templates;
 pub use control_transformation_templates::*;
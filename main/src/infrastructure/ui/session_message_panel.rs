@@ -4,11 +4,14 @@ use crate::application::{
 use crate::base::when;
 use crate::domain::Compartment;
 use crate::infrastructure::ui::bindings::root;
+use crate::infrastructure::ui::util::{memorize_window_bounds, restore_window_bounds};
 use reaper_low::raw;
 use rxrust::prelude::*;
 use std::rc::Rc;
 use swell_ui::{SharedView, View, ViewContext, Window};
 
+const WINDOW_NAME: &str = "message-panel";
+
 #[derive(Debug)]
 pub struct SessionMessagePanel {
     view: ViewContext,
@@ -48,6 +51,9 @@ impl SessionMessagePanel {
                                 VirtualControlElementType::Button => {
                                     "Press a button-like control element!"
                                 }
+                                VirtualControlElementType::Display => {
+                                    "Touch a control element that belongs to the display!"
+                                }
                             },
                             Compartment::Main => "Touch a control element!",
                         };
@@ -103,14 +109,18 @@ impl View for SessionMessagePanel {
         &self.view
     }
 
-    fn opened(self: SharedView<Self>, _window: Window) -> bool {
+    fn opened(self: SharedView<Self>, window: Window) -> bool {
+        restore_window_bounds(window, &self.session().borrow(), WINDOW_NAME);
         self.invalidate_controls();
         self.register_listeners();
         true
     }
 
-    fn closed(self: SharedView<Self>, _window: Window) {
+    fn closed(self: SharedView<Self>, window: Window) {
         if let Some(session) = self.session.upgrade() {
+            if let Ok(session) = session.try_borrow() {
+                memorize_window_bounds(window, &session, WINDOW_NAME);
+            }
             session.borrow_mut().stop_learning_many_mappings();
         }
     }
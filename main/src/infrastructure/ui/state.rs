@@ -8,6 +8,7 @@ use crate::application::{MappingModel, Session};
 use enum_map::{enum_map, EnumMap};
 use rxrust::prelude::*;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt;
 use std::rc::Rc;
 use wildmatch::WildMatch;
@@ -24,6 +25,8 @@ pub struct MainState {
     pub displayed_group: EnumMap<Compartment, Prop<Option<GroupFilter>>>,
     pub search_expression: Prop<SearchExpression>,
     pub scroll_status: Prop<ScrollStatus>,
+    /// Groups whose mappings are hidden in the mapping list when showing all groups.
+    pub collapsed_groups: EnumMap<Compartment, Prop<HashSet<GroupId>>>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
@@ -91,6 +94,7 @@ impl Default for MainState {
             },
             search_expression: Default::default(),
             scroll_status: Default::default(),
+            collapsed_groups: Default::default(),
         }
     }
 }
@@ -119,6 +123,36 @@ impl MainState {
         self.displayed_group[self.active_compartment.get()].set(filter);
     }
 
+    pub fn group_is_collapsed(&self, compartment: Compartment, group_id: GroupId) -> bool {
+        self.collapsed_groups[compartment]
+            .get_ref()
+            .contains(&group_id)
+    }
+
+    pub fn toggle_group_collapsed(&mut self, compartment: Compartment, group_id: GroupId) {
+        self.collapsed_groups[compartment].mut_in_place(|groups| {
+            if !groups.remove(&group_id) {
+                groups.insert(group_id);
+            }
+        });
+    }
+
+    pub fn expand_group(&mut self, compartment: Compartment, group_id: GroupId) {
+        if self.group_is_collapsed(compartment, group_id) {
+            self.collapsed_groups[compartment].mut_in_place(|groups| {
+                groups.remove(&group_id);
+            });
+        }
+    }
+
+    pub fn collapsed_groups_for_any_compartment_changed(
+        &self,
+    ) -> impl LocalObservable<'static, Item = (), Err = ()> + 'static {
+        self.collapsed_groups[Compartment::Controller]
+            .changed()
+            .merge(self.collapsed_groups[Compartment::Main].changed())
+    }
+
     pub fn clear_all_filters(&mut self) {
         self.clear_source_filter();
         self.clear_target_filter();
@@ -1,8 +1,9 @@
-use crate::application::Session;
+use crate::application::{Session, WindowBounds};
 use crate::domain::{compartment_param_index_iter, Compartment, Tag};
 use crate::infrastructure::ui::bindings::root;
 use realearn_dialogs::constants;
 use reaper_high::Reaper;
+use reaper_low::raw;
 use std::path::Path;
 use std::str::FromStr;
 use swell_ui::{DialogScaling, DialogUnits, Dimensions, Window};
@@ -206,6 +207,44 @@ pub fn open_in_browser(url: &str) {
     }
 }
 
+/// Moves and resizes the given top-level window to the bounds it had when the user left it the
+/// last time.
+///
+/// If those bounds are not visible anymore (e.g. because a monitor has been disconnected), the
+/// window is moved back onto the screen.
+pub fn restore_window_bounds(window: Window, session: &Session, window_name: &str) {
+    let bounds = match session.window_bounds(window_name) {
+        None => return,
+        Some(b) => b,
+    };
+    let mut rect = raw::RECT {
+        left: bounds.x,
+        top: bounds.y,
+        right: bounds.x + bounds.width,
+        bottom: bounds.y + bounds.height,
+    };
+    unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .low()
+            .EnsureNotCompletelyOffscreen(&mut rect);
+    }
+    window.set_screen_rect(rect);
+}
+
+/// Memorizes the current position and size of the given top-level window in the session, so they
+/// can be restored next time.
+pub fn memorize_window_bounds(window: Window, session: &Session, window_name: &str) {
+    let rect = window.screen_rect();
+    let bounds = WindowBounds {
+        x: rect.left,
+        y: rect.top,
+        width: rect.right - rect.left,
+        height: rect.bottom - rect.top,
+    };
+    session.memorize_window_bounds(window_name, bounds);
+}
+
 #[cfg(target_os = "windows")]
 const FILE_MANAGER_CMD: &str = "explorer";
 
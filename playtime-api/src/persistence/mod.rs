@@ -38,6 +38,9 @@ pub struct Matrix {
     pub clip_play_settings: MatrixClipPlaySettings,
     pub clip_record_settings: MatrixClipRecordSettings,
     pub common_tempo_range: TempoRange,
+    /// Volume of the built-in click. If not set, 0 dB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub click_volume: Option<Db>,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
@@ -553,6 +556,17 @@ pub struct ColumnClipPlaySettings {
     /// `None` means it uses the matrix-global stop timing.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_timing: Option<ClipPlayStopTiming>,
+    /// If `true`, starting a slot stops all other slots in this column, even if the column play
+    /// mode is non-exclusive.
+    ///
+    /// Exclusive play modes always behave like that.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub stop_others_on_play: bool,
+    /// Stop timing used for stopping the other slots in this column when a slot is started.
+    ///
+    /// `None` means it uses the usual column/matrix-global stop timing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_stop_timing: Option<ClipPlayStopTiming>,
     pub audio_settings: ColumnClipPlayAudioSettings,
 }
 
@@ -634,15 +648,18 @@ pub struct ColumnClipPlayAudioSettings {
 ///   column type.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Row {
-    // TODO-clip-implement
+    /// An optional name of this row, e.g. the name of the song part which the scene represents.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// An optional custom color of this row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<RgbColor>,
     /// An optional tempo associated with this row.
-    // TODO-clip-implement
+    // TODO-clip-implement Apply when launching the scene
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tempo: Option<Bpm>,
     /// An optional time signature associated with this row.
-    // TODO-clip-implement
+    // TODO-clip-implement Apply when launching the scene
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_signature: Option<TimeSignature>,
 }
@@ -886,6 +903,10 @@ pub struct ClipMidiSettings {
     pub interaction_reset_settings: MidiResetMessageRange,
 }
 
+fn is_default<T: Default + PartialEq>(v: &T) -> bool {
+    v == &T::default()
+}
+
 pub fn preferred_clip_midi_settings() -> ClipMidiSettings {
     let no_reset = MidiResetMessages::default();
     let light_reset = MidiResetMessages {
@@ -46,8 +46,8 @@ pub struct ColumnSettings {
 }
 
 #[derive(Clone, Debug)]
-struct PlayingPreviewRegister {
-    _preview_register: SharedRegister,
+pub(crate) struct PlayingPreviewRegister {
+    preview_register: SharedRegister,
     play_handle: NonNull<preview_register_t>,
     track: Option<Track>,
 }
@@ -121,6 +121,9 @@ impl Column {
         self.rt_settings.play_mode = api_column.clip_play_settings.mode.unwrap_or_default();
         self.rt_settings.clip_play_start_timing = api_column.clip_play_settings.start_timing;
         self.rt_settings.clip_play_stop_timing = api_column.clip_play_settings.stop_timing;
+        self.rt_settings.stop_others_on_play = api_column.clip_play_settings.stop_others_on_play;
+        self.rt_settings.exclusive_stop_timing =
+            api_column.clip_play_settings.exclusive_stop_timing;
         // Slots
         for api_slot in api_column.slots.unwrap_or_default() {
             let row = api_slot.row;
@@ -144,7 +147,11 @@ impl Column {
     }
 
     fn init_preview_register(&mut self, track: Option<Track>) {
-        self.preview_register = Some(PlayingPreviewRegister::new(self.rt_column.clone(), track));
+        self.preview_register = Some(PlayingPreviewRegister::new(
+            self.rt_column.clone(),
+            track,
+            ReaperVolumeValue::ZERO_DB,
+        ));
     }
 
     pub fn sync_settings_to_rt(&self, matrix_settings: &MatrixSettings) {
@@ -225,6 +232,8 @@ impl Column {
                 track: track_id,
                 start_timing: self.rt_settings.clip_play_start_timing,
                 stop_timing: self.rt_settings.clip_play_stop_timing,
+                stop_others_on_play: self.rt_settings.stop_others_on_play,
+                exclusive_stop_timing: self.rt_settings.exclusive_stop_timing,
                 audio_settings: ColumnClipPlayAudioSettings {
                     resample_mode: self.rt_settings.audio_resample_mode,
                     time_stretch_mode: self.rt_settings.audio_time_stretch_mode,
@@ -586,9 +595,13 @@ impl Drop for PlayingPreviewRegister {
 }
 
 impl PlayingPreviewRegister {
-    pub fn new(source: impl CustomPcmSource + 'static, track: Option<Track>) -> Self {
+    pub fn new(
+        source: impl CustomPcmSource + 'static,
+        track: Option<Track>,
+        volume: ReaperVolumeValue,
+    ) -> Self {
         let mut register = OwnedPreviewRegister::default();
-        register.set_volume(ReaperVolumeValue::ZERO_DB);
+        register.set_volume(volume);
         let (out_chan, preview_track) = if let Some(t) = track.as_ref() {
             (-1, Some(t.raw()))
         } else {
@@ -601,12 +614,20 @@ impl PlayingPreviewRegister {
         let preview_register = Arc::new(ReaperMutex::new(register));
         let play_handle = start_playing_preview(&preview_register, track.as_ref());
         Self {
-            _preview_register: preview_register,
+            preview_register,
             play_handle,
             track,
         }
     }
 
+    pub fn set_volume(&self, volume: ReaperVolumeValue) -> ClipEngineResult<()> {
+        self.preview_register
+            .lock()
+            .map_err(|_| "couldn't acquire preview register lock")?
+            .set_volume(volume);
+        Ok(())
+    }
+
     fn stop_playing_preview(&mut self) {
         if let Some(track) = &self.track {
             // Check prevents error message on project close.
@@ -1,6 +1,5 @@
 use crate::base::history::History;
-use crate::base::row::Row;
-use crate::base::{Clip, Column, Slot, SlotKit};
+use crate::base::{Clip, Column, PlayingPreviewRegister, Row, Slot, SlotKit};
 use crate::rt::supplier::{
     keep_processing_cache_requests, keep_processing_pre_buffer_requests,
     keep_processing_recorder_requests, AudioRecordingEquipment, ChainEquipment,
@@ -24,8 +23,8 @@ use playtime_api::persistence::{
     MatrixClipPlayAudioSettings, MatrixClipPlaySettings, MatrixClipRecordSettings, RecordLength,
     TempoRange,
 };
-use reaper_high::{OrCurrentProject, Project, Reaper, Track};
-use reaper_medium::{Bpm, MidiInputDeviceId};
+use reaper_high::{OrCurrentProject, Project, Reaper, Track, Volume};
+use reaper_medium::{Bpm, MidiInputDeviceId, ReaperVolumeValue};
 use std::thread::JoinHandle;
 use std::{cmp, thread};
 
@@ -43,6 +42,9 @@ pub struct Matrix<H> {
     command_receiver: Receiver<MatrixCommand>,
     rt_command_sender: Sender<rt::MatrixCommand>,
     history: History,
+    click_volume: Db,
+    /// Only set while the click is enabled.
+    click_preview_register: Option<PlayingPreviewRegister>,
     // We use this just for RAII (joining worker threads when dropped)
     _worker_pool: WorkerPool,
 }
@@ -123,6 +125,7 @@ impl<H> Matrix<H> {
             },
             clip_record_settings: self.settings.clip_record_settings,
             common_tempo_range: self.settings.common_tempo_range,
+            click_volume: Some(self.click_volume),
         }
     }
 
@@ -188,6 +191,8 @@ impl<H: ClipMatrixHandler> Matrix<H> {
             command_receiver: main_command_receiver,
             rt_command_sender,
             history: History::default(),
+            click_volume: Db::ZERO,
+            click_preview_register: None,
             _worker_pool: worker_pool,
         }
     }
@@ -227,6 +232,11 @@ impl<H: ClipMatrixHandler> Matrix<H> {
         self.settings.overridable.clip_play_start_timing =
             api_matrix.clip_play_settings.start_timing;
         self.settings.overridable.clip_play_stop_timing = api_matrix.clip_play_settings.stop_timing;
+        // Click
+        self.click_volume = api_matrix.click_volume.unwrap_or(Db::ZERO);
+        if let Some(r) = &self.click_preview_register {
+            r.set_volume(convert_db_to_reaper_volume(self.click_volume))?;
+        }
         // Columns
         for (i, api_column) in api_matrix
             .columns
@@ -249,7 +259,7 @@ impl<H: ClipMatrixHandler> Matrix<H> {
             .rows
             .unwrap_or_default()
             .into_iter()
-            .map(|_| Row {})
+            .map(Row::load)
             .collect();
         // Emit event
         self.notify_everything_changed();
@@ -262,6 +272,68 @@ impl<H: ClipMatrixHandler> Matrix<H> {
         self.rt_command_sender.clear_columns();
     }
 
+    /// Inserts a new empty column at the given index.
+    ///
+    /// All columns at or after that index move one position to the right.
+    pub fn insert_column(&mut self, index: usize) -> ClipEngineResult<()> {
+        check_column_insertion(index, self.columns.len())?;
+        let mut column = Column::new(self.permanent_project());
+        let api_column = api::Column {
+            clip_play_settings: Default::default(),
+            clip_record_settings: Default::default(),
+            slots: None,
+        };
+        column.load(
+            api_column,
+            &self.chain_equipment,
+            &self.recorder_request_sender,
+            &self.settings,
+        )?;
+        column.sync_settings_to_rt(&self.settings);
+        // Everything which can fail is done at this point, so we don't record an undo point for
+        // a failed insertion.
+        self.undoable("Insert column", |matrix| {
+            initialize_new_column(
+                index,
+                column,
+                &matrix.rt_command_sender,
+                &mut matrix.columns,
+            );
+            // Column indexes have shifted, so slot and clip addresses are not valid anymore.
+            matrix.notify_everything_changed();
+        });
+        Ok(())
+    }
+
+    /// Removes the column at the given index, including all of its clips.
+    ///
+    /// All columns after that index move one position to the left.
+    pub fn remove_column(&mut self, index: usize) -> ClipEngineResult<()> {
+        check_column_removal(index, self.columns.len())?;
+        self.undoable("Remove column", |matrix| {
+            matrix.columns.remove(index);
+            matrix.rt_command_sender.remove_column(index);
+            matrix.notify_everything_changed();
+        });
+        Ok(())
+    }
+
+    /// Moves the column at the given index to the given destination index.
+    pub fn move_column(&mut self, source_index: usize, dest_index: usize) -> ClipEngineResult<()> {
+        if !check_column_move(source_index, dest_index, self.columns.len())? {
+            return Ok(());
+        }
+        self.undoable("Move column", |matrix| {
+            let column = matrix.columns.remove(source_index);
+            matrix.columns.insert(dest_index, column);
+            matrix
+                .rt_command_sender
+                .move_column(source_index, dest_index);
+            matrix.notify_everything_changed();
+        });
+        Ok(())
+    }
+
     pub fn next_undo_label(&self) -> Option<&str> {
         self.history.next_undo_label()
     }
@@ -563,6 +635,22 @@ impl<H: ClipMatrixHandler> Matrix<H> {
         }
     }
 
+    /// Finds the row (scene) at the given index.
+    ///
+    /// Returns `None` if the row doesn't have any properties yet, even if it contains slots.
+    pub fn find_row(&self, index: usize) -> Option<&Row> {
+        self.rows.get(index)
+    }
+
+    /// Replaces the properties (name, color, ...) of the given row (scene).
+    pub fn set_row_data(&mut self, index: usize, api_row: api::Row) {
+        self.undoable("Change scene", |matrix| {
+            let row = Row::load(api_row);
+            put_row(&mut matrix.rows, index, row.clone());
+            matrix.emit(ClipMatrixEvent::row_changed(index, row));
+        });
+    }
+
     /// Returns the settings of this matrix.
     pub fn settings(&self) -> &MatrixSettings {
         &self.settings
@@ -574,6 +662,50 @@ impl<H: ClipMatrixHandler> Matrix<H> {
         self.emit(ClipMatrixEvent::RecordDurationChanged);
     }
 
+    /// Returns whether the built-in click is enabled.
+    pub fn click_is_enabled(&self) -> bool {
+        self.click_preview_register.is_some()
+    }
+
+    /// Enables or disables the built-in click.
+    ///
+    /// The click follows the clip timeline and is played on the track which contains the matrix,
+    /// independently from REAPER's own metronome. It's not available if the matrix is
+    /// project-less (monitoring FX chain).
+    pub fn set_click_enabled(&mut self, enabled: bool) -> ClipEngineResult<()> {
+        if enabled == self.click_is_enabled() {
+            return Ok(());
+        }
+        self.click_preview_register = if enabled {
+            let track = self
+                .containing_track
+                .clone()
+                .ok_or("click not available without containing track")?;
+            let source = rt::Click::new(self.permanent_project());
+            let volume = convert_db_to_reaper_volume(self.click_volume);
+            Some(PlayingPreviewRegister::new(source, Some(track), volume))
+        } else {
+            None
+        };
+        self.emit(ClipMatrixEvent::ClickChanged);
+        Ok(())
+    }
+
+    /// Returns the volume of the built-in click.
+    pub fn click_volume(&self) -> Db {
+        self.click_volume
+    }
+
+    /// Sets the volume of the built-in click.
+    pub fn set_click_volume(&mut self, volume: Db) -> ClipEngineResult<()> {
+        if let Some(r) = &self.click_preview_register {
+            r.set_volume(convert_db_to_reaper_volume(volume))?;
+        }
+        self.click_volume = volume;
+        self.emit(ClipMatrixEvent::ClickChanged);
+        Ok(())
+    }
+
     /// Builds a scene of all currently playing clips, in the first empty row.
     pub fn build_scene_in_first_empty_row(&mut self) -> ClipEngineResult<()> {
         let empty_row_index = (0usize..)
@@ -908,6 +1040,10 @@ impl<H: ClipMatrixHandler> Matrix<H> {
     }
 }
 
+fn convert_db_to_reaper_volume(db: Db) -> ReaperVolumeValue {
+    Volume::from_db(reaper_medium::Db::new(db.get())).reaper_value()
+}
+
 fn get_column(columns: &[Column], index: usize) -> ClipEngineResult<&Column> {
     columns.get(index).ok_or(NO_SUCH_COLUMN)
 }
@@ -1055,12 +1191,25 @@ pub trait ClipMatrixHandler: Sized {
 pub enum ClipMatrixEvent {
     EverythingChanged,
     RecordDurationChanged,
+    ClickChanged,
     HistoryChanged,
+    RowChanged(QualifiedRowChangeEvent),
     SlotChanged(QualifiedSlotChangeEvent),
     ClipChanged(QualifiedClipChangeEvent),
 }
 
+#[derive(Debug)]
+pub struct QualifiedRowChangeEvent {
+    pub row_index: usize,
+    /// The complete row data after the change.
+    pub row: Row,
+}
+
 impl ClipMatrixEvent {
+    pub fn row_changed(row_index: usize, row: Row) -> Self {
+        Self::RowChanged(QualifiedRowChangeEvent { row_index, row })
+    }
+
     pub fn slot_changed(slot_address: ClipSlotAddress, event: SlotChangeEvent) -> Self {
         Self::SlotChanged(QualifiedSlotChangeEvent {
             slot_address,
@@ -1145,6 +1294,43 @@ pub type SlotWithColumn<'a> = WithColumn<&'a Slot>;
 
 pub type ApiClipWithColumn = WithColumn<api::Clip>;
 
+/// Puts the given row at the given index, creating all rows before it if they don't exist yet.
+fn put_row(rows: &mut Vec<Row>, index: usize, row: Row) {
+    if index >= rows.len() {
+        rows.resize_with(index + 1, Default::default);
+    }
+    rows[index] = row;
+}
+
+/// Returns an error if a column can't be inserted at the given index.
+fn check_column_insertion(index: usize, column_count: usize) -> ClipEngineResult<()> {
+    if index > column_count {
+        return Err("column index out of bounds");
+    }
+    Ok(())
+}
+
+/// Returns an error if there's no column at the given index.
+fn check_column_removal(index: usize, column_count: usize) -> ClipEngineResult<()> {
+    if index >= column_count {
+        return Err(NO_SUCH_COLUMN);
+    }
+    Ok(())
+}
+
+/// Returns whether moving a column from the source to the destination index changes anything or
+/// an error if one of the indexes doesn't point to an existing column.
+fn check_column_move(
+    source_index: usize,
+    dest_index: usize,
+    column_count: usize,
+) -> ClipEngineResult<bool> {
+    if source_index >= column_count || dest_index >= column_count {
+        return Err(NO_SUCH_COLUMN);
+    }
+    Ok(source_index != dest_index)
+}
+
 fn initialize_new_column(
     column_index: usize,
     column: Column,
@@ -1156,5 +1342,62 @@ fn initialize_new_column(
         command_sender: column.rt_command_sender().clone(),
     };
     rt_command_sender.insert_column(column_index, handle);
-    columns.push(column);
+    columns.insert(column_index, column);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_insertion() {
+        // Given
+        let column_count = 3;
+        // When/Then
+        assert!(check_column_insertion(0, column_count).is_ok());
+        assert!(check_column_insertion(3, column_count).is_ok());
+        assert!(check_column_insertion(4, column_count).is_err());
+    }
+
+    #[test]
+    fn column_removal() {
+        // Given
+        let column_count = 3;
+        // When/Then
+        assert!(check_column_removal(2, column_count).is_ok());
+        assert!(check_column_removal(3, column_count).is_err());
+        assert!(check_column_removal(0, 0).is_err());
+    }
+
+    #[test]
+    fn column_move() {
+        // Given
+        let column_count = 3;
+        // When/Then
+        assert_eq!(check_column_move(0, 2, column_count), Ok(true));
+        assert_eq!(check_column_move(1, 1, column_count), Ok(false));
+        assert!(check_column_move(3, 0, column_count).is_err());
+        assert!(check_column_move(0, 3, column_count).is_err());
+    }
+
+    #[test]
+    fn row_data() {
+        // Given
+        let mut rows = vec![];
+        let api_row = api::Row {
+            name: Some("Chorus".to_string()),
+            color: None,
+            tempo: None,
+            time_signature: None,
+        };
+        // When
+        put_row(&mut rows, 2, Row::load(api_row.clone()));
+        put_row(&mut rows, 0, Row::default());
+        // Then
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], Row::default());
+        assert_eq!(rows[1], Row::default());
+        assert_eq!(rows[2].name(), Some("Chorus"));
+        assert_eq!(rows[2].save(), api_row);
+    }
 }
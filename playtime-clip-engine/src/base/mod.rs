@@ -9,4 +9,5 @@ pub use clip::*;
 pub use column::*;
 pub use history::*;
 pub use matrix::*;
+pub use row::*;
 pub use slot::*;
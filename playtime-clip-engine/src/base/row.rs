@@ -1,14 +1,51 @@
 use playtime_api::persistence as api;
+use playtime_api::persistence::{Bpm, RgbColor, TimeSignature};
 
-#[derive(Clone, Debug)]
-pub struct Row {}
+/// A row (scene) in the clip matrix.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Row {
+    name: Option<String>,
+    color: Option<RgbColor>,
+    tempo: Option<Bpm>,
+    time_signature: Option<TimeSignature>,
+}
 
 impl Row {
+    pub fn load(api_row: api::Row) -> Self {
+        Self {
+            name: api_row.name.filter(|n| !n.is_empty()),
+            color: api_row.color,
+            tempo: api_row.tempo,
+            time_signature: api_row.time_signature,
+        }
+    }
+
     pub fn save(&self) -> api::Row {
         api::Row {
-            name: None,
-            tempo: None,
-            time_signature: None,
+            name: self.name.clone(),
+            color: self.color,
+            tempo: self.tempo,
+            time_signature: self.time_signature,
         }
     }
+
+    /// Returns the name of this row (scene), if one was given.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the custom color of this row (scene), if one was given.
+    pub fn color(&self) -> Option<RgbColor> {
+        self.color
+    }
+
+    /// Returns the tempo which should be applied when launching this scene, if any.
+    pub fn tempo(&self) -> Option<Bpm> {
+        self.tempo
+    }
+
+    /// Returns the time signature which should be applied when launching this scene, if any.
+    pub fn time_signature(&self) -> Option<TimeSignature> {
+        self.time_signature
+    }
 }
@@ -107,6 +107,15 @@ pub struct TriggerRowRequest {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetRowDataRequest {
+    #[prost(message, optional, tag = "1")]
+    pub row_address: ::core::option::Option<FullRowAddress>,
+    /// Row data as JSON
+    #[prost(string, tag = "2")]
+    pub data: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TriggerSlotRequest {
     #[prost(message, optional, tag = "1")]
     pub slot_address: ::core::option::Option<FullSlotAddress>,
@@ -785,6 +794,10 @@ pub mod clip_engine_server {
             &self,
             request: tonic::Request<super::TriggerRowRequest>,
         ) -> Result<tonic::Response<super::Empty>, tonic::Status>;
+        async fn set_row_data(
+            &self,
+            request: tonic::Request<super::SetRowDataRequest>,
+        ) -> Result<tonic::Response<super::Empty>, tonic::Status>;
         /// Slot commands
         async fn trigger_slot(
             &self,
@@ -1145,6 +1158,37 @@ pub mod clip_engine_server {
                     };
                     Box::pin(fut)
                 }
+                "/playtime.clip_engine.ClipEngine/SetRowData" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetRowDataSvc<T: ClipEngine>(pub Arc<T>);
+                    impl<T: ClipEngine> tonic::server::UnaryService<super::SetRowDataRequest> for SetRowDataSvc<T> {
+                        type Response = super::Empty;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetRowDataRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).set_row_data(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetRowDataSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/playtime.clip_engine.ClipEngine/TriggerSlot" => {
                     #[allow(non_camel_case_types)]
                     struct TriggerSlotSvc<T: ClipEngine>(pub Arc<T>);
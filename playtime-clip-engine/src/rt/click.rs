@@ -0,0 +1,242 @@
+use crate::conversion_util::convert_duration_in_frames_to_seconds;
+use crate::rt::{AudioBufMut, BasicAudioRequestProps};
+use crate::timeline::{clip_timeline, Timeline};
+use assert_no_alloc::assert_no_alloc;
+use reaper_high::Project;
+use reaper_medium::{
+    reaper_str, CustomPcmSource, DurationInBeats, DurationInSeconds, ExtendedArgs, GetPeakInfoArgs,
+    GetSamplesArgs, Hz, LoadStateArgs, OwnedPcmSource, PcmSource, PeaksClearArgs,
+    PositionInSeconds, PropertiesWindowArgs, ReaperStr, SaveStateArgs, SetAvailableArgs,
+    SetFileNameArgs, SetSourceArgs,
+};
+use std::error::Error;
+use std::f64::consts::TAU;
+
+/// Duration of one click sound.
+const CLICK_DURATION_IN_SECS: f64 = 0.03;
+/// Frequency of the click on the first beat of a measure.
+const ACCENTED_CLICK_FREQUENCY: f64 = 1760.0;
+/// Frequency of the click on all other beats.
+const NORMAL_CLICK_FREQUENCY: f64 = 880.0;
+/// Peak amplitude of the click (before applying the preview register volume).
+const CLICK_AMPLITUDE: f64 = 0.5;
+
+/// A metronome which follows the beats of the clip timeline.
+///
+/// Meant to be played by a preview register, independently from REAPER's own metronome. The
+/// volume is controlled via the preview register, so this source just generates the sound.
+#[derive(Debug)]
+pub struct Click {
+    /// Should be set to the project of the ReaLearn instance or `None` if on monitoring FX.
+    project: Option<Project>,
+    /// The click that is currently sounding, if any.
+    current_sound: Option<ClickSound>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ClickSound {
+    frequency: f64,
+    /// Number of frames that have been rendered already.
+    rendered_frame_count: usize,
+}
+
+impl Click {
+    pub fn new(project: Option<Project>) -> Self {
+        Self {
+            project,
+            current_sound: None,
+        }
+    }
+
+    fn write_samples(&mut self, args: GetSamplesArgs) {
+        assert_no_alloc(|| {
+            let request_props = BasicAudioRequestProps::from_transfer(args.block);
+            unsafe {
+                args.block.set_samples_out(args.block.length());
+            }
+            let mut output_buffer = unsafe {
+                AudioBufMut::from_raw(
+                    args.block.samples(),
+                    args.block.nch() as usize,
+                    request_props.block_length,
+                )
+            };
+            output_buffer.clear();
+            let timeline = clip_timeline(self.project, false);
+            if !timeline.is_running() {
+                self.current_sound = None;
+                return;
+            }
+            // Find out whether a beat starts within this block
+            let block_start_pos = timeline.cursor_pos();
+            let block_duration = convert_duration_in_frames_to_seconds(
+                request_props.block_length,
+                request_props.frame_rate,
+            );
+            let block_end_pos =
+                PositionInSeconds::new(block_start_pos.get() + block_duration.get());
+            let start_beat = timeline.full_beats_at_pos(block_start_pos).get();
+            let end_beat = timeline.full_beats_at_pos(block_end_pos).get();
+            let next_beat = start_beat.ceil();
+            // A beat exactly at the block end is handled in the next block.
+            let beat_start_frame = if next_beat < end_beat {
+                let fraction = (next_beat - start_beat) / (end_beat - start_beat);
+                let frame = (fraction * request_props.block_length as f64) as usize;
+                let beats_per_measure =
+                    timeline.time_signature_at(block_start_pos).numerator.get() as i64;
+                let is_downbeat = (next_beat as i64).rem_euclid(beats_per_measure) == 0;
+                let frequency = if is_downbeat {
+                    ACCENTED_CLICK_FREQUENCY
+                } else {
+                    NORMAL_CLICK_FREQUENCY
+                };
+                Some((frame, frequency))
+            } else {
+                None
+            };
+            // Render
+            let click_frame_count =
+                (CLICK_DURATION_IN_SECS * request_props.frame_rate.get()) as usize;
+            let frame_rate = request_props.frame_rate.get();
+            let mut current_sound = self.current_sound;
+            let mut frame_value = 0.0;
+            output_buffer.modify_frames(|sample| {
+                // All channels get the same value, so we calculate it once per frame.
+                if sample.index.channel == 0 {
+                    if let Some((frame, frequency)) = beat_start_frame {
+                        if sample.index.frame == frame {
+                            current_sound = Some(ClickSound {
+                                frequency,
+                                rendered_frame_count: 0,
+                            });
+                        }
+                    }
+                    frame_value = match current_sound.as_mut() {
+                        None => 0.0,
+                        Some(sound) => {
+                            let value = sound.sample_value(click_frame_count, frame_rate);
+                            sound.rendered_frame_count += 1;
+                            if sound.rendered_frame_count >= click_frame_count {
+                                current_sound = None;
+                            }
+                            value
+                        }
+                    };
+                }
+                frame_value
+            });
+            self.current_sound = current_sound;
+        });
+    }
+}
+
+impl ClickSound {
+    /// Returns the sample value at the current position of the click.
+    ///
+    /// The sound is a sine wave which decays linearly.
+    fn sample_value(&self, click_frame_count: usize, frame_rate: f64) -> f64 {
+        let pos = self.rendered_frame_count as f64;
+        let envelope = 1.0 - pos / click_frame_count as f64;
+        (TAU * self.frequency * pos / frame_rate).sin() * envelope * CLICK_AMPLITUDE
+    }
+}
+
+impl CustomPcmSource for Click {
+    fn duplicate(&mut self) -> Option<OwnedPcmSource> {
+        unimplemented!()
+    }
+
+    fn is_available(&mut self) -> bool {
+        unimplemented!()
+    }
+
+    fn set_available(&mut self, _: SetAvailableArgs) {
+        unimplemented!()
+    }
+
+    fn get_type(&mut self) -> &ReaperStr {
+        // This is not relevant for usage in preview registers, but it will be called.
+        reaper_str!("WAVE")
+    }
+
+    fn get_file_name(&mut self) -> Option<&ReaperStr> {
+        unimplemented!()
+    }
+
+    fn set_file_name(&mut self, _: SetFileNameArgs) -> bool {
+        unimplemented!()
+    }
+
+    fn get_source(&mut self) -> Option<PcmSource> {
+        unimplemented!()
+    }
+
+    fn set_source(&mut self, _: SetSourceArgs) {
+        unimplemented!()
+    }
+
+    fn get_num_channels(&mut self) -> Option<u32> {
+        // This will only be called if the preview register is played without track.
+        unimplemented!("track-less click not supported")
+    }
+
+    fn get_sample_rate(&mut self) -> Option<Hz> {
+        unimplemented!()
+    }
+
+    fn get_length(&mut self) -> DurationInSeconds {
+        DurationInSeconds::MAX
+    }
+
+    fn get_length_beats(&mut self) -> Option<DurationInBeats> {
+        unimplemented!()
+    }
+
+    fn get_bits_per_sample(&mut self) -> u32 {
+        unimplemented!()
+    }
+
+    fn get_preferred_position(&mut self) -> Option<PositionInSeconds> {
+        unimplemented!()
+    }
+
+    fn properties_window(&mut self, _: PropertiesWindowArgs) -> i32 {
+        unimplemented!()
+    }
+
+    fn get_samples(&mut self, args: GetSamplesArgs) {
+        self.write_samples(args)
+    }
+
+    fn get_peak_info(&mut self, _: GetPeakInfoArgs) {
+        unimplemented!()
+    }
+
+    fn save_state(&mut self, _: SaveStateArgs) {
+        unimplemented!()
+    }
+
+    fn load_state(&mut self, _: LoadStateArgs) -> Result<(), Box<dyn Error>> {
+        unimplemented!()
+    }
+
+    fn peaks_clear(&mut self, _: PeaksClearArgs) {
+        unimplemented!()
+    }
+
+    fn peaks_build_begin(&mut self) -> bool {
+        unimplemented!()
+    }
+
+    fn peaks_build_run(&mut self) -> bool {
+        unimplemented!()
+    }
+
+    fn peaks_build_finish(&mut self) {
+        unimplemented!()
+    }
+
+    unsafe fn extended(&mut self, _: ExtendedArgs) -> i32 {
+        0
+    }
+}
@@ -295,6 +295,15 @@ pub struct ColumnSettings {
     pub audio_resample_mode: Option<VirtualResampleMode>,
     pub audio_cache_behavior: Option<AudioCacheBehavior>,
     pub play_mode: ColumnPlayMode,
+    pub stop_others_on_play: bool,
+    pub exclusive_stop_timing: Option<ClipPlayStopTiming>,
+}
+
+impl ColumnSettings {
+    /// Returns whether starting a slot should stop all other slots in this column.
+    pub fn stops_others_on_play(&self) -> bool {
+        self.play_mode.is_exclusive() || self.stop_others_on_play
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -369,17 +378,18 @@ impl Column {
         let slot = get_slot_mut_insert(&mut self.slots, args.slot_index);
         if slot.is_filled() {
             slot.play(slot_args)?;
-            if self.settings.play_mode.is_exclusive() {
+            if self.settings.stops_others_on_play() {
                 self.stop_all_clips(
                     audio_request_props,
                     ref_pos,
                     &args.timeline,
                     Some(args.slot_index),
+                    self.settings.exclusive_stop_timing,
                 );
             }
             Ok(())
         } else if args.options.stop_column_if_slot_empty {
-            self.stop_all_clips(audio_request_props, ref_pos, &args.timeline, None);
+            self.stop_all_clips(audio_request_props, ref_pos, &args.timeline, None, None);
             Ok(())
         } else {
             Err("slot is empty")
@@ -397,7 +407,7 @@ impl Column {
         if !self.settings.play_mode.follows_scene() {
             return Ok(());
         }
-        if !self.settings.play_mode.is_exclusive() {
+        if !self.settings.stops_others_on_play() {
             // When in column play mode "NonExclusiveFollowingScene", playing the clip itself
             // doesn't take care of stopping the other clips. But when playing scenes, we want
             // other clips to stop (otherwise they would accumulate). Do it manually.
@@ -406,6 +416,7 @@ impl Column {
                 args.ref_pos,
                 &args.timeline,
                 Some(args.slot_index),
+                None,
             );
         }
         let play_args = ColumnPlaySlotArgs {
@@ -422,7 +433,7 @@ impl Column {
 
     pub fn stop(&mut self, args: ColumnStopArgs, audio_request_props: BasicAudioRequestProps) {
         let ref_pos = args.ref_pos.unwrap_or_else(|| args.timeline.cursor_pos());
-        self.stop_all_clips(audio_request_props, ref_pos, &args.timeline, None);
+        self.stop_all_clips(audio_request_props, ref_pos, &args.timeline, None, None);
     }
 
     fn stop_all_clips(
@@ -431,6 +442,7 @@ impl Column {
         ref_pos: PositionInSeconds,
         timeline: &HybridTimeline,
         except: Option<usize>,
+        stop_timing: Option<ClipPlayStopTiming>,
     ) {
         for (i, slot) in self
             .slots
@@ -439,7 +451,7 @@ impl Column {
             .filter(|(i, _)| except.map(|e| e != *i).unwrap_or(true))
         {
             let stop_args = SlotStopArgs {
-                stop_timing: None,
+                stop_timing,
                 timeline,
                 ref_pos: Some(ref_pos),
                 enforce_play_stop: true,
@@ -516,10 +528,16 @@ impl Column {
         let result = slot.record_clip(instruction, &self.matrix_settings, &self.settings);
         let (informative_result, ack_result) = match result {
             Ok(slot_runtime_data) => {
-                if self.settings.play_mode.is_exclusive() {
+                if self.settings.stops_others_on_play() {
                     let timeline = clip_timeline(self.project, false);
                     let ref_pos = timeline.cursor_pos();
-                    self.stop_all_clips(audio_request_props, ref_pos, &timeline, Some(slot_index));
+                    self.stop_all_clips(
+                        audio_request_props,
+                        ref_pos,
+                        &timeline,
+                        Some(slot_index),
+                        self.settings.exclusive_stop_timing,
+                    );
                 }
                 (Ok(()), Ok(slot_runtime_data))
             }
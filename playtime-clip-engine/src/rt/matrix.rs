@@ -119,6 +119,14 @@ impl Matrix {
                     let handle = self.column_handles.remove(index);
                     self.main_command_sender.throw_away(handle);
                 }
+                MoveColumn {
+                    source_index,
+                    dest_index,
+                } => {
+                    // Doesn't allocate because the vector only shrinks and grows by one.
+                    let handle = self.column_handles.remove(source_index);
+                    self.column_handles.insert(dest_index, handle);
+                }
                 ClearColumns => {
                     for handles in self.column_handles.drain(..) {
                         self.main_command_sender.throw_away(handles);
@@ -288,12 +296,17 @@ impl Matrix {
 pub enum MatrixCommand {
     InsertColumn(usize, ColumnHandle),
     RemoveColumn(usize),
+    MoveColumn {
+        source_index: usize,
+        dest_index: usize,
+    },
     ClearColumns,
 }
 
 pub trait RtMatrixCommandSender {
     fn insert_column(&self, index: usize, handle: ColumnHandle);
     fn remove_column(&self, index: usize);
+    fn move_column(&self, source_index: usize, dest_index: usize);
     fn clear_columns(&self);
     fn send_command(&self, command: MatrixCommand);
 }
@@ -307,6 +320,13 @@ impl RtMatrixCommandSender for Sender<MatrixCommand> {
         self.send_command(MatrixCommand::RemoveColumn(index));
     }
 
+    fn move_column(&self, source_index: usize, dest_index: usize) {
+        self.send_command(MatrixCommand::MoveColumn {
+            source_index,
+            dest_index,
+        });
+    }
+
     fn clear_columns(&self) {
         self.send_command(MatrixCommand::ClearColumns);
     }
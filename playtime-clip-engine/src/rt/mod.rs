@@ -1,4 +1,5 @@
 mod buffer;
+mod click;
 mod clip;
 mod column;
 mod matrix;
@@ -10,6 +11,7 @@ pub mod tempo_util;
 pub mod supplier;
 
 pub use buffer::*;
+pub use click::*;
 pub use clip::*;
 pub use column::*;
 pub use matrix::*;
@@ -579,6 +579,31 @@ impl Window {
         }
     }
 
+    /// Returns the bounds of this window in screen coordinates (pixels).
+    pub fn screen_rect(self) -> RECT {
+        let mut rect = RECT::default();
+        unsafe { Swell::get().GetWindowRect(self.raw, &mut rect) };
+        rect
+    }
+
+    /// Moves and resizes this window to the given bounds in screen coordinates (pixels).
+    ///
+    /// In contrast to [`Self::move_to`], the coordinates can be negative, which is important on
+    /// multi-monitor setups.
+    pub fn set_screen_rect(self, rect: RECT) {
+        unsafe {
+            Swell::get().SetWindowPos(
+                self.raw,
+                null_mut(),
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                raw::SWP_NOZORDER as _,
+            );
+        }
+    }
+
     pub fn taborder_first(self) {
         /// zorder is used to set taborder,
         /// note HWND_BOTTOM should be drawn as the first (to be the last in zorder),